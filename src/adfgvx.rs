@@ -3,47 +3,62 @@
 //! ADFGVX was an extension of an earlier cipher called ADFGX. It uses a polybius square and a
 //! columnar transposition cipher.
 //!
-use crate::columnar_transposition::ColumnarTransposition;
+use crate::columnar_transposition::{ColumnarTransposition, ColumnarTranspositionKey};
 use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
 use crate::common::{alphabet, keygen};
-use crate::Polybius;
+use crate::polybius::{Polybius, PolybiusKey};
 use std::string::String;
 
 const ADFGVX_CHARS: [char; 6] = ['A', 'D', 'F', 'G', 'V', 'X'];
 
 /// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
 pub struct ADFGVX {
     polybius_cipher: Polybius,
     columnar_cipher: ColumnarTransposition,
 }
 
+/// The key of an ADFGVX cipher.
+///
+/// This struct is given to the `new()` method of `ADFGVX`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ADFGVXKey {
+    /// The phrase used to key the polybius square. It can contain the characters `a-z 0-9`.
+    pub polybius_key: String,
+    /// The key of the columnar transposition. See the `ColumnarTransposition` documentation
+    /// for more.
+    pub columnar_key: String,
+    /// An optional character that is used to pad uneven messages during the columnar
+    /// transposition.
+    pub null_char: Option<char>,
+}
+
 impl Cipher for ADFGVX {
-    type Key = (String, String, Option<char>);
+    type Key = ADFGVXKey;
     type Algorithm = ADFGVX;
 
     /// Initialise a ADFGVX cipher.
     ///
-    /// The `key` tuple maps to the following `(String, String, Option<char>) = (polybius_key,
-    /// columnar_key, null_char)`. Where ...
-    ///
-    /// * The `polybius_key` is used to init a polybius cipher. See it's documentation for more
-    /// information.
-    /// * The `columnar_key` is used to init a columnar transposition cipher. See it's
-    /// documentation for more information.
-    /// * The `null_char` is an optional character that will be used to pad uneven messages
-    /// during the columnar transposition stage. See the `columnar_transposition` documentation
-    /// for more information.
+    /// The parts of the `key` are described by the fields of `ADFGVXKey`.
     ///
     /// # Panics
     /// * If a non-alphanumeric symbol is part of the key.
     ///
-    fn new(key: (String, String, Option<char>)) -> ADFGVX {
+    fn new(key: ADFGVXKey) -> ADFGVX {
         // Generate the keyed alphabet key for the polybius square
-        let p_key = keygen::keyed_alphabet(&key.0, &alphabet::ALPHANUMERIC, false);
+        let p_key = keygen::keyed_alphabet(&key.polybius_key, &alphabet::ALPHANUMERIC, false);
 
         ADFGVX {
-            polybius_cipher: Polybius::new((p_key, ADFGVX_CHARS, ADFGVX_CHARS)),
-            columnar_cipher: ColumnarTransposition::new((key.1, key.2)),
+            polybius_cipher: Polybius::new(PolybiusKey {
+                phrase: p_key,
+                column_ids: ADFGVX_CHARS,
+                row_ids: ADFGVX_CHARS,
+            }),
+            columnar_cipher: ColumnarTransposition::new(ColumnarTranspositionKey {
+                keystream: key.columnar_key,
+                null_char: key.null_char,
+            }),
         }
     }
 
@@ -53,17 +68,17 @@ impl Cipher for ADFGVX {
     /// Basic usage:
     ///
     /// ```
-    /// use cipher_crypt::{Cipher, ADFGVX};
+    /// use cipher_crypt::{Cipher, ADFGVX, ADFGVXKey};
     ///
     /// let polybius_key = String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8");
     /// let columnar_key = String::from("GERMAN");
     /// let null_char = None;
     ///
-    /// let a = ADFGVX::new((
+    /// let a = ADFGVX::new(ADFGVXKey {
     ///     polybius_key,
     ///     columnar_key,
     ///     null_char
-    /// ));
+    /// });
     ///
     /// let cipher_text = concat!(
     ///     "gfxffgxgDFAXDAVGDgxvadaaxxXFDDFGGGFdfaxdavgdVDAGFAXVVxfdd",
@@ -90,17 +105,17 @@ impl Cipher for ADFGVX {
     /// Basic usage:
     ///
     /// ```
-    /// use cipher_crypt::{Cipher, ADFGVX};
+    /// use cipher_crypt::{Cipher, ADFGVX, ADFGVXKey};
     ///
     /// let polybius_key = String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8");
     /// let columnar_key = String::from("GERMAN");
     /// let null_char = None;
     ///
-    /// let a = ADFGVX::new((
+    /// let a = ADFGVX::new(ADFGVXKey {
     ///     polybius_key,
     ///     columnar_key,
     ///     null_char
-    /// ));
+    /// });
     ///
     /// let cipher_text = concat!(
     ///     "gfxffgxgDFAXDAVGD gxvadaaxxXFDDFGGGFdfaxdav",
@@ -119,6 +134,119 @@ impl Cipher for ADFGVX {
         //Step 2: decrypt using polybius
         self.polybius_cipher.decrypt(&step_one)
     }
+
+    /// Check that a message can be encrypted using a ADFGVX cipher.
+    ///
+    /// As the columnar transposition stage is applied to the polybius encoded message, this will
+    /// return `Err` if that encoding contains the `null_char`.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, ADFGVX, ADFGVXKey};
+    ///
+    /// let a = ADFGVX::new(ADFGVXKey {
+    ///     polybius_key: String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
+    ///     columnar_key: String::from("GERMAN"),
+    ///     null_char: Some(' ')
+    /// });
+    ///
+    /// assert!(a.validate_message("Attack!").is_ok());
+    /// assert!(a.validate_message("Attack at dawn!").is_err());
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        // An offset into the Polybius square output would not point at a symbol of the message
+        self.columnar_cipher
+            .validate_message(&self.polybius_cipher.encrypt(message)?)
+            .map_err(CipherError::without_symbol)
+    }
+
+    /// Whether a character is foreign to the cipher, being outside of `a-z`, `A-Z` and `0-9`.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        !c.is_ascii_alphanumeric()
+    }
+}
+
+impl ADFGVX {
+    /// Start building a ADFGVX cipher, with defaults for the parts of the key that are not set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, ADFGVX, ADFGVXKey};
+    ///
+    /// let a = ADFGVX::builder()
+    ///     .square("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8")
+    ///     .keyword("GERMAN")
+    ///     .build();
+    /// let b = ADFGVX::new(ADFGVXKey {
+    ///     polybius_key: String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
+    ///     columnar_key: String::from("GERMAN"),
+    ///     null_char: None,
+    /// });
+    ///
+    /// assert_eq!(b.encrypt("Attack at 1200").unwrap(), a.encrypt("Attack at 1200").unwrap());
+    /// ```
+    ///
+    pub fn builder() -> ADFGVXBuilder {
+        ADFGVXBuilder::default()
+    }
+}
+
+/// Builds a ADFGVX cipher one part of its key at a time.
+///
+/// This struct is created by the `builder()` method of `ADFGVX`. See its documentation for more.
+#[derive(Clone, Debug, Default)]
+pub struct ADFGVXBuilder {
+    square: String,
+    keyword: Option<String>,
+    null_char: Option<char>,
+}
+
+impl ADFGVXBuilder {
+    /// Set the phrase used to key the polybius square, which is the plain alphanumeric alphabet
+    /// by default.
+    ///
+    pub fn square(mut self, phrase: &str) -> ADFGVXBuilder {
+        self.square = phrase.to_string();
+        self
+    }
+
+    /// Set the key of the columnar transposition. It must be set before the cipher is built.
+    ///
+    pub fn keyword(mut self, keyword: &str) -> ADFGVXBuilder {
+        self.keyword = Some(keyword.to_string());
+        self
+    }
+
+    /// Set the character used to pad uneven messages during the columnar transposition. By
+    /// default, messages are not padded.
+    ///
+    pub fn null_char(mut self, null_char: char) -> ADFGVXBuilder {
+        self.null_char = Some(null_char);
+        self
+    }
+
+    /// Initialise the ADFGVX cipher, as by `ADFGVX::new()`.
+    ///
+    /// # Panics
+    /// * The `keyword` was not set.
+    /// * The parts of the key are invalid, as for `ADFGVX::new()`.
+    ///
+    pub fn build(self) -> ADFGVX {
+        let keyword = self
+            .keyword
+            .expect("The keyword of the columnar transposition must be set.");
+
+        ADFGVX::new(ADFGVXKey {
+            polybius_key: self.square,
+            columnar_key: keyword,
+            null_char: self.null_char,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -127,11 +255,11 @@ mod tests {
 
     #[test]
     fn encrypt_simple() {
-        let a = ADFGVX::new((
-            String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
-            String::from("GERMAN"),
-            None,
-        ));
+        let a = ADFGVX::new(ADFGVXKey {
+            polybius_key: String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
+            columnar_key: String::from("GERMAN"),
+            null_char: None,
+        });
 
         let cipher_text = concat!(
             "gfxffgxgDFAXDAVGDgxvadaaxxXFDDFGGGFdfaxdavgdVDAGFAX",
@@ -146,11 +274,11 @@ mod tests {
 
     #[test]
     fn encrypt_with_space_padding() {
-        let a = ADFGVX::new((
-            String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
-            String::from("GERMAN"),
-            Some(' '),
-        ));
+        let a = ADFGVX::new(ADFGVXKey {
+            polybius_key: String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
+            columnar_key: String::from("GERMAN"),
+            null_char: Some(' '),
+        });
 
         // Note: this works as per crate version 0.11.0 - and leaves a trailing
         //       ' ' in the ciphertext.
@@ -167,11 +295,11 @@ mod tests {
 
     #[test]
     fn decrypt_message() {
-        let a = ADFGVX::new((
-            String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
-            String::from("GERMAN"),
-            None,
-        ));
+        let a = ADFGVX::new(ADFGVXKey {
+            polybius_key: String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
+            columnar_key: String::from("GERMAN"),
+            null_char: None,
+        });
 
         let cipher_text = concat!(
             "gfxffgxgDFAXDAVGDgxvadaaxxXFDDFGGGFdfaxdavgdVDAGFAX",
@@ -185,11 +313,11 @@ mod tests {
 
     #[test]
     fn decrypt_with_space_padding() {
-        let a = ADFGVX::new((
-            String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
-            String::from("GERMAN"),
-            Some(' '),
-        ));
+        let a = ADFGVX::new(ADFGVXKey {
+            polybius_key: String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
+            columnar_key: String::from("GERMAN"),
+            null_char: Some(' '),
+        });
 
         // Note: this works as per crate version 0.11.0 - and leaves a trailing
         //       ' ' in the ciphertext.
@@ -205,11 +333,11 @@ mod tests {
 
     #[test]
     fn simple() {
-        let a = ADFGVX::new((
-            String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
-            String::from("VICTORY"),
-            None,
-        ));
+        let a = ADFGVX::new(ADFGVXKey {
+            polybius_key: String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
+            columnar_key: String::from("VICTORY"),
+            null_char: None,
+        });
 
         let plain_text = concat!(
             "We attack at dawn, not later when it is light, ",
@@ -223,11 +351,11 @@ mod tests {
 
     #[test]
     fn simple_with_padding() {
-        let a = ADFGVX::new((
-            String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
-            String::from("VICTORY"),
-            Some('\u{0}'),
-        ));
+        let a = ADFGVX::new(ADFGVXKey {
+            polybius_key: String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
+            columnar_key: String::from("VICTORY"),
+            null_char: Some('\u{0}'),
+        });
 
         let plain_text = concat!(
             "We attack at dawn, not later when it is light, ",
@@ -241,24 +369,39 @@ mod tests {
 
     #[test]
     fn plaintext_with_padding() {
-        let a = ADFGVX::new((
-            String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
-            String::from("VICTORY"),
-            Some(' '),
-        ));
+        let a = ADFGVX::new(ADFGVXKey {
+            polybius_key: String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
+            columnar_key: String::from("VICTORY"),
+            null_char: Some(' '),
+        });
 
         let plain_text = "This will fail because of spaces.";
         assert!(a.encrypt(plain_text).is_err());
     }
 
+    #[test]
+    fn validate_plaintext_with_padding() {
+        let a = ADFGVX::new(ADFGVXKey {
+            polybius_key: String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
+            columnar_key: String::from("VICTORY"),
+            null_char: Some(' '),
+        });
+
+        assert_eq!(
+            Err(CipherError::Message("Message contains null characters.")),
+            a.validate_message("This will fail because of spaces.")
+        );
+        assert!(a.validate_message("ThisWillNot.").is_ok());
+    }
+
     #[test]
     fn with_utf8() {
         let plain_text = "Attack 🗡️ the east wall";
-        let a = ADFGVX::new((
-            String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
-            String::from("GERMAN"),
-            None,
-        ));
+        let a = ADFGVX::new(ADFGVXKey {
+            polybius_key: String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
+            columnar_key: String::from("GERMAN"),
+            null_char: None,
+        });
 
         assert_eq!(
             plain_text,
@@ -269,11 +412,11 @@ mod tests {
     #[test]
     fn with_utf8_with_padding() {
         let plain_text = "Attack 🗡️ the east wall";
-        let a = ADFGVX::new((
-            String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
-            String::from("GERMAN"),
-            Some('\u{0}'),
-        ));
+        let a = ADFGVX::new(ADFGVXKey {
+            polybius_key: String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
+            columnar_key: String::from("GERMAN"),
+            null_char: Some('\u{0}'),
+        });
 
         assert_eq!(
             plain_text,
@@ -284,6 +427,33 @@ mod tests {
     #[test]
     #[should_panic]
     fn invalid_key_phrase() {
-        ADFGVX::new((String::from("F@il"), String::from("GERMAN"), None));
+        ADFGVX::new(ADFGVXKey {
+            polybius_key: String::from("F@il"),
+            columnar_key: String::from("GERMAN"),
+            null_char: None,
+        });
+    }
+
+    #[test]
+    fn builder_defaults() {
+        let a = ADFGVX::builder().keyword("GERMAN").build();
+        let b = ADFGVX::new(ADFGVXKey {
+            polybius_key: String::new(),
+            columnar_key: String::from("GERMAN"),
+            null_char: None,
+        });
+
+        assert_eq!(
+            b.encrypt("Attack at 1200").unwrap(),
+            a.encrypt("Attack at 1200").unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn builder_without_keyword() {
+        ADFGVX::builder()
+            .square("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8")
+            .build();
     }
 }
@@ -5,40 +5,202 @@
 //! weakness comes from the fact that if the cryptanalyst can discover the plaintext of two
 //! ciphertext characters, then the key can be obtained by solving a simultaneous equation
 //!
-use crate::common::alphabet::Alphabet;
-use crate::common::cipher::Cipher;
-use crate::common::{alphabet, substitute};
+use crate::common::alphabet::{Alphabet, Custom};
+use crate::common::cipher::{ByteCipher, CharCipher, Cipher};
+use crate::common::key_problem::KeyProblem;
+#[cfg(feature = "parallel")]
+use crate::common::parallel;
+use crate::common::substitute;
 use num::integer::gcd;
+use std::fmt;
+use std::str::FromStr;
 
 /// An Affine cipher.
 ///
-/// This struct is created by the `new()` method. See its documentation for more.
+/// This struct is created by the `new()` or `with_alphabet()` methods. See their documentation
+/// for more.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Affine {
     a: usize,
+    a_inv: usize,
     b: usize,
+    alphabet: Custom,
 }
 
-impl Cipher for Affine {
-    type Key = (usize, usize);
-    type Algorithm = Affine;
-
+impl Affine {
     /// Initialise an Affine cipher given the key (`a`, `b`).
     ///
+    /// This can be called in a `const` context, where an invalid key fails to compile.
+    ///
     /// # Panics
     /// * `a` or `b` are not in the inclusive range `1 - 26`.
     /// * `a` has a factor in common with 26.
     ///
-    fn new(key: (usize, usize)) -> Affine {
-        let (a, b) = key;
-        if (a < 1 || b < 1) || (a > 26 || b > 26) {
-            panic!("The keys a & b must be within the range 1 <= n <= 26.");
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Affine, AffineKey, Cipher};
+    ///
+    /// const AFFINE: Affine = Affine::new(AffineKey { a: 3, b: 7 });
+    /// assert_eq!("Hmmhnl hm qhvu!", AFFINE.encrypt("Attack at dawn!").unwrap());
+    /// ```
+    ///
+    pub const fn new(key: AffineKey) -> Affine {
+        let a_inv = match validate(key, 26) {
+            Ok(a_inv) => a_inv,
+            Err(e) => panic!("{}", e),
+        };
+
+        Affine {
+            a: key.a,
+            a_inv,
+            b: key.b,
+            alphabet: Custom::standard(),
         }
+    }
 
-        if gcd(a, 26) > 1 {
-            panic!("The key 'a' cannot share a common factor with 26.");
+    /// The key of the cipher.
+    ///
+    pub fn key(&self) -> AffineKey {
+        AffineKey {
+            a: self.a,
+            b: self.b,
         }
+    }
 
-        Affine { a, b }
+    /// Initialise an Affine cipher given the key (`a`, `b`), that substitutes the symbols of an
+    /// `alphabet` other than `a-z`.
+    ///
+    /// Characters outside of the alphabet are left as they are.
+    ///
+    /// # Panics
+    /// * `a` or `b` are not in the inclusive range from 1 to the length of the `alphabet`.
+    /// * `a` has a factor in common with the length of the `alphabet`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::alphabet::ALPHANUMERIC;
+    /// use cipher_crypt::{Affine, Cipher, AffineKey};
+    ///
+    /// let a = Affine::with_alphabet(AffineKey { a: 5, b: 7 }, &ALPHANUMERIC);
+    /// assert_eq!("5114 h4 83k5", a.encrypt("meet at 10pm").unwrap());
+    /// ```
+    ///
+    pub fn with_alphabet<A: Alphabet>(key: AffineKey, alphabet: &A) -> Affine {
+        let a_inv = validate(key, alphabet.length()).unwrap_or_else(|e| panic!("{}", e));
+
+        Affine {
+            a: key.a,
+            a_inv,
+            b: key.b,
+            alphabet: Custom::from_alphabet(alphabet),
+        }
+    }
+
+    /// List the problems with a key (`a`, `b`) that would stop it from being given to `new()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Affine, KeyProblem, AffineKey};
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         KeyProblem::SharedFactor { value: 2, modulus: 26 },
+    ///         KeyProblem::OutOfRange { value: 30, min: 1, max: 26 },
+    ///     ],
+    ///     Affine::key_problems(AffineKey { a: 2, b: 30 })
+    /// );
+    /// ```
+    ///
+    pub fn key_problems(key: AffineKey) -> Vec<KeyProblem> {
+        let AffineKey { a, b } = key;
+        let out_of_range = |value| KeyProblem::OutOfRange {
+            value,
+            min: 1,
+            max: 26,
+        };
+
+        let mut problems = Vec::new();
+        if !(1..=26).contains(&a) {
+            problems.push(out_of_range(a));
+        } else if gcd(a, 26) > 1 {
+            problems.push(KeyProblem::SharedFactor {
+                value: a,
+                modulus: 26,
+            });
+        }
+        if !(1..=26).contains(&b) {
+            problems.push(out_of_range(b));
+        }
+
+        problems
+    }
+
+    /// Encrypt a long message using an Affine cipher, sharing the work across threads.
+    ///
+    /// The message is split into chunks, which are encrypted on the threads of the global `rayon`
+    /// thread pool. The ciphertext is the same as that of `encrypt()`.
+    ///
+    #[cfg(feature = "parallel")]
+    pub fn encrypt_parallel(&self, message: &str) -> Result<String, &'static str> {
+        parallel::map_chunks(message, |chunk| self.encrypt(chunk))
+    }
+
+    /// Decrypt a long message using an Affine cipher, sharing the work across threads.
+    ///
+    /// The plaintext is the same as that of `decrypt()`.
+    ///
+    #[cfg(feature = "parallel")]
+    pub fn decrypt_parallel(&self, ciphertext: &str) -> Result<String, &'static str> {
+        parallel::map_chunks(ciphertext, |chunk| self.decrypt(chunk))
+    }
+}
+
+/// The key of an Affine cipher.
+///
+/// This struct is given to the `new()` method of `Affine`. See its documentation for more.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AffineKey {
+    /// The multiplier of the cipher, which must not share a factor with the length of the
+    /// alphabet.
+    pub a: usize,
+    /// The shift of the cipher.
+    pub b: usize,
+}
+
+/// Check that the key (`a`, `b`) suits an alphabet of `length` symbols, returning the
+/// multiplicative inverse of `a`.
+///
+const fn validate(key: AffineKey, length: usize) -> Result<usize, &'static str> {
+    let AffineKey { a, b } = key;
+    if a < 1 || b < 1 || a > length || b > length {
+        return Err(
+            "The keys a & b must be within the range 1 <= n <= the length of the alphabet.",
+        );
+    }
+
+    // There is an inverse exactly when `a` shares no factor with the length
+    let mut a_inv = 1;
+    while a_inv < length {
+        if (a * a_inv) % length == 1 {
+            return Ok(a_inv);
+        }
+        a_inv += 1;
+    }
+
+    Err("The key 'a' cannot share a common factor with the length of the alphabet.")
+}
+
+impl Cipher for Affine {
+    type Key = AffineKey;
+    type Algorithm = Affine;
+
+    /// Initialise an Affine cipher given a specific key, as by `Affine::new()`.
+    ///
+    fn new(key: AffineKey) -> Affine {
+        Affine::new(key)
     }
 
     /// Encrypt a message using an Affine cipher.
@@ -47,20 +209,21 @@ impl Cipher for Affine {
     /// Basic usage:
     ///
     /// ```
-    /// use cipher_crypt::{Cipher, Affine};
+    /// use cipher_crypt::{Cipher, Affine, AffineKey};
     ///
-    /// let a = Affine::new((3, 7));
+    /// let a = Affine::new(AffineKey { a: 3, b: 7 });
     /// assert_eq!("Hmmhnl hm qhvu!", a.encrypt("Attack at dawn!").unwrap());
     /// ```
     ///
     fn encrypt(&self, message: &str) -> Result<String, &'static str> {
-        // Encryption of a letter:
-        //         E(x) = (ax + b) mod 26
-        // Where;  x    = position of letter in alphabet
-        //         a, b = the numbers of the affine key
-        Ok(substitute::shift_substitution(message, |idx| {
-            alphabet::STANDARD.modulo(((self.a * idx) + self.b) as isize)
-        }))
+        Ok(self.encrypt_chars(message.chars()).collect())
+    }
+
+    /// Encrypt a message onto the end of `out`, one character at a time.
+    ///
+    fn encrypt_into(&self, message: &str, out: &mut String) -> Result<(), &'static str> {
+        out.extend(self.encrypt_chars(message.chars()));
+        Ok(())
     }
 
     /// Decrypt a message using an Affine cipher.
@@ -69,52 +232,144 @@ impl Cipher for Affine {
     /// Basic usage:
     ///
     /// ```
-    /// use cipher_crypt::{Cipher, Affine};
+    /// use cipher_crypt::{Cipher, Affine, AffineKey};
     ///
-    /// let a = Affine::new((3, 7));
+    /// let a = Affine::new(AffineKey { a: 3, b: 7 });
     /// assert_eq!("Attack at dawn!", a.decrypt("Hmmhnl hm qhvu!").unwrap());
     /// ```
     ///
     fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        Ok(self.decrypt_chars(ciphertext.chars()).collect())
+    }
+
+    /// Whether a character is foreign to the cipher, being outside of its alphabet.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        self.alphabet.find_position(c).is_none()
+    }
+}
+
+impl CharCipher for Affine {
+    fn encrypt_char(&self, c: char) -> char {
+        // Encryption of a letter:
+        //         E(x) = (ax + b) mod 26
+        // Where;  x    = position of letter in alphabet
+        //         a, b = the numbers of the affine key
+        substitute::alphabet_shift_char(c, &self.alphabet, |idx| {
+            self.alphabet.modulo(((self.a * idx) + self.b) as isize)
+        })
+    }
+
+    fn decrypt_char(&self, c: char) -> char {
         // Decryption of a letter:
         //         D(x) = (a^-1*(x - b)) mod 26
         // Where;  x    = position of letter in alphabet
         //         a^-1 = multiplicative inverse of the key number `a`
         //         b    = a number of the affine key
-        let a_inv = alphabet::STANDARD
-            .multiplicative_inverse(self.a as isize)
-            .expect("Multiplicative inverse for 'a' could not be calculated.");
+        substitute::alphabet_shift_char(c, &self.alphabet, |idx| {
+            self.alphabet
+                .modulo(self.a_inv as isize * (idx as isize - self.b as isize))
+        })
+    }
+}
+
+impl ByteCipher for Affine {}
+
+impl FromStr for Affine {
+    type Err = &'static str;
+
+    /// Parse an Affine cipher over the standard alphabet from its key `a` and `b`, separated by a
+    /// comma (such as `"3,7"`).
+    ///
+    /// Returns `Err` if the key is not two numbers, or if it could not be given to `new()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Affine, Cipher};
+    ///
+    /// let a: Affine = "3, 7".parse().unwrap();
+    /// assert_eq!("Hmmhnl hm qhvu!", a.encrypt("Attack at dawn!").unwrap());
+    /// assert_eq!("3,7", a.to_string());
+    /// ```
+    ///
+    fn from_str(s: &str) -> Result<Affine, &'static str> {
+        let mut numbers = s.split(',').map(|n| n.trim().parse::<usize>());
+        let (a, b) = match (numbers.next(), numbers.next(), numbers.next()) {
+            (Some(Ok(a)), Some(Ok(b)), None) => (a, b),
+            _ => return Err("The key must be two numbers separated by a comma."),
+        };
 
-        Ok(substitute::shift_substitution(ciphertext, |idx| {
-            alphabet::STANDARD.modulo(a_inv as isize * (idx as isize - self.b as isize))
-        }))
+        let key = AffineKey { a, b };
+        validate(key, 26)?;
+
+        Ok(Affine::new(key))
+    }
+}
+
+impl fmt::Display for Affine {
+    /// Writes the key `a` and `b` of the cipher, as parsed by `from_str()`.
+    ///
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.a, self.b)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::alphabet;
 
     #[test]
     fn encrypt_message() {
-        let a = Affine::new((3, 7));
+        let a = Affine::new(AffineKey { a: 3, b: 7 });
         assert_eq!("Hmmhnl hm qhvu!", a.encrypt("Attack at dawn!").unwrap());
     }
 
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn parallel_long_message() {
+        let a = Affine::new(AffineKey { a: 3, b: 7 });
+        let message = "Attack at dawn 🗡️ ".repeat(10_000);
+        let encrypted = a.encrypt_parallel(&message).unwrap();
+
+        assert_eq!(a.encrypt(&message).unwrap(), encrypted);
+        assert_eq!(message, a.decrypt_parallel(&encrypted).unwrap());
+    }
+
+    #[test]
+    fn key_getter() {
+        let key = AffineKey { a: 3, b: 7 };
+        assert_eq!(key, Affine::new(key).key());
+    }
+
     #[test]
     fn decrypt_message() {
-        let a = Affine::new((3, 7));
+        let a = Affine::new(AffineKey { a: 3, b: 7 });
         assert_eq!("Attack at dawn!", a.decrypt("Hmmhnl hm qhvu!").unwrap());
     }
 
     #[test]
     fn with_utf8() {
-        let a = Affine::new((15, 10));
+        let a = Affine::new(AffineKey { a: 15, b: 10 });
         let message = "Peace ✌️ Freedom and Liberty!";
 
         assert_eq!(message, a.decrypt(&a.encrypt(message).unwrap()).unwrap());
     }
 
+    #[test]
+    fn with_bytes() {
+        let a = Affine::new(AffineKey { a: 3, b: 7 });
+        let message = "Attack at dawn! 🗡️".as_bytes();
+        let encrypted = a.encrypt_bytes(message).unwrap();
+
+        assert_eq!(
+            a.encrypt("Attack at dawn! 🗡️").unwrap().as_bytes(),
+            &encrypted[..]
+        );
+        assert_eq!(message, &a.decrypt_bytes(&encrypted).unwrap()[..]);
+    }
+
     #[test]
     fn exhaustive_encrypt() {
         //Test with every combination of a and b
@@ -126,37 +381,78 @@ mod tests {
             }
 
             for b in 1..27 {
-                let a = Affine::new((a, b));
+                let a = Affine::new(AffineKey { a, b });
                 assert_eq!(message, a.decrypt(&a.encrypt(message).unwrap()).unwrap());
             }
         }
     }
 
+    #[test]
+    fn custom_alphabet() {
+        let message = "the 1st unit moves at 0900!";
+
+        for a in 1..37 {
+            if gcd(a, 36) > 1 {
+                continue;
+            }
+
+            let a = Affine::with_alphabet(AffineKey { a, b: 7 }, &alphabet::ALPHANUMERIC);
+            assert_eq!(message, a.decrypt(&a.encrypt(message).unwrap()).unwrap());
+        }
+    }
+
+    #[test]
+    fn parse_key() {
+        assert_eq!("15,17", "15,17".parse::<Affine>().unwrap().to_string());
+        assert!("2,15".parse::<Affine>().is_err());
+        assert!("3".parse::<Affine>().is_err());
+        assert!("3,7,9".parse::<Affine>().is_err());
+    }
+
     #[test]
     fn valid_key() {
-        Affine::new((15, 17));
+        Affine::new(AffineKey { a: 15, b: 17 });
     }
 
     #[test]
     fn b_shares_factor() {
-        Affine::new((15, 2));
+        Affine::new(AffineKey { a: 15, b: 2 });
     }
 
     #[test]
     #[should_panic]
     fn a_shares_factor() {
-        Affine::new((2, 15));
+        Affine::new(AffineKey { a: 2, b: 15 });
+    }
+
+    #[test]
+    #[should_panic]
+    fn a_shares_factor_with_alphabet() {
+        Affine::with_alphabet(AffineKey { a: 3, b: 7 }, &alphabet::ALPHANUMERIC);
     }
 
     #[test]
     #[should_panic]
     fn keys_to_small() {
-        Affine::new((0, 10));
+        Affine::new(AffineKey { a: 0, b: 10 });
     }
 
     #[test]
     #[should_panic]
     fn keys_to_big() {
-        Affine::new((30, 51));
+        Affine::new(AffineKey { a: 30, b: 51 });
+    }
+
+    #[test]
+    fn const_new_matches_with_alphabet() {
+        for a in [1, 3, 5, 7, 9, 11, 15, 17, 19, 21, 23, 25].iter() {
+            for b in 1..=26 {
+                let key = AffineKey { a: *a, b };
+                assert_eq!(
+                    Affine::with_alphabet(key, &alphabet::STANDARD),
+                    Affine::new(key)
+                );
+            }
+        }
     }
 }
@@ -0,0 +1,326 @@
+//! The Affine Hill cipher extends the Hill cipher with a shift vector, in the same way that the
+//! Affine cipher extends a multiplicative cipher with a shift.
+//!
+//! Each chunk `M` of the message is encrypted as `C = K·M + V mod 26`, where `K` is an invertible
+//! key matrix and `V` is the shift vector. Without the shift, any chunk of `a`s is left unchanged
+//! by the Hill cipher; the shift removes this weakness, although the cipher remains vulnerable to
+//! a known-plaintext attack.
+//!
+//! Like the Hill cipher, this cipher uses the external library
+//! [rulinalg](https://crates.io/crates/rulinalg) for the key matrix. An instance can also be
+//! created via the function `AffineHill::from_phrase(...)`, which avoids dealing with matrices
+//! altogether.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
+use crate::hill::Hill;
+use rulinalg::matrix::{BaseMatrix, BaseMatrixMut, Matrix};
+
+/// An Affine Hill cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AffineHill {
+    matrix: Matrix<f64>,
+    shift: Matrix<f64>,
+    inverse: Matrix<f64>,
+    inverse_shift: Matrix<f64>,
+}
+
+/// The key of an Affine Hill cipher.
+///
+/// This struct is given to the `new()` method of `AffineHill`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AffineHillKey {
+    /// The square key matrix, which must be invertible modulo 26.
+    pub matrix: Matrix<isize>,
+    /// The shift vector, which has one element for each row of the `matrix`.
+    pub shift: Vec<isize>,
+}
+
+impl Cipher for AffineHill {
+    type Key = AffineHillKey;
+    type Algorithm = AffineHill;
+
+    /// Initialise an Affine Hill cipher given a key matrix and a shift vector.
+    ///
+    /// # Panics
+    /// * The `key` matrix is not a square
+    /// * The `key` matrix is non-invertible
+    /// * The inverse determinant of the `key` matrix cannot be calculated such that
+    ///   `d*d^-1 == 1 mod 26`
+    /// * The length of the `shift` vector does not match the size of the `key` matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate rulinalg;
+    /// extern crate cipher_crypt;
+    ///
+    /// use rulinalg::matrix::Matrix;
+    /// use cipher_crypt::{Cipher, AffineHill, AffineHillKey};
+    ///
+    /// fn main() {
+    ///     //Initialise an Affine Hill cipher from a 3 x 3 matrix and a shift of length 3
+    ///     let m = Matrix::new(3, 3, vec![2, 4, 5, 9, 2, 1, 3, 17, 7]);
+    ///     let h = AffineHill::new(AffineHillKey { matrix: m, shift: vec![1, 2, 3] });
+    /// }
+    /// ```
+    ///
+    fn new(key: AffineHillKey) -> AffineHill {
+        let AffineHillKey { matrix: key, shift } = key;
+        Hill::validate_key(&key);
+
+        if shift.len() != key.rows() {
+            panic!("The length of the shift must match the size of the key matrix.");
+        }
+
+        let shift = Matrix::new(
+            shift.len(),
+            1,
+            shift
+                .into_iter()
+                .map(|s| alphabet::STANDARD.modulo(s) as f64)
+                .collect::<Vec<f64>>(),
+        );
+
+        // The key is inverted once, rather than for every message that is decrypted:
+        //         M = K^-1·(C - V) = K^-1·C - K^-1·V mod 26
+        let matrix: Matrix<f64> = key.try_into().unwrap();
+        let inverse = Hill::calc_inverse_key(matrix.clone()).unwrap();
+        let inverse_shift = (&inverse * &shift).apply(&|x| (26.0 - (x % 26.0).round()) % 26.0);
+
+        AffineHill {
+            matrix,
+            shift,
+            inverse,
+            inverse_shift,
+        }
+    }
+
+    /// Encrypt a message using an Affine Hill cipher.
+    ///
+    /// As with the Hill cipher, the message may only contain alphabetic characters, and padding
+    /// characters are added when the length of the message is not a multiple of the key matrix
+    /// size.
+    ///
+    /// # Example
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, AffineHill};
+    ///
+    /// let h = AffineHill::from_phrase("CEFJCBDRH", "BCD");
+    /// //Padding characters are added during the encryption process
+    /// assert_eq!("QHRHQDVELNri", h.encrypt("ATTACKEAST").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        // Encryption of a chunk of the message:
+        //         C = K·M + V mod 26
+        Hill::transform_message(&self.matrix, &self.shift, message)
+    }
+
+    /// Decrypt a message using an Affine Hill cipher.
+    ///
+    /// # Example
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, AffineHill};
+    ///
+    /// let h = AffineHill::from_phrase("CEFJCBDRH", "BCD");
+    /// assert_eq!("ATTACKEASTaa", h.decrypt("QHRHQDVELNri").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        // Decryption of a chunk of the message:
+        //         M = K^-1·C - K^-1·V mod 26
+        Hill::transform_message(&self.inverse, &self.inverse_shift, ciphertext)
+    }
+
+    /// Check that a message can be encrypted using an Affine Hill cipher.
+    ///
+    /// Returns `Err` if the message contains any non-alphabetic symbols.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, AffineHill};
+    ///
+    /// let h = AffineHill::from_phrase("CEFJCBDRH", "BCD");
+    /// assert!(h.validate_message("ATTACKEAST").is_ok());
+    /// assert!(h.validate_message("Attack east!").is_err());
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        CipherError::find(
+            message,
+            "Message cannot contain non-alphabetic symbols.",
+            |c| alphabet::STANDARD.find_position(c).is_none(),
+        )
+    }
+}
+
+impl AffineHill {
+    /// Initialise an Affine Hill cipher given a phrase for the key matrix and a phrase for the
+    /// shift vector.
+    ///
+    /// The position of each character within the alphabet is used to construct the matrix
+    /// (read row by row) and the shift vector. The length of the `shift` phrase defines how many
+    /// chars (or chunks) of a message will be transposed during encryption/decryption.
+    ///
+    /// # Panics
+    /// * The length of the `shift` is less than 2
+    /// * The square of the length of the `shift` is not equal to the phrase length
+    /// * The phrase or shift contains non-alphabetic symbols
+    /// * Any of the Err conditions as stipulated by the `new()` fn
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, AffineHill};
+    ///
+    /// let h = AffineHill::from_phrase("CEFJCBDRH", "BCD");
+    /// h.encrypt("thing");
+    /// ```
+    ///
+    pub fn from_phrase(phrase: &str, shift: &str) -> AffineHill {
+        let chunk_size = shift.len();
+        if chunk_size < 2 {
+            panic!("The chunk size must be greater than 1.");
+        }
+
+        if chunk_size * chunk_size != phrase.len() {
+            panic!("The square of the chunk size must equal the length of the phrase.");
+        }
+
+        if !alphabet::STANDARD.is_valid(phrase) || !alphabet::STANDARD.is_valid(shift) {
+            panic!("Phrase cannot contain non-alphabetic symbols.");
+        }
+
+        let positions = |text: &str| -> Vec<isize> {
+            text.chars()
+                .map(|c| alphabet::STANDARD.find_position(c).unwrap() as isize)
+                .collect()
+        };
+
+        AffineHill::new(AffineHillKey {
+            matrix: Matrix::new(chunk_size, chunk_size, positions(phrase)),
+            shift: positions(shift),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn affine_hill() -> AffineHill {
+        AffineHill::new(AffineHillKey {
+            matrix: Matrix::new(3, 3, vec![2, 4, 5, 9, 2, 1, 3, 17, 7]),
+            shift: vec![1, 2, 3],
+        })
+    }
+
+    #[test]
+    fn encrypt_test() {
+        assert_eq!(
+            "QHRHQDORJYHA",
+            affine_hill().encrypt("ATTACKATDAWN").unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_test() {
+        assert_eq!(
+            "ATTACKATDAWN",
+            affine_hill().decrypt("QHRHQDORJYHA").unwrap()
+        );
+    }
+
+    #[test]
+    fn zero_shift_matches_hill() {
+        let m = "ATTACKatDAWN";
+        let key = Matrix::new(3, 3, vec![2, 4, 5, 9, 2, 1, 3, 17, 7]);
+
+        assert_eq!(
+            Hill::new(key.clone()).encrypt(m).unwrap(),
+            AffineHill::new(AffineHillKey {
+                matrix: key,
+                shift: vec![0, 0, 0]
+            })
+            .encrypt(m)
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn shift_is_reduced() {
+        let m = "ATTACKATDAWN";
+        let key = Matrix::new(3, 3, vec![2, 4, 5, 9, 2, 1, 3, 17, 7]);
+
+        assert_eq!(
+            affine_hill().encrypt(m).unwrap(),
+            AffineHill::new(AffineHillKey {
+                matrix: key,
+                shift: vec![27, -24, 55]
+            })
+            .encrypt(m)
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn padding_req() {
+        let h = affine_hill();
+        let m = "ATTACKATDAWNz";
+
+        let e = h.encrypt(m).unwrap();
+        assert_eq!(15, e.len());
+        assert_eq!("ATTACKATDAWNzaa", h.decrypt(&e).unwrap());
+    }
+
+    #[test]
+    fn from_phrase_matches_new() {
+        let m = "ATTACKATDAWN";
+        assert_eq!(
+            affine_hill().encrypt(m).unwrap(),
+            AffineHill::from_phrase("CEFJCBDRH", "BCD")
+                .encrypt(m)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn encrypt_with_symbols() {
+        assert!(affine_hill().encrypt("This won!t w@rk").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_invertable_matrix() {
+        AffineHill::new(AffineHillKey {
+            matrix: Matrix::new(3, 3, vec![2, 2, 3, 6, 6, 9, 1, 4, 8]),
+            shift: vec![1, 2, 3],
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn shift_wrong_length() {
+        AffineHill::new(AffineHillKey {
+            matrix: Matrix::new(3, 3, vec![2, 4, 5, 9, 2, 1, 3, 17, 7]),
+            shift: vec![1, 2],
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_shift_phrase() {
+        AffineHill::from_phrase("CEFJCBDRH", "B!D");
+    }
+}
@@ -0,0 +1,264 @@
+//! Multiple anagramming attacks a columnar transposition cipher when two or more messages of the
+//! same length have been encrypted with the same key.
+//!
+//! Each ciphertext is cut into the columns of the transposition, which must then be put back in
+//! their original order. With a single message, there are too few letters in each row to tell
+//! which columns belong side by side. With several messages, however, the same columns are
+//! adjacent in every one of them, so the evidence of all of the messages can be combined: the
+//! columns that form common pairs of letters across every row of every message are likely to be
+//! neighbours. This technique was used extensively by codebreakers during both World Wars.
+//!
+use super::frequency::ENGLISH_BIGRAMS;
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+
+/// The widest transposition that can be searched.
+const MAX_WIDTH: usize = 12;
+
+/// The solution of a multiple anagramming attack, as returned by `multiple_anagram`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Anagram {
+    /// The numeric key of the transposition: the position (from 0) at which each column of the
+    /// plaintext was read into the ciphertext.
+    pub order: Vec<usize>,
+    /// The plaintexts of each of the ciphertexts, in the order they were given.
+    pub plaintexts: Vec<String>,
+    /// How well the neighbouring columns pair together, across all of the plaintexts. The higher
+    /// the score, the more likely the solution is to be correct.
+    pub score: f64,
+}
+
+/// Recover the key of a columnar transposition of `width` columns, given several `ciphertexts`
+/// of the same length that were encrypted with it.
+///
+/// The length of the ciphertexts must be a multiple of the `width`, so that every column is
+/// complete (as when the messages are padded with nulls). Every arrangement of the columns is
+/// considered, scoring each pair of neighbouring columns by the bigrams of English that they form
+/// in every row of every ciphertext, so the best arrangement is always found.
+///
+/// # Panics
+/// * There are fewer than two `ciphertexts`.
+/// * The `ciphertexts` are not all of the same length.
+/// * The `width` is not between 2 and 12.
+/// * The length of the ciphertexts is not a multiple of the `width`.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::multiple_anagram;
+/// use cipher_crypt::{Cipher, ColumnarTransposition, ColumnarTranspositionKey};
+///
+/// let ct = ColumnarTransposition::new(ColumnarTranspositionKey {
+///     keystream: String::from("zebras"),
+///     null_char: None,
+/// });
+/// let first = ct.encrypt("attackthenorthernbridgeatdawnwithalltanksx").unwrap();
+/// let second = ct.encrypt("holdtheriverlineuntilthereliefforcearrives").unwrap();
+///
+/// let anagram = multiple_anagram(&[&first, &second], 6);
+/// assert_eq!(vec![5, 2, 1, 3, 0, 4], anagram.order);
+/// assert_eq!("holdtheriverlineuntilthereliefforcearrives", anagram.plaintexts[1]);
+/// ```
+///
+pub fn multiple_anagram(ciphertexts: &[&str], width: usize) -> Anagram {
+    if ciphertexts.len() < 2 {
+        panic!("There must be at least two ciphertexts.");
+    }
+    let texts: Vec<Vec<char>> = ciphertexts.iter().map(|c| c.chars().collect()).collect();
+    let length = texts[0].len();
+    if texts.iter().any(|t| t.len() != length) {
+        panic!("The ciphertexts must all be of the same length.");
+    }
+    if !(2..=MAX_WIDTH).contains(&width) {
+        panic!("The width must be between 2 and 12.");
+    }
+    if !length.is_multiple_of(width) {
+        panic!("The length of the ciphertexts must be a multiple of the width.");
+    }
+
+    // The columns of every ciphertext, in the order they were read into it
+    let height = length / width;
+    let column = |t: &Vec<char>, c: usize| t[c * height..(c + 1) * height].to_vec();
+
+    // The score of placing each column immediately to the left of each other, and of placing
+    // each column at the end of a row with each other at the start of the next row
+    let mut adjacency = vec![vec![0.0; width]; width];
+    let mut wrap = vec![vec![0.0; width]; width];
+    for left in 0..width {
+        for right in (0..width).filter(|&r| r != left) {
+            for t in &texts {
+                let (l, r) = (column(t, left), column(t, right));
+                adjacency[left][right] += l
+                    .iter()
+                    .zip(&r)
+                    .map(|(&a, &b)| bigram_score(a, b))
+                    .sum::<f64>();
+                wrap[left][right] += l
+                    .iter()
+                    .zip(&r[1..])
+                    .map(|(&a, &b)| bigram_score(a, b))
+                    .sum::<f64>();
+            }
+        }
+    }
+
+    let (score, path) = best_path(&adjacency, &wrap);
+
+    let mut order = vec![0; width];
+    for (position, &c) in path.iter().enumerate() {
+        order[position] = c;
+    }
+    let plaintexts = texts
+        .iter()
+        .map(|t| {
+            (0..height)
+                .flat_map(|r| path.iter().map(move |&c| t[c * height + r]))
+                .collect()
+        })
+        .collect();
+
+    Anagram {
+        order,
+        plaintexts,
+        score,
+    }
+}
+
+/// The log (base 10) frequency of the bigram `ab` in English, with a floor for the bigrams that
+/// are not among the most common. Pairs that are not both letters score nothing.
+///
+fn bigram_score(a: char, b: char) -> f64 {
+    if alphabet::STANDARD.find_position(a).is_none()
+        || alphabet::STANDARD.find_position(b).is_none()
+    {
+        return 0.0;
+    }
+
+    let bigram: String = [a, b].iter().collect::<String>().to_ascii_lowercase();
+    let frequency = ENGLISH_BIGRAMS
+        .iter()
+        .find(|&&(g, _)| g == bigram)
+        .map_or(0.0005, |&(_, f)| f);
+
+    frequency.log10()
+}
+
+/// Find the order of all of the columns that has the highest total score, returning that
+/// score along with the order.
+///
+/// The score of an order is the sum of the `adjacency` scores of its neighbouring columns, plus
+/// the `wrap` score of its last column followed by its first (as the end of each row is followed
+/// by the start of the next). Every order is considered, by finding the best order of each subset
+/// of the columns that starts with a given column, and ends with each other column.
+///
+fn best_path(adjacency: &[Vec<f64>], wrap: &[Vec<f64>]) -> (f64, Vec<usize>) {
+    let width = adjacency.len();
+    let subsets = 1 << width;
+    let full = subsets - 1;
+
+    let mut best_score = f64::NEG_INFINITY;
+    let mut best_path = Vec::new();
+    for first in 0..width {
+        // The best score of each subset of the columns that ends with each column, along with
+        // the column before it
+        let mut best = vec![vec![(f64::NEG_INFINITY, None); width]; subsets];
+        best[1 << first][first] = (0.0, None);
+
+        for subset in (1..subsets).filter(|s| s & (1 << first) != 0) {
+            for last in (0..width).filter(|&l| subset & (1 << l) != 0) {
+                let (score, _) = best[subset][last];
+                if score == f64::NEG_INFINITY {
+                    continue;
+                }
+
+                for next in (0..width).filter(|&n| subset & (1 << n) == 0) {
+                    let candidate = score + adjacency[last][next];
+                    if candidate > best[subset | (1 << next)][next].0 {
+                        best[subset | (1 << next)][next] = (candidate, Some(last));
+                    }
+                }
+            }
+        }
+
+        for last in 0..width {
+            let score = best[full][last].0 + wrap[last][first];
+            if score <= best_score {
+                continue;
+            }
+
+            let mut path = vec![last];
+            let (mut subset, mut column) = (full, last);
+            while let Some(previous) = best[subset][column].1 {
+                subset &= !(1 << column);
+                column = previous;
+                path.push(column);
+            }
+            path.reverse();
+
+            best_score = score;
+            best_path = path;
+        }
+    }
+
+    (best_score, best_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cipher, ColumnarTransposition, ColumnarTranspositionKey};
+
+    #[test]
+    fn multiple_anagram_test() {
+        let ct = ColumnarTransposition::new(ColumnarTranspositionKey {
+            keystream: String::from("german"),
+            null_char: None,
+        });
+        let messages = [
+            "theenemyisadvancingfromthenorthsendmorereinforcementsx",
+            "weareholdingthelineatthebridgebutneedmoreammunitionnow",
+            "theartilleryhasbeensilencedandtheinfantrywillattacknow",
+        ];
+        let ciphertexts: Vec<String> = messages.iter().map(|m| ct.encrypt(m).unwrap()).collect();
+
+        let anagram = multiple_anagram(
+            &ciphertexts
+                .iter()
+                .map(|c| c.as_str())
+                .collect::<Vec<&str>>(),
+            6,
+        );
+        assert_eq!(messages.to_vec(), anagram.plaintexts);
+    }
+
+    #[test]
+    fn best_path_test() {
+        let adjacency = vec![
+            vec![0.0, -5.0, -1.0],
+            vec![-5.0, 0.0, -5.0],
+            vec![-5.0, -1.0, 0.0],
+        ];
+
+        let wrap = vec![vec![-1.0; 3]; 3];
+
+        assert_eq!((-3.0, vec![0, 2, 1]), best_path(&adjacency, &wrap));
+    }
+
+    #[test]
+    #[should_panic]
+    fn single_ciphertext() {
+        multiple_anagram(&["abcdef"], 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn different_lengths() {
+        multiple_anagram(&["abcdef", "abcdefgh"], 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn incomplete_columns() {
+        multiple_anagram(&["abcdefg", "abcdefg"], 3);
+    }
+}
@@ -0,0 +1,260 @@
+//! A simulated annealing search over the keys of a cipher.
+//!
+//! Simulated annealing is a variation of hill climbing that will sometimes keep a mutation of
+//! the key that scores lower than the current key. The chance of keeping a worse key depends on
+//! how much worse it is and on a temperature, which falls as the search goes on: early in the
+//! search the key wanders freely over the key space, and by the end only better keys are kept.
+//! This allows the search to escape keys that no single mutation improves, which is common when a
+//! small change to a key (such as swapping two letters of a Playfair square) changes much of the
+//! plaintext.
+//!
+use super::solver::{evaluate, record, seeded_rng, KeySearch, Optimizer, Progress};
+use super::Candidate;
+use rand::Rng;
+
+/// A simulated annealing search over the keys of a cipher.
+///
+/// This struct is created by the `new()` method, and configured by its builder methods.
+#[derive(Clone, Debug)]
+pub struct SimulatedAnnealing {
+    restarts: usize,
+    iterations: usize,
+    temperature: f64,
+    seed: Option<u64>,
+}
+
+impl Default for SimulatedAnnealing {
+    fn default() -> SimulatedAnnealing {
+        SimulatedAnnealing::new()
+    }
+}
+
+impl SimulatedAnnealing {
+    /// Initialise a simulated annealing search that makes 5 runs of 10000 mutations each,
+    /// starting at a temperature of 2, with random choices that differ from one search to the
+    /// next.
+    ///
+    pub fn new() -> SimulatedAnnealing {
+        SimulatedAnnealing {
+            restarts: 5,
+            iterations: 10000,
+            temperature: 2.0,
+            seed: None,
+        }
+    }
+
+    /// Set the number of runs made from a random key.
+    ///
+    /// # Panics
+    /// * `restarts` is 0.
+    ///
+    pub fn restarts(mut self, restarts: usize) -> SimulatedAnnealing {
+        if restarts == 0 {
+            panic!("The search must make at least one run.");
+        }
+
+        self.restarts = restarts;
+        self
+    }
+
+    /// Set the number of mutations made during each run.
+    ///
+    pub fn iterations(mut self, iterations: usize) -> SimulatedAnnealing {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Set the temperature at the start of each run, which falls evenly to 0 by its end.
+    ///
+    /// A mutation that lowers the score by `d` is kept with a chance of `e^(-d/t)` at a
+    /// temperature of `t`, so the temperature should be set in proportion to the differences in
+    /// the scores of the fitness function.
+    ///
+    /// # Panics
+    /// * `temperature` is negative or not finite.
+    ///
+    pub fn temperature(mut self, temperature: f64) -> SimulatedAnnealing {
+        if !temperature.is_finite() || temperature < 0.0 {
+            panic!("The temperature must be a finite, non-negative number.");
+        }
+
+        self.temperature = temperature;
+        self
+    }
+
+    /// Seed the random choices of the search, so that it is repeatable.
+    ///
+    pub fn seed(mut self, seed: u64) -> SimulatedAnnealing {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl Optimizer for SimulatedAnnealing {
+    /// Search the keys of a cipher by simulated annealing, reporting each better candidate to a
+    /// `callback`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::solver::{Optimizer, SubstitutionSearch};
+    /// use cipher_crypt::analysis::{FrequencyTable, Language, SimulatedAnnealing};
+    ///
+    /// let search = SubstitutionSearch::new("Pybb cbvd dxkiyb");
+    /// let fitness = |text: &str| {
+    ///     FrequencyTable::letters(text).log_likelihood(Language::English.letters())
+    /// };
+    ///
+    /// let best = SimulatedAnnealing::new()
+    ///     .seed(1)
+    ///     .restarts(1)
+    ///     .iterations(500)
+    ///     .temperature(1.0)
+    ///     .optimize(&search, fitness);
+    ///
+    /// assert!((fitness(&best.plaintext) - best.score).abs() < 1e-9);
+    /// ```
+    ///
+    fn optimize_with<S, F, C>(&self, search: &S, fitness: F, mut callback: C) -> Candidate<S::Key>
+    where
+        S: KeySearch,
+        F: Fn(&str) -> f64,
+        C: FnMut(&Progress<S::Key>) -> bool,
+    {
+        let mut rng = seeded_rng(self.seed);
+
+        let mut best = None;
+        for restart in 0..self.restarts {
+            let mut current = evaluate(search, &fitness, search.random_key(&mut rng));
+            if !record(&mut best, &current, restart, 0, &mut callback) {
+                return best.unwrap();
+            }
+
+            for iteration in 1..=self.iterations {
+                let temperature =
+                    self.temperature * (1.0 - iteration as f64 / self.iterations as f64);
+                let next = evaluate(search, &fitness, search.mutate(&current.key, &mut rng));
+
+                let change = next.score - current.score;
+                if change > 0.0
+                    || (temperature > 0.0 && rng.gen::<f64>() < (change / temperature).exp())
+                {
+                    current = next;
+                }
+
+                if !record(&mut best, &current, restart, iteration, &mut callback) {
+                    return best.unwrap();
+                }
+            }
+        }
+
+        best.unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::seq::SliceRandom;
+    use rand::RngCore;
+
+    /// Search for a permutation of the numbers 0 to 7, mutated by swapping two of them.
+    struct Permutation;
+
+    impl KeySearch for Permutation {
+        type Key = Vec<usize>;
+
+        fn random_key(&self, rng: &mut dyn RngCore) -> Vec<usize> {
+            let mut key: Vec<usize> = (0..8).collect();
+            key.shuffle(rng);
+            key
+        }
+
+        fn mutate(&self, key: &Vec<usize>, rng: &mut dyn RngCore) -> Vec<usize> {
+            let mut key = key.clone();
+            key.swap(rng.gen_range(0..8), rng.gen_range(0..8));
+            key
+        }
+
+        fn decrypt(&self, key: &Vec<usize>) -> String {
+            key.iter()
+                .map(|i| "abcdefgh".chars().nth(*i).unwrap())
+                .collect()
+        }
+    }
+
+    fn sorted(text: &str) -> f64 {
+        text.chars()
+            .zip("abcdefgh".chars())
+            .filter(|(a, b)| a == b)
+            .count() as f64
+    }
+
+    #[test]
+    fn finds_optimum() {
+        let best = SimulatedAnnealing::new()
+            .seed(3)
+            .temperature(1.0)
+            .optimize(&Permutation, sorted);
+
+        assert_eq!("abcdefgh", best.plaintext);
+        assert_eq!(8.0, best.score);
+    }
+
+    #[test]
+    fn seeded_is_repeatable() {
+        let annealing = SimulatedAnnealing::new().seed(11).iterations(50);
+
+        assert_eq!(
+            annealing.optimize(&Permutation, sorted),
+            annealing.optimize(&Permutation, sorted)
+        );
+    }
+
+    #[test]
+    fn callback_stops_search() {
+        let mut calls = 0;
+        SimulatedAnnealing::new()
+            .seed(5)
+            .optimize_with(&Permutation, sorted, |_| {
+                calls += 1;
+                false
+            });
+
+        assert_eq!(1, calls);
+    }
+
+    #[cfg(feature = "quadgrams")]
+    #[test]
+    fn crack_substitution() {
+        use crate::analysis::{QuadgramScorer, SubstitutionSearch};
+        use crate::{Cipher, SimpleSubstitution};
+
+        let m = "It is a truth universally acknowledged, that a single man in possession of a \
+            good fortune, must be in want of a wife. However little known the feelings or views of \
+            such a man may be on his first entering a neighbourhood, this truth is so well fixed \
+            in the minds of the surrounding families, that he is considered the rightful property \
+            of some one or other of their daughters.";
+        let s = SimpleSubstitution::new(String::from("phqgiumeaylnofdxjkrcvstzwb"));
+        let c = s.encrypt(m).unwrap();
+
+        let scorer = QuadgramScorer::english();
+        let best = SimulatedAnnealing::new()
+            .seed(42)
+            .optimize(&SubstitutionSearch::new(&c), |p| scorer.score(p));
+
+        assert_eq!(m, best.plaintext);
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative_temperature() {
+        SimulatedAnnealing::new().temperature(-1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_restarts() {
+        SimulatedAnnealing::new().restarts(0);
+    }
+}
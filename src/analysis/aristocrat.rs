@@ -0,0 +1,352 @@
+//! Solves substitution ciphertexts that keep their word divisions by matching word patterns.
+//!
+//! In an "aristocrat" puzzle, a message is enciphered with a Simple Substitution cipher but its
+//! spaces and punctuation are kept. Each word of the ciphertext then has the same pattern of
+//! repeated letters as its plaintext: `xyzzx` can only be a word such as `level`, and `wkh` any
+//! word of three different letters. Matching the patterns against a dictionary gives a short list
+//! of candidates for each word, and since a ciphertext letter stands for the same plaintext letter
+//! throughout the message, choosing a candidate for one word constrains the choices for the rest.
+//!
+//! A backtracking search over these constraints usually finds the solution far faster than a
+//! statistical search such as a `HillClimber`, provided most of the words are in the dictionary.
+//!
+use super::assistant::pattern;
+use super::Candidate;
+use super::FrequencyTable;
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::SimpleSubstitution;
+use std::collections::HashMap;
+
+/// The greatest number of steps taken by a search, which bounds its time on a ciphertext with
+/// few words in the dictionary.
+const MAX_STEPS: usize = 1_000_000;
+
+/// The greatest number of solutions kept by a search.
+const MAX_SOLUTIONS: usize = 100;
+
+/// Solves Simple Substitution ciphertexts with word divisions from a dictionary.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug)]
+pub struct AristocratSolver {
+    patterns: HashMap<Vec<usize>, Vec<String>>,
+}
+
+impl AristocratSolver {
+    /// Initialise a solver from a dictionary of `words`.
+    ///
+    /// The case of the words is ignored, and words with symbols other than the letters `a-z` are
+    /// skipped.
+    ///
+    pub fn new<I, S>(words: I) -> AristocratSolver
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut patterns: HashMap<Vec<usize>, Vec<String>> = HashMap::new();
+        for word in words {
+            let word = word.as_ref().to_lowercase();
+            if word.is_empty() || !alphabet::STANDARD.is_valid(&word) {
+                continue;
+            }
+
+            let matches = patterns.entry(pattern(&word)).or_default();
+            if !matches.contains(&word) {
+                matches.push(word);
+            }
+        }
+
+        AristocratSolver { patterns }
+    }
+
+    /// Initialise a solver from the embedded English wordlist (`wordlist::ENGLISH_WORDS`).
+    ///
+    #[cfg(feature = "wordlist")]
+    pub fn english() -> AristocratSolver {
+        AristocratSolver::new(super::wordlist::ENGLISH_WORDS.lines())
+    }
+
+    /// Solve a `ciphertext`, returning up to `top` candidates with the highest scores.
+    ///
+    /// The key of each candidate is a key for `SimpleSubstitution::new()`. The score is the
+    /// proportion of the words of the ciphertext that decrypt to words of the dictionary, so words
+    /// missing from the dictionary (such as names) are tolerated. Candidates with equal scores are
+    /// ordered by how closely their letter frequencies match English.
+    ///
+    /// Ciphertext letters that appear only in words missing from the dictionary cannot be
+    /// resolved, and are decrypted to whichever plaintext letters are left unused. The search is
+    /// bounded, so a ciphertext with few dictionary words may not be fully searched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::AristocratSolver;
+    ///
+    /// let solver = AristocratSolver::new(vec![
+    ///     "attack", "at", "the", "east", "eats", "west", "wall", "tall",
+    /// ]);
+    /// let candidates = solver.solve("Pccpqn cei iprc tpll!", 1);
+    ///
+    /// assert_eq!("Attack the east wall!", candidates[0].plaintext);
+    /// assert_eq!(1.0, candidates[0].score);
+    /// ```
+    ///
+    pub fn solve(&self, ciphertext: &str, top: usize) -> Vec<Candidate<String>> {
+        // Count each distinct word of the ciphertext
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for word in ciphertext
+            .split(|c: char| alphabet::STANDARD.find_position(c).is_none())
+            .filter(|w| !w.is_empty())
+        {
+            *counts.entry(word.to_ascii_lowercase()).or_default() += 1;
+        }
+        let total: usize = counts.values().sum();
+        if total == 0 {
+            return Vec::new();
+        }
+
+        // Search the most constrained words first: those with the fewest candidates
+        let mut words: Vec<Word> = counts
+            .into_iter()
+            .map(|(cipher, count)| Word {
+                letters: cipher.bytes().map(|b| (b - b'a') as usize).collect(),
+                candidates: self
+                    .patterns
+                    .get(&pattern(&cipher))
+                    .map_or(&[][..], |c| &c[..]),
+                count,
+                cipher,
+            })
+            .collect();
+        words.sort_by(|a, b| {
+            a.candidates
+                .len()
+                .cmp(&b.candidates.len())
+                .then_with(|| b.letters.len().cmp(&a.letters.len()))
+                .then_with(|| a.cipher.cmp(&b.cipher))
+        });
+        let mut remaining = vec![0; words.len() + 1];
+        for i in (0..words.len()).rev() {
+            remaining[i] = remaining[i + 1] + words[i].count;
+        }
+
+        let mut search = Search {
+            words: &words,
+            remaining: &remaining,
+            mapping: [None; 26],
+            used: [false; 26],
+            steps: 0,
+            best: 0,
+            solutions: Vec::new(),
+        };
+        search.run(0, 0);
+
+        let mut candidates: Vec<(f64, Candidate<String>)> = Vec::new();
+        for mapping in search.solutions {
+            let key = complete_key(&mapping);
+            let plaintext = SimpleSubstitution::new(key.clone())
+                .decrypt(ciphertext)
+                .unwrap();
+            if candidates.iter().any(|(_, c)| c.plaintext == plaintext) {
+                continue;
+            }
+
+            let chi = FrequencyTable::letters(&plaintext)
+                .english_chi_squared()
+                .unwrap_or(f64::INFINITY);
+            candidates.push((
+                chi,
+                Candidate {
+                    key,
+                    plaintext,
+                    score: search.best as f64 / total as f64,
+                },
+            ));
+        }
+
+        candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+        candidates
+            .into_iter()
+            .map(|(_, candidate)| candidate)
+            .take(top)
+            .collect()
+    }
+}
+
+/// A distinct word of the ciphertext, with its candidate plaintexts from the dictionary.
+struct Word<'a> {
+    cipher: String,
+    letters: Vec<usize>,
+    candidates: &'a [String],
+    count: usize,
+}
+
+/// The state of a backtracking search for the substitutions of the ciphertext letters.
+struct Search<'a> {
+    words: &'a [Word<'a>],
+    /// The number of word occurrences from each word onwards.
+    remaining: &'a [usize],
+    /// The plaintext letter substituted for each ciphertext letter.
+    mapping: [Option<usize>; 26],
+    /// Whether each plaintext letter is substituted for a ciphertext letter.
+    used: [bool; 26],
+    steps: usize,
+    /// The number of word occurrences matched by the best solutions.
+    best: usize,
+    solutions: Vec<[Option<usize>; 26]>,
+}
+
+impl<'a> Search<'a> {
+    /// Choose a candidate for each word from the `i`th onwards, having matched `matched` word
+    /// occurrences so far.
+    ///
+    fn run(&mut self, i: usize, matched: usize) {
+        self.steps += 1;
+        if self.steps > MAX_STEPS || matched + self.remaining[i] < self.best {
+            return;
+        }
+
+        if i == self.words.len() {
+            if matched > self.best {
+                self.best = matched;
+                self.solutions.clear();
+            }
+            if self.solutions.len() < MAX_SOLUTIONS {
+                self.solutions.push(self.mapping);
+            }
+            return;
+        }
+
+        let word = &self.words[i];
+        for candidate in word.candidates {
+            let plain: Vec<usize> = candidate.bytes().map(|b| (b - b'a') as usize).collect();
+            let consistent =
+                word.letters
+                    .iter()
+                    .zip(&plain)
+                    .all(|(&c, &p)| match self.mapping[c] {
+                        Some(m) => m == p,
+                        None => !self.used[p],
+                    });
+            if !consistent {
+                continue;
+            }
+
+            // The word and candidate share a pattern, so each new letter is assigned once
+            let mut assigned = Vec::new();
+            for (&c, &p) in word.letters.iter().zip(&plain) {
+                if self.mapping[c].is_none() {
+                    self.mapping[c] = Some(p);
+                    self.used[p] = true;
+                    assigned.push(c);
+                }
+            }
+
+            self.run(i + 1, matched + word.count);
+
+            for c in assigned {
+                self.used[self.mapping[c].take().unwrap()] = false;
+            }
+        }
+
+        // The word may be missing from the dictionary
+        self.run(i + 1, matched);
+    }
+}
+
+/// Complete a `mapping` of ciphertext letters to plaintext letters into a key for
+/// `SimpleSubstitution::new()`, pairing the unmapped letters in alphabetical order.
+///
+fn complete_key(mapping: &[Option<usize>; 26]) -> String {
+    let mut key = [None; 26];
+    for (c, p) in mapping.iter().enumerate() {
+        if let Some(p) = p {
+            key[*p] = Some(c);
+        }
+    }
+
+    let mut unused = (0..26).filter(|c| mapping[*c].is_none());
+    key.iter()
+        .map(|c| {
+            let c = c.unwrap_or_else(|| unused.next().unwrap());
+            alphabet::STANDARD.get_letter(c, false)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "phqgiumeaylnofdxjkrcvstzwb";
+
+    fn encrypt(message: &str) -> String {
+        SimpleSubstitution::new(String::from(KEY))
+            .encrypt(message)
+            .unwrap()
+    }
+
+    #[test]
+    fn solves_with_dictionary() {
+        let m = "The quick brown fox jumps over the lazy dogs";
+        let solver = AristocratSolver::new(vec![
+            "the", "quick", "brown", "fox", "jumps", "over", "lazy", "dogs", "cat", "and", "bring",
+            "black", "jump", "ever",
+        ]);
+        let candidates = solver.solve(&encrypt(m), 3);
+
+        assert_eq!(m, candidates[0].plaintext);
+        assert_eq!(1.0, candidates[0].score);
+        assert_eq!(KEY, candidates[0].key);
+    }
+
+    #[test]
+    fn tolerates_missing_words() {
+        let m = "Meet Holmes at the station";
+        let solver = AristocratSolver::new(vec!["meet", "at", "the", "station", "as", "tea"]);
+        let candidates = solver.solve(&encrypt(m), 1);
+
+        assert_eq!(0.8, candidates[0].score);
+        assert!(candidates[0].plaintext.starts_with("Meet "));
+        assert!(candidates[0].plaintext.ends_with(" at the station"));
+    }
+
+    #[test]
+    fn complete_keys_are_valid() {
+        let mut mapping = [None; 26];
+        mapping[0] = Some(25);
+        mapping[3] = Some(0);
+
+        let key = complete_key(&mapping);
+        assert_eq!('d', key.chars().next().unwrap());
+        assert_eq!('a', key.chars().last().unwrap());
+        SimpleSubstitution::new(key);
+    }
+
+    #[test]
+    fn no_words() {
+        let solver = AristocratSolver::new(vec!["the"]);
+
+        assert!(solver.solve("123 !?", 5).is_empty());
+    }
+
+    #[test]
+    fn ignores_invalid_words() {
+        let solver = AristocratSolver::new(vec!["don't", "", "Ten", "ten"]);
+
+        assert_eq!(1, solver.patterns.len());
+        assert_eq!(vec!["ten"], solver.patterns[&vec![0, 1, 2]]);
+    }
+
+    #[cfg(feature = "wordlist")]
+    #[test]
+    fn solves_english() {
+        let m = "The programmer could not find the error in the source until she read the manual \
+                 twice, which explained exactly how the library should be used.";
+        let candidates = AristocratSolver::english().solve(&encrypt(m), 1);
+
+        assert_eq!(m, candidates[0].plaintext);
+    }
+}
@@ -0,0 +1,465 @@
+//! An assistant for solving monoalphabetic substitution ciphers by hand.
+//!
+//! Puzzles such as cryptograms are solved a letter at a time: the solver guesses a few
+//! substitutions from the evidence of the ciphertext, reads the partly decoded text, and uses the
+//! new fragments of words to guess further. The `Assistant` keeps track of the substitutions that
+//! have been confirmed, and suggests new ones from three kinds of evidence:
+//!
+//! * The frequencies of the letters, matched in rank with those of English.
+//! * Words of the ciphertext whose pattern of repeated letters (e.g. `xyzzx`) matches a common
+//!   English word, and that agree with the substitutions already made.
+//! * Common n-grams, such as a doubled letter or the most frequent trigram (usually `the`).
+//!
+//! This is the backend for an interactive solving tool, which displays the decoded text and the
+//! suggestions and lets the user confirm or reject each.
+//!
+use super::frequency::ENGLISH_LETTERS;
+use super::FrequencyTable;
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use std::collections::HashMap;
+
+/// Common English words, matched against the words of the ciphertext by their patterns.
+const COMMON_WORDS: [&str; 100] = [
+    "the", "of", "and", "to", "in", "is", "you", "that", "it", "he", "was", "for", "on", "are",
+    "as", "with", "his", "they", "at", "be", "this", "have", "from", "or", "one", "had", "by",
+    "word", "but", "not", "what", "all", "were", "we", "when", "your", "can", "said", "there",
+    "use", "an", "each", "which", "she", "do", "how", "their", "if", "will", "up", "other",
+    "about", "out", "many", "then", "them", "these", "so", "some", "her", "would", "make", "like",
+    "him", "into", "time", "has", "look", "two", "more", "write", "go", "see", "number", "no",
+    "way", "could", "people", "my", "than", "first", "water", "been", "call", "who", "its", "now",
+    "find", "long", "down", "day", "did", "get", "come", "made", "may", "part", "over", "after",
+    "little",
+];
+
+/// The letters most often doubled within English words, from the most to the least common.
+const ENGLISH_DOUBLES: [char; 5] = ['l', 'e', 's', 'o', 't'];
+
+/// The weight of the evidence from matching the rank of a letter's frequency.
+const FREQUENCY_WEIGHT: f64 = 0.25;
+
+/// The weight of the evidence from a common n-gram.
+const NGRAM_WEIGHT: f64 = 0.5;
+
+/// The weight of the evidence from a word of the ciphertext that matches a single common word.
+const WORD_WEIGHT: f64 = 1.0;
+
+/// A piece of evidence for a suggested substitution.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Evidence {
+    /// The ciphertext letter is as frequent (by rank) as the plaintext letter is in English.
+    Frequency,
+    /// A word of the ciphertext has the same pattern as a common English word.
+    Word {
+        /// The word of the ciphertext, in lowercase.
+        ciphertext: String,
+        /// The English word that it may decode to.
+        plaintext: String,
+    },
+    /// An n-gram of the ciphertext may decode to a common English n-gram.
+    Ngram {
+        /// The n-gram of the ciphertext, in lowercase.
+        ciphertext: String,
+        /// The English n-gram that it may decode to.
+        plaintext: String,
+    },
+}
+
+/// A substitution suggested by the `Assistant`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    /// The letter of the ciphertext, in lowercase.
+    pub cipher: char,
+    /// The letter of the plaintext that it is suggested to decode to, in lowercase.
+    pub plain: char,
+    /// The total weight of the evidence for the substitution. The higher the score, the more
+    /// likely the substitution is to be correct.
+    pub score: f64,
+    /// The evidence for the substitution.
+    pub evidence: Vec<Evidence>,
+}
+
+/// Assists in solving a monoalphabetic substitution ciphertext by hand.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug)]
+pub struct Assistant {
+    ciphertext: String,
+    substitutions: HashMap<char, char>,
+}
+
+impl Assistant {
+    /// Initialise an assistant for a `ciphertext`, with no substitutions made.
+    ///
+    pub fn new(ciphertext: &str) -> Assistant {
+        Assistant {
+            ciphertext: ciphertext.to_string(),
+            substitutions: HashMap::new(),
+        }
+    }
+
+    /// Substitute the letter `plain` for each `cipher` letter of the ciphertext, ignoring case.
+    ///
+    /// Any substitution already made for the `cipher` letter is replaced. Returns `Err` if either
+    /// character is not one of the letters `a-z`, or if another letter of the ciphertext is
+    /// already substituted by `plain`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::Assistant;
+    ///
+    /// let mut assistant = Assistant::new("Wkh fdw");
+    /// assistant.substitute('w', 't').unwrap();
+    /// assistant.substitute('h', 'e').unwrap();
+    ///
+    /// assert_eq!("T_e __t", assistant.decoded());
+    /// assert!(assistant.substitute('k', 't').is_err());
+    /// ```
+    ///
+    pub fn substitute(&mut self, cipher: char, plain: char) -> Result<(), &'static str> {
+        let (cipher, plain) = match (lowercase(cipher), lowercase(plain)) {
+            (Some(c), Some(p)) => (c, p),
+            _ => return Err("Substitutions can only be made between the letters a-z."),
+        };
+        if self
+            .substitutions
+            .iter()
+            .any(|(&c, &p)| p == plain && c != cipher)
+        {
+            return Err("Another letter is already substituted by this letter.");
+        }
+
+        self.substitutions.insert(cipher, plain);
+        Ok(())
+    }
+
+    /// Undo the substitution of a `cipher` letter, if one has been made.
+    ///
+    pub fn clear(&mut self, cipher: char) {
+        if let Some(cipher) = lowercase(cipher) {
+            self.substitutions.remove(&cipher);
+        }
+    }
+
+    /// The letter substituted for a `cipher` letter, if one has been made.
+    ///
+    pub fn substitution(&self, cipher: char) -> Option<char> {
+        self.substitutions.get(&lowercase(cipher)?).cloned()
+    }
+
+    /// Whether a substitution has been made for every letter of the ciphertext.
+    ///
+    pub fn is_solved(&self) -> bool {
+        self.ciphertext
+            .chars()
+            .filter_map(lowercase)
+            .all(|c| self.substitutions.contains_key(&c))
+    }
+
+    /// The ciphertext, decoded with the substitutions made so far.
+    ///
+    /// Each letter without a substitution is shown as `_`, and the case of the letters and all
+    /// other symbols are kept.
+    ///
+    pub fn decoded(&self) -> String {
+        self.ciphertext
+            .chars()
+            .map(|c| match lowercase(c) {
+                Some(l) => match self.substitutions.get(&l) {
+                    Some(&p) if c.is_uppercase() => p.to_ascii_uppercase(),
+                    Some(&p) => p,
+                    None => '_',
+                },
+                None => c,
+            })
+            .collect()
+    }
+
+    /// Suggest substitutions for the letters of the ciphertext that have none.
+    ///
+    /// Each suggestion pairs a ciphertext letter with the plaintext letter best supported by the
+    /// evidence, and only pairs that agree with the substitutions already made are suggested.
+    /// The suggestions are returned from the highest score to the lowest.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::Assistant;
+    ///
+    /// // "the" is the only common word with the pattern of "wkh" that fits
+    /// let mut assistant = Assistant::new("Wkh fdw vdw rq wkh pdw");
+    /// assistant.substitute('w', 't').unwrap();
+    ///
+    /// let suggestions = assistant.suggestions();
+    /// assert!(suggestions.iter().any(|s| s.cipher == 'k' && s.plain == 'h'));
+    /// assert!(suggestions.iter().all(|s| s.cipher != 'w' && s.plain != 't'));
+    /// ```
+    ///
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        let mut evidence: HashMap<(char, char), (f64, Vec<Evidence>)> = HashMap::new();
+        let mut add = |cipher: char, plain: char, weight: f64, e: Evidence| {
+            if self.agrees(cipher, plain) {
+                let entry = evidence.entry((cipher, plain)).or_default();
+                entry.0 += weight;
+                entry.1.push(e);
+            }
+        };
+
+        let letters: String = self.ciphertext.chars().filter_map(lowercase).collect();
+
+        // Match the unsubstituted letters by the rank of their frequencies
+        let mut english: Vec<(char, f64)> = ENGLISH_LETTERS
+            .iter()
+            .map(|&(l, f)| (l.chars().next().unwrap(), f))
+            .filter(|(l, _)| !self.substitutions.values().any(|p| p == l))
+            .collect();
+        english.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let counted = FrequencyTable::letters(&letters).most_common();
+        let unsubstituted = counted
+            .iter()
+            .map(|(l, _)| l.chars().next().unwrap())
+            .filter(|l| !self.substitutions.contains_key(l));
+        for (cipher, &(plain, _)) in unsubstituted.zip(&english) {
+            add(cipher, plain, FREQUENCY_WEIGHT, Evidence::Frequency);
+        }
+
+        // Match the patterns of the words against common English words
+        let mut words: Vec<String> = self
+            .ciphertext
+            .split(|c: char| lowercase(c).is_none())
+            .filter(|w| !w.is_empty())
+            .map(str::to_ascii_lowercase)
+            .collect();
+        words.sort();
+        words.dedup();
+        for word in &words {
+            let matches: Vec<&str> = COMMON_WORDS
+                .iter()
+                .chain(["a", "i"].iter())
+                .filter(|w| self.fits(word, w))
+                .cloned()
+                .collect();
+            for plain_word in &matches {
+                for (c, p) in word.chars().zip(plain_word.chars()) {
+                    add(
+                        c,
+                        p,
+                        WORD_WEIGHT / matches.len() as f64,
+                        Evidence::Word {
+                            ciphertext: word.clone(),
+                            plaintext: plain_word.to_string(),
+                        },
+                    );
+                }
+            }
+        }
+
+        // Doubled letters, in order of how often they appear
+        let chars: Vec<char> = letters.chars().collect();
+        let mut doubles = FrequencyTable::bigrams(&letters).most_common();
+        doubles.retain(|(g, _)| g.as_bytes()[0] == g.as_bytes()[1]);
+        for ((double, _), &plain) in doubles.iter().zip(&ENGLISH_DOUBLES) {
+            let cipher = double.chars().next().unwrap();
+            add(
+                cipher,
+                plain,
+                NGRAM_WEIGHT,
+                Evidence::Ngram {
+                    ciphertext: double.clone(),
+                    plaintext: format!("{}{}", plain, plain),
+                },
+            );
+        }
+
+        // The most common trigram of English is "the"
+        if chars.len() >= 3 {
+            let trigrams = FrequencyTable::trigrams(&letters).most_common();
+            let (trigram, count) = &trigrams[0];
+            if *count > 1 && self.fits(trigram, "the") {
+                for (c, p) in trigram.chars().zip("the".chars()) {
+                    add(
+                        c,
+                        p,
+                        NGRAM_WEIGHT,
+                        Evidence::Ngram {
+                            ciphertext: trigram.clone(),
+                            plaintext: String::from("the"),
+                        },
+                    );
+                }
+            }
+        }
+
+        // Keep the best supported plaintext letter for each ciphertext letter
+        let mut best: HashMap<char, Suggestion> = HashMap::new();
+        for ((cipher, plain), (score, evidence)) in evidence {
+            if best
+                .get(&cipher)
+                .is_none_or(|s| score > s.score || (score == s.score && plain < s.plain))
+            {
+                best.insert(
+                    cipher,
+                    Suggestion {
+                        cipher,
+                        plain,
+                        score,
+                        evidence,
+                    },
+                );
+            }
+        }
+
+        let mut suggestions: Vec<Suggestion> = best.into_values().collect();
+        suggestions.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.cipher.cmp(&b.cipher))
+        });
+
+        suggestions
+    }
+
+    /// Whether substituting `plain` for `cipher` agrees with the substitutions already made, and
+    /// is not already made.
+    ///
+    fn agrees(&self, cipher: char, plain: char) -> bool {
+        !self.substitutions.contains_key(&cipher)
+            && !self.substitutions.values().any(|&p| p == plain)
+    }
+
+    /// Whether the ciphertext `word` could decode to the `plain` word: they have the same pattern
+    /// of repeated letters, and agree with the substitutions already made.
+    ///
+    fn fits(&self, word: &str, plain: &str) -> bool {
+        word.len() == plain.len()
+            && pattern(word) == pattern(plain)
+            && word
+                .chars()
+                .zip(plain.chars())
+                .all(|(c, p)| match self.substitutions.get(&c) {
+                    Some(&s) => s == p,
+                    None => !self.substitutions.values().any(|&s| s == p),
+                })
+    }
+}
+
+/// The pattern of repeated letters of a `word`, as the index of the first appearance of each of
+/// its letters (e.g. `[0, 1, 2, 2, 0]` for `xyzzx`).
+///
+pub(super) fn pattern(word: &str) -> Vec<usize> {
+    let chars: Vec<char> = word.chars().collect();
+    chars
+        .iter()
+        .map(|c| chars.iter().position(|d| d == c).unwrap())
+        .collect()
+}
+
+/// The lowercase form of `c` if it is one of the letters `a-z`.
+///
+fn lowercase(c: char) -> Option<char> {
+    alphabet::STANDARD
+        .find_position(c)
+        .map(|i| alphabet::STANDARD.get_letter(i, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cipher, SimpleSubstitution};
+
+    const PLAINTEXT: &str = "It is a truth universally acknowledged, that a single man in \
+        possession of a good fortune, must be in want of a wife.";
+
+    fn ciphertext() -> String {
+        SimpleSubstitution::new(String::from("phqgiumeaylnofdxjkrcvstzwb"))
+            .encrypt(PLAINTEXT)
+            .unwrap()
+    }
+
+    #[test]
+    fn substitutions() {
+        let mut assistant = Assistant::new("Abc, cab!");
+        assert_eq!("___, ___!", assistant.decoded());
+
+        assistant.substitute('A', 'x').unwrap();
+        assistant.substitute('c', 'z').unwrap();
+        assert_eq!("X_z, zx_!", assistant.decoded());
+        assert_eq!(Some('x'), assistant.substitution('a'));
+
+        assistant.substitute('a', 'y').unwrap();
+        assert_eq!("Y_z, zy_!", assistant.decoded());
+
+        assistant.clear('c');
+        assert_eq!(None, assistant.substitution('c'));
+        assert!(!assistant.is_solved());
+    }
+
+    #[test]
+    fn invalid_substitutions() {
+        let mut assistant = Assistant::new("abc");
+
+        assert!(assistant.substitute('1', 'a').is_err());
+        assert!(assistant.substitute('a', '🗡').is_err());
+
+        assistant.substitute('a', 'q').unwrap();
+        assert!(assistant.substitute('b', 'q').is_err());
+    }
+
+    #[test]
+    fn suggestions_ranked() {
+        let suggestions = Assistant::new(&ciphertext()).suggestions();
+
+        assert!(!suggestions.is_empty());
+        assert!(suggestions.windows(2).all(|w| w[0].score >= w[1].score));
+        // Each ciphertext letter and plaintext letter is suggested at most once per letter
+        let mut ciphers: Vec<char> = suggestions.iter().map(|s| s.cipher).collect();
+        ciphers.dedup();
+        assert_eq!(suggestions.len(), ciphers.len());
+    }
+
+    #[test]
+    fn single_letter_words() {
+        // The word "a" is encrypted to "p"
+        let suggestions = Assistant::new(&ciphertext()).suggestions();
+        let p = suggestions.iter().find(|s| s.cipher == 'p').unwrap();
+
+        assert!(p.plain == 'a' || p.plain == 'i');
+        assert!(p
+            .evidence
+            .iter()
+            .any(|e| matches!(e, Evidence::Word { .. })));
+    }
+
+    #[test]
+    fn solve_by_suggestions() {
+        // Accepting the best suggestion in turn, correcting any that are wrong, solves the text
+        let key = "phqgiumeaylnofdxjkrcvstzwb";
+        let mut assistant = Assistant::new(&ciphertext());
+        let mut correct = 0;
+        while !assistant.is_solved() {
+            let best = assistant.suggestions()[0].clone();
+            let plain = (b'a' + key.find(best.cipher).unwrap() as u8) as char;
+            if best.plain == plain {
+                correct += 1;
+            }
+            if let Some(c) = "abcdefghijklmnopqrstuvwxyz"
+                .chars()
+                .find(|&c| assistant.substitution(c) == Some(plain))
+            {
+                assistant.clear(c);
+            }
+            assistant.substitute(best.cipher, plain).unwrap();
+        }
+
+        assert_eq!(PLAINTEXT, assistant.decoded());
+        assert!(correct > 0);
+    }
+
+    #[test]
+    fn word_patterns() {
+        assert_eq!(vec![0, 1, 2, 2, 0], pattern("xyzzx"));
+        assert_eq!(pattern("that"), pattern("wxyw"));
+        assert_ne!(pattern("the"), pattern("see"));
+    }
+}
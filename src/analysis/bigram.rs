@@ -0,0 +1,267 @@
+//! Bigram statistics are a fast fitness function for the attacks on transposition ciphers.
+//!
+//! A transposition cipher keeps the letters of the plaintext, so statistics of single letters
+//! cannot tell its candidate plaintexts apart. What a transposition destroys is the order of the
+//! letters, and the pairs of adjacent letters (the bigrams) are the cheapest statistic that
+//! measures it. Scoring a candidate by the log probabilities of its bigrams needs only a table of
+//! `26 * 26` entries, so is much faster than quadgram statistics when evaluating the many
+//! thousands of column orderings of a transposition.
+//!
+//! The columns of a columnar transposition can also be fitted together a pair at a time: the
+//! column that follows another in the plaintext forms likely bigrams with it on every row.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+
+/// The counts of the bigrams of the embedded English corpus. Each line holds a bigram in
+/// uppercase and the number of times it appears, separated by a space.
+///
+/// The counts are those of the first two letters of the quadgrams of the same corpus (see
+/// `quadgram::ENGLISH_QUADGRAMS`), so are available without the `quadgrams` feature. Unlike
+/// `frequency::ENGLISH_BIGRAMS`, they cover every bigram seen in the corpus.
+pub const ENGLISH_BIGRAM_COUNTS: &str = include_str!("english_bigrams.txt");
+
+/// The number of possible bigrams of the letters `a-z`.
+const BIGRAMS: usize = 26 * 26;
+
+/// Scores candidate plaintexts by the log-likelihood of their bigrams.
+///
+/// This struct is created by the `new()` or `english()` methods. See their documentation for
+/// more.
+#[derive(Clone, Debug)]
+pub struct BigramScorer {
+    log_probabilities: Vec<f64>,
+}
+
+impl BigramScorer {
+    /// Initialise a scorer from the `counts` of the bigrams of a corpus.
+    ///
+    /// The case of the bigrams is ignored. Bigrams that are not counted are given a floor
+    /// probability of `0.01 / total`.
+    ///
+    /// # Panics
+    /// * A bigram is not made of two of the letters `a-z`.
+    /// * The total of the `counts` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::BigramScorer;
+    ///
+    /// let scorer = BigramScorer::new(vec![("th", 9), ("he", 1)]);
+    /// assert!(scorer.score("the") > scorer.score("het"));
+    /// ```
+    ///
+    pub fn new<I, S>(counts: I) -> BigramScorer
+    where
+        I: IntoIterator<Item = (S, usize)>,
+        S: AsRef<str>,
+    {
+        let mut bigram_counts = vec![0; BIGRAMS];
+        for (bigram, count) in counts {
+            let letters: Vec<usize> = bigram
+                .as_ref()
+                .chars()
+                .map(|c| alphabet::STANDARD.find_position(c))
+                .collect::<Option<Vec<usize>>>()
+                .filter(|l| l.len() == 2)
+                .expect("A bigram must be made of two of the letters a-z.");
+            bigram_counts[letters[0] * 26 + letters[1]] += count;
+        }
+
+        let total: usize = bigram_counts.iter().sum();
+        if total == 0 {
+            panic!("The total of the bigram counts must be greater than 0.");
+        }
+
+        let floor = (0.01 / total as f64).log10();
+        BigramScorer {
+            log_probabilities: bigram_counts
+                .into_iter()
+                .map(|count| match count {
+                    0 => floor,
+                    c => (c as f64 / total as f64).log10(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Initialise a scorer from the embedded `ENGLISH_BIGRAM_COUNTS`.
+    ///
+    pub fn english() -> BigramScorer {
+        BigramScorer::new(ENGLISH_BIGRAM_COUNTS.lines().map(|line| {
+            let mut parts = line.split(' ');
+            let bigram = parts.next().unwrap();
+            let count = parts.next().unwrap().parse().unwrap();
+
+            (bigram, count)
+        }))
+    }
+
+    /// Score a candidate `text` by the sum of the log (base 10) probabilities of its bigrams.
+    ///
+    /// Only the letters `a-z` of the text are scored, ignoring case. The score is 0 for a text of
+    /// fewer than two letters, and is otherwise negative. As longer texts have lower scores, only
+    /// the scores of texts of the same length should be compared.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::BigramScorer;
+    /// use cipher_crypt::{Cipher, Railfence};
+    ///
+    /// let scorer = BigramScorer::english();
+    /// let c = Railfence::new(3).encrypt("We are discovered, flee at once").unwrap();
+    ///
+    /// let best = (1..=10)
+    ///     .max_by(|&a, &b| {
+    ///         let score = |rails| scorer.score(&Railfence::new(rails).decrypt(&c).unwrap());
+    ///         score(a).total_cmp(&score(b))
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(3, best);
+    /// ```
+    ///
+    pub fn score(&self, text: &str) -> f64 {
+        let letters: Vec<usize> = text
+            .chars()
+            .filter_map(|c| alphabet::STANDARD.find_position(c))
+            .collect();
+
+        letters
+            .windows(2)
+            .map(|b| self.log_probabilities[b[0] * 26 + b[1]])
+            .sum()
+    }
+
+    /// The log (base 10) probability of the bigram of the letters at the indices `first` and
+    /// `second` within `a-z`.
+    ///
+    pub(super) fn log_probability(&self, first: usize, second: usize) -> f64 {
+        self.log_probabilities[first * 26 + second]
+    }
+
+    /// Score how well the `right` column of a transposition follows the `left` column, by the sum
+    /// of the log (base 10) probabilities of the bigrams formed across each row.
+    ///
+    /// The columns are given as the letters from their top to bottom, and only the letters `a-z`
+    /// are scored, ignoring case. Where one column is longer than the other, its extra letters
+    /// are ignored. The higher the score, the more likely the `right` column is to follow the
+    /// `left` in the plaintext.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::BigramScorer;
+    ///
+    /// // The columns of "the cat sat" written in rows of three
+    /// let scorer = BigramScorer::english();
+    /// let columns = ["tcs", "haa", "ett"];
+    ///
+    /// let forwards = scorer.column_fit(columns[0], columns[1]);
+    /// assert!(forwards > scorer.column_fit(columns[1], columns[0]));
+    /// ```
+    ///
+    pub fn column_fit(&self, left: &str, right: &str) -> f64 {
+        let letters = |column: &str| -> Vec<usize> {
+            column
+                .chars()
+                .filter_map(|c| alphabet::STANDARD.find_position(c))
+                .collect()
+        };
+
+        letters(left)
+            .into_iter()
+            .zip(letters(right))
+            .map(|(l, r)| self.log_probabilities[l * 26 + r])
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_prefers_plaintext() {
+        let scorer = BigramScorer::english();
+
+        assert!(
+            scorer.score("Defend the east wall of the castle")
+                > scorer.score("Dfn h atwl fte ateedtees aloh csl")
+        );
+    }
+
+    #[test]
+    fn ignores_case_and_symbols() {
+        let scorer = BigramScorer::english();
+        assert_eq!(
+            scorer.score("attackatdawn"),
+            scorer.score("Attack at dawn! 🗡️")
+        );
+    }
+
+    #[test]
+    fn short_text() {
+        assert_eq!(0.0, BigramScorer::english().score("a"));
+    }
+
+    #[test]
+    fn floor_probability() {
+        let scorer = BigramScorer::new(vec![("TH", 100)]);
+
+        assert_eq!(0.0, scorer.score("th"));
+        assert_eq!(-4.0, scorer.score("qx"));
+    }
+
+    #[test]
+    fn orders_columns() {
+        // Write the plaintext in rows of five, and read off the columns
+        let scorer = BigramScorer::english();
+        let m: Vec<char> =
+            "It was the best of times, it was the worst of times, it was the age of \
+            wisdom, it was the age of foolishness"
+                .chars()
+                .filter(char::is_ascii_alphabetic)
+                .collect();
+        let columns: Vec<String> = (0..5)
+            .map(|c| m.iter().skip(c).step_by(5).collect())
+            .collect();
+
+        // Chain the columns greedily, from the first
+        let mut order = vec![0];
+        while order.len() < columns.len() {
+            let last = *order.last().unwrap();
+            let next = (0..columns.len())
+                .filter(|c| !order.contains(c))
+                .max_by(|&a, &b| {
+                    scorer
+                        .column_fit(&columns[last], &columns[a])
+                        .total_cmp(&scorer.column_fit(&columns[last], &columns[b]))
+                })
+                .unwrap();
+            order.push(next);
+        }
+
+        assert_eq!(vec![0, 1, 2, 3, 4], order);
+    }
+
+    #[test]
+    fn uneven_columns() {
+        let scorer = BigramScorer::new(vec![("th", 1)]);
+
+        assert_eq!(0.0, scorer.column_fit("tt", "h"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_bigram() {
+        BigramScorer::new(vec![("t", 1)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_counts() {
+        BigramScorer::new(Vec::<(&str, usize)>::new());
+    }
+}
@@ -0,0 +1,162 @@
+//! An exhaustive search of the keys of a cipher.
+//!
+//! Where a cipher has few enough keys to try each in turn (see the iterators of `keyspace`), the
+//! most likely plaintexts can be found by decrypting the ciphertext with every key and keeping
+//! those that score best. With the `parallel` feature enabled, the keys are shared out across
+//! threads.
+//!
+use super::Candidate;
+use crate::common::cipher::Cipher;
+#[cfg(feature = "parallel")]
+use rayon::iter::{ParallelBridge, ParallelIterator};
+
+/// Decrypt a `ciphertext` with each of the `keys` of a cipher, returning the `top` candidates
+/// with the highest `score`.
+///
+/// The candidates are returned from the highest score to the lowest. Keys that fail to decrypt
+/// the ciphertext are skipped. Only the best `top` candidates are kept as the keys are tried, so
+/// the search can cover many more keys than could be held in memory at once.
+///
+/// With the `parallel` feature enabled, the keys are decrypted and scored on the threads of the
+/// global `rayon` thread pool.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::{brute_force, keyspace, FrequencyTable};
+/// use cipher_crypt::{Affine, AffineKey};
+///
+/// let score = |m: &str| -FrequencyTable::letters(m).english_chi_squared().unwrap();
+/// let candidates = brute_force::<Affine, _, _>(
+///     keyspace::affine(),
+///     "Hmmhnl hm qhvu, hzhfujm mct thjm vhoo!",
+///     score,
+///     3,
+/// );
+///
+/// assert_eq!(3, candidates.len());
+/// assert_eq!(AffineKey { a: 3, b: 7 }, candidates[0].key);
+/// assert_eq!("Attack at dawn, against the east wall!", candidates[0].plaintext);
+/// ```
+///
+pub fn brute_force<C, I, F>(
+    keys: I,
+    ciphertext: &str,
+    score: F,
+    top: usize,
+) -> Vec<Candidate<C::Key>>
+where
+    C: Cipher<Algorithm = C>,
+    C::Key: Clone + Send,
+    I: Iterator<Item = C::Key> + Send,
+    F: Fn(&str) -> f64 + Sync,
+{
+    let decrypt = |key: C::Key| {
+        let plaintext = C::new(key.clone()).decrypt(ciphertext).ok()?;
+        Some(Candidate {
+            score: score(&plaintext),
+            key,
+            plaintext,
+        })
+    };
+
+    #[cfg(feature = "parallel")]
+    let best = keys
+        .par_bridge()
+        .filter_map(decrypt)
+        .fold(Vec::new, |best, candidate| keep(best, candidate, top))
+        .reduce(Vec::new, |best, other| {
+            other
+                .into_iter()
+                .fold(best, |best, candidate| keep(best, candidate, top))
+        });
+
+    #[cfg(not(feature = "parallel"))]
+    let best = keys
+        .filter_map(decrypt)
+        .fold(Vec::new(), |best, candidate| keep(best, candidate, top));
+
+    best
+}
+
+/// Insert a `candidate` into a list of the `best` candidates (ordered from the highest score to
+/// the lowest), keeping no more than `top` of them.
+///
+fn keep<K>(mut best: Vec<Candidate<K>>, candidate: Candidate<K>, top: usize) -> Vec<Candidate<K>> {
+    let position = best
+        .iter()
+        .position(|b| candidate.score > b.score)
+        .unwrap_or(best.len());
+    if position < top {
+        best.insert(position, candidate);
+        best.truncate(top);
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::frequency::ENGLISH_BIGRAMS;
+    use crate::analysis::{keyspace, FrequencyTable};
+    use crate::{Caesar, Railfence};
+
+    fn english(m: &str) -> f64 {
+        -FrequencyTable::letters(m).english_chi_squared().unwrap()
+    }
+
+    #[test]
+    fn ordered_top() {
+        let c = Caesar::new(9)
+            .encrypt("Defend the east wall of the castle")
+            .unwrap();
+        let candidates = brute_force::<Caesar, _, _>(keyspace::caesar(), &c, english, 5);
+
+        assert_eq!(5, candidates.len());
+        assert_eq!(9, candidates[0].key);
+        assert_eq!(
+            "Defend the east wall of the castle",
+            candidates[0].plaintext
+        );
+        assert!(candidates.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn top_larger_than_keyspace() {
+        let candidates = brute_force::<Caesar, _, _>(keyspace::caesar(), "abc", english, 100);
+
+        assert_eq!(26, candidates.len());
+    }
+
+    #[test]
+    fn top_zero() {
+        assert!(brute_force::<Caesar, _, _>(keyspace::caesar(), "abc", english, 0).is_empty());
+    }
+
+    #[test]
+    fn transposition() {
+        let m = "We are discovered, flee at once";
+        let c = Railfence::new(4).encrypt(m).unwrap();
+        let bigrams = |m: &str| FrequencyTable::bigrams(m).log_likelihood(&ENGLISH_BIGRAMS);
+        let candidates = brute_force::<Railfence, _, _>(keyspace::railfence(10), &c, bigrams, 1);
+
+        assert_eq!(4, candidates[0].key);
+        assert_eq!(m, candidates[0].plaintext);
+    }
+
+    #[test]
+    fn keep_orders_candidates() {
+        let best = [3.0, 1.0, 2.0, 5.0, 4.0]
+            .iter()
+            .map(|&score| Candidate {
+                key: (),
+                plaintext: String::new(),
+                score,
+            })
+            .fold(Vec::new(), |best, candidate| keep(best, candidate, 3));
+        let scores: Vec<f64> = best.iter().map(|c| c.score).collect();
+
+        assert_eq!(vec![5.0, 4.0, 3.0], scores);
+    }
+}
@@ -0,0 +1,1013 @@
+//! Brute-force solvers for ciphers with a small number of possible keys.
+//!
+//! Each solver decrypts the ciphertext with every key, scores how closely each candidate
+//! plaintext resembles English, and returns the candidates from the most to the least likely.
+//!
+//! Where the cipher is unknown, `auto_decrypt` identifies it and runs the appropriate attacks in
+//! a single call.
+//!
+use super::frequency::{ENGLISH_BIGRAMS, ENGLISH_LETTERS};
+#[cfg(feature = "quadgrams")]
+use super::QuadgramScorer;
+use super::{brute_force, friedman, identify, keyspace, BigramScorer, FrequencyTable};
+use super::{HillClimber, Optimizer, SubstitutionSearch};
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::registry;
+use crate::{
+    Affine, Beaufort, Caesar, CipherKind, Gronsfeld, Hill, Keyword, Porta, Railfence, Scytale,
+    SimpleSubstitution, Vigenere,
+};
+use rulinalg::matrix::Matrix;
+use std::any::TypeId;
+
+/// The key symbols of the ciphers whose keys are made of the letters `a-z`.
+const STANDARD_SYMBOLS: &str = "abcdefghijklmnopqrstuvwxyz";
+
+/// A candidate plaintext recovered by a solver, along with the key that produced it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candidate<K> {
+    /// The key that decrypts the ciphertext to the `plaintext`.
+    pub key: K,
+    /// The ciphertext decrypted with the `key`.
+    pub plaintext: String,
+    /// How closely the `plaintext` resembles English. The higher the score, the more likely the
+    /// candidate is to be correct.
+    pub score: f64,
+}
+
+/// Crack a Caesar cipher by trying each of its 26 shifts.
+///
+/// Each candidate is scored by the chi-squared statistic of its letters against those expected
+/// of English, negated so that higher scores are better. The candidates are returned from the
+/// highest score to the lowest, and the `key` of each is the shift of the Caesar cipher.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::crack_caesar;
+///
+/// let candidates = crack_caesar("Vhqg pruh wurrsv wr wkh iurqw olqh!");
+///
+/// assert_eq!(3, candidates[0].key);
+/// assert_eq!("Send more troops to the front line!", candidates[0].plaintext);
+/// ```
+///
+pub fn crack_caesar(ciphertext: &str) -> Vec<Candidate<usize>> {
+    rank(
+        (1..=26).map(|shift| (shift, Caesar::new(shift).decrypt(ciphertext).unwrap())),
+        |plaintext| {
+            -FrequencyTable::letters(plaintext)
+                .english_chi_squared()
+                .unwrap()
+        },
+    )
+}
+
+/// Crack a Railfence cipher by trying each number of rails up to `max_rails`.
+///
+/// As a Railfence cipher with a single rail, or with at least as many rails as there are
+/// characters in the ciphertext, leaves it unchanged, the rails from 2 up to the smaller of
+/// `max_rails` and one less than the length of the ciphertext are tried. As the cipher only
+/// rearranges the letters, each candidate is scored by the log likelihood of its bigrams given
+/// those expected of English. The candidates are returned from the highest score to the lowest,
+/// and the `key` of each is the number of rails.
+///
+/// As each decryption takes time proportional to the number of rails multiplied by the length of
+/// the ciphertext, `max_rails` should be kept small for long ciphertexts (as for
+/// `keyspace::railfence`). An empty `Vec` is returned if there are no rails to try.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::crack_railfence;
+///
+/// let candidates = crack_railfence("W vfteedoe la e ricrde ocaseen", 10);
+///
+/// assert_eq!(4, candidates[0].key);
+/// assert_eq!("We are discovered flee at once", candidates[0].plaintext);
+/// ```
+///
+pub fn crack_railfence(ciphertext: &str, max_rails: usize) -> Vec<Candidate<usize>> {
+    let most = max_rails.min(ciphertext.chars().count().saturating_sub(1));
+    rank(
+        (2..=most).map(|rails| (rails, Railfence::new(rails).decrypt(ciphertext).unwrap())),
+        |plaintext| FrequencyTable::bigrams(plaintext).log_likelihood(&ENGLISH_BIGRAMS),
+    )
+}
+
+/// Crack a Scytale cipher by trying each height of cylinder up to `max_height`.
+///
+/// As a cylinder of height 1, or one at least as tall as the ciphertext is long, leaves it
+/// unchanged, the heights from 2 up to the smaller of `max_height` and one less than the length
+/// of the ciphertext are tried. As with `crack_railfence`, each candidate is scored by the log
+/// likelihood of its bigrams given those expected of English. The candidates are returned from
+/// the highest score to the lowest, and the `key` of each is the height of the cylinder. Where
+/// several heights decrypt the ciphertext to the same plaintext, only the smallest of them is
+/// returned. An empty `Vec` is returned if there are no heights to try.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::crack_scytale;
+/// use cipher_crypt::{Cipher, Scytale};
+///
+/// let m = "The enemy will attack the northern gate at dawn tomorrow";
+/// let c = Scytale::new(5).encrypt(m).unwrap();
+///
+/// let candidates = crack_scytale(&c, 10);
+/// assert_eq!(m, candidates[0].plaintext);
+/// ```
+///
+pub fn crack_scytale(ciphertext: &str, max_height: usize) -> Vec<Candidate<usize>> {
+    let most = max_height.min(ciphertext.chars().count().saturating_sub(1));
+    let mut decryptions: Vec<(usize, String)> = Vec::new();
+    for height in 2..=most {
+        // The plaintext is only copied once it is known not to be a duplicate
+        let plaintext = Scytale::new(height).decrypt_cow(ciphertext).unwrap();
+        if decryptions.iter().all(|(_, p)| *p != plaintext) {
+            decryptions.push((height, plaintext.into_owned()));
+        }
+    }
+
+    rank(decryptions.into_iter(), |plaintext| {
+        FrequencyTable::bigrams(plaintext).log_likelihood(&ENGLISH_BIGRAMS)
+    })
+}
+
+/// Crack a Hill cipher with a 2 x 2 key matrix by trying each of its 157,248 keys, returning the
+/// `top` candidates with the highest scores.
+///
+/// Every invertible matrix is tried as the inverse of the key, and each decryption is scored by
+/// `BigramScorer::english()`. As the first letter of each pair of the plaintext depends only on
+/// the first row of the inverse, and the second letter only on the second row, the letters given
+/// by each of the 676 possible rows are found once and shared between the matrices. The `key` of
+/// each candidate is the key matrix of the cipher, as given to `Hill::new()`.
+///
+/// The ciphertext must be as given by `Hill::encrypt()`: an even number of the letters `a-z`.
+/// Returns an empty `Vec` otherwise.
+///
+/// # Example
+///
+/// ```
+/// extern crate rulinalg;
+/// extern crate cipher_crypt;
+///
+/// use cipher_crypt::analysis::crack_hill2;
+/// use cipher_crypt::{Cipher, Hill};
+/// use rulinalg::matrix::Matrix;
+///
+/// fn main() {
+///     let key = Matrix::new(2, 2, vec![3, 3, 2, 5]);
+///     let c = Hill::new(key.clone())
+///         .encrypt("wemustholdthebridgeuntilthereinforcementsarrivetomorrowmorning")
+///         .unwrap();
+///
+///     let candidates = crack_hill2(&c, 1);
+///     assert_eq!(key, candidates[0].key);
+/// }
+/// ```
+///
+pub fn crack_hill2(ciphertext: &str, top: usize) -> Vec<Candidate<Matrix<isize>>> {
+    let letters: Vec<isize> = ciphertext
+        .chars()
+        .map(|c| alphabet::STANDARD.find_position(c).map(|l| l as isize))
+        .collect::<Option<Vec<isize>>>()
+        .unwrap_or_default();
+    if letters.is_empty() || !letters.len().is_multiple_of(2) {
+        return Vec::new();
+    }
+
+    // The letters decrypted by each row `(x, y)` of the inverse matrix, at index `x * 26 + y`
+    let rows: Vec<Vec<usize>> = (0..26 * 26)
+        .map(|row| {
+            let (x, y) = (row / 26, row % 26);
+            letters
+                .chunks_exact(2)
+                .map(|pair| ((x * pair[0] + y * pair[1]) % 26) as usize)
+                .collect()
+        })
+        .collect();
+
+    let scorer = BigramScorer::english();
+    let mut scores: Vec<(f64, Matrix<isize>)> = keyspace::hill2()
+        .map(|inverse| {
+            let first = &rows[(inverse[[0, 0]] * 26 + inverse[[0, 1]]) as usize];
+            let second = &rows[(inverse[[1, 0]] * 26 + inverse[[1, 1]]) as usize];
+            let within: f64 = first
+                .iter()
+                .zip(second)
+                .map(|(&a, &b)| scorer.log_probability(a, b))
+                .sum();
+            let across: f64 = second
+                .iter()
+                .zip(&first[1..])
+                .map(|(&a, &b)| scorer.log_probability(a, b))
+                .sum();
+
+            (within + across, inverse)
+        })
+        .collect();
+    scores.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    scores
+        .into_iter()
+        .take(top)
+        .map(|(score, inverse)| {
+            let key = invert2(&inverse);
+            Candidate {
+                plaintext: Hill::new(key.clone()).decrypt(ciphertext).unwrap(),
+                key,
+                score,
+            }
+        })
+        .collect()
+}
+
+/// The inverse (modulo 26) of an invertible 2 x 2 `matrix`.
+///
+fn invert2(matrix: &Matrix<isize>) -> Matrix<isize> {
+    let (a, b, c, d) = (
+        matrix[[0, 0]],
+        matrix[[0, 1]],
+        matrix[[1, 0]],
+        matrix[[1, 1]],
+    );
+    let det = (a * d - b * c).rem_euclid(26);
+    let inverse_det = (1..26).find(|i| det * i % 26 == 1).unwrap();
+
+    Matrix::new(
+        2,
+        2,
+        [d, -b, -c, a]
+            .iter()
+            .map(|e| (e * inverse_det).rem_euclid(26))
+            .collect::<Vec<isize>>(),
+    )
+}
+
+/// Crack a periodic polyalphabetic cipher, given the rule by which its letters are decrypted.
+///
+/// The `decrypt(ci, ki)` rule gives the index of the plaintext letter within `a-z`. Where:
+/// * `ci` is the index of the ciphertext letter within `a-z`.
+/// * `ki` is the index of the key symbol within the `key_symbols`.
+///
+/// This allows any cipher that uses a table of alphabets in turn, such as the Vigenère, Beaufort,
+/// Variant Beaufort, Gronsfeld and Porta ciphers, to be attacked in the same way. The length of
+/// the key is estimated with the Friedman test. The letters of the ciphertext are then split into
+/// a column for each symbol of the key, and each column is solved by the key symbol whose
+/// decryption best matches the letter frequencies of English.
+///
+/// Returns `None` if the length of the key cannot be estimated. Otherwise, the `key` of the
+/// candidate is made of the `key_symbols`, and its score is the chi-squared statistic of its
+/// letters against those expected of English, negated so that higher scores are better. As the
+/// attack is statistical, the longer the ciphertext, the more likely it is to succeed.
+///
+/// # Panics
+/// * The `key_symbols` are empty.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::crack_periodic;
+/// use cipher_crypt::{Beaufort, Cipher};
+///
+/// let m = "It is a truth universally acknowledged, that a single man in possession of a good \
+///     fortune, must be in want of a wife. However little known the feelings or views of such a \
+///     man may be on his first entering a neighbourhood, this truth is so well fixed in the \
+///     minds of the surrounding families, that he is considered the rightful property of some \
+///     one or other of their daughters.";
+/// let ciphertext = Beaufort::new(String::from("darcy")).encrypt(m).unwrap();
+///
+/// // The Beaufort cipher decrypts a letter by subtracting it from the key letter
+/// let candidate = crack_periodic(&ciphertext, "abcdefghijklmnopqrstuvwxyz", |ci, ki| {
+///     (26 + ki - ci) % 26
+/// })
+/// .unwrap();
+///
+/// assert_eq!("darcy", candidate.key);
+/// assert_eq!(m, candidate.plaintext);
+/// ```
+///
+pub fn crack_periodic<F>(
+    ciphertext: &str,
+    key_symbols: &str,
+    decrypt: F,
+) -> Option<Candidate<String>>
+where
+    F: Fn(usize, usize) -> usize,
+{
+    let symbols: Vec<char> = key_symbols.chars().collect();
+    if symbols.is_empty() {
+        panic!("There must be at least one key symbol.");
+    }
+
+    let period = friedman(ciphertext)?.length;
+    let letters: Vec<usize> = ciphertext
+        .chars()
+        .filter_map(|c| alphabet::STANDARD.find_position(c))
+        .collect();
+
+    // Solve each column by the key symbol that best decrypts it to English letter frequencies
+    let key: Vec<usize> = (0..period)
+        .map(|column| {
+            (0..symbols.len())
+                .map(|ki| {
+                    let decrypted: String = letters
+                        .iter()
+                        .skip(column)
+                        .step_by(period)
+                        .map(|&ci| alphabet::STANDARD.get_letter(decrypt(ci, ki), false))
+                        .collect();
+
+                    (
+                        ki,
+                        FrequencyTable::letters(&decrypted).chi_squared(&ENGLISH_LETTERS),
+                    )
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap()
+                .0
+        })
+        .collect();
+
+    let plaintext = decrypt_periodic(ciphertext, &key, &decrypt);
+
+    Some(Candidate {
+        key: key.iter().map(|&k| symbols[k]).collect(),
+        score: -FrequencyTable::letters(&plaintext).chi_squared(&ENGLISH_LETTERS),
+        plaintext,
+    })
+}
+
+/// Decrypt a `ciphertext` with a periodic `key` (of indices into the key symbols), using the
+/// `decrypt(ci, ki)` rule of `crack_periodic`. Symbols other than the letters `a-z` are left
+/// unchanged, and do not use up a symbol of the key.
+///
+pub(super) fn decrypt_periodic<F>(ciphertext: &str, key: &[usize], decrypt: F) -> String
+where
+    F: Fn(usize, usize) -> usize,
+{
+    let mut ki = key.iter().cycle();
+    ciphertext
+        .chars()
+        .map(|c| match alphabet::STANDARD.find_position(c) {
+            Some(ci) => {
+                alphabet::STANDARD.get_letter(decrypt(ci, *ki.next().unwrap()), c.is_uppercase())
+            }
+            None => c,
+        })
+        .collect()
+}
+
+/// The rules by which the periodic ciphers of the tabula recta (and the Porta table) decrypt each
+/// letter, as found by `periodic_rule`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PeriodicRule {
+    /// The plaintext letter is the ciphertext letter minus the key letter.
+    Vigenere,
+    /// The plaintext letter is the key letter minus the ciphertext letter.
+    Beaufort,
+    /// The plaintext letter is the ciphertext letter plus the key letter.
+    VariantBeaufort,
+    /// The key letter selects one of 13 reciprocal alphabets, each swapping the halves `a-m`
+    /// and `n-z` of the alphabet.
+    Porta,
+}
+
+impl PeriodicRule {
+    /// The key symbols of the rule for `crack_periodic`. As each pair of the letters of a Porta
+    /// key selects the same alphabet, only the first of each pair is given for the Porta rule.
+    ///
+    pub fn key_symbols(self) -> &'static str {
+        match self {
+            PeriodicRule::Porta => "acegikmoqsuwy",
+            _ => STANDARD_SYMBOLS,
+        }
+    }
+
+    /// The index of the plaintext letter within `a-z`, decrypted from the ciphertext letter `ci`
+    /// with the key symbol `ki`, as the `decrypt` rule of `crack_periodic`.
+    ///
+    pub fn decrypt(self, ci: usize, ki: usize) -> usize {
+        match self {
+            PeriodicRule::Vigenere => (26 + ci - ki) % 26,
+            PeriodicRule::Beaufort => (26 + ki - ci) % 26,
+            PeriodicRule::VariantBeaufort => (ci + ki) % 26,
+            PeriodicRule::Porta if ci < 13 => (ci + ki) % 13 + 13,
+            PeriodicRule::Porta => (ci - ki) % 13,
+        }
+    }
+}
+
+/// Determine which rule of a periodic polyalphabetic cipher best explains a `ciphertext`: the
+/// Vigenère, Beaufort, Variant Beaufort or Porta rule.
+///
+/// The ciphertext is cracked with `crack_periodic` under each rule, and the rule whose candidate
+/// has the best score is returned along with it. Returns `None` if the length of the key cannot be
+/// estimated.
+///
+/// The Vigenère and Variant Beaufort rules decrypt a ciphertext to the same plaintext, with keys
+/// that are the negation of one another, so cannot be told apart by the plaintext alone. Between
+/// these, the rule whose key reads most like English is chosen, so a random key may be credited
+/// to the wrong rule.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::{periodic_rule, PeriodicRule};
+/// use cipher_crypt::{Beaufort, Cipher};
+///
+/// let m = "It is a truth universally acknowledged, that a single man in possession of a good \
+///     fortune, must be in want of a wife. However little known the feelings or views of such a \
+///     man may be on his first entering a neighbourhood, this truth is so well fixed in the \
+///     minds of the surrounding families, that he is considered the rightful property of some \
+///     one or other of their daughters.";
+/// let ciphertext = Beaufort::new(String::from("darcy")).encrypt(m).unwrap();
+///
+/// let (rule, candidate) = periodic_rule(&ciphertext).unwrap();
+/// assert_eq!(PeriodicRule::Beaufort, rule);
+/// assert_eq!("darcy", candidate.key);
+/// ```
+///
+pub fn periodic_rule(ciphertext: &str) -> Option<(PeriodicRule, Candidate<String>)> {
+    let scorer = BigramScorer::english();
+    [
+        PeriodicRule::Vigenere,
+        PeriodicRule::Beaufort,
+        PeriodicRule::VariantBeaufort,
+        PeriodicRule::Porta,
+    ]
+    .iter()
+    .filter_map(|&rule| {
+        crack_periodic(ciphertext, rule.key_symbols(), |ci, ki| {
+            rule.decrypt(ci, ki)
+        })
+        .map(|candidate| (rule, candidate))
+    })
+    // Of equal rules, `max_by` returns the last, so the rules are reversed to prefer the first
+    .rev()
+    .max_by(|a, b| {
+        a.1.score
+            .total_cmp(&b.1.score)
+            .then_with(|| scorer.score(&a.1.key).total_cmp(&scorer.score(&b.1.key)))
+    })
+}
+
+/// A decryption found by `auto_decrypt`, with the cipher and key believed to have been used.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Solution {
+    /// The cipher believed to have been used, holding the key that was found for it.
+    pub kind: CipherKind,
+    /// The ciphertext decrypted with the cipher of the `kind`.
+    pub plaintext: String,
+    /// The average log likelihood of the bigrams of the `plaintext` given those expected of
+    /// English. The higher the score, the more likely the solution is to be correct.
+    pub score: f64,
+}
+
+/// An attack of `auto_decrypt` on a cipher of the `registry`, giving the cipher with the key that
+/// it found, and the plaintext.
+struct Attack {
+    cipher: fn() -> TypeId,
+    crack: fn(&str) -> Option<(CipherKind, String)>,
+}
+
+/// The attacks of `auto_decrypt`. The Keyword cipher is cracked as the Simple Substitution that
+/// it is a special case of.
+const ATTACKS: [Attack; 10] = [
+    Attack {
+        cipher: TypeId::of::<Caesar>,
+        crack: |ciphertext| {
+            best_of(crack_caesar(ciphertext), |shift| CipherKind::Caesar {
+                shift,
+            })
+        },
+    },
+    Attack {
+        cipher: TypeId::of::<Railfence>,
+        crack: |ciphertext| {
+            best_of(crack_railfence(ciphertext, max_key(ciphertext)), |rails| {
+                CipherKind::Railfence { rails }
+            })
+        },
+    },
+    Attack {
+        cipher: TypeId::of::<Scytale>,
+        crack: |ciphertext| {
+            best_of(crack_scytale(ciphertext, max_key(ciphertext)), |height| {
+                CipherKind::Scytale { height }
+            })
+        },
+    },
+    Attack {
+        cipher: TypeId::of::<Affine>,
+        crack: |ciphertext| {
+            best_of(
+                brute_force::<Affine, _, _>(keyspace::affine(), ciphertext, bigram_score, 1),
+                |k| CipherKind::Affine { a: k.a, b: k.b },
+            )
+        },
+    },
+    Attack {
+        cipher: TypeId::of::<Vigenere>,
+        crack: |ciphertext| {
+            crack_periodic_rule(ciphertext, PeriodicRule::Vigenere, |key| {
+                CipherKind::Vigenere { key }
+            })
+        },
+    },
+    Attack {
+        cipher: TypeId::of::<Gronsfeld>,
+        crack: |ciphertext| {
+            let candidate = crack_periodic(ciphertext, "0123456789", |ci, ki| {
+                PeriodicRule::Vigenere.decrypt(ci, ki)
+            })?;
+            Some((
+                CipherKind::Gronsfeld { key: candidate.key },
+                candidate.plaintext,
+            ))
+        },
+    },
+    Attack {
+        cipher: TypeId::of::<Beaufort>,
+        crack: |ciphertext| {
+            crack_periodic_rule(ciphertext, PeriodicRule::Beaufort, |key| {
+                CipherKind::Beaufort { key }
+            })
+        },
+    },
+    Attack {
+        cipher: TypeId::of::<Porta>,
+        crack: |ciphertext| {
+            crack_periodic_rule(ciphertext, PeriodicRule::Porta, |key| CipherKind::Porta {
+                key,
+            })
+        },
+    },
+    Attack {
+        cipher: TypeId::of::<SimpleSubstitution>,
+        crack: crack_substitution,
+    },
+    Attack {
+        cipher: TypeId::of::<Keyword>,
+        crack: crack_substitution,
+    },
+];
+
+/// Identify the cipher of a `ciphertext` and crack it, returning the most likely decryptions.
+///
+/// The ciphertext is first classified with `identify`. Each of the guessed ciphers for which
+/// this module has an attack is then cracked, and the best decryption for each is returned, from
+/// the highest score to the lowest. As each attack scores its plaintexts differently, the
+/// solutions are ranked by a common `score`: the average log likelihood of their bigrams.
+///
+/// The ciphers that can be cracked are Caesar, Affine, Railfence, Scytale, Vigenère, Beaufort,
+/// Gronsfeld, Porta and Simple Substitution (along with the Keyword cipher, which is a Simple
+/// Substitution with a particular key). The substitution attack is a `HillClimber`, which uses the
+/// `QuadgramScorer` when the `quadgrams` feature is enabled. An empty `Vec` is returned if none of
+/// the guessed ciphers can be cracked.
+///
+/// The Railfence and Scytale attacks try at most 20 rails or heights, and never more than half
+/// the length of the ciphertext: with more, the rows of the cipher hold only one or two letters,
+/// and a meaningless rearrangement can outscore the real plaintext.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::auto_decrypt;
+/// use cipher_crypt::CipherKind;
+///
+/// let solutions = auto_decrypt(
+///     "Vhqg pruh wurrsv wr wkh iurqw olqh, wkh hqhpb lv dgydqflqj rq wkh hdvwhuq zdoo!",
+/// );
+///
+/// assert_eq!(CipherKind::Caesar { shift: 3 }, solutions[0].kind);
+/// assert_eq!(
+///     "Send more troops to the front line, the enemy is advancing on the eastern wall!",
+///     solutions[0].plaintext
+/// );
+/// ```
+///
+pub fn auto_decrypt(ciphertext: &str) -> Vec<Solution> {
+    let mut solutions: Vec<Solution> = Vec::new();
+    for guess in identify(ciphertext) {
+        if guess.likelihood <= 0.0 {
+            continue;
+        }
+
+        let attack = registry::lookup(guess.cipher)
+            .and_then(|factory| ATTACKS.iter().find(|a| (a.cipher)() == factory.type_id()));
+        if let Some((kind, plaintext)) = attack.and_then(|a| (a.crack)(ciphertext)) {
+            solutions.push(Solution {
+                kind,
+                score: bigram_score(&plaintext),
+                plaintext,
+            });
+        }
+    }
+
+    // The substitution attack also cracks the ciphers that are special cases of it, so those
+    // ciphers are credited with any plaintext that it finds too
+    solutions.sort_by_key(|s| matches!(s.kind, CipherKind::SimpleSubstitution { .. }));
+    let mut unique: Vec<Solution> = Vec::new();
+    for solution in solutions {
+        if !unique.iter().any(|u| u.plaintext == solution.plaintext) {
+            unique.push(solution);
+        }
+    }
+    unique.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    unique
+}
+
+/// The most rails or heights tried by the Railfence and Scytale attacks of `auto_decrypt`.
+///
+fn max_key(ciphertext: &str) -> usize {
+    const MAX_KEY: usize = 20;
+
+    MAX_KEY.min(ciphertext.chars().count() / 2)
+}
+
+/// Crack a periodic cipher that decrypts by a `rule` for `auto_decrypt`, giving its key to
+/// `kind`.
+///
+fn crack_periodic_rule<F>(
+    ciphertext: &str,
+    rule: PeriodicRule,
+    kind: F,
+) -> Option<(CipherKind, String)>
+where
+    F: Fn(String) -> CipherKind,
+{
+    let candidate = crack_periodic(ciphertext, rule.key_symbols(), |ci, ki| {
+        rule.decrypt(ci, ki)
+    })?;
+
+    Some((kind(candidate.key), candidate.plaintext))
+}
+
+/// Crack a Simple Substitution cipher for `auto_decrypt`, with a `HillClimber`.
+///
+fn crack_substitution(ciphertext: &str) -> Option<(CipherKind, String)> {
+    #[cfg(feature = "quadgrams")]
+    let fitness = {
+        let scorer = QuadgramScorer::english();
+        move |text: &str| scorer.score(text)
+    };
+    #[cfg(not(feature = "quadgrams"))]
+    let fitness = bigram_score;
+
+    let candidate = HillClimber::new()
+        .seed(42)
+        .restarts(20)
+        .iterations(5000)
+        .optimize(&SubstitutionSearch::new(ciphertext), fitness);
+
+    Some((
+        CipherKind::SimpleSubstitution { key: candidate.key },
+        candidate.plaintext,
+    ))
+}
+
+/// The average log likelihood of the bigrams of a `plaintext` given those expected of English.
+///
+fn bigram_score(plaintext: &str) -> f64 {
+    let table = FrequencyTable::bigrams(plaintext);
+    if table.total() == 0 {
+        return f64::NEG_INFINITY;
+    }
+
+    table.log_likelihood(&ENGLISH_BIGRAMS) / table.total() as f64
+}
+
+/// The cipher (given its key by `kind`) and plaintext of the best of the `candidates`, if any.
+///
+fn best_of<K, F>(candidates: Vec<Candidate<K>>, kind: F) -> Option<(CipherKind, String)>
+where
+    F: Fn(K) -> CipherKind,
+{
+    candidates
+        .into_iter()
+        .next()
+        .map(|c| (kind(c.key), c.plaintext))
+}
+
+/// Score each of the `(key, plaintext)` pairs of the `candidates` with the `fitness` function,
+/// and sort them from the highest score to the lowest.
+///
+fn rank<K, I, F>(candidates: I, fitness: F) -> Vec<Candidate<K>>
+where
+    I: Iterator<Item = (K, String)>,
+    F: Fn(&str) -> f64,
+{
+    let mut ranked: Vec<Candidate<K>> = candidates
+        .map(|(key, plaintext)| Candidate {
+            score: fitness(&plaintext),
+            key,
+            plaintext,
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crack_caesar_test() {
+        let m = "Defend the east wall of the castle";
+        for shift in 1..=26 {
+            let ciphertext = Caesar::new(shift).encrypt(m).unwrap();
+            let best = &crack_caesar(&ciphertext)[0];
+
+            assert_eq!(shift, best.key);
+            assert_eq!(m, best.plaintext);
+        }
+    }
+
+    #[test]
+    fn every_shift_ranked() {
+        let candidates = crack_caesar("Wkh txlfn eurzq ira");
+        let mut shifts: Vec<usize> = candidates.iter().map(|c| c.key).collect();
+        shifts.sort();
+
+        assert_eq!((1..=26).collect::<Vec<usize>>(), shifts);
+        assert!(candidates.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn crack_railfence_test() {
+        let m = "It was the best of times, it was the worst of times";
+        for rails in 2..=8 {
+            let ciphertext = Railfence::new(rails).encrypt(m).unwrap();
+            let best = &crack_railfence(&ciphertext, 10)[0];
+
+            assert_eq!(rails, best.key);
+            assert_eq!(m, best.plaintext);
+        }
+    }
+
+    #[test]
+    fn crack_railfence_every_rail() {
+        let candidates = crack_railfence("Hoo!el,Wrdl l", 20);
+        let mut rails: Vec<usize> = candidates.iter().map(|c| c.key).collect();
+        rails.sort_unstable();
+
+        assert_eq!((2..=12).collect::<Vec<usize>>(), rails);
+        assert_eq!(4, crack_railfence("Hoo!el,Wrdl l", 5).len());
+        assert!(crack_railfence("Hi", 5).is_empty());
+    }
+
+    #[test]
+    fn crack_railfence_long_ciphertext() {
+        let m = PLAINTEXT.repeat(6);
+        let ciphertext = Railfence::new(7).encrypt(&m).unwrap();
+        let best = &crack_railfence(&ciphertext, 30)[0];
+
+        assert_eq!(7, best.key);
+        assert_eq!(m, best.plaintext);
+    }
+
+    #[test]
+    fn crack_scytale_test() {
+        let m = "It was the best of times, it was the worst of times";
+        for height in 2..=8 {
+            let ciphertext = Scytale::new(height).encrypt(m).unwrap();
+            let best = &crack_scytale(&ciphertext, 10)[0];
+
+            assert!(best.key <= height);
+            assert_eq!(m, best.plaintext);
+        }
+    }
+
+    #[test]
+    fn crack_scytale_every_height() {
+        let ciphertext = Scytale::new(3).encrypt("Hello, World!").unwrap();
+        let candidates = crack_scytale(&ciphertext, 20);
+        let mut plaintexts: Vec<&str> = candidates.iter().map(|c| c.plaintext.as_str()).collect();
+        plaintexts.sort_unstable();
+        plaintexts.dedup();
+
+        assert_eq!(candidates.len(), plaintexts.len());
+        assert!(candidates.iter().any(|c| c.plaintext == "Hello, World!"));
+        let longest = ciphertext.chars().count();
+        assert!(candidates.iter().all(|c| (2..longest).contains(&c.key)));
+        assert!(crack_scytale("", 20).is_empty());
+    }
+
+    const PLAINTEXT: &str = "It is a truth universally acknowledged, that a single man in \
+        possession of a good fortune, must be in want of a wife. However little known the feelings \
+        or views of such a man may be on his first entering a neighbourhood, this truth is so well \
+        fixed in the minds of the surrounding families, that he is considered the rightful property \
+        of some one or other of their daughters. My dear Mr. Bennet, said his lady to him one day, \
+        have you heard that Netherfield Park is let at last? Mr. Bennet replied that he had not.";
+
+    const LETTERS: &str = "abcdefghijklmnopqrstuvwxyz";
+
+    #[test]
+    fn crack_hill2_test() {
+        let m: String = PLAINTEXT
+            .chars()
+            .filter(char::is_ascii_alphabetic)
+            .collect();
+        let key = Matrix::new(2, 2, vec![7, 8, 11, 11]);
+        let c = Hill::new(key.clone()).encrypt(&m).unwrap();
+        let candidates = crack_hill2(&c, 5);
+
+        assert_eq!(5, candidates.len());
+        assert_eq!(key, candidates[0].key);
+        assert!(candidates[0].plaintext.starts_with(&m));
+    }
+
+    #[test]
+    fn crack_hill2_invalid() {
+        assert!(crack_hill2("abc", 5).is_empty());
+        assert!(crack_hill2("ab cd", 5).is_empty());
+        assert!(crack_hill2("", 5).is_empty());
+    }
+
+    #[test]
+    fn crack_periodic_vigenere() {
+        let ciphertext = Vigenere::new(String::from("lemon"))
+            .encrypt(PLAINTEXT)
+            .unwrap();
+        let candidate = crack_periodic(&ciphertext, LETTERS, |ci, ki| (26 + ci - ki) % 26).unwrap();
+
+        assert_eq!("lemon", candidate.key);
+        assert_eq!(PLAINTEXT, candidate.plaintext);
+    }
+
+    #[test]
+    fn crack_periodic_variant_beaufort() {
+        // The Variant Beaufort cipher encrypts with Vigenère decryption, and so decrypts by adding
+        let ciphertext = Vigenere::new(String::from("bingley"))
+            .decrypt(PLAINTEXT)
+            .unwrap();
+        let candidate = crack_periodic(&ciphertext, LETTERS, |ci, ki| (ci + ki) % 26).unwrap();
+
+        assert_eq!("bingley", candidate.key);
+        assert_eq!(PLAINTEXT, candidate.plaintext);
+    }
+
+    #[test]
+    fn crack_periodic_gronsfeld() {
+        let ciphertext = Gronsfeld::new(String::from("31415"))
+            .encrypt(PLAINTEXT)
+            .unwrap();
+        let candidate =
+            crack_periodic(&ciphertext, "0123456789", |ci, ki| (26 + ci - ki) % 26).unwrap();
+
+        assert_eq!("31415", candidate.key);
+        assert_eq!(PLAINTEXT, candidate.plaintext);
+    }
+
+    #[test]
+    fn periodic_rules() {
+        let vigenere = Vigenere::new(String::from("lemon"));
+        let porta = Porta::new(String::from("meryton"));
+        for (ciphertext, rule, key) in [
+            (
+                vigenere.encrypt(PLAINTEXT).unwrap(),
+                PeriodicRule::Vigenere,
+                "lemon",
+            ),
+            (
+                Beaufort::new(String::from("wickham"))
+                    .encrypt(PLAINTEXT)
+                    .unwrap(),
+                PeriodicRule::Beaufort,
+                "wickham",
+            ),
+            (
+                vigenere.decrypt(PLAINTEXT).unwrap(),
+                PeriodicRule::VariantBeaufort,
+                "lemon",
+            ),
+            (
+                porta.encrypt(PLAINTEXT).unwrap(),
+                PeriodicRule::Porta,
+                "meqysom",
+            ),
+        ] {
+            let (found, candidate) = periodic_rule(&ciphertext).unwrap();
+
+            assert_eq!(rule, found);
+            assert_eq!(key, candidate.key);
+            assert_eq!(PLAINTEXT, candidate.plaintext);
+        }
+    }
+
+    #[test]
+    fn crack_periodic_porta() {
+        // Each pair of key letters selects the same alphabet, so only the first of each is tried
+        let ciphertext = Porta::new(String::from("meryton"))
+            .encrypt(PLAINTEXT)
+            .unwrap();
+        let candidate = crack_periodic(&ciphertext, "acegikmoqsuwy", |ci, ki| {
+            if ci < 13 {
+                (ci + ki) % 13 + 13
+            } else {
+                (ci - ki) % 13
+            }
+        })
+        .unwrap();
+
+        assert_eq!("meqysom", candidate.key);
+        assert_eq!(PLAINTEXT, candidate.plaintext);
+    }
+
+    #[test]
+    fn crack_periodic_without_letters() {
+        assert!(crack_periodic("123 🗡️", LETTERS, |ci, ki| (ci + ki) % 26).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn crack_periodic_without_key_symbols() {
+        crack_periodic("Attack at dawn", "", |ci, _| ci);
+    }
+
+    #[test]
+    fn without_letters() {
+        let candidates = crack_caesar("123 🗡️");
+
+        assert_eq!(26, candidates.len());
+        assert!(candidates.iter().all(|c| c.plaintext == "123 🗡️"));
+    }
+
+    #[test]
+    fn auto_decrypt_transposition() {
+        let ciphertext = Railfence::new(5).encrypt(PLAINTEXT).unwrap();
+        let best = &auto_decrypt(&ciphertext)[0];
+
+        assert_eq!(CipherKind::Railfence { rails: 5 }, best.kind);
+        assert_eq!(PLAINTEXT, best.plaintext);
+    }
+
+    #[test]
+    fn auto_decrypt_long_transposition() {
+        let m = PLAINTEXT.repeat(6);
+        let ciphertext = Railfence::new(7).encrypt(&m).unwrap();
+        let solutions = auto_decrypt(&ciphertext);
+
+        assert_eq!(CipherKind::Railfence { rails: 7 }, solutions[0].kind);
+        assert_eq!(m, solutions[0].plaintext);
+        assert!(solutions.iter().all(|s| match s.kind {
+            CipherKind::Railfence { rails } => rails <= 20,
+            CipherKind::Scytale { height } => height <= 20,
+            _ => true,
+        }));
+    }
+
+    #[test]
+    fn auto_decrypt_periodic() {
+        let ciphertext = Vigenere::new(String::from("longbourn"))
+            .encrypt(PLAINTEXT)
+            .unwrap();
+        let solutions = auto_decrypt(&ciphertext);
+
+        assert_eq!(
+            CipherKind::Vigenere {
+                key: String::from("longbourn")
+            },
+            solutions[0].kind
+        );
+        assert_eq!(PLAINTEXT, solutions[0].plaintext);
+        assert!(solutions.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[cfg(feature = "quadgrams")]
+    #[test]
+    fn auto_decrypt_substitution() {
+        let ciphertext = SimpleSubstitution::new(String::from("phqgiumeaylnofdxjkrcvstzwb"))
+            .encrypt(PLAINTEXT)
+            .unwrap();
+        let best = &auto_decrypt(&ciphertext)[0];
+
+        assert_eq!(
+            CipherKind::SimpleSubstitution {
+                key: String::from("phqgiumeaylnofdxjkrcvstzwb")
+            },
+            best.kind
+        );
+        assert_eq!(PLAINTEXT, best.plaintext);
+    }
+
+    #[test]
+    fn every_attack_is_registered() {
+        for attack in ATTACKS.iter() {
+            assert!(registry::ciphers().any(|f| f.type_id() == (attack.cipher)()));
+        }
+    }
+
+    #[test]
+    fn auto_decrypt_unsupported() {
+        assert!(auto_decrypt("").is_empty());
+        assert!(auto_decrypt("FA GV AD DX XG VV").is_empty());
+    }
+}
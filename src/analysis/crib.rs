@@ -0,0 +1,396 @@
+//! Crib dragging attacks ciphers where the same key has been used more than once, or where the
+//! key is itself readable text (such as the running key cipher).
+//!
+//! If two messages are encrypted by adding the same pad to each (as with a reused one-time pad),
+//! subtracting one ciphertext from the other removes the pad, leaving the difference of the two
+//! plaintexts. A guessed word of one plaintext (a _crib_) can then be slid along this difference.
+//! At the position where the crib is correct, the letters beneath it are those of the other
+//! plaintext, and so read as a fragment of sensible text. At other positions they are usually
+//! gibberish.
+//!
+//! Similarly, if the key is English text, sliding a crib along a single ciphertext reveals
+//! readable fragments of the key.
+//!
+//! A crib also reveals part of the key of a periodic cipher (such as the Vigenère or Beaufort
+//! ciphers). Where the crib is longer than the keyword, the fragment of the key repeats and so
+//! gives the whole keyword; otherwise, the keyword may be completed by finding the words of a
+//! dictionary that agree with the fragment.
+//!
+//! The fragments are ranked by how closely they resemble English, but a short fragment can
+//! resemble English by chance. The best few alignments should be read, rather than trusting only
+//! the first, and longer cribs give more reliable rankings.
+//!
+use super::crack::decrypt_periodic;
+use super::frequency::{ENGLISH_BIGRAMS, ENGLISH_LETTERS};
+use super::{Candidate, FrequencyTable};
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+
+/// The placement of a crib, as returned by `crib_drag` and `crib_drag_key`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Alignment {
+    /// The position of the crib, counted in letters from the start of the ciphertext.
+    pub position: usize,
+    /// The letters revealed by placing the crib at the `position`.
+    pub fragment: String,
+    /// How closely the `fragment` resembles English. The higher the score, the more likely the
+    /// crib is to be at this `position`.
+    pub score: f64,
+}
+
+/// Drag a `crib` across two ciphertexts that were encrypted by adding (mod 26) the same pad, as
+/// with a reused one-time pad.
+///
+/// The crib is assumed to be part of the plaintext of the `first` ciphertext, and the fragment of
+/// each alignment is the letters of the plaintext of the `second` ciphertext beneath it. Only
+/// the letters `a-z` are considered, ignoring case. The alignments are returned from the highest
+/// score to the lowest, and are empty if the crib has no letters or is longer than the shorter
+/// ciphertext.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::crib_drag;
+/// use cipher_crypt::{Cipher, OneTimePad};
+///
+/// let otp = OneTimePad::new(String::from("qhzvkdmrwnyeoxtbalsgujicpfkwhdnqzyrm"));
+/// let first = otp.encrypt("We will attack the northern fort").unwrap();
+/// let second = otp.encrypt("Hold the bridge until morning").unwrap();
+///
+/// let alignments = crib_drag(&first, &second, "northern");
+/// assert_eq!(15, alignments[0].position);
+/// assert_eq!("tilmorni", alignments[0].fragment);
+/// ```
+///
+pub fn crib_drag(first: &str, second: &str, crib: &str) -> Vec<Alignment> {
+    let difference: Vec<usize> = letters(first)
+        .into_iter()
+        .zip(letters(second))
+        .map(|(a, b)| (26 + a - b) % 26)
+        .collect();
+
+    // The second plaintext is the first, less the difference
+    drag(&difference, crib, |m, d| (26 + m - d) % 26)
+}
+
+/// Drag a `crib` across a `ciphertext` encrypted by adding (mod 26) a key of readable text, as
+/// with a running key cipher.
+///
+/// The crib is assumed to be part of the plaintext, and the fragment of each alignment is the
+/// letters of the key beneath it. Only the letters `a-z` are considered, ignoring case. The
+/// alignments are returned from the highest score to the lowest, and are empty if the crib has
+/// no letters or is longer than the ciphertext.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::crib_drag_key;
+/// use cipher_crypt::{Cipher, OneTimePad};
+///
+/// let running_key = OneTimePad::new(String::from("itwasthebestoftimesitwastheworstoftimes"));
+/// let ciphertext = running_key.encrypt("Send reinforcements to the bridge").unwrap();
+///
+/// let alignments = crib_drag_key(&ciphertext, "bridge");
+/// assert_eq!(23, alignments[0].position);
+/// assert_eq!("sthewo", alignments[0].fragment);
+/// ```
+///
+pub fn crib_drag_key(ciphertext: &str, crib: &str) -> Vec<Alignment> {
+    // The key is the ciphertext, less the plaintext
+    drag(&letters(ciphertext), crib, |m, c| (26 + c - m) % 26)
+}
+
+/// The fragment of the key of a periodic cipher beneath a `crib` placed at a `position` (counted
+/// in letters from the start of the `ciphertext`).
+///
+/// The `decrypt(ci, ki)` rule of the cipher and its `key_symbols` are as for `crack_periodic`.
+/// Only the letters `a-z` are considered, ignoring case. Returns `None` if the crib has no
+/// letters, if it runs past the end of the ciphertext, or if no key symbol decrypts one of the
+/// letters of the ciphertext to the letter of the crib above it.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::crib_key_fragment;
+/// use cipher_crypt::{Cipher, Vigenere};
+///
+/// let ciphertext = Vigenere::new(String::from("lemon")).encrypt("Attack at dawn").unwrap();
+/// let (letters, vigenere) = ("abcdefghijklmnopqrstuvwxyz", |ci, ki| (26 + ci - ki) % 26);
+///
+/// let fragment = crib_key_fragment(&ciphertext, "dawn", 8, letters, vigenere);
+/// assert_eq!(Some(String::from("onle")), fragment);
+/// ```
+///
+pub fn crib_key_fragment<F>(
+    ciphertext: &str,
+    crib: &str,
+    position: usize,
+    key_symbols: &str,
+    decrypt: F,
+) -> Option<String>
+where
+    F: Fn(usize, usize) -> usize,
+{
+    let symbols: Vec<char> = key_symbols.chars().collect();
+    let crib = letters(crib);
+    let stream = letters(ciphertext);
+    if crib.is_empty() || position + crib.len() > stream.len() {
+        return None;
+    }
+
+    crib.iter()
+        .zip(&stream[position..])
+        .map(|(&m, &c)| {
+            (0..symbols.len())
+                .find(|&ki| decrypt(c, ki) == m)
+                .map(|ki| symbols[ki])
+        })
+        .collect()
+}
+
+/// Recover the keyword of a periodic cipher from a `crib`, by sliding it across the `ciphertext`.
+///
+/// At each position, the fragment of the key beneath the crib is found as by
+/// `crib_key_fragment`. A keyword is recovered from a fragment in two ways:
+/// * If the fragment repeats itself (as it does when the crib is longer than the keyword), the
+///   repeating part gives the keyword.
+/// * Each word of the `dictionary` that agrees with the fragment at its position is taken as a
+///   keyword, so that a keyword longer than the crib can be completed.
+///
+/// Each keyword found is used to decrypt the whole ciphertext. The candidates are scored by the
+/// chi-squared statistic of their letters against those expected of English, negated so that
+/// higher scores are better, and are returned from the highest score to the lowest. Each keyword
+/// is only returned once.
+///
+/// # Panics
+/// * The `key_symbols` are empty.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::crib_keyword;
+/// use cipher_crypt::{Cipher, Vigenere};
+///
+/// let m = "The convoy will leave the harbour at dawn, escorted by two destroyers";
+/// let ciphertext = Vigenere::new(String::from("neptune")).encrypt(m).unwrap();
+/// let (letters, vigenere) = ("abcdefghijklmnopqrstuvwxyz", |ci, ki| (26 + ci - ki) % 26);
+/// let dictionary = vec!["mercury", "venus", "mars", "jupiter", "saturn", "uranus", "neptune"];
+///
+/// let candidates = crib_keyword(&ciphertext, "harbour", letters, vigenere, dictionary);
+/// assert_eq!("neptune", candidates[0].key);
+/// assert_eq!(m, candidates[0].plaintext);
+/// ```
+///
+pub fn crib_keyword<F, I, S>(
+    ciphertext: &str,
+    crib: &str,
+    key_symbols: &str,
+    decrypt: F,
+    dictionary: I,
+) -> Vec<Candidate<String>>
+where
+    F: Fn(usize, usize) -> usize,
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let symbols: Vec<char> = key_symbols.chars().collect();
+    if symbols.is_empty() {
+        panic!("There must be at least one key symbol.");
+    }
+
+    let dictionary: Vec<Vec<char>> = dictionary
+        .into_iter()
+        .map(|w| w.as_ref().to_lowercase().chars().collect())
+        .filter(|w: &Vec<char>| !w.is_empty() && w.iter().all(|c| symbols.contains(c)))
+        .collect();
+
+    let mut keywords: Vec<Vec<char>> = Vec::new();
+    let mut position = 0;
+    while let Some(fragment) = crib_key_fragment(ciphertext, crib, position, key_symbols, &decrypt)
+    {
+        let fragment: Vec<char> = fragment.chars().collect();
+        let agrees = |keyword: &[char]| {
+            fragment
+                .iter()
+                .enumerate()
+                .all(|(i, &k)| keyword[(position + i) % keyword.len()] == k)
+        };
+
+        // The shortest period at which the fragment repeats itself
+        if let Some(period) = (1..fragment.len())
+            .find(|&p| (p..fragment.len()).all(|i| fragment[i] == fragment[i - p]))
+        {
+            let keyword: Vec<char> = (0..period)
+                .map(|i| fragment[(i + period - position % period) % period])
+                .collect();
+            keywords.push(keyword);
+        }
+        keywords.extend(dictionary.iter().filter(|w| agrees(w)).cloned());
+
+        position += 1;
+    }
+    keywords.sort();
+    keywords.dedup();
+
+    let mut candidates: Vec<Candidate<String>> = keywords
+        .into_iter()
+        .map(|keyword| {
+            let key: Vec<usize> = keyword
+                .iter()
+                .map(|k| symbols.iter().position(|s| s == k).unwrap())
+                .collect();
+            let plaintext = decrypt_periodic(ciphertext, &key, &decrypt);
+
+            Candidate {
+                key: keyword.into_iter().collect(),
+                score: -FrequencyTable::letters(&plaintext).chi_squared(&ENGLISH_LETTERS),
+                plaintext,
+            }
+        })
+        .collect();
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    candidates
+}
+
+/// Slide the `crib` along the `stream` of letter indices, revealing a letter at each position
+/// with `reveal(crib_letter, stream_letter)`, and rank the resulting fragments.
+///
+fn drag<F>(stream: &[usize], crib: &str, reveal: F) -> Vec<Alignment>
+where
+    F: Fn(usize, usize) -> usize,
+{
+    let crib = letters(crib);
+    if crib.is_empty() || crib.len() > stream.len() {
+        return Vec::new();
+    }
+
+    let mut alignments: Vec<Alignment> = stream
+        .windows(crib.len())
+        .enumerate()
+        .map(|(position, window)| {
+            let fragment: String = crib
+                .iter()
+                .zip(window)
+                .map(|(&m, &s)| alphabet::STANDARD.get_letter(reveal(m, s), false))
+                .collect();
+
+            Alignment {
+                position,
+                score: readability(&fragment),
+                fragment,
+            }
+        })
+        .collect();
+    alignments.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    alignments
+}
+
+/// The log likelihood, per letter, of the letters and bigrams of a `fragment` of English.
+///
+fn readability(fragment: &str) -> f64 {
+    let likelihood = FrequencyTable::letters(fragment).log_likelihood(&ENGLISH_LETTERS)
+        + FrequencyTable::bigrams(fragment).log_likelihood(&ENGLISH_BIGRAMS);
+
+    likelihood / fragment.len() as f64
+}
+
+/// The indices of the letters of the `text` within the alphabet.
+///
+fn letters(text: &str) -> Vec<usize> {
+    text.chars()
+        .filter_map(|c| alphabet::STANDARD.find_position(c))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Beaufort, Cipher, OneTimePad, Vigenere};
+
+    const LETTERS: &str = "abcdefghijklmnopqrstuvwxyz";
+
+    const PAD: &str = "zqmvhatrkdpweoclxsnbguyfijtqrmvbzxkeuhwopdlsgnfacyi";
+
+    #[test]
+    fn reused_pad() {
+        let otp = OneTimePad::new(String::from(PAD));
+        let first = otp.encrypt("Meet me at the station at noon").unwrap();
+        let second = otp.encrypt("The package is hidden in the church").unwrap();
+
+        let best = &crib_drag(&first, &second, "noon")[0];
+        assert_eq!(20, best.position);
+        assert_eq!("thec", best.fragment);
+
+        assert!(crib_drag(&first, &second, "station")[..3]
+            .iter()
+            .any(|a| a.position == 11 && a.fragment == "shidden"));
+    }
+
+    #[test]
+    fn every_position() {
+        let alignments = crib_drag("abcdef", "ghijklmn", "xyz");
+
+        assert_eq!(4, alignments.len());
+        assert!(alignments.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn crib_too_long() {
+        assert!(crib_drag("abc", "abcdef", "abcd").is_empty());
+        assert!(crib_drag_key("abc", "abcd").is_empty());
+        assert!(crib_drag_key("abc", "🗡️").is_empty());
+    }
+
+    #[test]
+    fn key_fragment() {
+        let c = Vigenere::new(String::from("fortify"))
+            .encrypt("Hold the line")
+            .unwrap();
+        let vigenere = |ci, ki| (26 + ci - ki) % 26;
+
+        assert_eq!(
+            Some(String::from("ortif")),
+            crib_key_fragment(&c, "oldth", 1, LETTERS, vigenere)
+        );
+        assert_eq!(None, crib_key_fragment(&c, "line", 8, LETTERS, vigenere));
+        assert_eq!(None, crib_key_fragment(&c, "", 0, LETTERS, vigenere));
+    }
+
+    #[test]
+    fn keyword_from_repeating_fragment() {
+        let m = "Send the reserves to the eastern ridge before nightfall";
+        let c = Beaufort::new(String::from("ridge")).encrypt(m).unwrap();
+        let beaufort = |ci, ki| (26 + ki - ci) % 26;
+
+        let candidates = crib_keyword(&c, "reserves to the", LETTERS, beaufort, Vec::<&str>::new());
+        assert_eq!("ridge", candidates[0].key);
+        assert_eq!(m, candidates[0].plaintext);
+    }
+
+    #[test]
+    fn keyword_from_dictionary() {
+        let m = "The garrison will surrender at midnight unless relieved";
+        let c = Vigenere::new(String::from("wellington"))
+            .encrypt(m)
+            .unwrap();
+        let vigenere = |ci, ki| (26 + ci - ki) % 26;
+
+        let dictionary = vec!["napoleon", "Wellington", "blucher", "nelson"];
+        let candidates = crib_keyword(&c, "midnight", LETTERS, vigenere, dictionary);
+        assert_eq!(1, candidates.len());
+        assert_eq!("wellington", candidates[0].key);
+        assert_eq!(m, candidates[0].plaintext);
+    }
+
+    #[test]
+    fn no_keyword() {
+        let c = Vigenere::new(String::from("secret"))
+            .encrypt("Retreat")
+            .unwrap();
+        let vigenere = |ci, ki| (26 + ci - ki) % 26;
+
+        assert!(crib_keyword(&c, "advance", LETTERS, vigenere, vec!["unknown"]).is_empty());
+    }
+}
@@ -0,0 +1,413 @@
+//! Attacks on the fractionating ciphers of Félix Delastelle: the Bifid and Trifid ciphers.
+//!
+//! Both ciphers split each letter into coordinates, mix the coordinates of each block of `period`
+//! letters, and join them back into letters. The attack is made in two steps.
+//!
+//! First, the period is found from the signature it leaves in the ciphertext. Within a block of a
+//! Bifid cipher with an even period, the letter at position `k` of the ciphertext is made from the
+//! rows of the plaintext letters `2k` and `2k + 1`, and the letter half a period later from their
+//! columns. The pair of ciphertext letters is therefore a fixed substitution of the plaintext
+//! bigram, and pairs of letters half a period apart repeat as often as English bigrams do. In
+//! general, the coordinates of each plaintext letter are found at multiples of `period / n`
+//! symbols apart (rounded down or up), where `n` is the number of coordinates: 2 for the Bifid
+//! cipher and 3 for the Trifid cipher. So each period is scored by how often the pairs at those
+//! distances within its blocks repeat, beyond what is expected of independent letters. Only those
+//! distances are scored, as the blocks of a multiple of the true period contain its pairs at
+//! other distances.
+//!
+//! Second, with the period known, the square (or cube) is recovered by a search such as
+//! `SimulatedAnnealing`, using `BifidSearch` or `TrifidSearch` as the key space.
+//!
+use super::solver::KeySearch;
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::{Bifid, BifidKey, Trifid, TrifidKey};
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Estimate the period of a Bifid `ciphertext`, trying each period from `2` up to `max_period`.
+///
+/// Each period is scored by how often pairs of letters a fixed distance apart within each block
+/// of the period repeat, compared with pairs of independent letters. Returns `None` if no period in
+/// the range has a full block of ciphertext to measure. As the attack is statistical, the longer
+/// the ciphertext, the more likely it is to succeed; several hundred letters are needed.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::bifid_period;
+/// use cipher_crypt::{Bifid, Cipher, BifidKey};
+///
+/// let m = "It is a truth universally acknowledged, that a single man in possession of a good \
+///     fortune, must be in want of a wife. However little known the feelings or views of such a \
+///     man may be on his first entering a neighbourhood, this truth is so well fixed in the \
+///     minds of the surrounding families, that he is considered the rightful property of some \
+///     one or other of their daughters.";
+/// let c = Bifid::new(BifidKey {
+///     phrase: String::from("pemberley"),
+///     period: 8,
+/// })
+/// .encrypt(m)
+/// .unwrap();
+///
+/// assert_eq!(Some(8), bifid_period(&c, 20));
+/// ```
+///
+pub fn bifid_period(ciphertext: &str, max_period: usize) -> Option<usize> {
+    let letters: Vec<char> = ciphertext
+        .chars()
+        .filter_map(|c| {
+            let c = match c.to_ascii_lowercase() {
+                'j' => 'i',
+                lower => lower,
+            };
+            alphabet::PLAYFAIR.find_position(c).map(|_| c)
+        })
+        .collect();
+
+    best_period(&letters, 2, max_period)
+}
+
+/// Estimate the period of a Trifid `ciphertext`, trying each period from `2` up to `max_period`.
+///
+/// Each period is scored as for `bifid_period`. Returns `None` if no period in the range has a
+/// full block of ciphertext to measure. The Trifid signature is weaker than that of the Bifid
+/// cipher, so more ciphertext is needed: a thousand letters or more, rather than several hundred.
+///
+/// For either cipher, a plaintext that repeats itself misleads the estimate, as its pairs of
+/// letters then repeat at every distance.
+///
+pub fn trifid_period(ciphertext: &str, max_period: usize) -> Option<usize> {
+    let symbols: Vec<char> = ciphertext
+        .chars()
+        .filter(|&c| c == FILL_CHAR || alphabet::STANDARD.find_position(c).is_some())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+
+    best_period(&symbols, 3, max_period)
+}
+
+/// The 27th symbol of the cube of a Trifid cipher.
+const FILL_CHAR: char = '+';
+
+/// Find the period from `2` to `max_period` whose blocks of `symbols` show the strongest
+/// signature of fractionation, where each letter is split into a number of `coordinates`.
+///
+/// The coordinates of a letter are found a multiple of `period / coordinates` symbols apart
+/// (rounded down or up), and the signatures at each of those distances are combined into one
+/// score.
+///
+fn best_period(symbols: &[char], coordinates: usize, max_period: usize) -> Option<usize> {
+    (2..=max_period)
+        .filter_map(|period| {
+            let mut distances: Vec<usize> = (1..coordinates)
+                .flat_map(|j| {
+                    [
+                        (j * period / coordinates).max(1),
+                        (j * period).div_ceil(coordinates),
+                    ]
+                })
+                .filter(|&d| d < period)
+                .collect();
+            distances.sort_unstable();
+            distances.dedup();
+
+            let scores: Vec<f64> = distances
+                .into_iter()
+                .filter_map(|distance| signature(symbols, period, distance))
+                .collect();
+            if scores.is_empty() {
+                return None;
+            }
+
+            // Stouffer's method: the sum of the standard scores, scaled back to a standard score
+            let score = scores.iter().sum::<f64>() / (scores.len() as f64).sqrt();
+            Some((period, score))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(period, _)| period)
+}
+
+/// How many standard deviations more often the pairs of `symbols` a `distance` apart within each
+/// block of a `period` repeat than they would if the two symbols of each pair were independent.
+///
+/// The expected repeats are found from the frequencies of the first and of the second symbols of
+/// the pairs, as the symbols at each position of a block have frequencies of their own.
+///
+fn signature(symbols: &[char], period: usize, distance: usize) -> Option<f64> {
+    let pairs: Vec<(char, char)> = symbols
+        .chunks_exact(period)
+        .flat_map(|block| (0..period - distance).map(move |i| (block[i], block[i + distance])))
+        .collect();
+    if pairs.len() < 2 {
+        return None;
+    }
+
+    let firsts: Vec<char> = pairs.iter().map(|p| p.0).collect();
+    let seconds: Vec<char> = pairs.iter().map(|p| p.1).collect();
+    let n = (pairs.len() * (pairs.len() - 1)) as f64;
+    let expected = coincidence(&firsts) * coincidence(&seconds) * n;
+    if expected == 0.0 {
+        return None;
+    }
+
+    Some((coincidence(&pairs) * n - expected) / expected.sqrt())
+}
+
+/// The chance that two of the `items`, chosen at random, are equal.
+///
+fn coincidence<T: Eq + Hash>(items: &[T]) -> f64 {
+    let mut counts: HashMap<&T, usize> = HashMap::new();
+    for item in items {
+        *counts.entry(item).or_default() += 1;
+    }
+
+    let coincidences: usize = counts.values().map(|n| n * (n - 1)).sum();
+    coincidences as f64 / (items.len() * (items.len() - 1)) as f64
+}
+
+/// The key space of a Bifid cipher with a 5x5 square and a known period, for a `HillClimber` or
+/// `SimulatedAnnealing` search.
+///
+/// Each key is the 25 letters of the square (without `j`) read row by row, which can be given as
+/// the phrase of `Bifid::new()`. A key is mutated by swapping two of its letters, or now and then
+/// by swapping two rows or two columns of the square.
+#[derive(Clone, Debug)]
+pub struct BifidSearch {
+    ciphertext: String,
+    period: usize,
+}
+
+impl BifidSearch {
+    /// Initialise a search for the square of a Bifid `ciphertext` with a known `period`.
+    ///
+    /// # Panics
+    /// * The `period` is 0.
+    ///
+    pub fn new(ciphertext: &str, period: usize) -> BifidSearch {
+        if period == 0 {
+            panic!("The period must be greater than 0.");
+        }
+
+        BifidSearch {
+            ciphertext: ciphertext.to_string(),
+            period,
+        }
+    }
+}
+
+impl KeySearch for BifidSearch {
+    type Key = String;
+
+    fn random_key(&self, rng: &mut dyn RngCore) -> String {
+        let mut letters: Vec<char> = ('a'..='z').filter(|&c| c != 'j').collect();
+        letters.shuffle(rng);
+
+        letters.into_iter().collect()
+    }
+
+    fn mutate(&self, key: &String, rng: &mut dyn RngCore) -> String {
+        let mut letters: Vec<char> = key.chars().collect();
+        let (a, b) = (rng.gen_range(0..5), rng.gen_range(0..5));
+        match rng.gen_range(0..20) {
+            0 => {
+                for col in 0..5 {
+                    letters.swap(a * 5 + col, b * 5 + col);
+                }
+            }
+            1 => {
+                for row in 0..5 {
+                    letters.swap(row * 5 + a, row * 5 + b);
+                }
+            }
+            _ => letters.swap(rng.gen_range(0..25), rng.gen_range(0..25)),
+        }
+
+        letters.into_iter().collect()
+    }
+
+    fn decrypt(&self, key: &String) -> String {
+        Bifid::new(BifidKey {
+            phrase: key.clone(),
+            period: self.period,
+        })
+        .decrypt(&self.ciphertext)
+        .unwrap()
+    }
+}
+
+/// The key space of a Trifid cipher with a known period, for a `HillClimber` or
+/// `SimulatedAnnealing` search.
+///
+/// Each key is the 26 letters and the `+` symbol of the cube, read layer by layer and row by row,
+/// which can be given as the phrase of `Trifid::new()`. A key is mutated by swapping two of its
+/// symbols.
+#[derive(Clone, Debug)]
+pub struct TrifidSearch {
+    ciphertext: String,
+    period: usize,
+}
+
+impl TrifidSearch {
+    /// Initialise a search for the cube of a Trifid `ciphertext` with a known `period`.
+    ///
+    /// # Panics
+    /// * The `period` is 0.
+    ///
+    pub fn new(ciphertext: &str, period: usize) -> TrifidSearch {
+        if period == 0 {
+            panic!("The period must be greater than 0.");
+        }
+
+        TrifidSearch {
+            ciphertext: ciphertext.to_string(),
+            period,
+        }
+    }
+}
+
+impl KeySearch for TrifidSearch {
+    type Key = String;
+
+    fn random_key(&self, rng: &mut dyn RngCore) -> String {
+        let mut symbols: Vec<char> = ('a'..='z').chain(std::iter::once(FILL_CHAR)).collect();
+        symbols.shuffle(rng);
+
+        symbols.into_iter().collect()
+    }
+
+    fn mutate(&self, key: &String, rng: &mut dyn RngCore) -> String {
+        let mut symbols: Vec<char> = key.chars().collect();
+        symbols.swap(rng.gen_range(0..27), rng.gen_range(0..27));
+
+        symbols.into_iter().collect()
+    }
+
+    fn decrypt(&self, key: &String) -> String {
+        Trifid::new(TrifidKey {
+            phrase: key.clone(),
+            period: self.period,
+        })
+        .decrypt(&self.ciphertext)
+        .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const PLAINTEXT: &str = "It is a truth universally acknowledged, that a single man in \
+        possession of a good fortune, must be in want of a wife. However little known the feelings \
+        or views of such a man may be on his first entering a neighbourhood, this truth is so well \
+        fixed in the minds of the surrounding families, that he is considered the rightful property \
+        of some one or other of their daughters. My dear Mr. Bennet, said his lady to him one day, \
+        have you heard that Netherfield Park is let at last? Mr. Bennet replied that he had not.";
+
+    /// The rest of the opening chapter, as the Trifid signature needs a longer ciphertext.
+    const CONTINUED: &str = "But it is, returned she; for Mrs. Long has just been here, and she \
+        told me all about it. Mr. Bennet made no answer. Do you not want to know who has taken \
+        it? cried his wife impatiently. You want to tell me, and I have no objection to hearing \
+        it. This was invitation enough. Why, my dear, you must know, Mrs. Long says that \
+        Netherfield is taken by a young man of large fortune from the north of England; that he \
+        came down on Monday in a chaise and four to see the place, and was so much delighted with \
+        it that he agreed with Mr. Morris immediately; that he is to take possession before \
+        Michaelmas, and some of his servants are to be in the house by the end of next week. What \
+        is his name? Bingley. Is he married or single? Oh, single, my dear, to be sure! A single \
+        man of large fortune; four or five thousand a year. What a fine thing for our girls! How \
+        so? How can it affect them? My dear Mr. Bennet, replied his wife, how can you be so \
+        tiresome! You must know that I am thinking of his marrying one of them. Is that his \
+        design in settling here? Design! Nonsense, how can you talk so! But it is very likely \
+        that he may fall in love with one of them, and therefore you must visit him as soon as \
+        he comes. I see no occasion for that. You and the girls may go, or you may send them by \
+        themselves, which perhaps will be still better, for as you are as handsome as any of \
+        them, Mr. Bingley may like you the best of the party. My dear, you flatter me. I \
+        certainly have had my share of beauty, but I do not pretend to be anything extraordinary \
+        now. When a woman has five grown-up daughters, she ought to give over thinking of her own \
+        beauty. In such cases, a woman has not often much beauty to think of. But, my dear, you \
+        must indeed go and see Mr. Bingley when he comes into the neighbourhood. It is more than \
+        I engage for, I assure you.";
+
+    #[test]
+    fn bifid_periods() {
+        for period in 2..=12 {
+            let c = Bifid::new(BifidKey {
+                phrase: String::from("netherfield"),
+                period,
+            })
+            .encrypt(PLAINTEXT)
+            .unwrap();
+
+            assert_eq!(Some(period), bifid_period(&c, 15), "{}", period);
+        }
+    }
+
+    #[test]
+    fn trifid_periods() {
+        let m = format!("{} {}", PLAINTEXT, CONTINUED);
+        for period in 2..=12 {
+            let c = Trifid::new(TrifidKey {
+                phrase: String::from("netherfield"),
+                period,
+            })
+            .encrypt(&m)
+            .unwrap();
+
+            assert_eq!(Some(period), trifid_period(&c, 15), "{}", period);
+        }
+    }
+
+    #[test]
+    fn too_short() {
+        assert_eq!(None, bifid_period("abc", 10));
+        assert_eq!(None, trifid_period("", 10));
+    }
+
+    #[test]
+    fn bifid_keys_are_valid() {
+        let search = BifidSearch::new("Uaeo lw rins!", 10);
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut key = search.random_key(&mut rng);
+        for _ in 0..100 {
+            key = search.mutate(&key, &mut rng);
+
+            let mut letters: Vec<char> = key.chars().collect();
+            letters.sort();
+            letters.dedup();
+            assert_eq!(25, letters.len());
+            assert!(!key.contains('j'));
+        }
+
+        let square = "bgwkzqpndsioaxefclumthyvr";
+        assert_eq!("Flee at once!", search.decrypt(&square.to_string()));
+    }
+
+    #[test]
+    fn trifid_keys_are_valid() {
+        let m = "aidetoilecieltaidera";
+        let cube = "felixmardstbcghjknopquvwyz+";
+        let c = Trifid::new(TrifidKey {
+            phrase: String::from(cube),
+            period: 5,
+        })
+        .encrypt(m)
+        .unwrap();
+        let search = TrifidSearch::new(&c, 5);
+        let mut rng = StdRng::seed_from_u64(0);
+        let key = search.mutate(&search.random_key(&mut rng), &mut rng);
+
+        assert_eq!(27, key.chars().count());
+        assert_eq!(m.to_uppercase(), search.decrypt(&cube.to_string()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_period() {
+        BifidSearch::new("abc", 0);
+    }
+}
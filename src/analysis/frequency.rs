@@ -0,0 +1,453 @@
+//! Frequency analysis counts how often each letter, or group of letters (an n-gram), appears
+//! within a text. As the letters of a language are not used equally often, comparing these counts
+//! against those expected of the language is the basis of breaking most classical ciphers.
+//!
+use super::Language;
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use std::collections::HashMap;
+
+/// The approximate frequency of each of the letters `a-z` within English text.
+pub const ENGLISH_LETTERS: [(&str, f64); 26] = [
+    ("a", 0.08167),
+    ("b", 0.01492),
+    ("c", 0.02782),
+    ("d", 0.04253),
+    ("e", 0.12702),
+    ("f", 0.02228),
+    ("g", 0.02015),
+    ("h", 0.06094),
+    ("i", 0.06966),
+    ("j", 0.00153),
+    ("k", 0.00772),
+    ("l", 0.04025),
+    ("m", 0.02406),
+    ("n", 0.06749),
+    ("o", 0.07507),
+    ("p", 0.01929),
+    ("q", 0.00095),
+    ("r", 0.05987),
+    ("s", 0.06327),
+    ("t", 0.09056),
+    ("u", 0.02758),
+    ("v", 0.00978),
+    ("w", 0.02360),
+    ("x", 0.00150),
+    ("y", 0.01974),
+    ("z", 0.00074),
+];
+
+/// The approximate frequency of the 30 most common bigrams within English text.
+pub const ENGLISH_BIGRAMS: [(&str, f64); 30] = [
+    ("th", 0.0356),
+    ("he", 0.0307),
+    ("in", 0.0243),
+    ("er", 0.0205),
+    ("an", 0.0199),
+    ("re", 0.0185),
+    ("on", 0.0176),
+    ("at", 0.0149),
+    ("en", 0.0145),
+    ("nd", 0.0135),
+    ("ti", 0.0134),
+    ("es", 0.0134),
+    ("or", 0.0128),
+    ("te", 0.0120),
+    ("of", 0.0117),
+    ("ed", 0.0117),
+    ("is", 0.0113),
+    ("it", 0.0112),
+    ("al", 0.0109),
+    ("ar", 0.0107),
+    ("st", 0.0105),
+    ("to", 0.0104),
+    ("nt", 0.0104),
+    ("ng", 0.0095),
+    ("se", 0.0093),
+    ("ha", 0.0093),
+    ("as", 0.0087),
+    ("ou", 0.0087),
+    ("io", 0.0083),
+    ("le", 0.0083),
+];
+
+/// The approximate frequency of the 30 most common trigrams within English text.
+pub const ENGLISH_TRIGRAMS: [(&str, f64); 30] = [
+    ("the", 0.0181),
+    ("and", 0.0073),
+    ("ing", 0.0072),
+    ("ent", 0.0042),
+    ("ion", 0.0042),
+    ("her", 0.0036),
+    ("for", 0.0034),
+    ("tha", 0.0033),
+    ("nth", 0.0033),
+    ("int", 0.0032),
+    ("ere", 0.0031),
+    ("tio", 0.0031),
+    ("ter", 0.0030),
+    ("est", 0.0028),
+    ("ers", 0.0028),
+    ("ati", 0.0026),
+    ("hat", 0.0026),
+    ("ate", 0.0025),
+    ("all", 0.0025),
+    ("eth", 0.0024),
+    ("hes", 0.0024),
+    ("ver", 0.0024),
+    ("his", 0.0024),
+    ("oft", 0.0022),
+    ("ith", 0.0021),
+    ("fth", 0.0021),
+    ("sth", 0.0021),
+    ("oth", 0.0021),
+    ("res", 0.0021),
+    ("ont", 0.0020),
+];
+
+/// A table of the number of times each n-gram appears within a text.
+///
+/// The n-grams are taken from the letters of the text in lowercase, ignoring any other symbols,
+/// and overlap one another. For example, the bigrams of `"The cat"` are `th`, `he`, `ec`, `ca`
+/// and `at`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrequencyTable {
+    n: usize,
+    counts: HashMap<String, usize>,
+    total: usize,
+}
+
+impl FrequencyTable {
+    /// Count the n-grams of length `n` within the `text`.
+    ///
+    /// # Panics
+    /// * `n` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::FrequencyTable;
+    ///
+    /// let table = FrequencyTable::new("The quick brown fox", 4);
+    /// assert_eq!(13, table.total());
+    /// assert_eq!(1, table.count("ckbr"));
+    /// ```
+    ///
+    pub fn new(text: &str, n: usize) -> FrequencyTable {
+        if n == 0 {
+            panic!("The length of the n-grams must be greater than 0.");
+        }
+
+        let letters: Vec<char> = alphabet::STANDARD
+            .scrub(text)
+            .to_ascii_lowercase()
+            .chars()
+            .collect();
+
+        let mut counts = HashMap::new();
+        for gram in letters.windows(n) {
+            *counts.entry(gram.iter().collect()).or_insert(0) += 1;
+        }
+        let total = counts.values().sum();
+
+        FrequencyTable { n, counts, total }
+    }
+
+    /// Count the letters within the `text`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::FrequencyTable;
+    ///
+    /// let table = FrequencyTable::letters("Hello, World!");
+    /// assert_eq!(3, table.count("l"));
+    /// assert_eq!(0.3, table.frequency("l"));
+    /// ```
+    ///
+    pub fn letters(text: &str) -> FrequencyTable {
+        FrequencyTable::new(text, 1)
+    }
+
+    /// Count the bigrams (pairs of letters) within the `text`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::FrequencyTable;
+    ///
+    /// let table = FrequencyTable::bigrams("Hello, World!");
+    /// assert_eq!(1, table.count("ow"));
+    /// ```
+    ///
+    pub fn bigrams(text: &str) -> FrequencyTable {
+        FrequencyTable::new(text, 2)
+    }
+
+    /// Count the trigrams (groups of three letters) within the `text`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::FrequencyTable;
+    ///
+    /// let table = FrequencyTable::trigrams("The theme of the thesis");
+    /// assert_eq!(4, table.count("the"));
+    /// ```
+    ///
+    pub fn trigrams(text: &str) -> FrequencyTable {
+        FrequencyTable::new(text, 3)
+    }
+
+    /// The length of the n-grams that were counted.
+    ///
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// The total number of n-grams that were counted.
+    ///
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// The number of times the n-gram `gram` appears. The case of the n-gram is ignored.
+    ///
+    pub fn count(&self, gram: &str) -> usize {
+        self.counts
+            .get(&gram.to_ascii_lowercase())
+            .cloned()
+            .unwrap_or(0)
+    }
+
+    /// The proportion of the n-grams that are `gram`, between 0 and 1. The case of the n-gram is
+    /// ignored.
+    ///
+    pub fn frequency(&self, gram: &str) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+
+        self.count(gram) as f64 / self.total as f64
+    }
+
+    /// The n-grams that were counted, along with their counts, from the most to the least common.
+    /// N-grams with the same count are in alphabetical order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::FrequencyTable;
+    ///
+    /// let table = FrequencyTable::letters("Mississippi");
+    /// assert_eq!(
+    ///     vec![(String::from("i"), 4), (String::from("s"), 4), (String::from("p"), 2), (String::from("m"), 1)],
+    ///     table.most_common()
+    /// );
+    /// ```
+    ///
+    pub fn most_common(&self) -> Vec<(String, usize)> {
+        let mut grams: Vec<(String, usize)> =
+            self.counts.iter().map(|(g, &c)| (g.clone(), c)).collect();
+        grams.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        grams
+    }
+
+    /// The index of coincidence of the n-grams: the probability that two n-grams chosen at
+    /// random from the text are the same.
+    ///
+    /// For the letters of English text this is around 0.066, while for random letters it is
+    /// around 0.038.
+    ///
+    pub fn index_of_coincidence(&self) -> f64 {
+        if self.total < 2 {
+            return 0.0;
+        }
+
+        let matches: usize = self.counts.values().map(|&c| c * (c - 1)).sum();
+        matches as f64 / (self.total * (self.total - 1)) as f64
+    }
+
+    /// The chi-squared statistic of the counts against the `expected` frequencies of some
+    /// n-grams. Only the n-grams of `expected` are compared. The lower the statistic, the more
+    /// closely the text matches the expected frequencies. The statistic is 0 if no n-grams were
+    /// counted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::frequency::ENGLISH_LETTERS;
+    /// use cipher_crypt::analysis::FrequencyTable;
+    ///
+    /// let english = FrequencyTable::letters("Defend the east wall of the castle");
+    /// let shifted = FrequencyTable::letters("Efgfoe uif fbtu xbmm pg uif dbtumf");
+    ///
+    /// assert!(english.chi_squared(&ENGLISH_LETTERS) < shifted.chi_squared(&ENGLISH_LETTERS));
+    /// ```
+    ///
+    pub fn chi_squared(&self, expected: &[(&str, f64)]) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+
+        expected
+            .iter()
+            .map(|&(gram, frequency)| {
+                let e = frequency * self.total as f64;
+                let difference = self.count(gram) as f64 - e;
+
+                difference * difference / e
+            })
+            .sum()
+    }
+
+    /// The log (base 10) likelihood of the counted n-grams, given the `expected` frequencies of
+    /// some n-grams. N-grams that are not in `expected` are given a floor frequency of a tenth of
+    /// the least frequent n-gram within it. The higher the likelihood, the more closely the text
+    /// matches the expected frequencies.
+    ///
+    /// Unlike the chi-squared statistic, this considers the order of the letters, so is suited to
+    /// comparing texts that contain the same letters in different orders.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::frequency::ENGLISH_BIGRAMS;
+    /// use cipher_crypt::analysis::FrequencyTable;
+    ///
+    /// let english = FrequencyTable::bigrams("Defend the east wall of the castle");
+    /// let transposed = FrequencyTable::bigrams("Dnhaw tcleedtees alo h atef  tlfes");
+    ///
+    /// assert!(english.log_likelihood(&ENGLISH_BIGRAMS) > transposed.log_likelihood(&ENGLISH_BIGRAMS));
+    /// ```
+    ///
+    pub fn log_likelihood(&self, expected: &[(&str, f64)]) -> f64 {
+        let floor = expected
+            .iter()
+            .map(|&(_, frequency)| frequency)
+            .fold(f64::INFINITY, f64::min)
+            / 10.0;
+
+        // Sum in the order of `expected`, so that equal tables always give equal likelihoods
+        let mut unexpected = self.total;
+        let likelihood: f64 = expected
+            .iter()
+            .map(|&(gram, frequency)| {
+                let count = self.count(gram);
+                unexpected -= count;
+
+                count as f64 * frequency.log10()
+            })
+            .sum();
+
+        likelihood + unexpected as f64 * floor.log10()
+    }
+
+    /// The chi-squared statistic of the counts against the frequencies expected of English.
+    ///
+    /// Returns `None` if the n-grams are longer than trigrams, as there are no built-in
+    /// frequencies to compare against.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::FrequencyTable;
+    ///
+    /// let english = FrequencyTable::letters("Defend the east wall of the castle");
+    /// let shifted = FrequencyTable::letters("Efgfoe uif fbtu xbmm pg uif dbtumf");
+    ///
+    /// assert!(english.english_chi_squared().unwrap() < shifted.english_chi_squared().unwrap());
+    /// assert_eq!(None, FrequencyTable::new("Defend the east wall", 4).english_chi_squared());
+    /// ```
+    ///
+    pub fn english_chi_squared(&self) -> Option<f64> {
+        self.language_chi_squared(Language::English)
+    }
+
+    /// The chi-squared statistic of the counts against the frequencies expected of a `language`.
+    ///
+    /// Returns `None` if the n-grams are longer than trigrams, as there are no built-in
+    /// frequencies to compare against.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::{FrequencyTable, Language};
+    ///
+    /// let table = FrequencyTable::letters("Le renard brun saute par-dessus le chien paresseux");
+    ///
+    /// assert!(
+    ///     table.language_chi_squared(Language::French).unwrap()
+    ///         < table.language_chi_squared(Language::German).unwrap()
+    /// );
+    /// ```
+    ///
+    pub fn language_chi_squared(&self, language: Language) -> Option<f64> {
+        language
+            .ngrams(self.n)
+            .map(|expected| self.chi_squared(expected))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_ignore_case_and_symbols() {
+        let table = FrequencyTable::letters("AaB b, c! 🗡️");
+
+        assert_eq!(5, table.total());
+        assert_eq!(2, table.count("a"));
+        assert_eq!(2, table.count("B"));
+        assert_eq!(0, table.count("z"));
+    }
+
+    #[test]
+    fn ngrams_span_words() {
+        let table = FrequencyTable::bigrams("The cat");
+
+        assert_eq!(5, table.total());
+        assert_eq!(1, table.count("ec"));
+    }
+
+    #[test]
+    fn short_text() {
+        let table = FrequencyTable::trigrams("ab");
+
+        assert_eq!(0, table.total());
+        assert_eq!(0.0, table.frequency("abc"));
+        assert_eq!(0.0, table.index_of_coincidence());
+    }
+
+    #[test]
+    fn index_of_coincidence() {
+        assert_eq!(1.0, FrequencyTable::letters("aaaa").index_of_coincidence());
+        assert_eq!(0.0, FrequencyTable::letters("abcd").index_of_coincidence());
+        assert_eq!(
+            1.0 / 3.0,
+            FrequencyTable::letters("aabb").index_of_coincidence()
+        );
+    }
+
+    #[test]
+    fn english_frequencies_sum_to_one() {
+        let sum: f64 = ENGLISH_LETTERS.iter().map(|(_, f)| f).sum();
+        assert!((sum - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn english_chi_squared() {
+        let text = "It was the best of times, it was the worst of times";
+
+        assert!(FrequencyTable::new(text, 3).english_chi_squared().is_some());
+        assert!(FrequencyTable::new(text, 4).english_chi_squared().is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_length_ngrams() {
+        FrequencyTable::new("abc", 0);
+    }
+}
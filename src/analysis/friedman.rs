@@ -0,0 +1,154 @@
+//! The Friedman test, devised by William F. Friedman in the 1920s, estimates the length of the
+//! key of a periodic polyalphabetic cipher (such as the Vigenère cipher) from its ciphertext.
+//!
+//! The test relies on the index of coincidence (kappa): the probability that two letters chosen
+//! at random from a text are the same. For English plaintext this is around `0.0667`, whereas for
+//! uniformly random letters it is `1/26`, or around `0.0385`. The more alphabets that a message has
+//! been spread across, the closer the kappa of its ciphertext is to that of random letters, so the
+//! kappa of the ciphertext gives an estimate of the number of alphabets used.
+//!
+//! As the estimate is only approximate (it is often far off for short messages), it is refined by
+//! splitting the ciphertext into columns, one for each letter of a candidate key. If the length
+//! of the candidate is right, each column was encrypted with a single alphabet and so has a kappa
+//! close to that of English.
+//!
+use super::period::column_ic;
+use super::FrequencyTable;
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+
+/// The approximate index of coincidence of English text.
+pub const KAPPA_ENGLISH: f64 = 0.0667;
+
+/// The index of coincidence of uniformly random letters.
+pub const KAPPA_RANDOM: f64 = 1.0 / 26.0;
+
+/// The estimated length of a key, as returned by the `friedman` test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeyLength {
+    /// The key length given by the Friedman formula, before it is rounded.
+    pub estimate: f64,
+    /// The most likely key length, found by checking the columns of the ciphertext for the
+    /// lengths up to twice the `estimate`.
+    pub length: usize,
+    /// How confident the test is in the `length`, between 0 and 1.
+    ///
+    /// This is how close the average kappa of the columns of the ciphertext is to that of
+    /// English, where 0 is the kappa of random letters or lower.
+    pub confidence: f64,
+}
+
+/// Estimate the length of the key used to encrypt a `ciphertext` with a periodic polyalphabetic
+/// cipher.
+///
+/// Only the letters `a-z` of the ciphertext are considered. Returns `None` if the ciphertext has
+/// fewer than two letters, or if its letters are so evenly distributed that no estimate can be
+/// made.
+///
+/// The test is statistical, so the longer the ciphertext, the more reliable the estimate.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::friedman;
+/// use cipher_crypt::{Caesar, Cipher};
+///
+/// let c = Caesar::new(7);
+/// let ciphertext = c
+///     .encrypt("It was the best of times, it was the worst of times, it was the age of wisdom, \
+///         it was the age of foolishness, it was the epoch of belief, it was the epoch of \
+///         incredulity, it was the season of Light, it was the season of Darkness")
+///     .unwrap();
+///
+/// let key_length = friedman(&ciphertext).unwrap();
+/// assert_eq!(1, key_length.length);
+/// assert!(key_length.confidence > 0.5);
+/// ```
+///
+pub fn friedman(ciphertext: &str) -> Option<KeyLength> {
+    let letters = alphabet::STANDARD.scrub(ciphertext).to_ascii_lowercase();
+    let n = letters.len() as f64;
+    if letters.len() < 2 {
+        return None;
+    }
+
+    let kappa = FrequencyTable::letters(&letters).index_of_coincidence();
+    let estimate = ((KAPPA_ENGLISH - KAPPA_RANDOM) * n)
+        / ((KAPPA_ENGLISH - kappa) + n * (kappa - KAPPA_RANDOM));
+    if !estimate.is_finite() || estimate <= 0.0 {
+        return None;
+    }
+
+    // Multiples of the key length also have columns with a kappa close to that of English, so
+    // the shortest length that is nearly as good as the best is taken
+    let longest = ((estimate * 2.0).ceil() as usize).clamp(1, (letters.len() / 2).max(1));
+    let candidates: Vec<(usize, f64)> = (1..=longest)
+        .map(|length| (length, confidence(&letters, length)))
+        .collect();
+    let best = candidates.iter().map(|&(_, c)| c).fold(0.0, f64::max);
+    let (length, confidence) = candidates
+        .into_iter()
+        .find(|&(_, c)| c >= best * 0.9)
+        .unwrap();
+
+    Some(KeyLength {
+        estimate,
+        length,
+        confidence,
+    })
+}
+
+/// How close the average kappa of the `letters` split into `length` columns is to that of
+/// English, between 0 and 1.
+///
+fn confidence(letters: &str, length: usize) -> f64 {
+    let kappa = column_ic(letters, length);
+    ((kappa - KAPPA_RANDOM) / (KAPPA_ENGLISH - KAPPA_RANDOM)).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cipher, Vigenere};
+
+    const PLAINTEXT: &str = "It is a truth universally acknowledged, that a single man in \
+        possession of a good fortune, must be in want of a wife. However little known the feelings \
+        or views of such a man may be on his first entering a neighbourhood, this truth is so well \
+        fixed in the minds of the surrounding families, that he is considered the rightful property \
+        of some one or other of their daughters. My dear Mr. Bennet, said his lady to him one day, \
+        have you heard that Netherfield Park is let at last? Mr. Bennet replied that he had not. \
+        But it is, returned she; for Mrs. Long has just been here, and she told me all about it. \
+        Mr. Bennet made no answer. Do you not want to know who has taken it? cried his wife \
+        impatiently. You want to tell me, and I have no objection to hearing it. This was \
+        invitation enough.";
+
+    #[test]
+    fn vigenere_key_length() {
+        let v = Vigenere::new(String::from("lemon"));
+        let key_length = friedman(&v.encrypt(PLAINTEXT).unwrap()).unwrap();
+
+        assert_eq!(5, key_length.length);
+        assert!(key_length.confidence > 0.9);
+    }
+
+    #[test]
+    fn confidence_of_correct_length() {
+        let v = Vigenere::new(String::from("lemon"));
+        let letters = alphabet::STANDARD
+            .scrub(&v.encrypt(PLAINTEXT).unwrap())
+            .to_ascii_lowercase();
+
+        assert!(confidence(&letters, 5) > 0.7);
+        assert!(confidence(&letters, 5) > confidence(&letters, 4));
+    }
+
+    #[test]
+    fn plaintext_key_length() {
+        assert_eq!(1, friedman(PLAINTEXT).unwrap().length);
+    }
+
+    #[test]
+    fn too_short() {
+        assert!(friedman("a 🗡️").is_none());
+    }
+}
@@ -0,0 +1,265 @@
+//! A genetic search over the keys of a cipher.
+//!
+//! A genetic algorithm evolves a population of keys, rather than improving a single key. Each
+//! generation, the fittest keys are carried over unchanged, and the rest of the next generation
+//! is bred from parents chosen by tournament: the fittest of a few keys picked at random. Each
+//! child combines the keys of its two parents with `KeySearch::crossover()`, and is sometimes
+//! mutated too.
+//!
+use super::solver::{evaluate, record, seeded_rng, KeySearch, Optimizer, Progress};
+use super::Candidate;
+use rand::rngs::StdRng;
+use rand::Rng;
+
+/// The number of keys picked at random for each tournament to choose a parent.
+const TOURNAMENT_SIZE: usize = 3;
+
+/// A genetic search over the keys of a cipher.
+///
+/// This struct is created by the `new()` method, and configured by its builder methods.
+#[derive(Clone, Debug)]
+pub struct Genetic {
+    population: usize,
+    generations: usize,
+    mutation_rate: f64,
+    seed: Option<u64>,
+}
+
+impl Default for Genetic {
+    fn default() -> Genetic {
+        Genetic::new()
+    }
+}
+
+impl Genetic {
+    /// Initialise a genetic search of 200 generations of 100 keys, where a fifth of the children
+    /// are mutated, with random choices that differ from one search to the next.
+    ///
+    pub fn new() -> Genetic {
+        Genetic {
+            population: 100,
+            generations: 200,
+            mutation_rate: 0.2,
+            seed: None,
+        }
+    }
+
+    /// Set the number of keys in each generation.
+    ///
+    /// # Panics
+    /// * `population` is less than 2.
+    ///
+    pub fn population(mut self, population: usize) -> Genetic {
+        if population < 2 {
+            panic!("The population must contain at least two keys.");
+        }
+
+        self.population = population;
+        self
+    }
+
+    /// Set the number of generations bred after the first.
+    ///
+    pub fn generations(mut self, generations: usize) -> Genetic {
+        self.generations = generations;
+        self
+    }
+
+    /// Set the chance that a child is mutated after it is bred.
+    ///
+    /// # Panics
+    /// * `mutation_rate` is not in the inclusive range `0 - 1`.
+    ///
+    pub fn mutation_rate(mut self, mutation_rate: f64) -> Genetic {
+        if !(0.0..=1.0).contains(&mutation_rate) {
+            panic!("The mutation rate must be within the range 0 <= n <= 1.");
+        }
+
+        self.mutation_rate = mutation_rate;
+        self
+    }
+
+    /// Seed the random choices of the search, so that it is repeatable.
+    ///
+    pub fn seed(mut self, seed: u64) -> Genetic {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl Optimizer for Genetic {
+    /// Search the keys of a cipher with a genetic algorithm, reporting each better candidate to a
+    /// `callback`.
+    ///
+    /// The `iteration` of the progress given to the `callback` is the generation in which the
+    /// candidate was found, and its `restart` is always 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::solver::{Optimizer, SubstitutionSearch};
+    /// use cipher_crypt::analysis::{FrequencyTable, Genetic, Language};
+    ///
+    /// let search = SubstitutionSearch::new("Pybb cbvd dxkiyb");
+    /// let fitness = |text: &str| {
+    ///     FrequencyTable::letters(text).log_likelihood(Language::English.letters())
+    /// };
+    ///
+    /// let best = Genetic::new()
+    ///     .seed(1)
+    ///     .population(20)
+    ///     .generations(10)
+    ///     .optimize(&search, fitness);
+    ///
+    /// assert!((fitness(&best.plaintext) - best.score).abs() < 1e-9);
+    /// ```
+    ///
+    fn optimize_with<S, F, C>(&self, search: &S, fitness: F, mut callback: C) -> Candidate<S::Key>
+    where
+        S: KeySearch,
+        F: Fn(&str) -> f64,
+        C: FnMut(&Progress<S::Key>) -> bool,
+    {
+        let mut rng = seeded_rng(self.seed);
+        let elite = (self.population / 10).max(1);
+
+        let mut best = None;
+        let mut population: Vec<Candidate<S::Key>> = (0..self.population)
+            .map(|_| evaluate(search, &fitness, search.random_key(&mut rng)))
+            .collect();
+
+        for generation in 0..=self.generations {
+            if generation > 0 {
+                let mut next: Vec<Candidate<S::Key>> = population[..elite].to_vec();
+                while next.len() < self.population {
+                    let first = &tournament(&population, &mut rng).key;
+                    let second = &tournament(&population, &mut rng).key;
+                    let mut child = search.crossover(first, second, &mut rng);
+                    if rng.gen::<f64>() < self.mutation_rate {
+                        child = search.mutate(&child, &mut rng);
+                    }
+
+                    next.push(evaluate(search, &fitness, child));
+                }
+                population = next;
+            }
+
+            population.sort_by(|a, b| b.score.total_cmp(&a.score));
+            if !record(&mut best, &population[0], 0, generation, &mut callback) {
+                break;
+            }
+        }
+
+        best.unwrap()
+    }
+}
+
+/// Choose the fittest of a few candidates picked at random from a `population`.
+///
+fn tournament<'a, K>(population: &'a [Candidate<K>], rng: &mut StdRng) -> &'a Candidate<K> {
+    (0..TOURNAMENT_SIZE)
+        .map(|_| &population[rng.gen_range(0..population.len())])
+        .max_by(|a, b| a.score.total_cmp(&b.score))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::SubstitutionSearch;
+    use rand::seq::SliceRandom;
+    use rand::{RngCore, SeedableRng};
+
+    /// Search for a permutation of the numbers 0 to 7, mutated by swapping two of them.
+    struct Permutation;
+
+    impl KeySearch for Permutation {
+        type Key = Vec<usize>;
+
+        fn random_key(&self, rng: &mut dyn RngCore) -> Vec<usize> {
+            let mut key: Vec<usize> = (0..8).collect();
+            key.shuffle(rng);
+            key
+        }
+
+        fn mutate(&self, key: &Vec<usize>, rng: &mut dyn RngCore) -> Vec<usize> {
+            let mut key = key.clone();
+            key.swap(rng.gen_range(0..8), rng.gen_range(0..8));
+            key
+        }
+
+        fn decrypt(&self, key: &Vec<usize>) -> String {
+            key.iter()
+                .map(|i| "abcdefgh".chars().nth(*i).unwrap())
+                .collect()
+        }
+    }
+
+    fn sorted(text: &str) -> f64 {
+        text.chars()
+            .zip("abcdefgh".chars())
+            .filter(|(a, b)| a == b)
+            .count() as f64
+    }
+
+    #[test]
+    fn finds_optimum() {
+        let best = Genetic::new().seed(3).optimize(&Permutation, sorted);
+
+        assert_eq!("abcdefgh", best.plaintext);
+        assert_eq!(8.0, best.score);
+    }
+
+    #[test]
+    fn seeded_is_repeatable() {
+        let genetic = Genetic::new().seed(11).population(10).generations(5);
+
+        assert_eq!(
+            genetic.optimize(&Permutation, sorted),
+            genetic.optimize(&Permutation, sorted)
+        );
+    }
+
+    #[test]
+    fn best_never_worsens() {
+        let mut scores = Vec::new();
+        Genetic::new()
+            .seed(5)
+            .generations(20)
+            .optimize_with(&Permutation, sorted, |progress| {
+                scores.push(progress.best.score);
+                true
+            });
+
+        assert!(scores.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn substitution_crossover() {
+        let search = SubstitutionSearch::new("");
+        let mut rng = StdRng::seed_from_u64(9);
+        let first = search.random_key(&mut rng);
+        let second = search.random_key(&mut rng);
+
+        for _ in 0..20 {
+            let mut child: Vec<char> = search
+                .crossover(&first, &second, &mut rng)
+                .chars()
+                .collect();
+            child.sort_unstable();
+            assert_eq!(('a'..='z').collect::<Vec<char>>(), child);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn small_population() {
+        Genetic::new().population(1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_mutation_rate() {
+        Genetic::new().mutation_rate(1.5);
+    }
+}
@@ -0,0 +1,195 @@
+//! An attack on homophonic substitution ciphers.
+//!
+//! A homophonic cipher flattens the letter frequencies of its ciphertext by giving each letter
+//! several symbols, so frequency analysis alone cannot break it. Each symbol still stands for a
+//! single letter though, so the cipher can be broken by searching the assignments of letters to
+//! symbols for the one whose plaintext scores best under n-gram statistics (such as
+//! `QuadgramScorer::score`).
+//!
+//! Unlike a Simple Substitution, the number of symbols given to each letter is unknown. The search
+//! therefore changes the letter of a single symbol at a time, rather than only swapping the
+//! letters of two symbols, so that the number of symbols of each letter can change.
+//!
+//! With so many symbols to assign, n-gram scores alone tend to lead the search to plaintexts that
+//! repeat a few common letters (such as `eseseth...`). Subtracting a multiple of the
+//! `english_chi_squared()` of the candidate's letter frequencies from its score keeps the search
+//! near English frequencies, and several hundred letters of ciphertext are needed for the search
+//! to succeed.
+//!
+//! The Mexican Army cipher disk and the Grandpré cipher are not implemented by this crate, but
+//! their ciphertexts can be searched in the same way once written as symbols separated by spaces.
+//!
+use super::frequency::ENGLISH_LETTERS;
+use super::solver::KeySearch;
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::{Rng, RngCore};
+
+/// The key space of a homophonic substitution cipher, for a `HillClimber`, `SimulatedAnnealing`
+/// or `Genetic` search.
+///
+/// The symbols of the ciphertext are separated by whitespace, as they are in the output of
+/// `Homophonic::encrypt()`. Each key holds the plaintext letter of each distinct symbol, in the
+/// order given by `symbols()`. A random key draws the letters in proportion to their frequency in
+/// English. A key is mutated by giving one symbol a new letter, or by swapping the letters of two
+/// symbols, and two keys are combined by taking the letter of each symbol from either of them.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::{HomophonicSearch, KeySearch};
+///
+/// let search = HomophonicSearch::new("12 07 31 07 12");
+///
+/// assert_eq!(&["12", "07", "31"], search.symbols());
+/// assert_eq!("level", search.decrypt(&String::from("lev")));
+/// ```
+#[derive(Clone, Debug)]
+pub struct HomophonicSearch {
+    symbols: Vec<String>,
+    ciphertext: Vec<usize>,
+}
+
+impl HomophonicSearch {
+    /// Initialise a search for the key of a homophonic `ciphertext`, whose symbols are separated
+    /// by whitespace.
+    ///
+    pub fn new(ciphertext: &str) -> HomophonicSearch {
+        let mut symbols: Vec<String> = Vec::new();
+        let ciphertext = ciphertext
+            .split_whitespace()
+            .map(|token| match symbols.iter().position(|s| s == token) {
+                Some(index) => index,
+                None => {
+                    symbols.push(token.to_string());
+                    symbols.len() - 1
+                }
+            })
+            .collect();
+
+        HomophonicSearch {
+            symbols,
+            ciphertext,
+        }
+    }
+
+    /// The distinct symbols of the ciphertext, in the order in which they first appear.
+    ///
+    pub fn symbols(&self) -> &[String] {
+        &self.symbols
+    }
+}
+
+impl KeySearch for HomophonicSearch {
+    type Key = String;
+
+    fn random_key(&self, rng: &mut dyn RngCore) -> String {
+        let letters = WeightedIndex::new(ENGLISH_LETTERS.iter().map(|&(_, f)| f)).unwrap();
+
+        (0..self.symbols.len())
+            .map(|_| alphabet::STANDARD.get_letter(letters.sample(rng), false))
+            .collect()
+    }
+
+    fn mutate(&self, key: &String, rng: &mut dyn RngCore) -> String {
+        let mut letters: Vec<char> = key.chars().collect();
+        if letters.is_empty() {
+            return key.clone();
+        }
+
+        let (a, b) = (
+            rng.gen_range(0..letters.len()),
+            rng.gen_range(0..letters.len()),
+        );
+        if rng.gen_bool(0.5) {
+            letters[a] = alphabet::STANDARD.get_letter(rng.gen_range(0..26), false);
+        } else {
+            letters.swap(a, b);
+        }
+
+        letters.into_iter().collect()
+    }
+
+    fn crossover(&self, first: &String, second: &String, rng: &mut dyn RngCore) -> String {
+        first
+            .chars()
+            .zip(second.chars())
+            .map(|(a, b)| if rng.gen() { a } else { b })
+            .collect()
+    }
+
+    /// Decrypt the ciphertext with a `key`, giving the plaintext in lowercase without spaces.
+    ///
+    /// # Panics
+    /// * The `key` has fewer letters than there are `symbols()`.
+    ///
+    fn decrypt(&self, key: &String) -> String {
+        let letters: Vec<char> = key.chars().collect();
+
+        self.ciphertext.iter().map(|&s| letters[s]).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::cipher::Cipher;
+    use crate::Homophonic;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn symbols_in_order() {
+        let search = HomophonicSearch::new(" 5  3\n5 9 ");
+
+        assert_eq!(&["5", "3", "9"], search.symbols());
+        assert_eq!("abaz", search.decrypt(&String::from("abz")));
+    }
+
+    #[test]
+    fn decrypts_with_key() {
+        let h = Homophonic::seeded(
+            (b'a'..=b'z')
+                .map(|c| {
+                    let c = c as char;
+                    (c, vec![format!("{}1", c), format!("{}2", c)])
+                })
+                .collect(),
+            7,
+        );
+        let c = h.encrypt("Attack at dawn").unwrap();
+        let search = HomophonicSearch::new(&c);
+        let key: String = search
+            .symbols()
+            .iter()
+            .map(|s| s.chars().next().unwrap())
+            .collect();
+
+        assert_eq!("attackatdawn", search.decrypt(&key));
+    }
+
+    #[test]
+    fn keys_cover_symbols() {
+        let search = HomophonicSearch::new("01 02 03 04 05 01");
+        let mut rng = StdRng::seed_from_u64(3);
+        let first = search.random_key(&mut rng);
+        let second = search.mutate(&first, &mut rng);
+        let child = search.crossover(&first, &second, &mut rng);
+
+        for key in &[first, second, child] {
+            assert_eq!(5, key.len());
+            assert!(key.chars().all(|c| c.is_ascii_lowercase()));
+        }
+    }
+
+    #[test]
+    fn empty_ciphertext() {
+        let search = HomophonicSearch::new("");
+        let mut rng = StdRng::seed_from_u64(3);
+        let key = search.random_key(&mut rng);
+
+        assert_eq!("", search.mutate(&key, &mut rng));
+        assert_eq!("", search.decrypt(&key));
+    }
+}
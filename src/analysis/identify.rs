@@ -0,0 +1,356 @@
+//! Identifies which of the ciphers of this crate is likely to have produced a ciphertext.
+//!
+//! The first step in breaking an unknown ciphertext is to work out how it was made. Many of the
+//! ciphers leave clues in their output: some use only a particular set of symbols (such as the
+//! letters of `ADFGVX`, or groups of digits), and others change the statistics of the letters in
+//! ways that can be measured. A monoalphabetic substitution keeps the index of coincidence of the
+//! plaintext, and a transposition keeps its letter frequencies too, whereas a polyalphabetic
+//! cipher flattens them towards those of random letters.
+//!
+//! These clues can only narrow down the possibilities, so the guesses are ranked by how well the
+//! ciphertext matches each cipher.
+//!
+use super::frequency::ENGLISH_LETTERS;
+use super::FrequencyTable;
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::pigpen;
+
+/// The index of coincidence above which the letters are considered to keep the statistics of
+/// English.
+const KAPPA_THRESHOLD: f64 = 0.055;
+
+/// The chi-squared statistic (per letter) below which the letters are considered to have the
+/// frequencies of English.
+const CHI_SQUARED_THRESHOLD: f64 = 0.5;
+
+/// A guess at the cipher that produced a ciphertext, as returned by `identify`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Guess {
+    /// The name of the cipher, as it is exported by this crate (e.g. `"Vigenere"`).
+    pub cipher: &'static str,
+    /// How likely the cipher is compared with the other guesses, between 0 and 1. The
+    /// likelihoods of the guesses sum to 1.
+    pub likelihood: f64,
+}
+
+/// Guess which of the ciphers of this crate produced a `ciphertext`.
+///
+/// The guesses are made from the symbols of the ciphertext, the way it is grouped, and the
+/// statistics of its letters (such as the index of coincidence and the letter frequencies). They
+/// are returned from the most to the least likely. An empty `Vec` is returned if the ciphertext
+/// has no symbols other than whitespace.
+///
+/// The statistical guesses assume that the plaintext is English, and are more reliable the
+/// longer the ciphertext.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::identify;
+/// use cipher_crypt::{Cipher, Railfence, Vigenere};
+///
+/// let m = "It was the best of times, it was the worst of times, it was the age of wisdom, it \
+///     was the age of foolishness, it was the epoch of belief, it was the epoch of incredulity";
+///
+/// let transposed = Railfence::new(4).encrypt(m).unwrap();
+/// assert_eq!("Railfence", identify(&transposed)[0].cipher);
+///
+/// let substituted = Vigenere::new(String::from("dickens")).encrypt(m).unwrap();
+/// assert_eq!("Vigenere", identify(&substituted)[0].cipher);
+///
+/// assert_eq!("ADFGVX", identify("FA GV AD DX XG VV")[0].cipher);
+/// ```
+///
+pub fn identify(ciphertext: &str) -> Vec<Guess> {
+    let symbols: Vec<char> = ciphertext.chars().filter(|c| !c.is_whitespace()).collect();
+    if symbols.is_empty() {
+        return Vec::new();
+    }
+
+    let groups: Vec<&str> = ciphertext.split_whitespace().collect();
+    // The ADFGVX cipher keeps the punctuation of the plaintext, so only its letters are checked
+    let letters: Vec<char> = symbols
+        .iter()
+        .copied()
+        .filter(|c| c.is_alphabetic())
+        .collect();
+    let weights = if symbols.iter().all(|&c| c == '.') {
+        vec![("TapCode", 1.0)]
+    } else if letters.len() * 2 > symbols.len()
+        && letters.iter().all(|c| "ADFGVXadfgvx".contains(*c))
+    {
+        vec![("ADFGVX", 1.0)]
+    } else if symbols.iter().all(|c| pigpen_symbol(*c)) {
+        vec![("Pigpen", 1.0)]
+    } else if symbols
+        .iter()
+        .any(|c| ('\u{1D608}'..='\u{1D63B}').contains(c))
+    {
+        vec![("Baconian", 1.0)]
+    } else if symbols.iter().all(char::is_ascii_digit) {
+        numeric(&symbols, &groups)
+    } else {
+        alphabetic(&symbols, &groups)
+    };
+
+    let total: f64 = weights.iter().map(|&(_, w)| w).sum();
+    let mut guesses: Vec<Guess> = weights
+        .into_iter()
+        .map(|(cipher, w)| Guess {
+            cipher,
+            likelihood: w / total,
+        })
+        .collect();
+    guesses.sort_by(|a, b| b.likelihood.total_cmp(&a.likelihood));
+
+    guesses
+}
+
+/// Whether `c` is used by either of the Pigpen symbol tables.
+///
+fn pigpen_symbol(c: char) -> bool {
+    pigpen::STANDARD
+        .iter()
+        .chain(pigpen::ROSICRUCIAN.iter())
+        .any(|s| s.contains(c))
+}
+
+/// Weigh the ciphers that write their ciphertext as digits, by the way the digits are grouped.
+///
+fn numeric(symbols: &[char], groups: &[&str]) -> Vec<(&'static str, f64)> {
+    let all_of_length = |n: usize| groups.iter().all(|g| g.len() == n);
+
+    if all_of_length(5) {
+        vec![("VIC", 1.0)]
+    } else if all_of_length(3) {
+        vec![("PortaDigraphic", 1.0)]
+    } else if all_of_length(2) {
+        // The Uesugi square is labelled with the numbers 1 to 7
+        let uesugi = if symbols.iter().all(|c| ('1'..='7').contains(c)) {
+            2.0
+        } else {
+            0.0
+        };
+
+        vec![
+            ("Homophonic", 1.0),
+            ("Syllabary", 1.0),
+            ("GreatCipher", 0.5),
+            ("Uesugi", uesugi),
+        ]
+    } else {
+        vec![("GreatCipher", 1.0), ("Homophonic", 0.25)]
+    }
+}
+
+/// Weigh the ciphers that write their ciphertext as letters, by the statistics of the letters.
+///
+fn alphabetic(symbols: &[char], groups: &[&str]) -> Vec<(&'static str, f64)> {
+    let letters: String = symbols
+        .iter()
+        .filter(|c| alphabet::STANDARD.find_position(**c).is_some())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if letters.is_empty() {
+        return Vec::new();
+    }
+
+    let table = FrequencyTable::letters(&letters);
+    let english_kappa = table.index_of_coincidence() >= KAPPA_THRESHOLD;
+    let english_frequencies = |text: &str| {
+        let table = FrequencyTable::letters(text);
+        table.chi_squared(&ENGLISH_LETTERS) < CHI_SQUARED_THRESHOLD * table.total() as f64
+    };
+    let shifted = |shift: usize| -> String {
+        letters
+            .chars()
+            .map(|c| {
+                let pos = alphabet::STANDARD.find_position(c).unwrap();
+                alphabet::STANDARD.get_letter((pos + shift) % 26, false)
+            })
+            .collect()
+    };
+    let caesar_shift = (1..26)
+        .min_by(|&a, &b| {
+            let chi_squared =
+                |shift| FrequencyTable::letters(&shifted(shift)).chi_squared(&ENGLISH_LETTERS);
+            chi_squared(a).total_cmp(&chi_squared(b))
+        })
+        .filter(|&shift| english_frequencies(&shifted(shift)));
+
+    let mut weights = Vec::new();
+    if english_kappa && english_frequencies(&letters) {
+        // Only the order of the letters has changed
+        weights.extend_from_slice(&[
+            ("Railfence", 1.0),
+            ("ColumnarTransposition", 1.0),
+            ("Scytale", 0.75),
+            ("CaesarBox", 0.75),
+            ("Redefence", 0.5),
+            ("RS44", 0.5),
+        ]);
+    } else if english_kappa && caesar_shift.is_some() {
+        let rot13 = if caesar_shift == Some(13) { 1.5 } else { 0.0 };
+        weights.extend_from_slice(&[
+            ("Caesar", 1.0),
+            ("Rot13", rot13),
+            ("KeywordCaesar", 0.25),
+            ("Affine", 0.25),
+        ]);
+    } else if english_kappa {
+        // The Wolseley cipher merges j into i, so never writes a j
+        let wolseley = if letters.contains('j') { 0.0 } else { 0.5 };
+        weights.extend_from_slice(&[
+            ("SimpleSubstitution", 1.0),
+            ("Keyword", 1.0),
+            ("Affine", 0.75),
+            ("KamaSutra", 0.75),
+            ("Wolseley", wolseley),
+        ]);
+    } else {
+        weights.extend_from_slice(&[
+            ("Vigenere", 1.0),
+            ("Beaufort", 0.75),
+            ("Autokey", 0.75),
+            ("Porta", 0.5),
+            ("Quagmire", 0.5),
+            ("Gronsfeld", 0.5),
+            ("Bifid", 0.5),
+            ("Trifid", 0.25),
+            ("FractionatedMorse", 0.25),
+            ("Hill", 0.25),
+        ]);
+
+        // The Playfair cipher has no j, and never encrypts a pair of identical letters
+        let pairs: Vec<char> = letters.chars().collect();
+        if !letters.contains('j')
+            && pairs.len().is_multiple_of(2)
+            && pairs.chunks(2).all(|p| p[0] != p[1])
+        {
+            weights.push(("Playfair", 2.0));
+        }
+
+        // The M-209 prints its ciphertext in groups of five letters
+        if groups.len() > 1 && groups.iter().all(|g| g.len() == 5) {
+            weights.push(("M209", 2.0));
+        }
+    }
+
+    weights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::porta_digraphic::Output;
+    use crate::uesugi::Script;
+    use crate::{
+        ADFGVXKey, Caesar, Cipher, Homophonic, Playfair, PlayfairKey, PortaDigraphic,
+        PortaDigraphicKey, Rot13, SimpleSubstitution, TapCode, TapCodeKey, Uesugi, UesugiKey,
+        Vigenere, ADFGVX,
+    };
+
+    const PLAINTEXT: &str = "It is a truth universally acknowledged, that a single man in \
+        possession of a good fortune, must be in want of a wife. However little known the feelings \
+        or views of such a man may be on his first entering a neighbourhood, this truth is so well \
+        fixed in the minds of the surrounding families, that he is considered the rightful property \
+        of some one or other of their daughters.";
+
+    fn best(ciphertext: &str) -> &'static str {
+        identify(ciphertext)[0].cipher
+    }
+
+    #[test]
+    fn likelihoods_sum_to_one() {
+        let guesses = identify(PLAINTEXT);
+        let total: f64 = guesses.iter().map(|g| g.likelihood).sum();
+
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(guesses
+            .windows(2)
+            .all(|w| w[0].likelihood >= w[1].likelihood));
+    }
+
+    #[test]
+    fn monoalphabetic() {
+        assert_eq!("Caesar", best(&Caesar::new(7).encrypt(PLAINTEXT).unwrap()));
+        assert_eq!("Rot13", best(&Rot13::encrypt(PLAINTEXT)));
+
+        let s = SimpleSubstitution::new(String::from("phqgiumeaylnofdxjkrcvstzwb"));
+        assert_eq!("SimpleSubstitution", best(&s.encrypt(PLAINTEXT).unwrap()));
+    }
+
+    #[test]
+    fn polyalphabetic() {
+        let v = Vigenere::new(String::from("bennet"));
+        assert_eq!("Vigenere", best(&v.encrypt(PLAINTEXT).unwrap()));
+    }
+
+    #[test]
+    fn playfair() {
+        let p = Playfair::new(PlayfairKey {
+            keystream: String::from("pemberley"),
+            null_char: Some('Q'),
+        });
+        let m: String = PLAINTEXT
+            .chars()
+            .filter(char::is_ascii_alphabetic)
+            .collect();
+
+        assert_eq!("Playfair", best(&p.encrypt(&m).unwrap()));
+    }
+
+    #[test]
+    fn numeric() {
+        let h = Homophonic::from_keyword("longbourn");
+        let m: String = PLAINTEXT
+            .chars()
+            .filter(|c| c.is_ascii_alphabetic() || c.is_whitespace())
+            .collect();
+        assert!(identify(&h.encrypt(&m).unwrap())
+            .iter()
+            .any(|g| g.cipher == "Homophonic"));
+
+        let labels = vec![1, 2, 3, 4, 5, 6, 7];
+        let u = Uesugi::new(UesugiKey {
+            row_labels: labels.clone(),
+            column_labels: labels,
+            script: Script::Kana,
+        });
+        assert_eq!("Uesugi", best(&u.encrypt("こんやのおつきみ").unwrap()));
+
+        let p = PortaDigraphic::new(PortaDigraphicKey {
+            row_keyword: String::from("fortune"),
+            column_keyword: String::from("silent"),
+            output: Output::Numeric,
+        });
+        assert_eq!("PortaDigraphic", best(&p.encrypt("Meet me").unwrap()));
+    }
+
+    #[test]
+    fn adfgvx() {
+        let a = ADFGVX::new(ADFGVXKey {
+            polybius_key: String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
+            columnar_key: String::from("GERMAN"),
+            null_char: None,
+        });
+        assert_eq!("ADFGVX", best(&a.encrypt(PLAINTEXT).unwrap()));
+        assert_ne!("ADFGVX", best("'A' - \"D\"?"));
+    }
+
+    #[test]
+    fn symbols() {
+        let t = TapCode::new(TapCodeKey {
+            tap: '.',
+            separator: ' ',
+        });
+        assert_eq!("TapCode", best(&t.encrypt("Lex").unwrap()));
+        assert_eq!("Pigpen", best("⌟>>⌟⌞⊔· ⌟> ⊐⌟∨·□·"));
+    }
+
+    #[test]
+    fn empty() {
+        assert!(identify(" \n").is_empty());
+    }
+}
@@ -0,0 +1,327 @@
+//! Iterators over the keys of the ciphers of this crate.
+//!
+//! Each iterator yields keys in the form taken by the `new()` method of its cipher, so that a
+//! brute-force attack can be written against the `Cipher` trait:
+//!
+//! ```
+//! use cipher_crypt::analysis::keyspace;
+//! use cipher_crypt::{Affine, Cipher};
+//!
+//! let c = "Hmmhnl hm qhvu!";
+//! let m = keyspace::affine()
+//!     .map(|key| Affine::new(key).decrypt(c).unwrap())
+//!     .find(|m| m.contains("dawn"));
+//!
+//! assert_eq!(Some(String::from("Attack at dawn!")), m);
+//! ```
+//!
+//! Where a cipher has a key space too large to try in full (such as all the keywords of a
+//! Vigenère cipher), the iterator covers the keys up to a given size.
+//!
+use crate::affine::AffineKey;
+use crate::columnar_transposition::ColumnarTranspositionKey;
+use crate::keyboard_shift::{Direction, KeyboardShiftKey, Layout};
+use crate::redefence::RedefenceKey;
+use rulinalg::matrix::Matrix;
+
+/// The shifts of a Caesar cipher, `1` to `26`.
+///
+pub fn caesar() -> impl Iterator<Item = usize> {
+    1..=26
+}
+
+/// The keys `a` and `b` of an Affine cipher, where `a` has no factor in common with 26.
+///
+/// There are 312 keys, with `a` and `b` each in the range `1` to `26`.
+///
+pub fn affine() -> impl Iterator<Item = AffineKey> {
+    (1..=26)
+        .filter(|a| a % 2 != 0 && a % 13 != 0)
+        .flat_map(|a| (1..=26).map(move |b| AffineKey { a, b }))
+}
+
+/// The numbers of rails of a Railfence cipher, from `1` up to `max_rails`.
+///
+pub fn railfence(max_rails: usize) -> impl Iterator<Item = usize> {
+    1..=max_rails
+}
+
+/// The keys of a Redefence cipher with up to `max_rails` rails: each order of the rails, with
+/// each of the offsets into the zigzag.
+///
+/// The number of keys grows with the factorial of `max_rails`.
+///
+pub fn redefence(max_rails: usize) -> impl Iterator<Item = RedefenceKey> {
+    (1..=max_rails).flat_map(|rails| {
+        let offsets = (2 * rails - 2).max(1);
+        permutations(rails).flat_map(move |order| {
+            let order: Vec<usize> = order.into_iter().map(|r| r + 1).collect();
+            (0..offsets).map(move |offset| RedefenceKey {
+                order: order.clone(),
+                offset,
+            })
+        })
+    })
+}
+
+/// The heights of a Scytale cipher, from `1` up to `max_height`.
+///
+pub fn scytale(max_height: usize) -> impl Iterator<Item = usize> {
+    1..=max_height
+}
+
+/// The keys of a Caesar Box cipher with a square up to `max_size` long: `None`, followed by each
+/// size of square from `1`.
+///
+pub fn caesar_box(max_size: usize) -> impl Iterator<Item = Option<usize>> {
+    std::iter::once(None).chain((1..=max_size).map(Some))
+}
+
+/// The keys of a Columnar Transposition cipher with `columns` columns, without a null character.
+///
+/// As the cipher depends only on the alphabetical order of the letters of its key, each key is a
+/// permutation of the first `columns` letters of the alphabet, and the `columns!` keys cover
+/// every ordering of the columns.
+///
+/// # Panics
+/// * `columns` is not in the inclusive range `1 - 26`.
+///
+pub fn columnar_transposition(columns: usize) -> impl Iterator<Item = ColumnarTranspositionKey> {
+    if !(1..=26).contains(&columns) {
+        panic!("The number of columns must be within the range 1 <= n <= 26.");
+    }
+
+    permutations(columns).map(|order| {
+        let keystream = order
+            .into_iter()
+            .map(|i| (b'a' + i as u8) as char)
+            .collect();
+        ColumnarTranspositionKey {
+            keystream,
+            null_char: None,
+        }
+    })
+}
+
+/// The keys of a Keyboard Shift cipher: each layout, direction and distinct shift.
+///
+/// As the shifts wrap around the rows of ten keys, and the columns of four keys, the shifts are
+/// `1` to `9` to the left or right, and `1` to `3` up or down.
+///
+pub fn keyboard_shift() -> impl Iterator<Item = KeyboardShiftKey> {
+    [Layout::Qwerty, Layout::Azerty, Layout::Dvorak]
+        .iter()
+        .flat_map(|&layout| {
+            [
+                (Direction::Left, 9),
+                (Direction::Right, 9),
+                (Direction::Up, 3),
+                (Direction::Down, 3),
+            ]
+            .iter()
+            .flat_map(move |&(direction, shifts)| {
+                (1..=shifts).map(move |shift| KeyboardShiftKey {
+                    layout,
+                    direction,
+                    shift,
+                })
+            })
+        })
+}
+
+/// Every keyword of `length` letters, such as for a Vigenère, Beaufort or Porta cipher.
+///
+/// The keywords are given in alphabetical order, from `aaa...` to `zzz...`. There are `26^length`
+/// of them.
+///
+/// # Panics
+/// * `length` is 0.
+///
+pub fn keywords(length: usize) -> impl Iterator<Item = String> {
+    if length == 0 {
+        panic!("The keyword length must be greater than 0.");
+    }
+
+    odometer(26, length).map(|digits| {
+        digits
+            .into_iter()
+            .map(|d| (b'a' + d as u8) as char)
+            .collect()
+    })
+}
+
+/// Every key of `length` digits for a Gronsfeld cipher, from `000...` to `999...`.
+///
+/// # Panics
+/// * `length` is 0.
+///
+pub fn gronsfeld(length: usize) -> impl Iterator<Item = String> {
+    if length == 0 {
+        panic!("The key length must be greater than 0.");
+    }
+
+    odometer(10, length).map(|digits| {
+        digits
+            .into_iter()
+            .map(|d| (b'0' + d as u8) as char)
+            .collect()
+    })
+}
+
+/// The keys of a Hill cipher with a 2 x 2 matrix: each matrix with entries from `0` to `25`
+/// whose determinant has no factor in common with 26.
+///
+/// There are 157,248 keys, given in order of their entries read row by row.
+///
+pub fn hill2() -> impl Iterator<Item = Matrix<isize>> {
+    (0..26 * 26 * 26 * 26).filter_map(|n: isize| {
+        let (a, b, c, d) = (n / (26 * 26 * 26), n / (26 * 26) % 26, n / 26 % 26, n % 26);
+        let det = (a * d - b * c).rem_euclid(26);
+
+        if det % 2 != 0 && det % 13 != 0 {
+            Some(Matrix::new(2, 2, vec![a, b, c, d]))
+        } else {
+            None
+        }
+    })
+}
+
+/// Every permutation of the numbers `0` to `n - 1`, in lexicographic order.
+///
+fn permutations(n: usize) -> impl Iterator<Item = Vec<usize>> {
+    std::iter::successors(Some((0..n).collect::<Vec<usize>>()), |previous| {
+        // Find the rightmost element that is smaller than its successor
+        let i = (1..previous.len())
+            .rev()
+            .find(|&i| previous[i - 1] < previous[i])?
+            - 1;
+        let j = (i + 1..previous.len())
+            .rev()
+            .find(|&j| previous[i] < previous[j])?;
+
+        let mut next = previous.clone();
+        next.swap(i, j);
+        next[i + 1..].reverse();
+        Some(next)
+    })
+}
+
+/// Every sequence of `length` digits in the given `base`, counting up from all zeros.
+///
+fn odometer(base: usize, length: usize) -> impl Iterator<Item = Vec<usize>> {
+    std::iter::successors(Some(vec![0; length]), move |previous| {
+        let mut next = previous.clone();
+        for digit in next.iter_mut().rev() {
+            *digit += 1;
+            if *digit < base {
+                return Some(next);
+            }
+            *digit = 0;
+        }
+
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::cipher::Cipher;
+    use crate::{
+        Affine, CaesarBox, ColumnarTransposition, Gronsfeld, Hill, KeyboardShift, Redefence,
+        Vigenere,
+    };
+
+    #[test]
+    fn affine_keys_are_valid() {
+        let keys: Vec<AffineKey> = affine().collect();
+
+        assert_eq!(312, keys.len());
+        for key in keys {
+            Affine::new(key);
+        }
+    }
+
+    #[test]
+    fn hill2_keys_are_valid() {
+        assert_eq!(157_248, hill2().count());
+        for key in hill2().step_by(997) {
+            Hill::new(key);
+        }
+    }
+
+    #[test]
+    fn redefence_keys_are_valid() {
+        // 1 + 2 * 2 + 6 * 4 keys
+        assert_eq!(29, redefence(3).count());
+        for key in redefence(4) {
+            Redefence::new(key);
+        }
+    }
+
+    #[test]
+    fn caesar_box_keys_are_valid() {
+        for key in caesar_box(5) {
+            CaesarBox::new(key);
+        }
+    }
+
+    #[test]
+    fn columnar_keys_are_distinct() {
+        let keys: Vec<ColumnarTranspositionKey> = columnar_transposition(4).collect();
+        let mut ciphertexts: Vec<String> = keys
+            .into_iter()
+            .map(|key| ColumnarTransposition::new(key).encrypt("abcd").unwrap())
+            .collect();
+        ciphertexts.sort();
+        ciphertexts.dedup();
+
+        assert_eq!(24, ciphertexts.len());
+    }
+
+    #[test]
+    fn keyboard_shift_keys_are_valid() {
+        assert_eq!(72, keyboard_shift().count());
+        for key in keyboard_shift() {
+            KeyboardShift::new(key);
+        }
+    }
+
+    #[test]
+    fn keywords_in_order() {
+        let keys: Vec<String> = keywords(2).collect();
+
+        assert_eq!(676, keys.len());
+        assert_eq!("aa", keys[0]);
+        assert_eq!("ab", keys[1]);
+        assert_eq!("zz", keys[675]);
+        Vigenere::new(keys[100].clone());
+    }
+
+    #[test]
+    fn gronsfeld_keys() {
+        let keys: Vec<String> = gronsfeld(3).collect();
+
+        assert_eq!(1000, keys.len());
+        assert_eq!("042", keys[42]);
+        Gronsfeld::new(keys[42].clone());
+    }
+
+    #[test]
+    fn permutations_are_complete() {
+        assert_eq!(vec![vec![0]], permutations(1).collect::<Vec<Vec<usize>>>());
+        assert_eq!(120, permutations(5).count());
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_keywords() {
+        let _ = keywords(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_columns() {
+        let _ = columnar_transposition(27);
+    }
+}
@@ -0,0 +1,506 @@
+//! Contains the languages that plaintexts can be scored against, along with their expected
+//! n-gram frequencies.
+//!
+//! The frequencies of the languages other than English were counted over the 1,000 test sentences
+//! of each language of the Lingua language detector (around 100,000 letters each). The sentences
+//! are general prose from news and web text, drawn from the Leipzig Corpora Collection, and are
+//! distributed under the Apache License 2.0 in the `lingua-*-language-model` 1.3.0 crates. The
+//! Spanish sentences have lost their accented letters, which were restored with Lingua's Spanish
+//! n-gram model before counting.
+//!
+//! Accented letters were counted as their unaccented forms (e.g. `é` as `e`), and `ß` as `ss`, as
+//! is usual when these languages are enciphered with a 26 letter alphabet.
+//!
+use super::frequency::{ENGLISH_BIGRAMS, ENGLISH_LETTERS, ENGLISH_TRIGRAMS};
+
+/// The languages that plaintexts can be scored against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    /// English, using the frequencies of `ENGLISH_LETTERS`, `ENGLISH_BIGRAMS` and
+    /// `ENGLISH_TRIGRAMS`.
+    English,
+    /// French.
+    French,
+    /// German.
+    German,
+    /// Spanish.
+    Spanish,
+    /// Italian.
+    Italian,
+}
+
+impl Language {
+    /// The approximate frequency of each of the letters `a-z` within the language.
+    ///
+    pub fn letters(self) -> &'static [(&'static str, f64)] {
+        match self {
+            Language::English => &ENGLISH_LETTERS,
+            Language::French => &FRENCH_LETTERS,
+            Language::German => &GERMAN_LETTERS,
+            Language::Spanish => &SPANISH_LETTERS,
+            Language::Italian => &ITALIAN_LETTERS,
+        }
+    }
+
+    /// The approximate frequency of the 30 most common bigrams within the language.
+    ///
+    pub fn bigrams(self) -> &'static [(&'static str, f64)] {
+        match self {
+            Language::English => &ENGLISH_BIGRAMS,
+            Language::French => &FRENCH_BIGRAMS,
+            Language::German => &GERMAN_BIGRAMS,
+            Language::Spanish => &SPANISH_BIGRAMS,
+            Language::Italian => &ITALIAN_BIGRAMS,
+        }
+    }
+
+    /// The approximate frequency of the 30 most common trigrams within the language.
+    ///
+    pub fn trigrams(self) -> &'static [(&'static str, f64)] {
+        match self {
+            Language::English => &ENGLISH_TRIGRAMS,
+            Language::French => &FRENCH_TRIGRAMS,
+            Language::German => &GERMAN_TRIGRAMS,
+            Language::Spanish => &SPANISH_TRIGRAMS,
+            Language::Italian => &ITALIAN_TRIGRAMS,
+        }
+    }
+
+    /// The approximate frequencies of the n-grams of length `n` within the language, or `None`
+    /// if there are no built-in frequencies for n-grams of that length.
+    ///
+    pub fn ngrams(self, n: usize) -> Option<&'static [(&'static str, f64)]> {
+        match n {
+            1 => Some(self.letters()),
+            2 => Some(self.bigrams()),
+            3 => Some(self.trigrams()),
+            _ => None,
+        }
+    }
+}
+
+/// The approximate frequency of each of the letters `a-z` within French text.
+pub const FRENCH_LETTERS: [(&str, f64); 26] = [
+    ("a", 0.07603),
+    ("b", 0.00825),
+    ("c", 0.03667),
+    ("d", 0.04089),
+    ("e", 0.17600),
+    ("f", 0.01102),
+    ("g", 0.01162),
+    ("h", 0.00824),
+    ("i", 0.07485),
+    ("j", 0.00313),
+    ("k", 0.00074),
+    ("l", 0.05609),
+    ("m", 0.02728),
+    ("n", 0.07301),
+    ("o", 0.05679),
+    ("p", 0.03159),
+    ("q", 0.00890),
+    ("r", 0.06708),
+    ("s", 0.08174),
+    ("t", 0.07256),
+    ("u", 0.05535),
+    ("v", 0.01346),
+    ("w", 0.00052),
+    ("x", 0.00460),
+    ("y", 0.00264),
+    ("z", 0.00095),
+];
+
+/// The approximate frequency of the 30 most common bigrams within French text.
+pub const FRENCH_BIGRAMS: [(&str, f64); 30] = [
+    ("es", 0.0334),
+    ("de", 0.0235),
+    ("le", 0.0218),
+    ("en", 0.0210),
+    ("re", 0.0210),
+    ("on", 0.0179),
+    ("nt", 0.0170),
+    ("te", 0.0163),
+    ("er", 0.0161),
+    ("el", 0.0139),
+    ("se", 0.0137),
+    ("et", 0.0133),
+    ("ti", 0.0129),
+    ("la", 0.0127),
+    ("ed", 0.0116),
+    ("ne", 0.0115),
+    ("an", 0.0115),
+    ("ec", 0.0105),
+    ("ur", 0.0105),
+    ("ou", 0.0103),
+    ("me", 0.0101),
+    ("is", 0.0096),
+    ("em", 0.0093),
+    ("ie", 0.0092),
+    ("qu", 0.0088),
+    ("ee", 0.0085),
+    ("ue", 0.0084),
+    ("ce", 0.0083),
+    ("st", 0.0083),
+    ("ra", 0.0083),
+];
+
+/// The approximate frequency of the 30 most common trigrams within French text.
+pub const FRENCH_TRIGRAMS: [(&str, f64); 30] = [
+    ("ent", 0.0092),
+    ("les", 0.0073),
+    ("ede", 0.0072),
+    ("ion", 0.0072),
+    ("que", 0.0061),
+    ("des", 0.0058),
+    ("tio", 0.0057),
+    ("res", 0.0049),
+    ("eme", 0.0047),
+    ("est", 0.0046),
+    ("ela", 0.0045),
+    ("ati", 0.0044),
+    ("sde", 0.0043),
+    ("del", 0.0043),
+    ("men", 0.0043),
+    ("ese", 0.0042),
+    ("tre", 0.0041),
+    ("ele", 0.0039),
+    ("our", 0.0038),
+    ("ere", 0.0035),
+    ("esd", 0.0035),
+    ("lle", 0.0034),
+    ("ons", 0.0033),
+    ("par", 0.0033),
+    ("ant", 0.0032),
+    ("nte", 0.0032),
+    ("con", 0.0032),
+    ("nde", 0.0031),
+    ("ont", 0.0030),
+    ("tde", 0.0030),
+];
+
+/// The approximate frequency of each of the letters `a-z` within German text.
+pub const GERMAN_LETTERS: [(&str, f64); 26] = [
+    ("a", 0.06265),
+    ("b", 0.02064),
+    ("c", 0.02866),
+    ("d", 0.04714),
+    ("e", 0.16119),
+    ("f", 0.01770),
+    ("g", 0.02984),
+    ("h", 0.04390),
+    ("i", 0.07893),
+    ("j", 0.00239),
+    ("k", 0.01451),
+    ("l", 0.03743),
+    ("m", 0.02738),
+    ("n", 0.10100),
+    ("o", 0.03003),
+    ("p", 0.00958),
+    ("q", 0.00034),
+    ("r", 0.07240),
+    ("s", 0.06698),
+    ("t", 0.06110),
+    ("u", 0.04646),
+    ("v", 0.00943),
+    ("w", 0.01554),
+    ("x", 0.00068),
+    ("y", 0.00130),
+    ("z", 0.01279),
+];
+
+/// The approximate frequency of the 30 most common bigrams within German text.
+pub const GERMAN_BIGRAMS: [(&str, f64); 30] = [
+    ("en", 0.0379),
+    ("er", 0.0356),
+    ("ch", 0.0253),
+    ("de", 0.0202),
+    ("ei", 0.0201),
+    ("nd", 0.0190),
+    ("te", 0.0185),
+    ("in", 0.0176),
+    ("ie", 0.0162),
+    ("un", 0.0146),
+    ("ge", 0.0144),
+    ("ne", 0.0127),
+    ("es", 0.0127),
+    ("st", 0.0120),
+    ("an", 0.0108),
+    ("re", 0.0106),
+    ("he", 0.0106),
+    ("be", 0.0104),
+    ("se", 0.0100),
+    ("ng", 0.0095),
+    ("ic", 0.0093),
+    ("au", 0.0087),
+    ("di", 0.0087),
+    ("ns", 0.0083),
+    ("sc", 0.0079),
+    ("it", 0.0076),
+    ("le", 0.0073),
+    ("ra", 0.0072),
+    ("al", 0.0070),
+    ("ss", 0.0070),
+];
+
+/// The approximate frequency of the 30 most common trigrams within German text.
+pub const GERMAN_TRIGRAMS: [(&str, f64); 30] = [
+    ("ich", 0.0087),
+    ("ein", 0.0087),
+    ("der", 0.0086),
+    ("sch", 0.0078),
+    ("nde", 0.0070),
+    ("die", 0.0067),
+    ("che", 0.0065),
+    ("und", 0.0060),
+    ("den", 0.0055),
+    ("end", 0.0054),
+    ("ung", 0.0053),
+    ("ine", 0.0050),
+    ("ten", 0.0049),
+    ("gen", 0.0046),
+    ("ens", 0.0042),
+    ("ter", 0.0040),
+    ("cht", 0.0040),
+    ("ste", 0.0040),
+    ("ers", 0.0039),
+    ("ere", 0.0039),
+    ("hen", 0.0039),
+    ("nge", 0.0035),
+    ("eit", 0.0033),
+    ("ver", 0.0033),
+    ("ind", 0.0032),
+    ("nen", 0.0031),
+    ("rde", 0.0030),
+    ("and", 0.0029),
+    ("lic", 0.0029),
+    ("rei", 0.0028),
+];
+
+/// The approximate frequency of each of the letters `a-z` within Spanish text.
+pub const SPANISH_LETTERS: [(&str, f64); 26] = [
+    ("a", 0.12090),
+    ("b", 0.01255),
+    ("c", 0.04903),
+    ("d", 0.05165),
+    ("e", 0.13031),
+    ("f", 0.00748),
+    ("g", 0.01123),
+    ("h", 0.00750),
+    ("i", 0.06769),
+    ("j", 0.00425),
+    ("k", 0.00067),
+    ("l", 0.05242),
+    ("m", 0.02800),
+    ("n", 0.07372),
+    ("o", 0.08948),
+    ("p", 0.02615),
+    ("q", 0.00831),
+    ("r", 0.06550),
+    ("s", 0.07846),
+    ("t", 0.04747),
+    ("u", 0.04158),
+    ("v", 0.01098),
+    ("w", 0.00085),
+    ("x", 0.00159),
+    ("y", 0.00872),
+    ("z", 0.00348),
+];
+
+/// The approximate frequency of the 30 most common bigrams within Spanish text.
+pub const SPANISH_BIGRAMS: [(&str, f64); 30] = [
+    ("es", 0.0244),
+    ("de", 0.0241),
+    ("en", 0.0223),
+    ("on", 0.0176),
+    ("os", 0.0176),
+    ("as", 0.0164),
+    ("el", 0.0159),
+    ("la", 0.0157),
+    ("er", 0.0156),
+    ("ra", 0.0147),
+    ("ar", 0.0141),
+    ("ad", 0.0139),
+    ("re", 0.0135),
+    ("an", 0.0127),
+    ("al", 0.0125),
+    ("co", 0.0123),
+    ("nt", 0.0123),
+    ("ci", 0.0120),
+    ("te", 0.0115),
+    ("ue", 0.0114),
+    ("ta", 0.0111),
+    ("ac", 0.0109),
+    ("st", 0.0104),
+    ("se", 0.0103),
+    ("io", 0.0098),
+    ("do", 0.0096),
+    ("na", 0.0095),
+    ("or", 0.0095),
+    ("ca", 0.0092),
+    ("da", 0.0084),
+];
+
+/// The approximate frequency of the 30 most common trigrams within Spanish text.
+pub const SPANISH_TRIGRAMS: [(&str, f64); 30] = [
+    ("que", 0.0066),
+    ("ent", 0.0064),
+    ("con", 0.0064),
+    ("ion", 0.0062),
+    ("cio", 0.0057),
+    ("est", 0.0057),
+    ("del", 0.0054),
+    ("nte", 0.0052),
+    ("ade", 0.0049),
+    ("aci", 0.0046),
+    ("sde", 0.0046),
+    ("ela", 0.0044),
+    ("ode", 0.0042),
+    ("los", 0.0042),
+    ("des", 0.0039),
+    ("ado", 0.0038),
+    ("las", 0.0037),
+    ("sta", 0.0036),
+    ("res", 0.0035),
+    ("ara", 0.0034),
+    ("par", 0.0034),
+    ("ien", 0.0033),
+    ("ida", 0.0032),
+    ("dad", 0.0031),
+    ("ede", 0.0031),
+    ("ene", 0.0031),
+    ("ica", 0.0029),
+    ("sco", 0.0029),
+    ("ese", 0.0029),
+    ("com", 0.0028),
+];
+
+/// The approximate frequency of each of the letters `a-z` within Italian text.
+pub const ITALIAN_LETTERS: [(&str, f64); 26] = [
+    ("a", 0.11540),
+    ("b", 0.01021),
+    ("c", 0.04031),
+    ("d", 0.03672),
+    ("e", 0.11107),
+    ("f", 0.01034),
+    ("g", 0.01795),
+    ("h", 0.00873),
+    ("i", 0.11889),
+    ("j", 0.00019),
+    ("k", 0.00054),
+    ("l", 0.06234),
+    ("m", 0.02866),
+    ("n", 0.07633),
+    ("o", 0.08971),
+    ("p", 0.02897),
+    ("q", 0.00323),
+    ("r", 0.06932),
+    ("s", 0.05199),
+    ("t", 0.06563),
+    ("u", 0.02708),
+    ("v", 0.01445),
+    ("w", 0.00052),
+    ("x", 0.00028),
+    ("y", 0.00049),
+    ("z", 0.01066),
+];
+
+/// The approximate frequency of the 30 most common bigrams within Italian text.
+pub const ITALIAN_BIGRAMS: [(&str, f64); 30] = [
+    ("on", 0.0185),
+    ("er", 0.0171),
+    ("ri", 0.0161),
+    ("in", 0.0161),
+    ("re", 0.0153),
+    ("an", 0.0150),
+    ("ta", 0.0148),
+    ("al", 0.0144),
+    ("di", 0.0142),
+    ("ar", 0.0141),
+    ("en", 0.0135),
+    ("el", 0.0131),
+    ("la", 0.0131),
+    ("es", 0.0129),
+    ("to", 0.0128),
+    ("io", 0.0123),
+    ("co", 0.0119),
+    ("ia", 0.0119),
+    ("no", 0.0117),
+    ("at", 0.0116),
+    ("nt", 0.0113),
+    ("ra", 0.0112),
+    ("de", 0.0110),
+    ("ti", 0.0110),
+    ("or", 0.0107),
+    ("ne", 0.0106),
+    ("li", 0.0106),
+    ("te", 0.0105),
+    ("is", 0.0099),
+    ("le", 0.0099),
+];
+
+/// The approximate frequency of the 30 most common trigrams within Italian text.
+pub const ITALIAN_TRIGRAMS: [(&str, f64); 30] = [
+    ("ion", 0.0060),
+    ("del", 0.0057),
+    ("ent", 0.0056),
+    ("ell", 0.0054),
+    ("one", 0.0051),
+    ("lla", 0.0049),
+    ("con", 0.0049),
+    ("ari", 0.0049),
+    ("che", 0.0046),
+    ("per", 0.0044),
+    ("zio", 0.0043),
+    ("ato", 0.0039),
+    ("are", 0.0035),
+    ("est", 0.0035),
+    ("edi", 0.0035),
+    ("sta", 0.0035),
+    ("nte", 0.0034),
+    ("ita", 0.0033),
+    ("ede", 0.0033),
+    ("adi", 0.0032),
+    ("men", 0.0032),
+    ("all", 0.0031),
+    ("eri", 0.0031),
+    ("ino", 0.0031),
+    ("ale", 0.0031),
+    ("azi", 0.0029),
+    ("mar", 0.0029),
+    ("res", 0.0029),
+    ("ico", 0.0028),
+    ("rin", 0.0027),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LANGUAGES: [Language; 5] = [
+        Language::English,
+        Language::French,
+        Language::German,
+        Language::Spanish,
+        Language::Italian,
+    ];
+
+    #[test]
+    fn letter_frequencies_sum_to_one() {
+        for language in LANGUAGES.iter() {
+            let sum: f64 = language.letters().iter().map(|(_, f)| f).sum();
+            assert!((sum - 1.0).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn ngrams() {
+        for language in LANGUAGES.iter() {
+            assert_eq!(26, language.ngrams(1).unwrap().len());
+            assert!(language
+                .ngrams(3)
+                .unwrap()
+                .iter()
+                .all(|(g, _)| g.len() == 3));
+            assert!(language.ngrams(4).is_none());
+        }
+    }
+}
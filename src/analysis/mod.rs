@@ -0,0 +1,55 @@
+//! Contains tools for the cryptanalysis of classical ciphers.
+//!
+//! These tools operate on the letters `a-z` of a text, ignoring case and any other symbols.
+//!
+pub mod anagram;
+pub mod annealing;
+pub mod aristocrat;
+pub mod assistant;
+pub mod bigram;
+pub mod brute_force;
+pub mod crack;
+pub mod crib;
+pub mod fractionation;
+pub mod frequency;
+pub mod friedman;
+pub mod genetic;
+pub mod homophonic;
+pub mod identify;
+pub mod keyspace;
+pub mod language;
+pub mod period;
+#[cfg(feature = "quadgrams")]
+pub mod quadgram;
+pub mod randomness;
+pub mod session;
+pub mod solver;
+#[cfg(feature = "wordlist")]
+pub mod wordlist;
+
+pub use self::anagram::{multiple_anagram, Anagram};
+pub use self::annealing::SimulatedAnnealing;
+pub use self::aristocrat::AristocratSolver;
+pub use self::assistant::Assistant;
+pub use self::bigram::BigramScorer;
+pub use self::brute_force::brute_force;
+pub use self::crack::{
+    auto_decrypt, crack_caesar, crack_hill2, crack_periodic, crack_railfence, crack_scytale,
+    periodic_rule, Candidate, PeriodicRule, Solution,
+};
+pub use self::crib::{crib_drag, crib_drag_key, crib_key_fragment, crib_keyword};
+pub use self::fractionation::{bifid_period, trifid_period, BifidSearch, TrifidSearch};
+pub use self::frequency::FrequencyTable;
+pub use self::friedman::friedman;
+pub use self::genetic::Genetic;
+pub use self::homophonic::HomophonicSearch;
+pub use self::identify::identify;
+pub use self::language::Language;
+pub use self::period::{autocorrelation, periodic_ic, PeriodProfile};
+#[cfg(feature = "quadgrams")]
+pub use self::quadgram::QuadgramScorer;
+pub use self::randomness::{monobit, runs_test, serial_test};
+pub use self::session::{Change, Session};
+pub use self::solver::{HillClimber, KeySearch, Optimizer, SubstitutionSearch};
+#[cfg(feature = "wordlist")]
+pub use self::wordlist::{segment_words, WordRecognizer};
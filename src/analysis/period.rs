@@ -0,0 +1,235 @@
+//! Finds the period of a periodic polyalphabetic cipher (the length of its key) from the
+//! statistics of its ciphertext.
+//!
+//! A periodic cipher encrypts every letter a whole number of periods apart with the same alphabet.
+//! The letters that share an alphabet keep the statistics of the plaintext, such as its index of
+//! coincidence, whereas letters encrypted with different alphabets look random. Measuring these
+//! statistics for each candidate period gives a profile that peaks at the period and its
+//! multiples.
+//!
+use super::friedman::KAPPA_RANDOM;
+use super::FrequencyTable;
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+
+/// A statistic measured for each of a range of candidate periods, as returned by `periodic_ic`
+/// and `autocorrelation`.
+///
+/// The statistic is near the index of coincidence of the plaintext language (around `0.0667` for
+/// English) at the true period and its multiples, and near that of random letters (around
+/// `0.0385`) elsewhere.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeriodProfile {
+    scores: Vec<(usize, f64)>,
+}
+
+impl PeriodProfile {
+    /// The candidate periods, starting from 1, each paired with its statistic.
+    ///
+    pub fn scores(&self) -> &[(usize, f64)] {
+        &self.scores
+    }
+
+    /// The statistic measured for a candidate `period`, or `None` if it was not measured.
+    ///
+    pub fn score(&self, period: usize) -> Option<f64> {
+        self.scores
+            .iter()
+            .find(|&&(p, _)| p == period)
+            .map(|&(_, s)| s)
+    }
+
+    /// The most likely period, or `None` if no periods were measured.
+    ///
+    /// As the score of a single period can be high by chance, each period is judged by the
+    /// average score (above that of random letters) of the period and its multiples. Only the
+    /// periods with at least two multiples measured are judged, where there are any. As the
+    /// multiples of the period score as well as the period itself, the shortest period that is
+    /// judged to be at least 90% as good as the best is taken.
+    ///
+    pub fn likely_period(&self) -> Option<usize> {
+        let longest = self.scores.last()?.0;
+        let judged: Vec<(usize, f64)> = self
+            .scores
+            .iter()
+            .filter(|&&(p, _)| longest < 2 || 2 * p <= longest)
+            .map(|&(p, _)| {
+                let multiples: Vec<f64> = (p..=longest)
+                    .step_by(p)
+                    .filter_map(|m| self.score(m))
+                    .collect();
+
+                (
+                    p,
+                    multiples.iter().map(|s| s - KAPPA_RANDOM).sum::<f64>()
+                        / multiples.len() as f64,
+                )
+            })
+            .collect();
+        let best = judged
+            .iter()
+            .map(|&(_, s)| s)
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        judged
+            .iter()
+            .find(|&&(_, s)| s >= best * 0.9)
+            .map(|&(p, _)| p)
+    }
+}
+
+/// Measure the periodic index of coincidence of a `text`, for each period from 1 up to
+/// `max_period`.
+///
+/// For each period, the letters of the text are split into that many columns, and the score of
+/// the period is the average index of coincidence of the columns. Only the letters `a-z` are
+/// considered, ignoring case, and no period longer than the number of letters is measured.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::periodic_ic;
+/// use cipher_crypt::{Cipher, Vigenere};
+///
+/// let m = "It was the best of times, it was the worst of times, it was the age of wisdom, it \
+///     was the age of foolishness, it was the epoch of belief, it was the epoch of incredulity, \
+///     it was the season of Light, it was the season of Darkness, it was the spring of hope, it \
+///     was the winter of despair.";
+/// let ciphertext = Vigenere::new(String::from("dickens")).encrypt(m).unwrap();
+///
+/// let profile = periodic_ic(&ciphertext, 20);
+/// assert_eq!(Some(7), profile.likely_period());
+/// assert!(profile.score(7).unwrap() > profile.score(6).unwrap());
+/// ```
+///
+pub fn periodic_ic(text: &str, max_period: usize) -> PeriodProfile {
+    let letters = alphabet::STANDARD.scrub(text).to_ascii_lowercase();
+
+    PeriodProfile {
+        scores: (1..=max_period.min(letters.len()))
+            .map(|period| (period, column_ic(&letters, period)))
+            .collect(),
+    }
+}
+
+/// Measure the autocorrelation of a `text`, for each shift from 1 up to `max_shift`.
+///
+/// The letters of the text are compared with those of a copy shifted along by each candidate
+/// period, and the score of the period is the proportion of the overlapping letters that
+/// coincide. Only the letters `a-z` are considered, ignoring case, and no shift that leaves fewer
+/// than one overlapping letter is measured.
+///
+/// Unlike the `periodic_ic`, which divides a text into ever shorter columns, every shift compares
+/// nearly the whole of the text, so the scores of long periods are less noisy.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::autocorrelation;
+/// use cipher_crypt::{Cipher, Vigenere};
+///
+/// let m = "It was the best of times, it was the worst of times, it was the age of wisdom, it \
+///     was the age of foolishness, it was the epoch of belief, it was the epoch of incredulity, \
+///     it was the season of Light, it was the season of Darkness, it was the spring of hope, it \
+///     was the winter of despair.";
+/// let ciphertext = Vigenere::new(String::from("dickens")).encrypt(m).unwrap();
+///
+/// let profile = autocorrelation(&ciphertext, 20);
+/// assert_eq!(Some(7), profile.likely_period());
+/// ```
+///
+pub fn autocorrelation(text: &str, max_shift: usize) -> PeriodProfile {
+    let letters: Vec<char> = alphabet::STANDARD
+        .scrub(text)
+        .to_ascii_lowercase()
+        .chars()
+        .collect();
+
+    PeriodProfile {
+        scores: (1..=max_shift.min(letters.len().saturating_sub(1)))
+            .map(|shift| {
+                let coincidences = letters
+                    .iter()
+                    .zip(&letters[shift..])
+                    .filter(|(a, b)| a == b)
+                    .count();
+
+                (shift, coincidences as f64 / (letters.len() - shift) as f64)
+            })
+            .collect(),
+    }
+}
+
+/// The average index of coincidence of the `letters` split into `period` columns.
+///
+pub(super) fn column_ic(letters: &str, period: usize) -> f64 {
+    let mut columns = vec![String::new(); period];
+    for (i, c) in letters.chars().enumerate() {
+        columns[i % period].push(c);
+    }
+
+    columns
+        .iter()
+        .map(|c| FrequencyTable::letters(c).index_of_coincidence())
+        .sum::<f64>()
+        / period as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cipher, Vigenere};
+
+    #[test]
+    fn periodic_ic_test() {
+        let profile = periodic_ic("abcabcabcabc", 6);
+
+        assert_eq!(6, profile.scores().len());
+        assert_eq!(Some(1.0), profile.score(3));
+        assert_eq!(Some(1.0), profile.score(6));
+        assert_eq!(Some(3), profile.likely_period());
+    }
+
+    #[test]
+    fn longer_than_text() {
+        let profile = periodic_ic("Abc, d!", 10);
+
+        assert_eq!(4, profile.scores().len());
+        assert_eq!(None, profile.score(5));
+    }
+
+    #[test]
+    fn autocorrelation_test() {
+        let profile = autocorrelation("abcdabcdab", 8);
+
+        assert_eq!(8, profile.scores().len());
+        assert_eq!(Some(0.0), profile.score(1));
+        assert_eq!(Some(1.0), profile.score(4));
+        assert_eq!(Some(4), profile.likely_period());
+    }
+
+    #[test]
+    fn detectors_agree() {
+        let v = Vigenere::new(String::from("lemon"));
+        let ciphertext = v
+            .encrypt(
+                "We shall go on to the end, we shall fight in France, we shall fight on the seas \
+                and oceans, we shall fight with growing confidence and growing strength in the air, \
+                we shall defend our island, whatever the cost may be, we shall fight on the beaches, \
+                we shall fight on the landing grounds, we shall fight in the fields and in the \
+                streets, we shall fight in the hills; we shall never surrender",
+            )
+            .unwrap();
+
+        assert_eq!(Some(5), periodic_ic(&ciphertext, 20).likely_period());
+        assert_eq!(Some(5), autocorrelation(&ciphertext, 20).likely_period());
+    }
+
+    #[test]
+    fn without_letters() {
+        for profile in [periodic_ic("🗡️", 5), autocorrelation("a 🗡️", 5)].iter() {
+            assert!(profile.scores().is_empty());
+            assert_eq!(None, profile.likely_period());
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! Quadgram statistics are the standard fitness function for the stochastic solvers of classical
+//! ciphers (such as hill climbing and simulated annealing). A candidate plaintext is scored by
+//! how likely each of its quadgrams (groups of four letters) are to appear in English. The
+//! closer the candidate is to English, the higher its score.
+//!
+//! The probabilities of the quadgrams are taken from counts over a corpus of English. Those that
+//! do not appear within the corpus are given a small floor probability, rather than zero, so that
+//! a single unusual quadgram does not rule out a candidate.
+//!
+//! The statistics of each `Language` are embedded within the crate, and so are only available
+//! with the `quadgrams` feature.
+//!
+use super::Language;
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+
+/// The counts of the quadgrams of the embedded English corpus. Each line holds a quadgram in
+/// uppercase and the number of times it appears, separated by a space.
+///
+/// The corpus is around 975,000 letters. It is made of the four English texts of the Canterbury
+/// Corpus (`alice29.txt`, `asyoulik.txt`, `lcet10.txt` and `plrabn12.txt`), which are in the
+/// public domain, and the 1,000 English test sentences of the Lingua language detector, which are
+/// drawn from the Leipzig Corpora Collection and distributed under the Apache License 2.0. Only
+/// the quadgrams that appear at least 3 times are included.
+pub const ENGLISH_QUADGRAMS: &str = include_str!("english_quadgrams.txt");
+
+/// The counts of the quadgrams that appear at least twice in the embedded French corpus, in the
+/// same format as `ENGLISH_QUADGRAMS`. See the `language` module for the corpus.
+pub const FRENCH_QUADGRAMS: &str = include_str!("french_quadgrams.txt");
+
+/// The counts of the quadgrams that appear at least twice in the embedded German corpus, in the
+/// same format as `ENGLISH_QUADGRAMS`. See the `language` module for the corpus.
+pub const GERMAN_QUADGRAMS: &str = include_str!("german_quadgrams.txt");
+
+/// The counts of the quadgrams that appear at least twice in the embedded Spanish corpus, in the
+/// same format as `ENGLISH_QUADGRAMS`. See the `language` module for the corpus.
+pub const SPANISH_QUADGRAMS: &str = include_str!("spanish_quadgrams.txt");
+
+/// The counts of the quadgrams that appear at least twice in the embedded Italian corpus, in the
+/// same format as `ENGLISH_QUADGRAMS`. See the `language` module for the corpus.
+pub const ITALIAN_QUADGRAMS: &str = include_str!("italian_quadgrams.txt");
+
+/// The number of possible quadgrams of the letters `a-z`.
+const QUADGRAMS: usize = 26 * 26 * 26 * 26;
+
+/// Scores candidate plaintexts by the log-likelihood of their quadgrams.
+///
+/// This struct is created by the `new()` or `english()` methods. See their documentation for
+/// more.
+#[derive(Clone, Debug)]
+pub struct QuadgramScorer {
+    log_probabilities: Vec<f64>,
+}
+
+impl QuadgramScorer {
+    /// Initialise a scorer from the `counts` of the quadgrams of a corpus.
+    ///
+    /// The case of the quadgrams is ignored. Quadgrams that are not counted are given a floor
+    /// probability of `0.01 / total`.
+    ///
+    /// # Panics
+    /// * A quadgram is not made of four of the letters `a-z`.
+    /// * The total of the `counts` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::QuadgramScorer;
+    ///
+    /// let scorer = QuadgramScorer::new(vec![("tion", 9), ("atio", 1)]);
+    /// assert!(scorer.score("nation") > scorer.score("nottin"));
+    /// ```
+    ///
+    pub fn new<I, S>(counts: I) -> QuadgramScorer
+    where
+        I: IntoIterator<Item = (S, usize)>,
+        S: AsRef<str>,
+    {
+        let mut quadgram_counts = vec![0; QUADGRAMS];
+        for (quadgram, count) in counts {
+            let index = index(quadgram.as_ref())
+                .expect("A quadgram must be made of four of the letters a-z.");
+            quadgram_counts[index] += count;
+        }
+
+        let total: usize = quadgram_counts.iter().sum();
+        if total == 0 {
+            panic!("The total of the quadgram counts must be greater than 0.");
+        }
+
+        let floor = (0.01 / total as f64).log10();
+        QuadgramScorer {
+            log_probabilities: quadgram_counts
+                .into_iter()
+                .map(|count| match count {
+                    0 => floor,
+                    c => (c as f64 / total as f64).log10(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Initialise a scorer from the embedded `ENGLISH_QUADGRAMS`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::QuadgramScorer;
+    ///
+    /// let scorer = QuadgramScorer::english();
+    /// assert!(scorer.score("Attack at dawn") > scorer.score("Dwwdfn dw gdzq"));
+    /// ```
+    ///
+    pub fn english() -> QuadgramScorer {
+        QuadgramScorer::language(Language::English)
+    }
+
+    /// Initialise a scorer from the embedded quadgram counts of a `language`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::{Language, QuadgramScorer};
+    ///
+    /// let german = QuadgramScorer::language(Language::German);
+    /// let italian = QuadgramScorer::language(Language::Italian);
+    /// let m = "Der schnelle braune Fuchs springt";
+    ///
+    /// assert!(german.score(m) > italian.score(m));
+    /// ```
+    ///
+    pub fn language(language: Language) -> QuadgramScorer {
+        let counts = match language {
+            Language::English => ENGLISH_QUADGRAMS,
+            Language::French => FRENCH_QUADGRAMS,
+            Language::German => GERMAN_QUADGRAMS,
+            Language::Spanish => SPANISH_QUADGRAMS,
+            Language::Italian => ITALIAN_QUADGRAMS,
+        };
+
+        QuadgramScorer::new(counts.lines().map(|line| {
+            let mut parts = line.split(' ');
+            let quadgram = parts.next().unwrap();
+            let count = parts.next().unwrap().parse().unwrap();
+
+            (quadgram, count)
+        }))
+    }
+
+    /// Score a candidate `text` by the sum of the log (base 10) probabilities of its quadgrams.
+    ///
+    /// Only the letters `a-z` of the text are scored, ignoring case. The score is 0 for a text of
+    /// fewer than four letters, and is otherwise negative. As longer texts have lower scores, only
+    /// the scores of texts of the same length should be compared.
+    ///
+    pub fn score(&self, text: &str) -> f64 {
+        let letters: Vec<usize> = text
+            .chars()
+            .filter_map(|c| alphabet::STANDARD.find_position(c))
+            .collect();
+
+        letters
+            .windows(4)
+            .map(|q| self.log_probabilities[((q[0] * 26 + q[1]) * 26 + q[2]) * 26 + q[3]])
+            .sum()
+    }
+}
+
+/// The index of a `quadgram` within the table of log probabilities.
+///
+fn index(quadgram: &str) -> Option<usize> {
+    if quadgram.chars().count() != 4 {
+        return None;
+    }
+
+    quadgram.chars().try_fold(0, |index, c| {
+        alphabet::STANDARD
+            .find_position(c)
+            .map(|pos| index * 26 + pos)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_prefers_plaintext() {
+        let scorer = QuadgramScorer::english();
+
+        assert!(
+            scorer.score("Defend the east wall of the castle")
+                > scorer.score("Efgfoe uif fbtu xbmm pg uif dbtumf")
+        );
+    }
+
+    #[test]
+    fn languages() {
+        let m = "Le renard brun saute par-dessus le chien paresseux";
+        let french = QuadgramScorer::language(Language::French).score(m);
+
+        for language in [Language::English, Language::German, Language::Spanish].iter() {
+            assert!(french > QuadgramScorer::language(*language).score(m));
+        }
+    }
+
+    #[test]
+    fn ignores_case_and_symbols() {
+        let scorer = QuadgramScorer::english();
+        assert_eq!(
+            scorer.score("attackatdawn"),
+            scorer.score("Attack at dawn! 🗡️")
+        );
+    }
+
+    #[test]
+    fn short_text() {
+        assert_eq!(0.0, QuadgramScorer::english().score("the"));
+    }
+
+    #[test]
+    fn floor_probability() {
+        let scorer = QuadgramScorer::new(vec![("TION", 100)]);
+
+        assert_eq!(0.0, scorer.score("tion"));
+        assert_eq!(-4.0, scorer.score("qxzj"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_quadgram() {
+        QuadgramScorer::new(vec![("tio", 1)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_counts() {
+        QuadgramScorer::new(Vec::<(&str, usize)>::new());
+    }
+}
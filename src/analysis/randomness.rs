@@ -0,0 +1,195 @@
+//! Statistical tests of how closely a ciphertext resembles uniformly random letters.
+//!
+//! A good cipher hides the statistics of its plaintext, so its ciphertext should pass the same
+//! tests as a stream of random letters. Each test here gives a z-score: the number of standard
+//! deviations by which the text departs from what random letters would give. A score near 0 is
+//! what random letters give, and scores beyond about ±3 are unlikely to occur by chance. This
+//! makes the tests useful for comparing how well each cipher flattens the statistics of English.
+//!
+//! * The `monobit` test counts the letters in each half of the alphabet (`a-m` and `n-z`), as the
+//!   monobit test of a bit stream counts its zeros and ones.
+//! * The `runs_test` counts the runs of consecutive letters from the same half of the alphabet.
+//! * The `serial_test` compares the frequencies of the bigrams with those expected from the
+//!   frequencies of the single letters, as in the serial test of I. J. Good.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+
+/// The monobit test of a `text`: how far the number of its letters from `n-z` departs from half.
+///
+/// Only the letters `a-z` of the text are considered, ignoring case. The score is positive if the
+/// second half of the alphabet is the more common. Returns `None` if the text has no letters.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::monobit;
+///
+/// assert_eq!(Some(0.0), monobit("abyz"));
+/// assert_eq!(Some(-2.0), monobit("abcd"));
+/// ```
+///
+pub fn monobit(text: &str) -> Option<f64> {
+    let halves = halves(text);
+    if halves.is_empty() {
+        return None;
+    }
+
+    let upper = halves.iter().filter(|&&h| h).count() as f64;
+    let n = halves.len() as f64;
+
+    Some((2.0 * upper - n) / n.sqrt())
+}
+
+/// The runs test of a `text`: how far the number of runs of letters from the same half of the
+/// alphabet departs from the number expected in a random ordering of its letters.
+///
+/// Only the letters `a-z` of the text are considered, ignoring case. The score is negative if the
+/// letters of each half cluster together, and positive if the halves alternate too regularly.
+/// Returns `None` if the text has too few letters from either half for the test to be made.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::runs_test;
+///
+/// // Perfect alternation has far more runs than chance would give
+/// assert!(runs_test("anbocpdqerfsgthu").unwrap() > 3.0);
+/// ```
+///
+pub fn runs_test(text: &str) -> Option<f64> {
+    let halves = halves(text);
+    let upper = halves.iter().filter(|&&h| h).count() as f64;
+    let lower = halves.len() as f64 - upper;
+    let n = halves.len() as f64;
+    if upper == 0.0 || lower == 0.0 {
+        return None;
+    }
+
+    let runs = 1 + halves.windows(2).filter(|w| w[0] != w[1]).count();
+    let expected = 2.0 * upper * lower / n + 1.0;
+    let variance = (expected - 1.0) * (expected - 2.0) / (n - 1.0);
+    if variance <= 0.0 {
+        return None;
+    }
+
+    Some((runs as f64 - expected) / variance.sqrt())
+}
+
+/// The serial test of a `text`: how far the frequencies of its (overlapping) bigrams depart from
+/// those expected of random letters, beyond what the frequencies of its single letters explain.
+///
+/// The statistic of Good's serial test has a chi-squared distribution with `26^2 - 26` degrees of
+/// freedom for random letters, and is given here as a z-score. A substitution keeps the bigram
+/// statistics of English, so scores highly, whereas a polyalphabetic cipher with a long key
+/// spreads its bigrams more evenly. Only the letters `a-z` of the text are considered, ignoring
+/// case. Returns `None` if the text has fewer than two letters.
+///
+/// As the test compares 676 bigrams, it needs a few thousand letters to be reliable.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::serial_test;
+/// use cipher_crypt::{Caesar, Cipher, Vigenere};
+///
+/// let m = "It is a truth universally acknowledged, that a single man in possession of a good \
+///     fortune, must be in want of a wife. However little known the feelings or views of such a \
+///     man may be on his first entering a neighbourhood, this truth is so well fixed in the \
+///     minds of the surrounding families, that he is considered the rightful property of some \
+///     one or other of their daughters.";
+/// let caesar = Caesar::new(3).encrypt(m).unwrap();
+/// let vigenere = Vigenere::new(String::from("netherfieldparkislet")).encrypt(m).unwrap();
+///
+/// assert!(serial_test(&caesar).unwrap() > serial_test(&vigenere).unwrap());
+/// ```
+///
+pub fn serial_test(text: &str) -> Option<f64> {
+    let letters: Vec<usize> = text
+        .chars()
+        .filter_map(|c| alphabet::STANDARD.find_position(c))
+        .collect();
+    if letters.len() < 2 {
+        return None;
+    }
+
+    let mut singles = [0usize; 26];
+    for &l in &letters {
+        singles[l] += 1;
+    }
+    let mut pairs = vec![0usize; 26 * 26];
+    for pair in letters.windows(2) {
+        pairs[pair[0] * 26 + pair[1]] += 1;
+    }
+
+    // The statistic of each order is the chi-squared of its counts against a uniform distribution
+    let psi = |counts: &[usize], total: usize| -> f64 {
+        let squares: usize = counts.iter().map(|c| c * c).sum();
+        counts.len() as f64 * squares as f64 / total as f64 - total as f64
+    };
+    let statistic = psi(&pairs, letters.len() - 1) - psi(&singles, letters.len());
+    let freedom = (26 * 26 - 26) as f64;
+
+    Some((statistic - freedom) / (2.0 * freedom).sqrt())
+}
+
+/// Whether each letter `a-z` of a `text` is from the second half of the alphabet (`n-z`).
+///
+fn halves(text: &str) -> Vec<bool> {
+    text.chars()
+        .filter_map(|c| alphabet::STANDARD.find_position(c))
+        .map(|l| l >= 13)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn random_letters(n: usize) -> String {
+        let mut rng = StdRng::seed_from_u64(0);
+        (0..n)
+            .map(|_| alphabet::STANDARD.get_letter(rng.gen_range(0..26), false))
+            .collect()
+    }
+
+    #[test]
+    fn random_letters_pass() {
+        let text = random_letters(5000);
+
+        assert!(monobit(&text).unwrap().abs() < 3.0);
+        assert!(runs_test(&text).unwrap().abs() < 3.0);
+        assert!(serial_test(&text).unwrap().abs() < 3.0);
+    }
+
+    #[test]
+    fn english_fails_serial() {
+        let text = "It was the best of times, it was the worst of times, it was the age of \
+            wisdom, it was the age of foolishness, it was the epoch of belief, it was the epoch of \
+            incredulity, it was the season of Light, it was the season of Darkness, it was the \
+            spring of hope, it was the winter of despair.";
+
+        assert!(serial_test(text).unwrap() > 3.0);
+    }
+
+    #[test]
+    fn clustered_runs() {
+        assert!(runs_test("abcdefghijklmnopqrstuvwxyz").unwrap() < -3.0);
+    }
+
+    #[test]
+    fn ignores_case_and_symbols() {
+        assert_eq!(monobit("abyz"), monobit("A-b Y!z"));
+        assert_eq!(serial_test("attackatdawn"), serial_test("Attack at dawn!"));
+    }
+
+    #[test]
+    fn too_short() {
+        assert_eq!(None, monobit("123"));
+        assert_eq!(None, runs_test("abc"));
+        assert_eq!(None, runs_test("an"));
+        assert_eq!(None, serial_test("a"));
+    }
+}
@@ -0,0 +1,264 @@
+//! Records the changes made while solving a ciphertext by hand, so they can be undone and redone.
+//!
+//! Solving a classical cipher by hand is a process of trial and error: a letter is guessed, the
+//! text is re-read, and the guess is kept or taken back. A `Session` is the state behind a tool
+//! for this kind of solving. It keeps the changes applied to a ciphertext in order, and renders
+//! the partly solved text from them after each change, undo or redo.
+//!
+use super::Assistant;
+
+/// A change made to the solution of a ciphertext in a `Session`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    /// Substitute the `plain` letter for each `cipher` letter, as with `Assistant::substitute()`.
+    Substitute { cipher: char, plain: char },
+    /// Remove the substitution of a `cipher` letter, as with `Assistant::clear()`.
+    Clear { cipher: char },
+    /// Read the ciphertext as a columnar transposition, where the column order gives the position
+    /// (from 0) at which each column of the plaintext was read into the ciphertext (as does
+    /// `Anagram::order`). Replaces any column order given before.
+    ColumnOrder(Vec<usize>),
+}
+
+/// The state of a session of solving a ciphertext by hand, with undo and redo.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::{Change, Session};
+///
+/// let mut session = Session::new("Wkh fdw");
+/// session.apply(Change::Substitute { cipher: 'w', plain: 't' }).unwrap();
+/// session.apply(Change::Substitute { cipher: 'k', plain: 'a' }).unwrap();
+/// assert_eq!("Ta_ __t", session.text());
+///
+/// // That was a bad guess
+/// session.undo();
+/// session.apply(Change::Substitute { cipher: 'k', plain: 'h' }).unwrap();
+/// assert_eq!("Th_ __t", session.text());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Session {
+    ciphertext: String,
+    applied: Vec<Change>,
+    undone: Vec<Change>,
+}
+
+impl Session {
+    /// Initialise a session for a `ciphertext`, with no changes made.
+    ///
+    pub fn new(ciphertext: &str) -> Session {
+        Session {
+            ciphertext: ciphertext.to_string(),
+            applied: Vec::new(),
+            undone: Vec::new(),
+        }
+    }
+
+    /// Apply a `change` to the solution, discarding any changes that could be redone.
+    ///
+    /// Returns `Err` (and leaves the session unchanged) if the change cannot be made: if a
+    /// substitution is not between the letters `a-z`, if another letter is already substituted by
+    /// its `plain` letter, or if a column order is not an ordering of the numbers from 0 up to its
+    /// length.
+    ///
+    pub fn apply(&mut self, change: Change) -> Result<(), &'static str> {
+        self.applied.push(change);
+        if let Err(e) = render(&self.ciphertext, &self.applied) {
+            self.applied.pop();
+            return Err(e);
+        }
+
+        self.undone.clear();
+        Ok(())
+    }
+
+    /// Undo the last change applied, returning `false` if there is no change to undo.
+    ///
+    pub fn undo(&mut self) -> bool {
+        match self.applied.pop() {
+            Some(change) => {
+                self.undone.push(change);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Redo the last change undone, returning `false` if there is no change to redo.
+    ///
+    pub fn redo(&mut self) -> bool {
+        match self.undone.pop() {
+            Some(change) => {
+                self.applied.push(change);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The changes applied to the solution, from the first to the last.
+    ///
+    pub fn changes(&self) -> &[Change] {
+        &self.applied
+    }
+
+    /// The ciphertext, rendered with the changes applied so far.
+    ///
+    /// With a column order, the characters of the ciphertext other than whitespace are read back
+    /// into the rows of the transposition, and its whitespace is dropped. Until a substitution is
+    /// made the letters are shown as they are, and after that each letter without a substitution
+    /// is shown as `_` (see `Assistant::decoded()`).
+    ///
+    pub fn text(&self) -> String {
+        render(&self.ciphertext, &self.applied).unwrap()
+    }
+}
+
+/// Render a `ciphertext` with a sequence of `changes`, or return `Err` if one of the changes
+/// cannot be made.
+///
+fn render(ciphertext: &str, changes: &[Change]) -> Result<String, &'static str> {
+    let text = match changes.iter().rev().find_map(|change| match change {
+        Change::ColumnOrder(order) => Some(order),
+        _ => None,
+    }) {
+        Some(order) => untranspose(ciphertext, order)?,
+        None => ciphertext.to_string(),
+    };
+
+    let mut assistant = Assistant::new(&text);
+    let mut substituted = false;
+    for change in changes {
+        match *change {
+            Change::Substitute { cipher, plain } => {
+                assistant.substitute(cipher, plain)?;
+                substituted = true;
+            }
+            Change::Clear { cipher } => assistant.clear(cipher),
+            Change::ColumnOrder(_) => (),
+        }
+    }
+
+    if substituted {
+        Ok(assistant.decoded())
+    } else {
+        Ok(text)
+    }
+}
+
+/// Read the characters of a `ciphertext` (other than whitespace) back into the rows of a columnar
+/// transposition with the column `order`. The first columns of the plaintext are a character
+/// longer than the rest when the rows are not all full.
+///
+fn untranspose(ciphertext: &str, order: &[usize]) -> Result<String, &'static str> {
+    let mut sorted = order.to_vec();
+    sorted.sort_unstable();
+    if sorted.is_empty() || sorted.iter().enumerate().any(|(i, &o)| i != o) {
+        return Err("The column order must be an ordering of the numbers from 0 up to its length.");
+    }
+
+    let chars: Vec<char> = ciphertext.chars().filter(|c| !c.is_whitespace()).collect();
+    let width = order.len();
+    let height = |column: usize| chars.len() / width + usize::from(column < chars.len() % width);
+
+    // The columns of the plaintext, cut from the ciphertext in the order they were read into it
+    let mut columns: Vec<&[char]> = vec![&[]; width];
+    let mut start = 0;
+    for position in 0..width {
+        let column = order.iter().position(|&o| o == position).unwrap();
+        columns[column] = &chars[start..start + height(column)];
+        start += height(column);
+    }
+
+    Ok((0..chars.len())
+        .map(|i| columns[i % width][i / width])
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cipher, ColumnarTransposition, ColumnarTranspositionKey};
+
+    #[test]
+    fn undo_and_redo() {
+        let mut session = Session::new("Wkh fdw");
+        session
+            .apply(Change::Substitute {
+                cipher: 'w',
+                plain: 't',
+            })
+            .unwrap();
+        session.apply(Change::Clear { cipher: 'w' }).unwrap();
+        assert_eq!("___ ___", session.text());
+
+        assert!(session.undo());
+        assert_eq!("T__ __t", session.text());
+        assert!(session.undo());
+        assert_eq!("Wkh fdw", session.text());
+        assert!(!session.undo());
+
+        assert!(session.redo());
+        assert_eq!("T__ __t", session.text());
+        assert_eq!(1, session.changes().len());
+    }
+
+    #[test]
+    fn apply_discards_redo() {
+        let mut session = Session::new("abc");
+        session.apply(Change::ColumnOrder(vec![1, 0])).unwrap();
+        session.undo();
+        session.apply(Change::ColumnOrder(vec![0, 1])).unwrap();
+
+        assert!(!session.redo());
+        assert_eq!(vec![Change::ColumnOrder(vec![0, 1])], session.changes());
+    }
+
+    #[test]
+    fn column_order() {
+        let m = "wearediscoveredfleeatonce";
+        let c = ColumnarTransposition::new(ColumnarTranspositionKey {
+            keystream: String::from("zebras"),
+            null_char: None,
+        })
+        .encrypt(m)
+        .unwrap();
+        let mut session = Session::new(&c);
+        session
+            .apply(Change::ColumnOrder(vec![5, 2, 1, 3, 0, 4]))
+            .unwrap();
+        assert_eq!(m, session.text());
+
+        session
+            .apply(Change::Substitute {
+                cipher: 'e',
+                plain: 'e',
+            })
+            .unwrap();
+        assert_eq!("_e__e______e_e___ee_____e", session.text());
+    }
+
+    #[test]
+    fn rejects_invalid_changes() {
+        let mut session = Session::new("Wkh fdw");
+        session
+            .apply(Change::Substitute {
+                cipher: 'w',
+                plain: 't',
+            })
+            .unwrap();
+
+        assert!(session
+            .apply(Change::Substitute {
+                cipher: 'k',
+                plain: 't'
+            })
+            .is_err());
+        assert!(session.apply(Change::ColumnOrder(vec![0, 2])).is_err());
+        assert!(session.apply(Change::ColumnOrder(Vec::new())).is_err());
+        assert_eq!(1, session.changes().len());
+    }
+}
@@ -0,0 +1,482 @@
+//! The optimizers shared by the solvers for ciphers with too many keys to try each in turn.
+//!
+//! A cipher is attacked by describing its key space with the `KeySearch` trait: how to choose a
+//! random key, how to make a small change to a key, and how to decrypt the ciphertext with a key.
+//! An `Optimizer` then searches the key space for the key whose plaintext scores best under a
+//! fitness function (such as `QuadgramScorer::score`). Each attack chooses the optimizer that
+//! suits its key space:
+//!
+//! * A `HillClimber` repeatedly mutates the key of the current climb and keeps the mutation
+//!   whenever it scores higher. As the climb can become stuck on a key that no single mutation
+//!   improves, it is restarted from a new random key a number of times. This works well for
+//!   substitution keys.
+//! * A `SimulatedAnnealing` search also keeps some of the mutations that score lower, less often
+//!   as the search goes on, so that it can escape such keys. This suits keys such as those of the
+//!   Playfair and Columnar Transposition ciphers, where a mutation changes many letters at once.
+//! * A `Genetic` search breeds a population of keys, combining the best of them to form the
+//!   next generation.
+//!
+use super::Candidate;
+use crate::common::cipher::Cipher;
+use crate::SimpleSubstitution;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore, SeedableRng};
+
+/// The key space of a cipher, searched by a `HillClimber`.
+pub trait KeySearch {
+    /// The type of key searched for.
+    type Key: Clone;
+
+    /// Choose a key at random, from which a climb is started.
+    fn random_key(&self, rng: &mut dyn RngCore) -> Self::Key;
+
+    /// Make a small random change to a `key`, such as swapping two of its letters.
+    fn mutate(&self, key: &Self::Key, rng: &mut dyn RngCore) -> Self::Key;
+
+    /// Decrypt the ciphertext being attacked with a `key`.
+    fn decrypt(&self, key: &Self::Key) -> String;
+
+    /// Combine two parent keys into a child key, for a `Genetic` search.
+    ///
+    /// By default, the child is a mutation of the `first` parent.
+    fn crossover(
+        &self,
+        first: &Self::Key,
+        _second: &Self::Key,
+        rng: &mut dyn RngCore,
+    ) -> Self::Key {
+        self.mutate(first, rng)
+    }
+}
+
+/// The progress of an `Optimizer`, passed to the callback of `Optimizer::optimize_with()` each
+/// time a better candidate is found.
+#[derive(Debug)]
+pub struct Progress<'a, K> {
+    /// The number of the climb (or other restart of the search), counting from 0.
+    pub restart: usize,
+    /// The number of steps (mutations or generations) made so far since the restart.
+    pub iteration: usize,
+    /// The best candidate found so far.
+    pub best: &'a Candidate<K>,
+}
+
+/// A strategy for searching the keys of a cipher, such as a `HillClimber`.
+///
+/// Each attack can choose the optimizer that best suits its key space, as they all search a
+/// `KeySearch` for the key whose plaintext has the highest fitness.
+pub trait Optimizer {
+    /// Search the keys of a cipher for the one whose plaintext has the highest `fitness`,
+    /// reporting each better candidate to a `callback`.
+    ///
+    /// The `callback` is called each time a candidate is found that scores higher than any
+    /// before it. The search stops early if the `callback` returns `false`, and the best
+    /// candidate found so far is returned. The `score` of the returned candidate is its
+    /// `fitness`.
+    ///
+    fn optimize_with<S, F, C>(&self, search: &S, fitness: F, callback: C) -> Candidate<S::Key>
+    where
+        S: KeySearch,
+        F: Fn(&str) -> f64,
+        C: FnMut(&Progress<S::Key>) -> bool;
+
+    /// Search the keys of a cipher for the one whose plaintext has the highest `fitness`.
+    ///
+    /// The `score` of the returned candidate is its `fitness`.
+    ///
+    fn optimize<S, F>(&self, search: &S, fitness: F) -> Candidate<S::Key>
+    where
+        S: KeySearch,
+        F: Fn(&str) -> f64,
+    {
+        self.optimize_with(search, fitness, |_| true)
+    }
+}
+
+/// A hill-climbing search over the keys of a cipher.
+///
+/// This struct is created by the `new()` method, and configured by its builder methods.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::solver::{HillClimber, KeySearch, Optimizer};
+/// use rand::{Rng, RngCore};
+///
+/// // Find the Caesar shift that turns "hal" into "ibm"
+/// struct Shift;
+///
+/// impl KeySearch for Shift {
+///     type Key = u8;
+///
+///     fn random_key(&self, rng: &mut dyn RngCore) -> u8 {
+///         rng.gen_range(0..26)
+///     }
+///
+///     fn mutate(&self, key: &u8, _rng: &mut dyn RngCore) -> u8 {
+///         (key + 1) % 26
+///     }
+///
+///     fn decrypt(&self, key: &u8) -> String {
+///         "hal".bytes().map(|b| ((b - b'a' + key) % 26 + b'a') as char).collect()
+///     }
+/// }
+///
+/// let fitness = |text: &str| text.chars().zip("ibm".chars()).filter(|(a, b)| a == b).count();
+/// let best = HillClimber::new()
+///     .seed(7)
+///     .iterations(26)
+///     .optimize(&Shift, |text| fitness(text) as f64);
+///
+/// assert_eq!(1, best.key);
+/// assert_eq!("ibm", best.plaintext);
+/// ```
+///
+#[derive(Clone, Debug)]
+pub struct HillClimber {
+    restarts: usize,
+    iterations: usize,
+    seed: Option<u64>,
+}
+
+impl Default for HillClimber {
+    fn default() -> HillClimber {
+        HillClimber::new()
+    }
+}
+
+impl HillClimber {
+    /// Initialise a hill climber that makes 10 climbs of 2000 mutations each, with random choices
+    /// that differ from one search to the next.
+    ///
+    pub fn new() -> HillClimber {
+        HillClimber {
+            restarts: 10,
+            iterations: 2000,
+            seed: None,
+        }
+    }
+
+    /// Set the number of climbs made from a random key.
+    ///
+    /// # Panics
+    /// * `restarts` is 0.
+    ///
+    pub fn restarts(mut self, restarts: usize) -> HillClimber {
+        if restarts == 0 {
+            panic!("The hill climber must make at least one climb.");
+        }
+
+        self.restarts = restarts;
+        self
+    }
+
+    /// Set the number of mutations made during each climb.
+    ///
+    pub fn iterations(mut self, iterations: usize) -> HillClimber {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Seed the random choices of the search, so that it is repeatable.
+    ///
+    pub fn seed(mut self, seed: u64) -> HillClimber {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl Optimizer for HillClimber {
+    /// Search the keys of a cipher by hill climbing, reporting each better candidate to a
+    /// `callback`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::solver::{HillClimber, Optimizer, SubstitutionSearch};
+    /// use cipher_crypt::analysis::FrequencyTable;
+    /// use cipher_crypt::analysis::Language;
+    ///
+    /// let search = SubstitutionSearch::new("Pybb cbvd dxkiyb");
+    /// let fitness = |text: &str| {
+    ///     FrequencyTable::letters(text).log_likelihood(Language::English.letters())
+    /// };
+    ///
+    /// let mut improvements = 0;
+    /// HillClimber::new().seed(1).optimize_with(&search, fitness, |progress| {
+    ///     improvements += 1;
+    ///     progress.iteration < 100
+    /// });
+    ///
+    /// assert!(improvements > 0);
+    /// ```
+    ///
+    fn optimize_with<S, F, C>(&self, search: &S, fitness: F, mut callback: C) -> Candidate<S::Key>
+    where
+        S: KeySearch,
+        F: Fn(&str) -> f64,
+        C: FnMut(&Progress<S::Key>) -> bool,
+    {
+        let mut rng = seeded_rng(self.seed);
+
+        let mut best = None;
+        for restart in 0..self.restarts {
+            let mut current = evaluate(search, &fitness, search.random_key(&mut rng));
+
+            for iteration in 0..=self.iterations {
+                if iteration > 0 {
+                    let next = evaluate(search, &fitness, search.mutate(&current.key, &mut rng));
+                    if next.score <= current.score {
+                        continue;
+                    }
+                    current = next;
+                }
+
+                if !record(&mut best, &current, restart, iteration, &mut callback) {
+                    return best.unwrap();
+                }
+            }
+        }
+
+        best.unwrap()
+    }
+}
+
+/// Create the random number generator of an optimizer, seeded if a `seed` is given.
+///
+pub(super) fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// Decrypt the ciphertext of a `search` with a `key`, and score the plaintext by its `fitness`.
+///
+pub(super) fn evaluate<S, F>(search: &S, fitness: &F, key: S::Key) -> Candidate<S::Key>
+where
+    S: KeySearch,
+    F: Fn(&str) -> f64,
+{
+    let plaintext = search.decrypt(&key);
+    Candidate {
+        score: fitness(&plaintext),
+        key,
+        plaintext,
+    }
+}
+
+/// Keep a `candidate` as the `best` if it scores higher, and report it to the `callback`.
+///
+/// Returns `false` if the `callback` asks for the search to stop.
+///
+pub(super) fn record<K, C>(
+    best: &mut Option<Candidate<K>>,
+    candidate: &Candidate<K>,
+    restart: usize,
+    iteration: usize,
+    callback: &mut C,
+) -> bool
+where
+    K: Clone,
+    C: FnMut(&Progress<K>) -> bool,
+{
+    if best.as_ref().is_some_and(|b| candidate.score <= b.score) {
+        return true;
+    }
+    *best = Some(candidate.clone());
+
+    callback(&Progress {
+        restart,
+        iteration,
+        best: best.as_ref().unwrap(),
+    })
+}
+
+/// The key space of a Simple Substitution cipher, or any other monoalphabetic substitution.
+///
+/// Each key is a permutation of the alphabet, as given to `SimpleSubstitution::new()`, and is
+/// mutated by swapping two of its letters. Two keys are combined by taking the substitutions of
+/// some letters from the first key, and filling in the rest in the order of the second.
+#[derive(Clone, Debug)]
+pub struct SubstitutionSearch {
+    ciphertext: String,
+}
+
+impl SubstitutionSearch {
+    /// Initialise a search for the key of a Simple Substitution `ciphertext`.
+    ///
+    pub fn new(ciphertext: &str) -> SubstitutionSearch {
+        SubstitutionSearch {
+            ciphertext: ciphertext.to_string(),
+        }
+    }
+}
+
+impl KeySearch for SubstitutionSearch {
+    type Key = String;
+
+    fn random_key(&self, rng: &mut dyn RngCore) -> String {
+        let mut letters: Vec<char> = ('a'..='z').collect();
+        letters.shuffle(rng);
+
+        letters.into_iter().collect()
+    }
+
+    fn mutate(&self, key: &String, rng: &mut dyn RngCore) -> String {
+        let mut letters: Vec<char> = key.chars().collect();
+        let (a, b) = (rng.gen_range(0..26), rng.gen_range(0..26));
+        letters.swap(a, b);
+
+        letters.into_iter().collect()
+    }
+
+    fn crossover(&self, first: &String, second: &String, rng: &mut dyn RngCore) -> String {
+        let kept: Vec<bool> = (0..26).map(|_| rng.gen()).collect();
+        let taken: Vec<char> = first
+            .chars()
+            .zip(&kept)
+            .filter(|&(_, &k)| k)
+            .map(|(c, _)| c)
+            .collect();
+        let mut rest = second.chars().filter(|c| !taken.contains(c));
+
+        first
+            .chars()
+            .zip(kept)
+            .map(|(c, k)| if k { c } else { rest.next().unwrap() })
+            .collect()
+    }
+
+    fn decrypt(&self, key: &String) -> String {
+        SimpleSubstitution::new(key.clone())
+            .decrypt(&self.ciphertext)
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Search for a permutation of the numbers 0 to 7, mutated by swapping two of them.
+    struct Permutation;
+
+    impl KeySearch for Permutation {
+        type Key = Vec<usize>;
+
+        fn random_key(&self, rng: &mut dyn RngCore) -> Vec<usize> {
+            let mut key: Vec<usize> = (0..8).collect();
+            key.shuffle(rng);
+            key
+        }
+
+        fn mutate(&self, key: &Vec<usize>, rng: &mut dyn RngCore) -> Vec<usize> {
+            let mut key = key.clone();
+            key.swap(rng.gen_range(0..8), rng.gen_range(0..8));
+            key
+        }
+
+        fn decrypt(&self, key: &Vec<usize>) -> String {
+            key.iter()
+                .map(|i| "abcdefgh".chars().nth(*i).unwrap())
+                .collect()
+        }
+    }
+
+    fn sorted(text: &str) -> f64 {
+        text.chars()
+            .zip("abcdefgh".chars())
+            .filter(|(a, b)| a == b)
+            .count() as f64
+    }
+
+    #[test]
+    fn finds_optimum() {
+        let best = HillClimber::new().seed(3).optimize(&Permutation, sorted);
+
+        assert_eq!((0..8).collect::<Vec<usize>>(), best.key);
+        assert_eq!("abcdefgh", best.plaintext);
+        assert_eq!(8.0, best.score);
+    }
+
+    #[test]
+    fn seeded_is_repeatable() {
+        let climber = HillClimber::new().seed(11).restarts(1).iterations(5);
+
+        assert_eq!(
+            climber.optimize(&Permutation, sorted),
+            climber.optimize(&Permutation, sorted)
+        );
+    }
+
+    #[test]
+    fn callback_sees_improvements() {
+        let mut scores = Vec::new();
+        let best = HillClimber::new().seed(5).restarts(3).optimize_with(
+            &Permutation,
+            sorted,
+            |progress| {
+                scores.push(progress.best.score);
+                true
+            },
+        );
+
+        assert!(scores.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(Some(&best.score), scores.last());
+    }
+
+    #[test]
+    fn callback_stops_search() {
+        let mut calls = 0;
+        HillClimber::new()
+            .seed(5)
+            .optimize_with(&Permutation, sorted, |_| {
+                calls += 1;
+                false
+            });
+
+        assert_eq!(1, calls);
+    }
+
+    #[test]
+    fn no_mutations() {
+        let best = HillClimber::new()
+            .seed(2)
+            .restarts(1)
+            .iterations(0)
+            .optimize(&Permutation, sorted);
+
+        assert_eq!(sorted(&best.plaintext), best.score);
+    }
+
+    #[cfg(feature = "quadgrams")]
+    #[test]
+    fn crack_substitution() {
+        use crate::analysis::QuadgramScorer;
+
+        let m = "It is a truth universally acknowledged, that a single man in possession of a \
+            good fortune, must be in want of a wife. However little known the feelings or views of \
+            such a man may be on his first entering a neighbourhood, this truth is so well fixed \
+            in the minds of the surrounding families, that he is considered the rightful property \
+            of some one or other of their daughters.";
+        let key = "phqgiumeaylnofdxjkrcvstzwb";
+        let c = SimpleSubstitution::new(String::from(key))
+            .encrypt(m)
+            .unwrap();
+
+        let scorer = QuadgramScorer::english();
+        let best = HillClimber::new()
+            .seed(42)
+            .optimize(&SubstitutionSearch::new(&c), |p| scorer.score(p));
+
+        assert_eq!(m, best.plaintext);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_restarts() {
+        HillClimber::new().restarts(0);
+    }
+}
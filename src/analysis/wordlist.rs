@@ -0,0 +1,282 @@
+//! Recognises plaintext by the proportion of its words that are found in a wordlist.
+//!
+//! Statistical fitness functions (such as letter frequencies or quadgrams) need a reasonable
+//! amount of text to tell English from gibberish, so they are unreliable for very short messages.
+//! A message that keeps its word breaks can instead be recognised by its words: a correct
+//! decryption of `"Send more help"` is made entirely of English words, whereas a wrong one is
+//! unlikely to contain any.
+//!
+//! The same wordlist can put the word breaks back into a decryption that has lost them (see
+//! `segment_words`).
+//!
+//! The English wordlist is embedded within the crate, and so is only available with the
+//! `wordlist` feature.
+//!
+use std::collections::{HashMap, HashSet};
+
+/// The embedded English wordlist, with one word in lowercase on each line.
+///
+/// The list holds the 7,739 most common words of a corpus of around 14 million words of English
+/// documentation, of those appearing in at least three of its sources.
+pub const ENGLISH_WORDS: &str = include_str!("english_words.txt");
+
+// The cost of each word of `ENGLISH_WORDS` within a segmentation: the negative log of its
+// probability, assuming the frequencies of the words follow Zipf's law
+lazy_static! {
+    static ref WORD_COSTS: HashMap<&'static str, f64> = {
+        let count = ENGLISH_WORDS.lines().count() as f64;
+        ENGLISH_WORDS
+            .lines()
+            .enumerate()
+            .map(|(rank, word)| (word, ((rank + 1) as f64 * count.ln()).ln()))
+            .collect()
+    };
+    static ref LONGEST_WORD: usize = ENGLISH_WORDS.lines().map(str::len).max().unwrap_or(0);
+}
+
+/// The cost of a word that is not in the wordlist, which is more than that of the rarest word in
+/// the list, so that unknown words are used only as a last resort.
+const UNKNOWN_WORD_COST: f64 = 12.0;
+
+/// The additional cost of each letter of a word that is not in the wordlist.
+const UNKNOWN_LETTER_COST: f64 = 4.0;
+
+/// Reinsert the most likely word breaks into a `text` that has lost its spaces, such as the
+/// output of many of the ciphers of this crate.
+///
+/// The words are found by dynamic programming over the embedded English wordlist
+/// (`ENGLISH_WORDS`), where the more common a word is, the more likely it is. Letters that cannot
+/// be made into words of the list are kept together as unknown words. The case of the letters is
+/// kept, and any characters other than the letters `a-z` (such as punctuation) are kept as they
+/// are and always break words.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::analysis::segment_words;
+///
+/// assert_eq!(
+///     "Send more help to the gate at once!",
+///     segment_words("Sendmorehelptothegateatonce!")
+/// );
+/// ```
+///
+pub fn segment_words(text: &str) -> String {
+    let mut segmented = String::new();
+    let mut run: Vec<char> = Vec::new();
+    for c in text.chars() {
+        if c.is_ascii_alphabetic() {
+            run.push(c);
+        } else {
+            segmented.push_str(&segment_run(&run));
+            segmented.push(c);
+            run.clear();
+        }
+    }
+    segmented.push_str(&segment_run(&run));
+
+    segmented
+}
+
+/// Split a `run` of letters into the sequence of words with the lowest total cost, joined by
+/// spaces.
+///
+fn segment_run(run: &[char]) -> String {
+    let lower: String = run.iter().map(|c| c.to_ascii_lowercase()).collect();
+
+    // The lowest cost of segmenting the first `i` letters, and the start of its last word
+    let mut best: Vec<(f64, usize)> = vec![(0.0, 0)];
+    for end in 1..=run.len() {
+        let known = (end.saturating_sub(*LONGEST_WORD)..end).filter_map(|start| {
+            WORD_COSTS
+                .get(&lower[start..end])
+                .map(|cost| (best[start].0 + cost, start))
+        });
+        let unknown = (0..end).map(|start| {
+            let cost = UNKNOWN_WORD_COST + (end - start) as f64 * UNKNOWN_LETTER_COST;
+            (best[start].0 + cost, start)
+        });
+
+        best.push(
+            known
+                .chain(unknown)
+                .min_by(|a, b| a.0.total_cmp(&b.0))
+                .unwrap(),
+        );
+    }
+
+    let mut words = Vec::new();
+    let mut end = run.len();
+    while end > 0 {
+        let start = best[end].1;
+        words.push(run[start..end].iter().collect::<String>());
+        end = start;
+    }
+    words.reverse();
+
+    words.join(" ")
+}
+
+/// Scores candidate plaintexts by the proportion of their words that are in a wordlist.
+///
+/// This struct is created by the `new()` or `english()` methods. See their documentation for
+/// more.
+#[derive(Clone, Debug)]
+pub struct WordRecognizer {
+    words: HashSet<String>,
+}
+
+impl WordRecognizer {
+    /// Initialise a recognizer from a list of `words`.
+    ///
+    /// The case of the words is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::WordRecognizer;
+    ///
+    /// let recognizer = WordRecognizer::new(vec!["attack", "at", "dawn"]);
+    /// assert_eq!(1.0, recognizer.score("Attack at DAWN!"));
+    /// assert_eq!(0.5, recognizer.score("Attack at noon, retreat"));
+    /// ```
+    ///
+    pub fn new<I, S>(words: I) -> WordRecognizer
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        WordRecognizer {
+            words: words
+                .into_iter()
+                .map(|w| w.as_ref().to_lowercase())
+                .collect(),
+        }
+    }
+
+    /// Initialise a recognizer from the embedded English wordlist (`ENGLISH_WORDS`).
+    ///
+    pub fn english() -> WordRecognizer {
+        WordRecognizer::new(ENGLISH_WORDS.lines())
+    }
+
+    /// Whether a `word` is in the wordlist, ignoring case.
+    ///
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase())
+    }
+
+    /// Score a candidate `text` by the proportion of its words that are in the wordlist, between
+    /// 0 and 1.
+    ///
+    /// The words of the text are separated by whitespace, and any symbols around each word (such
+    /// as punctuation) are ignored, as is any ending after an apostrophe (such as the `'s` of a
+    /// possessive). The score is 0 for a text with no words.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::analysis::{brute_force, keyspace, WordRecognizer};
+    /// use cipher_crypt::Caesar;
+    ///
+    /// let recognizer = WordRecognizer::english();
+    /// let candidates = brute_force::<Caesar, _, _>(
+    ///     keyspace::caesar(),
+    ///     "Vhqg pruh khos",
+    ///     |m| recognizer.score(m),
+    ///     1,
+    /// );
+    ///
+    /// assert_eq!("Send more help", candidates[0].plaintext);
+    /// assert_eq!(1.0, candidates[0].score);
+    /// ```
+    ///
+    pub fn score(&self, text: &str) -> f64 {
+        let words: Vec<&str> = text
+            .split_whitespace()
+            .filter_map(|token| {
+                let word = token
+                    .trim_matches(|c: char| !c.is_alphabetic())
+                    .split('\'')
+                    .next()
+                    .unwrap();
+                Some(word).filter(|w| !w.is_empty())
+            })
+            .collect();
+        if words.is_empty() {
+            return 0.0;
+        }
+
+        let found = words.iter().filter(|w| self.contains(w)).count();
+        found as f64 / words.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_words() {
+        let recognizer = WordRecognizer::english();
+
+        for word in &[
+            "the", "and", "secret", "message", "attack", "house", "water",
+        ] {
+            assert!(recognizer.contains(word), "{}", word);
+        }
+        assert!(!recognizer.contains("qxzv"));
+    }
+
+    #[test]
+    fn ignores_punctuation_and_case() {
+        let recognizer = WordRecognizer::new(vec!["the", "key", "is", "hidden"]);
+
+        assert_eq!(1.0, recognizer.score("(The) KEY'S... hidden!"));
+        assert_eq!(0.75, recognizer.score("\"The key is -- unknown\""));
+    }
+
+    #[test]
+    fn partial() {
+        let recognizer = WordRecognizer::english();
+
+        assert_eq!(0.75, recognizer.score("meet me at zqv"));
+    }
+
+    #[test]
+    fn ranks_decryptions() {
+        let recognizer = WordRecognizer::english();
+
+        assert!(recognizer.score("we are discovered") > recognizer.score("zh duh glvfryhuhg"));
+    }
+
+    #[test]
+    fn segments_words() {
+        assert_eq!(
+            "it was the best of times it was the worst of times",
+            segment_words("itwasthebestoftimesitwastheworstoftimes")
+        );
+    }
+
+    #[test]
+    fn segments_around_symbols() {
+        assert_eq!(
+            "Send MORE help, 42 people!",
+            segment_words("SendMOREhelp, 42 people!")
+        );
+        assert_eq!("", segment_words(""));
+    }
+
+    #[test]
+    fn keeps_unknown_words() {
+        assert_eq!("meet qxzvk at once", segment_words("meetqxzvkatonce"));
+    }
+
+    #[test]
+    fn no_words() {
+        let recognizer = WordRecognizer::english();
+
+        assert_eq!(0.0, recognizer.score(""));
+        assert_eq!(0.0, recognizer.score(" 123 !? "));
+    }
+}
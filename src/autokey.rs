@@ -4,36 +4,94 @@
 //! For example, say the message was `ATTACK AT DAWN` and the key was `CRYPT` then the calculated
 //! keystream would be `CRYPTA TT ACKA`. It was invented by Blaise de Vigenère in 1586, and is
 //! generally more secure than the Vigenere cipher.
-use crate::common::alphabet::Alphabet;
+use crate::common::alphabet::{Alphabet, Custom};
 use crate::common::cipher::Cipher;
+use crate::common::key_problem;
+use crate::common::key_problem::KeyProblem;
 use crate::common::keygen::concatonated_keystream;
 use crate::common::{alphabet, substitute};
 
 /// An Autokey cipher.
 ///
-/// This struct is created by the `new()` method. See its documentation for more.
+/// This struct is created by the `new()` or `with_alphabet()` methods. See their documentation
+/// for more.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Autokey {
     key: String,
+    alphabet: Custom,
 }
 
-impl Cipher for Autokey {
-    type Key = String;
-    type Algorithm = Autokey;
-
+impl Autokey {
     /// Initialise an Autokey cipher given a specific key.
     ///
     /// # Panics
     /// * The `key` contains non-alphabetic symbols.
     /// * The `key` is empty.
     ///
-    fn new(key: String) -> Autokey {
+    pub fn new(key: impl Into<String>) -> Autokey {
+        Autokey::with_alphabet(key, &alphabet::STANDARD)
+    }
+
+    /// The key of the cipher, which is followed by the message to make the keystream.
+    ///
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Initialise an Autokey cipher given a specific key, that substitutes the symbols of an
+    /// `alphabet` other than `a-z`.
+    ///
+    /// The symbols of the message outside of the alphabet are left as they are, and are not added
+    /// to the keystream.
+    ///
+    /// # Panics
+    /// * The `key` contains symbols outside of the `alphabet`.
+    /// * The `key` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::alphabet::ALPHANUMERIC;
+    /// use cipher_crypt::{Autokey, Cipher};
+    ///
+    /// let a = Autokey::with_alphabet("f0rt", &ALPHANUMERIC);
+    /// let c = a.encrypt("Attack gate 3 at 0600").unwrap();
+    /// assert_eq!("Attack gate 3 at 0600", a.decrypt(&c).unwrap());
+    /// ```
+    ///
+    pub fn with_alphabet<A: Alphabet>(key: impl Into<String>, alphabet: &A) -> Autokey {
+        let key = key.into();
         if key.is_empty() {
             panic!("The key must contain at least one character.");
-        } else if !alphabet::STANDARD.is_valid(&key) {
-            panic!("The key cannot contain non-alphabetic symbols.");
+        } else if !alphabet.is_valid(&key) {
+            panic!("The key cannot contain symbols outside of the alphabet.");
         }
 
-        Autokey { key }
+        Autokey {
+            key,
+            alphabet: Custom::from_alphabet(alphabet),
+        }
+    }
+
+    /// List the problems with a `key` that would stop it from being given to `new()`.
+    ///
+    pub fn key_problems(key: &str) -> Vec<KeyProblem> {
+        if key.is_empty() {
+            return vec![KeyProblem::Empty];
+        }
+
+        key_problem::invalid_symbols(key, &alphabet::STANDARD)
+    }
+}
+
+impl Cipher for Autokey {
+    type Key = String;
+    type Algorithm = Autokey;
+
+    /// Initialise an Autokey cipher given a specific key, as by `Autokey::new()`.
+    ///
+    fn new(key: String) -> Autokey {
+        Autokey::new(key)
     }
 
     /// Encrypt a message using an Autokey cipher.
@@ -44,7 +102,7 @@ impl Cipher for Autokey {
     /// ```
     /// use cipher_crypt::{Cipher, Autokey};
     ///
-    /// let a = Autokey::new(String::from("fort"));
+    /// let a = Autokey::new("fort");
     /// assert_eq!("Fhktcd 🗡 mhg otzx aade", a.encrypt("Attack 🗡 the east wall").unwrap());
     /// ```
     ///
@@ -53,10 +111,11 @@ impl Cipher for Autokey {
         //         Ci = Ek(Mi) = (Mi + Ki) mod 26
         // Where;  Mi = position within the alphabet of ith char in message
         //         Ki = position within the alphabet of ith char in key
-        Ok(substitute::key_substitution(
+        Ok(substitute::alphabet_key_substitution(
             message,
-            &concatonated_keystream(&self.key, message),
-            |mi, ki| alphabet::STANDARD.modulo((mi + ki) as isize),
+            concatonated_keystream(&self.key, message, &self.alphabet),
+            &self.alphabet,
+            |mi, ki| self.alphabet.modulo((mi + ki) as isize),
         ))
     }
 
@@ -68,7 +127,7 @@ impl Cipher for Autokey {
     /// ```
     /// use cipher_crypt::{Cipher, Autokey};
     ///
-    /// let a = Autokey::new(String::from("fort"));;
+    /// let a = Autokey::new("fort");;
     /// assert_eq!("Attack 🗡 the east wall", a.decrypt("Fhktcd 🗡 mhg otzx aade").unwrap());
     /// ```
     ///
@@ -81,16 +140,15 @@ impl Cipher for Autokey {
         let mut stream_idx: usize = 0;
 
         for ct in ciphertext.chars() {
-            let ctpos = alphabet::STANDARD.find_position(ct);
+            let ctpos = self.alphabet.find_position(ct);
             match ctpos {
                 Some(ci) => {
                     let decrypted_character: char;
                     if let Some(kc) = keystream.get(stream_idx) {
-                        if let Some(ki) = alphabet::STANDARD.find_position(*kc) {
+                        if let Some(ki) = self.alphabet.find_position(*kc) {
                             //Calculate the index and retrieve the letter to substitute
-                            let si = alphabet::STANDARD.modulo(ci as isize - ki as isize);
-                            decrypted_character =
-                                alphabet::STANDARD.get_letter(si, ct.is_uppercase());
+                            let si = self.alphabet.modulo(ci as isize - ki as isize);
+                            decrypted_character = self.alphabet.get_letter(si, ct.is_uppercase());
                         } else {
                             panic!("Keystream contains a non-alphabetic symbol.");
                         }
@@ -108,6 +166,12 @@ impl Cipher for Autokey {
 
         Ok(plaintext)
     }
+
+    /// Whether a character is foreign to the cipher, being outside of its alphabet.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        self.alphabet.find_position(c).is_none()
+    }
 }
 
 #[cfg(test)]
@@ -140,6 +204,26 @@ mod tests {
         assert_eq!("attackatdawn", v.decrypt(ciphertext).unwrap());
     }
 
+    #[test]
+    fn custom_alphabet() {
+        let a = Autokey::with_alphabet(String::from("3"), &alphabet::NUMERIC);
+        assert_eq!("Dial 3500!", a.encrypt("Dial 0555!").unwrap());
+        assert_eq!("Dial 0555!", a.decrypt("Dial 3500!").unwrap());
+    }
+
+    #[test]
+    fn key_problems_test() {
+        assert!(Autokey::key_problems("fort").is_empty());
+        assert_eq!(vec![KeyProblem::Empty], Autokey::key_problems(""));
+        assert_eq!(
+            vec![KeyProblem::InvalidSymbol {
+                position: 2,
+                symbol: ' '
+            }],
+            Autokey::key_problems("fo rt")
+        );
+    }
+
     #[test]
     fn valid_key() {
         Autokey::new(String::from("LeMon"));
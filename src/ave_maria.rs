@@ -0,0 +1,371 @@
+//! The Ave Maria cipher was published by Johannes Trithemius in _Polygraphia_ (1518). It is a
+//! steganographic cipher, where the ciphertext is disguised as a prayer.
+//!
+//! Trithemius gave a long series of tables, each of which pairs every letter of the alphabet with
+//! a Latin word. The first letter of the message is replaced with its word from the first table,
+//! the second letter with its word from the second table, and so on. The tables are arranged so
+//! that the words form a sentence, and the ciphertext reads as innocuous Latin prose:
+//!
+//! ```text
+//! a    t         t       a   c        k           a         t
+//! Deus pacificus tueatur nos Conditor invisibilis benedicat terras
+//! ```
+//!
+//! The tables were not meant to be secret, so the security of the cipher lies in the ciphertext
+//! not being recognised as one. In this implementation the tables are reused in turn once they
+//! have all been used. The `DEFAULT_TABLE` is a short set of tables in the style of Trithemius.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
+
+/// A set of four tables in the style of Trithemius, giving a Latin word for each of the letters
+/// `a-z`. In turn they hold the subject, an adjective, a verb and an object of a sentence.
+pub const DEFAULT_TABLE: [[&str; 26]; 4] = [
+    [
+        "Deus",
+        "Creator",
+        "Conditor",
+        "Opifex",
+        "Dominus",
+        "Dominator",
+        "Consolator",
+        "Arbiter",
+        "Iudex",
+        "Iustus",
+        "Illuminator",
+        "Illustrator",
+        "Rector",
+        "Rex",
+        "Imperator",
+        "Gubernator",
+        "Factor",
+        "Fabricator",
+        "Conservator",
+        "Redemptor",
+        "Auctor",
+        "Servator",
+        "Salvator",
+        "Princeps",
+        "Pastor",
+        "Moderator",
+    ],
+    [
+        "clemens",
+        "clementissimus",
+        "pius",
+        "piissimus",
+        "magnus",
+        "excelsus",
+        "maximus",
+        "optimus",
+        "sapientissimus",
+        "iustissimus",
+        "invisibilis",
+        "immortalis",
+        "aeternus",
+        "sempiternus",
+        "gloriosus",
+        "fortissimus",
+        "sanctissimus",
+        "incomprehensibilis",
+        "omnipotens",
+        "pacificus",
+        "misericors",
+        "verax",
+        "excellentissimus",
+        "misericordissimus",
+        "cunctipotens",
+        "magnificus",
+    ],
+    [
+        "benedicat",
+        "conservet",
+        "custodiat",
+        "dirigat",
+        "exaudiat",
+        "foveat",
+        "gubernet",
+        "honoret",
+        "illuminet",
+        "iuvet",
+        "confirmet",
+        "laetificet",
+        "muniat",
+        "nutriat",
+        "ornet",
+        "protegat",
+        "sanctificet",
+        "regat",
+        "salvet",
+        "tueatur",
+        "visitet",
+        "vivificet",
+        "defendat",
+        "eligat",
+        "adiuvet",
+        "amet",
+    ],
+    [
+        "nos",
+        "omnes",
+        "populum",
+        "fideles",
+        "servos",
+        "filios",
+        "pauperes",
+        "homines",
+        "gentes",
+        "iustos",
+        "amicos",
+        "electos",
+        "mundum",
+        "animas",
+        "ecclesiam",
+        "pueros",
+        "corda",
+        "reges",
+        "sanctos",
+        "terras",
+        "urbes",
+        "viros",
+        "peregrinos",
+        "christianos",
+        "famulos",
+        "mansuetos",
+    ],
+];
+
+/// An Ave Maria cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AveMaria {
+    tables: Vec<Vec<String>>,
+}
+
+impl Cipher for AveMaria {
+    type Key = Option<Vec<Vec<String>>>;
+    type Algorithm = AveMaria;
+
+    /// Initialise an Ave Maria cipher given an optional set of tables.
+    ///
+    /// Each table holds the words for each of the letters `a-z`, in order. If no tables are given,
+    /// the `DEFAULT_TABLE` is used.
+    ///
+    /// # Panics
+    /// * There are no tables.
+    /// * A table does not contain 26 words.
+    /// * A word is empty or contains whitespace.
+    /// * A word appears more than once within a table, ignoring case.
+    ///
+    fn new(key: Option<Vec<Vec<String>>>) -> AveMaria {
+        let tables = key.unwrap_or_else(|| {
+            DEFAULT_TABLE
+                .iter()
+                .map(|t| t.iter().map(|w| w.to_string()).collect())
+                .collect()
+        });
+
+        if tables.is_empty() {
+            panic!("There must be at least one table.");
+        }
+
+        for table in &tables {
+            if table.len() != 26 {
+                panic!("Each table must contain a word for each of the letters a-z.");
+            }
+            for (i, word) in table.iter().enumerate() {
+                if word.is_empty() || word.chars().any(char::is_whitespace) {
+                    panic!("A word cannot be empty or contain whitespace.");
+                }
+                if table[..i]
+                    .iter()
+                    .any(|w| w.to_lowercase() == word.to_lowercase())
+                {
+                    panic!("A word cannot appear more than once within a table.");
+                }
+            }
+        }
+
+        AveMaria { tables }
+    }
+
+    /// Encrypt a message using an Ave Maria cipher.
+    ///
+    /// The words of the ciphertext are separated by spaces. Whitespace within the message is not
+    /// preserved.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{AveMaria, Cipher};
+    ///
+    /// let a = AveMaria::new(None);
+    /// assert_eq!(
+    ///     "Deus pacificus tueatur nos Conditor invisibilis benedicat terras",
+    ///     a.encrypt("Attack at").unwrap()
+    /// );
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.validate_message(message)?;
+
+        Ok(message
+            .chars()
+            .filter_map(|c| alphabet::STANDARD.find_position(c))
+            .enumerate()
+            .map(|(i, pos)| self.tables[i % self.tables.len()][pos].as_str())
+            .collect::<Vec<&str>>()
+            .join(" "))
+    }
+
+    /// Decrypt a message using an Ave Maria cipher.
+    ///
+    /// The case of the words is ignored. The plaintext is lowercase.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{AveMaria, Cipher};
+    ///
+    /// let a = AveMaria::new(None);
+    /// assert_eq!(
+    ///     "attackat",
+    ///     a.decrypt("Deus pacificus tueatur nos Conditor invisibilis benedicat terras").unwrap()
+    /// );
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        ciphertext
+            .split_whitespace()
+            .enumerate()
+            .map(|(i, word)| {
+                self.tables[i % self.tables.len()]
+                    .iter()
+                    .position(|w| w.to_lowercase() == word.to_lowercase())
+                    .map(|pos| alphabet::STANDARD.get_letter(pos, false))
+                    .ok_or("Ciphertext contains a word that is not in its table.")
+            })
+            .collect()
+    }
+
+    /// Check that a message can be encrypted with an Ave Maria cipher.
+    ///
+    /// Returns `Err` if the message contains a symbol that is not alphabetic or whitespace.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{AveMaria, Cipher};
+    ///
+    /// let a = AveMaria::new(None);
+    ///
+    /// assert!(a.validate_message("Attack at dawn").is_ok());
+    /// assert!(a.validate_message("Attack at dawn!").is_err());
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        CipherError::find(
+            message,
+            "Message contains a symbol that is not alphabetic or whitespace.",
+            |c| !c.is_whitespace() && alphabet::STANDARD.find_position(c).is_none(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tables() -> Vec<Vec<String>> {
+        let words = |prefix: &str| -> Vec<String> {
+            (b'a'..=b'z')
+                .map(|c| format!("{}{}", prefix, c as char))
+                .collect()
+        };
+
+        vec![words("alpha"), words("beta")]
+    }
+
+    #[test]
+    fn encrypt_test() {
+        let a = AveMaria::new(Some(tables()));
+        assert_eq!(
+            "alphaa betat alphat betaa alphac betak",
+            a.encrypt("Attack").unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let a = AveMaria::new(Some(tables()));
+        assert_eq!(
+            "attack",
+            a.decrypt("alphaa betat alphat betaa alphac betak").unwrap()
+        );
+    }
+
+    #[test]
+    fn default_table() {
+        let a = AveMaria::new(None);
+        let m = "The quick brown fox jumps over the lazy dog";
+
+        assert_eq!(
+            "thequickbrownfoxjumpsoverthelazydog",
+            a.decrypt(&a.encrypt(m).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_ignores_case() {
+        let a = AveMaria::new(None);
+        assert_eq!("ab", a.decrypt("DEUS Clementissimus").unwrap());
+    }
+
+    #[test]
+    fn word_in_wrong_table() {
+        let a = AveMaria::new(None);
+        assert!(a.decrypt("clemens Deus").is_err());
+    }
+
+    #[test]
+    fn with_utf8() {
+        let a = AveMaria::new(None);
+        assert!(a.encrypt("Peace 🗡️ Freedom").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_tables() {
+        AveMaria::new(Some(Vec::new()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn short_table() {
+        let mut tables = tables();
+        tables[1].pop();
+        AveMaria::new(Some(tables));
+    }
+
+    #[test]
+    #[should_panic]
+    fn repeated_word() {
+        let mut tables = tables();
+        tables[0][25] = String::from("ALPHAA");
+        AveMaria::new(Some(tables));
+    }
+
+    #[test]
+    #[should_panic]
+    fn word_with_whitespace() {
+        let mut tables = tables();
+        tables[0][0] = String::from("alpha a");
+        AveMaria::new(Some(tables));
+    }
+}
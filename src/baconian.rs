@@ -12,162 +12,110 @@
 //! a plaintext message of up to ~50 characters may be hidden.
 //!
 use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
 use lipsum::lipsum;
-use std::collections::HashMap;
 use std::string::String;
 
 // The default code length
 const CODE_LEN: usize = 5;
 
-// Code mappings:
-//  * note: that str is preferred over char as it cannot be guaranteed that
-//     there will be a single codepoint for a given character.
-lazy_static! {
-    static ref CODE_MAP: HashMap<&'static str, &'static str> = hashmap! {
-        "A" => "AAAAA",
-        "B" => "AAAAB",
-        "C" => "AAABA",
-        "D" => "AAABB",
-        "E" => "AABAA",
-        "F" => "AABAB",
-        "G" => "AABBA",
-        "H" => "AABBB",
-        "I" => "ABAAA",
-        "J" => "ABAAB",
-        "K" => "ABABA",
-        "L" => "ABABB",
-        "M" => "ABBAA",
-        "N" => "ABBAB",
-        "O" => "ABBBA",
-        "P" => "ABBBB",
-        "Q" => "BAAAA",
-        "R" => "BAAAB",
-        "S" => "BAABA",
-        "T" => "BAABB",
-        "U" => "BABAA",
-        "V" => "BABAB",
-        "W" => "BABBA",
-        "X" => "BABBB",
-        "Y" => "BBAAA",
-        "Z" => "BBAAB"
-    };
+// The code of each letter, indexed by its position within the alphabet. Each code is the position
+// written in binary, with 'A' for 0 and 'B' for 1.
+const CODES: [&str; 26] = [
+    "AAAAA", "AAAAB", "AAABA", "AAABB", "AABAA", "AABAB", "AABBA", "AABBB", "ABAAA", "ABAAB",
+    "ABABA", "ABABB", "ABBAA", "ABBAB", "ABBBA", "ABBBB", "BAAAA", "BAAAB", "BAABA", "BAABB",
+    "BABAA", "BABAB", "BABBA", "BABBB", "BBAAA", "BBAAB",
+];
+
+// The italic forms of the letters, which begin at these code points and follow the order of the
+// alphabet. Uppercase letters use Mathematical Italic, and lowercase letters use Mathematical
+// Sans-Serif Italic.
+const ITALIC_UPPER: char = '\u{1D434}';
+const ITALIC_LOWER: char = '\u{1D622}';
+
+/// Get the position of a letter within the alphabet `A-Z`, ignoring its case.
+fn letter_index(c: char) -> Option<usize> {
+    let mut upper = c.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(u @ 'A'..='Z'), None) => Some(u as usize - 'A' as usize),
+        _ => None,
+    }
 }
 
-// A mapping of alphabet to italic UTF-8 italic codes
-lazy_static! {
-    static ref ITALIC_CODES: HashMap<&'static str, char> = hashmap!{
-        // Using Mathematical Italic
-        "A" => '\u{1D434}',
-        "B" => '\u{1D435}',
-        "C" => '\u{1D436}',
-        "D" => '\u{1D437}',
-        "E" => '\u{1D438}',
-        "F" => '\u{1D439}',
-        "G" => '\u{1D43a}',
-        "H" => '\u{1D43b}',
-        "I" => '\u{1D43c}',
-        "J" => '\u{1D43d}',
-        "K" => '\u{1D43e}',
-        "L" => '\u{1D43f}',
-        "M" => '\u{1D440}',
-        "N" => '\u{1D441}',
-        "O" => '\u{1D442}',
-        "P" => '\u{1D443}',
-        "Q" => '\u{1D444}',
-        "R" => '\u{1D445}',
-        "S" => '\u{1D446}',
-        "T" => '\u{1D447}',
-        "U" => '\u{1D448}',
-        "V" => '\u{1D449}',
-        "W" => '\u{1D44a}',
-        "X" => '\u{1D44b}',
-        "Y" => '\u{1D44c}',
-        "Z" => '\u{1D44d}',
-        // Using Mathematical Sans-Serif Italic
-        "a" => '\u{1D622}',
-        "b" => '\u{1D623}',
-        "c" => '\u{1D624}',
-        "d" => '\u{1D625}',
-        "e" => '\u{1D626}',
-        "f" => '\u{1D627}',
-        "g" => '\u{1D628}',
-        "h" => '\u{1D629}',
-        "i" => '\u{1D62a}',
-        "j" => '\u{1D62b}',
-        "k" => '\u{1D62c}',
-        "l" => '\u{1D62d}',
-        "m" => '\u{1D62e}',
-        "n" => '\u{1D62f}',
-        "o" => '\u{1D630}',
-        "p" => '\u{1D631}',
-        "q" => '\u{1D632}',
-        "r" => '\u{1D633}',
-        "s" => '\u{1D634}',
-        "t" => '\u{1D635}',
-        "u" => '\u{1D636}',
-        "v" => '\u{1D637}',
-        "w" => '\u{1D638}',
-        "x" => '\u{1D639}',
-        "y" => '\u{1D63a}',
-        "z" => '\u{1D63b}'
+/// Get the italic form of an ASCII letter.
+fn italic(c: char) -> Option<char> {
+    let base = match c {
+        'A'..='Z' => ITALIC_UPPER as u32 - 'A' as u32,
+        'a'..='z' => ITALIC_LOWER as u32 - 'a' as u32,
+        _ => return None,
     };
+
+    char::from_u32(base + c as u32)
+}
+
+/// Whether a character is the italic form of a letter.
+fn is_italic(c: char) -> bool {
+    let within = |first: char| (first as u32..first as u32 + 26).contains(&(c as u32));
+    within(ITALIC_UPPER) || within(ITALIC_LOWER)
 }
 
 /// Get the code for a given key (source character)
-fn get_code(use_distinct_alphabet: bool, key: &str) -> String {
-    let mut code = String::new();
+fn get_code(use_distinct_alphabet: bool, key: char) -> &'static str {
     // Need to handle 'I'/'J' and 'U'/'V'
     //  for traditional usage.
-    let mut key_upper = key.to_uppercase();
-    if !use_distinct_alphabet {
-        match key_upper.as_str() {
-            "J" => key_upper = "I".to_string(),
-            "U" => key_upper = "V".to_string(),
-            _ => {}
-        }
+    match letter_index(key) {
+        Some(9) if !use_distinct_alphabet => CODES[8],
+        Some(20) if !use_distinct_alphabet => CODES[21],
+        Some(i) => CODES[i],
+        None => "",
     }
-    if CODE_MAP.contains_key(key_upper.as_str()) {
-        code.push_str(CODE_MAP.get(key_upper.as_str()).unwrap());
-    }
-    code
 }
 
 /// Gets the key (the source character) for a given cipher text code
-fn get_key(code: &str) -> String {
-    let mut key = String::new();
+fn get_key(code: &str) -> Option<char> {
+    let index = code
+        .chars()
+        .fold(0, |index, c| index * 2 + usize::from(c == 'B'));
 
-    for (_key, val) in CODE_MAP.iter() {
-        if val == &code {
-            key.push_str(_key);
-        }
+    if index < CODES.len() {
+        Some((b'A' + index as u8) as char)
+    } else {
+        None
     }
-    key
 }
 
 /// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Baconian {
     use_distinct_alphabet: bool,
     decoy_text: String,
 }
 
+/// The options of a Baconian cipher.
+///
+/// This struct is given to the `new()` method of `Baconian`. See its documentation for more.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BaconianOptions {
+    /// Whether every letter has its own code. If `false`, the classical alphabet is used,
+    /// in which I and J, and U and V, share a code.
+    pub distinct: bool,
+    /// An optional decoy text that the message is hidden in - default is boilerplate "Lorem
+    /// ipsum" text.
+    pub decoy_text: Option<String>,
+}
+
 impl Cipher for Baconian {
-    type Key = (bool, Option<String>);
+    type Key = BaconianOptions;
     type Algorithm = Baconian;
 
     /// Initialise a Baconian cipher
     ///
-    /// The `key` tuple maps to the following: `(bool, Option<str>) = (use_distinct_alphabet, decoy_text)`.
-    /// Where ...
-    ///
-    /// * The encoding will be use_distinct_alphabet for all alphabetical characters, or classical
-    ///     where I, J, U and V are mapped to the same value pairs
-    /// * An optional decoy message that will will be used to hide the message -
-    ///     default is boilerplate "Lorem ipsum" text.
+    /// The parts of the `key` are described by the fields of `BaconianOptions`.
     ///
-    fn new(key: (bool, Option<String>)) -> Baconian {
+    fn new(key: BaconianOptions) -> Baconian {
         Baconian {
-            use_distinct_alphabet: key.0,
-            decoy_text: key.1.unwrap_or_else(|| lipsum(160)),
+            use_distinct_alphabet: key.distinct,
+            decoy_text: key.decoy_text.unwrap_or_else(|| lipsum(160)),
         }
     }
 
@@ -182,31 +130,21 @@ impl Cipher for Baconian {
     /// Basic usage:
     ///
     /// ```
-    /// use cipher_crypt::{Cipher, Baconian};
+    /// use cipher_crypt::{Cipher, Baconian, BaconianOptions};
     ///
-    /// let b = Baconian::new((false, None));;
+    /// let b = Baconian::new(BaconianOptions { distinct: false, decoy_text: None });;
     /// let message = "Hello";
     /// let cipher_text = "Lo𝘳𝘦𝘮 ip𝘴um d𝘰l𝘰𝘳 s𝘪t 𝘢𝘮e𝘵, 𝘤𝘰n";
     ///
     /// assert_eq!(cipher_text, b.encrypt(message).unwrap());
     /// ```
     fn encrypt(&self, message: &str) -> Result<String, &'static str> {
-        let num_non_alphas = self
-            .decoy_text
-            .chars()
-            .filter(|c| !c.is_alphabetic())
-            .count();
-
-        // Check whether the message fits in the decoy
-        // Note: that non-alphabetical characters will be skipped.
-        if (message.len() * CODE_LEN) > self.decoy_text.len() - num_non_alphas {
-            return Err("Message too long for supplied decoy text.");
-        }
+        self.validate_message(message)?;
 
         // Iterate through the message encoding each char (ignoring non-alphabetical chars)
         let secret: String = message
             .chars()
-            .map(|c| get_code(self.use_distinct_alphabet, &c.to_string()))
+            .map(|c| get_code(self.use_distinct_alphabet, c))
             .collect();
 
         let mut num_alphas = 0;
@@ -240,8 +178,7 @@ impl Cipher for Baconian {
                 if let Some(sc) = secret_iter.next() {
                     if sc == 'B' {
                         // match the binary 'B' and swap for italic
-                        let italic = *ITALIC_CODES.get(c.to_string().as_str()).unwrap();
-                        decoy_msg.push(italic);
+                        decoy_msg.push(italic(c).unwrap());
                     } else {
                         decoy_msg.push(c);
                     }
@@ -260,9 +197,9 @@ impl Cipher for Baconian {
     /// Basic usage:
     ///
     /// ```
-    /// use cipher_crypt::{Cipher, Baconian};
+    /// use cipher_crypt::{Cipher, Baconian, BaconianOptions};
     ///
-    /// let b = Baconian::new((false, None));;
+    /// let b = Baconian::new(BaconianOptions { distinct: false, decoy_text: None });;
     /// let cipher_text = "Lo𝘳𝘦𝘮 ip𝘴um d𝘰l𝘰𝘳 s𝘪t 𝘢𝘮e𝘵, 𝘯𝘦 t";
     ///
     /// assert_eq!("HELLO", b.decrypt(cipher_text).unwrap());
@@ -270,18 +207,12 @@ impl Cipher for Baconian {
     ///
     fn decrypt(&self, message: &str) -> Result<String, &'static str> {
         // The message is decoy text
-        // Iterate through swapping any alphabetical chars found in the ITALIC_CODES
-        // set to be 'B', else 'A', skip anything else.
+        // Iterate through swapping any italic alphabetical chars to be 'B', else 'A', skip
+        // anything else.
         let ciphertext: String = message
             .chars()
             .filter(|c| c.is_alphabetic())
-            .map(|c| {
-                if ITALIC_CODES.iter().any(|e| *e.1 == c) {
-                    'B'
-                } else {
-                    'A'
-                }
-            })
+            .map(|c| if is_italic(c) { 'B' } else { 'A' })
             .collect();
 
         let mut plaintext = String::new();
@@ -290,13 +221,107 @@ impl Cipher for Baconian {
             code.push(c);
             if code.len() == CODE_LEN {
                 // If we have the right length code
-                plaintext += &get_key(&code);
+                plaintext.extend(get_key(&code));
                 code.clear();
             }
         }
 
         Ok(plaintext)
     }
+
+    /// Check that a message can be hidden within the decoy text of a Baconian cipher.
+    ///
+    /// Returns `Err` if the decoy text does not have enough alphabetic characters to conceal
+    /// every character of the message.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Baconian, BaconianOptions};
+    ///
+    /// let b = Baconian::new(BaconianOptions {
+    ///     distinct: false,
+    ///     decoy_text: Some(String::from("Hold off the attack")),
+    /// });
+    ///
+    /// assert!(b.validate_message("Go").is_ok());
+    /// assert!(b.validate_message("Retreat").is_err());
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        let num_alphas = self
+            .decoy_text
+            .chars()
+            .filter(|c| c.is_alphabetic())
+            .count();
+
+        // Check whether the message fits in the decoy
+        // Note: that non-alphabetical characters will be skipped.
+        if (message.chars().count() * CODE_LEN) > num_alphas {
+            return Err(CipherError::Message(
+                "Message too long for supplied decoy text.",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Baconian {
+    /// Start building a Baconian cipher, with defaults for the options that are not set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Baconian, Cipher};
+    ///
+    /// let b = Baconian::builder()
+    ///     .decoy_text("Lorem ipsum dolor sit amet, con")
+    ///     .build();
+    ///
+    /// assert_eq!("Lo𝘳𝘦𝘮 ip𝘴um d𝘰l𝘰𝘳 s𝘪t 𝘢𝘮e𝘵, 𝘤𝘰n", b.encrypt("Hello").unwrap());
+    /// ```
+    ///
+    pub fn builder() -> BaconianBuilder {
+        BaconianBuilder::default()
+    }
+}
+
+/// Builds a Baconian cipher one option at a time.
+///
+/// This struct is created by the `builder()` method of `Baconian`. See its documentation for
+/// more.
+#[derive(Clone, Debug, Default)]
+pub struct BaconianBuilder {
+    use_distinct_alphabet: bool,
+    decoy_text: Option<String>,
+}
+
+impl BaconianBuilder {
+    /// Set whether every letter has its own code. By default the classical alphabet is used, in
+    /// which I and J, and U and V, share a code.
+    ///
+    pub fn distinct_alphabet(mut self, use_distinct_alphabet: bool) -> BaconianBuilder {
+        self.use_distinct_alphabet = use_distinct_alphabet;
+        self
+    }
+
+    /// Set the decoy text that the message is hidden in, which is "Lorem ipsum" text by default.
+    ///
+    pub fn decoy_text(mut self, decoy_text: &str) -> BaconianBuilder {
+        self.decoy_text = Some(decoy_text.to_string());
+        self
+    }
+
+    /// Initialise the Baconian cipher, as by `Baconian::new()`.
+    ///
+    pub fn build(self) -> Baconian {
+        Baconian::new(BaconianOptions {
+            distinct: self.use_distinct_alphabet,
+            decoy_text: self.decoy_text,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -305,7 +330,10 @@ mod tests {
 
     #[test]
     fn encrypt_simple() {
-        let b = Baconian::new((false, None));
+        let b = Baconian::new(BaconianOptions {
+            distinct: false,
+            decoy_text: None,
+        });
         let message = "Hello";
         let cipher_text = "Lo𝘳𝘦𝘮 ip𝘴um d𝘰l𝘰𝘳 s𝘪t 𝘢𝘮e𝘵, 𝘤𝘰n";
         assert_eq!(cipher_text, b.encrypt(message).unwrap());
@@ -313,8 +341,14 @@ mod tests {
     // Need to test that the traditional and use_distinct_alphabet codes give different results
     #[test]
     fn encrypt_trad_v_dist() {
-        let b_trad = Baconian::new((false, None));
-        let b_dist = Baconian::new((true, None));
+        let b_trad = Baconian::new(BaconianOptions {
+            distinct: false,
+            decoy_text: None,
+        });
+        let b_dist = Baconian::new(BaconianOptions {
+            distinct: true,
+            decoy_text: None,
+        });
         let message = "I JADE YOU VERVENT UNICORN";
 
         assert_ne!(
@@ -338,7 +372,10 @@ mod tests {
              And where's a city from all vice so free, \
              But may be term'd the worst of all the three?",
         );
-        let b = Baconian::new((false, Some(decoy_text)));
+        let b = Baconian::new(BaconianOptions {
+            distinct: false,
+            decoy_text: Some(decoy_text),
+        });
         let message = "Peace, Freedom 🗡️ and Liberty!";
         let cipher_text = "T𝘩𝘦 𝘸𝘰rl𝘥\'s a bubble; an𝘥 the 𝘭ife o𝘧 m𝘢𝘯 les𝘴 th𝘢n a sp𝘢n. \
                            In hi𝘴 𝘤o𝘯𝘤𝘦pt𝘪𝘰n wretche𝘥; 𝘧r𝘰m th𝘦 𝘸o𝘮b 𝘴𝘰 t𝘰 the tomb: \
@@ -349,7 +386,10 @@ mod tests {
     #[test]
     #[should_panic(expected = r#"Message too long for supplied decoy text."#)]
     fn encrypt_decoy_too_short() {
-        let b = Baconian::new((false, None));
+        let b = Baconian::new(BaconianOptions {
+            distinct: false,
+            decoy_text: None,
+        });
         let message = "This is a long message that will be too long to encode using \
                        the default decoy text. In order to have a long message encoded you need a \
                        decoy text that is at least five times as long, plus the non-alphabeticals.";
@@ -357,6 +397,34 @@ mod tests {
         b.encrypt(message).unwrap();
     }
 
+    #[test]
+    fn validate_decoy_capacity() {
+        let b = Baconian::new(BaconianOptions {
+            distinct: false,
+            decoy_text: Some(String::from("Hold off the attack")),
+        });
+
+        assert!(b.validate_message("Go").is_ok());
+        assert_eq!(
+            b.validate_message("Retreat"),
+            Err(CipherError::Message(
+                "Message too long for supplied decoy text."
+            ))
+        );
+    }
+
+    #[test]
+    fn validate_counts_chars() {
+        // Eleven letters, but sixteen bytes
+        let b = Baconian::new(BaconianOptions {
+            distinct: false,
+            decoy_text: Some(String::from("Très éloigné")),
+        });
+
+        assert!(b.validate_message("Go").is_ok());
+        assert!(b.validate_message("Run").is_err());
+    }
+
     #[test]
     fn encrypt_with_use_distinct_alphabet_codeset() {
         let message = "Peace, Freedom 🗡️ and Liberty!";
@@ -376,7 +444,10 @@ mod tests {
         let cipher_text = "T𝘩𝘦 𝘸𝘰rl𝘥's a bubble; an𝘥 the 𝘭ife o𝘧 m𝘢𝘯 les𝘴 th𝘢n a sp𝘢n. \
                            In hi𝘴 𝘤o𝘯𝘤𝘦pt𝘪𝘰n wretche𝘥; 𝘧r𝘰m th𝘦 𝘸o𝘮b 𝘴𝘰 t𝘰 the tomb: \
                            𝐶ur𝘴t f𝘳om t𝘩𝘦 cr𝘢𝘥𝘭𝘦, and";
-        let b = Baconian::new((true, Some(decoy_text)));
+        let b = Baconian::new(BaconianOptions {
+            distinct: true,
+            decoy_text: Some(decoy_text),
+        });
         assert_eq!(cipher_text, b.encrypt(message).unwrap());
     }
 
@@ -385,7 +456,10 @@ mod tests {
         let cipher_text = String::from("Let's c𝘰mp𝘳𝘰𝘮is𝘦. 𝐻old off th𝘦 at𝘵a𝘤k");
         let message = "ATTACK";
         let decoy_text = String::from("Let's compromise. Hold off the attack");
-        let b = Baconian::new((true, Some(decoy_text)));
+        let b = Baconian::new(BaconianOptions {
+            distinct: true,
+            decoy_text: Some(decoy_text),
+        });
         assert_eq!(message, b.decrypt(&cipher_text).unwrap());
     }
 
@@ -411,7 +485,24 @@ mod tests {
              And where's a city from all vice so free, \
              But may be term'd the worst of all the three?",
         );
-        let b = Baconian::new((false, Some(decoy_text)));
+        let b = Baconian::new(BaconianOptions {
+            distinct: false,
+            decoy_text: Some(decoy_text),
+        });
         assert_eq!(message, b.decrypt(&cipher_text).unwrap());
     }
+
+    #[test]
+    fn builder_distinct_alphabet() {
+        let b = Baconian::builder()
+            .distinct_alphabet(true)
+            .decoy_text("Lorem ipsum dolor sit amet")
+            .build();
+        let c = Baconian::new(BaconianOptions {
+            distinct: true,
+            decoy_text: Some(String::from("Lorem ipsum dolor sit amet")),
+        });
+
+        assert_eq!(c.encrypt("Jug"), b.encrypt("Jug"));
+    }
 }
@@ -0,0 +1,164 @@
+//! The Beaufort cipher is a polyalphabetic substitution cipher that uses the same tabula recta
+//! as the Vigenère cipher. It is named after Sir Francis Beaufort, although it was first
+//! described by Giovanni Sestri in 1710.
+//!
+//! Unlike Vigenère, each letter of the message is subtracted from the corresponding letter of the
+//! key. This makes the Beaufort cipher reciprocal, meaning that encryption and decryption are the
+//! same operation.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::keygen::cyclic_keystream;
+use crate::common::substitute;
+
+/// A Beaufort cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Beaufort {
+    key: String,
+}
+
+impl Beaufort {
+    /// Initialise a Beaufort cipher given a specific key.
+    ///
+    /// # Panics
+    /// * The `key` is empty.
+    /// * The `key` contains a non-alphabetic symbol.
+    ///
+    pub fn new(key: impl Into<String>) -> Beaufort {
+        let key = key.into();
+        if key.is_empty() {
+            panic!("The key is empty.");
+        }
+        if !alphabet::STANDARD.is_valid(&key) {
+            panic!("The key contains a non-alphabetic symbol.");
+        }
+
+        Beaufort { key }
+    }
+
+    /// The key of the cipher.
+    ///
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl Cipher for Beaufort {
+    type Key = String;
+    type Algorithm = Beaufort;
+
+    /// Initialise a Beaufort cipher given a specific key, as by `Beaufort::new()`.
+    ///
+    fn new(key: String) -> Beaufort {
+        Beaufort::new(key)
+    }
+
+    /// Encrypt a message using a Beaufort cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Beaufort};
+    ///
+    /// let b = Beaufort::new("fortification");
+    /// assert_eq!("Ckmpvc pvw piwu jogi", b.encrypt("Defend the east wall").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        // Encryption of a letter in a message:
+        //         Ci = Ek(Mi) = (Ki - Mi) mod 26
+        // Where;  Mi = position within the alphabet of ith char in message
+        //         Ki = position within the alphabet of ith char in key
+        Ok(substitute::key_substitution(
+            message,
+            cyclic_keystream(&self.key, message),
+            |mi, ki| alphabet::STANDARD.modulo(ki as isize - mi as isize),
+        ))
+    }
+
+    /// Decrypt a message using a Beaufort cipher.
+    ///
+    /// As the Beaufort cipher is reciprocal, this is the same operation as encryption.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Beaufort};
+    ///
+    /// let b = Beaufort::new("fortification");
+    /// assert_eq!("Defend the east wall", b.decrypt("Ckmpvc pvw piwu jogi").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        // Decryption of a letter in a message:
+        //         Mi = Dk(Ci) = (Ki - Ci) mod 26
+        // Where;  Ci = position within the alphabet of ith char in cipher text
+        //         Ki = position within the alphabet of ith char in key
+        self.encrypt(ciphertext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_test() {
+        let message = "defendtheeastwallofthecastle";
+        let b = Beaufort::new(String::from("fortification"));
+        assert_eq!("ckmpvcpvwpiwujogiuapvwriwuuk", b.encrypt(message).unwrap());
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let ciphertext = "ckmpvcpvwpiwujogiuapvwriwuuk";
+        let b = Beaufort::new(String::from("fortification"));
+        assert_eq!(
+            "defendtheeastwallofthecastle",
+            b.decrypt(ciphertext).unwrap()
+        );
+    }
+
+    #[test]
+    fn mixed_case() {
+        let message = "Attack at Dawn!";
+        let b = Beaufort::new(String::from("giovan"));
+
+        let ciphertext = b.encrypt(message).unwrap();
+        let plain_text = b.decrypt(&ciphertext).unwrap();
+
+        assert_eq!(plain_text, message);
+    }
+
+    #[test]
+    fn with_utf8() {
+        let b = Beaufort::new(String::from("utfeightisfun"));
+        let message = "Peace 🗡️ Freedom and Liberty!";
+        let encrypted = b.encrypt(message).unwrap();
+        let decrypted = b.decrypt(&encrypted).unwrap();
+
+        assert_eq!(decrypted, message);
+    }
+
+    #[test]
+    fn valid_key() {
+        Beaufort::new(String::from("LeMon"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn key_with_symbols() {
+        Beaufort::new(String::from("!em@n"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn key_with_whitespace() {
+        Beaufort::new(String::from("wow this key is a real lemon"));
+    }
+}
@@ -0,0 +1,328 @@
+//! The Bifid cipher was invented by Félix Delastelle in 1895. It combines a Polybius square with
+//! transposition, and uses fractionation to achieve diffusion.
+//!
+//! Each letter of the message is converted into its row and column coordinates within the
+//! square. For every group of `period` letters, the row coordinates are written out followed by
+//! the column coordinates. This sequence is then read in pairs, with each pair being converted
+//! back into a letter of the square.
+//!
+//! This implementation supports both the classical 5x5 square (where 'I' and 'J' share a cell)
+//! and a 6x6 alphanumeric square. Any symbols that are not part of the square are left in place.
+//!
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::{alphabet, keygen};
+
+const SQUARE_IDS: [char; 6] = ['A', 'B', 'C', 'D', 'E', 'F'];
+
+/// A Bifid cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bifid {
+    square: Vec<char>,
+    size: usize,
+    period: usize,
+}
+
+/// The key of a Bifid cipher.
+///
+/// This struct is given to the `new()` method of `Bifid`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BifidKey {
+    /// The phrase used to generate a keyed alphabet that fills the square. As 'I' and 'J'
+    /// share a cell, any 'J' in the phrase is treated as an 'I'.
+    pub phrase: String,
+    /// The number of letters that are fractionated together.
+    pub period: usize,
+}
+
+impl Cipher for Bifid {
+    type Key = BifidKey;
+    type Algorithm = Bifid;
+
+    /// Initialise a Bifid cipher with a 5x5 square.
+    ///
+    /// The parts of the `key` are described by the fields of `BifidKey`.
+    ///
+    /// # Panics
+    /// * The `phrase` contains non-alphabetic symbols.
+    /// * The `period` is 0.
+    ///
+    fn new(key: BifidKey) -> Bifid {
+        let phrase = key.phrase.replace('j', "i").replace('J', "I");
+        let keyed = keygen::keyed_alphabet(&phrase, &alphabet::PLAYFAIR, true);
+
+        Bifid::from_square(&keyed, &alphabet::PLAYFAIR, key.period)
+    }
+
+    /// Encrypt a message using a Bifid cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Bifid, BifidKey};
+    ///
+    /// let b = Bifid::new(BifidKey {
+    ///     phrase: String::from("bgwkzqpndsioaxefclumthyvr"),
+    ///     period: 10,
+    /// });
+    /// assert_eq!("Uaeo lw rins!", b.encrypt("Flee at once!").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        Ok(self.transform(message, |coords| {
+            // Write out the rows, followed by the columns and then read them off in pairs
+            let seq: Vec<usize> = coords
+                .iter()
+                .map(|c| c.0)
+                .chain(coords.iter().map(|c| c.1))
+                .collect();
+
+            seq.chunks(2).map(|p| (p[0], p[1])).collect()
+        }))
+    }
+
+    /// Decrypt a message using a Bifid cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Bifid, BifidKey};
+    ///
+    /// let b = Bifid::new(BifidKey {
+    ///     phrase: String::from("bgwkzqpndsioaxefclumthyvr"),
+    ///     period: 10,
+    /// });
+    /// assert_eq!("Flee at once!", b.decrypt("Uaeo lw rins!").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        Ok(self.transform(ciphertext, |coords| {
+            // The first half of the coordinate sequence are the rows, the second the columns
+            let seq: Vec<usize> = coords.iter().flat_map(|c| vec![c.0, c.1]).collect();
+            let (rows, cols) = seq.split_at(coords.len());
+
+            rows.iter().cloned().zip(cols.iter().cloned()).collect()
+        }))
+    }
+
+    /// Whether a character is foreign to the cipher, being outside of the square. In the 5x5
+    /// square `J` is enciphered as `I`, so is not foreign.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        self.coordinates(c).is_none()
+    }
+}
+
+impl Bifid {
+    /// The number of characters that are fractionated together.
+    ///
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Initialise a Bifid cipher with a 6x6 alphanumeric square.
+    ///
+    /// The `phrase` is used to generate an alphanumeric keyed alphabet that fills the square.
+    /// It can contain characters `a-z 0-9`. As letters may be substituted for digits (and vice
+    /// versa), the case of the message is not retained and all output is uppercase.
+    ///
+    /// # Panics
+    /// * The `phrase` contains non-alphanumeric symbols.
+    /// * The `period` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Bifid};
+    ///
+    /// let b = Bifid::alphanumeric("or0an3ge", 5);
+    /// assert_eq!("10 ORANGES", b.decrypt(&b.encrypt("10 Oranges").unwrap()).unwrap());
+    /// ```
+    ///
+    pub fn alphanumeric(phrase: &str, period: usize) -> Bifid {
+        let keyed = keygen::keyed_alphabet(phrase, &alphabet::ALPHANUMERIC, true);
+
+        Bifid::from_square(&keyed, &alphabet::ALPHANUMERIC, period)
+    }
+
+    /// Lay out a keyed alphabet as a polybius square, reading it back row by row.
+    ///
+    fn from_square<T: Alphabet>(keyed: &str, alpha_type: &T, period: usize) -> Bifid {
+        if period == 0 {
+            panic!("The period must be greater than 0.");
+        }
+
+        let size = (alpha_type.length() as f64).sqrt() as usize;
+        let ids = &SQUARE_IDS[0..size];
+        let square = keygen::polybius_square(keyed, alpha_type, ids, ids);
+
+        let mut grid = Vec::new();
+        for row in ids {
+            for col in ids {
+                grid.push(square[&format!("{}{}", row, col)]);
+            }
+        }
+
+        Bifid {
+            square: grid,
+            size,
+            period,
+        }
+    }
+
+    /// Find the (row, column) coordinates of a character within the square.
+    ///
+    fn coordinates(&self, c: char) -> Option<(usize, usize)> {
+        let c = match c.to_ascii_uppercase() {
+            'J' if self.size == 5 => 'I',
+            upper => upper,
+        };
+
+        self.square
+            .iter()
+            .position(|&s| s == c)
+            .map(|pos| (pos / self.size, pos % self.size))
+    }
+
+    /// Fractionate every character of the text that is part of the square. For each block of
+    /// `period` coordinates, the closure `fractionate` returns the transformed coordinates.
+    /// The transformed characters are then placed back in the same positions, retaining case.
+    ///
+    fn transform<F>(&self, text: &str, fractionate: F) -> String
+    where
+        F: Fn(&[(usize, usize)]) -> Vec<(usize, usize)>,
+    {
+        let coords: Vec<(usize, usize)> =
+            text.chars().filter_map(|c| self.coordinates(c)).collect();
+        let mut transformed = coords
+            .chunks(self.period)
+            .flat_map(fractionate)
+            .map(|(row, col)| self.square[row * self.size + col]);
+
+        text.chars()
+            .map(|c| match self.coordinates(c) {
+                Some(_) => {
+                    let t = transformed.next().expect("Transformed text is too short.");
+                    // Case can only be retained when the square is purely alphabetic
+                    if c.is_lowercase() && self.size == 5 {
+                        t.to_ascii_lowercase()
+                    } else {
+                        t
+                    }
+                }
+                None => c,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::policy::ForeignCharPolicy;
+
+    #[test]
+    fn encrypt_message() {
+        let b = Bifid::new(BifidKey {
+            phrase: String::from("bgwkzqpndsioaxefclumthyvr"),
+            period: 10,
+        });
+        assert_eq!("UAEOLWRINS", b.encrypt("FLEEATONCE").unwrap());
+    }
+
+    #[test]
+    fn decrypt_message() {
+        let b = Bifid::new(BifidKey {
+            phrase: String::from("bgwkzqpndsioaxefclumthyvr"),
+            period: 10,
+        });
+        assert_eq!("FLEEATONCE", b.decrypt("UAEOLWRINS").unwrap());
+    }
+
+    #[test]
+    fn period_changes_ciphertext() {
+        let m = "Defend the east wall of the castle";
+        let b1 = Bifid::new(BifidKey {
+            phrase: String::from("playfair"),
+            period: 5,
+        });
+        let b2 = Bifid::new(BifidKey {
+            phrase: String::from("playfair"),
+            period: 7,
+        });
+
+        assert_ne!(b1.encrypt(m).unwrap(), b2.encrypt(m).unwrap());
+        assert_eq!(m, b1.decrypt(&b1.encrypt(m).unwrap()).unwrap());
+        assert_eq!(m, b2.decrypt(&b2.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn j_is_treated_as_i() {
+        let b = Bifid::new(BifidKey {
+            phrase: String::from("jam"),
+            period: 5,
+        });
+        assert_eq!("iiump", b.decrypt(&b.encrypt("jjump").unwrap()).unwrap());
+    }
+
+    #[test]
+    fn with_utf8() {
+        let m = "Attack 🗡️ the east wall";
+        let b = Bifid::new(BifidKey {
+            phrase: String::from("utfeight"),
+            period: 4,
+        });
+
+        assert_eq!(m, b.decrypt(&b.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn alphanumeric_square() {
+        let m = "10 Oranges and 2 Apples!";
+        let b = Bifid::alphanumeric("or0an3ge", 6);
+
+        let c = b.encrypt(m).unwrap();
+        assert_ne!(m.to_uppercase(), c);
+        assert_eq!(m.to_uppercase(), b.decrypt(&c).unwrap());
+    }
+
+    #[test]
+    fn strip_keeps_square() {
+        let b = Bifid::new(BifidKey {
+            phrase: String::from("jam"),
+            period: 5,
+        });
+        assert_eq!(
+            b.encrypt("Jumpjump"),
+            b.encrypt_with_policy("Jump, jump!", ForeignCharPolicy::Strip)
+        );
+
+        let b = Bifid::alphanumeric("or0an3ge", 6);
+        assert_eq!(
+            b.encrypt("10Oranges"),
+            b.encrypt_with_policy("10 Oranges!", ForeignCharPolicy::Strip)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_period() {
+        Bifid::new(BifidKey {
+            phrase: String::from("playfair"),
+            period: 0,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_phrase() {
+        Bifid::new(BifidKey {
+            phrase: String::from("F@IL"),
+            period: 5,
+        });
+    }
+}
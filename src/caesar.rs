@@ -4,32 +4,152 @@
 //! As with all single-alphabet substitution ciphers, the Caesar cipher is easily broken
 //! and in modern practice offers essentially no communication security.
 //!
-use crate::common::alphabet::Alphabet;
-use crate::common::cipher::Cipher;
-use crate::common::{alphabet, substitute};
+use crate::common::alphabet::{Alphabet, Custom};
+use crate::common::cipher::{ByteCipher, CharCipher, Cipher};
+use crate::common::key_problem::KeyProblem;
+#[cfg(feature = "parallel")]
+use crate::common::parallel;
+use crate::common::substitute;
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
 
 /// A Caesar cipher.
 ///
-/// This struct is created by the `new()` method. See its documentation for more.
+/// This struct is created by the `new()` or `with_alphabet()` methods. See their documentation
+/// for more.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Caesar {
     shift: usize,
+    alphabet: Custom,
 }
 
-impl Cipher for Caesar {
-    type Key = usize;
-    type Algorithm = Caesar;
-
+impl Caesar {
     /// Initialise a Caesar cipher given a specific shift value.
     ///
+    /// This can be called in a `const` context, where an invalid shift fails to compile.
+    ///
     /// # Panics
     /// * `shift` is not in the inclusive range `1 - 26`.
     ///
-    fn new(shift: usize) -> Caesar {
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Caesar, Cipher};
+    ///
+    /// const ROT3: Caesar = Caesar::new(3);
+    /// assert_eq!("Dwwdfn dw gdzq!", ROT3.encrypt("Attack at dawn!").unwrap());
+    /// ```
+    ///
+    pub const fn new(shift: usize) -> Caesar {
         if shift < 1 || shift > 26 {
             panic!("The shift factor must be within the range 1 <= n <= 26.");
         }
 
-        Caesar { shift }
+        Caesar {
+            shift,
+            alphabet: Custom::standard(),
+        }
+    }
+
+    /// The number of places that the cipher shifts each symbol by.
+    ///
+    pub fn shift(&self) -> usize {
+        self.shift
+    }
+
+    /// Whether the cipher leaves a `text` unchanged, as it does when the shift is the length of
+    /// the alphabet or the text has no symbols of the alphabet.
+    ///
+    fn leaves_unchanged(&self, text: &str) -> bool {
+        self.shift == self.alphabet.length() || text.chars().all(|c| self.is_foreign(c))
+    }
+
+    /// Initialise a Caesar cipher that shifts the symbols of an `alphabet` other than `a-z`.
+    ///
+    /// Characters outside of the alphabet are left as they are.
+    ///
+    /// # Panics
+    /// * `shift` is not in the inclusive range from 1 to the length of the `alphabet`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::alphabet::ALPHANUMERIC;
+    /// use cipher_crypt::{Caesar, Cipher};
+    ///
+    /// let c = Caesar::with_alphabet(3, &ALPHANUMERIC);
+    /// assert_eq!("Wkh 4vw xqlw lv dw c!", c.encrypt("The 1st unit is at 9!").unwrap());
+    /// ```
+    ///
+    pub fn with_alphabet<A: Alphabet>(shift: usize, alphabet: &A) -> Caesar {
+        if shift < 1 || shift > alphabet.length() {
+            panic!(
+                "The shift factor must be within the range 1 <= n <= {}.",
+                alphabet.length()
+            );
+        }
+
+        Caesar {
+            shift,
+            alphabet: Custom::from_alphabet(alphabet),
+        }
+    }
+
+    /// List the problems with a `shift` that would stop it from being given to `new()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Caesar, KeyProblem};
+    ///
+    /// assert!(Caesar::key_problems(3).is_empty());
+    /// assert_eq!(
+    ///     vec![KeyProblem::OutOfRange { value: 27, min: 1, max: 26 }],
+    ///     Caesar::key_problems(27)
+    /// );
+    /// ```
+    ///
+    pub fn key_problems(shift: usize) -> Vec<KeyProblem> {
+        if (1..=26).contains(&shift) {
+            Vec::new()
+        } else {
+            vec![KeyProblem::OutOfRange {
+                value: shift,
+                min: 1,
+                max: 26,
+            }]
+        }
+    }
+
+    /// Encrypt a long message using a Caesar cipher, sharing the work across threads.
+    ///
+    /// The message is split into chunks, which are encrypted on the threads of the global `rayon`
+    /// thread pool. The ciphertext is the same as that of `encrypt()`.
+    ///
+    #[cfg(feature = "parallel")]
+    pub fn encrypt_parallel(&self, message: &str) -> Result<String, &'static str> {
+        parallel::map_chunks(message, |chunk| self.encrypt(chunk))
+    }
+
+    /// Decrypt a long message using a Caesar cipher, sharing the work across threads.
+    ///
+    /// The plaintext is the same as that of `decrypt()`.
+    ///
+    #[cfg(feature = "parallel")]
+    pub fn decrypt_parallel(&self, ciphertext: &str) -> Result<String, &'static str> {
+        parallel::map_chunks(ciphertext, |chunk| self.decrypt(chunk))
+    }
+}
+
+impl Cipher for Caesar {
+    type Key = usize;
+    type Algorithm = Caesar;
+
+    /// Initialise a Caesar cipher given a specific key, as by `Caesar::new()`.
+    ///
+    fn new(key: usize) -> Caesar {
+        Caesar::new(key)
     }
 
     /// Encrypt a message using a Caesar cipher.
@@ -45,14 +165,14 @@ impl Cipher for Caesar {
     /// ```
     ///
     fn encrypt(&self, message: &str) -> Result<String, &'static str> {
-        // Encryption of a letter:
-        //         E(x) = (x + n) mod 26
-        // Where;  x = position of letter in alphabet
-        //         n = shift factor (or key)
+        Ok(self.encrypt_chars(message.chars()).collect())
+    }
 
-        Ok(substitute::shift_substitution(message, |idx| {
-            alphabet::STANDARD.modulo((idx + self.shift) as isize)
-        }))
+    /// Encrypt a message onto the end of `out`, one character at a time.
+    ///
+    fn encrypt_into(&self, message: &str, out: &mut String) -> Result<(), &'static str> {
+        out.extend(self.encrypt_chars(message.chars()));
+        Ok(())
     }
 
     /// Decrypt a message using a Caesar cipher.
@@ -68,20 +188,105 @@ impl Cipher for Caesar {
     /// ```
     ///
     fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        Ok(self.decrypt_chars(ciphertext.chars()).collect())
+    }
+
+    /// Whether a character is foreign to the cipher, being outside of its alphabet.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        self.alphabet.find_position(c).is_none()
+    }
+
+    /// Encrypt a message, borrowing it where the shift would leave it unchanged.
+    ///
+    fn encrypt_cow<'a>(&self, message: &'a str) -> Result<Cow<'a, str>, &'static str> {
+        if self.leaves_unchanged(message) {
+            return Ok(Cow::Borrowed(message));
+        }
+
+        self.encrypt(message).map(Cow::Owned)
+    }
+
+    /// Decrypt a ciphertext, borrowing it where the shift would leave it unchanged.
+    ///
+    fn decrypt_cow<'a>(&self, ciphertext: &'a str) -> Result<Cow<'a, str>, &'static str> {
+        if self.leaves_unchanged(ciphertext) {
+            return Ok(Cow::Borrowed(ciphertext));
+        }
+
+        self.decrypt(ciphertext).map(Cow::Owned)
+    }
+}
+
+impl CharCipher for Caesar {
+    fn encrypt_char(&self, c: char) -> char {
+        // Encryption of a letter:
+        //         E(x) = (x + n) mod 26
+        // Where;  x = position of letter in alphabet
+        //         n = shift factor (or key)
+
+        substitute::alphabet_shift_char(c, &self.alphabet, |idx| {
+            self.alphabet.modulo((idx + self.shift) as isize)
+        })
+    }
+
+    fn decrypt_char(&self, c: char) -> char {
         // Decryption of a letter:
         //         D(x) = (x - n) mod 26
         // Where;  x = position of letter in alphabet
         //         n = shift factor (or key)
 
-        Ok(substitute::shift_substitution(ciphertext, |idx| {
-            alphabet::STANDARD.modulo(idx as isize - self.shift as isize)
-        }))
+        substitute::alphabet_shift_char(c, &self.alphabet, |idx| {
+            self.alphabet.modulo(idx as isize - self.shift as isize)
+        })
+    }
+}
+
+impl ByteCipher for Caesar {}
+
+impl FromStr for Caesar {
+    type Err = &'static str;
+
+    /// Parse a Caesar cipher over the standard alphabet from its shift, such as `"3"`.
+    ///
+    /// Returns `Err` if the shift is not a number in the inclusive range `1 - 26`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Caesar, Cipher};
+    ///
+    /// let c: Caesar = "3".parse().unwrap();
+    /// assert_eq!("Dwwdfn dw gdzq!", c.encrypt("Attack at dawn!").unwrap());
+    /// assert_eq!("3", c.to_string());
+    /// ```
+    ///
+    fn from_str(s: &str) -> Result<Caesar, &'static str> {
+        let shift: usize = s
+            .trim()
+            .parse()
+            .map_err(|_| "The shift must be a number.")?;
+        if !(1..=26).contains(&shift) {
+            return Err("The shift factor must be within the range 1 <= n <= 26.");
+        }
+
+        Ok(Caesar::new(shift))
+    }
+}
+
+impl fmt::Display for Caesar {
+    /// Writes the shift of the cipher, as parsed by `from_str()`.
+    ///
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.shift)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::alphabet;
+    use crate::common::policy::ForeignCharPolicy;
 
     #[test]
     fn encrypt_message() {
@@ -89,6 +294,26 @@ mod tests {
         assert_eq!("Cvvcem cv fcyp!", c.encrypt("Attack at dawn!").unwrap());
     }
 
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn parallel_long_message() {
+        let c = Caesar::new(7);
+        let message = "Attack at dawn 🗡️ ".repeat(10_000);
+        let encrypted = c.encrypt_parallel(&message).unwrap();
+
+        assert_eq!(c.encrypt(&message).unwrap(), encrypted);
+        assert_eq!(message, c.decrypt_parallel(&encrypted).unwrap());
+    }
+
+    #[test]
+    fn clone_and_compare() {
+        let c = Caesar::new(3);
+
+        assert_eq!(c, c.clone());
+        assert_ne!(c, Caesar::new(4));
+        assert_eq!("Caesar { .. }", format!("{:?}", c.redacted()));
+    }
+
     #[test]
     fn decrypt_message() {
         let c = Caesar::new(2);
@@ -105,6 +330,42 @@ mod tests {
         assert_eq!(decrypted, message);
     }
 
+    #[test]
+    fn encrypt_chars_lazily() {
+        let c = Caesar::new(3);
+        let message = "Attack at dawn! 🗡️";
+        let encrypted: String = c.encrypt_chars(message.chars()).collect();
+        let decrypted: String = c.decrypt_chars(encrypted.chars()).collect();
+
+        assert_eq!(c.encrypt(message).unwrap(), encrypted);
+        assert_eq!(message, decrypted);
+
+        // An endless stream is only encrypted as far as it is read
+        let first: String = c.encrypt_chars(std::iter::repeat('z')).take(3).collect();
+        assert_eq!("ccc", first);
+    }
+
+    #[test]
+    fn custom_alphabet() {
+        let c = Caesar::with_alphabet(1, &alphabet::Custom::new("αβγδε"));
+        assert_eq!("Βγδ, εα!", c.encrypt("Αβγ, δε!").unwrap());
+        assert_eq!("Αβγ, δε!", c.decrypt("Βγδ, εα!").unwrap());
+    }
+
+    #[test]
+    fn bytes_outside_ascii() {
+        let c = Caesar::with_alphabet(3, &alphabet::Custom::new("abcé"));
+        assert!(c.encrypt_bytes(b"a").is_err());
+        assert_eq!(b"a!".to_vec(), c.encrypt_bytes(b"b!").unwrap());
+    }
+
+    #[test]
+    fn parse_shift() {
+        assert_eq!("25", " 25 ".parse::<Caesar>().unwrap().to_string());
+        assert!("0".parse::<Caesar>().is_err());
+        assert!("three".parse::<Caesar>().is_err());
+    }
+
     #[test]
     fn exhaustive_encrypt() {
         //Test with every possible shift combination
@@ -129,4 +390,53 @@ mod tests {
     fn key_to_big() {
         Caesar::new(27);
     }
+
+    #[test]
+    #[should_panic]
+    fn key_to_big_for_alphabet() {
+        Caesar::with_alphabet(11, &alphabet::NUMERIC);
+    }
+
+    #[test]
+    fn reject_outside_alphabet() {
+        let c = Caesar::with_alphabet(3, &alphabet::ALPHANUMERIC);
+
+        assert!(c
+            .encrypt_with_policy("Attack at 1200", ForeignCharPolicy::Reject)
+            .is_err());
+        assert_eq!(
+            c.encrypt("Attackat1200"),
+            c.encrypt_with_policy("Attackat1200", ForeignCharPolicy::Reject)
+        );
+    }
+
+    #[test]
+    fn encrypt_into_appends() {
+        let c = Caesar::new(3);
+        let mut out = String::new();
+        for m in ["Attack ", "at dawn!"].iter() {
+            c.encrypt_into(m, &mut out).unwrap();
+        }
+
+        assert_eq!(c.encrypt("Attack at dawn!").unwrap(), out);
+    }
+
+    #[test]
+    fn cow_borrows_unchanged() {
+        assert!(matches!(
+            Caesar::new(26).encrypt_cow("Attack"),
+            Ok(Cow::Borrowed(_))
+        ));
+        assert!(matches!(
+            Caesar::new(3).decrypt_cow("1984!"),
+            Ok(Cow::Borrowed(_))
+        ));
+        assert_eq!("Dwwdfn", Caesar::new(3).encrypt_cow("Attack").unwrap());
+    }
+
+    #[test]
+    fn const_new_matches_with_alphabet() {
+        const C: Caesar = Caesar::new(3);
+        assert_eq!(Caesar::with_alphabet(3, &alphabet::STANDARD), C);
+    }
 }
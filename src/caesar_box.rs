@@ -0,0 +1,180 @@
+//! The Caesar Box (or square route) cipher is one of the simplest transposition ciphers, and is
+//! said to have been used by Julius Caesar.
+//!
+//! The message is written along the rows of a square, and then read down its columns. For
+//! example, the message `attackatdawn!` fills a 4x4 square as follows:
+//!
+//! ```text
+//! a t t a
+//! c k a t
+//! d a w n
+//! !
+//! ```
+//!
+//! The ciphertext is `acd!tka taw atn `. Unless the size of the square is given, the smallest
+//! square that fits the message is used. Any unused cells of the square are filled with spaces,
+//! so the length of the ciphertext is always a square number.
+//!
+use crate::common::cipher::Cipher;
+
+/// A Caesar Box cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaesarBox {
+    size: Option<usize>,
+}
+
+impl Cipher for CaesarBox {
+    type Key = Option<usize>;
+    type Algorithm = CaesarBox;
+
+    /// Initialise a Caesar Box cipher.
+    ///
+    /// The `key` is the optional length of a side of the square. If it is `None`, the smallest
+    /// square that fits the message is used.
+    ///
+    /// # Panics
+    /// * The `key` is `Some(0)`.
+    ///
+    fn new(key: Option<usize>) -> CaesarBox {
+        if key == Some(0) {
+            panic!("The size of the square cannot be zero.");
+        }
+
+        CaesarBox { size: key }
+    }
+
+    /// Encrypt a message using a Caesar Box cipher.
+    ///
+    /// Whilst all characters (including utf8) can be encrypted during the transposition process,
+    /// it is important to note that the space character is also treated as padding. As such,
+    /// whitespace characters at the end of a message are not preserved during the decryption
+    /// process.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, CaesarBox};
+    ///
+    /// let c = CaesarBox::new(None);
+    /// assert_eq!("acd!tka taw atn ", c.encrypt("attackatdawn!").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        let mut chars: Vec<char> = message.chars().collect();
+        let size = match self.size {
+            Some(s) if s * s < chars.len() => {
+                return Err("The message does not fit in the square.")
+            }
+            Some(s) => s,
+            None => smallest_square(chars.len()),
+        };
+        chars.resize(size * size, ' ');
+
+        Ok(transpose(&chars, size))
+    }
+
+    /// Decrypt a message using a Caesar Box cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, CaesarBox};
+    ///
+    /// let c = CaesarBox::new(None);
+    /// assert_eq!("attackatdawn!", c.decrypt("acd!tka taw atn ").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let chars: Vec<char> = ciphertext.chars().collect();
+        let size = self.size.unwrap_or_else(|| smallest_square(chars.len()));
+        if size * size != chars.len() {
+            return Err("The ciphertext does not fill the square.");
+        }
+
+        // Reading down the columns of a square is its own inverse
+        Ok(transpose(&chars, size).trim_end().to_string())
+    }
+}
+
+/// The length of a side of the smallest square that fits `len` characters.
+///
+fn smallest_square(len: usize) -> usize {
+    (0..).find(|s| s * s >= len).unwrap()
+}
+
+/// Read the characters of a square, written along its rows, down its columns.
+///
+fn transpose(chars: &[char], size: usize) -> String {
+    (0..size)
+        .flat_map(|col| (0..size).map(move |row| chars[row * size + col]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_test() {
+        let c = CaesarBox::new(None);
+        assert_eq!("aaatcttkd", c.encrypt("attackatd").unwrap());
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let c = CaesarBox::new(None);
+        assert_eq!("attackatd", c.decrypt("aaatcttkd").unwrap());
+    }
+
+    #[test]
+    fn square_is_inferred() {
+        let c = CaesarBox::new(None);
+
+        assert_eq!(9, c.encrypt("attackatd").unwrap().chars().count());
+        assert_eq!(16, c.encrypt("attackatdawn").unwrap().chars().count());
+        assert_eq!(25, c.encrypt("attackatdawnnow!!").unwrap().chars().count());
+    }
+
+    #[test]
+    fn given_size() {
+        let c = CaesarBox::new(Some(5));
+        let ciphertext = c.encrypt("attackatdawn").unwrap();
+
+        assert_eq!("akw  tan  tt   ad   ca   ", ciphertext);
+        assert_eq!("attackatdawn", c.decrypt(&ciphertext).unwrap());
+    }
+
+    #[test]
+    fn message_too_long() {
+        assert!(CaesarBox::new(Some(3)).encrypt("attackatdawn").is_err());
+    }
+
+    #[test]
+    fn ciphertext_not_square() {
+        assert!(CaesarBox::new(None).decrypt("attackatdawn").is_err());
+    }
+
+    #[test]
+    fn empty_message() {
+        let c = CaesarBox::new(None);
+        assert_eq!("", c.decrypt(&c.encrypt("").unwrap()).unwrap());
+    }
+
+    #[test]
+    fn with_utf8() {
+        let c = CaesarBox::new(None);
+        let m = "Peace 🗡️ Freedom and Liberty!";
+
+        assert_eq!(m, c.decrypt(&c.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_size() {
+        CaesarBox::new(Some(0));
+    }
+}
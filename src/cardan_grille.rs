@@ -0,0 +1,283 @@
+//! The Cardan grille was invented by Gerolamo Cardano in 1550. Like the Baconian cipher, it hides
+//! a secret message in plain sight rather than generating ciphertext (steganography).
+//!
+//! A grille is a sheet of card with holes cut into it. The sender lays the grille over the page
+//! and writes the secret message through the holes. The grille is then removed, and the rest of
+//! the page is filled with an innocent looking decoy text. The recipient, who holds an identical
+//! grille, lays it over the page to read the message back.
+//!
+//! In this implementation a grille is described by a mask, where each `X` is a hole and each `.`
+//! (or `-`) is solid card. If the message does not fit through the holes of a single grille, the
+//! grille is laid over the text again, immediately after the previous position.
+//!
+//! If no decoy text is given, the boilerplate of "Lorem ipsum..." is used.
+//!
+use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
+use lipsum::lipsum;
+
+/// A Cardan Grille cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CardanGrille {
+    holes: Vec<bool>,
+    decoy_text: Option<String>,
+}
+
+/// The key of a Cardan Grille cipher.
+///
+/// This struct is given to the `new()` method of `CardanGrille`. See its documentation for
+/// more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CardanGrilleKey {
+    /// A mask of holes `X` and solid cells `.` or `-`. Whitespace is ignored, so the mask
+    /// can be written as rows on separate lines.
+    pub grille: String,
+    /// An optional decoy text that fills the cells of the page that are not holes - default
+    /// is boilerplate "Lorem ipsum" text.
+    pub decoy_text: Option<String>,
+}
+
+impl Cipher for CardanGrille {
+    type Key = CardanGrilleKey;
+    type Algorithm = CardanGrille;
+
+    /// Initialise a Cardan Grille cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `CardanGrilleKey`.
+    ///
+    /// # Panics
+    /// * The `grille` contains a symbol other than `X`, `.`, `-` or whitespace.
+    /// * The `grille` has no holes.
+    ///
+    fn new(key: CardanGrilleKey) -> CardanGrille {
+        let holes: Vec<bool> = key
+            .grille
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| match c {
+                'X' | 'x' => true,
+                '.' | '-' => false,
+                _ => panic!("The grille may only contain holes `X` or solid cells `.` and `-`."),
+            })
+            .collect();
+
+        if !holes.contains(&true) {
+            panic!("The grille must contain at least one hole.");
+        }
+
+        CardanGrille {
+            holes,
+            decoy_text: key.decoy_text,
+        }
+    }
+
+    /// Encrypt a message using a Cardan Grille cipher.
+    ///
+    /// The grille is laid over the page as many times as needed to fit the message. Any holes
+    /// left over at the end of the message are filled with spaces.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, CardanGrille, CardanGrilleKey};
+    ///
+    /// let grille = String::from("X..X.\n..X..");
+    /// let decoy = String::from("the weather is lovely today");
+    ///
+    /// let c = CardanGrille::new(CardanGrilleKey { grille, decoy_text: Some(decoy) });
+    /// assert_eq!("rthse wveapth er  is", c.encrypt("rsvp").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.validate_message(message)?;
+
+        let mut secret = message.chars();
+        let mut decoy = self.decoy(message).into_iter();
+        let mut page = String::new();
+
+        for hole in self.holes.iter().cycle().take(self.page_len(message)) {
+            let c = if *hole {
+                secret.next().unwrap_or(' ')
+            } else {
+                decoy
+                    .next()
+                    .ok_or("Decoy text is too short to hide the message.")?
+            };
+            page.push(c);
+        }
+
+        Ok(page)
+    }
+
+    /// Decrypt a message using a Cardan Grille cipher.
+    ///
+    /// Trailing whitespace in the revealed message is removed.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, CardanGrille, CardanGrilleKey};
+    ///
+    /// let c = CardanGrille::new(CardanGrilleKey {
+    ///     grille: String::from("X..X...X.."),
+    ///     decoy_text: None,
+    /// });
+    /// assert_eq!("rsvp", c.decrypt("rthsewevatpea").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let message: String = ciphertext
+            .chars()
+            .zip(self.holes.iter().cycle())
+            .filter(|&(_, hole)| *hole)
+            .map(|(c, _)| c)
+            .collect();
+
+        Ok(message.trim_end().to_string())
+    }
+
+    /// Check that a message can be encrypted with a Cardan Grille cipher.
+    ///
+    /// Returns `Err` if a decoy text was given, and it is too short to fill the solid cells of
+    /// the page needed to hide the message.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, CardanGrille, CardanGrilleKey};
+    ///
+    /// let c = CardanGrille::new(CardanGrilleKey {
+    ///     grille: String::from("X.."),
+    ///     decoy_text: Some(String::from("decoy")),
+    /// });
+    ///
+    /// assert!(c.validate_message("hi").is_ok());
+    /// assert!(c.validate_message("hello").is_err());
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        if self.decoy(message).len() < self.page_len(message) - self.hole_count(message) {
+            return Err(CipherError::Message(
+                "Decoy text is too short to hide the message.",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl CardanGrille {
+    /// The number of cells needed on the page, laying the grille down as many times as required
+    /// to fit the message.
+    ///
+    fn page_len(&self, message: &str) -> usize {
+        let per_grille = self.holes.iter().filter(|&&h| h).count();
+        let grilles = message.chars().count().div_ceil(per_grille).max(1);
+
+        grilles * self.holes.len()
+    }
+
+    /// The number of holes on the page.
+    ///
+    fn hole_count(&self, message: &str) -> usize {
+        self.holes
+            .iter()
+            .cycle()
+            .take(self.page_len(message))
+            .filter(|&&h| h)
+            .count()
+    }
+
+    /// The decoy characters used to fill the solid cells of the page.
+    ///
+    fn decoy(&self, message: &str) -> Vec<char> {
+        match self.decoy_text {
+            Some(ref text) => text.chars().collect(),
+            None => {
+                // Each word is at least one character long, so this is always enough text
+                lipsum(self.page_len(message)).chars().collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grille(decoy: &str) -> CardanGrille {
+        CardanGrille::new(CardanGrilleKey {
+            grille: String::from("X..X."),
+            decoy_text: Some(String::from(decoy)),
+        })
+    }
+
+    #[test]
+    fn encrypt_test() {
+        assert_eq!("habic", grille("abcdefghij").encrypt("hi").unwrap());
+    }
+
+    #[test]
+    fn decrypt_test() {
+        assert_eq!("hi", grille("abcdefghij").decrypt("habic").unwrap());
+    }
+
+    #[test]
+    fn multiple_grilles() {
+        let c = grille("abcdefghij");
+        let page = c.encrypt("hello").unwrap();
+
+        assert_eq!(15, page.chars().count());
+        assert_eq!("hello", c.decrypt(&page).unwrap());
+    }
+
+    #[test]
+    fn default_decoy() {
+        let m = "Meet me at the old mill at midnight";
+        let c = CardanGrille::new(CardanGrilleKey {
+            grille: String::from("..X.\n.X..\nX..X"),
+            decoy_text: None,
+        });
+
+        assert_eq!(m, c.decrypt(&c.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn with_utf8() {
+        let m = "Peace, Freedom 🗡️ and Liberty!";
+        let c = CardanGrille::new(CardanGrilleKey {
+            grille: String::from("-X-X--X"),
+            decoy_text: None,
+        });
+
+        assert_eq!(m, c.decrypt(&c.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn decoy_too_short() {
+        assert!(grille("abc").encrypt("hello").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_grille() {
+        CardanGrille::new(CardanGrilleKey {
+            grille: String::from("X..O."),
+            decoy_text: None,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn grille_without_holes() {
+        CardanGrille::new(CardanGrilleKey {
+            grille: String::from("...\n..."),
+            decoy_text: None,
+        });
+    }
+}
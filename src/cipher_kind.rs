@@ -0,0 +1,381 @@
+//! An enumeration of the ciphers of this crate, so that a cipher can be chosen at runtime.
+//!
+//! Each cipher is its own type, with its own type of key, so a program that lets its user choose
+//! the cipher (from a configuration file or a command line flag, say) would otherwise need to
+//! match on the choice wherever a cipher is used. A `CipherKind` holds the choice of cipher along
+//! with its key, and implements `Cipher` itself by dispatching to the chosen cipher.
+//!
+use crate::common::cipher::{Cipher, DynCipher};
+use crate::common::cipher_error::CipherError;
+use crate::{
+    ADFGVXKey, Affine, AffineKey, Autokey, Baconian, BaconianOptions, Beaufort, Bifid, BifidKey,
+    Caesar, ColumnarTransposition, ColumnarTranspositionKey, FractionatedMorse, Gronsfeld, Hill,
+    Keyword, KeywordCaesar, KeywordCaesarKey, OneTimePad, Playfair, PlayfairKey, Polybius,
+    PolybiusKey, Porta, Railfence, Scytale, SimpleSubstitution, Trifid, TrifidKey, Vigenere,
+    Wolseley, ADFGVX,
+};
+
+/// A cipher and its key, chosen at runtime.
+///
+/// The fields of each variant are the parts of the key of that cipher. See the documentation of
+/// each cipher's `new()` method for more.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::{Cipher, CipherKind};
+///
+/// let kinds = vec![
+///     CipherKind::Caesar { shift: 3 },
+///     CipherKind::Vigenere { key: String::from("lemon") },
+/// ];
+///
+/// for kind in kinds {
+///     let c = CipherKind::new(kind);
+///     assert_eq!("Attack at dawn!", c.decrypt(&c.encrypt("Attack at dawn!").unwrap()).unwrap());
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CipherKind {
+    ADFGVX {
+        polybius_key: String,
+        columnar_key: String,
+        null_char: Option<char>,
+    },
+    Affine {
+        a: usize,
+        b: usize,
+    },
+    Autokey {
+        key: String,
+    },
+    Baconian {
+        distinct: bool,
+        decoy_text: Option<String>,
+    },
+    Beaufort {
+        key: String,
+    },
+    Bifid {
+        phrase: String,
+        period: usize,
+    },
+    Caesar {
+        shift: usize,
+    },
+    ColumnarTransposition {
+        keystream: String,
+        null_char: Option<char>,
+    },
+    FractionatedMorse {
+        key: String,
+    },
+    Gronsfeld {
+        key: String,
+    },
+    Hill {
+        phrase: String,
+        chunk_size: usize,
+    },
+    Keyword {
+        keyword: String,
+    },
+    KeywordCaesar {
+        keyword: String,
+        shift: usize,
+    },
+    OneTimePad {
+        pad: String,
+    },
+    Playfair {
+        keystream: String,
+        null_char: Option<char>,
+    },
+    Polybius {
+        phrase: String,
+        column_ids: [char; 6],
+        row_ids: [char; 6],
+    },
+    Porta {
+        key: String,
+    },
+    Railfence {
+        rails: usize,
+    },
+    Scytale {
+        height: usize,
+    },
+    SimpleSubstitution {
+        key: String,
+    },
+    Trifid {
+        phrase: String,
+        period: usize,
+    },
+    Vigenere {
+        key: String,
+    },
+    Wolseley {
+        keyword: String,
+    },
+}
+
+impl CipherKind {
+    /// The name of the chosen cipher, by which it is looked up in the `registry`.
+    ///
+    pub fn name(&self) -> &'static str {
+        match self {
+            CipherKind::ADFGVX { .. } => "adfgvx",
+            CipherKind::Affine { .. } => "affine",
+            CipherKind::Autokey { .. } => "autokey",
+            CipherKind::Baconian { .. } => "baconian",
+            CipherKind::Beaufort { .. } => "beaufort",
+            CipherKind::Bifid { .. } => "bifid",
+            CipherKind::Caesar { .. } => "caesar",
+            CipherKind::ColumnarTransposition { .. } => "columnar_transposition",
+            CipherKind::FractionatedMorse { .. } => "fractionated_morse",
+            CipherKind::Gronsfeld { .. } => "gronsfeld",
+            CipherKind::Hill { .. } => "hill",
+            CipherKind::Keyword { .. } => "keyword",
+            CipherKind::KeywordCaesar { .. } => "keyword_caesar",
+            CipherKind::OneTimePad { .. } => "one_time_pad",
+            CipherKind::Playfair { .. } => "playfair",
+            CipherKind::Polybius { .. } => "polybius",
+            CipherKind::Porta { .. } => "porta",
+            CipherKind::Railfence { .. } => "railfence",
+            CipherKind::Scytale { .. } => "scytale",
+            CipherKind::SimpleSubstitution { .. } => "simple_substitution",
+            CipherKind::Trifid { .. } => "trifid",
+            CipherKind::Vigenere { .. } => "vigenere",
+            CipherKind::Wolseley { .. } => "wolseley",
+        }
+    }
+
+    /// Initialise the chosen cipher with its key, as a trait object.
+    ///
+    /// # Panics
+    /// * The key could not be given to the chosen cipher.
+    ///
+    pub fn cipher(&self) -> Box<dyn DynCipher> {
+        match self.clone() {
+            CipherKind::ADFGVX {
+                polybius_key,
+                columnar_key,
+                null_char,
+            } => Box::new(ADFGVX::new(ADFGVXKey {
+                polybius_key,
+                columnar_key,
+                null_char,
+            })),
+            CipherKind::Affine { a, b } => Box::new(Affine::new(AffineKey { a, b })),
+            CipherKind::Autokey { key } => Box::new(Autokey::new(key)),
+            CipherKind::Baconian {
+                distinct,
+                decoy_text,
+            } => Box::new(Baconian::new(BaconianOptions {
+                distinct,
+                decoy_text,
+            })),
+            CipherKind::Beaufort { key } => Box::new(Beaufort::new(key)),
+            CipherKind::Bifid { phrase, period } => {
+                Box::new(Bifid::new(BifidKey { phrase, period }))
+            }
+            CipherKind::Caesar { shift } => Box::new(Caesar::new(shift)),
+            CipherKind::ColumnarTransposition {
+                keystream,
+                null_char,
+            } => Box::new(ColumnarTransposition::new(ColumnarTranspositionKey {
+                keystream,
+                null_char,
+            })),
+            CipherKind::FractionatedMorse { key } => Box::new(FractionatedMorse::new(key)),
+            CipherKind::Gronsfeld { key } => Box::new(Gronsfeld::new(key)),
+            CipherKind::Hill { phrase, chunk_size } => {
+                Box::new(Hill::from_phrase(&phrase, chunk_size))
+            }
+            CipherKind::Keyword { keyword } => Box::new(Keyword::new(keyword)),
+            CipherKind::KeywordCaesar { keyword, shift } => {
+                Box::new(KeywordCaesar::new(KeywordCaesarKey { keyword, shift }))
+            }
+            CipherKind::OneTimePad { pad } => Box::new(OneTimePad::new(pad)),
+            CipherKind::Playfair {
+                keystream,
+                null_char,
+            } => Box::new(Playfair::new(PlayfairKey {
+                keystream,
+                null_char,
+            })),
+            CipherKind::Polybius {
+                phrase,
+                column_ids,
+                row_ids,
+            } => Box::new(Polybius::new(PolybiusKey {
+                phrase,
+                column_ids,
+                row_ids,
+            })),
+            CipherKind::Porta { key } => Box::new(Porta::new(key)),
+            CipherKind::Railfence { rails } => Box::new(Railfence::new(rails)),
+            CipherKind::Scytale { height } => Box::new(Scytale::new(height)),
+            CipherKind::SimpleSubstitution { key } => Box::new(SimpleSubstitution::new(key)),
+            CipherKind::Trifid { phrase, period } => {
+                Box::new(Trifid::new(TrifidKey { phrase, period }))
+            }
+            CipherKind::Vigenere { key } => Box::new(Vigenere::new(key)),
+            CipherKind::Wolseley { keyword } => Box::new(Wolseley::new(keyword)),
+        }
+    }
+}
+
+impl Cipher for CipherKind {
+    type Key = CipherKind;
+    type Algorithm = CipherKind;
+
+    /// Initialise the cipher of a `kind`.
+    ///
+    /// The key is checked by initialising the chosen cipher. Its cipher is initialised again each
+    /// time a message is encrypted or decrypted, so where a single cipher is used for many
+    /// messages, the cipher itself should be preferred.
+    ///
+    /// # Panics
+    /// * The key could not be given to the chosen cipher.
+    ///
+    fn new(kind: CipherKind) -> CipherKind {
+        kind.cipher();
+        kind
+    }
+
+    /// Encrypt a message with the chosen cipher.
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.cipher().dyn_encrypt(message)
+    }
+
+    /// Decrypt a message with the chosen cipher.
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        self.cipher().dyn_decrypt(ciphertext)
+    }
+
+    /// Check that a message can be encrypted with the chosen cipher.
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        self.cipher().dyn_validate_message(message)
+    }
+
+    /// Whether a character is foreign to the chosen cipher.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        self.cipher().dyn_is_foreign(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dispatches_to_cipher() {
+        let kind = CipherKind::new(CipherKind::Caesar { shift: 3 });
+        assert_eq!(
+            Caesar::new(3).encrypt("Attack at dawn!"),
+            kind.encrypt("Attack at dawn!")
+        );
+    }
+
+    #[test]
+    fn every_kind() {
+        let ids = ['a', 'b', 'c', 'd', 'e', 'f'];
+        let key = String::from("fortification");
+        let kinds = vec![
+            CipherKind::ADFGVX {
+                polybius_key: String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
+                columnar_key: String::from("GERMAN"),
+                null_char: None,
+            },
+            CipherKind::Affine { a: 3, b: 7 },
+            CipherKind::Autokey { key: key.clone() },
+            CipherKind::Baconian {
+                distinct: false,
+                decoy_text: None,
+            },
+            CipherKind::Beaufort { key: key.clone() },
+            CipherKind::Bifid {
+                phrase: key.clone(),
+                period: 5,
+            },
+            CipherKind::Caesar { shift: 3 },
+            CipherKind::ColumnarTransposition {
+                keystream: String::from("zebras"),
+                null_char: None,
+            },
+            CipherKind::FractionatedMorse { key: key.clone() },
+            CipherKind::Gronsfeld {
+                key: String::from("31415"),
+            },
+            CipherKind::Hill {
+                phrase: String::from("CEFJCBDRH"),
+                chunk_size: 3,
+            },
+            CipherKind::Keyword {
+                keyword: key.clone(),
+            },
+            CipherKind::KeywordCaesar {
+                keyword: key.clone(),
+                shift: 5,
+            },
+            CipherKind::OneTimePad {
+                pad: String::from("xmckldpqwnvbjaosdkfelqwpz"),
+            },
+            CipherKind::Playfair {
+                keystream: String::from("playfair"),
+                null_char: None,
+            },
+            CipherKind::Polybius {
+                phrase: String::from("or0an3ge"),
+                column_ids: ids,
+                row_ids: ids,
+            },
+            CipherKind::Porta { key: key.clone() },
+            CipherKind::Railfence { rails: 3 },
+            CipherKind::Scytale { height: 3 },
+            CipherKind::SimpleSubstitution {
+                key: String::from("phqgiumeaylnofdxjkrcvstzwb"),
+            },
+            CipherKind::Trifid {
+                phrase: String::from("felixmardstbcghjknopquvwyz+"),
+                period: 5,
+            },
+            CipherKind::Vigenere { key: key.clone() },
+            CipherKind::Wolseley { keyword: key },
+        ];
+
+        for kind in kinds {
+            let c = CipherKind::new(kind.clone());
+            let encrypted = c.encrypt("attackatdawn").unwrap();
+            let decrypted = c.decrypt(&encrypted).unwrap().to_lowercase();
+
+            // Some ciphers pad the message, so only its start is compared
+            assert!(decrypted.starts_with("attackatdawn"), "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn validates_with_cipher() {
+        let kind = CipherKind::new(CipherKind::Hill {
+            phrase: String::from("CEFJCBDRH"),
+            chunk_size: 3,
+        });
+
+        assert_eq!(
+            Hill::from_phrase("CEFJCBDRH", 3).validate_message("Attack at dawn"),
+            kind.validate_message("Attack at dawn")
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_key() {
+        CipherKind::new(CipherKind::Caesar { shift: 0 });
+    }
+}
@@ -7,27 +7,40 @@
 //!
 use crate::common::alphabet::Alphabet;
 use crate::common::cipher::Cipher;
-use crate::common::{alphabet, keygen};
+use crate::common::cipher_error::CipherError;
+use crate::common::key_problem::KeyProblem;
+use crate::common::{alphabet, key_problem, keygen};
+use std::fmt;
+use std::str::FromStr;
 
 /// A Columnar Transposition cipher.
 /// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
 pub struct ColumnarTransposition {
     keystream: String,
     null_char: Option<char>,
     derived_key: Vec<(char, Vec<char>)>,
 }
 
+/// The key of a Columnar Transposition cipher.
+///
+/// This struct is given to the `new()` method of `ColumnarTransposition`. See its documentation
+/// for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColumnarTranspositionKey {
+    /// The characters used as the column identifiers.
+    pub keystream: String,
+    /// An optional character that is used to pad messages of uneven length.
+    pub null_char: Option<char>,
+}
+
 impl Cipher for ColumnarTransposition {
-    type Key = (String, Option<char>);
+    type Key = ColumnarTranspositionKey;
     type Algorithm = ColumnarTransposition;
 
     /// Initialize a Columnar Transposition cipher.
     ///
-    /// Where...
-    ///
-    /// * Elements of `keystream` are used as the column identifiers.
-    /// * The optional `null_char` is used to pad messages of uneven length.
-    /// * The `derived_key` is used to initialise the column structures in the cipher.
+    /// The parts of the `key` are described by the fields of `ColumnarTranspositionKey`.
     ///
     /// # Panics
     /// * The `keystream` length is 0.
@@ -35,17 +48,17 @@ impl Cipher for ColumnarTransposition {
     /// * The `keystream` contains duplicate characters.
     /// * The `null_char` is a character within the `keystream`
     ///
-    fn new(key: (String, Option<char>)) -> ColumnarTransposition {
-        if let Some(null_char) = key.1 {
-            if key.0.contains(null_char) {
+    fn new(key: ColumnarTranspositionKey) -> ColumnarTransposition {
+        if let Some(null_char) = key.null_char {
+            if key.keystream.contains(null_char) {
                 panic!("The `keystream` contains a `null_char`.");
             }
         }
 
         ColumnarTransposition {
-            derived_key: keygen::columnar_key(&key.0),
-            keystream: key.0,
-            null_char: key.1,
+            derived_key: keygen::columnar_key(&key.keystream),
+            keystream: key.keystream,
+            null_char: key.null_char,
         }
     }
 
@@ -61,22 +74,21 @@ impl Cipher for ColumnarTransposition {
     /// Basic usage:
     ///
     /// ```
-    /// use cipher_crypt::{Cipher, ColumnarTransposition};
+    /// use cipher_crypt::{Cipher, ColumnarTransposition, ColumnarTranspositionKey};
     ///
     /// let key_word = String::from("zebras");
     /// let null_char = None;
     ///
-    /// let ct = ColumnarTransposition::new((key_word, null_char));;
+    /// let ct = ColumnarTransposition::new(ColumnarTranspositionKey {
+    ///     keystream: key_word,
+    ///     null_char,
+    /// });
     ///
     /// assert_eq!("respce!uemeers-taSs g", ct.encrypt("Super-secret message!").unwrap());
     /// ```
     ///
     fn encrypt(&self, message: &str) -> Result<String, &'static str> {
-        if let Some(null_char) = self.null_char {
-            if message.contains(null_char) {
-                return Err("Message contains null characters.");
-            }
-        }
+        self.validate_message(message)?;
 
         let mut key = self.derived_key.clone();
 
@@ -120,25 +132,31 @@ impl Cipher for ColumnarTransposition {
     /// Basic usage:
     ///
     /// ```
-    /// use cipher_crypt::{Cipher, ColumnarTransposition};
+    /// use cipher_crypt::{Cipher, ColumnarTransposition, ColumnarTranspositionKey};
     ///
     /// let key_word = String::from("zebras");
     /// let null_char = None;
     ///
-    /// let ct = ColumnarTransposition::new((key_word, null_char));;
+    /// let ct = ColumnarTransposition::new(ColumnarTranspositionKey {
+    ///     keystream: key_word,
+    ///     null_char,
+    /// });
     /// assert_eq!("Super-secret message!", ct.decrypt("respce!uemeers-taSs g").unwrap());
     /// ```
     /// Using whitespace as null (special case):
     ///  This will strip only trailing whitespace in message during decryption
     ///
     /// ```
-    /// use cipher_crypt::{Cipher, ColumnarTransposition};
+    /// use cipher_crypt::{Cipher, ColumnarTransposition, ColumnarTranspositionKey};
     ///
     /// let key_word = String::from("zebras");
     /// let null_char = None;
     /// let message = "we are discovered  "; // Only trailing spaces will be stripped
     ///
-    /// let ct = ColumnarTransposition::new((key_word, null_char));;
+    /// let ct = ColumnarTransposition::new(ColumnarTranspositionKey {
+    ///     keystream: key_word,
+    ///     null_char,
+    /// });
     ///
     /// assert_eq!(ct.decrypt(&ct.encrypt(message).unwrap()).unwrap(),"we are discovered");
     /// ```
@@ -214,6 +232,132 @@ impl Cipher for ColumnarTransposition {
 
         Ok(plaintext.trim_end().to_string())
     }
+
+    /// Check that a message can be encrypted with a Columnar Transposition cipher.
+    ///
+    /// Returns `Err` if padding characters are being used (`null_char`) and the message contains
+    /// them.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, ColumnarTransposition, ColumnarTranspositionKey};
+    ///
+    /// let ct = ColumnarTransposition::new(ColumnarTranspositionKey {
+    ///     keystream: String::from("zebras"),
+    ///     null_char: Some(' '),
+    /// });
+    ///
+    /// assert!(ct.validate_message("Super-secret-message!").is_ok());
+    /// assert!(ct.validate_message("Super-secret message!").is_err());
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        CipherError::find(message, "Message contains null characters.", |c| {
+            Some(c) == self.null_char
+        })
+    }
+}
+
+impl ColumnarTransposition {
+    /// The key of the cipher.
+    ///
+    pub fn key(&self) -> ColumnarTranspositionKey {
+        ColumnarTranspositionKey {
+            keystream: self.keystream.clone(),
+            null_char: self.null_char,
+        }
+    }
+
+    /// List the problems with a `keystream` and `null_char` that would stop them from being given
+    /// to `new()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{ColumnarTransposition, KeyProblem};
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         KeyProblem::DuplicateSymbol { position: 4, symbol: 'e' },
+    ///         KeyProblem::PaddingInKey { position: 2 },
+    ///     ],
+    ///     ColumnarTransposition::key_problems("zebre", Some('b'))
+    /// );
+    /// ```
+    ///
+    pub fn key_problems(keystream: &str, null_char: Option<char>) -> Vec<KeyProblem> {
+        if keystream.is_empty() {
+            return vec![KeyProblem::Empty];
+        }
+
+        let mut problems = key_problem::invalid_symbols(keystream, &alphabet::ALPHANUMERIC);
+        problems.extend(key_problem::duplicate_symbols(keystream));
+        if let Some(null_char) = null_char {
+            problems.extend(
+                keystream
+                    .chars()
+                    .enumerate()
+                    .filter(|&(_, c)| c == null_char)
+                    .map(|(position, _)| KeyProblem::PaddingInKey { position }),
+            );
+        }
+
+        problems
+    }
+}
+
+impl FromStr for ColumnarTransposition {
+    type Err = &'static str;
+
+    /// Parse a Columnar Transposition cipher from its keystream, optionally followed by a comma
+    /// and the `null_char` (such as `"zebras"` or `"zebras,x"`).
+    ///
+    /// Returns `Err` if the key could not be given to `new()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::ColumnarTransposition;
+    ///
+    /// let c: ColumnarTransposition = "zebras,x".parse().unwrap();
+    /// assert_eq!("zebras,x", c.to_string());
+    /// ```
+    ///
+    fn from_str(s: &str) -> Result<ColumnarTransposition, &'static str> {
+        let (keystream, null_char) = match s.split_once(',') {
+            Some((keystream, null_char)) => {
+                let mut chars = null_char.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => (keystream, Some(c)),
+                    _ => return Err("The `null_char` must be a single character."),
+                }
+            }
+            None => (s, None),
+        };
+
+        keygen::check_columnar_key(keystream)?;
+        if null_char.is_some_and(|c| keystream.contains(c)) {
+            return Err("The `keystream` contains a `null_char`.");
+        }
+
+        Ok(ColumnarTransposition::new(ColumnarTranspositionKey {
+            keystream: keystream.to_string(),
+            null_char,
+        }))
+    }
+}
+
+impl fmt::Display for ColumnarTransposition {
+    /// Writes the keystream and any `null_char` of the cipher, as parsed by `from_str()`.
+    ///
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.null_char {
+            Some(c) => write!(f, "{},{}", self.keystream, c),
+            None => write!(f, "{}", self.keystream),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -226,7 +370,10 @@ mod tests {
 
         let key_word = String::from("zebras");
         let null_char = Some('\u{0}');
-        let ct = ColumnarTransposition::new((key_word, null_char));
+        let ct = ColumnarTransposition::new(ColumnarTranspositionKey {
+            keystream: key_word,
+            null_char,
+        });
 
         assert_eq!(ct.decrypt(&ct.encrypt(message).unwrap()).unwrap(), message);
     }
@@ -237,7 +384,10 @@ mod tests {
 
         let key_word = String::from("zebras");
         let null_char = None;
-        let ct = ColumnarTransposition::new((key_word, null_char));
+        let ct = ColumnarTransposition::new(ColumnarTranspositionKey {
+            keystream: key_word,
+            null_char,
+        });
 
         assert_eq!(ct.decrypt(&ct.encrypt(message).unwrap()).unwrap(), message);
     }
@@ -248,7 +398,10 @@ mod tests {
 
         let key_word = String::from("zebras");
         let null_char = Some('\u{0}');
-        let ct = ColumnarTransposition::new((key_word, null_char));
+        let ct = ColumnarTransposition::new(ColumnarTranspositionKey {
+            keystream: key_word,
+            null_char,
+        });
         let encrypted = ct.encrypt(message).unwrap();
         assert_eq!(ct.decrypt(&encrypted).unwrap(), message);
     }
@@ -259,7 +412,10 @@ mod tests {
 
         let key_word = String::from("zebras");
         let null_char = None;
-        let ct = ColumnarTransposition::new((key_word, null_char));
+        let ct = ColumnarTransposition::new(ColumnarTranspositionKey {
+            keystream: key_word,
+            null_char,
+        });
         let encrypted = ct.encrypt(message).unwrap();
         assert_eq!(ct.decrypt(&encrypted).unwrap(), message);
     }
@@ -270,7 +426,10 @@ mod tests {
 
         let key_word = String::from("z");
         let null_char = Some('\u{0}');
-        let ct = ColumnarTransposition::new((key_word, null_char));
+        let ct = ColumnarTransposition::new(ColumnarTranspositionKey {
+            keystream: key_word,
+            null_char,
+        });
         assert_eq!(ct.decrypt(&ct.encrypt(message).unwrap()).unwrap(), message);
     }
 
@@ -280,7 +439,10 @@ mod tests {
 
         let key_word = String::from("z");
         let null_char = None;
-        let ct = ColumnarTransposition::new((key_word, null_char));
+        let ct = ColumnarTransposition::new(ColumnarTranspositionKey {
+            keystream: key_word,
+            null_char,
+        });
         assert_eq!(ct.decrypt(&ct.encrypt(message).unwrap()).unwrap(), message);
     }
 
@@ -290,7 +452,10 @@ mod tests {
 
         let key_word = String::from("z");
         let null_char = None;
-        let ct = ColumnarTransposition::new((key_word, null_char));
+        let ct = ColumnarTransposition::new(ColumnarTranspositionKey {
+            keystream: key_word,
+            null_char,
+        });
 
         assert_eq!(
             ct.decrypt(&ct.encrypt(message).unwrap()).unwrap(),
@@ -302,19 +467,38 @@ mod tests {
     fn plaintext_containing_padding() {
         let key_word = String::from("zebras");
         let null_char = Some(' ');
-        let ct = ColumnarTransposition::new((key_word, null_char));
+        let ct = ColumnarTransposition::new(ColumnarTranspositionKey {
+            keystream: key_word,
+            null_char,
+        });
 
         let plain_text = "This will fail because of spaces.";
         assert!(ct.encrypt(plain_text).is_err());
     }
 
+    #[test]
+    fn validate_plaintext_containing_padding() {
+        let ct = ColumnarTransposition::new(ColumnarTranspositionKey {
+            keystream: String::from("zebras"),
+            null_char: Some(' '),
+        });
+
+        assert!(ct
+            .validate_message("This will fail because of spaces.")
+            .is_err());
+        assert!(ct.validate_message("ThisIsFine.").is_ok());
+    }
+
     #[test]
     fn trailing_spaces_no_padding() {
         let message = "we are discovered  "; //The trailing spaces will be stripped
 
         let key_word = String::from("z");
         let null_char = None;
-        let ct = ColumnarTransposition::new((key_word, null_char));
+        let ct = ColumnarTransposition::new(ColumnarTranspositionKey {
+            keystream: key_word,
+            null_char,
+        });
 
         assert_eq!(
             ct.decrypt(&ct.encrypt(message).unwrap()).unwrap(),
@@ -322,9 +506,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_key() {
+        assert_eq!(
+            "zebras",
+            "zebras"
+                .parse::<ColumnarTransposition>()
+                .unwrap()
+                .to_string()
+        );
+        assert!("zebras,".parse::<ColumnarTransposition>().is_err());
+        assert!("zebras,z".parse::<ColumnarTransposition>().is_err());
+        assert!("zebra zebra".parse::<ColumnarTransposition>().is_err());
+    }
+
     #[test]
     #[should_panic]
     fn padding_in_key() {
-        ColumnarTransposition::new((String::from("zebras"), Some('z')));
+        ColumnarTransposition::new(ColumnarTranspositionKey {
+            keystream: String::from("zebras"),
+            null_char: Some('z'),
+        });
     }
 }
@@ -1,5 +1,11 @@
-//! Contains helpful constants and functions used in substitution ciphers.
+//! Contains the alphabets used in substitution ciphers.
 //!
+//! The `Alphabet` trait maps the symbols of an alphabet to their positions and back. Besides the
+//! built-in alphabets, a `Custom` alphabet of any symbols can be given to the ciphers that accept
+//! one (such as `Caesar::with_alphabet()`).
+//!
+use std::borrow::Cow;
+
 const ALPHABET_LOWER: [char; 26] = [
     'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
     't', 'u', 'v', 'w', 'x', 'y', 'z',
@@ -10,12 +16,18 @@ const ALPHABET_UPPER: [char; 26] = [
     'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
 ];
 
-const NUMERIC: [char; 10] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+const DIGITS: [char; 10] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
 
+/// The letters `a-z`.
 pub const STANDARD: Standard = Standard;
+/// The letters `a-z`, followed by the digits `0-9`.
 pub const ALPHANUMERIC: Alphanumeric = Alphanumeric;
+/// The letters `a-z` without `j`, as used by the Playfair cipher.
 pub const PLAYFAIR: Playfair = Playfair;
+/// The digits `0-9`.
+pub const NUMERIC: Numeric = Numeric;
 
+/// An ordered set of symbols, in which each symbol may have an uppercase and lowercase form.
 pub trait Alphabet {
     /// Attempts to find the position of the character in the alphabet.
     ///
@@ -67,6 +79,8 @@ pub trait Alphabet {
     fn length(&self) -> usize;
 }
 
+/// The letters `a-z`. See `STANDARD`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Standard;
 impl Alphabet for Standard {
     fn find_position(&self, c: char) -> Option<usize> {
@@ -93,6 +107,8 @@ impl Alphabet for Standard {
     }
 }
 
+/// The letters `a-z`, followed by the digits `0-9`. See `ALPHANUMERIC`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Alphanumeric;
 impl Alphabet for Alphanumeric {
     fn find_position(&self, c: char) -> Option<usize> {
@@ -100,7 +116,7 @@ impl Alphabet for Alphanumeric {
             return Some(pos);
         }
 
-        if let Some(pos) = NUMERIC.iter().position(|&n| n == c) {
+        if let Some(pos) = DIGITS.iter().position(|&n| n == c) {
             return Some(pos + 26);
         }
 
@@ -113,7 +129,7 @@ impl Alphabet for Alphanumeric {
         }
 
         if index > 25 {
-            NUMERIC[index - 26]
+            DIGITS[index - 26]
         } else if is_uppercase {
             ALPHABET_UPPER[index]
         } else {
@@ -126,6 +142,8 @@ impl Alphabet for Alphanumeric {
     }
 }
 
+/// The letters `a-z` without `j`. See `PLAYFAIR`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Playfair;
 impl Alphabet for Playfair {
     fn find_position(&self, c: char) -> Option<usize> {
@@ -166,10 +184,145 @@ impl Alphabet for Playfair {
     }
 }
 
+/// The digits `0-9`. See `NUMERIC`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Numeric;
+impl Alphabet for Numeric {
+    fn find_position(&self, c: char) -> Option<usize> {
+        DIGITS.iter().position(|&n| n == c)
+    }
+
+    fn get_letter(&self, index: usize, _is_uppercase: bool) -> char {
+        if index > self.length() {
+            panic!("Invalid index to the alphabet: {}.", index);
+        }
+
+        DIGITS[index]
+    }
+
+    fn length(&self) -> usize {
+        10
+    }
+}
+
+/// An alphabet of any symbols, such as one supplied by the user.
+///
+/// This struct is created by the `new()` or `from_alphabet()` methods. See their documentation
+/// for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Custom {
+    lower: Cow<'static, [char]>,
+    upper: Cow<'static, [char]>,
+}
+
+impl Custom {
+    /// A copy of the `STANDARD` alphabet, which (unlike `from_alphabet()`) can be made in a
+    /// `const` context.
+    ///
+    pub(crate) const fn standard() -> Custom {
+        Custom {
+            lower: Cow::Borrowed(&ALPHABET_LOWER),
+            upper: Cow::Borrowed(&ALPHABET_UPPER),
+        }
+    }
+
+    /// Initialise an alphabet of the `symbols`, in the order given.
+    ///
+    /// Each letter is found in either case, and is given in the case of the character it
+    /// replaces.
+    ///
+    /// # Panics
+    /// * There are no `symbols`.
+    /// * A symbol is repeated, ignoring case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::alphabet::{Alphabet, Custom};
+    ///
+    /// let hex = Custom::new("0123456789abcdef");
+    /// assert_eq!(Some(10), hex.find_position('A'));
+    /// assert_eq!('F', hex.get_letter(15, true));
+    /// ```
+    ///
+    pub fn new(symbols: &str) -> Custom {
+        let lower: Vec<char> = symbols.chars().map(|c| other_case(c, false)).collect();
+        let upper: Vec<char> = symbols.chars().map(|c| other_case(c, true)).collect();
+
+        if lower.is_empty() {
+            panic!("The alphabet must contain at least one symbol.");
+        }
+        if (1..lower.len()).any(|i| lower[..i].contains(&lower[i])) {
+            panic!("The alphabet cannot contain a repeated symbol.");
+        }
+
+        Custom {
+            lower: lower.into(),
+            upper: upper.into(),
+        }
+    }
+
+    /// Initialise a copy of another `alphabet`, such as `STANDARD` or a user's own implementation
+    /// of `Alphabet`.
+    ///
+    pub fn from_alphabet<A: Alphabet>(alphabet: &A) -> Custom {
+        Custom {
+            lower: (0..alphabet.length())
+                .map(|i| alphabet.get_letter(i, false))
+                .collect::<Vec<char>>()
+                .into(),
+            upper: (0..alphabet.length())
+                .map(|i| alphabet.get_letter(i, true))
+                .collect::<Vec<char>>()
+                .into(),
+        }
+    }
+}
+
+/// The letter `c` in uppercase or lowercase, or `c` itself if that case is not a single character.
+///
+fn other_case(c: char, is_uppercase: bool) -> char {
+    let mut case: Vec<char> = if is_uppercase {
+        c.to_uppercase().collect()
+    } else {
+        c.to_lowercase().collect()
+    };
+
+    match case.len() {
+        1 => case.remove(0),
+        _ => c,
+    }
+}
+
+impl Alphabet for Custom {
+    fn find_position(&self, c: char) -> Option<usize> {
+        self.lower
+            .iter()
+            .position(|&a| a == c)
+            .or_else(|| self.upper.iter().position(|&a| a == c))
+    }
+
+    fn get_letter(&self, index: usize, is_uppercase: bool) -> char {
+        if index >= self.length() {
+            panic!("Invalid index to the alphabet: {}.", index);
+        }
+
+        if is_uppercase {
+            self.upper[index]
+        } else {
+            self.lower[index]
+        }
+    }
+
+    fn length(&self) -> usize {
+        self.lower.len()
+    }
+}
+
 /// Determines if the char is a number.
 ///
 pub fn is_numeric(c: char) -> bool {
-    NUMERIC.iter().any(|&n| n == c)
+    DIGITS.iter().any(|&n| n == c)
 }
 
 #[cfg(test)]
@@ -197,7 +350,7 @@ mod tests {
         let valid_iter = ALPHABET_LOWER
             .iter()
             .chain(ALPHABET_UPPER.iter())
-            .chain(NUMERIC.iter());
+            .chain(DIGITS.iter());
         for c in valid_iter {
             assert!(ALPHANUMERIC.is_valid(&c.to_string()))
         }
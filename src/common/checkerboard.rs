@@ -0,0 +1,171 @@
+//! Contains a straddling checkerboard, used to convert text into a stream of digits.
+//!
+//! A straddling checkerboard has three rows of ten cells. The top row contains eight frequent
+//! letters and two blank cells, each of which is encoded as a single digit (the column header).
+//! The digits above the blank cells are used as prefixes for the remaining two rows, so that
+//! their symbols are encoded as two digits.
+//!
+use std::collections::HashMap;
+
+// The symbol that shifts the following digit into numeric mode
+pub const NUMBER_SHIFT: char = '/';
+
+/// A straddling checkerboard.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct StraddlingCheckerboard {
+    encoding: HashMap<char, Vec<u8>>,
+    decoding: HashMap<Vec<u8>, char>,
+    prefixes: [u8; 2],
+}
+
+impl StraddlingCheckerboard {
+    /// Construct a straddling checkerboard.
+    ///
+    /// The `header` is the ten distinct digits that label the columns. The `top` row contains
+    /// eight symbols and two blanks (spaces), while `rows` contains the twenty symbols for the
+    /// lower two rows, filled from left to right.
+    ///
+    /// # Panics
+    /// * The `header` is not a permutation of the digits `0-9`.
+    /// * The `top` row does not contain exactly ten cells, two of which are blank.
+    /// * The lower `rows` do not contain exactly twenty symbols.
+    ///
+    pub fn new(header: &[u8], top: &str, rows: &str) -> StraddlingCheckerboard {
+        let mut sorted = header.to_vec();
+        sorted.sort_unstable();
+        if sorted != (0..10).collect::<Vec<u8>>() {
+            panic!("The header must contain each of the digits 0-9.");
+        }
+
+        let top: Vec<char> = top.chars().collect();
+        let blanks: Vec<usize> = (0..top.len()).filter(|&i| top[i] == ' ').collect();
+        if top.len() != 10 || blanks.len() != 2 {
+            panic!("The top row must contain ten cells, two of which are blank.");
+        }
+        let rows: Vec<char> = rows.chars().collect();
+        if rows.len() != 20 {
+            panic!("The lower rows must contain twenty symbols.");
+        }
+
+        let prefixes = [header[blanks[0]], header[blanks[1]]];
+        let mut encoding = HashMap::new();
+        for (i, &c) in top.iter().enumerate().filter(|&(_, &c)| c != ' ') {
+            encoding.insert(c, vec![header[i]]);
+        }
+        for (i, &c) in rows.iter().enumerate() {
+            encoding.insert(c, vec![prefixes[i / 10], header[i % 10]]);
+        }
+        let decoding = encoding.iter().map(|(&c, d)| (d.clone(), c)).collect();
+
+        StraddlingCheckerboard {
+            encoding,
+            decoding,
+            prefixes,
+        }
+    }
+
+    /// Encode text as a sequence of digits. Each digit in the text is written as the
+    /// `NUMBER_SHIFT` code followed by the digit itself.
+    ///
+    /// Will return `Err` if the text contains a symbol that is not within the checkerboard.
+    ///
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>, &'static str> {
+        let mut digits = Vec::new();
+        for c in text.chars() {
+            if let Some(d) = c.to_digit(10) {
+                digits.extend(self.code(NUMBER_SHIFT)?);
+                digits.push(d as u8);
+            } else {
+                digits.extend(self.code(c.to_ascii_uppercase())?);
+            }
+        }
+
+        Ok(digits)
+    }
+
+    /// Decode a sequence of digits back into text.
+    ///
+    /// Will return `Err` if the sequence ends part way through a code.
+    ///
+    pub fn decode(&self, digits: &[u8]) -> Result<String, &'static str> {
+        let mut text = String::new();
+        let mut iter = digits.iter();
+        while let Some(&d) = iter.next() {
+            let mut code = vec![d];
+            if self.prefixes.contains(&d) {
+                code.push(*iter.next().ok_or("Incomplete checkerboard code.")?);
+            }
+
+            match self.decoding.get(&code) {
+                Some(&NUMBER_SHIFT) => {
+                    let n = iter.next().ok_or("Incomplete checkerboard code.")?;
+                    text.push((b'0' + n) as char);
+                }
+                Some(&c) => text.push(c),
+                None => return Err("Invalid checkerboard code."),
+            }
+        }
+
+        Ok(text)
+    }
+
+    fn code(&self, c: char) -> Result<Vec<u8>, &'static str> {
+        self.encoding
+            .get(&c)
+            .cloned()
+            .ok_or("Symbol is not within the checkerboard.")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board() -> StraddlingCheckerboard {
+        StraddlingCheckerboard::new(
+            &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+            "ET AON RIS",
+            "BCDFGHJKLMPQUVWXYZ./",
+        )
+    }
+
+    #[test]
+    fn encode_text() {
+        // 'A' is in the top row, 'B' and '.' in the rows prefixed by 2 and 6 respectively
+        assert_eq!(vec![3, 2, 0, 6, 8], board().encode("ab.").unwrap());
+    }
+
+    #[test]
+    fn encode_digits() {
+        assert_eq!(vec![1, 6, 9, 7], board().encode("T7").unwrap());
+    }
+
+    #[test]
+    fn decode_digits() {
+        assert_eq!(
+            "TAB.7",
+            board().decode(&[1, 3, 2, 0, 6, 8, 6, 9, 7]).unwrap()
+        );
+    }
+
+    #[test]
+    fn decode_incomplete() {
+        assert!(board().decode(&[3, 2]).is_err());
+    }
+
+    #[test]
+    fn encode_invalid_symbol() {
+        assert!(board().encode("a!").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_top_row() {
+        StraddlingCheckerboard::new(
+            &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+            "ETAONRISHD",
+            "BCDFGHJKLMPQUVWXYZ./",
+        );
+    }
+}
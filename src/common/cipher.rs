@@ -1,3 +1,13 @@
+use super::cipher_error::CipherError;
+use super::grouping::Grouping;
+use super::options::EncryptOptions;
+use super::policy::ForeignCharPolicy;
+use crate::registry;
+use crate::vectors::{self, Vector};
+use std::any;
+use std::borrow::Cow;
+use std::fmt;
+
 pub trait Cipher {
     type Key;
     type Algorithm;
@@ -13,4 +23,529 @@ pub trait Cipher {
     /// Decrypt a `message` using a cipher's algorithm.
     ///
     fn decrypt(&self, message: &str) -> Result<String, &'static str>;
+
+    /// Check whether a `message` can be encrypted by this cipher, without encrypting it.
+    ///
+    /// Ciphers that accept any input (such as those that pass unknown symbols through as-is) will
+    /// always return `Ok`. Otherwise the `Err` gives the reason that `encrypt` would produce for
+    /// the message and, where a symbol of the message is to blame, the first such symbol and its
+    /// byte offset.
+    ///
+    fn validate_message(&self, _message: &str) -> Result<(), CipherError> {
+        Ok(())
+    }
+
+    /// Whether a character `c` is foreign to the cipher, so is subject to a `ForeignCharPolicy`.
+    ///
+    /// By default, the characters outside of `a-z` and `A-Z` are foreign. Ciphers over other
+    /// alphabets override this.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        !c.is_ascii_alphabetic()
+    }
+
+    /// Encrypt a `message`, first applying a `policy` to the characters that are foreign to the
+    /// cipher.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, ForeignCharPolicy, Hill};
+    ///
+    /// let h = Hill::from_phrase("CEFJCBDRH", 3);
+    /// assert!(h.encrypt("Attack at dawn!").is_err());
+    ///
+    /// let c = h.encrypt_with_policy("Attack at dawn!", ForeignCharPolicy::Strip).unwrap();
+    /// assert_eq!("Pfogoanpgxfx", c);
+    /// ```
+    ///
+    fn encrypt_with_policy(
+        &self,
+        message: &str,
+        policy: ForeignCharPolicy,
+    ) -> Result<String, &'static str> {
+        self.encrypt(&policy.apply(message, |c| self.is_foreign(c))?)
+    }
+
+    /// Encrypt a `message`, laying out the ciphertext in groups.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Grouping, Vigenere};
+    ///
+    /// let v = Vigenere::new(String::from("lemon"));
+    /// let c = v.encrypt_grouped("Attack at dawn", &Grouping::new()).unwrap();
+    ///
+    /// assert_eq!("Lxfop vefrn hr", c);
+    /// assert_eq!("Attackatdawn", v.decrypt_grouped(&c).unwrap());
+    /// ```
+    ///
+    fn encrypt_grouped(&self, message: &str, grouping: &Grouping) -> Result<String, &'static str> {
+        Ok(grouping.apply(&self.encrypt(message)?))
+    }
+
+    /// Decrypt a `ciphertext` that was laid out in groups, ignoring the whitespace between them.
+    ///
+    fn decrypt_grouped(&self, ciphertext: &str) -> Result<String, &'static str> {
+        self.decrypt(&Grouping::remove(ciphertext))
+    }
+
+    /// Encrypt a `message`, borrowing it as the ciphertext where the cipher would leave it
+    /// unchanged.
+    ///
+    /// By default the ciphertext of `encrypt()` is always returned as `Cow::Owned`. Ciphers whose
+    /// key can leave a message unchanged (such as a Railfence cipher of one rail) override this,
+    /// so that a solver trying many keys does not copy the message for each of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Railfence};
+    /// use std::borrow::Cow;
+    ///
+    /// let r = Railfence::new(1);
+    /// assert_eq!(Cow::Borrowed("Attack at dawn!"), r.encrypt_cow("Attack at dawn!").unwrap());
+    /// ```
+    ///
+    fn encrypt_cow<'a>(&self, message: &'a str) -> Result<Cow<'a, str>, &'static str> {
+        self.encrypt(message).map(Cow::Owned)
+    }
+
+    /// Decrypt a `ciphertext`, borrowing it as the message where the cipher would leave it
+    /// unchanged. See `encrypt_cow()` for more.
+    ///
+    fn decrypt_cow<'a>(&self, ciphertext: &'a str) -> Result<Cow<'a, str>, &'static str> {
+        self.decrypt(ciphertext).map(Cow::Owned)
+    }
+
+    /// Encrypt a `message`, appending the ciphertext to `out` rather than returning a new
+    /// `String`.
+    ///
+    /// A caller that builds a large output from many messages can reuse the one buffer. The
+    /// ciphers that encrypt one character at a time write straight into it; the others append the
+    /// ciphertext of `encrypt()`. Nothing is appended if the message could not be encrypted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Caesar, Cipher};
+    ///
+    /// let c = Caesar::new(3);
+    /// let mut out = String::from("Ciphertext: ");
+    /// c.encrypt_into("Attack at dawn!", &mut out).unwrap();
+    ///
+    /// assert_eq!("Ciphertext: Dwwdfn dw gdzq!", out);
+    /// ```
+    ///
+    fn encrypt_into(&self, message: &str, out: &mut String) -> Result<(), &'static str> {
+        out.push_str(&self.encrypt(message)?);
+        Ok(())
+    }
+
+    /// Encrypt a `message`, writing the ciphertext to any `fmt::Write` (such as a `Formatter`).
+    ///
+    /// Returns `Err` if the message could not be encrypted, or if the writer failed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Vigenere};
+    /// use std::fmt::Write;
+    ///
+    /// let v = Vigenere::new(String::from("lemon"));
+    /// let mut out = String::new();
+    /// for line in ["Attack", "at dawn"].iter() {
+    ///     v.encrypt_fmt(line, &mut out).unwrap();
+    ///     writeln!(out).unwrap();
+    /// }
+    ///
+    /// assert_eq!("Lxfopv\nlx pojy\n", out);
+    /// ```
+    ///
+    fn encrypt_fmt<W: fmt::Write>(&self, message: &str, out: &mut W) -> Result<(), &'static str> {
+        let mut ciphertext = String::new();
+        self.encrypt_into(message, &mut ciphertext)?;
+
+        out.write_str(&ciphertext)
+            .map_err(|_| "The ciphertext could not be written.")
+    }
+
+    /// Encrypt a `message` with a set of `options`, such as the policy for its foreign characters
+    /// and the layout of its ciphertext.
+    ///
+    /// See the documentation of `EncryptOptions` for the order in which the options are applied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{
+    ///     CasePolicy, Cipher, EncryptOptions, ForeignCharPolicy, Grouping, Vigenere,
+    /// };
+    ///
+    /// let v = Vigenere::new(String::from("lemon"));
+    /// let opts = EncryptOptions::new()
+    ///     .case(CasePolicy::Upper)
+    ///     .foreign_chars(ForeignCharPolicy::Strip)
+    ///     .grouping(Grouping::new())
+    ///     .padding('x');
+    ///
+    /// assert_eq!("LXFOP VEFRN HRJLK", v.encrypt_with("Attack at dawn!", &opts).unwrap());
+    /// ```
+    ///
+    fn encrypt_with(
+        &self,
+        message: &str,
+        options: &EncryptOptions,
+    ) -> Result<String, &'static str> {
+        let message = options.prepare(message, |c| self.is_foreign(c))?;
+
+        Ok(options.finish(&self.encrypt(&message)?))
+    }
+
+    /// Encrypt each of a batch of `messages` with the same cipher, returning the ciphertexts in
+    /// the same order.
+    ///
+    /// The state that a cipher derives from its key (such as a keyed alphabet, a square or an
+    /// inverse matrix) is derived once when the cipher is made, so is shared by every message of
+    /// the batch. Returns the first `Err` of any message that could not be encrypted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Hill};
+    ///
+    /// let h = Hill::from_phrase("CEFJCBDRH", 3);
+    /// let batch = h.encrypt_batch(&["ATTACKEAST", "ATDAWN"]).unwrap();
+    ///
+    /// assert_eq!(vec!["PFOGOAUCIMpf", "NPGXFX"], batch);
+    /// assert!(h.encrypt_batch(&["ATTACK", "AT DAWN"]).is_err());
+    /// ```
+    ///
+    fn encrypt_batch(&self, messages: &[&str]) -> Result<Vec<String>, &'static str> {
+        messages.iter().map(|m| self.encrypt(m)).collect()
+    }
+
+    /// Check the cipher against its known-answer vectors (see the `vectors` module), returning
+    /// the number that passed.
+    ///
+    /// A cipher without vectors (such as one that is not in the `registry`) passes with `Ok(0)`,
+    /// as it has nothing to get wrong. Returns `Err` with the first vector that failed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Bifid, Cipher, Vigenere};
+    ///
+    /// assert_eq!(Ok(1), Vigenere::self_test());
+    /// assert_eq!(Ok(0), Bifid::self_test());
+    /// ```
+    ///
+    fn self_test() -> Result<usize, &'static Vector>
+    where
+        Self: Sized + 'static,
+    {
+        let vectors = registry::lookup_cipher::<Self>()
+            .into_iter()
+            .flat_map(|factory| vectors::of(factory.name()));
+
+        vectors::check_all(vectors)
+    }
+
+    /// Wrap the cipher so that it is formatted for debugging by its name alone, keeping its key
+    /// out of logs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Vigenere};
+    ///
+    /// let v = Vigenere::new(String::from("lemon"));
+    /// assert!(format!("{:?}", v).contains("lemon"));
+    /// assert_eq!("Vigenere { .. }", format!("{:?}", v.redacted()));
+    /// ```
+    ///
+    fn redacted(&self) -> Redacted<'_, Self> {
+        Redacted(self)
+    }
+}
+
+/// A `Cipher` whose `Key` and `Algorithm` types have been erased, so that it can be used as a trait
+/// object.
+///
+/// The `new()` method and associated types of `Cipher` stop it from being made into a trait
+/// object. This trait is implemented for every `Cipher`, so any cipher can be boxed as a
+/// `Box<dyn DynCipher>` and held alongside ciphers of other types. The box implements `Cipher`
+/// itself, so its `encrypt()` and `decrypt()` methods are called as for any other cipher.
+///
+/// The methods of this trait are named apart from those of `Cipher`, so that a cipher never has
+/// two methods of the same name in scope.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::{Caesar, Cipher, DynCipher, Railfence, Vigenere};
+///
+/// let ciphers: Vec<Box<dyn DynCipher>> = vec![
+///     Box::new(Caesar::new(3)),
+///     Box::new(Railfence::new(3)),
+///     Box::new(Vigenere::new(String::from("lemon"))),
+/// ];
+///
+/// for c in ciphers {
+///     assert_eq!("Attack at dawn!", c.decrypt(&c.encrypt("Attack at dawn!").unwrap()).unwrap());
+/// }
+/// ```
+pub trait DynCipher {
+    /// Encrypt a `message`, as by `Cipher::encrypt()`.
+    ///
+    fn dyn_encrypt(&self, message: &str) -> Result<String, &'static str>;
+
+    /// Decrypt a `message`, as by `Cipher::decrypt()`.
+    ///
+    fn dyn_decrypt(&self, message: &str) -> Result<String, &'static str>;
+
+    /// Check whether a `message` can be encrypted, as by `Cipher::validate_message()`.
+    ///
+    fn dyn_validate_message(&self, message: &str) -> Result<(), CipherError>;
+
+    /// Whether a character `c` is foreign to the cipher, as by `Cipher::is_foreign()`.
+    ///
+    fn dyn_is_foreign(&self, c: char) -> bool;
+}
+
+impl<C: Cipher> DynCipher for C {
+    fn dyn_encrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.encrypt(message)
+    }
+
+    fn dyn_decrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.decrypt(message)
+    }
+
+    fn dyn_validate_message(&self, message: &str) -> Result<(), CipherError> {
+        self.validate_message(message)
+    }
+
+    fn dyn_is_foreign(&self, c: char) -> bool {
+        self.is_foreign(c)
+    }
+}
+
+impl Cipher for Box<dyn DynCipher> {
+    type Key = Box<dyn DynCipher>;
+    type Algorithm = Box<dyn DynCipher>;
+
+    /// A boxed cipher is its own key, so is returned as-is.
+    ///
+    fn new(cipher: Box<dyn DynCipher>) -> Box<dyn DynCipher> {
+        cipher
+    }
+
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.as_ref().dyn_encrypt(message)
+    }
+
+    fn decrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.as_ref().dyn_decrypt(message)
+    }
+
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        self.as_ref().dyn_validate_message(message)
+    }
+
+    fn is_foreign(&self, c: char) -> bool {
+        self.as_ref().dyn_is_foreign(c)
+    }
+}
+
+/// A cipher that is formatted for debugging without its key.
+///
+/// This struct is created by the `redacted()` method of `Cipher`. See its documentation for more.
+pub struct Redacted<'a, C: ?Sized>(&'a C);
+
+impl<C: ?Sized> fmt::Debug for Redacted<'_, C> {
+    /// Writes the name of the type of the cipher, without the path of its module.
+    ///
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {{ .. }}", type_name::<C>())
+    }
+}
+
+/// The name of a type `T`, without the path of its module or any generic arguments (e.g.
+/// `"Vigenere"`).
+///
+pub(crate) fn type_name<T: ?Sized>() -> &'static str {
+    let path = any::type_name::<T>();
+    let path = &path[..path.find('<').unwrap_or(path.len())];
+
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+/// A cipher that substitutes each character of a message on its own, without regard to the
+/// characters around it (such as the Caesar, Affine and Simple Substitution ciphers).
+///
+/// Such a cipher can encrypt and decrypt a stream of characters lazily, so it can be composed with
+/// other iterators without building an intermediate `String`.
+pub trait CharCipher {
+    /// Encrypt a single character. Characters that the cipher does not substitute are returned
+    /// as-is.
+    ///
+    fn encrypt_char(&self, c: char) -> char;
+
+    /// Decrypt a single character. Characters that the cipher does not substitute are returned
+    /// as-is.
+    ///
+    fn decrypt_char(&self, c: char) -> char;
+
+    /// Lazily encrypt each of the characters of an iterator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Caesar, CharCipher, Cipher};
+    ///
+    /// let c = Caesar::new(3);
+    /// let shouted: String = c
+    ///     .encrypt_chars("Attack at dawn!".chars())
+    ///     .flat_map(char::to_uppercase)
+    ///     .collect();
+    ///
+    /// assert_eq!("DWWDFN DW GDZQ!", shouted);
+    /// ```
+    ///
+    fn encrypt_chars<I>(&self, chars: I) -> CipherChars<'_, Self, I::IntoIter>
+    where
+        Self: Sized,
+        I: IntoIterator<Item = char>,
+    {
+        CipherChars {
+            cipher: self,
+            chars: chars.into_iter(),
+            decrypt: false,
+        }
+    }
+
+    /// Lazily decrypt each of the characters of an iterator.
+    ///
+    fn decrypt_chars<I>(&self, chars: I) -> CipherChars<'_, Self, I::IntoIter>
+    where
+        Self: Sized,
+        I: IntoIterator<Item = char>,
+    {
+        CipherChars {
+            cipher: self,
+            chars: chars.into_iter(),
+            decrypt: true,
+        }
+    }
+}
+
+/// A `CharCipher` that substitutes ASCII characters with other ASCII characters, and so can
+/// encrypt and decrypt bytes directly.
+///
+/// Each ASCII byte of a message is substituted as a character, and every other byte is passed
+/// through unchanged. As the bytes of a multi-byte UTF-8 character are never ASCII, a valid UTF-8
+/// message stays valid UTF-8, but no UTF-8 validation is needed to encrypt it. The methods are
+/// provided, and return `Err` if the cipher substitutes an ASCII byte with a character that is not
+/// ASCII, as it may when it is built over a custom alphabet.
+pub trait ByteCipher: CharCipher {
+    /// Encrypt the bytes of a `message`.
+    ///
+    /// Will return `Err` if an ASCII byte of the `message` is substituted by a character that is
+    /// not ASCII.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{ByteCipher, Caesar, Cipher};
+    ///
+    /// let c = Caesar::new(3);
+    /// assert_eq!(b"Dwwdfn dw gdzq!".to_vec(), c.encrypt_bytes(b"Attack at dawn!").unwrap());
+    /// ```
+    ///
+    fn encrypt_bytes(&self, message: &[u8]) -> Result<Vec<u8>, &'static str> {
+        message
+            .iter()
+            .map(|&b| {
+                if b.is_ascii() {
+                    ascii_byte(self.encrypt_char(b as char))
+                } else {
+                    Ok(b)
+                }
+            })
+            .collect()
+    }
+
+    /// Decrypt the bytes of a `ciphertext`.
+    ///
+    /// Will return `Err` if an ASCII byte of the `ciphertext` is substituted by a character that
+    /// is not ASCII.
+    ///
+    fn decrypt_bytes(&self, ciphertext: &[u8]) -> Result<Vec<u8>, &'static str> {
+        ciphertext
+            .iter()
+            .map(|&b| {
+                if b.is_ascii() {
+                    ascii_byte(self.decrypt_char(b as char))
+                } else {
+                    Ok(b)
+                }
+            })
+            .collect()
+    }
+}
+
+/// The byte of an ASCII character `c`, substituted for an ASCII byte by a `ByteCipher`.
+///
+fn ascii_byte(c: char) -> Result<u8, &'static str> {
+    if !c.is_ascii() {
+        return Err("An ASCII byte cannot be substituted by a character that is not ASCII.");
+    }
+
+    Ok(c as u8)
+}
+
+/// An iterator that encrypts or decrypts the characters of another iterator with a `CharCipher`.
+///
+/// This struct is created by the `encrypt_chars()` and `decrypt_chars()` methods of
+/// `CharCipher`. See their documentation for more.
+#[derive(Clone, Debug)]
+pub struct CipherChars<'a, C, I> {
+    cipher: &'a C,
+    chars: I,
+    decrypt: bool,
+}
+
+impl<'a, C, I> Iterator for CipherChars<'a, C, I>
+where
+    C: CharCipher,
+    I: Iterator<Item = char>,
+{
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if self.decrypt {
+            Some(self.cipher.decrypt_char(c))
+        } else {
+            Some(self.cipher.encrypt_char(c))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chars.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vigenere;
+
+    #[test]
+    fn type_names() {
+        assert_eq!("Vigenere", type_name::<Vigenere>());
+        assert_eq!("Vec", type_name::<Vec<Vigenere>>());
+    }
 }
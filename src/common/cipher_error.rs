@@ -0,0 +1,153 @@
+//! Describes why a cipher cannot encrypt a message.
+//!
+//! The `encrypt()` method of a cipher returns a `&'static str` when it is given a message it
+//! cannot handle, which says what is wrong but not where. The `validate_message()` method of a
+//! cipher checks a message before it is encrypted, and returns a `CipherError` that also gives
+//! the first symbol that cannot be encrypted and its position in the message, where a symbol is
+//! to blame.
+//!
+use std::error::Error;
+use std::fmt;
+
+/// Why a cipher cannot encrypt a message, as returned by `Cipher::validate_message()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CipherError {
+    /// A symbol of the message cannot be encrypted.
+    Symbol {
+        /// The first symbol of the message that cannot be encrypted.
+        symbol: char,
+        /// The byte offset of the symbol in the message.
+        offset: usize,
+        /// The error that `encrypt()` returns for the message.
+        reason: &'static str,
+    },
+    /// The message as a whole cannot be encrypted (for example, when it is too long to be hidden
+    /// in a decoy text), holding the error that `encrypt()` returns for it.
+    Message(&'static str),
+}
+
+impl CipherError {
+    /// A `CipherError` for the first character of a `message` for which `is_invalid` is `true`,
+    /// if there is one, giving the `reason` that it cannot be encrypted.
+    ///
+    pub(crate) fn find<F>(message: &str, reason: &'static str, is_invalid: F) -> Result<(), Self>
+    where
+        F: Fn(char) -> bool,
+    {
+        match message.char_indices().find(|&(_, c)| is_invalid(c)) {
+            Some((offset, symbol)) => Err(CipherError::Symbol {
+                symbol,
+                offset,
+                reason,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// The error that `encrypt()` returns for the message, without the symbol or its position.
+    ///
+    pub fn reason(&self) -> &'static str {
+        match *self {
+            CipherError::Symbol { reason, .. } | CipherError::Message(reason) => reason,
+        }
+    }
+
+    /// The same error, without the symbol or its position.
+    ///
+    /// This is used where a message is checked after it has been transformed (such as by an
+    /// earlier stage of a `Pipeline`), as an offset into the transformed message would not point
+    /// at a symbol of the original.
+    ///
+    pub(crate) fn without_symbol(self) -> Self {
+        CipherError::Message(self.reason())
+    }
+}
+
+impl fmt::Display for CipherError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CipherError::Symbol {
+                symbol,
+                offset,
+                reason,
+            } => write!(f, "{} Found '{}' at byte {}.", reason, symbol, offset),
+            CipherError::Message(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl Error for CipherError {}
+
+impl From<&'static str> for CipherError {
+    /// The error of a message that could not be encrypted as a whole.
+    ///
+    fn from(reason: &'static str) -> CipherError {
+        CipherError::Message(reason)
+    }
+}
+
+impl From<CipherError> for &'static str {
+    /// The error that `encrypt()` returns, without the symbol or its position.
+    ///
+    fn from(error: CipherError) -> &'static str {
+        error.reason()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_first_symbol() {
+        let e = CipherError::find("Héllo, world!", "Bad symbol.", |c| {
+            !c.is_ascii_alphabetic()
+        });
+
+        assert_eq!(
+            Err(CipherError::Symbol {
+                symbol: 'é',
+                offset: 1,
+                reason: "Bad symbol.",
+            }),
+            e
+        );
+        assert_eq!(
+            "Bad symbol. Found 'é' at byte 1.",
+            e.unwrap_err().to_string()
+        );
+        assert_eq!(
+            Ok(()),
+            CipherError::find("Hello", "Bad symbol.", |c| c == '!')
+        );
+    }
+
+    #[test]
+    fn byte_offset() {
+        let e = CipherError::find("héllo!", "Bad symbol.", |c| c == '!').unwrap_err();
+
+        assert_eq!(
+            CipherError::Symbol {
+                symbol: '!',
+                offset: 6,
+                reason: "Bad symbol.",
+            },
+            e
+        );
+        assert_eq!("Bad symbol.", <&str>::from(e));
+    }
+
+    #[test]
+    fn whole_message() {
+        let e = CipherError::from("Message too long.");
+
+        assert_eq!(CipherError::Message("Message too long."), e);
+        assert_eq!("Message too long.", e.to_string());
+        assert_eq!(
+            e,
+            CipherError::find("ab!", "Message too long.", |c| c == '!')
+                .unwrap_err()
+                .without_symbol()
+        );
+    }
+}
@@ -0,0 +1,46 @@
+//! Splits text into the units that transposition ciphers move as a whole.
+//!
+//! Without the `graphemes` feature each unit is a single `char`. With the feature enabled, each
+//! unit is an extended grapheme cluster, so that a letter and its combining marks, or an emoji
+//! and its variation selector (such as `🗡️`), are never split apart by a transposition.
+//!
+#[cfg(feature = "graphemes")]
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The units of a `text`, in order.
+///
+#[cfg(feature = "graphemes")]
+pub fn units(text: &str) -> Vec<&str> {
+    text.graphemes(true).collect()
+}
+
+/// The units of a `text`, in order.
+///
+#[cfg(not(feature = "graphemes"))]
+pub fn units(text: &str) -> Vec<&str> {
+    text.char_indices()
+        .map(|(i, c)| &text[i..i + c.len_utf8()])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_chars() {
+        assert_eq!(vec!["a", "ß", "✓"], units("aß✓"));
+    }
+
+    #[cfg(feature = "graphemes")]
+    #[test]
+    fn keeps_clusters_whole() {
+        assert_eq!(vec!["e\u{301}", "🗡️", "!"], units("e\u{301}🗡️!"));
+    }
+
+    #[cfg(not(feature = "graphemes"))]
+    #[test]
+    fn splits_clusters() {
+        assert_eq!(vec!["e", "\u{301}"], units("e\u{301}"));
+    }
+}
@@ -0,0 +1,157 @@
+//! Formats ciphertext into the fixed-size groups of letters in which it was traditionally sent.
+//!
+//! Historical ciphertexts were transmitted in groups of five letters, several groups to a line,
+//! so that the lengths and boundaries of the words of the message were hidden. A `Grouping`
+//! describes such a layout, and is given to the `encrypt_grouped()` method of `Cipher`. The
+//! `decrypt_grouped()` method ignores the whitespace between the groups again.
+//!
+
+/// A layout of ciphertext in groups of letters.
+///
+/// This struct is created by the `new()` method, and configured by its builder methods.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Grouping {
+    size: usize,
+    line_width: Option<usize>,
+}
+
+impl Default for Grouping {
+    fn default() -> Grouping {
+        Grouping::new()
+    }
+}
+
+impl Grouping {
+    /// Initialise a layout of groups of five characters, separated by spaces, on a single line.
+    ///
+    pub fn new() -> Grouping {
+        Grouping {
+            size: 5,
+            line_width: None,
+        }
+    }
+
+    /// Set the number of characters in each group.
+    ///
+    /// # Panics
+    /// * `size` is 0.
+    ///
+    pub fn size(mut self, size: usize) -> Grouping {
+        if size == 0 {
+            panic!("A group must have at least one character.");
+        }
+
+        self.size = size;
+        self
+    }
+
+    /// Set the most characters on each line, including the spaces between groups. Lines are only
+    /// broken between groups, so each line holds at least one group.
+    ///
+    pub fn line_width(mut self, line_width: usize) -> Grouping {
+        self.line_width = Some(line_width);
+        self
+    }
+
+    /// Lay out a `text` in groups, ignoring the whitespace within it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::Grouping;
+    ///
+    /// let g = Grouping::new().line_width(11);
+    /// assert_eq!("Attac katda\nwn", g.apply("Attack at dawn"));
+    /// ```
+    ///
+    pub fn apply(&self, text: &str) -> String {
+        let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+
+        let mut grouped = String::new();
+        let mut line = 0;
+        for group in chars.chunks(self.size) {
+            if !grouped.is_empty() {
+                match self.line_width {
+                    Some(width) if line + 1 + group.len() > width => {
+                        grouped.push('\n');
+                        line = 0;
+                    }
+                    _ => {
+                        grouped.push(' ');
+                        line += 1;
+                    }
+                }
+            }
+
+            grouped.extend(group);
+            line += group.len();
+        }
+
+        grouped
+    }
+
+    /// Pad a `text` with a `padding` character, so that it fills a whole number of groups. The
+    /// whitespace within the text is not counted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::Grouping;
+    ///
+    /// assert_eq!("Attack at dawnxxx", Grouping::new().pad("Attack at dawn", 'x'));
+    /// ```
+    ///
+    pub fn pad(&self, text: &str, padding: char) -> String {
+        let length = text.chars().filter(|c| !c.is_whitespace()).count();
+        let mut padded = text.to_string();
+        if length % self.size > 0 {
+            padded.extend((length % self.size..self.size).map(|_| padding));
+        }
+
+        padded
+    }
+
+    /// Remove the whitespace between the groups of a `text`.
+    ///
+    pub fn remove(text: &str) -> String {
+        text.chars().filter(|c| !c.is_whitespace()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn five_letter_groups() {
+        assert_eq!(
+            "DEFEN DTHEE ASTWA LL",
+            Grouping::new().apply("DEFEND THE EAST WALL")
+        );
+    }
+
+    #[test]
+    fn breaks_lines() {
+        let g = Grouping::new().size(3).line_width(8);
+        assert_eq!("abc def\nghi j", g.apply("abcdefghij"));
+    }
+
+    #[test]
+    fn narrow_lines() {
+        let g = Grouping::new().line_width(1);
+        assert_eq!("abcde\nf", g.apply("abcdef"));
+    }
+
+    #[test]
+    fn pad_whole_groups() {
+        let g = Grouping::new().size(3);
+        assert_eq!("abc def", g.pad("abc def", 'x'));
+        assert_eq!("", g.pad("", 'x'));
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_groups() {
+        Grouping::new().size(0);
+    }
+}
@@ -0,0 +1,158 @@
+//! Describes the problems that make a key unusable by a cipher.
+//!
+//! The constructors of the ciphers panic on the first problem they find with a key. Where a key is
+//! typed in by a user, it is more helpful to find every problem with it at once, and to know where
+//! in the key each one lies. The ciphers that support this have a `key_problems()` method, which
+//! lists the problems with a key as `KeyProblem`s; a key with no problems will not panic when
+//! given to the cipher.
+//!
+use super::alphabet::Alphabet;
+use std::fmt;
+
+/// A problem with the key of a cipher.
+///
+/// The positions given count the characters of a key from 0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeyProblem {
+    /// The key is empty.
+    Empty,
+    /// The `value` of a number in the key is not in the inclusive range from `min` to `max`.
+    OutOfRange {
+        value: usize,
+        min: usize,
+        max: usize,
+    },
+    /// The `value` of a number in the key shares a factor with the `modulus` of the cipher, so
+    /// has no inverse.
+    SharedFactor { value: usize, modulus: usize },
+    /// The key has a `symbol` at a `position` that the cipher cannot use.
+    InvalidSymbol { position: usize, symbol: char },
+    /// The key repeats a `symbol` at a `position`, which already appears earlier in the key.
+    DuplicateSymbol { position: usize, symbol: char },
+    /// The key has an `actual` length (in characters), when the cipher needs it to have an
+    /// `expected` length.
+    Length { expected: usize, actual: usize },
+    /// The key matrix has a different number of `rows` to `columns`.
+    NotSquare { rows: usize, columns: usize },
+    /// The key matrix cannot be inverted (modulo the length of the alphabet), so could not be used
+    /// for decryption.
+    NotInvertible,
+    /// The padding character of the cipher appears in the key at a `position`.
+    PaddingInKey { position: usize },
+}
+
+impl fmt::Display for KeyProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            KeyProblem::Empty => write!(f, "The key is empty."),
+            KeyProblem::OutOfRange { value, min, max } => write!(
+                f,
+                "The number {} must be within the range {} <= n <= {}.",
+                value, min, max
+            ),
+            KeyProblem::SharedFactor { value, modulus } => write!(
+                f,
+                "The number {} cannot share a common factor with {}.",
+                value, modulus
+            ),
+            KeyProblem::InvalidSymbol { position, symbol } => write!(
+                f,
+                "The symbol '{}' at position {} cannot be used.",
+                symbol, position
+            ),
+            KeyProblem::DuplicateSymbol { position, symbol } => write!(
+                f,
+                "The symbol '{}' at position {} is repeated.",
+                symbol, position
+            ),
+            KeyProblem::Length { expected, actual } => write!(
+                f,
+                "The key has a length of {}, but must have a length of {}.",
+                actual, expected
+            ),
+            KeyProblem::NotSquare { rows, columns } => write!(
+                f,
+                "The key has {} rows and {} columns, but must be square.",
+                rows, columns
+            ),
+            KeyProblem::NotInvertible => write!(f, "The key cannot be inverted for decryption."),
+            KeyProblem::PaddingInKey { position } => write!(
+                f,
+                "The padding character appears in the key at position {}.",
+                position
+            ),
+        }
+    }
+}
+
+/// An `InvalidSymbol` problem for each character of a `key` outside of the `alphabet`.
+///
+pub(crate) fn invalid_symbols<A: Alphabet>(key: &str, alphabet: &A) -> Vec<KeyProblem> {
+    key.chars()
+        .enumerate()
+        .filter(|&(_, c)| alphabet.find_position(c).is_none())
+        .map(|(position, symbol)| KeyProblem::InvalidSymbol { position, symbol })
+        .collect()
+}
+
+/// A `DuplicateSymbol` problem for each character of a `key` that appears earlier in the key.
+///
+pub(crate) fn duplicate_symbols(key: &str) -> Vec<KeyProblem> {
+    let chars: Vec<char> = key.chars().collect();
+
+    (0..chars.len())
+        .filter(|&i| chars[..i].contains(&chars[i]))
+        .map(|position| KeyProblem::DuplicateSymbol {
+            position,
+            symbol: chars[position],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::alphabet;
+
+    #[test]
+    fn finds_every_symbol() {
+        assert_eq!(
+            vec![
+                KeyProblem::InvalidSymbol {
+                    position: 1,
+                    symbol: '3'
+                },
+                KeyProblem::InvalidSymbol {
+                    position: 3,
+                    symbol: '!'
+                },
+            ],
+            invalid_symbols("a3c!", &alphabet::STANDARD)
+        );
+        assert_eq!(
+            vec![
+                KeyProblem::DuplicateSymbol {
+                    position: 2,
+                    symbol: 'b'
+                },
+                KeyProblem::DuplicateSymbol {
+                    position: 3,
+                    symbol: 'b'
+                },
+            ],
+            duplicate_symbols("abbbB")
+        );
+    }
+
+    #[test]
+    fn describes_problems() {
+        assert_eq!(
+            "The symbol '!' at position 3 cannot be used.",
+            KeyProblem::InvalidSymbol {
+                position: 3,
+                symbol: '!'
+            }
+            .to_string()
+        );
+    }
+}
@@ -2,6 +2,7 @@
 //!
 use super::alphabet;
 use super::alphabet::{Alphabet, ALPHANUMERIC, PLAYFAIR, STANDARD};
+use rand::Rng;
 use std::collections::HashMap;
 
 /// Generates a scrambled alphabet using a key phrase for a given alphabet type.
@@ -54,15 +55,8 @@ pub fn keyed_alphabet<T: Alphabet>(key: &str, alpha_type: &T, to_uppercase: bool
 /// * The `keystream` contains non-alphanumeric symbols.
 /// * The `keystream` contains duplicate characters.
 pub fn columnar_key(keystream: &str) -> Vec<(char, Vec<char>)> {
-    let unique_chars: HashMap<_, _> = keystream.chars().map(|c| (c, c)).collect();
-
-    //Validate key
-    if keystream.is_empty() {
-        panic!("The keystream is empty.");
-    } else if keystream.len() - unique_chars.len() > 0 {
-        panic!("The keystream cannot contain duplicate alphanumeric characters.");
-    } else if !ALPHANUMERIC.is_valid(keystream) {
-        panic!("The keystream cannot contain non-alphanumeric symbols.");
+    if let Err(e) = check_columnar_key(keystream) {
+        panic!("{}", e);
     }
 
     keystream
@@ -71,13 +65,32 @@ pub fn columnar_key(keystream: &str) -> Vec<(char, Vec<char>)> {
         .collect::<Vec<(char, Vec<char>)>>()
 }
 
-/// Generate a 6x6 polybius square hashmap from an alphanumeric key.
+/// Check that a keystream can be used as a Columnar Transposition key, returning `Err` for any of
+/// the conditions under which `columnar_key` panics.
+pub fn check_columnar_key(keystream: &str) -> Result<(), &'static str> {
+    let unique_chars: HashMap<_, _> = keystream.chars().map(|c| (c, c)).collect();
+
+    if keystream.is_empty() {
+        Err("The keystream is empty.")
+    } else if keystream.chars().count() > unique_chars.len() {
+        Err("The keystream cannot contain duplicate alphanumeric characters.")
+    } else if !ALPHANUMERIC.is_valid(keystream) {
+        Err("The keystream cannot contain non-alphanumeric symbols.")
+    } else {
+        Ok(())
+    }
+}
+
+/// Generate a polybius square hashmap from a key of a given alphabet type.
 /// For successfull generation, the following must be met:
 ///
 /// # Panics
-/// * The `key` must have a length of 36.
-/// * The `key` must contain each character of the alphanumeric alphabet `a-z`, `0-9`.
-/// * The `key` must contain alphanumeric characters only.
+/// * The `key` must have the same length as the alphabet (i.e. 36 for a 6x6 alphanumeric square,
+///   or 25 for a 5x5 playfair square).
+/// * The `key` must contain each character of the alphabet.
+/// * The `key` must contain characters of the alphabet only.
+/// * The number of `column_ids` multiplied by the number of `row_ids` must equal the alphabet
+///   length.
 /// * The `column` and `row_ids` must contain alphabetic characters only.
 /// * The `column` or `row_ids` contain repeated characters.
 ///
@@ -94,24 +107,29 @@ pub fn columnar_key(keystream: &str) -> Vec<(char, Vec<char>)> {
 /// E| 5 t u 6 v w
 /// F| 7 x 8 y 9 z
 ///
-/// `let square = keygen::polybius_square("or0ange1bcdf2hijk3lmp4qs5tu6vw7x8y9z",
-///     ['A','B','C','D','E', 'F'], ['A','B','C','D','E', 'F']).unwrap();`
+/// `let square = keygen::polybius_square("or0ange1bcdf2hijk3lmp4qs5tu6vw7x8y9z", &ALPHANUMERIC,
+///     &['A','B','C','D','E', 'F'], &['A','B','C','D','E', 'F']);`
 ///
 /// `assert_eq!(&'c', square.get("bd").unwrap());`
-pub fn polybius_square(
+pub fn polybius_square<T: Alphabet>(
     key: &str,
-    column_ids: &[char; 6],
-    row_ids: &[char; 6],
+    alpha_type: &T,
+    column_ids: &[char],
+    row_ids: &[char],
 ) -> HashMap<String, char> {
     let unique_chars: HashMap<_, _> = key.chars().map(|c| (c, c)).collect();
 
     //Validate the key
-    if key.len() != 36 {
-        panic!("The key must contain each character of the alphanumeric alphabet a-z 0-9.");
+    if key.len() != alpha_type.length() {
+        panic!("The key must contain each character of the alphabet.");
     } else if key.len() - unique_chars.len() > 0 {
-        panic!("The key cannot contain duplicate alphanumeric characters.");
-    } else if !ALPHANUMERIC.is_valid(key) {
-        panic!("The key cannot contain non-alphanumeric symbols.");
+        panic!("The key cannot contain duplicate characters.");
+    } else if !alpha_type.is_valid(key) {
+        panic!("The key cannot contain symbols outside of the alphabet.");
+    }
+
+    if column_ids.len() * row_ids.len() != alpha_type.length() {
+        panic!("The column and row ids do not match the size of the alphabet.");
     }
 
     //Check that the column and row ids are valid
@@ -139,8 +157,8 @@ pub fn polybius_square(
     let mut polybius_square = HashMap::new();
     let mut values = key.chars();
 
-    for row in row_ids.iter().take(6) {
-        for column in column_ids.iter().take(6) {
+    for row in row_ids.iter() {
+        for column in column_ids.iter() {
             let k = row.to_string() + &column.to_string();
             let v = values.next().expect("Alphabet square is invalid.");
 
@@ -158,6 +176,58 @@ pub fn polybius_square(
     polybius_square
 }
 
+/// Generate a 3x3x3 trifid cube from a key phrase.
+///
+/// The cube holds the 26 letters of the alphabet plus an additional `fill_char`. The phrase,
+/// omitting repeated characters, fills the cube first (layer by layer, row by row). The
+/// remaining letters are then appended, followed by the `fill_char` if it did not appear in the
+/// phrase. The cube is indexed as `cube[layer][row][column]`.
+///
+/// # Panics
+/// * The `phrase` contains a symbol that is not alphabetic or the `fill_char`.
+/// * The `fill_char` is alphabetic.
+///
+/// # Example
+/// Given the phrase `FELIXMARIEDELASTELLE` and a `fill_char` of `+`, the following cube is
+/// generated:
+///
+/// Layer 1   Layer 2   Layer 3
+/// F E L     S T B     O P Q
+/// I X M     C G H     U V W
+/// A R D     J K N     Y Z +
+///
+pub fn trifid_cube(phrase: &str, fill_char: char) -> [[[char; 3]; 3]; 3] {
+    if STANDARD.find_position(fill_char).is_some() {
+        panic!("The fill character cannot be alphabetic.");
+    }
+    if phrase
+        .chars()
+        .any(|c| c != fill_char && STANDARD.find_position(c).is_none())
+    {
+        panic!("The phrase cannot contain non-alphabetic symbols.");
+    }
+
+    let mut unique: Vec<char> = Vec::new();
+    let upper = phrase.to_uppercase();
+    let chars = upper
+        .chars()
+        .chain((0..STANDARD.length()).map(|i| STANDARD.get_letter(i, true)))
+        .chain(std::iter::once(fill_char));
+
+    for c in chars {
+        if !unique.contains(&c) {
+            unique.push(c);
+        }
+    }
+
+    let mut cube = [[[fill_char; 3]; 3]; 3];
+    for (i, c) in unique.into_iter().enumerate() {
+        cube[i / 9][(i / 3) % 3][i % 3] = c;
+    }
+
+    cube
+}
+
 /// Create a new Playfair key table
 ///
 /// The table is a 5x5 (I=J) matrix. Any repeated characters are removed
@@ -218,30 +288,104 @@ pub fn playfair_table(keystream: &str) -> ([String; 5], [String; 5]) {
     (rows, cols)
 }
 
+/// Sequentialise a key, numbering each element by its rank within the key.
+///
+/// Elements are ranked in ascending order starting from 1, with ties broken from left to right.
+/// Digit keys should have `0` mapped to `10` before sequentialising, as is the convention in
+/// ciphers such as VIC.
+///
+/// # Example
+/// The key `['C', 'A', 'B', 'A']` is sequentialised to `[4, 1, 3, 2]`.
+///
+pub fn sequentialise<T: Ord>(key: &[T]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..key.len()).collect();
+    order.sort_by(|&a, &b| key[a].cmp(&key[b])); //A stable sort keeps ties left to right
+
+    let mut ranks = vec![0; key.len()];
+    for (rank, &pos) in order.iter().enumerate() {
+        ranks[pos] = rank + 1;
+    }
+
+    ranks
+}
+
+/// Extend a sequence of digits to `length` using chain addition (a lagged Fibonacci generator).
+///
+/// Each new digit is the sum (mod 10) of the first two digits of the preceding window, where
+/// the window is the same size as the `seed`.
+///
+/// # Panics
+/// * The `seed` has less than 2 digits.
+///
+/// # Example
+/// The seed `77651` chain added to a length of 10 produces `7765143165`.
+///
+pub fn chain_addition(seed: &[u8], length: usize) -> Vec<u8> {
+    if seed.len() < 2 {
+        panic!("The seed must contain at least two digits.");
+    }
+
+    let mut digits = seed.to_vec();
+    let window = seed.len();
+    while digits.len() < length {
+        let i = digits.len() - window;
+        digits.push((digits[i] + digits[i + 1]) % 10);
+    }
+
+    digits.truncate(length);
+    digits
+}
+
+/// Generate a random pad of lowercase letters, for use with a one-time pad.
+///
+/// The pad is generated using the thread-local random number generator of the `rand` crate.
+///
+pub fn one_time_pad(length: usize) -> String {
+    let mut rng = rand::thread_rng();
+    (0..length)
+        .map(|_| STANDARD.get_letter(rng.gen_range(0..26), false))
+        .collect()
+}
+
 /// Generate a cyclic keystream.
 ///
 /// For this, we simply repeat the key until we have enough symbols to
 /// encrypt all alphabetic symbols of the message.
-pub fn cyclic_keystream(key: &str, message: &str) -> String {
-    let scrubbed_msg = alphabet::STANDARD.scrub(message);
-    key.chars().cycle().take(scrubbed_msg.len()).collect()
+pub fn cyclic_keystream<'a>(key: &'a str, message: &str) -> impl Iterator<Item = char> + 'a {
+    alphabet_cyclic_keystream(key, message, &alphabet::STANDARD)
 }
 
-/// Generate a concatonated keystream (key + message).
+/// Generate a cyclic keystream for the symbols of the message that belong to the alphabet
+/// `alpha`.
 ///
-pub fn concatonated_keystream(key: &str, message: &str) -> String {
-    //The key will only be used to encrypt the portion of the message that is alphabetic
-    let scrubbed_msg = alphabet::STANDARD.scrub(message);
+pub fn alphabet_cyclic_keystream<'a, T: Alphabet>(
+    key: &'a str,
+    message: &str,
+    alpha: &T,
+) -> impl Iterator<Item = char> + 'a {
+    let symbols = message
+        .chars()
+        .filter(|&c| alpha.find_position(c).is_some())
+        .count();
+    key.chars().cycle().take(symbols)
+}
 
-    //The key is large enough for the message already
-    if key.len() >= scrubbed_msg.len() {
-        return key.chars().take(scrubbed_msg.len()).collect();
-    }
+/// Generate a concatonated keystream (key + message) for the symbols of the message that belong
+/// to the alphabet `alpha`.
+///
+pub fn concatonated_keystream<'a, T: Alphabet>(
+    key: &'a str,
+    message: &'a str,
+    alpha: &'a T,
+) -> impl Iterator<Item = char> + 'a {
+    //The key will only be used to encrypt the portion of the message within the alphabet, and
+    //is simply followed by that portion of the message where it is not large enough already
+    let in_alphabet = move |c: &char| alpha.find_position(*c).is_some();
+    let symbols = message.chars().filter(in_alphabet).count();
 
-    //The keystream is simply a concatonation of the base key + the scrubbed message
     key.chars()
-        .chain(scrubbed_msg.chars().take(scrubbed_msg.len() - key.len()))
-        .collect()
+        .chain(message.chars().filter(in_alphabet))
+        .take(symbols)
 }
 
 #[cfg(test)]
@@ -252,33 +396,78 @@ mod tests {
     fn cyclic_smaller_base_key() {
         assert_eq!(
             "lemonlemonlemon",
-            cyclic_keystream("lemon", "We are under seige!")
+            cyclic_keystream("lemon", "We are under seige!").collect::<String>()
         );
     }
 
     #[test]
     fn cyclic_larger_base_key() {
-        assert_eq!("le", cyclic_keystream("lemon", "hi"));
+        assert_eq!("le", cyclic_keystream("lemon", "hi").collect::<String>());
+    }
+
+    #[test]
+    fn cyclic_custom_alphabet() {
+        let greek = alphabet::Custom::new("αβγδ");
+        assert_eq!(
+            "βαβ",
+            alphabet_cyclic_keystream("βα", "Αβ-δ!", &greek).collect::<String>()
+        );
     }
 
     #[test]
     fn concatonated_larger_base_key() {
-        assert_eq!("forti", concatonated_keystream("fortification", "Hello"));
+        assert_eq!(
+            "forti",
+            concatonated_keystream("fortification", "Hello", &STANDARD).collect::<String>()
+        );
     }
 
     #[test]
     fn concatonated_smaller_base_key() {
         assert_eq!(
             "lemonWeareunder",
-            concatonated_keystream("lemon", "We are under seige")
+            concatonated_keystream("lemon", "We are under seige", &STANDARD).collect::<String>()
+        );
+    }
+
+    #[test]
+    fn sequentialise_letters() {
+        assert_eq!(vec![4, 1, 3, 2], sequentialise(&['C', 'A', 'B', 'A']));
+    }
+
+    #[test]
+    fn sequentialise_ten_elements() {
+        let key: Vec<char> = "IDREAMOFJE".chars().collect();
+        assert_eq!(vec![6, 2, 10, 3, 1, 8, 9, 5, 7, 4], sequentialise(&key));
+    }
+
+    #[test]
+    fn one_time_pad_length() {
+        let pad = one_time_pad(100);
+        assert_eq!(100, pad.len());
+        assert!(STANDARD.is_valid(&pad));
+    }
+
+    #[test]
+    fn chain_addition_extends_seed() {
+        assert_eq!(
+            vec![7, 7, 6, 5, 1, 4, 3, 1, 6, 5],
+            chain_addition(&[7, 7, 6, 5, 1], 10)
         );
     }
 
+    #[test]
+    #[should_panic]
+    fn chain_addition_short_seed() {
+        chain_addition(&[1], 10);
+    }
+
     //Polybius tests
     #[test]
     fn polybius_hashmap_order() {
         let p = polybius_square(
             "abcdefghijklmnopqrstuvwxyz0123456789",
+            &ALPHANUMERIC,
             &['a', 'b', 'c', 'd', 'e', 'f'],
             &['a', 'b', 'c', 'd', 'e', 'f'],
         );
@@ -290,11 +479,37 @@ mod tests {
         assert_eq!(&'z', &p["eb"]);
     }
 
+    #[test]
+    fn polybius_playfair_hashmap_order() {
+        let p = polybius_square(
+            "abcdefghiklmnopqrstuvwxyz",
+            &PLAYFAIR,
+            &['a', 'b', 'c', 'd', 'e'],
+            &['a', 'b', 'c', 'd', 'e'],
+        );
+
+        assert_eq!(&'a', &p["aa"]);
+        assert_eq!(&'k', &p["be"]);
+        assert_eq!(&'Z', &p["EE"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn polybius_ids_size_mismatch() {
+        polybius_square(
+            "abcdefghiklmnopqrstuvwxyz",
+            &PLAYFAIR,
+            &['a', 'b', 'c', 'd', 'e', 'f'],
+            &['a', 'b', 'c', 'd', 'e'],
+        );
+    }
+
     #[test]
     #[should_panic]
     fn polybius_duplicate_characters() {
         polybius_square(
             "abcdefghijklnnopqrstuvwxyz0123456789",
+            &ALPHANUMERIC,
             &['a', 'b', 'c', 'd', 'e', 'f'],
             &['a', 'b', 'c', 'd', 'e', 'f'],
         );
@@ -305,6 +520,7 @@ mod tests {
     fn polybius_missing_characters() {
         polybius_square(
             "adefghiklnnopqrstuvwxyz",
+            &ALPHANUMERIC,
             &['a', 'b', 'c', 'd', 'e', 'f'],
             &['a', 'b', 'c', 'd', 'e', 'f'],
         );
@@ -315,6 +531,7 @@ mod tests {
     fn polybius_non_alpha_characters() {
         polybius_square(
             "abcd@#!ghiklnnopqrstuvwxyz0123456789",
+            &ALPHANUMERIC,
             &['a', 'b', 'c', 'd', 'e', 'f'],
             &['a', 'b', 'c', 'd', 'e', 'f'],
         );
@@ -325,6 +542,7 @@ mod tests {
     fn polybius_repeated_column_ids() {
         polybius_square(
             "abcdefghijklmnopqrstuvwxyz0123456789",
+            &ALPHANUMERIC,
             &['a', 'a', 'c', 'd', 'e', 'f'],
             &['a', 'b', 'c', 'd', 'e', 'f'],
         );
@@ -335,6 +553,7 @@ mod tests {
     fn polybius_repeated_row_ids() {
         polybius_square(
             "abcdefghijklmnopqrstuvwxyz0123456789",
+            &ALPHANUMERIC,
             &['a', 'b', 'c', 'd', 'e', 'f'],
             &['a', 'b', 'c', 'c', 'e', 'f'],
         );
@@ -410,6 +629,34 @@ mod tests {
         columnar_key("Fx !@#$");
     }
 
+    #[test]
+    fn trifid_cube_layout() {
+        let cube = trifid_cube("FELIXMARIEDELASTELLE", '+');
+        assert_eq!(['F', 'E', 'L'], cube[0][0]);
+        assert_eq!(['A', 'R', 'D'], cube[0][2]);
+        assert_eq!(['S', 'T', 'B'], cube[1][0]);
+        assert_eq!(['Y', 'Z', '+'], cube[2][2]);
+    }
+
+    #[test]
+    fn trifid_cube_keyed_fill_char() {
+        let cube = trifid_cube("a.b", '.');
+        assert_eq!(['A', '.', 'B'], cube[0][0]);
+        assert_eq!(['X', 'Y', 'Z'], cube[2][2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn trifid_cube_rejects_symbols() {
+        trifid_cube("bad key", '+');
+    }
+
+    #[test]
+    #[should_panic]
+    fn trifid_cube_rejects_alphabetic_fill() {
+        trifid_cube("key", 'q');
+    }
+
     // PlayfairTable Tests
     #[test]
     fn playfair_accepts_simple_key() {
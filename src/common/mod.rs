@@ -1,7 +1,17 @@
 //! Contains common constructs used in multiple modules.
 //!
 pub mod alphabet;
+pub mod checkerboard;
 pub mod cipher;
+pub mod cipher_error;
+pub mod grapheme;
+pub mod grouping;
+pub mod key_problem;
 pub mod keygen;
 pub mod morse;
+pub mod options;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod policy;
+pub mod render;
 pub mod substitute;
@@ -1,4 +1,29 @@
-//! Contains helpful constants and functions used in Morse-based ciphers.
+//! Encodes and decodes messages in International Morse code.
+//!
+//! Morse code is not a cipher, as it has no key, but it underlies several that are (such as the
+//! Fractionated Morse cipher). Each letter, digit and punctuation mark of a message is written as a
+//! sequence of dots and dashes. The letters of a word are separated by a space, and the words of a
+//! message by a slash, so that `SOS HELP` is written as `... --- ... / .... . .-.. .--.`.
+//!
+//! Procedural signals (prosigns) are written in a message between angle brackets, such as `<AR>`
+//! for the end of a message. Each is sent as one sequence, without the gaps between its letters.
+//!
+//! The dots and dashes, and the separators, can be written with other symbols (see `Morse`).
+//! The `timing()` function gives the length of each tone and silence of a message instead, for
+//! driving a light or a sounder.
+//!
+//! With the `audio` feature enabled, a message can also be rendered as the tone that would be
+//! sent over the air, with `Tone`.
+//!
+//! # Example
+//!
+//! ```
+//! use cipher_crypt::morse;
+//!
+//! let code = morse::encode("SOS help <AR>").unwrap();
+//! assert_eq!("... --- ... / .... . .-.. .--. / .-.-.", code);
+//! assert_eq!("SOS HELP <AR>", morse::decode(&code).unwrap());
+//! ```
 //!
 
 // The Morse alphabet (Obtained from https://morsecode.scphillips.com/morse2.html)
@@ -54,6 +79,18 @@ const MORSE_ALPHABET: [(&str, &str); 49] = [
     ("=", "-...-"),
 ];
 
+// The procedural signals, which are written as the letters they run together
+const PROSIGNS: [(&str, &str); 8] = [
+    ("<AR>", ".-.-."),
+    ("<AS>", ".-..."),
+    ("<BT>", "-...-"),
+    ("<CT>", "-.-.-"),
+    ("<HH>", "........"),
+    ("<KN>", "-.--."),
+    ("<SK>", "...-.-"),
+    ("<SOS>", "...---..."),
+];
+
 /// Attempts to decode a morsecode sequence into a character of the known alphabet.
 ///
 /// Will return None if the Morse code isn't present in the alphabet
@@ -76,3 +113,594 @@ pub fn encode_character(c: char) -> Option<String> {
         None => None,
     }
 }
+
+#[cfg(feature = "audio")]
+use std::f64::consts::PI;
+#[cfg(feature = "audio")]
+use std::time::Duration;
+
+/// Encode a `message` into Morse code, with the default separators of `Morse::new()`.
+///
+/// # Errors
+/// * As for `Morse::encode()`.
+///
+pub fn encode(message: &str) -> Result<String, &'static str> {
+    Morse::new().encode(message)
+}
+
+/// Decode Morse `code` into a message, with the default separators of `Morse::new()`.
+///
+/// # Errors
+/// * As for `Morse::decode()`.
+///
+pub fn decode(code: &str) -> Result<String, &'static str> {
+    Morse::new().decode(code)
+}
+
+/// An encoder and decoder of Morse code, with configurable symbols and separators.
+///
+/// This struct is created by the `new()` method, and configured by its builder methods.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::morse::Morse;
+///
+/// // Each unit of time as a digit, with 1 for a tone and 0 for a silence
+/// let binary = Morse::new()
+///     .dot("1")
+///     .dash("111")
+///     .symbol_separator("0")
+///     .letter_separator("000")
+///     .word_separator("0000000");
+///
+/// assert_eq!("10111000111", binary.encode("at").unwrap());
+/// assert_eq!("AT", binary.decode("10111000111").unwrap());
+///
+/// let dotted = Morse::new().dot("·").dash("−");
+/// assert_eq!("·− / −", dotted.encode("a t").unwrap());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Morse {
+    dot: String,
+    dash: String,
+    symbol_separator: String,
+    letter_separator: String,
+    word_separator: String,
+}
+
+impl Default for Morse {
+    fn default() -> Morse {
+        Morse::new()
+    }
+}
+
+impl Morse {
+    /// Initialise an encoder that writes dots as `.` and dashes as `-`, and separates letters with
+    /// a space and words with ` / `.
+    ///
+    pub fn new() -> Morse {
+        Morse {
+            dot: String::from("."),
+            dash: String::from("-"),
+            symbol_separator: String::new(),
+            letter_separator: String::from(" "),
+            word_separator: String::from(" / "),
+        }
+    }
+
+    /// Set the symbol written for a dot.
+    ///
+    /// # Panics
+    /// * The `dot` is empty.
+    ///
+    pub fn dot(mut self, dot: &str) -> Morse {
+        if dot.is_empty() {
+            panic!("The dot is empty.");
+        }
+
+        self.dot = dot.to_string();
+        self
+    }
+
+    /// Set the symbol written for a dash.
+    ///
+    /// # Panics
+    /// * The `dash` is empty.
+    ///
+    pub fn dash(mut self, dash: &str) -> Morse {
+        if dash.is_empty() {
+            panic!("The dash is empty.");
+        }
+
+        self.dash = dash.to_string();
+        self
+    }
+
+    /// Set the separator written between the dots and dashes of a letter, which is empty by
+    /// default.
+    ///
+    pub fn symbol_separator(mut self, separator: &str) -> Morse {
+        self.symbol_separator = separator.to_string();
+        self
+    }
+
+    /// Set the separator written between the letters of a word.
+    ///
+    /// # Panics
+    /// * The `separator` is empty.
+    ///
+    pub fn letter_separator(mut self, separator: &str) -> Morse {
+        if separator.is_empty() {
+            panic!("The letter separator is empty.");
+        }
+
+        self.letter_separator = separator.to_string();
+        self
+    }
+
+    /// Set the separator written between the words of a message.
+    ///
+    /// # Panics
+    /// * The `separator` is empty.
+    ///
+    pub fn word_separator(mut self, separator: &str) -> Morse {
+        if separator.is_empty() {
+            panic!("The word separator is empty.");
+        }
+
+        self.word_separator = separator.to_string();
+        self
+    }
+
+    /// Encode a `message` into Morse code.
+    ///
+    /// The words of the message are split on whitespace. A prosign is written between angle
+    /// brackets, such as `<SK>`; the case of the letters of the message is ignored.
+    ///
+    /// # Errors
+    /// * The message contains a character, or a prosign, that has no Morse code.
+    ///
+    /// # Panics
+    /// * The dot and the dash are the same symbol.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::morse::Morse;
+    ///
+    /// let m = Morse::new().letter_separator("|").word_separator("||");
+    /// assert_eq!(".-|-||.-|-", m.encode("At at").unwrap());
+    /// ```
+    ///
+    pub fn encode(&self, message: &str) -> Result<String, &'static str> {
+        self.check_symbols();
+        let words = message
+            .split_whitespace()
+            .map(|word| {
+                Ok(encode_word(word)?
+                    .iter()
+                    .map(|seq| self.write_symbols(seq))
+                    .collect::<Vec<String>>()
+                    .join(&self.letter_separator))
+            })
+            .collect::<Result<Vec<String>, &'static str>>()?;
+
+        Ok(words.join(&self.word_separator))
+    }
+
+    /// Decode Morse `code` into a message, whose letters are in uppercase.
+    ///
+    /// Repeated letter separators, such as the extra spaces left by hand, are ignored.
+    ///
+    /// # Errors
+    /// * The code contains a sequence that is not the code of a character or prosign.
+    ///
+    /// # Panics
+    /// * The dot and the dash are the same symbol.
+    ///
+    pub fn decode(&self, code: &str) -> Result<String, &'static str> {
+        self.check_symbols();
+        let words = code
+            .split(self.word_separator.as_str())
+            .map(|word| {
+                word.split(self.letter_separator.as_str())
+                    .filter(|seq| !seq.is_empty())
+                    .map(|seq| {
+                        let seq = self
+                            .read_symbols(seq)
+                            .ok_or("The code contains an unknown symbol.")?;
+
+                        decode_sequence(&seq)
+                            .or_else(|| {
+                                PROSIGNS
+                                    .iter()
+                                    .find(|p| p.1 == seq)
+                                    .map(|p| p.0.to_string())
+                            })
+                            .ok_or("The code contains an unknown sequence.")
+                    })
+                    .collect::<Result<String, &'static str>>()
+            })
+            .collect::<Result<Vec<String>, &'static str>>()?;
+
+        Ok(words
+            .into_iter()
+            .filter(|w| !w.is_empty())
+            .collect::<Vec<String>>()
+            .join(" "))
+    }
+
+    /// Check that the dot and the dash can be told apart.
+    ///
+    fn check_symbols(&self) {
+        if self.dot == self.dash {
+            panic!("The dot and the dash are the same symbol.");
+        }
+    }
+
+    /// Write a sequence of `.` and `-` with the symbols of the encoder.
+    ///
+    fn write_symbols(&self, seq: &str) -> String {
+        seq.chars()
+            .map(|s| if s == '.' { &self.dot } else { &self.dash }.as_str())
+            .collect::<Vec<&str>>()
+            .join(&self.symbol_separator)
+    }
+
+    /// Read a sequence written with the symbols of the encoder back into `.` and `-`.
+    ///
+    /// Returns `None` if the sequence has any other symbols.
+    ///
+    fn read_symbols(&self, seq: &str) -> Option<String> {
+        let symbols = [(self.dot.as_str(), '.'), (self.dash.as_str(), '-')];
+        if !self.symbol_separator.is_empty() {
+            return seq
+                .split(self.symbol_separator.as_str())
+                .map(|s| symbols.iter().find(|sym| sym.0 == s).map(|sym| sym.1))
+                .collect();
+        }
+
+        // Without a separator, the longer symbol is matched first, in case the other begins it
+        let mut by_length = symbols;
+        by_length.sort_by_key(|sym| std::cmp::Reverse(sym.0.len()));
+
+        let mut standard = String::new();
+        let mut rest = seq;
+        while !rest.is_empty() {
+            let &(symbol, s) = by_length.iter().find(|sym| rest.starts_with(sym.0))?;
+            standard.push(s);
+            rest = &rest[symbol.len()..];
+        }
+
+        Some(standard)
+    }
+}
+
+/// Encode the characters and prosigns of a `word`, giving the sequence of each.
+///
+fn encode_word(word: &str) -> Result<Vec<String>, &'static str> {
+    let mut sequences = Vec::new();
+    let mut rest = word;
+    while let Some(c) = rest.chars().next() {
+        let prosign = PROSIGNS
+            .iter()
+            .find(|p| rest.to_uppercase().starts_with(p.0));
+
+        match prosign {
+            Some(&(name, seq)) => {
+                sequences.push(seq.to_string());
+                rest = &rest[name.len()..];
+            }
+            None => {
+                sequences.push(
+                    encode_character(c)
+                        .ok_or("The message contains a character that has no Morse code.")?,
+                );
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    Ok(sequences)
+}
+
+/// A tone or a silence of a message in Morse code, lasting some number of units.
+///
+/// A unit is the length of a dot, so a dash lasts three. The silence between the dots and dashes of
+/// a letter lasts one unit, that between letters three, and that between words seven.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Signal {
+    /// A tone, or the key held down.
+    On(usize),
+    /// A silence, or the key let up.
+    Off(usize),
+}
+
+/// The signals of a `message` in Morse code, from the first tone to the last.
+///
+/// # Errors
+/// * As for `Morse::encode()`.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::morse::{self, Signal};
+///
+/// assert_eq!(
+///     vec![Signal::On(1), Signal::Off(1), Signal::On(3), Signal::Off(7), Signal::On(3)],
+///     morse::timing("A T").unwrap()
+/// );
+/// ```
+///
+pub fn timing(message: &str) -> Result<Vec<Signal>, &'static str> {
+    let mut signals = Vec::new();
+    for (w, word) in message.split_whitespace().enumerate() {
+        if w > 0 {
+            signals.push(Signal::Off(7));
+        }
+
+        for (l, seq) in encode_word(word)?.iter().enumerate() {
+            if l > 0 {
+                signals.push(Signal::Off(3));
+            }
+
+            for (s, symbol) in seq.chars().enumerate() {
+                if s > 0 {
+                    signals.push(Signal::Off(1));
+                }
+                signals.push(Signal::On(if symbol == '.' { 1 } else { 3 }));
+            }
+        }
+    }
+
+    Ok(signals)
+}
+
+/// Renders messages in Morse code as a tone, at a given speed and pitch.
+///
+/// This struct is created by the `new()` method, and configured by its builder methods. It is only
+/// available with the `audio` feature enabled.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::morse::Tone;
+///
+/// let wav = Tone::new().wpm(15).frequency(700.0).wav("CQ CQ").unwrap();
+/// assert_eq!(b"RIFF", &wav[0..4]);
+/// ```
+#[cfg(feature = "audio")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tone {
+    wpm: u32,
+    frequency: f64,
+    sample_rate: u32,
+}
+
+#[cfg(feature = "audio")]
+impl Default for Tone {
+    fn default() -> Tone {
+        Tone::new()
+    }
+}
+
+#[cfg(feature = "audio")]
+impl Tone {
+    /// Initialise a tone of 600 Hz, sent at 20 words per minute and sampled 8000 times a second.
+    ///
+    pub fn new() -> Tone {
+        Tone {
+            wpm: 20,
+            frequency: 600.0,
+            sample_rate: 8000,
+        }
+    }
+
+    /// Set the speed of the tone in words per minute, where a word is the fifty units of `PARIS`.
+    ///
+    /// # Panics
+    /// * `wpm` is 0.
+    ///
+    pub fn wpm(mut self, wpm: u32) -> Tone {
+        if wpm == 0 {
+            panic!("The speed must be at least one word per minute.");
+        }
+
+        self.wpm = wpm;
+        self
+    }
+
+    /// Set the pitch of the tone in Hz.
+    ///
+    pub fn frequency(mut self, frequency: f64) -> Tone {
+        self.frequency = frequency;
+        self
+    }
+
+    /// Set the number of samples taken each second.
+    ///
+    /// # Panics
+    /// * `sample_rate` is 0.
+    ///
+    pub fn sample_rate(mut self, sample_rate: u32) -> Tone {
+        if sample_rate == 0 {
+            panic!("The sample rate must be at least one sample per second.");
+        }
+
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// The duration of a single unit (a dot) of the tone.
+    ///
+    pub fn unit(&self) -> Duration {
+        Duration::from_secs_f64(1.2 / f64::from(self.wpm))
+    }
+
+    /// The durations for which the tone is on (`true`) and off (`false`) as a `message` is sent.
+    ///
+    /// # Errors
+    /// * As for `Morse::encode()`.
+    ///
+    pub fn durations(&self, message: &str) -> Result<Vec<(bool, Duration)>, &'static str> {
+        Ok(timing(message)?
+            .into_iter()
+            .map(|signal| match signal {
+                Signal::On(units) => (true, self.unit() * units as u32),
+                Signal::Off(units) => (false, self.unit() * units as u32),
+            })
+            .collect())
+    }
+
+    /// The samples of the tone as a `message` is sent, as signed 16-bit integers.
+    ///
+    /// # Errors
+    /// * As for `Morse::encode()`.
+    ///
+    pub fn samples(&self, message: &str) -> Result<Vec<i16>, &'static str> {
+        let per_unit = (f64::from(self.sample_rate) * 1.2 / f64::from(self.wpm)).round() as usize;
+        let amplitude = f64::from(i16::MAX) / 2.0;
+
+        let mut samples = Vec::new();
+        for signal in timing(message)? {
+            let (on, units) = match signal {
+                Signal::On(units) => (true, units),
+                Signal::Off(units) => (false, units),
+            };
+
+            for _ in 0..units * per_unit {
+                let sample = if on {
+                    let t = samples.len() as f64 / f64::from(self.sample_rate);
+                    (amplitude * (2.0 * PI * self.frequency * t).sin()) as i16
+                } else {
+                    0
+                };
+                samples.push(sample);
+            }
+        }
+
+        Ok(samples)
+    }
+
+    /// The tone as a `message` is sent, as the bytes of a mono 16-bit PCM WAV file.
+    ///
+    /// # Errors
+    /// * As for `Morse::encode()`.
+    ///
+    pub fn wav(&self, message: &str) -> Result<Vec<u8>, &'static str> {
+        let samples = self.samples(message)?;
+        let data_len = (samples.len() * 2) as u32;
+
+        let mut wav = Vec::with_capacity(44 + samples.len() * 2);
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes()); // The length of the format chunk
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&1u16.to_le_bytes()); // Mono
+        wav.extend_from_slice(&self.sample_rate.to_le_bytes());
+        wav.extend_from_slice(&(self.sample_rate * 2).to_le_bytes()); // Bytes per second
+        wav.extend_from_slice(&2u16.to_le_bytes()); // Bytes per sample
+        wav.extend_from_slice(&16u16.to_le_bytes()); // Bits per sample
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        for sample in samples {
+            wav.extend_from_slice(&sample.to_le_bytes());
+        }
+
+        Ok(wav)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_message() {
+        assert_eq!(
+            "-- --- .-. ... . / -.-. --- -.. .",
+            encode("Morse  code").unwrap()
+        );
+        assert!(encode("Snowman ☃").is_err());
+    }
+
+    #[test]
+    fn decode_message() {
+        assert_eq!(
+            "MORSE CODE",
+            decode("-- ---  .-. ... . / -.-. --- -.. .").unwrap()
+        );
+        assert!(decode("........-").is_err());
+    }
+
+    #[test]
+    fn prosigns() {
+        assert_eq!("...-.-", encode("<sk>").unwrap());
+        assert_eq!("CQ <SK>", decode("-.-. --.- / ...-.-").unwrap());
+
+        // A sequence shared with a character decodes as the character
+        assert_eq!("=", decode("-...-").unwrap());
+        assert!(encode("<XY>").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "audio")]
+    fn tone_timing() {
+        let tone = Tone::new();
+        assert_eq!(Duration::from_millis(60), tone.unit());
+
+        // E E is a dot, a gap between words, then a dot
+        let durations = tone.durations("E E").unwrap();
+        assert_eq!(
+            vec![
+                (true, Duration::from_millis(60)),
+                (false, Duration::from_millis(420)),
+                (true, Duration::from_millis(60)),
+            ],
+            durations
+        );
+        assert_eq!(9 * 480, tone.samples("E E").unwrap().len());
+    }
+
+    #[test]
+    #[cfg(feature = "audio")]
+    fn wav_header() {
+        let wav = Tone::new().sample_rate(4000).wav("T").unwrap();
+
+        assert_eq!(44 + 3 * 240 * 2, wav.len());
+        assert_eq!(b"WAVE", &wav[8..12]);
+        assert_eq!(4000u32.to_le_bytes(), wav[24..28]);
+        assert!(Tone::new().wav("☃").is_err());
+    }
+
+    #[test]
+    fn custom_symbols() {
+        let emoji = Morse::new().dot("🔴").dash("🟥🟥").letter_separator("⬜");
+        let code = emoji.encode("Sos").unwrap();
+
+        assert_eq!("🔴🔴🔴⬜🟥🟥🟥🟥🟥🟥⬜🔴🔴🔴", code);
+        assert_eq!("SOS", emoji.decode(&code).unwrap());
+        assert!(emoji.decode("🔴🟥").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn indistinct_symbols() {
+        Morse::new().dot("x").dash("x").encode("e").unwrap();
+    }
+
+    #[test]
+    fn timing_of_prosigns() {
+        assert_eq!(
+            9,
+            timing("<SOS>")
+                .unwrap()
+                .iter()
+                .filter(|s| matches!(s, Signal::On(_)))
+                .count()
+        );
+        assert!(timing("☃").is_err());
+    }
+}
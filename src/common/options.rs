@@ -0,0 +1,135 @@
+//! Gathers the options of an encryption that are common to every cipher.
+//!
+//! An `EncryptOptions` is given to the `encrypt_with()` method of `Cipher`, which applies the
+//! options around the cipher's own `encrypt()`. The options are applied in this order:
+//!
+//! 1. The `ForeignCharPolicy` is applied to the message.
+//! 2. The message is padded to a whole number of groups, if a padding character is set.
+//! 3. The message is encrypted by the cipher.
+//! 4. The `CasePolicy` is applied to the ciphertext.
+//! 5. The ciphertext is laid out by the `Grouping`, if one is set.
+//!
+use super::grouping::Grouping;
+use super::policy::{CasePolicy, ForeignCharPolicy};
+
+/// The options of an encryption, as given to the `encrypt_with()` method of `Cipher`.
+///
+/// This struct is created by the `new()` method, and configured by its builder methods. With none
+/// of the builder methods called, the ciphertext is the same as that given by `encrypt()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EncryptOptions {
+    case: CasePolicy,
+    foreign_chars: ForeignCharPolicy,
+    grouping: Option<Grouping>,
+    padding: Option<char>,
+}
+
+impl EncryptOptions {
+    /// Initialise the options of an encryption that leaves the message and its ciphertext as
+    /// they are.
+    ///
+    pub fn new() -> EncryptOptions {
+        EncryptOptions::default()
+    }
+
+    /// Set the case of the ciphertext.
+    ///
+    pub fn case(mut self, case: CasePolicy) -> EncryptOptions {
+        self.case = case;
+        self
+    }
+
+    /// Set what happens to the characters of the message that are foreign to the cipher.
+    ///
+    pub fn foreign_chars(mut self, policy: ForeignCharPolicy) -> EncryptOptions {
+        self.foreign_chars = policy;
+        self
+    }
+
+    /// Lay out the ciphertext in groups.
+    ///
+    pub fn grouping(mut self, grouping: Grouping) -> EncryptOptions {
+        self.grouping = Some(grouping);
+        self
+    }
+
+    /// Pad the message with a `padding` character before it is encrypted, so that it fills a
+    /// whole number of groups. The groups are those of the grouping, or of five characters if no
+    /// grouping is set.
+    ///
+    pub fn padding(mut self, padding: char) -> EncryptOptions {
+        self.padding = Some(padding);
+        self
+    }
+
+    /// Apply the options to a `message`, before it is given to the cipher.
+    ///
+    pub(crate) fn prepare<F>(&self, message: &str, is_foreign: F) -> Result<String, &'static str>
+    where
+        F: Fn(char) -> bool,
+    {
+        let message = self.foreign_chars.apply(message, is_foreign)?;
+
+        Ok(match self.padding {
+            Some(padding) => self.grouping.unwrap_or_default().pad(&message, padding),
+            None => message,
+        })
+    }
+
+    /// Apply the options to a `ciphertext`, after it is returned by the cipher.
+    ///
+    pub(crate) fn finish(&self, ciphertext: &str) -> String {
+        let ciphertext = self.case.apply(ciphertext);
+
+        match self.grouping {
+            Some(grouping) => grouping.apply(&ciphertext),
+            None => ciphertext,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Caesar, Cipher};
+
+    #[test]
+    fn default_matches_encrypt() {
+        let c = Caesar::new(3);
+        let message = "Attack at dawn!";
+
+        assert_eq!(
+            c.encrypt(message),
+            c.encrypt_with(message, &EncryptOptions::new())
+        );
+    }
+
+    #[test]
+    fn all_options() {
+        let c = Caesar::new(3);
+        let opts = EncryptOptions::new()
+            .case(CasePolicy::Upper)
+            .foreign_chars(ForeignCharPolicy::Strip)
+            .grouping(Grouping::new().size(4))
+            .padding('x');
+
+        assert_eq!(
+            "DWWD FNDW GDZQ",
+            c.encrypt_with("Attack at dawn!", &opts).unwrap()
+        );
+    }
+
+    #[test]
+    fn padding_without_grouping() {
+        let c = Caesar::new(3);
+        let opts = EncryptOptions::new().padding('x');
+
+        assert_eq!("Dwwdfnaaaa", c.encrypt_with("Attack", &opts).unwrap());
+    }
+
+    #[test]
+    fn rejected_foreign_chars() {
+        let opts = EncryptOptions::new().foreign_chars(ForeignCharPolicy::Reject);
+        assert!(Caesar::new(3).encrypt_with("Attack!", &opts).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+//! Shares the encryption of a long message across threads.
+//!
+//! The ciphers that substitute each character of a message on its own, without any state carried
+//! from one character to the next, give the same result whether a message is encrypted whole or
+//! in pieces. Such a message can be split into chunks (on the boundaries of its characters), each
+//! chunk encrypted on the threads of the global `rayon` thread pool, and the results joined back
+//! together in order.
+//!
+use rayon::prelude::*;
+
+/// The number of bytes of a message in each chunk, besides the last.
+const CHUNK_SIZE: usize = 1 << 16;
+
+/// Apply a `substitute` function to the chunks of a `text` in parallel, joining the results in
+/// order.
+///
+/// Returns the first `Err` of a chunk, if any.
+///
+pub(crate) fn map_chunks<F>(text: &str, substitute: F) -> Result<String, &'static str>
+where
+    F: Fn(&str) -> Result<String, &'static str> + Sync,
+{
+    Ok(chunks(text, CHUNK_SIZE)
+        .par_iter()
+        .map(|chunk| substitute(chunk))
+        .collect::<Result<Vec<String>, &'static str>>()?
+        .concat())
+}
+
+/// Split a `text` into chunks of about `size` bytes, without splitting any of its characters.
+///
+fn chunks(text: &str, size: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let mut end = size.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end += 1;
+        }
+
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_char_boundaries() {
+        let text = "a🗡️bcé";
+        let split = chunks(text, 2);
+
+        assert_eq!(text, split.concat());
+        assert_eq!(vec!["a🗡", "\u{fe0f}", "bc", "é"], split);
+    }
+
+    #[test]
+    fn joins_in_order() {
+        let text = "abc".repeat(CHUNK_SIZE);
+        let upper = map_chunks(&text, |chunk| Ok(chunk.to_uppercase())).unwrap();
+
+        assert_eq!(text.to_uppercase(), upper);
+        assert!(map_chunks(&text, |_| Err("Failed.")).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+//! Policies for the characters of a message that a cipher does not encipher, and for the case of
+//! the ciphertext.
+//!
+//! Each cipher has its own rule for such characters. Most substitution ciphers pass punctuation
+//! and whitespace through as-is, while others (such as the Hill cipher) reject a message that
+//! contains any. A `ForeignCharPolicy` lets the caller choose what happens to them instead, with
+//! the `encrypt_with_policy()` method of `Cipher`.
+//!
+
+/// What to do with the characters of a message that are foreign to a cipher.
+///
+/// The characters that are foreign to a cipher are those for which its `is_foreign()` method
+/// returns `true`; by default these are the characters outside of `a-z` and `A-Z`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ForeignCharPolicy {
+    /// Give the message to the cipher unchanged, so that the cipher's own rule applies. This is
+    /// the default.
+    #[default]
+    PassThrough,
+    /// Remove the foreign characters from the message before it is encrypted.
+    Strip,
+    /// Return an `Err` if the message contains a foreign character.
+    Reject,
+}
+
+impl ForeignCharPolicy {
+    /// Apply the policy to a `message`, where `is_foreign` tells whether a character is foreign.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::ForeignCharPolicy;
+    ///
+    /// let is_foreign = |c: char| !c.is_ascii_alphabetic();
+    ///
+    /// assert_eq!(Ok(String::from("Attackatdawn")),
+    ///     ForeignCharPolicy::Strip.apply("Attack at dawn!", is_foreign));
+    /// assert!(ForeignCharPolicy::Reject.apply("Attack at dawn!", is_foreign).is_err());
+    /// ```
+    ///
+    pub fn apply<F>(self, message: &str, is_foreign: F) -> Result<String, &'static str>
+    where
+        F: Fn(char) -> bool,
+    {
+        match self {
+            ForeignCharPolicy::PassThrough => Ok(message.to_string()),
+            ForeignCharPolicy::Strip => Ok(message.chars().filter(|&c| !is_foreign(c)).collect()),
+            ForeignCharPolicy::Reject => {
+                if message.chars().any(is_foreign) {
+                    return Err("Message contains a character that is foreign to the cipher.");
+                }
+
+                Ok(message.to_string())
+            }
+        }
+    }
+}
+
+/// The case of the letters of a ciphertext.
+///
+/// Most ciphers keep the case of each letter of the message, although some (such as the Bifid
+/// cipher) give ciphertext of a single case.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CasePolicy {
+    /// Leave the ciphertext in the case that the cipher gave it. This is the default.
+    #[default]
+    Preserve,
+    /// Convert the ciphertext to uppercase, as it was traditionally written.
+    Upper,
+    /// Convert the ciphertext to lowercase.
+    Lower,
+}
+
+impl CasePolicy {
+    /// Apply the policy to a `text`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::CasePolicy;
+    ///
+    /// assert_eq!("DWWDFN", CasePolicy::Upper.apply("Dwwdfn"));
+    /// assert_eq!("Dwwdfn", CasePolicy::Preserve.apply("Dwwdfn"));
+    /// ```
+    ///
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            CasePolicy::Preserve => text.to_string(),
+            CasePolicy::Upper => text.to_uppercase(),
+            CasePolicy::Lower => text.to_lowercase(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pass_through() {
+        assert_eq!(
+            Ok(String::from("Attack at dawn!")),
+            ForeignCharPolicy::default().apply("Attack at dawn!", |c| !c.is_ascii_alphabetic())
+        );
+    }
+
+    #[test]
+    fn reject_clean_message() {
+        assert_eq!(
+            Ok(String::from("Attack")),
+            ForeignCharPolicy::Reject.apply("Attack", |c| !c.is_ascii_alphabetic())
+        );
+    }
+}
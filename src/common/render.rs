@@ -0,0 +1,45 @@
+//! Draws the tables used by the ciphers as plain text.
+//!
+use std::iter;
+
+/// Draw a grid of `rows`, each labelled by a character, beneath a header of `column_labels`.
+///
+/// For example, a grid with the columns `A B C` would be drawn as
+///
+/// ```md,no_run
+///   | A B C
+/// --+------
+/// A | a b c
+/// B | d e f
+/// ```
+///
+pub(crate) fn labelled_grid(column_labels: &[char], rows: &[(char, Vec<char>)]) -> String {
+    let spaced = |cells: &mut dyn Iterator<Item = &char>| -> String {
+        cells.map(|c| format!(" {}", c)).collect()
+    };
+
+    iter::once(format!("  |{}", spaced(&mut column_labels.iter())))
+        .chain(iter::once(format!(
+            "--+{}",
+            "-".repeat(column_labels.len() * 2)
+        )))
+        .chain(
+            rows.iter()
+                .map(|(label, cells)| format!("{} |{}", label, spaced(&mut cells.iter()))),
+        )
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draws_grid() {
+        assert_eq!(
+            "  | A B\n--+----\nX | a b\nY | c d",
+            labelled_grid(&['A', 'B'], &[('X', vec!['a', 'b']), ('Y', vec!['c', 'd'])])
+        );
+    }
+}
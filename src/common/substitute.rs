@@ -12,23 +12,64 @@ use super::alphabet::Alphabet;
 pub fn shift_substitution<F>(text: &str, calc_index: F) -> String
 where
     F: Fn(usize) -> usize,
+{
+    alphabet_shift_substitution(text, &alphabet::STANDARD, calc_index)
+}
+
+/// Performs a shift substitution of the characters within a piece of text that belong to the
+/// alphabet `alpha`, based on the index of them within that alphabet.
+///
+/// This substitution is defined by the closure `calc_index(ti)`.
+///     * ti = the index of the character to shift
+///     * note; the closure should shift the value set within the bounds of `alpha`
+pub fn alphabet_shift_substitution<T, F>(text: &str, alpha: &T, calc_index: F) -> String
+where
+    T: Alphabet,
+    F: Fn(usize) -> usize,
 {
     let mut s_text = String::new();
     for c in text.chars() {
         //Find the index of the character in the alphabet (if it exists in there)
-        let pos = alphabet::STANDARD.find_position(c);
+        let pos = alpha.find_position(c);
         match pos {
             Some(pos) => {
                 let si = calc_index(pos); //Calculate substitution index
-                s_text.push(alphabet::STANDARD.get_letter(si, c.is_uppercase()));
+                s_text.push(alpha.get_letter(si, c.is_uppercase()));
             }
-            None => s_text.push(c), //Push non-alphabetic chars 'as-is'
+            None => s_text.push(c), //Push chars outside of the alphabet 'as-is'
         }
     }
 
     s_text
 }
 
+/// Performs a shift substitution of a single character `c`, if it is a letter of the standard
+/// alphabet, based on its index within the alphabet. Other characters are returned 'as-is'.
+///
+/// This substitution is defined by the closure `calc_index(ti)`, as for `shift_substitution`.
+pub fn shift_char<F>(c: char, calc_index: F) -> char
+where
+    F: Fn(usize) -> usize,
+{
+    alphabet_shift_char(c, &alphabet::STANDARD, calc_index)
+}
+
+/// Performs a shift substitution of a single character `c`, if it belongs to the alphabet `alpha`,
+/// based on its index within that alphabet. Other characters are returned 'as-is'.
+///
+/// This substitution is defined by the closure `calc_index(ti)`, as for
+/// `alphabet_shift_substitution`.
+pub fn alphabet_shift_char<T, F>(c: char, alpha: &T, calc_index: F) -> char
+where
+    T: Alphabet,
+    F: Fn(usize) -> usize,
+{
+    match alpha.find_position(c) {
+        Some(pos) => alpha.get_letter(calc_index(pos), c.is_uppercase()),
+        None => c,
+    }
+}
+
 /// Performs a poly-substitution on a piece of text based on the index of its characters
 /// (within the alphabet) and the keystream `k`.
 ///
@@ -36,22 +77,41 @@ where
 /// Where:
 ///     * ti = the index of the character to shift
 ///     * ki = the index of the next key character in the stream
-pub fn key_substitution<F>(text: &str, keystream: &str, calc_index: F) -> String
+pub fn key_substitution<K, F>(text: &str, keystream: K, calc_index: F) -> String
+where
+    K: IntoIterator<Item = char>,
+    F: Fn(usize, usize) -> usize,
+{
+    alphabet_key_substitution(text, keystream, &alphabet::STANDARD, calc_index)
+}
+
+/// Performs a poly-substitution on the characters of a piece of text that belong to the alphabet
+/// `alpha`, based on their index (within that alphabet) and the keystream `k`.
+///
+/// This substitution is defined by the closure `calc_index(ti, ki)`, as for `key_substitution`.
+pub fn alphabet_key_substitution<K, T, F>(
+    text: &str,
+    keystream: K,
+    alpha: &T,
+    calc_index: F,
+) -> String
 where
+    K: IntoIterator<Item = char>,
+    T: Alphabet,
     F: Fn(usize, usize) -> usize,
 {
     let mut s_text = String::new();
-    let mut keystream_iter = keystream.chars().peekable();
+    let mut keystream_iter = keystream.into_iter().peekable();
     for tc in text.chars() {
         //Find the index of the character in the alphabet (if it exists in there)
-        let tpos = alphabet::STANDARD.find_position(tc);
+        let tpos = alpha.find_position(tc);
         match tpos {
             Some(ti) => {
                 if let Some(kc) = keystream_iter.peek() {
-                    if let Some(ki) = alphabet::STANDARD.find_position(*kc) {
+                    if let Some(ki) = alpha.find_position(*kc) {
                         //Calculate the index and retrieve the letter to substitute
                         let si = calc_index(ti, ki);
-                        s_text.push(alphabet::STANDARD.get_letter(si, tc.is_uppercase()));
+                        s_text.push(alpha.get_letter(si, tc.is_uppercase()));
                     } else {
                         panic!("Keystream contains a non-alphabetic symbol.");
                     }
@@ -66,3 +126,29 @@ where
 
     s_text
 }
+
+/// Performs a poly-substitution through a tableau, where each row pairs the `plain` alphabet with
+/// the `cipher` alphabet slid along by some number of positions.
+///
+/// Both alphabets hold the indices (within the standard alphabet) of their letters in order. The
+/// row used for each character is defined by the closure `calc_shift(ki)`.
+/// Where:
+///     * ki = the index of the next key character in the stream
+///
+/// Decryption is performed by swapping the alphabets and negating the shift.
+pub fn tableau_substitution<K, F>(
+    text: &str,
+    keystream: K,
+    plain: &[usize],
+    cipher: &[usize],
+    calc_shift: F,
+) -> String
+where
+    K: IntoIterator<Item = char>,
+    F: Fn(usize) -> isize,
+{
+    key_substitution(text, keystream, |ti, ki| {
+        let column = plain.iter().position(|&p| p == ti).unwrap();
+        cipher[alphabet::STANDARD.modulo(column as isize + calc_shift(ki))]
+    })
+}
@@ -0,0 +1,380 @@
+//! DRYAD is a paper cipher used by the armed forces of the United States and NATO to
+//! authenticate stations, and to encrypt short numeric messages such as map grid references.
+//!
+//! A DRYAD sheet has 25 rows labelled with the letters `A-Y`. Each row holds the same 25 letters
+//! in a scrambled order, divided into ten groups that are headed by the digits `0-9`. The groups
+//! for the digits `0`, `1`, `2` and `4` hold extra letters, as these digits are the most common
+//! in numeric messages:
+//!
+//! ```text
+//!      0    1   2   3  4   5  6  7  8  9
+//! A  SWBN  QGE LCR  YK DAF  OP TM XH IV JU
+//! ```
+//!
+//! To encrypt, the sender selects a row and replaces each digit of the message with any of the
+//! letters of its group within that row. Each sheet was only valid for a short period, and was
+//! distributed in secret.
+//!
+use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// The labels of the rows of a DRYAD sheet, which are also the letters within each row.
+pub const LETTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXY";
+
+/// The number of letters within each row of a DRYAD sheet that are given to each of the digits
+/// `0-9`.
+pub const GROUP_SIZES: [usize; 10] = [4, 3, 3, 2, 3, 2, 2, 2, 2, 2];
+
+/// A DRYAD cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dryad {
+    pad: Vec<Vec<char>>,
+    row: usize,
+}
+
+/// The key of a DRYAD cipher.
+///
+/// This struct is given to the `new()` method of `Dryad`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DryadKey {
+    /// The 25 rows of the DRYAD sheet, from `A` to `Y`. Each row contains the letters `A-Y`
+    /// in a scrambled order, and is divided into groups for the digits `0-9` according to
+    /// `GROUP_SIZES`. A pad can be generated with `Dryad::generate_pad()`.
+    pub pad: Vec<String>,
+    /// The label of the row that is used to encrypt and decrypt.
+    pub row: char,
+}
+
+impl Cipher for Dryad {
+    type Key = DryadKey;
+    type Algorithm = Dryad;
+
+    /// Initialise a DRYAD cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `DryadKey`.
+    ///
+    /// # Panics
+    /// * The `pad` does not have 25 rows.
+    /// * A row of the `pad` does not contain each of the letters `A-Y` exactly once.
+    /// * The `row` is not one of the letters `A-Y`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Dryad, DryadKey};
+    ///
+    /// let d = Dryad::new(DryadKey { pad: Dryad::generate_pad(1944), row: 'C' });
+    /// ```
+    ///
+    fn new(key: DryadKey) -> Dryad {
+        let DryadKey { pad, row } = key;
+        if pad.len() != LETTERS.len() {
+            panic!("The pad must have 25 rows.");
+        }
+
+        let pad: Vec<Vec<char>> = pad
+            .iter()
+            .map(|r| r.chars().map(|c| c.to_ascii_uppercase()).collect())
+            .collect();
+        for r in &pad {
+            if r.len() != LETTERS.len() || !LETTERS.chars().all(|c| r.contains(&c)) {
+                panic!("Each row of the pad must contain each of the letters A-Y exactly once.");
+            }
+        }
+
+        Dryad {
+            pad,
+            row: row_index(row),
+        }
+    }
+
+    /// Encrypt a message using a DRYAD cipher.
+    ///
+    /// Each digit is replaced with a letter of its group, chosen at random. Whitespace within the
+    /// message is not preserved.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Dryad, DryadKey};
+    ///
+    /// let d = Dryad::new(DryadKey { pad: Dryad::generate_pad(1944), row: 'C' });
+    /// let c = d.encrypt("4815 1623").unwrap();
+    ///
+    /// assert_eq!(8, c.len());
+    /// assert_eq!("48151623", d.decrypt(&c).unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.validate_message(message)?;
+
+        let groups = self.groups();
+        let mut rng = rand::thread_rng();
+        Ok(message
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .map(|d| *groups[d as usize].choose(&mut rng).unwrap())
+            .collect())
+    }
+
+    /// Decrypt a message using a DRYAD cipher.
+    ///
+    /// Whitespace within the ciphertext is ignored.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Dryad, DryadKey};
+    ///
+    /// let pad: Vec<String> = (0..25).map(|_| String::from("SWBNQGELCRYKDAFOPTMXHIVJU")).collect();
+    /// let d = Dryad::new(DryadKey { pad, row: 'A' });
+    ///
+    /// assert_eq!("0123456789", d.decrypt("SQLKDPMHVU").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let groups = self.groups();
+
+        ciphertext
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| {
+                groups
+                    .iter()
+                    .position(|g| g.contains(&c.to_ascii_uppercase()))
+                    .map(|d| std::char::from_digit(d as u32, 10).unwrap())
+                    .ok_or("Ciphertext contains a symbol that is not one of the letters A-Y.")
+            })
+            .collect()
+    }
+
+    /// Check that a message can be encrypted with a DRYAD cipher.
+    ///
+    /// Returns `Err` if the message contains a symbol that is not a digit or whitespace.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Dryad, DryadKey};
+    ///
+    /// let d = Dryad::new(DryadKey { pad: Dryad::generate_pad(1944), row: 'C' });
+    ///
+    /// assert!(d.validate_message("4815 1623").is_ok());
+    /// assert!(d.validate_message("Grid 4815").is_err());
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        CipherError::find(
+            message,
+            "Message contains a symbol that is not a digit or whitespace.",
+            |c| !c.is_whitespace() && !c.is_ascii_digit(),
+        )
+    }
+
+    /// Whether a character is foreign to the cipher, being other than a digit.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        !c.is_ascii_digit()
+    }
+}
+
+impl Dryad {
+    /// Generate the 25 rows of a DRYAD sheet from a `seed`, where each row contains the letters
+    /// `A-Y` in a scrambled order. The same seed will always generate the same pad.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::Dryad;
+    ///
+    /// let pad = Dryad::generate_pad(1944);
+    ///
+    /// assert_eq!(25, pad.len());
+    /// assert_eq!(pad, Dryad::generate_pad(1944));
+    /// ```
+    ///
+    pub fn generate_pad(seed: u64) -> Vec<String> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut letters: Vec<char> = LETTERS.chars().collect();
+
+        (0..LETTERS.len())
+            .map(|_| {
+                letters.shuffle(&mut rng);
+                letters.iter().collect()
+            })
+            .collect()
+    }
+
+    /// Select the `row` of the pad that is used to encrypt and decrypt.
+    ///
+    /// # Panics
+    /// * The `row` is not one of the letters `A-Y`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Dryad, DryadKey};
+    ///
+    /// let mut d = Dryad::new(DryadKey { pad: Dryad::generate_pad(1944), row: 'C' });
+    /// d.select_row('Q');
+    /// assert_eq!('Q', d.row());
+    /// ```
+    ///
+    pub fn select_row(&mut self, row: char) {
+        self.row = row_index(row);
+    }
+
+    /// The label of the row of the pad that is used to encrypt and decrypt.
+    ///
+    pub fn row(&self) -> char {
+        LETTERS.chars().nth(self.row).unwrap()
+    }
+
+    /// The letters of the selected row, grouped by the digit they represent.
+    ///
+    fn groups(&self) -> Vec<&[char]> {
+        let mut start = 0;
+        GROUP_SIZES
+            .iter()
+            .map(|&size| {
+                start += size;
+                &self.pad[self.row][start - size..start]
+            })
+            .collect()
+    }
+}
+
+/// The index of the row of the pad with the label `row`.
+///
+fn row_index(row: char) -> usize {
+    LETTERS
+        .find(row.to_ascii_uppercase())
+        .expect("The row must be one of the letters A-Y.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROW: &str = "SWBNQGELCRYKDAFOPTMXHIVJU";
+
+    fn pad() -> Vec<String> {
+        (0..25)
+            .map(|i| format!("{}{}", &ROW[i..], &ROW[..i]))
+            .collect()
+    }
+
+    #[test]
+    fn encrypt_test() {
+        let d = Dryad::new(DryadKey {
+            pad: pad(),
+            row: 'A',
+        });
+        let c: Vec<char> = d.encrypt("0 3 9").unwrap().chars().collect();
+
+        assert_eq!(3, c.len());
+        assert!("SWBN".contains(c[0]));
+        assert!("YK".contains(c[1]));
+        assert!("JU".contains(c[2]));
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let d = Dryad::new(DryadKey {
+            pad: pad(),
+            row: 'A',
+        });
+        assert_eq!("0123456789", d.decrypt("sqlkd pmhvu").unwrap());
+    }
+
+    #[test]
+    fn select_row() {
+        let mut d = Dryad::new(DryadKey {
+            pad: pad(),
+            row: 'A',
+        });
+        d.select_row('b');
+
+        // The second row begins with the letters WBNQ
+        assert_eq!('B', d.row());
+        assert_eq!("0000", d.decrypt("WBNQ").unwrap());
+        assert_eq!("9", d.decrypt("S").unwrap());
+    }
+
+    #[test]
+    fn generated_pad() {
+        let pad = Dryad::generate_pad(7);
+
+        assert_ne!(pad, Dryad::generate_pad(8));
+        for row in &pad {
+            let mut letters: Vec<char> = row.chars().collect();
+            letters.sort();
+            assert_eq!(LETTERS, letters.into_iter().collect::<String>());
+        }
+    }
+
+    #[test]
+    fn generated_pad_round_trip() {
+        for row in LETTERS.chars() {
+            let d = Dryad::new(DryadKey {
+                pad: Dryad::generate_pad(1944),
+                row,
+            });
+            let m = "0123456789";
+
+            assert_eq!(m, d.decrypt(&d.encrypt(m).unwrap()).unwrap());
+        }
+    }
+
+    #[test]
+    fn invalid_ciphertext() {
+        let d = Dryad::new(DryadKey {
+            pad: pad(),
+            row: 'A',
+        });
+        assert!(d.decrypt("SQZ").is_err());
+    }
+
+    #[test]
+    fn with_utf8() {
+        let d = Dryad::new(DryadKey {
+            pad: pad(),
+            row: 'A',
+        });
+        assert!(d.encrypt("12 🗡️ 34").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn short_pad() {
+        Dryad::new(DryadKey {
+            pad: pad()[1..].to_vec(),
+            row: 'A',
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn row_with_repeated_letter() {
+        let mut pad = pad();
+        pad[3] = ROW.replace('U', "S");
+        Dryad::new(DryadKey { pad, row: 'A' });
+    }
+
+    #[test]
+    #[should_panic]
+    fn unknown_row() {
+        Dryad::new(DryadKey {
+            pad: pad(),
+            row: 'Z',
+        });
+    }
+}
@@ -7,6 +7,8 @@
 //!
 //!
 use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
+use crate::common::morse::Morse;
 use crate::common::{alphabet, keygen, morse};
 
 // The fractionated morse trigraph 'alphabet'. Each sequence represents a letter of the alphabet.
@@ -18,6 +20,7 @@ const TRIGRAPH_ALPHABET: [&str; 26] = [
 /// A Fractionated Morse cipher.
 ///
 /// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
 pub struct FractionatedMorse {
     keyed_alphabet: String,
 }
@@ -26,19 +29,11 @@ impl Cipher for FractionatedMorse {
     type Key = String;
     type Algorithm = FractionatedMorse;
 
-    /// Initialise a Fractionated Morse cipher given a specific key.
-    ///
-    /// # Panics
-    /// * The `key` is empty.
-    /// * The `key` contains a non-alphabetic symbol.
+    /// Initialise a Fractionated Morse cipher given a specific key,
+    /// as by `FractionatedMorse::new()`.
     ///
     fn new(key: String) -> FractionatedMorse {
-        if key.is_empty() {
-            panic!("Key is empty.");
-        }
-
-        let keyed_alphabet = keygen::keyed_alphabet(&key, &alphabet::STANDARD, true);
-        FractionatedMorse { keyed_alphabet }
+        FractionatedMorse::new(key)
     }
 
     /// Encrypt a message using a Fractionated Morse cipher.
@@ -54,7 +49,7 @@ impl Cipher for FractionatedMorse {
     /// ```
     /// use cipher_crypt::{Cipher, FractionatedMorse};
     ///
-    /// let fm = FractionatedMorse::new(String::from("key"));;
+    /// let fm = FractionatedMorse::new("key");;
     /// assert_eq!("CPSUJISWHSSPFANR", fm.encrypt("AttackAtDawn!").unwrap());
     /// ```
     ///
@@ -80,7 +75,8 @@ impl Cipher for FractionatedMorse {
         //   (4) The alphabet `alphbetcdfgijkmnoqrsuvwxyz` is produced.
         //   (5) 0(a), 6(t), 19(s), 2(p)
         //   (6) The ciphertext `atsphcmr` is produced.
-        let mut morse = FractionatedMorse::encode_to_morse(message)?;
+        self.validate_message(message)?;
+        let mut morse = FractionatedMorse::encode_to_morse(message);
 
         //Pad the morse so that it can be interpreted properly as a fractionated message
         FractionatedMorse::pad(&mut morse);
@@ -100,7 +96,7 @@ impl Cipher for FractionatedMorse {
     /// ```
     /// use cipher_crypt::{Cipher, FractionatedMorse};
     ///
-    /// let fm = FractionatedMorse::new(String::from("key"));;
+    /// let fm = FractionatedMorse::new("key");;
     /// assert_eq!("ATTACKATDAWN!", fm.decrypt("cpsujiswhsspfanr").unwrap());
     /// ```
     ///
@@ -127,28 +123,77 @@ impl Cipher for FractionatedMorse {
         let seq = FractionatedMorse::decrypt_morse(&self.keyed_alphabet, cipher_text)?;
         FractionatedMorse::decode_morse(&seq)
     }
+
+    /// Check that a message can be encrypted using a Fractionated Morse cipher.
+    ///
+    /// Returns `Err` if the message contains a symbol that cannot be represented in Morse code.
+    /// The supported characters are `a-z`, `A-Z`, `0-9` and the special characters
+    /// `@ ( ) . , : ' " ! ? - ; =`.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, CipherError, FractionatedMorse};
+    ///
+    /// let fm = FractionatedMorse::new("key");;
+    /// assert!(fm.validate_message("AttackAtDawn!").is_ok());
+    /// assert!(fm.validate_message("Attack at dawn!").is_err());
+    ///
+    /// let e = fm.validate_message("AttackAtDawn&Dusk").unwrap_err();
+    /// assert_eq!(
+    ///     CipherError::Symbol {
+    ///         symbol: '&',
+    ///         offset: 12,
+    ///         reason: "Unsupported character detected in message.",
+    ///     },
+    ///     e
+    /// );
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        CipherError::find(message, "Unsupported character detected in message.", |c| {
+            morse::encode_character(c).is_none()
+        })
+    }
+
+    /// Whether a character is foreign to the cipher, having no Morse code.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        morse::encode_character(c).is_none()
+    }
 }
 
 impl FractionatedMorse {
-    /// Takes a message and converts it to Morse code, using the character `|` as a separator.
-    /// The transposed sequence is ended with two separators `||`. This function returns `Err`
-    /// if an unsupported symbol is present. The support characters are `a-z`, `A-Z`, `0-9` and
-    /// the special characters `@ ( ) . , : ' " ! ? - ; =`.
-    fn encode_to_morse(message: &str) -> Result<String, &'static str> {
-        if message
-            .chars()
-            .any(|c| morse::encode_character(c).is_none())
-        {
-            return Err("Unsupported character detected in message.");
+    /// Initialise a Fractionated Morse cipher given a specific key.
+    ///
+    /// # Panics
+    /// * The `key` is empty.
+    /// * The `key` contains a non-alphabetic symbol.
+    ///
+    pub fn new(key: impl Into<String>) -> FractionatedMorse {
+        let key = key.into();
+        if key.is_empty() {
+            panic!("Key is empty.");
         }
 
-        let mut morse: String = message
-            .chars()
-            .map(|c| format!("{}{}", morse::encode_character(c).unwrap(), '|'))
-            .collect();
+        let keyed_alphabet = keygen::keyed_alphabet(&key, &alphabet::STANDARD, true);
+        FractionatedMorse { keyed_alphabet }
+    }
 
-        morse.push('|'); // Finish the Morse message with a double separator `||`.
-        Ok(morse)
+    /// Takes a message and converts it to Morse code, using the character `|` as a separator.
+    /// The transposed sequence is ended with two separators `||`. The message is expected to
+    /// have already been checked by `validate_message`.
+    fn encode_to_morse(message: &str) -> String {
+        let mut morse = Morse::new().letter_separator("|").encode(message).unwrap();
+
+        // Follow the last character with a separator, and finish the Morse message with a double
+        // separator `||`.
+        if !morse.is_empty() {
+            morse.push('|');
+        }
+        morse.push('|');
+        morse
     }
 
     /// Takes a morse sequence and converts it to an alphabetic string using the fractionated
@@ -302,6 +347,31 @@ mod tests {
         assert!(f.encrypt(message).is_err());
     }
 
+    #[test]
+    fn validate_bad_message() {
+        let f = FractionatedMorse::new(String::from("test"));
+        assert!(f.validate_message("Spaces are not supported.").is_err());
+        assert!(f.validate_message("Nospacesarefine.").is_ok());
+    }
+
+    #[test]
+    fn check_bad_message() {
+        let f = FractionatedMorse::new(String::from("test"));
+        let e = f
+            .validate_message("Noñospaces are not supported.")
+            .unwrap_err();
+
+        assert_eq!(
+            CipherError::Symbol {
+                symbol: 'ñ',
+                offset: 2,
+                reason: "Unsupported character detected in message.",
+            },
+            e
+        );
+        assert_eq!(Ok(()), f.validate_message("Nospacesarefine."));
+    }
+
     #[test]
     fn decrypt_bad_message() {
         let message = "badmessagefordecryption";
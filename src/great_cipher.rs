@@ -0,0 +1,392 @@
+//! The Great Cipher (Grand Chiffre) was a nomenclator developed by Antoine and Bonaventure
+//! Rossignol for the court of Louis XIV. It was so successful that it remained unbroken for two
+//! centuries after it fell out of use, until it was solved by Étienne Bazeries in the 1890s.
+//!
+//! Rather than replacing single letters, the Great Cipher replaced the syllables of French with
+//! numbers, and gave common syllables several numbers (homophones) to choose from. This hid the
+//! letter frequencies that would otherwise reveal the plaintext.
+//!
+//! In this implementation the message is broken into the longest syllables of the code table
+//! that match it, ignoring case and whitespace, and each syllable is replaced with one of its
+//! numbers chosen at random. The original code table has not survived intact, so the
+//! `DEFAULT_TABLE` is a small demonstration table in the same style.
+//!
+use crate::common::cipher::Cipher;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cell::RefCell;
+
+/// A demonstration code table of the letters and some common syllables of French, with the
+/// numbers (homophones) that each may be replaced with.
+pub const DEFAULT_TABLE: [(&str, &[&str]); 56] = [
+    ("a", &["345", "502", "549"]),
+    ("b", &["147", "472"]),
+    ("c", &["362", "444"]),
+    ("d", &["149", "367"]),
+    ("e", &["402", "499", "547"]),
+    ("f", &["346", "574"]),
+    ("g", &["419", "487"]),
+    ("h", &["284", "447"]),
+    ("i", &["185", "368", "462"]),
+    ("j", &["568"]),
+    ("k", &["154"]),
+    ("l", &["120", "225", "259"]),
+    ("m", &["503", "537"]),
+    ("n", &["138", "218", "599"]),
+    ("o", &["140", "298", "538"]),
+    ("p", &["263", "273"]),
+    ("q", &["288"]),
+    ("r", &["107", "118", "173"]),
+    ("s", &["365", "485", "554"]),
+    ("t", &["372", "475", "496"]),
+    ("u", &["272", "274", "460"]),
+    ("v", &["255", "350"]),
+    ("w", &["191"]),
+    ("x", &["304"]),
+    ("y", &["186"]),
+    ("z", &["528"]),
+    ("ai", &["591"]),
+    ("an", &["262"]),
+    ("ce", &["261"]),
+    ("ch", &["153"]),
+    ("de", &["179", "324"]),
+    ("des", &["536"]),
+    ("du", &["410"]),
+    ("en", &["112", "229"]),
+    ("ent", &["124", "332"]),
+    ("es", &["373", "498"]),
+    ("est", &["358"]),
+    ("et", &["375"]),
+    ("il", &["356"]),
+    ("ion", &["393"]),
+    ("la", &["293", "406"]),
+    ("le", &["269", "530"]),
+    ("les", &["226", "266"]),
+    ("me", &["354"]),
+    ("ne", &["199"]),
+    ("nt", &["232"]),
+    ("on", &["177", "575"]),
+    ("ou", &["171", "468"]),
+    ("par", &["253"]),
+    ("pour", &["339"]),
+    ("qu", &["494", "525"]),
+    ("que", &["174", "392"]),
+    ("re", &["109", "326"]),
+    ("roi", &["430"]),
+    ("se", &["417"]),
+    ("te", &["516"]),
+];
+
+/// A Great Cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GreatCipher {
+    table: Vec<(Vec<char>, Vec<String>)>,
+    rng: RefCell<StdRng>,
+}
+
+impl Cipher for GreatCipher {
+    type Key = Option<Vec<(String, Vec<String>)>>;
+    type Algorithm = GreatCipher;
+
+    /// Initialise a Great Cipher given an optional code table.
+    ///
+    /// Each entry of the code table pairs a syllable with the numbers that it may be replaced
+    /// with. If no table is given, the `DEFAULT_TABLE` is used. The case of the syllables is
+    /// ignored.
+    ///
+    /// # Panics
+    /// * A syllable is empty, contains whitespace, or appears more than once.
+    /// * A syllable does not have any numbers.
+    /// * A number is empty, contains a symbol that is not a digit, or appears more than once.
+    ///
+    fn new(key: Option<Vec<(String, Vec<String>)>>) -> GreatCipher {
+        let table: Vec<(Vec<char>, Vec<String>)> = match key {
+            Some(t) => t
+                .into_iter()
+                .map(|(s, codes)| (s.to_lowercase().chars().collect(), codes))
+                .collect(),
+            None => DEFAULT_TABLE
+                .iter()
+                .map(|(s, codes)| {
+                    (
+                        s.chars().collect(),
+                        codes.iter().map(|c| c.to_string()).collect(),
+                    )
+                })
+                .collect(),
+        };
+
+        for (i, (syllable, codes)) in table.iter().enumerate() {
+            if syllable.is_empty() || syllable.iter().any(|c| c.is_whitespace()) {
+                panic!("A syllable cannot be empty or contain whitespace.");
+            }
+            if table[..i].iter().any(|(s, _)| s == syllable) {
+                panic!("A syllable cannot appear more than once.");
+            }
+            if codes.is_empty() {
+                panic!("Every syllable must have at least one number.");
+            }
+        }
+
+        let codes: Vec<&String> = table.iter().flat_map(|(_, codes)| codes).collect();
+        for (i, code) in codes.iter().enumerate() {
+            if code.is_empty() || !code.chars().all(|c| c.is_ascii_digit()) {
+                panic!("A number of the code table may only contain digits.");
+            }
+            if codes[..i].contains(code) {
+                panic!("A number cannot be used for more than one syllable.");
+            }
+        }
+
+        GreatCipher {
+            table,
+            rng: RefCell::new(StdRng::from_entropy()),
+        }
+    }
+
+    /// Encrypt a message using a Great Cipher.
+    ///
+    /// The numbers of the ciphertext are separated by spaces. Whitespace within the message is
+    /// not preserved.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, GreatCipher};
+    ///
+    /// let g = GreatCipher::new(None);
+    /// let c = g.encrypt("Le roi est mort").unwrap();
+    ///
+    /// // The message is made up of the syllables: le, roi, est, m, o, r, t
+    /// assert_eq!(7, c.split(' ').count());
+    /// assert_eq!("leroiestmort", g.decrypt(&c).unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        let text: Vec<char> = message
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .flat_map(char::to_lowercase)
+            .collect();
+
+        let mut rng = self.rng.borrow_mut();
+        let mut numbers = Vec::new();
+        let mut i = 0;
+        while i < text.len() {
+            let (syllable, codes) = longest_match(
+                &text[i..],
+                self.table.iter().map(|(s, codes)| (s.as_slice(), codes)),
+            )
+            .ok_or("Message contains a syllable that is not in the code table.")?;
+
+            numbers.push(codes[rng.gen_range(0..codes.len())].as_str());
+            i += syllable.len();
+        }
+
+        Ok(numbers.join(" "))
+    }
+
+    /// Decrypt a message using a Great Cipher.
+    ///
+    /// Each group of digits in the ciphertext is broken into the longest numbers of the code
+    /// table that match it, so the numbers may be separated by whitespace or run together. The
+    /// plaintext is lowercase.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, GreatCipher};
+    ///
+    /// let g = GreatCipher::new(None);
+    /// assert_eq!("leroiestmort", g.decrypt("269 430358 503 140 173 475").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let codes: Vec<(Vec<char>, &Vec<char>)> = self
+            .table
+            .iter()
+            .flat_map(|(s, codes)| codes.iter().map(move |c| (c.chars().collect(), s)))
+            .collect();
+
+        let mut message = String::new();
+        for group in ciphertext.split_whitespace() {
+            let digits: Vec<char> = group.chars().collect();
+            let mut i = 0;
+            while i < digits.len() {
+                let (code, syllable) =
+                    longest_match(&digits[i..], codes.iter().map(|(c, s)| (c.as_slice(), *s)))
+                        .ok_or("Ciphertext contains a number that is not in the code table.")?;
+
+                message.extend(syllable.iter());
+                i += code.len();
+            }
+        }
+
+        Ok(message)
+    }
+
+    /// Whether a character is foreign to the cipher, being in none of the syllables of the table.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        !c.to_lowercase()
+            .all(|l| self.table.iter().any(|(syllable, _)| syllable.contains(&l)))
+    }
+}
+
+impl GreatCipher {
+    /// Initialise a Great Cipher, where the numbers are chosen by a random number generator with
+    /// the given `seed`. The same message will always be encrypted in the same way.
+    ///
+    /// # Panics
+    /// * Any of the conditions as stipulated by the `new()` fn.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, GreatCipher};
+    ///
+    /// let g1 = GreatCipher::seeded(None, 1661);
+    /// let g2 = GreatCipher::seeded(None, 1661);
+    /// assert_eq!(g1.encrypt("Le roi est mort").unwrap(), g2.encrypt("Le roi est mort").unwrap());
+    /// ```
+    ///
+    pub fn seeded(key: Option<Vec<(String, Vec<String>)>>, seed: u64) -> GreatCipher {
+        let g = GreatCipher::new(key);
+        g.rng.replace(StdRng::seed_from_u64(seed));
+
+        g
+    }
+}
+
+/// Find the longest of the `candidates` that `text` begins with, returning it along with its
+/// associated value.
+///
+fn longest_match<'a, T>(
+    text: &[char],
+    candidates: impl Iterator<Item = (&'a [char], T)>,
+) -> Option<(&'a [char], T)> {
+    candidates
+        .filter(|(prefix, _)| text.starts_with(prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::policy::ForeignCharPolicy;
+
+    fn table() -> Vec<(String, Vec<String>)> {
+        vec![
+            (String::from("a"), vec![String::from("1")]),
+            (String::from("b"), vec![String::from("2")]),
+            (String::from("ab"), vec![String::from("12")]),
+            (
+                String::from("ba"),
+                vec![String::from("21"), String::from("3")],
+            ),
+        ]
+    }
+
+    #[test]
+    fn encrypt_test() {
+        let g = GreatCipher::new(Some(table()));
+        assert_eq!("12 12 1", g.encrypt("abab a").unwrap());
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let g = GreatCipher::new(Some(table()));
+        assert_eq!("abbaab", g.decrypt("12 3 12").unwrap());
+    }
+
+    #[test]
+    fn decrypt_longest_number() {
+        // "12" is read as a single number, rather than "1" followed by "2"
+        let g = GreatCipher::new(Some(table()));
+        assert_eq!("abab", g.decrypt("1212").unwrap());
+        assert_eq!("abab", g.decrypt("1 2 1 2").unwrap());
+    }
+
+    #[test]
+    fn homophones_are_used() {
+        let g = GreatCipher::seeded(Some(table()), 7);
+        let c = g.encrypt(&"ba".repeat(50)).unwrap();
+
+        assert!(c.contains("21") && c.contains('3'));
+        assert_eq!("ba".repeat(50), g.decrypt(&c).unwrap());
+    }
+
+    #[test]
+    fn default_table() {
+        let g = GreatCipher::new(None);
+        let m = "Les soldats du roi marchent vers Paris";
+
+        assert_eq!(
+            "lessoldatsduroimarchentversparis",
+            g.decrypt(&g.encrypt(m).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn unknown_syllable() {
+        let g = GreatCipher::new(Some(table()));
+        assert!(g.encrypt("abc").is_err());
+    }
+
+    #[test]
+    fn unknown_number() {
+        let g = GreatCipher::new(Some(table()));
+        assert!(g.decrypt("12 4").is_err());
+    }
+
+    #[test]
+    fn with_utf8() {
+        let g = GreatCipher::new(None);
+        assert!(g.encrypt("Peace 🗡️ Freedom").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn repeated_syllable() {
+        let mut t = table();
+        t.push((String::from("A"), vec![String::from("4")]));
+        GreatCipher::new(Some(t));
+    }
+
+    #[test]
+    #[should_panic]
+    fn repeated_number() {
+        let mut t = table();
+        t.push((String::from("c"), vec![String::from("3")]));
+        GreatCipher::new(Some(t));
+    }
+
+    #[test]
+    #[should_panic]
+    fn syllable_without_numbers() {
+        let mut t = table();
+        t.push((String::from("c"), Vec::new()));
+        GreatCipher::new(Some(t));
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_numeric_code() {
+        let mut t = table();
+        t.push((String::from("c"), vec![String::from("4a")]));
+        GreatCipher::new(Some(t));
+    }
+
+    #[test]
+    fn strip_keeps_syllables() {
+        let g = GreatCipher::new(Some(table()));
+        assert_eq!(
+            g.encrypt("abab"),
+            g.encrypt_with_policy("Ab-ab, c!", ForeignCharPolicy::Strip)
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! The Gronsfeld cipher is a variant of the Vigenère cipher, attributed to Count Gronsfeld in the
+//! 17th century. Instead of a keyword, the key is a sequence of digits, each of which gives the
+//! shift applied to the corresponding letter of the message.
+//!
+//! For example, given the message `ATTACK AT DAWN` and the key `31415`, the shifts applied would
+//! be `314153 14 1531`. As there are only ten possible shifts for each letter, it is weaker than
+//! the Vigenère cipher.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::keygen::cyclic_keystream;
+use crate::common::substitute;
+
+/// A Gronsfeld cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gronsfeld {
+    key: String,
+}
+
+impl Gronsfeld {
+    /// Initialise a Gronsfeld cipher given a specific numeric key.
+    ///
+    /// # Panics
+    /// * The `key` is empty.
+    /// * The `key` contains a symbol that is not a digit `0-9`.
+    ///
+    pub fn new(key: impl Into<String>) -> Gronsfeld {
+        let key = key.into();
+        if key.is_empty() {
+            panic!("The key is empty.");
+        }
+        if !key.chars().all(alphabet::is_numeric) {
+            panic!("The key contains a non-numeric symbol.");
+        }
+
+        // Each digit is equivalent to a Vigenère key letter in the range `a-j`, which lets the
+        // cipher share the keystream machinery of the other polyalphabetic ciphers.
+        let key = key
+            .chars()
+            .map(|d| alphabet::STANDARD.get_letter(d.to_digit(10).unwrap() as usize, false))
+            .collect();
+
+        Gronsfeld { key }
+    }
+
+    /// The key of the cipher, as a string of digits.
+    ///
+    pub fn key(&self) -> String {
+        self.key
+            .chars()
+            .map(|c| {
+                let digit = alphabet::STANDARD.find_position(c).unwrap() as u32;
+                std::char::from_digit(digit, 10).unwrap()
+            })
+            .collect()
+    }
+}
+
+impl Cipher for Gronsfeld {
+    type Key = String;
+    type Algorithm = Gronsfeld;
+
+    /// Initialise a Gronsfeld cipher given a specific numeric key, as by `Gronsfeld::new()`.
+    ///
+    fn new(key: String) -> Gronsfeld {
+        Gronsfeld::new(key)
+    }
+
+    /// Encrypt a message using a Gronsfeld cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Gronsfeld};
+    ///
+    /// let g = Gronsfeld::new("31415");
+    /// assert_eq!("Duxbhn bx efzo!", g.encrypt("Attack at dawn!").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        // Encryption of a letter in a message:
+        //         Ci = Ek(Mi) = (Mi + Ki) mod 26
+        // Where;  Mi = position within the alphabet of ith char in message
+        //         Ki = the ith digit of the key
+        Ok(substitute::key_substitution(
+            message,
+            cyclic_keystream(&self.key, message),
+            |mi, ki| alphabet::STANDARD.modulo((mi + ki) as isize),
+        ))
+    }
+
+    /// Decrypt a message using a Gronsfeld cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Gronsfeld};
+    ///
+    /// let g = Gronsfeld::new("31415");
+    /// assert_eq!("Attack at dawn!", g.decrypt("Duxbhn bx efzo!").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        // Decryption of a letter in a message:
+        //         Mi = Dk(Ci) = (Ci - Ki) mod 26
+        // Where;  Ci = position within the alphabet of ith char in cipher text
+        //         Ki = the ith digit of the key
+        Ok(substitute::key_substitution(
+            ciphertext,
+            cyclic_keystream(&self.key, ciphertext),
+            |ci, ki| alphabet::STANDARD.modulo(ci as isize - ki as isize),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_getter() {
+        assert_eq!("31415", Gronsfeld::new(String::from("31415")).key());
+    }
+
+    #[test]
+    fn encrypt_test() {
+        let message = "attackatdawn";
+        let g = Gronsfeld::new(String::from("31415"));
+        assert_eq!("duxbhnbxefzo", g.encrypt(message).unwrap());
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let ciphertext = "duxbhnbxefzo";
+        let g = Gronsfeld::new(String::from("31415"));
+        assert_eq!("attackatdawn", g.decrypt(ciphertext).unwrap());
+    }
+
+    #[test]
+    fn zero_key() {
+        let message = "Attack at dawn!";
+        let g = Gronsfeld::new(String::from("0"));
+        assert_eq!(message, g.encrypt(message).unwrap());
+    }
+
+    #[test]
+    fn with_utf8() {
+        let g = Gronsfeld::new(String::from("9876543210"));
+        let message = "Peace 🗡️ Freedom and Liberty!";
+        let encrypted = g.encrypt(message).unwrap();
+        let decrypted = g.decrypt(&encrypted).unwrap();
+
+        assert_eq!(decrypted, message);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_key() {
+        Gronsfeld::new(String::from(""));
+    }
+
+    #[test]
+    #[should_panic]
+    fn key_with_letters() {
+        Gronsfeld::new(String::from("31a15"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn key_with_whitespace() {
+        Gronsfeld::new(String::from("3 14 15"));
+    }
+}
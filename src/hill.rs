@@ -16,14 +16,22 @@
 use crate::common::alphabet;
 use crate::common::alphabet::Alphabet;
 use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
+use crate::common::key_problem;
+use crate::common::key_problem::KeyProblem;
 use num::integer::gcd;
 use rulinalg::matrix::{BaseMatrix, BaseMatrixMut, Matrix};
+use std::fmt;
+use std::str::FromStr;
 
 /// A Hill cipher.
 ///
 /// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Hill {
     key: Matrix<isize>,
+    matrix: Matrix<f64>,
+    inverse: Matrix<f64>,
 }
 
 impl Cipher for Hill {
@@ -55,26 +63,9 @@ impl Cipher for Hill {
     /// ```
     ///
     fn new(key: Matrix<isize>) -> Hill {
-        if key.cols() != key.rows() {
-            panic!("The key is not a square matrix.");
-        }
-
-        //We want to restrict the caller to supplying matrices of type isize
-        //However, the majority of the matrix operations will be done with type f64
-        let m: Matrix<f64> = key
-            .clone()
-            .try_into()
-            .expect("Could not convert Matrix of type `isize` to `f64`.");
+        Hill::validate_key(&key);
 
-        if m.clone().inverse().is_err() || Hill::calc_inverse_key(m.clone()).is_err() {
-            panic!("The inverse of this matrix cannot be calculated for decryption.");
-        }
-
-        if gcd(m.det() as isize, 26) != 1 {
-            panic!("The inverse determinant of the key cannot be calculated.");
-        }
-
-        Hill { key }
+        Hill::from_valid_key(key)
     }
 
     /// Encrypt a message using a Hill cipher.
@@ -128,7 +119,9 @@ impl Cipher for Hill {
 
             This is repeated until all the 'chunks' of the message have been consumed/transformed.
         */
-        Hill::transform_message(&self.key.clone().try_into().unwrap(), message)
+        let offset = Matrix::zeros(self.matrix.rows(), 1);
+
+        Hill::transform_message(&self.matrix, &offset, message)
     }
 
     /// Decrypt a message using a Hill cipher.
@@ -177,13 +170,49 @@ impl Cipher for Hill {
 
         This is repeated until all the 'chunks' of the message have been consumed/transformed.
         */
-        let inverse_key = Hill::calc_inverse_key(self.key.clone().try_into().unwrap())?;
+        let offset = Matrix::zeros(self.inverse.rows(), 1);
 
-        Hill::transform_message(&inverse_key, ciphertext)
+        Hill::transform_message(&self.inverse, &offset, ciphertext)
+    }
+
+    /// Check that a message can be encrypted using a Hill cipher.
+    ///
+    /// Returns `Err` with the first non-alphabetic symbol of the message, and its byte offset.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, CipherError, Hill};
+    ///
+    /// let h = Hill::from_phrase("CEFJCBDRH", 3);
+    /// assert!(h.validate_message("ATTACKEAST").is_ok());
+    /// assert_eq!(
+    ///     Err(CipherError::Symbol {
+    ///         symbol: ' ',
+    ///         offset: 6,
+    ///         reason: "Message cannot contain non-alphabetic symbols."
+    ///     }),
+    ///     h.validate_message("Attack east!")
+    /// );
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        CipherError::find(
+            message,
+            "Message cannot contain non-alphabetic symbols.",
+            |c| alphabet::STANDARD.find_position(c).is_none(),
+        )
     }
 }
 
 impl Hill {
+    /// The key matrix of the cipher.
+    ///
+    pub fn key(&self) -> &Matrix<isize> {
+        &self.key
+    }
+
     /// Initialise a Hill cipher given a phrase.
     ///
     /// The position of each character within the alphabet is used to construct the
@@ -227,9 +256,137 @@ impl Hill {
         Hill::new(Matrix::new(chunk_size, chunk_size, matrix))
     }
 
+    /// List the problems with a `key` matrix that would stop it from being given to `new()`.
+    ///
+    pub fn key_problems(key: &Matrix<isize>) -> Vec<KeyProblem> {
+        if key.rows() != key.cols() {
+            return vec![KeyProblem::NotSquare {
+                rows: key.rows(),
+                columns: key.cols(),
+            }];
+        }
+
+        match Hill::check_key(key) {
+            Ok(()) => Vec::new(),
+            Err(_) => vec![KeyProblem::NotInvertible],
+        }
+    }
+
+    /// List the problems with a `phrase` and `chunk_size` that would stop them from being given to
+    /// `from_phrase()`.
+    ///
+    /// Whether the matrix of the phrase can be inverted is only checked when the phrase has no
+    /// other problems.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Hill, KeyProblem};
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         KeyProblem::Length { expected: 4, actual: 5 },
+    ///         KeyProblem::InvalidSymbol { position: 2, symbol: '3' },
+    ///     ],
+    ///     Hill::phrase_problems("ab3de", 2)
+    /// );
+    /// assert_eq!(vec![KeyProblem::NotInvertible], Hill::phrase_problems("abcd", 2));
+    /// ```
+    ///
+    pub fn phrase_problems(phrase: &str, chunk_size: usize) -> Vec<KeyProblem> {
+        let mut problems = Vec::new();
+        let length = phrase.chars().count();
+        if chunk_size < 2 {
+            problems.push(KeyProblem::OutOfRange {
+                value: chunk_size,
+                min: 2,
+                max: usize::MAX,
+            });
+        } else if chunk_size * chunk_size != length {
+            problems.push(KeyProblem::Length {
+                expected: chunk_size * chunk_size,
+                actual: length,
+            });
+        }
+        problems.extend(key_problem::invalid_symbols(phrase, &alphabet::STANDARD));
+
+        if problems.is_empty() {
+            let matrix: Vec<isize> = phrase
+                .chars()
+                .map(|c| alphabet::STANDARD.find_position(c).unwrap() as isize)
+                .collect();
+            problems.extend(Hill::key_problems(&Matrix::new(
+                chunk_size, chunk_size, matrix,
+            )));
+        }
+
+        problems
+    }
+
+    /// Validate a key matrix, such that it can be used for both encryption and decryption.
+    ///
+    /// # Panics
+    /// * The `key` matrix is not a square
+    /// * The `key` matrix is non-invertible
+    /// * The inverse determinant of the `key` matrix cannot be calculated such that
+    ///   `d*d^-1 == 1 mod 26`
+    ///
+    pub(crate) fn validate_key(key: &Matrix<isize>) {
+        if let Err(e) = Hill::check_key(key) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Initialise a Hill cipher from a key matrix that has already been validated, inverting it
+    /// once so that it is not inverted for every message that is decrypted.
+    ///
+    fn from_valid_key(key: Matrix<isize>) -> Hill {
+        let matrix: Matrix<f64> = key.clone().try_into().unwrap();
+        let inverse = Hill::calc_inverse_key(matrix.clone()).unwrap();
+
+        Hill {
+            key,
+            matrix,
+            inverse,
+        }
+    }
+
+    /// Check that a key matrix can be used for both encryption and decryption, returning `Err`
+    /// for any of the conditions under which `validate_key()` panics.
+    ///
+    fn check_key(key: &Matrix<isize>) -> Result<(), &'static str> {
+        if key.cols() != key.rows() {
+            return Err("The key is not a square matrix.");
+        }
+
+        //We want to restrict the caller to supplying matrices of type isize
+        //However, the majority of the matrix operations will be done with type f64
+        let m: Matrix<f64> = key
+            .clone()
+            .try_into()
+            .expect("Could not convert Matrix of type `isize` to `f64`.");
+
+        if m.clone().inverse().is_err() || Hill::calc_inverse_key(m.clone()).is_err() {
+            return Err("The inverse of this matrix cannot be calculated for decryption.");
+        }
+
+        if gcd(m.det() as isize, 26) != 1 {
+            return Err("The inverse determinant of the key cannot be calculated.");
+        }
+
+        Ok(())
+    }
+
     /// Core logic of the hill cipher. Transposing messages with matrices
     ///
-    fn transform_message(key: &Matrix<f64>, message: &str) -> Result<String, &'static str> {
+    /// Each chunk `x` of the message is transformed to `key * x + offset mod 26`, where the
+    /// `offset` is a column vector.
+    ///
+    pub(crate) fn transform_message(
+        key: &Matrix<f64>,
+        offset: &Matrix<f64>,
+        message: &str,
+    ) -> Result<String, &'static str> {
         //Only allow chars in the alphabet (no whitespace or symbols)
         if !alphabet::STANDARD.is_valid(message) {
             return Err("Message cannot contain non-alphabetic symbols.");
@@ -252,7 +409,7 @@ impl Hill {
         //For each set of chunks in the message, transform based on the key.
         let mut i = 0;
         while i < buffer.len() {
-            match Hill::transform_chunk(key, &buffer[i..(i + chunk_size)]) {
+            match Hill::transform_chunk(key, offset, &buffer[i..(i + chunk_size)]) {
                 Ok(s) => transformed_message.push_str(&s),
                 Err(e) => return Err(e),
             }
@@ -266,7 +423,11 @@ impl Hill {
 
     /// Transforming a chunk of the message, whose length is determined by the size of the matrix
     ///
-    fn transform_chunk(key: &Matrix<f64>, chunk: &str) -> Result<String, &'static str> {
+    fn transform_chunk(
+        key: &Matrix<f64>,
+        offset: &Matrix<f64>,
+        chunk: &str,
+    ) -> Result<String, &'static str> {
         let mut transformed = String::new();
 
         if !alphabet::STANDARD.is_valid(chunk) {
@@ -284,8 +445,9 @@ impl Hill {
             .map(|c| alphabet::STANDARD.find_position(c).unwrap() as f64)
             .collect();
 
-        //Perform the transformation `k * [0, 19, 19] mod 26`
-        let mut product = key * Matrix::new(index_representation.len(), 1, index_representation);
+        //Perform the transformation `k * [0, 19, 19] + offset mod 26`
+        let mut product =
+            key * Matrix::new(index_representation.len(), 1, index_representation) + offset;
         product = product.apply(&|x| (x % 26.0).round());
 
         //Convert the transformed indices back into characters of the alphabet
@@ -303,7 +465,7 @@ impl Hill {
 
     /// Calculates the inverse key for decryption
     ///
-    fn calc_inverse_key(key: Matrix<f64>) -> Result<Matrix<f64>, &'static str> {
+    pub(crate) fn calc_inverse_key(key: Matrix<f64>) -> Result<Matrix<f64>, &'static str> {
         let det = key.clone().det();
 
         //Find the inverse determinant such that: d*d^-1 = 1 mod 26
@@ -318,9 +480,71 @@ impl Hill {
     }
 }
 
+impl FromStr for Hill {
+    type Err = &'static str;
+
+    /// Parse a Hill cipher from a phrase, as given to `from_phrase()`, whose length is the square
+    /// of the chunk size (such as `"CEFJCBDRH"` for a chunk size of 3).
+    ///
+    /// Returns `Err` if the phrase does not have the length of a square of at least 4, if it
+    /// contains non-alphabetic symbols, or if its matrix could not be given to `new()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Hill};
+    ///
+    /// let h: Hill = "CEFJCBDRH".parse().unwrap();
+    /// assert_eq!(Hill::from_phrase("CEFJCBDRH", 3).encrypt("thing"), h.encrypt("thing"));
+    /// assert_eq!("CEFJCBDRH", h.to_string());
+    /// ```
+    ///
+    fn from_str(s: &str) -> Result<Hill, &'static str> {
+        let phrase = s.trim();
+        if !alphabet::STANDARD.is_valid(phrase) {
+            return Err("Phrase cannot contain non-alphabetic symbols.");
+        }
+
+        let chunk_size = (2..)
+            .take_while(|n| n * n <= phrase.len())
+            .last()
+            .filter(|n| n * n == phrase.len())
+            .ok_or("The length of the phrase must be the square of a chunk size greater than 1.")?;
+        let matrix: Vec<isize> = phrase
+            .chars()
+            .map(|c| alphabet::STANDARD.find_position(c).unwrap() as isize)
+            .collect();
+        let key = Matrix::new(chunk_size, chunk_size, matrix);
+        Hill::check_key(&key)?;
+
+        Ok(Hill::from_valid_key(key))
+    }
+}
+
+impl fmt::Display for Hill {
+    /// Writes the key matrix of the cipher as a phrase, row by row, as parsed by `from_str()`.
+    ///
+    /// Each element of the matrix is written as the letter at its position (modulo 26) within the
+    /// alphabet, so a key given to `new()` with elements outside of `0 - 25` is written as an
+    /// equivalent phrase.
+    ///
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &element in self.key.iter() {
+            write!(
+                f,
+                "{}",
+                alphabet::STANDARD.get_letter(alphabet::STANDARD.modulo(element), true)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::policy::ForeignCharPolicy;
 
     #[test]
     fn keygen_from_phrase() {
@@ -333,6 +557,33 @@ mod tests {
         Hill::from_phrase("killer", 2);
     }
 
+    #[test]
+    fn parse_phrase() {
+        let h = Hill::new(Matrix::new(2, 2, vec![33, -1, 2, 5]));
+        assert_eq!("HZCF", h.to_string());
+        assert_eq!("HZCF", "hzcf".parse::<Hill>().unwrap().to_string());
+
+        assert!("killer".parse::<Hill>().is_err());
+        assert!("abcd".parse::<Hill>().is_err());
+        assert!("c3f!".parse::<Hill>().is_err());
+    }
+
+    #[test]
+    fn key_problems_test() {
+        assert!(Hill::phrase_problems("CEFJCBDRH", 3).is_empty());
+        assert_eq!(
+            vec![KeyProblem::NotSquare {
+                rows: 3,
+                columns: 2
+            }],
+            Hill::key_problems(&Matrix::new(3, 2, vec![2, 4, 9, 2, 3, 17]))
+        );
+        assert_eq!(
+            vec![KeyProblem::NotInvertible],
+            Hill::key_problems(&Matrix::new(3, 3, vec![2, 2, 3, 6, 6, 9, 1, 4, 8]))
+        );
+    }
+
     #[test]
     fn encrypt_no_padding_req() {
         let h = Hill::new(Matrix::new(3, 3, vec![2, 4, 5, 9, 2, 1, 3, 17, 7]));
@@ -347,6 +598,30 @@ mod tests {
         assert!(h.encrypt("This won!t w@rk").is_err());
     }
 
+    #[test]
+    fn validate_with_symbols() {
+        let h = Hill::from_phrase("CEFJCBDRH", 3);
+        assert!(h.validate_message("ThisWillWork").is_ok());
+        assert!(h.validate_message("This won!t w@rk").is_err());
+    }
+
+    #[test]
+    fn check_with_symbols() {
+        let h = Hill::from_phrase("CEFJCBDRH", 3);
+        assert_eq!(Ok(()), h.validate_message("ThisWillWork"));
+
+        let e = h.validate_message("ThisWon!tW@rk").unwrap_err();
+        assert_eq!(
+            CipherError::Symbol {
+                symbol: '!',
+                offset: 7,
+                reason: "Message cannot contain non-alphabetic symbols."
+            },
+            e
+        );
+        assert_eq!(h.encrypt("ThisWon!tW@rk"), Err(e.reason()));
+    }
+
     #[test]
     fn decrypt_with_symbols() {
         let h = Hill::from_phrase("CEFJCBDRH", 3);
@@ -382,4 +657,26 @@ mod tests {
     fn non_invertable_matrix() {
         Hill::new(Matrix::new(3, 3, vec![2, 2, 3, 6, 6, 9, 1, 4, 8]));
     }
+
+    #[test]
+    fn strip_foreign_chars() {
+        let h = Hill::from_phrase("CEFJCBDRH", 3);
+
+        assert_eq!(
+            h.encrypt("Attackatdawn"),
+            h.encrypt_with_policy("Attack at dawn!", ForeignCharPolicy::Strip)
+        );
+    }
+
+    #[test]
+    fn batch_matches_encrypt() {
+        let h = Hill::from_phrase("CEFJCBDRH", 3);
+        let messages = ["ATTACKEAST", "ATDAWN", "thing"];
+        let batch = h.encrypt_batch(&messages).unwrap();
+
+        for (m, c) in messages.iter().zip(batch.iter()) {
+            assert_eq!(&h.encrypt(m).unwrap(), c);
+            assert!(h.decrypt(c).unwrap().starts_with(m));
+        }
+    }
 }
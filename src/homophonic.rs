@@ -0,0 +1,337 @@
+//! A homophonic substitution cipher replaces each letter of the message with one of several
+//! ciphertext symbols (its homophones). The more common a letter is, the more homophones it is
+//! given, so that each symbol appears with roughly the same frequency in the ciphertext. This
+//! flattening of the letter frequencies defeats simple frequency analysis.
+//!
+//! Homophonic ciphers were in use by the 15th century, and were the basis of the nomenclators
+//! used across the courts of Europe for the next 400 years.
+//!
+//! The homophone used for each letter is chosen at random. The random choices can be seeded, so
+//! that the same message always gives the same ciphertext.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
+use crate::common::keygen;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// The number of homophones given to each of the letters `a-z` by `from_keyword()`, in
+/// proportion to their frequency in English. There are 100 homophones in total.
+pub const FREQUENCY_ALLOCATION: [usize; 26] = [
+    8, 2, 3, 4, 11, 2, 2, 6, 7, 1, 1, 4, 2, 7, 7, 2, 1, 6, 6, 8, 3, 1, 2, 1, 2, 1,
+];
+
+/// A Homophonic cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Homophonic {
+    homophones: Vec<Vec<String>>,
+    symbol_len: usize,
+    rng: RefCell<StdRng>,
+}
+
+impl Cipher for Homophonic {
+    type Key = HashMap<char, Vec<String>>;
+    type Algorithm = Homophonic;
+
+    /// Initialise a Homophonic cipher given a mapping of each letter to its homophones.
+    ///
+    /// The case of the letters in the mapping is ignored. The homophones of a letter are chosen
+    /// from at random during encryption; see `seeded()` for repeatable choices.
+    ///
+    /// # Panics
+    /// * The `key` does not contain at least one homophone for every letter `a-z`.
+    /// * The `key` contains a non-alphabetic letter.
+    /// * A homophone is empty, contains whitespace or is used more than once.
+    /// * The homophones are not all of the same length.
+    ///
+    fn new(key: HashMap<char, Vec<String>>) -> Homophonic {
+        let mut homophones = vec![Vec::new(); 26];
+        for (letter, symbols) in key {
+            let pos = alphabet::STANDARD
+                .find_position(letter)
+                .expect("The key contains a non-alphabetic letter.");
+            homophones[pos].extend(symbols);
+        }
+
+        if homophones.iter().any(|h| h.is_empty()) {
+            panic!("Every letter must have at least one homophone.");
+        }
+
+        let all: Vec<&String> = homophones.iter().flatten().collect();
+        let symbol_len = all[0].chars().count();
+        for (i, symbol) in all.iter().enumerate() {
+            if symbol.is_empty() || symbol.chars().any(char::is_whitespace) {
+                panic!("A homophone cannot be empty or contain whitespace.");
+            }
+            if symbol.chars().count() != symbol_len {
+                panic!("The homophones must all be of the same length.");
+            }
+            if all[..i].contains(symbol) {
+                panic!("A homophone cannot be used more than once.");
+            }
+        }
+
+        Homophonic {
+            homophones,
+            symbol_len,
+            rng: RefCell::new(StdRng::from_entropy()),
+        }
+    }
+
+    /// Encrypt a message using a Homophonic cipher.
+    ///
+    /// Each letter of the message is replaced with one of its homophones, and the homophones are
+    /// separated by spaces. Whitespace within the message is not preserved.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Homophonic};
+    ///
+    /// let h = Homophonic::from_keyword("cipher");
+    /// let c = h.encrypt("Attack at dawn").unwrap();
+    ///
+    /// // Each letter is replaced with a pair of digits
+    /// assert_eq!(12 * 3 - 1, c.len());
+    /// assert_eq!("attackatdawn", h.decrypt(&c).unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.validate_message(message)?;
+
+        let mut rng = self.rng.borrow_mut();
+        let symbols: Vec<&str> = message
+            .chars()
+            .filter_map(|c| alphabet::STANDARD.find_position(c))
+            .map(|pos| {
+                let choices = &self.homophones[pos];
+                choices[rng.gen_range(0..choices.len())].as_str()
+            })
+            .collect();
+
+        Ok(symbols.join(" "))
+    }
+
+    /// Decrypt a message using a Homophonic cipher.
+    ///
+    /// Whitespace within the ciphertext is ignored. The plaintext is lowercase.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Homophonic};
+    ///
+    /// let h = Homophonic::from_keyword("cipher");
+    /// assert_eq!("attack", h.decrypt("35 82 89 40 01 54").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let chars: Vec<char> = ciphertext.chars().filter(|c| !c.is_whitespace()).collect();
+        if !chars.len().is_multiple_of(self.symbol_len) {
+            return Err("Ciphertext contains an incomplete homophone.");
+        }
+
+        chars
+            .chunks(self.symbol_len)
+            .map(|chunk| {
+                let symbol: String = chunk.iter().collect();
+                self.homophones
+                    .iter()
+                    .position(|h| h.contains(&symbol))
+                    .map(|pos| alphabet::STANDARD.get_letter(pos, false))
+                    .ok_or("Ciphertext contains an unknown homophone.")
+            })
+            .collect()
+    }
+
+    /// Check that a message can be encrypted with a Homophonic cipher.
+    ///
+    /// Returns `Err` if the message contains a symbol that is not alphabetic or whitespace.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Homophonic};
+    ///
+    /// let h = Homophonic::from_keyword("cipher");
+    ///
+    /// assert!(h.validate_message("Attack at dawn").is_ok());
+    /// assert!(h.validate_message("Attack at 6am!").is_err());
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        CipherError::find(
+            message,
+            "Message contains a symbol that is not alphabetic or whitespace.",
+            |c| !c.is_whitespace() && alphabet::STANDARD.find_position(c).is_none(),
+        )
+    }
+}
+
+impl Homophonic {
+    /// Initialise a Homophonic cipher with the homophones `00` to `99`, allocated to each letter
+    /// according to `FREQUENCY_ALLOCATION`.
+    ///
+    /// The letters are taken in the order of the alphabet keyed by the `keyword`, and each is
+    /// given the next of its homophones in turn. For example, with the keyword `cipher` the
+    /// letter `c` is given `00-02`, the letter `i` is given `03-09`, and so on.
+    ///
+    /// # Panics
+    /// * The `keyword` contains a non-alphabetic symbol.
+    ///
+    pub fn from_keyword(keyword: &str) -> Homophonic {
+        let mut key = HashMap::new();
+        let mut next = 0;
+        for c in keygen::keyed_alphabet(keyword, &alphabet::STANDARD, false).chars() {
+            let count = FREQUENCY_ALLOCATION[alphabet::STANDARD.find_position(c).unwrap()];
+            key.insert(
+                c,
+                (next..next + count).map(|n| format!("{:02}", n)).collect(),
+            );
+            next += count;
+        }
+
+        Homophonic::new(key)
+    }
+
+    /// Initialise a Homophonic cipher, where the homophones are chosen by a random number
+    /// generator with the given `seed`. The same message will always be encrypted in the same
+    /// way.
+    ///
+    /// # Panics
+    /// * Any of the conditions as stipulated by the `new()` fn.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Homophonic};
+    /// use std::collections::HashMap;
+    ///
+    /// let key: HashMap<char, Vec<String>> = (b'a'..=b'z')
+    ///     .map(|c| (c as char, vec![format!("{}1", c as char), format!("{}2", c as char)]))
+    ///     .collect();
+    ///
+    /// let h1 = Homophonic::seeded(key.clone(), 42);
+    /// let h2 = Homophonic::seeded(key, 42);
+    /// assert_eq!(h1.encrypt("Attack at dawn").unwrap(), h2.encrypt("Attack at dawn").unwrap());
+    /// ```
+    ///
+    pub fn seeded(key: HashMap<char, Vec<String>>, seed: u64) -> Homophonic {
+        let h = Homophonic::new(key);
+        h.rng.replace(StdRng::seed_from_u64(seed));
+
+        h
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> HashMap<char, Vec<String>> {
+        let mut key: HashMap<char, Vec<String>> = (b'a'..=b'z')
+            .map(|c| (c as char, vec![format!("{:02}", c - b'a')]))
+            .collect();
+        key.get_mut(&'e')
+            .unwrap()
+            .extend(vec![String::from("26"), String::from("27")]);
+
+        key
+    }
+
+    #[test]
+    fn encrypt_test() {
+        let h = Homophonic::new(key());
+        assert_eq!("00 19 19 00 02 10", h.encrypt("Attack").unwrap());
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let h = Homophonic::new(key());
+        assert_eq!("meet", h.decrypt("12 04 27 19").unwrap());
+    }
+
+    #[test]
+    fn homophones_are_used() {
+        let h = Homophonic::seeded(key(), 7);
+        let c = h.encrypt(&"e".repeat(100)).unwrap();
+
+        for symbol in &["04", "26", "27"] {
+            assert!(c.contains(symbol));
+        }
+        assert_eq!("e".repeat(100), h.decrypt(&c).unwrap());
+    }
+
+    #[test]
+    fn seeded_is_repeatable() {
+        let m = "The quick brown fox jumps over the lazy dog";
+        let h1 = Homophonic::seeded(key(), 1234);
+        let h2 = Homophonic::seeded(key(), 1234);
+
+        assert_eq!(h1.encrypt(m).unwrap(), h2.encrypt(m).unwrap());
+    }
+
+    #[test]
+    fn from_keyword_allocation() {
+        let h = Homophonic::from_keyword("cipher");
+
+        assert_eq!(vec!["00", "01", "02"], h.homophones[2]);
+        assert_eq!(100, h.homophones.iter().flatten().count());
+        assert_eq!(100, FREQUENCY_ALLOCATION.iter().sum::<usize>());
+    }
+
+    #[test]
+    fn ciphertext_without_spaces() {
+        let h = Homophonic::from_keyword("cipher");
+        let c = h.encrypt("Attack at dawn").unwrap().replace(' ', "");
+
+        assert_eq!("attackatdawn", h.decrypt(&c).unwrap());
+    }
+
+    #[test]
+    fn invalid_ciphertext() {
+        let h = Homophonic::new(key());
+
+        assert!(h.decrypt("12 04 2").is_err());
+        assert!(h.decrypt("12 04 99").is_err());
+    }
+
+    #[test]
+    fn with_utf8() {
+        let h = Homophonic::from_keyword("utf");
+        assert!(h.encrypt("Peace 🗡️ Freedom").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn missing_letter() {
+        let mut key = key();
+        key.remove(&'q');
+        Homophonic::new(key);
+    }
+
+    #[test]
+    #[should_panic]
+    fn repeated_homophone() {
+        let mut key = key();
+        key.insert('q', vec![String::from("04")]);
+        Homophonic::new(key);
+    }
+
+    #[test]
+    #[should_panic]
+    fn uneven_homophones() {
+        let mut key = key();
+        key.insert('q', vec![String::from("160")]);
+        Homophonic::new(key);
+    }
+}
@@ -0,0 +1,306 @@
+//! The Jefferson wheel (or wheel cipher) was invented by Thomas Jefferson around 1795, and was
+//! independently reinvented by Étienne Bazeries in 1891. A variant, the M-94, was used by the US
+//! Army from 1922 until the early years of World War II.
+//!
+//! The device is a set of wheels, each with the letters of the alphabet around its edge in a
+//! different scrambled order. The wheels are stacked on an axle in an order agreed upon by the
+//! sender and recipient. The sender rotates the wheels so that the message can be read along one
+//! row, and then transcribes the letters of another row as the ciphertext. The recipient sets
+//! the ciphertext along a row, and looks for the row that reads as plaintext.
+//!
+//! In this implementation the ciphertext is always read a fixed `offset` number of rows below
+//! the plaintext. Messages longer than the number of wheels are enciphered in blocks.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+
+/// The 25 wheel alphabets of the US Army M-94 cylinder cipher.
+pub const M94_WHEELS: [&str; 25] = [
+    "ABCEIGDJFVUYMHTQKZOLRXSPWN",
+    "ACDEHFIJKTLMOUVYGZNPQXRWSB",
+    "ADKOMJUBGEPHSCZINXFYQRTVWL",
+    "AEDCBIFGJHLKMRUOQVPTNWYXZS",
+    "AFNQUKDOPITJBRHCYSLWEMZVXG",
+    "AGPOCIXLURNDYZHWBJSQFKVMET",
+    "AHXJEZBNIKPVROGSYDULCFMQTW",
+    "AIHPJOBWKCVFZLQERYNSUMGTDX",
+    "AJDSKQOIVTZEFHGYUNLPMBXWCR",
+    "AKELBDFJGHONMTPRQSVZUXYWIC",
+    "ALTMSXVQPNOHUWDIZYCGKRFBEJ",
+    "AMNFLHQGCUJTBYPZKXISRDVEWO",
+    "ANCJILDHBMKGXUZTSWQYVORPFE",
+    "AODWPKJVIUQHZCTXBLEGNYRSMF",
+    "APBVHIYKSGUENTCXOWFQDRLJZM",
+    "AQJNUBTGIMWZRVLXCSHDEOKFPY",
+    "ARMYOFTHEUSZJXDPCWGQIBKLNV",
+    "ASDMCNEQBOZPLGVJRKYTFUIWXH",
+    "ATOJYLFXNGWHVCMIRBSEKUPDZQ",
+    "AUTRZXQLYIOVBPESNHJWMDGFCK",
+    "AVNKHRGOXEYBFSJMUDQCLZWTIP",
+    "AWVSFDLIEBHKNRJQZGMXPUCOTY",
+    "AXKWREVDTUFOYHMLSIQNJCPGBZ",
+    "AYJPXMVKBQWUGLOSTECHNZFRID",
+    "AZDNBUHYFWJLVGRCQMPSOEXTKI",
+];
+
+/// A Jefferson Wheel cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JeffersonWheel {
+    wheels: Vec<Vec<usize>>,
+    offset: usize,
+}
+
+/// The key of a Jefferson Wheel cipher.
+///
+/// This struct is given to the `new()` method of `JeffersonWheel`. See its documentation for
+/// more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JeffersonWheelKey {
+    /// The alphabets of between 25 and 36 wheels. Each alphabet must be a permutation of
+    /// the letters `a-z`.
+    pub wheels: Vec<String>,
+    /// The sequence of wheel numbers (counting from 1) placed on the axle. Each wheel may
+    /// only be used once.
+    pub order: Vec<usize>,
+    /// The number of rows between the plaintext and ciphertext, from 1 to 25.
+    pub offset: usize,
+}
+
+impl Cipher for JeffersonWheel {
+    type Key = JeffersonWheelKey;
+    type Algorithm = JeffersonWheel;
+
+    /// Initialise a Jefferson Wheel cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `JeffersonWheelKey`.
+    ///
+    /// # Panics
+    /// * There are fewer than 25 or more than 36 `wheels`.
+    /// * A wheel alphabet is not a permutation of the letters `a-z`.
+    /// * The `order` is empty, refers to a wheel that does not exist, or uses a wheel twice.
+    /// * The `offset` is not within the range 1 to 25.
+    ///
+    fn new(key: JeffersonWheelKey) -> JeffersonWheel {
+        let JeffersonWheelKey {
+            wheels,
+            order,
+            offset,
+        } = key;
+        if wheels.len() < 25 || wheels.len() > 36 {
+            panic!("There must be between 25 and 36 wheels.");
+        }
+        if !(1..=25).contains(&offset) {
+            panic!("The offset must be between 1 and 25.");
+        }
+
+        let wheels: Vec<Vec<usize>> = wheels.iter().map(|w| wheel_positions(w)).collect();
+
+        if order.is_empty() {
+            panic!("The wheel order is empty.");
+        }
+        let mut placed = Vec::new();
+        for &n in &order {
+            if n < 1 || n > wheels.len() || placed.contains(&n) {
+                panic!("The wheel order must contain distinct wheel numbers.");
+            }
+            placed.push(n);
+        }
+
+        JeffersonWheel {
+            wheels: order.iter().map(|&n| wheels[n - 1].clone()).collect(),
+            offset,
+        }
+    }
+
+    /// Encrypt a message using a Jefferson Wheel cipher.
+    ///
+    /// Each letter of the message uses the next wheel on the axle, while any non-alphabetic
+    /// symbols are left in place.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, JeffersonWheel};
+    ///
+    /// let w = JeffersonWheel::m94((1..=25).collect(), 3);
+    /// assert_eq!("Eolcle ja qlzh!", w.encrypt("Attack at dawn!").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        Ok(self.transform(message, self.offset as isize))
+    }
+
+    /// Decrypt a message using a Jefferson Wheel cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, JeffersonWheel};
+    ///
+    /// let w = JeffersonWheel::m94((1..=25).collect(), 3);
+    /// assert_eq!("Attack at dawn!", w.decrypt("Eolcle ja qlzh!").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        Ok(self.transform(ciphertext, -(self.offset as isize)))
+    }
+}
+
+impl JeffersonWheel {
+    /// Initialise a Jefferson Wheel cipher using the wheels of the M-94.
+    ///
+    /// # Panics
+    /// * The `order` is empty, refers to a wheel that does not exist, or uses a wheel twice.
+    /// * The `offset` is not within the range 1 to 25.
+    ///
+    pub fn m94(order: Vec<usize>, offset: usize) -> JeffersonWheel {
+        JeffersonWheel::new(JeffersonWheelKey {
+            wheels: M94_WHEELS.iter().map(|w| w.to_string()).collect(),
+            order,
+            offset,
+        })
+    }
+
+    /// Move each letter `rows` positions around its wheel.
+    ///
+    fn transform(&self, text: &str, rows: isize) -> String {
+        let mut wheels = self.wheels.iter().cycle();
+
+        text.chars()
+            .map(|c| match alphabet::STANDARD.find_position(c) {
+                Some(pos) => {
+                    let wheel = wheels.next().unwrap();
+                    let row = wheel.iter().position(|&l| l == pos).unwrap() as isize;
+                    let letter = wheel[alphabet::STANDARD.modulo(row + rows)];
+
+                    alphabet::STANDARD.get_letter(letter, c.is_uppercase())
+                }
+                None => c,
+            })
+            .collect()
+    }
+}
+
+/// Convert a wheel alphabet into the alphabet positions of each of its letters.
+///
+fn wheel_positions(wheel: &str) -> Vec<usize> {
+    let positions: Vec<usize> = wheel
+        .chars()
+        .map(|c| {
+            alphabet::STANDARD
+                .find_position(c)
+                .expect("A wheel contains a non-alphabetic symbol.")
+        })
+        .collect();
+
+    if positions.len() != 26 || (0..26).any(|i| !positions.contains(&i)) {
+        panic!("A wheel must contain each letter of the alphabet exactly once.");
+    }
+
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_test() {
+        let w = JeffersonWheel::m94((1..=25).rev().collect(), 1);
+        assert_eq!("ZYXWVUTSRQ", w.encrypt("AAAAAAAAAA").unwrap());
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let w = JeffersonWheel::m94((1..=25).rev().collect(), 1);
+        assert_eq!("AAAAAAAAAA", w.decrypt("ZYXWVUTSRQ").unwrap());
+    }
+
+    #[test]
+    fn wheels_wrap_around() {
+        // The first wheel ends with 'N', which is followed by 'A' again
+        let w = JeffersonWheel::m94(vec![1], 1);
+        assert_eq!("AB", w.encrypt("NA").unwrap());
+    }
+
+    #[test]
+    fn message_longer_than_wheels() {
+        let m = "The quick brown fox jumps over the lazy dog, twice over!";
+        let w = JeffersonWheel::m94(vec![17, 3, 9, 21, 1, 5, 12], 14);
+
+        assert_eq!(m, w.decrypt(&w.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn custom_wheels() {
+        let wheels: Vec<String> = (0..30)
+            .map(|i| {
+                (0..26)
+                    .map(|j| alphabet::STANDARD.get_letter((j * 7 + i) % 26, false))
+                    .collect()
+            })
+            .collect();
+        let w = JeffersonWheel::new(JeffersonWheelKey {
+            wheels,
+            order: vec![30, 1, 15],
+            offset: 2,
+        });
+
+        let m = "Attack at dawn";
+        assert_eq!(m, w.decrypt(&w.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn with_utf8() {
+        let m = "Peace 🗡️ Freedom and Liberty!";
+        let w = JeffersonWheel::m94((1..=25).collect(), 25);
+
+        assert_eq!(m, w.decrypt(&w.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_few_wheels() {
+        let wheels = M94_WHEELS[..24].iter().map(|w| w.to_string()).collect();
+        JeffersonWheel::new(JeffersonWheelKey {
+            wheels,
+            order: vec![1],
+            offset: 1,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_wheel_alphabet() {
+        let mut wheels: Vec<String> = M94_WHEELS.iter().map(|w| w.to_string()).collect();
+        wheels[0] = String::from("AACEIGDJFVUYMHTQKZOLRXSPWN");
+        JeffersonWheel::new(JeffersonWheelKey {
+            wheels,
+            order: vec![1],
+            offset: 1,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn repeated_wheel() {
+        JeffersonWheel::m94(vec![1, 2, 1], 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unknown_wheel() {
+        JeffersonWheel::m94(vec![26], 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_offset() {
+        JeffersonWheel::m94(vec![1], 26);
+    }
+}
@@ -0,0 +1,241 @@
+//! The Kama-Sutra (or Vatsyayana) cipher is one of the earliest known substitution ciphers. It
+//! is described in the Kama Sutra, written by Vatsyayana around the 4th century AD, as one of the
+//! 64 arts that should be studied by women.
+//!
+//! The letters of the alphabet are randomly paired together, and each letter of the message is
+//! replaced with its partner. As each letter swaps with its partner, the substitution is its own
+//! inverse (an involution) and so encryption and decryption are the same operation.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::substitute;
+use rand::seq::SliceRandom;
+
+/// A Kama-Sutra cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KamaSutra {
+    partners: Vec<usize>,
+}
+
+impl Cipher for KamaSutra {
+    type Key = Vec<(char, char)>;
+    type Algorithm = KamaSutra;
+
+    /// Initialise a Kama-Sutra cipher given a pairing of the letters of the alphabet.
+    ///
+    /// The `key` holds 13 pairs of letters, which together contain each of the letters `a-z`
+    /// exactly once. The case of the letters is ignored.
+    ///
+    /// # Panics
+    /// * The `key` does not contain 13 pairs.
+    /// * The `key` contains a non-alphabetic symbol.
+    /// * A letter is paired with itself, or appears in more than one pair.
+    ///
+    fn new(key: Vec<(char, char)>) -> KamaSutra {
+        if key.len() != 13 {
+            panic!("The key must contain 13 pairs of letters.");
+        }
+
+        let mut partners = vec![None; 26];
+        for (a, b) in key {
+            let a = alphabet::STANDARD
+                .find_position(a)
+                .expect("The key contains a non-alphabetic symbol.");
+            let b = alphabet::STANDARD
+                .find_position(b)
+                .expect("The key contains a non-alphabetic symbol.");
+
+            if a == b || partners[a].is_some() || partners[b].is_some() {
+                panic!("Each letter must be paired with exactly one other letter.");
+            }
+            partners[a] = Some(b);
+            partners[b] = Some(a);
+        }
+
+        KamaSutra {
+            partners: partners.into_iter().map(Option::unwrap).collect(),
+        }
+    }
+
+    /// Encrypt a message using a Kama-Sutra cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, KamaSutra};
+    ///
+    /// // Pair each letter with the letter 13 places after it
+    /// let pairs = "abcdefghijklm".chars().zip("nopqrstuvwxyz".chars()).collect();
+    /// let k = KamaSutra::new(pairs);
+    ///
+    /// assert_eq!("Nggnpx ng qnja!", k.encrypt("Attack at dawn!").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        Ok(substitute::shift_substitution(message, |idx| {
+            self.partners[idx]
+        }))
+    }
+
+    /// Decrypt a message using a Kama-Sutra cipher.
+    ///
+    /// As the substitution is its own inverse, this is identical to encryption.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, KamaSutra};
+    ///
+    /// // Pair each letter with the letter 13 places after it
+    /// let pairs = "abcdefghijklm".chars().zip("nopqrstuvwxyz".chars()).collect();
+    /// let k = KamaSutra::new(pairs);
+    ///
+    /// assert_eq!("Attack at dawn!", k.decrypt("Nggnpx ng qnja!").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        self.encrypt(ciphertext)
+    }
+}
+
+impl KamaSutra {
+    /// Initialise a Kama-Sutra cipher with a random pairing of the letters of the alphabet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, KamaSutra};
+    ///
+    /// let k = KamaSutra::random();
+    /// assert_eq!("Attack at dawn!", k.decrypt(&k.encrypt("Attack at dawn!").unwrap()).unwrap());
+    /// ```
+    ///
+    pub fn random() -> KamaSutra {
+        let mut letters: Vec<char> = (0..26)
+            .map(|i| alphabet::STANDARD.get_letter(i, false))
+            .collect();
+        letters.shuffle(&mut rand::thread_rng());
+
+        KamaSutra::new(letters.chunks(2).map(|p| (p[0], p[1])).collect())
+    }
+
+    /// The pairs of letters used by the cipher, in alphabetical order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, KamaSutra};
+    ///
+    /// let pairs = "abcdefghijklm".chars().zip("nopqrstuvwxyz".chars()).collect();
+    /// let k = KamaSutra::new(pairs);
+    ///
+    /// assert_eq!(('a', 'n'), k.pairs()[0]);
+    /// ```
+    ///
+    pub fn pairs(&self) -> Vec<(char, char)> {
+        (0..26)
+            .filter(|&i| i < self.partners[i])
+            .map(|i| {
+                (
+                    alphabet::STANDARD.get_letter(i, false),
+                    alphabet::STANDARD.get_letter(self.partners[i], false),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> Vec<(char, char)> {
+        // Pair each letter with its mirror in the alphabet, as in the Atbash cipher
+        "ABCDEFGHIJKLM"
+            .chars()
+            .zip("ZYXWVUTSRQPON".chars())
+            .collect()
+    }
+
+    #[test]
+    fn encrypt_test() {
+        let k = KamaSutra::new(key());
+        assert_eq!("nvvg nv zg mllm", k.encrypt("meet me at noon").unwrap());
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let k = KamaSutra::new(key());
+        assert_eq!("meet me at noon", k.decrypt("nvvg nv zg mllm").unwrap());
+    }
+
+    #[test]
+    fn is_an_involution() {
+        let k = KamaSutra::new(key());
+        let m = "The quick brown fox jumps over the lazy dog";
+
+        assert_eq!(m, k.encrypt(&k.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn random_is_an_involution() {
+        let k = KamaSutra::random();
+
+        assert_eq!(13, k.pairs().len());
+        for i in 0..26 {
+            assert_ne!(i, k.partners[i]);
+            assert_eq!(i, k.partners[k.partners[i]]);
+        }
+    }
+
+    #[test]
+    fn pairs_round_trip() {
+        let k1 = KamaSutra::new(key());
+        let k2 = KamaSutra::new(k1.pairs());
+
+        assert_eq!(k1.partners, k2.partners);
+    }
+
+    #[test]
+    fn with_utf8() {
+        let k = KamaSutra::new(key());
+        let m = "Peace 🗡️ Freedom and Liberty!";
+
+        assert_eq!(m, k.decrypt(&k.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_few_pairs() {
+        KamaSutra::new(key()[1..].to_vec());
+    }
+
+    #[test]
+    #[should_panic]
+    fn letter_paired_with_itself() {
+        let mut pairs = key();
+        pairs[0] = ('a', 'a');
+        KamaSutra::new(pairs);
+    }
+
+    #[test]
+    #[should_panic]
+    fn letter_in_two_pairs() {
+        let mut pairs = key();
+        pairs[0] = ('b', 'z');
+        KamaSutra::new(pairs);
+    }
+
+    #[test]
+    #[should_panic]
+    fn pair_with_symbols() {
+        let mut pairs = key();
+        pairs[0] = ('a', '!');
+        KamaSutra::new(pairs);
+    }
+}
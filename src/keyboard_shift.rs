@@ -0,0 +1,325 @@
+//! The Keyboard Shift cipher replaces each character of the message with the key that is a
+//! number of positions away from it on a computer keyboard. It is the keyboard equivalent of the
+//! Caesar cipher, and is often found in puzzles and capture the flag competitions.
+//!
+//! Each `Layout` is treated as a grid of four rows of ten keys: the digits, followed by the three
+//! rows of letters. Shifting `Left` or `Right` moves along a row, while shifting `Up` or `Down`
+//! moves to the key in the same column of another row. Both wrap around at the edge of the grid.
+//! For example, on a `Qwerty` keyboard shifting right by one turns `p` into `q`, while shifting
+//! up by one turns `a` into `q`.
+//!
+//! Characters that are not on the keyboard (such as whitespace) are left unchanged. The case of a
+//! letter is preserved, unless it is shifted onto a key that is not a letter.
+//!
+use crate::common::cipher::Cipher;
+
+/// The keyboard layouts that can be used by the Keyboard Shift cipher.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// The QWERTY layout, used for English.
+    Qwerty,
+    /// The AZERTY layout, used for French.
+    Azerty,
+    /// The Dvorak simplified keyboard layout.
+    Dvorak,
+}
+
+impl Layout {
+    /// The rows of keys of the layout, from top to bottom.
+    ///
+    fn rows(self) -> [&'static str; 4] {
+        match self {
+            Layout::Qwerty => ["1234567890", "qwertyuiop", "asdfghjkl;", "zxcvbnm,./"],
+            Layout::Azerty => ["1234567890", "azertyuiop", "qsdfghjklm", "wxcvbn,;:!"],
+            Layout::Dvorak => ["1234567890", "',.pyfgcrl", "aoeuidhtns", ";qjkxbmwvz"],
+        }
+    }
+}
+
+/// The directions that the keys can be shifted in by the Keyboard Shift cipher.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Along the row, towards the left of the keyboard.
+    Left,
+    /// Along the row, towards the right of the keyboard.
+    Right,
+    /// To the row above.
+    Up,
+    /// To the row below.
+    Down,
+}
+
+const ROWS: usize = 4;
+const COLUMNS: usize = 10;
+
+/// A Keyboard Shift cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyboardShift {
+    keys: Vec<Vec<char>>,
+    direction: Direction,
+    shift: usize,
+}
+
+/// The key of a Keyboard Shift cipher.
+///
+/// This struct is given to the `new()` method of `KeyboardShift`. See its documentation for
+/// more.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyboardShiftKey {
+    /// The keyboard layout that the keys are shifted on.
+    pub layout: Layout,
+    /// The direction that each key is shifted in during encryption.
+    pub direction: Direction,
+    /// The number of keys (or rows) that each key is shifted by.
+    pub shift: usize,
+}
+
+impl Cipher for KeyboardShift {
+    type Key = KeyboardShiftKey;
+    type Algorithm = KeyboardShift;
+
+    /// Initialise a Keyboard Shift cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `KeyboardShiftKey`.
+    ///
+    /// # Panics
+    /// * The `shift` is 0.
+    ///
+    fn new(key: KeyboardShiftKey) -> KeyboardShift {
+        let KeyboardShiftKey {
+            layout,
+            direction,
+            shift,
+        } = key;
+        if shift == 0 {
+            panic!("The shift must be greater than 0.");
+        }
+
+        KeyboardShift {
+            keys: layout.rows().iter().map(|r| r.chars().collect()).collect(),
+            direction,
+            shift,
+        }
+    }
+
+    /// Encrypt a message using a Keyboard Shift cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::keyboard_shift::{Direction, Layout};
+    /// use cipher_crypt::{Cipher, KeyboardShift, KeyboardShiftKey};
+    ///
+    /// let k = KeyboardShift::new(KeyboardShiftKey {
+    ///     layout: Layout::Qwerty,
+    ///     direction: Direction::Right,
+    ///     shift: 1,
+    /// });
+    /// assert_eq!("Syysvl sy fsem", k.encrypt("Attack at dawn").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        Ok(self.substitute(message, self.direction))
+    }
+
+    /// Decrypt a message using a Keyboard Shift cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::keyboard_shift::{Direction, Layout};
+    /// use cipher_crypt::{Cipher, KeyboardShift, KeyboardShiftKey};
+    ///
+    /// let k = KeyboardShift::new(KeyboardShiftKey {
+    ///     layout: Layout::Qwerty,
+    ///     direction: Direction::Right,
+    ///     shift: 1,
+    /// });
+    /// assert_eq!("Attack at dawn", k.decrypt("Syysvl sy fsem").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let reverse = match self.direction {
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+        };
+
+        Ok(self.substitute(ciphertext, reverse))
+    }
+
+    /// Whether a character is foreign to the cipher, being none of the keys of the layout.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        let lower = c.to_ascii_lowercase();
+        !self.keys.iter().any(|row| row.contains(&lower))
+    }
+}
+
+impl KeyboardShift {
+    /// Replace each key within the `text` with the key `self.shift` positions away from it in the
+    /// given `direction`.
+    ///
+    fn substitute(&self, text: &str, direction: Direction) -> String {
+        text.chars()
+            .map(|c| {
+                let lower = c.to_ascii_lowercase();
+                let position = (0..ROWS).find_map(|r| {
+                    self.keys[r]
+                        .iter()
+                        .position(|&k| k == lower)
+                        .map(|col| (r, col))
+                });
+
+                match position {
+                    Some((row, col)) => {
+                        let (row, col) = match direction {
+                            Direction::Left => {
+                                (row, (col + COLUMNS - self.shift % COLUMNS) % COLUMNS)
+                            }
+                            Direction::Right => (row, (col + self.shift) % COLUMNS),
+                            Direction::Up => ((row + ROWS - self.shift % ROWS) % ROWS, col),
+                            Direction::Down => ((row + self.shift) % ROWS, col),
+                        };
+
+                        if c.is_uppercase() {
+                            self.keys[row][col].to_ascii_uppercase()
+                        } else {
+                            self.keys[row][col]
+                        }
+                    }
+                    None => c,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::policy::ForeignCharPolicy;
+
+    #[test]
+    fn encrypt_test() {
+        let k = KeyboardShift::new(KeyboardShiftKey {
+            layout: Layout::Qwerty,
+            direction: Direction::Left,
+            shift: 1,
+        });
+        assert_eq!(
+            "Nwwr nw vt rgw eucwe",
+            k.encrypt("Meet me by the river").unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let k = KeyboardShift::new(KeyboardShiftKey {
+            layout: Layout::Qwerty,
+            direction: Direction::Left,
+            shift: 1,
+        });
+        assert_eq!(
+            "Meet me by the river",
+            k.decrypt("Nwwr nw vt rgw eucwe").unwrap()
+        );
+    }
+
+    #[test]
+    fn shift_rows() {
+        let up = KeyboardShift::new(KeyboardShiftKey {
+            layout: Layout::Qwerty,
+            direction: Direction::Up,
+            shift: 1,
+        });
+        let down = KeyboardShift::new(KeyboardShiftKey {
+            layout: Layout::Qwerty,
+            direction: Direction::Down,
+            shift: 3,
+        });
+
+        assert_eq!("qax", up.encrypt("az2").unwrap());
+        assert_eq!(
+            up.encrypt("hello world").unwrap(),
+            down.encrypt("hello world").unwrap()
+        );
+    }
+
+    #[test]
+    fn wrap_around_row() {
+        let k = KeyboardShift::new(KeyboardShiftKey {
+            layout: Layout::Qwerty,
+            direction: Direction::Right,
+            shift: 12,
+        });
+        assert_eq!("ew", k.encrypt("qp").unwrap());
+    }
+
+    #[test]
+    fn other_layouts() {
+        let azerty = KeyboardShift::new(KeyboardShiftKey {
+            layout: Layout::Azerty,
+            direction: Direction::Right,
+            shift: 1,
+        });
+        let dvorak = KeyboardShift::new(KeyboardShiftKey {
+            layout: Layout::Dvorak,
+            direction: Direction::Right,
+            shift: 1,
+        });
+
+        assert_eq!("zertyu", azerty.encrypt("azerty").unwrap());
+        assert_eq!("oeuidh", dvorak.encrypt("aoeuid").unwrap());
+    }
+
+    #[test]
+    fn case_lost_on_symbol() {
+        let k = KeyboardShift::new(KeyboardShiftKey {
+            layout: Layout::Qwerty,
+            direction: Direction::Right,
+            shift: 1,
+        });
+        assert_eq!(";", k.encrypt("L").unwrap());
+    }
+
+    #[test]
+    fn with_utf8() {
+        let k = KeyboardShift::new(KeyboardShiftKey {
+            layout: Layout::Dvorak,
+            direction: Direction::Down,
+            shift: 2,
+        });
+        let m = "Peace 🗡️ freedom and liberty";
+
+        assert_eq!(m, k.decrypt(&k.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_shift() {
+        KeyboardShift::new(KeyboardShiftKey {
+            layout: Layout::Qwerty,
+            direction: Direction::Left,
+            shift: 0,
+        });
+    }
+
+    #[test]
+    fn strip_keeps_keys() {
+        let k = KeyboardShift::new(KeyboardShiftKey {
+            layout: Layout::Qwerty,
+            direction: Direction::Right,
+            shift: 1,
+        });
+        assert_eq!(
+            k.encrypt("Meetmeat10p.m."),
+            k.encrypt_with_policy("Meet me at 10 p.m.!", ForeignCharPolicy::Strip)
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! The Keyword cipher is a monoalphabetic substitution cipher, where the substitution alphabet is
+//! derived from a keyword.
+//!
+//! The keyword (with any duplicate letters removed) is written out first, followed by the
+//! remaining letters of the alphabet in order. For example, the keyword `zebras` produces the
+//! substitution alphabet `zebrascdfghijklmnopqtuvwxy`. Each letter of the message is then
+//! replaced with the letter at the same position in the substitution alphabet.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::{ByteCipher, CharCipher, Cipher};
+use crate::common::{keygen, substitute};
+
+/// A Keyword cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Keyword {
+    substitution: Vec<usize>,
+}
+
+impl Keyword {
+    /// Initialise a Keyword cipher given a specific keyword.
+    ///
+    /// # Panics
+    /// * The `keyword` is empty.
+    /// * The `keyword` contains a non-alphabetic symbol.
+    ///
+    pub fn new(keyword: impl Into<String>) -> Keyword {
+        let keyword = keyword.into();
+        if keyword.is_empty() {
+            panic!("The keyword is empty.");
+        }
+
+        let substitution = keygen::keyed_alphabet(&keyword, &alphabet::STANDARD, false)
+            .chars()
+            .map(|c| alphabet::STANDARD.find_position(c).unwrap())
+            .collect();
+
+        Keyword { substitution }
+    }
+}
+
+impl Cipher for Keyword {
+    type Key = String;
+    type Algorithm = Keyword;
+
+    /// Initialise a Keyword cipher given a specific keyword, as by `Keyword::new()`.
+    ///
+    fn new(keyword: String) -> Keyword {
+        Keyword::new(keyword)
+    }
+
+    /// Encrypt a message using a Keyword cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Keyword};
+    ///
+    /// let k = Keyword::new("zebras");
+    /// assert_eq!(
+    ///     "Siaa zq lkba. Va zoa rfpbluaoar!",
+    ///     k.encrypt("Flee at once. We are discovered!").unwrap()
+    /// );
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        Ok(self.encrypt_chars(message.chars()).collect())
+    }
+
+    /// Encrypt a message onto the end of `out`, one character at a time.
+    ///
+    fn encrypt_into(&self, message: &str, out: &mut String) -> Result<(), &'static str> {
+        out.extend(self.encrypt_chars(message.chars()));
+        Ok(())
+    }
+
+    /// Decrypt a message using a Keyword cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Keyword};
+    ///
+    /// let k = Keyword::new("zebras");
+    /// assert_eq!(
+    ///     "Flee at once. We are discovered!",
+    ///     k.decrypt("Siaa zq lkba. Va zoa rfpbluaoar!").unwrap()
+    /// );
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        Ok(self.decrypt_chars(ciphertext.chars()).collect())
+    }
+}
+
+impl CharCipher for Keyword {
+    fn encrypt_char(&self, c: char) -> char {
+        substitute::shift_char(c, |idx| self.substitution[idx])
+    }
+
+    fn decrypt_char(&self, c: char) -> char {
+        substitute::shift_char(c, |idx| {
+            self.substitution.iter().position(|&s| s == idx).unwrap()
+        })
+    }
+}
+
+impl ByteCipher for Keyword {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_test() {
+        let k = Keyword::new(String::from("keyword"));
+        assert_eq!("kqqkyf kq wkui", k.encrypt("attack at dawn").unwrap());
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let k = Keyword::new(String::from("keyword"));
+        assert_eq!("attack at dawn", k.decrypt("kqqkyf kq wkui").unwrap());
+    }
+
+    #[test]
+    fn mixed_case_keyword() {
+        let k1 = Keyword::new(String::from("ZeBrAs"));
+        let k2 = Keyword::new(String::from("zebras"));
+
+        let m = "Flee at once!";
+        assert_eq!(k1.encrypt(m).unwrap(), k2.encrypt(m).unwrap());
+    }
+
+    #[test]
+    fn with_utf8() {
+        let k = Keyword::new(String::from("utfeight"));
+        let m = "Peace 🗡️ Freedom and Liberty!";
+
+        assert_eq!(m, k.decrypt(&k.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_keyword() {
+        Keyword::new(String::from(""));
+    }
+
+    #[test]
+    #[should_panic]
+    fn keyword_with_symbols() {
+        Keyword::new(String::from("zebr@s"));
+    }
+}
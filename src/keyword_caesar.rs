@@ -0,0 +1,209 @@
+//! The Keyword Caesar cipher combines the Keyword cipher with the Caesar cipher. It is a common
+//! example in cryptography textbooks.
+//!
+//! A substitution alphabet is derived from a keyword, as in the Keyword cipher, and is then
+//! shifted along the plain alphabet so that it begins under a chosen key letter. For example,
+//! with the keyword `zebras` and the key letter `e` (a shift of 4):
+//!
+//! ```text
+//! Plain:  abcdefghijklmnopqrstuvwxyz
+//! Cipher: vwxyzebrascdfghijklmnopqtu
+//! ```
+//!
+//! With a shift of 0 this is identical to the Keyword cipher.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::{ByteCipher, CharCipher, Cipher};
+use crate::common::{keygen, substitute};
+
+/// A Keyword Caesar cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeywordCaesar {
+    substitution: Vec<usize>,
+}
+
+/// The key of a Keyword Caesar cipher.
+///
+/// This struct is given to the `new()` method of `KeywordCaesar`. See its documentation for
+/// more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeywordCaesarKey {
+    /// The keyword used to derive the substitution alphabet.
+    pub keyword: String,
+    /// The position within the plain alphabet that the substitution alphabet begins under.
+    pub shift: usize,
+}
+
+impl Cipher for KeywordCaesar {
+    type Key = KeywordCaesarKey;
+    type Algorithm = KeywordCaesar;
+
+    /// Initialise a Keyword Caesar cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `KeywordCaesarKey`.
+    ///
+    /// # Panics
+    /// * The `keyword` is empty.
+    /// * The `keyword` contains a non-alphabetic symbol.
+    /// * `shift` is not in the inclusive range `0 - 25`.
+    ///
+    fn new(key: KeywordCaesarKey) -> KeywordCaesar {
+        let KeywordCaesarKey { keyword, shift } = key;
+        if keyword.is_empty() {
+            panic!("The keyword is empty.");
+        }
+
+        if shift > 25 {
+            panic!("The shift factor must be within the range 0 <= n <= 25.");
+        }
+
+        let keyed: Vec<usize> = keygen::keyed_alphabet(&keyword, &alphabet::STANDARD, false)
+            .chars()
+            .map(|c| alphabet::STANDARD.find_position(c).unwrap())
+            .collect();
+
+        KeywordCaesar {
+            substitution: (0..26)
+                .map(|i| keyed[alphabet::STANDARD.modulo(i as isize - shift as isize)])
+                .collect(),
+        }
+    }
+
+    /// Encrypt a message using a Keyword Caesar cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, KeywordCaesar, KeywordCaesarKey};
+    ///
+    /// let k = KeywordCaesar::new(KeywordCaesarKey { keyword: String::from("zebras"), shift: 4 });
+    /// assert_eq!(
+    ///     "Edzz vm hgxz. Pz vkz yalxhozkzy!",
+    ///     k.encrypt("Flee at once. We are discovered!").unwrap()
+    /// );
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        Ok(self.encrypt_chars(message.chars()).collect())
+    }
+
+    /// Encrypt a message onto the end of `out`, one character at a time.
+    ///
+    fn encrypt_into(&self, message: &str, out: &mut String) -> Result<(), &'static str> {
+        out.extend(self.encrypt_chars(message.chars()));
+        Ok(())
+    }
+
+    /// Decrypt a message using a Keyword Caesar cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, KeywordCaesar, KeywordCaesarKey};
+    ///
+    /// let k = KeywordCaesar::new(KeywordCaesarKey { keyword: String::from("zebras"), shift: 4 });
+    /// assert_eq!(
+    ///     "Flee at once. We are discovered!",
+    ///     k.decrypt("Edzz vm hgxz. Pz vkz yalxhozkzy!").unwrap()
+    /// );
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        Ok(self.decrypt_chars(ciphertext.chars()).collect())
+    }
+}
+
+impl CharCipher for KeywordCaesar {
+    fn encrypt_char(&self, c: char) -> char {
+        substitute::shift_char(c, |idx| self.substitution[idx])
+    }
+
+    fn decrypt_char(&self, c: char) -> char {
+        substitute::shift_char(c, |idx| {
+            self.substitution.iter().position(|&s| s == idx).unwrap()
+        })
+    }
+}
+
+impl ByteCipher for KeywordCaesar {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Keyword;
+
+    #[test]
+    fn encrypt_test() {
+        let k = KeywordCaesar::new(KeywordCaesarKey {
+            keyword: String::from("keyword"),
+            shift: 7,
+        });
+        assert_eq!("qhhqtw qh uqld", k.encrypt("attack at dawn").unwrap());
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let k = KeywordCaesar::new(KeywordCaesarKey {
+            keyword: String::from("keyword"),
+            shift: 7,
+        });
+        assert_eq!("attack at dawn", k.decrypt("qhhqtw qh uqld").unwrap());
+    }
+
+    #[test]
+    fn no_shift_matches_keyword() {
+        let m = "Flee at once. We are discovered!";
+        assert_eq!(
+            Keyword::new(String::from("zebras")).encrypt(m).unwrap(),
+            KeywordCaesar::new(KeywordCaesarKey {
+                keyword: String::from("zebras"),
+                shift: 0
+            })
+            .encrypt(m)
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn with_utf8() {
+        let k = KeywordCaesar::new(KeywordCaesarKey {
+            keyword: String::from("utfeight"),
+            shift: 13,
+        });
+        let m = "Peace 🗡️ Freedom and Liberty!";
+
+        assert_eq!(m, k.decrypt(&k.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_keyword() {
+        KeywordCaesar::new(KeywordCaesarKey {
+            keyword: String::from(""),
+            shift: 3,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn keyword_with_symbols() {
+        KeywordCaesar::new(KeywordCaesarKey {
+            keyword: String::from("zebr@s"),
+            shift: 3,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn shift_too_large() {
+        KeywordCaesar::new(KeywordCaesarKey {
+            keyword: String::from("zebras"),
+            shift: 26,
+        });
+    }
+}
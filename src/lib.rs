@@ -24,44 +24,128 @@
 //! encrypt data of any real value.
 //!
 extern crate num;
+extern crate rand;
 extern crate rulinalg;
 
+#[cfg(feature = "wordlist")]
 #[macro_use]
 extern crate lazy_static;
 extern crate lipsum;
-#[macro_use]
-extern crate maplit;
 
 pub mod adfgvx;
 pub mod affine;
+pub mod affine_hill;
+pub mod analysis;
 pub mod autokey;
+pub mod ave_maria;
 pub mod baconian;
+pub mod beaufort;
+pub mod bifid;
 pub mod caesar;
+pub mod caesar_box;
+pub mod cardan_grille;
+pub mod cipher_kind;
 pub mod columnar_transposition;
 mod common;
+pub mod dryad;
 pub mod fractionated_morse;
+pub mod great_cipher;
+pub mod gronsfeld;
 pub mod hill;
+pub mod homophonic;
+pub mod jefferson_wheel;
+pub mod kama_sutra;
+pub mod keyboard_shift;
+pub mod keyword;
+pub mod keyword_caesar;
+pub mod m209;
+pub mod nomenclator;
+pub mod null_cipher;
+pub mod one_time_pad;
+pub mod pigpen;
+pub mod pipeline;
 pub mod playfair;
 pub mod polybius;
 pub mod porta;
+pub mod porta_digraphic;
+pub mod quagmire;
 pub mod railfence;
+pub mod redefence;
+pub mod registry;
 pub mod rot13;
+pub mod rot18;
+pub mod rot5;
+pub mod rs44;
 pub mod scytale;
+pub mod simple_substitution;
+pub mod syllabary;
+pub mod tableau;
+pub mod tap_code;
+pub mod trifid;
+pub mod uesugi;
+pub mod vectors;
+pub mod vic;
 pub mod vigenere;
+pub mod wheatstone;
+pub mod wolseley;
 
-pub use crate::adfgvx::ADFGVX;
-pub use crate::affine::Affine;
+pub use crate::adfgvx::{ADFGVXKey, ADFGVX};
+pub use crate::affine::{Affine, AffineKey};
+pub use crate::affine_hill::{AffineHill, AffineHillKey};
 pub use crate::autokey::Autokey;
-pub use crate::baconian::Baconian;
+pub use crate::ave_maria::AveMaria;
+pub use crate::baconian::{Baconian, BaconianOptions};
+pub use crate::beaufort::Beaufort;
+pub use crate::bifid::{Bifid, BifidKey};
 pub use crate::caesar::Caesar;
-pub use crate::columnar_transposition::ColumnarTransposition;
-pub use crate::common::cipher::Cipher;
+pub use crate::caesar_box::CaesarBox;
+pub use crate::cardan_grille::{CardanGrille, CardanGrilleKey};
+pub use crate::cipher_kind::CipherKind;
+pub use crate::columnar_transposition::{ColumnarTransposition, ColumnarTranspositionKey};
+pub use crate::common::alphabet;
+pub use crate::common::cipher::{ByteCipher, CharCipher, Cipher, CipherChars, DynCipher, Redacted};
+pub use crate::common::cipher_error::CipherError;
+pub use crate::common::grouping::Grouping;
+pub use crate::common::key_problem::KeyProblem;
+pub use crate::common::morse;
+pub use crate::common::options::EncryptOptions;
+pub use crate::common::policy::{CasePolicy, ForeignCharPolicy};
+pub use crate::dryad::{Dryad, DryadKey};
 pub use crate::fractionated_morse::FractionatedMorse;
+pub use crate::great_cipher::GreatCipher;
+pub use crate::gronsfeld::Gronsfeld;
 pub use crate::hill::Hill;
-pub use crate::playfair::Playfair;
-pub use crate::polybius::Polybius;
+pub use crate::homophonic::Homophonic;
+pub use crate::jefferson_wheel::{JeffersonWheel, JeffersonWheelKey};
+pub use crate::kama_sutra::KamaSutra;
+pub use crate::keyboard_shift::{KeyboardShift, KeyboardShiftKey};
+pub use crate::keyword::Keyword;
+pub use crate::keyword_caesar::{KeywordCaesar, KeywordCaesarKey};
+pub use crate::m209::{M209Key, M209};
+pub use crate::nomenclator::{Nomenclator, NomenclatorKey};
+pub use crate::null_cipher::{NullCipher, NullCipherKey};
+pub use crate::one_time_pad::OneTimePad;
+pub use crate::pigpen::Pigpen;
+pub use crate::pipeline::Pipeline;
+pub use crate::playfair::{Playfair, PlayfairKey};
+pub use crate::polybius::{Polybius, PolybiusKey};
 pub use crate::porta::Porta;
+pub use crate::porta_digraphic::{PortaDigraphic, PortaDigraphicKey};
+pub use crate::quagmire::{Quagmire, QuagmireKey};
 pub use crate::railfence::Railfence;
+pub use crate::redefence::{Redefence, RedefenceKey};
 pub use crate::rot13 as Rot13;
+pub use crate::rot18 as Rot18;
+pub use crate::rot5 as Rot5;
+pub use crate::rs44::{RS44Key, RS44};
 pub use crate::scytale::Scytale;
+pub use crate::simple_substitution::SimpleSubstitution;
+pub use crate::syllabary::{Syllabary, SyllabaryKey};
+pub use crate::tableau::{TableauCipher, TableauKey};
+pub use crate::tap_code::{TapCode, TapCodeKey};
+pub use crate::trifid::{Trifid, TrifidKey};
+pub use crate::uesugi::{Uesugi, UesugiKey};
+pub use crate::vic::{VICKey, VIC};
 pub use crate::vigenere::Vigenere;
+pub use crate::wheatstone::{Wheatstone, WheatstoneKey};
+pub use crate::wolseley::Wolseley;
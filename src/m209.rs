@@ -0,0 +1,563 @@
+//! The M-209 was a portable, mechanical cipher machine designed by Boris Hagelin and used by the
+//! US military during World War II and the Korean War.
+//!
+//! The machine has six key wheels, with 26, 25, 23, 21, 19 and 17 letters respectively. Each
+//! letter of a wheel has a pin that can be set to be effective or ineffective. Behind the wheels
+//! is a lug cage of 27 bars, each of which has two lugs that can be placed against one of the six
+//! wheels (or left in a neutral position).
+//!
+//! To encipher a letter, every bar that has a lug against a wheel with an effective pin is
+//! shifted. The number of shifted bars gives the key `k`, and the letter is enciphered as
+//! `C = (Z - P + k) mod 26`. This is the same as the Beaufort cipher using a reversed alphabet,
+//! making the machine reciprocal. After each letter all six wheels advance by one position.
+//!
+//! As the machine could not print spaces, the letter `Z` was used to separate words. This
+//! implementation enciphers whitespace as `Z`, and deciphers `Z` as a space.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
+
+// The letters of each key wheel
+const WHEELS: [&str; 6] = [
+    "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+    "ABCDEFGHIJKLMNOPQRSTUVXYZ",
+    "ABCDEFGHIJKLMNOPQRSTUVX",
+    "ABCDEFGHIJKLMNOPQRSTU",
+    "ABCDEFGHIJKLMNOPQRS",
+    "ABCDEFGHIJKLMNOPQ",
+];
+
+// The pin sensed by the lug cage is ahead of the letter displayed on each wheel
+const EFFECTIVE_OFFSETS: [usize; 6] = [15, 14, 13, 12, 11, 10];
+
+// The number of bars in the lug cage
+const BARS: usize = 27;
+
+// The number of letters printed per ciphertext group
+const GROUP_SIZE: usize = 5;
+
+/// An M-209 cipher machine.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct M209 {
+    lugs: Vec<(usize, usize)>,
+    pins: Vec<Vec<bool>>,
+    positions: Vec<usize>,
+}
+
+/// The key of an M-209 cipher machine.
+///
+/// This struct is given to the `new()` method of `M209`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct M209Key {
+    /// The positions of the two lugs on each of the 27 bars. A position is either a wheel
+    /// number `1-6`, or `0` for a neutral lug.
+    pub lugs: Vec<(usize, usize)>,
+    /// The letters of each of the six wheels which have an effective pin.
+    pub pins: Vec<String>,
+    /// The six letters that the wheels are initially set to.
+    pub positions: String,
+}
+
+impl Cipher for M209 {
+    type Key = M209Key;
+    type Algorithm = M209;
+
+    /// Initialise an M-209 cipher machine.
+    ///
+    /// The parts of the `key` are described by the fields of `M209Key`.
+    ///
+    /// # Panics
+    /// * There are not 27 bars in the `lugs`.
+    /// * A lug position is greater than 6, or both lugs of a bar are against the same wheel.
+    /// * There are not six wheels in the `pins` or `positions`.
+    /// * A letter in the `pins` or `positions` does not appear on its wheel.
+    ///
+    fn new(key: M209Key) -> M209 {
+        if key.lugs.len() != BARS {
+            panic!("The lug cage must contain 27 bars.");
+        }
+        for &(a, b) in &key.lugs {
+            if a > 6 || b > 6 || (a != 0 && a == b) {
+                panic!("The lugs of a bar must be against different wheels, or neutral.");
+            }
+        }
+
+        if key.pins.len() != WHEELS.len() {
+            panic!("There must be pin settings for six wheels.");
+        }
+        let pins = WHEELS
+            .iter()
+            .zip(key.pins.iter())
+            .map(|(wheel, effective)| {
+                for c in effective.chars() {
+                    wheel_position(wheel, c).expect("A pin is not a letter on its wheel.");
+                }
+                wheel
+                    .chars()
+                    .map(|c| effective.to_uppercase().contains(c))
+                    .collect()
+            })
+            .collect();
+
+        let positions: Vec<char> = key.positions.chars().collect();
+        if positions.len() != WHEELS.len() {
+            panic!("There must be a starting position for six wheels.");
+        }
+        let positions = WHEELS
+            .iter()
+            .zip(positions)
+            .map(|(wheel, c)| wheel_position(wheel, c).expect("A position is not on its wheel."))
+            .collect();
+
+        M209 {
+            lugs: key.lugs,
+            pins,
+            positions,
+        }
+    }
+
+    /// Encrypt a message using an M-209 cipher machine.
+    ///
+    /// Whitespace in the message is enciphered as the letter `Z`, and the ciphertext is printed
+    /// in groups of five letters.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, M209};
+    ///
+    /// let list = "lugs = 1-0 2-0*4 0-3*3 0-4*2 0-5*8 0-6*9
+    ///             wheel1 = ABDHIKMNSTVW
+    ///             wheel2 = ADEGJKLORSUX
+    ///             wheel3 = ABGHJLMNRSTUX
+    ///             wheel4 = CEFHIMNPSTU
+    ///             wheel5 = BDEFHIMNPS
+    ///             wheel6 = ABDHKNOQ";
+    ///
+    /// let m = M209::from_key_list(list, "AAAAAA").unwrap();
+    /// let c = m.encrypt("Attack at dawn").unwrap();
+    /// assert_eq!("ATTACK AT DAWN", m.decrypt(&c).unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.validate_message(message)?;
+
+        let letters: Vec<char> = message
+            .chars()
+            .map(|c| if c.is_whitespace() { 'Z' } else { c })
+            .collect();
+
+        Ok(self
+            .transform(&letters)
+            .chunks(GROUP_SIZE)
+            .map(|g| g.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join(" "))
+    }
+
+    /// Decrypt a message using an M-209 cipher machine.
+    ///
+    /// Whitespace in the ciphertext is ignored, and the letter `Z` is deciphered as a space.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, M209, M209Key};
+    ///
+    /// let lugs = vec![(0, 0); 27];
+    /// let pins = vec![String::new(); 6];
+    ///
+    /// // With all lugs neutral, the machine reverses the alphabet
+    /// let m = M209::new(M209Key { lugs, pins, positions: String::from("AAAAAA") });
+    /// assert_eq!("ATTACK AT DAWN", m.decrypt("ZGGZX PAZGA WZDM").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let letters: Vec<char> = ciphertext.chars().filter(|c| !c.is_whitespace()).collect();
+        if !letters
+            .iter()
+            .all(|&c| alphabet::STANDARD.is_valid(&c.to_string()))
+        {
+            return Err("Ciphertext contains a non-alphabetic symbol.");
+        }
+
+        Ok(self
+            .transform(&letters)
+            .into_iter()
+            .map(|c| if c == 'Z' { ' ' } else { c })
+            .collect())
+    }
+
+    /// Check that a message can be encrypted with an M-209 cipher machine.
+    ///
+    /// Returns `Err` if the message contains a symbol that is not alphabetic or whitespace.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, M209, M209Key};
+    ///
+    /// let m = M209::new(M209Key {
+    ///     lugs: vec![(0, 0); 27],
+    ///     pins: vec![String::new(); 6],
+    ///     positions: String::from("AAAAAA"),
+    /// });
+    ///
+    /// assert!(m.validate_message("Attack at dawn").is_ok());
+    /// assert!(m.validate_message("Attack at 5am").is_err());
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        CipherError::find(
+            message,
+            "Message contains a symbol that is not alphabetic or whitespace.",
+            |c| !c.is_whitespace() && alphabet::STANDARD.find_position(c).is_none(),
+        )
+    }
+
+    /// Whether a character is foreign to the cipher, being neither a letter nor whitespace, as
+    /// the whitespace separates the words of the message.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        !(c.is_ascii_alphabetic() || c.is_whitespace())
+    }
+}
+
+impl M209 {
+    /// Initialise an M-209 cipher machine from a key list.
+    ///
+    /// Each line of a key list has the form `name = value`, where the names are ...
+    ///
+    /// * `lugs` - The lug positions of each bar as a pair `a-b`, separated by whitespace. A
+    ///   repeated pair can be written with a count, for example `0-4*3`.
+    /// * `wheel1` to `wheel6` - The letters of each wheel that have an effective pin.
+    ///
+    /// Blank lines, and lines beginning with `#`, are ignored. The `positions` are the six
+    /// letters that the wheels are initially set to.
+    ///
+    /// Will return `Err` if the key list is malformed or incomplete. The settings are then
+    /// validated as per `new()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, M209};
+    ///
+    /// let list = "# Key list indicator: AB
+    ///             lugs = 1-0 2-0*4 0-3*3 0-4*2 0-5*8 0-6*9
+    ///             wheel1 = ABDHIKMNSTVW
+    ///             wheel2 = ADEGJKLORSUX
+    ///             wheel3 = ABGHJLMNRSTUX
+    ///             wheel4 = CEFHIMNPSTU
+    ///             wheel5 = BDEFHIMNPS
+    ///             wheel6 = ABDHKNOQ";
+    ///
+    /// assert!(M209::from_key_list(list, "AAAAAA").is_ok());
+    /// assert!(M209::from_key_list("lugs = 1-0*27", "AAAAAA").is_err());
+    /// ```
+    ///
+    pub fn from_key_list(list: &str, positions: &str) -> Result<M209, &'static str> {
+        let mut lugs = None;
+        let mut pins = vec![None; WHEELS.len()];
+
+        for line in list.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.splitn(2, '=');
+            let name = parts.next().unwrap_or("").trim();
+            let value = parts
+                .next()
+                .ok_or("Key list entry is missing a value.")?
+                .trim();
+
+            match name {
+                "lugs" => lugs = Some(parse_lugs(value)?),
+                _ if name.starts_with("wheel") => {
+                    let n: usize = name[5..].parse().map_err(|_| "Unknown key list wheel.")?;
+                    if n < 1 || n > WHEELS.len() {
+                        return Err("Unknown key list wheel.");
+                    }
+                    pins[n - 1] = Some(value.to_string());
+                }
+                _ => return Err("Unknown key list entry."),
+            }
+        }
+
+        let lugs = lugs.ok_or("Key list is missing the lug settings.")?;
+        if lugs.len() != BARS {
+            return Err("Key list must contain lug settings for 27 bars.");
+        }
+        if lugs
+            .iter()
+            .any(|&(a, b)| a > 6 || b > 6 || (a != 0 && a == b))
+        {
+            return Err("Key list contains an invalid lug setting.");
+        }
+
+        let pins: Option<Vec<String>> = pins.into_iter().collect();
+        let pins = pins.ok_or("Key list is missing the pin settings for a wheel.")?;
+        for (wheel, effective) in WHEELS.iter().zip(pins.iter()) {
+            if effective
+                .chars()
+                .any(|c| wheel_position(wheel, c).is_none())
+            {
+                return Err("Key list contains a pin that is not on its wheel.");
+            }
+        }
+
+        let valid_positions = positions.chars().count() == WHEELS.len()
+            && WHEELS
+                .iter()
+                .zip(positions.chars())
+                .all(|(wheel, c)| wheel_position(wheel, c).is_some());
+        if !valid_positions {
+            return Err("Invalid wheel positions.");
+        }
+
+        Ok(M209::new(M209Key {
+            lugs,
+            pins,
+            positions: positions.to_string(),
+        }))
+    }
+
+    /// Encipher (or decipher) a sequence of letters, advancing the wheels after each letter.
+    ///
+    fn transform(&self, letters: &[char]) -> Vec<char> {
+        letters
+            .iter()
+            .enumerate()
+            .map(|(step, &c)| {
+                let k = self.key_at(step) as isize;
+                let p = alphabet::STANDARD.find_position(c).unwrap() as isize;
+
+                alphabet::STANDARD.get_letter(alphabet::STANDARD.modulo(25 - p + k), true)
+            })
+            .collect()
+    }
+
+    /// The number of bars shifted by the lug cage, once the wheels have advanced `step` times.
+    ///
+    fn key_at(&self, step: usize) -> usize {
+        let effective: Vec<bool> = (0..WHEELS.len())
+            .map(|w| {
+                let size = self.pins[w].len();
+                self.pins[w][(self.positions[w] + step + EFFECTIVE_OFFSETS[w]) % size]
+            })
+            .collect();
+        let shifted = |lug: usize| lug != 0 && effective[lug - 1];
+
+        self.lugs
+            .iter()
+            .filter(|&&(a, b)| shifted(a) || shifted(b))
+            .count()
+    }
+}
+
+/// Find the position of a letter on a key wheel.
+///
+fn wheel_position(wheel: &str, c: char) -> Option<usize> {
+    wheel.chars().position(|w| w == c.to_ascii_uppercase())
+}
+
+/// Parse the lug settings of a key list, such as `1-0 2-0*4 0-3`.
+///
+fn parse_lugs(value: &str) -> Result<Vec<(usize, usize)>, &'static str> {
+    let mut lugs = Vec::new();
+    for setting in value.split_whitespace() {
+        let mut parts = setting.splitn(2, '*');
+        let pair = parts.next().unwrap_or("");
+        let count: usize = match parts.next() {
+            Some(n) => n.parse().map_err(|_| "Invalid lug repetition count.")?,
+            None => 1,
+        };
+
+        let mut positions = pair.splitn(2, '-').map(|p| p.parse::<usize>());
+        match (positions.next(), positions.next()) {
+            (Some(Ok(a)), Some(Ok(b))) => lugs.extend(vec![(a, b); count]),
+            _ => return Err("Invalid lug setting."),
+        }
+    }
+
+    Ok(lugs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::policy::ForeignCharPolicy;
+
+    const KEY_LIST: &str = "lugs = 1-0 2-0*4 0-3*3 0-4*2 0-5*8 0-6*9
+                            wheel1 = ABDHIKMNSTVW
+                            wheel2 = ADEGJKLORSUX
+                            wheel3 = ABGHJLMNRSTUX
+                            wheel4 = CEFHIMNPSTU
+                            wheel5 = BDEFHIMNPS
+                            wheel6 = ABDHKNOQ";
+
+    fn neutral() -> M209 {
+        M209::new(M209Key {
+            lugs: vec![(0, 0); 27],
+            pins: vec![String::new(); 6],
+            positions: String::from("AAAAAA"),
+        })
+    }
+
+    #[test]
+    fn encrypt_test() {
+        // With no bars shifted, the machine reverses the alphabet
+        assert_eq!(
+            "ZGGZX PAZGA WZDM",
+            neutral().encrypt("ATTACK AT DAWN").unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_test() {
+        assert_eq!(
+            "ATTACK AT DAWN",
+            neutral().decrypt("ZGGZXPAZGAWZDM").unwrap()
+        );
+    }
+
+    #[test]
+    fn all_bars_shifted() {
+        let pins = WHEELS.iter().map(|w| w.to_string()).collect();
+        let m = M209::new(M209Key {
+            lugs: vec![(1, 0); 27],
+            pins,
+            positions: String::from("AAAAAA"),
+        });
+
+        // Every bar is shifted, so C = (Z - P + 27) mod 26 = -P mod 26
+        assert_eq!("ABCZY", m.encrypt("AZYBC").unwrap());
+    }
+
+    #[test]
+    fn effective_pin_offset() {
+        // Only the pin sensed when wheel 1 displays 'A' is effective
+        let mut pins = vec![String::new(); 6];
+        pins[0] = String::from("P");
+        let m = M209::new(M209Key {
+            lugs: vec![(1, 0); 27],
+            pins,
+            positions: String::from("AAAAAA"),
+        });
+
+        // Every bar is shifted for the first letter, and none for the second
+        assert_eq!("AZ", m.encrypt("AA").unwrap());
+    }
+
+    #[test]
+    fn reciprocal() {
+        let m = M209::from_key_list(KEY_LIST, "ABCDEF").unwrap();
+        let c = m.encrypt("WE ARE DISCOVERED FLEE AT ONCE").unwrap();
+
+        assert_eq!(c, m.encrypt(&m.decrypt(&c).unwrap()).unwrap());
+        assert_eq!("WE ARE DISCOVERED FLEE AT ONCE", m.decrypt(&c).unwrap());
+    }
+
+    #[test]
+    fn positions_change_ciphertext() {
+        let m1 = M209::from_key_list(KEY_LIST, "AAAAAA").unwrap();
+        let m2 = M209::from_key_list(KEY_LIST, "QRSTMN").unwrap();
+
+        assert_ne!(
+            m1.encrypt("Attack at dawn").unwrap(),
+            m2.encrypt("Attack at dawn").unwrap()
+        );
+    }
+
+    #[test]
+    fn key_list_matches_new() {
+        let mut lugs = vec![(1, 0)];
+        lugs.extend(vec![(2, 0); 4]);
+        lugs.extend(vec![(0, 3); 3]);
+        lugs.extend(vec![(0, 4); 2]);
+        lugs.extend(vec![(0, 5); 8]);
+        lugs.extend(vec![(0, 6); 9]);
+        let pins = [
+            "ABDHIKMNSTVW",
+            "ADEGJKLORSUX",
+            "ABGHJLMNRSTUX",
+            "CEFHIMNPSTU",
+            "BDEFHIMNPS",
+            "ABDHKNOQ",
+        ];
+        let m1 = M209::new(M209Key {
+            lugs,
+            pins: pins.iter().map(|p| p.to_string()).collect(),
+            positions: String::from("AAAAAA"),
+        });
+        let m2 = M209::from_key_list(KEY_LIST, "AAAAAA").unwrap();
+
+        let m = "Attack at dawn";
+        assert_eq!(m1.encrypt(m).unwrap(), m2.encrypt(m).unwrap());
+    }
+
+    #[test]
+    fn key_list_errors() {
+        assert!(M209::from_key_list("lugs = 1-0*27", "AAAAAA").is_err());
+        assert!(M209::from_key_list(&KEY_LIST.replace("0-6*9", "0-6*8"), "AAAAAA").is_err());
+        assert!(M209::from_key_list(&KEY_LIST.replace("0-6*9", "6-6*9"), "AAAAAA").is_err());
+        assert!(M209::from_key_list(&KEY_LIST.replace("wheel6", "wheel7"), "AAAAAA").is_err());
+        assert!(M209::from_key_list(&KEY_LIST.replace("ABDHKNOQ", "ABDHKNOZ"), "AAAAAA").is_err());
+        assert!(M209::from_key_list(KEY_LIST, "AAAAAZ").is_err());
+    }
+
+    #[test]
+    fn invalid_message() {
+        assert!(neutral().encrypt("Attack at 5am").is_err());
+        assert!(neutral().decrypt("ZGGZX 5").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_bar_count() {
+        M209::new(M209Key {
+            lugs: vec![(0, 0); 26],
+            pins: vec![String::new(); 6],
+            positions: String::from("AAAAAA"),
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_pin() {
+        let mut pins = vec![String::new(); 6];
+        pins[1] = String::from("W");
+        M209::new(M209Key {
+            lugs: vec![(0, 0); 27],
+            pins,
+            positions: String::from("AAAAAA"),
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_position() {
+        M209::new(M209Key {
+            lugs: vec![(0, 0); 27],
+            pins: vec![String::new(); 6],
+            positions: String::from("AAAAAR"),
+        });
+    }
+
+    #[test]
+    fn strip_keeps_whitespace() {
+        let m = neutral();
+        assert_eq!(
+            m.encrypt("Attack at dawn"),
+            m.encrypt_with_policy("Attack at dawn!", ForeignCharPolicy::Strip)
+        );
+    }
+}
@@ -0,0 +1,381 @@
+//! A nomenclator combines a code book with a cipher. Nomenclators were the most widely used
+//! systems of secret writing in Europe from the 15th century until the early 19th century.
+//!
+//! The code book replaces common words and phrases, such as the names of people and places, with
+//! code groups. Any word that does not appear in the code book is spelled out using a homophonic
+//! substitution of its letters.
+//!
+//! In this implementation each phrase of the code book is matched against the words of the
+//! message, preferring the longest phrase where several match. Each word that is spelled out is
+//! encrypted as a single group, made up of one homophone for each of its letters.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
+use crate::homophonic::Homophonic;
+
+/// A Nomenclator cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Nomenclator {
+    code_book: Vec<(Vec<String>, String)>,
+    letters: Homophonic,
+}
+
+/// The key of a Nomenclator cipher.
+///
+/// This struct is given to the `new()` method of `Nomenclator`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NomenclatorKey {
+    /// Pairs each word or phrase with the code group that replaces it. The case of the
+    /// phrases, and the whitespace between their words, is ignored.
+    pub code_book: Vec<(String, String)>,
+    /// The cipher used to spell out the words that are not in the code book.
+    pub letters: Homophonic,
+}
+
+impl Cipher for Nomenclator {
+    type Key = NomenclatorKey;
+    type Algorithm = Nomenclator;
+
+    /// Initialise a Nomenclator cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `NomenclatorKey`.
+    ///
+    /// # Panics
+    /// * A phrase is empty, or contains a symbol that is not alphabetic or whitespace.
+    /// * A code group is empty or contains whitespace.
+    /// * A phrase or code group appears more than once.
+    /// * A code group could be mistaken for a word spelled out by the `letters` cipher.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Homophonic, Nomenclator, NomenclatorKey};
+    ///
+    /// let n = Nomenclator::new(NomenclatorKey {
+    ///     code_book: vec![(String::from("the king"), String::from("312"))],
+    ///     letters: Homophonic::from_keyword("cipher"),
+    /// });
+    /// ```
+    ///
+    fn new(key: NomenclatorKey) -> Nomenclator {
+        let NomenclatorKey {
+            code_book: entries,
+            letters,
+        } = key;
+
+        let code_book: Vec<(Vec<String>, String)> = entries
+            .into_iter()
+            .map(|(phrase, code)| {
+                (
+                    phrase.split_whitespace().map(str::to_lowercase).collect(),
+                    code,
+                )
+            })
+            .collect();
+
+        for (i, (phrase, code)) in code_book.iter().enumerate() {
+            if phrase.is_empty() || !phrase.iter().all(|w| alphabet::STANDARD.is_valid(w)) {
+                panic!("A phrase must contain one or more alphabetic words.");
+            }
+            if code.is_empty() || code.chars().any(char::is_whitespace) {
+                panic!("A code group cannot be empty or contain whitespace.");
+            }
+            if code_book[..i].iter().any(|(p, c)| p == phrase || c == code) {
+                panic!("A phrase or code group cannot appear more than once.");
+            }
+            if letters.decrypt(code).is_ok() {
+                panic!("A code group cannot be made up of homophones.");
+            }
+        }
+
+        Nomenclator { code_book, letters }
+    }
+
+    /// Encrypt a message using a Nomenclator cipher.
+    ///
+    /// The groups of the ciphertext are separated by spaces.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Homophonic, Nomenclator, NomenclatorKey};
+    ///
+    /// let n = Nomenclator::new(NomenclatorKey {
+    ///     code_book: vec![(String::from("the king"), String::from("312"))],
+    ///     letters: Homophonic::from_keyword("cipher"),
+    /// });
+    /// let c = n.encrypt("Long live the King").unwrap();
+    ///
+    /// // The words that are not in the code book are spelled out
+    /// assert!(c.ends_with(" 312"));
+    /// assert_eq!("long live the king", n.decrypt(&c).unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.validate_message(message)?;
+
+        let words: Vec<String> = message.split_whitespace().map(str::to_lowercase).collect();
+        let mut groups = Vec::new();
+        let mut i = 0;
+        while i < words.len() {
+            let entry = self
+                .code_book
+                .iter()
+                .filter(|(phrase, _)| words[i..].starts_with(phrase))
+                .max_by_key(|(phrase, _)| phrase.len());
+
+            match entry {
+                Some((phrase, code)) => {
+                    groups.push(code.clone());
+                    i += phrase.len();
+                }
+                None => {
+                    groups.push(self.letters.encrypt(&words[i])?.replace(' ', ""));
+                    i += 1;
+                }
+            }
+        }
+
+        Ok(groups.join(" "))
+    }
+
+    /// Decrypt a message using a Nomenclator cipher.
+    ///
+    /// The plaintext is lowercase, with a single space between each word.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Homophonic, Nomenclator, NomenclatorKey};
+    ///
+    /// let n = Nomenclator::new(NomenclatorKey {
+    ///     code_book: vec![(String::from("the king"), String::from("312"))],
+    ///     letters: Homophonic::from_keyword("cipher"),
+    /// });
+    /// assert_eq!("attack the king", n.decrypt("358289400154 312").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let words = ciphertext
+            .split_whitespace()
+            .map(
+                |group| match self.code_book.iter().find(|(_, code)| code == group) {
+                    Some((phrase, _)) => Ok(phrase.join(" ")),
+                    None => self.letters.decrypt(group),
+                },
+            )
+            .collect::<Result<Vec<String>, &'static str>>()?;
+
+        Ok(words.join(" "))
+    }
+
+    /// Check that a message can be encrypted with a Nomenclator cipher.
+    ///
+    /// Returns `Err` if the message contains a symbol that is not alphabetic or whitespace.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Homophonic, Nomenclator, NomenclatorKey};
+    ///
+    /// let n = Nomenclator::new(NomenclatorKey {
+    ///     code_book: Vec::new(),
+    ///     letters: Homophonic::from_keyword("cipher"),
+    /// });
+    ///
+    /// assert!(n.validate_message("Attack at dawn").is_ok());
+    /// assert!(n.validate_message("Attack at dawn!").is_err());
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        self.letters.validate_message(message)
+    }
+
+    /// Whether a character is foreign to the cipher, being neither a letter nor whitespace, as
+    /// the whitespace separates the words of the message.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        !(c.is_ascii_alphabetic() || c.is_whitespace())
+    }
+}
+
+impl Nomenclator {
+    /// Initialise a Nomenclator cipher, loading the code book from an iterator of
+    /// `(phrase, code_group)` pairs.
+    ///
+    /// # Panics
+    /// * Any of the conditions as stipulated by the `new()` fn.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Homophonic, Nomenclator};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut book = HashMap::new();
+    /// book.insert("the king", "312");
+    /// book.insert("the army", "465");
+    ///
+    /// let n = Nomenclator::from_pairs(book, Homophonic::from_keyword("cipher"));
+    /// assert_eq!("465 312", n.encrypt("The army the King").unwrap());
+    /// ```
+    ///
+    pub fn from_pairs<I, P, C>(code_book: I, letters: Homophonic) -> Nomenclator
+    where
+        I: IntoIterator<Item = (P, C)>,
+        P: Into<String>,
+        C: Into<String>,
+    {
+        Nomenclator::new(NomenclatorKey {
+            code_book: code_book
+                .into_iter()
+                .map(|(phrase, code)| (phrase.into(), code.into()))
+                .collect(),
+            letters,
+        })
+    }
+
+    /// The `(phrase, code_group)` pairs of the code book, in the order they were given. Each
+    /// phrase is lowercase, with a single space between each word.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Homophonic, Nomenclator};
+    ///
+    /// let n = Nomenclator::from_pairs(
+    ///     vec![("The  King", "312")],
+    ///     Homophonic::from_keyword("cipher"),
+    /// );
+    /// assert_eq!(vec![(String::from("the king"), String::from("312"))], n.code_book());
+    /// ```
+    ///
+    pub fn code_book(&self) -> Vec<(String, String)> {
+        self.code_book
+            .iter()
+            .map(|(phrase, code)| (phrase.join(" "), code.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::policy::ForeignCharPolicy;
+    use std::collections::HashMap;
+
+    fn letters() -> Homophonic {
+        Homophonic::new(
+            (b'a'..=b'z')
+                .map(|c| (c as char, vec![format!("{:02}", c - b'a')]))
+                .collect::<HashMap<char, Vec<String>>>(),
+        )
+    }
+
+    fn nomenclator() -> Nomenclator {
+        Nomenclator::from_pairs(
+            vec![
+                ("the king", "A1"),
+                ("the", "B2"),
+                ("king", "C3"),
+                ("the king of spain", "D4"),
+                ("attack", "E5"),
+            ],
+            letters(),
+        )
+    }
+
+    #[test]
+    fn encrypt_test() {
+        assert_eq!(
+            "E5 A1 0019 03002213",
+            nomenclator().encrypt("Attack the King at dawn").unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_test() {
+        assert_eq!(
+            "attack the king at dawn",
+            nomenclator().decrypt("E5 A1 0019 03002213").unwrap()
+        );
+    }
+
+    #[test]
+    fn longest_phrase_is_used() {
+        let n = nomenclator();
+
+        assert_eq!("D4", n.encrypt("the king of spain").unwrap());
+        assert_eq!("A1 1405", n.encrypt("the king of").unwrap());
+        assert_eq!("C3 B2", n.encrypt("king the").unwrap());
+    }
+
+    #[test]
+    fn whitespace_and_case_ignored() {
+        let n = nomenclator();
+        assert_eq!(
+            n.encrypt("attack the king").unwrap(),
+            n.encrypt("  ATTACK\tThe\n king ").unwrap()
+        );
+    }
+
+    #[test]
+    fn code_book_round_trip() {
+        let n1 = nomenclator();
+        let n2 = Nomenclator::from_pairs(n1.code_book(), letters());
+        let m = "Attack the King of Spain at dawn";
+
+        assert_eq!(n1.code_book(), n2.code_book());
+        assert_eq!(n1.encrypt(m).unwrap(), n2.encrypt(m).unwrap());
+    }
+
+    #[test]
+    fn unknown_group() {
+        assert!(nomenclator().decrypt("E5 Z9").is_err());
+    }
+
+    #[test]
+    fn with_utf8() {
+        assert!(nomenclator().encrypt("Peace 🗡️ Freedom").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn phrase_with_symbols() {
+        Nomenclator::from_pairs(vec![("the king!", "A1")], letters());
+    }
+
+    #[test]
+    #[should_panic]
+    fn repeated_phrase() {
+        Nomenclator::from_pairs(vec![("the king", "A1"), ("THE KING", "B2")], letters());
+    }
+
+    #[test]
+    #[should_panic]
+    fn repeated_code() {
+        Nomenclator::from_pairs(vec![("the king", "A1"), ("the queen", "A1")], letters());
+    }
+
+    #[test]
+    #[should_panic]
+    fn code_made_of_homophones() {
+        Nomenclator::from_pairs(vec![("the king", "0110")], letters());
+    }
+
+    #[test]
+    fn strip_keeps_whitespace() {
+        let n = nomenclator();
+        assert_eq!(
+            n.encrypt("Attack the King at dawn"),
+            n.encrypt_with_policy("Attack the King at dawn!", ForeignCharPolicy::Strip)
+        );
+    }
+}
@@ -0,0 +1,326 @@
+//! A null cipher hides a message in plain sight, amongst a large amount of non-cipher material
+//! (the nulls). Like the Baconian cipher and the Cardan grille, it is a form of steganography.
+//!
+//! In this implementation each letter of the message is carried by a single word of the cover
+//! text, at a position given by a repeating pattern. For example, with the pattern `[1]` the
+//! message is spelled out by the first letter of each word, while with the pattern `[1, 2]` it is
+//! spelled out by the first letter of the first word, the second letter of the second word, and
+//! so on.
+//!
+//! The words are chosen from a decoy text. If no decoy text is given, the boilerplate of "Lorem
+//! ipsum..." is used. As the Latin of the boilerplate lacks some letters entirely, a word of the
+//! boilerplate may be altered to carry a letter of the message.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
+use lipsum::lipsum;
+
+// The number of boilerplate words to choose from when no decoy text is given
+const LIPSUM_WORDS: usize = 200;
+
+/// A Null Cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NullCipher {
+    pattern: Vec<usize>,
+    decoy_text: Option<String>,
+}
+
+/// The key of a Null Cipher.
+///
+/// This struct is given to the `new()` method of `NullCipher`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NullCipherKey {
+    /// The sequence of letter positions (counting from 1) that carry the message in each
+    /// successive word. It is repeated for as many words as needed.
+    pub pattern: Vec<usize>,
+    /// An optional decoy text that provides the words used to carry the message - default
+    /// is boilerplate "Lorem ipsum" text.
+    pub decoy_text: Option<String>,
+}
+
+impl Cipher for NullCipher {
+    type Key = NullCipherKey;
+    type Algorithm = NullCipher;
+
+    /// Initialise a Null Cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `NullCipherKey`.
+    ///
+    /// # Panics
+    /// * The `pattern` is empty.
+    /// * The `pattern` contains a position of 0.
+    ///
+    fn new(key: NullCipherKey) -> NullCipher {
+        if key.pattern.is_empty() {
+            panic!("The pattern is empty.");
+        }
+        if key.pattern.contains(&0) {
+            panic!("The positions of the pattern must start from 1.");
+        }
+
+        NullCipher {
+            pattern: key.pattern,
+            decoy_text: key.decoy_text,
+        }
+    }
+
+    /// Encrypt a message using a Null Cipher.
+    ///
+    /// Each letter of the message is carried by the next word of the decoy text that has the
+    /// letter at the position given by the pattern. Whitespace within the message is ignored.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, NullCipher, NullCipherKey};
+    ///
+    /// let decoy = String::from("Have every letter posted sooner, as no grandmother is nervous");
+    /// let n = NullCipher::new(NullCipherKey { pattern: vec![1], decoy_text: Some(decoy) });
+    ///
+    /// assert_eq!("Have every letter posted", n.encrypt("help").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.validate_message(message)?;
+
+        let (decoy, alterable) = match self.decoy_text {
+            Some(ref text) => (text.clone(), false),
+            None => (lipsum(LIPSUM_WORDS), true),
+        };
+        let words: Vec<&str> = decoy.split_whitespace().collect();
+        if words.is_empty() {
+            return Err("The decoy text does not contain any words.");
+        }
+
+        let mut cover = Vec::new();
+        let mut next = 0;
+        for (c, &pos) in message
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .zip(self.pattern.iter().cycle())
+        {
+            let candidates = (0..words.len()).map(|i| (next + i) % words.len());
+            let mut carrier = candidates
+                .clone()
+                .find(|&i| letter_at(words[i], pos).is_some_and(|l| l.eq_ignore_ascii_case(&c)))
+                .map(|i| (i, words[i].to_string()));
+
+            if carrier.is_none() && alterable {
+                carrier = candidates
+                    .clone()
+                    .find(|&i| letter_at(words[i], pos).is_some())
+                    .map(|i| (i, replace_letter(words[i], pos, c)));
+            }
+
+            let (i, word) =
+                carrier.ok_or("The decoy text cannot carry a letter of the message.")?;
+            cover.push(word);
+            next = i + 1;
+        }
+
+        Ok(cover.join(" "))
+    }
+
+    /// Decrypt a message using a Null Cipher.
+    ///
+    /// Only the letters of each word are counted when finding the position of the hidden
+    /// letter. The plaintext is lowercase.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, NullCipher, NullCipherKey};
+    ///
+    /// let n = NullCipher::new(NullCipherKey { pattern: vec![1, 2], decoy_text: None });
+    /// assert_eq!("help", n.decrypt("Hope, despite loud opposition").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        ciphertext
+            .split_whitespace()
+            .filter(|w| {
+                w.chars()
+                    .any(|c| alphabet::STANDARD.find_position(c).is_some())
+            })
+            .zip(self.pattern.iter().cycle())
+            .map(|(w, &pos)| {
+                letter_at(w, pos)
+                    .map(|l| l.to_ascii_lowercase())
+                    .ok_or("A word of the ciphertext is too short for the pattern.")
+            })
+            .collect()
+    }
+
+    /// Check that a message can be encrypted with a Null Cipher.
+    ///
+    /// Returns `Err` if the message contains a symbol that is not alphabetic or whitespace.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, NullCipher, NullCipherKey};
+    ///
+    /// let n = NullCipher::new(NullCipherKey { pattern: vec![1], decoy_text: None });
+    ///
+    /// assert!(n.validate_message("Meet at noon").is_ok());
+    /// assert!(n.validate_message("Meet at 12").is_err());
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        CipherError::find(
+            message,
+            "Message contains a symbol that is not alphabetic or whitespace.",
+            |c| !c.is_whitespace() && alphabet::STANDARD.find_position(c).is_none(),
+        )
+    }
+}
+
+/// The letter at position `pos` (counting from 1) of a word, ignoring any non-alphabetic symbols.
+///
+fn letter_at(word: &str, pos: usize) -> Option<char> {
+    word.chars()
+        .filter(|&c| alphabet::STANDARD.find_position(c).is_some())
+        .nth(pos - 1)
+}
+
+/// Replace the letter at position `pos` (counting from 1) of a word with `letter`, keeping the
+/// case of the original letter.
+///
+fn replace_letter(word: &str, pos: usize, letter: char) -> String {
+    let mut seen = 0;
+    word.chars()
+        .map(|c| {
+            if alphabet::STANDARD.find_position(c).is_none() {
+                return c;
+            }
+
+            seen += 1;
+            match (seen == pos, c.is_uppercase()) {
+                (true, true) => letter.to_ascii_uppercase(),
+                (true, false) => letter.to_ascii_lowercase(),
+                (false, _) => c,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECOY: &str = "the quick brown fox jumps over the lazy dog";
+
+    #[test]
+    fn encrypt_test() {
+        let n = NullCipher::new(NullCipherKey {
+            pattern: vec![1],
+            decoy_text: Some(String::from(DECOY)),
+        });
+        assert_eq!("the brown dog", n.encrypt("tbd").unwrap());
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let n = NullCipher::new(NullCipherKey {
+            pattern: vec![1],
+            decoy_text: Some(String::from(DECOY)),
+        });
+        assert_eq!("tbd", n.decrypt("the brown dog").unwrap());
+    }
+
+    #[test]
+    fn decoy_words_are_reused() {
+        let n = NullCipher::new(NullCipherKey {
+            pattern: vec![1],
+            decoy_text: Some(String::from(DECOY)),
+        });
+        assert_eq!("the the the", n.encrypt("ttt").unwrap());
+    }
+
+    #[test]
+    fn pattern_repeats() {
+        let n = NullCipher::new(NullCipherKey {
+            pattern: vec![2, 3],
+            decoy_text: Some(String::from(DECOY)),
+        });
+        let c = n.encrypt("hou").unwrap();
+
+        assert_eq!("the brown jumps", c);
+        assert_eq!("hou", n.decrypt(&c).unwrap());
+    }
+
+    #[test]
+    fn punctuation_is_ignored() {
+        let n = NullCipher::new(NullCipherKey {
+            pattern: vec![2],
+            decoy_text: None,
+        });
+        assert_eq!("hi", n.decrypt("\"Oh, (hi)!\" -").unwrap());
+    }
+
+    #[test]
+    fn default_decoy() {
+        let n = NullCipher::new(NullCipherKey {
+            pattern: vec![1, 3, 2],
+            decoy_text: None,
+        });
+        let m = "Meet me at the old mill at midnight with the key";
+
+        assert_eq!(
+            "meetmeattheoldmillatmidnightwiththekey",
+            n.decrypt(&n.encrypt(m).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn decoy_cannot_carry_message() {
+        let n = NullCipher::new(NullCipherKey {
+            pattern: vec![1],
+            decoy_text: Some(String::from(DECOY)),
+        });
+        assert!(n.encrypt("xyz").is_err());
+    }
+
+    #[test]
+    fn ciphertext_too_short() {
+        let n = NullCipher::new(NullCipherKey {
+            pattern: vec![4],
+            decoy_text: None,
+        });
+        assert!(n.decrypt("some words are short").is_err());
+    }
+
+    #[test]
+    fn invalid_message() {
+        let n = NullCipher::new(NullCipherKey {
+            pattern: vec![1],
+            decoy_text: None,
+        });
+        assert!(n.encrypt("Peace 🗡️ Freedom and Liberty!").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_pattern() {
+        NullCipher::new(NullCipherKey {
+            pattern: Vec::new(),
+            decoy_text: None,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_position() {
+        NullCipher::new(NullCipherKey {
+            pattern: vec![1, 0],
+            decoy_text: None,
+        });
+    }
+}
@@ -0,0 +1,304 @@
+//! The one-time pad was described by Frank Miller in 1882, and reinvented by Gilbert Vernam and
+//! Joseph Mauborgne during World War I. Each letter of the message is added (mod 26) to the
+//! corresponding letter of a random pad.
+//!
+//! Provided the pad is truly random, at least as long as the message, kept secret and never
+//! reused, the one-time pad is unbreakable. Reusing any part of the pad, however, quickly
+//! compromises the messages it was used for.
+//!
+//! To discourage reuse, a pad can be created in a consuming mode, where each encryption (or
+//! decryption) uses the next unused portion of the pad.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::keygen;
+use crate::common::substitute;
+use std::cell::Cell;
+use std::fmt;
+
+/// A One-Time Pad cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+///
+/// It cannot be cloned, as a clone of a consuming pad could reuse the portion of the pad that has
+/// not yet been used by the original.
+#[derive(PartialEq)]
+pub struct OneTimePad {
+    pad: String,
+    consuming: bool,
+    used: Cell<usize>,
+}
+
+impl fmt::Debug for OneTimePad {
+    /// Writes the length of the pad and how much of it has been used, but not the pad itself.
+    ///
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OneTimePad")
+            .field("pad", &self.pad.len())
+            .field("consuming", &self.consuming)
+            .field("used", &self.used.get())
+            .finish()
+    }
+}
+
+impl Cipher for OneTimePad {
+    type Key = String;
+    type Algorithm = OneTimePad;
+
+    /// Initialise a One-Time Pad cipher given a supplied pad, as by `OneTimePad::new()`.
+    ///
+    fn new(pad: String) -> OneTimePad {
+        OneTimePad::new(pad)
+    }
+
+    /// Encrypt a message using a One-Time Pad cipher.
+    ///
+    /// Will return `Err` if the remaining pad is shorter than the alphabetic characters of the
+    /// message.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, OneTimePad};
+    ///
+    /// let otp = OneTimePad::new("xmckljweqz");
+    /// assert_eq!("Eqnvz!", otp.encrypt("Hello!").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        // Encryption of a letter in a message:
+        //         Ci = Ek(Mi) = (Mi + Ki) mod 26
+        // Where;  Mi = position within the alphabet of ith char in message
+        //         Ki = position within the alphabet of ith char in pad
+        let keystream = self.take_pad(message)?;
+        Ok(substitute::key_substitution(
+            message,
+            keystream.chars(),
+            |mi, ki| alphabet::STANDARD.modulo((mi + ki) as isize),
+        ))
+    }
+
+    /// Decrypt a message using a One-Time Pad cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, OneTimePad};
+    ///
+    /// let otp = OneTimePad::new("xmckljweqz");
+    /// assert_eq!("Hello!", otp.decrypt("Eqnvz!").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        // Decryption of a letter in a message:
+        //         Mi = Dk(Ci) = (Ci - Ki) mod 26
+        // Where;  Ci = position within the alphabet of ith char in cipher text
+        //         Ki = position within the alphabet of ith char in pad
+        let keystream = self.take_pad(ciphertext)?;
+        Ok(substitute::key_substitution(
+            ciphertext,
+            keystream.chars(),
+            |ci, ki| alphabet::STANDARD.modulo(ci as isize - ki as isize),
+        ))
+    }
+}
+
+impl OneTimePad {
+    /// Initialise a One-Time Pad cipher given a supplied pad.
+    ///
+    /// Every message is encrypted from the start of the pad. See `consuming()` for a pad that
+    /// tracks its usage.
+    ///
+    /// # Panics
+    /// * The `pad` is empty.
+    /// * The `pad` contains a non-alphabetic symbol.
+    ///
+    pub fn new(pad: impl Into<String>) -> OneTimePad {
+        let pad = pad.into();
+        if pad.is_empty() {
+            panic!("The pad is empty.");
+        }
+        if !alphabet::STANDARD.is_valid(&pad) {
+            panic!("The pad contains a non-alphabetic symbol.");
+        }
+
+        OneTimePad {
+            pad,
+            consuming: false,
+            used: Cell::new(0),
+        }
+    }
+
+    /// Initialise a One-Time Pad cipher with a randomly generated pad of `length` letters.
+    ///
+    /// # Panics
+    /// * The `length` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, OneTimePad};
+    ///
+    /// let otp = OneTimePad::random(64);
+    /// assert_eq!("Hello!", otp.decrypt(&otp.encrypt("Hello!").unwrap()).unwrap());
+    /// ```
+    ///
+    pub fn random(length: usize) -> OneTimePad {
+        OneTimePad::new(keygen::one_time_pad(length))
+    }
+
+    /// Initialise a One-Time Pad cipher that consumes its pad.
+    ///
+    /// Each encryption or decryption uses the next unused portion of the pad, so that no part of
+    /// it is ever used twice. The sender and recipient should each hold a consuming copy of the
+    /// pad, and process messages in the same order.
+    ///
+    /// # Panics
+    /// * The `pad` is empty.
+    /// * The `pad` contains a non-alphabetic symbol.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, OneTimePad};
+    ///
+    /// let sender = OneTimePad::consuming("xmckljweqzptbg");
+    /// let recipient = OneTimePad::consuming("xmckljweqzptbg");
+    ///
+    /// let c1 = sender.encrypt("Hello").unwrap();
+    /// let c2 = sender.encrypt("Hello").unwrap();
+    /// assert_ne!(c1, c2);
+    /// assert_eq!(4, sender.remaining());
+    ///
+    /// assert_eq!("Hello", recipient.decrypt(&c1).unwrap());
+    /// assert_eq!("Hello", recipient.decrypt(&c2).unwrap());
+    /// assert!(sender.encrypt("Hello").is_err());
+    /// ```
+    ///
+    pub fn consuming(pad: impl Into<String>) -> OneTimePad {
+        let mut otp = OneTimePad::new(pad);
+        otp.consuming = true;
+
+        otp
+    }
+
+    /// The number of pad letters that have been consumed.
+    ///
+    pub fn used(&self) -> usize {
+        self.used.get()
+    }
+
+    /// The number of pad letters that are available for use.
+    ///
+    pub fn remaining(&self) -> usize {
+        self.pad.len() - self.used.get()
+    }
+
+    /// Take the portion of the pad needed for the alphabetic characters of the text, consuming
+    /// it if required.
+    ///
+    fn take_pad(&self, text: &str) -> Result<String, &'static str> {
+        let length = alphabet::STANDARD.scrub(text).len();
+        if length > self.remaining() {
+            return Err("The pad is shorter than the message.");
+        }
+
+        let start = self.used.get();
+        if self.consuming {
+            self.used.set(start + length);
+        }
+
+        Ok(self.pad[start..start + length].to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_test() {
+        let otp = OneTimePad::new(String::from("xmckl"));
+        assert_eq!("eqnvz", otp.encrypt("hello").unwrap());
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let otp = OneTimePad::new(String::from("xmckl"));
+        assert_eq!("hello", otp.decrypt("eqnvz").unwrap());
+    }
+
+    #[test]
+    fn with_utf8() {
+        let m = "Peace 🗡️ Freedom and Liberty!";
+        let otp = OneTimePad::random(50);
+
+        assert_eq!(m, otp.decrypt(&otp.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn short_pad() {
+        let otp = OneTimePad::new(String::from("xmck"));
+        assert!(otp.encrypt("hello").is_err());
+        assert!(otp.decrypt("eqnvz").is_err());
+    }
+
+    #[test]
+    fn symbols_do_not_use_pad() {
+        let otp = OneTimePad::new(String::from("xmckl"));
+        assert_eq!("E q-n v z!", otp.encrypt("H e-l l o!").unwrap());
+    }
+
+    #[test]
+    fn reusable_pad() {
+        let otp = OneTimePad::new(String::from("xmckljweqz"));
+        assert_eq!(otp.encrypt("hello").unwrap(), otp.encrypt("hello").unwrap());
+        assert_eq!(0, otp.used());
+        assert_eq!(10, otp.remaining());
+    }
+
+    #[test]
+    fn consuming_pad() {
+        let otp = OneTimePad::consuming(String::from("xmckljweqz"));
+
+        assert_eq!("eqnvz", otp.encrypt("hello").unwrap());
+        assert_eq!(5, otp.used());
+        assert_eq!("qapbn", otp.encrypt("hello").unwrap());
+        assert_eq!(0, otp.remaining());
+        assert!(otp.encrypt("a").is_err());
+    }
+
+    #[test]
+    fn failed_encryption_does_not_consume() {
+        let otp = OneTimePad::consuming(String::from("xmckl"));
+
+        assert!(otp.encrypt("hello world").is_err());
+        assert_eq!(0, otp.used());
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_pad() {
+        OneTimePad::new(String::from(""));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pad_with_symbols() {
+        OneTimePad::new(String::from("xm ckl"));
+    }
+
+    #[test]
+    fn debug_hides_pad() {
+        let otp = OneTimePad::consuming(String::from("xmckljweqz"));
+        otp.encrypt("Hello").unwrap();
+
+        assert_eq!(
+            "OneTimePad { pad: 10, consuming: true, used: 5 }",
+            format!("{:?}", otp)
+        );
+    }
+}
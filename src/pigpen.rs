@@ -0,0 +1,256 @@
+//! The Pigpen cipher (also known as the Freemason's cipher) is a geometric substitution cipher,
+//! which replaces each letter with a symbol derived from fragments of a grid. It has been used
+//! since at least the 18th century by the Freemasons, and is commonly taught to children.
+//!
+//! In the standard layout, the letters `A-I` are placed in a tic-tac-toe grid, `J-R` in a grid
+//! with a dot in each cell, `S-V` in an X and `W-Z` in an X with dots. Each letter is represented
+//! by the lines (and dot) of the cell surrounding it.
+//!
+//! As these symbols cannot be typed directly, this implementation approximates them using Unicode
+//! characters, with a dot represented by a trailing `·`. The symbol table is swappable, so that
+//! other variants of the cipher (such as the Rosicrucian or Templar ciphers) can be expressed.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
+use std::cmp::Reverse;
+
+/// The standard Pigpen symbol table, for the letters `A-Z`.
+pub const STANDARD: [&str; 26] = [
+    "⌟", "⊔", "⌞", "⊐", "□", "⊏", "⌝", "⊓", "⌜", // A-I
+    "⌟·", "⊔·", "⌞·", "⊐·", "□·", "⊏·", "⌝·", "⊓·", "⌜·", // J-R
+    "∨", ">", "<", "∧", // S-V
+    "∨·", ">·", "<·", "∧·", // W-Z
+];
+
+/// The Rosicrucian symbol table, for the letters `A-Z`.
+///
+/// A single tic-tac-toe grid holds up to three letters in each cell, which are distinguished by
+/// the number of dots. As in the historical cipher, 'I' and 'J' share a symbol.
+pub const ROSICRUCIAN: [&str; 26] = [
+    "⌟", "⌟·", "⌟··", "⊔", "⊔·", "⊔··", "⌞", "⌞·", "⌞··", "⌞··", // A-J
+    "⊐", "⊐·", "⊐··", "□", "□·", "□··", "⊏", "⊏·", "⊏··", // K-S
+    "⌝", "⌝·", "⌝··", "⊓", "⊓·", "⊓··", "⌜", // T-Z
+];
+
+/// A Pigpen cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pigpen {
+    symbols: Vec<String>,
+}
+
+impl Cipher for Pigpen {
+    type Key = Vec<String>;
+    type Algorithm = Pigpen;
+
+    /// Initialise a Pigpen cipher given a symbol table.
+    ///
+    /// The table contains the symbol for each of the letters `A-Z`. Letters may share a symbol,
+    /// in which case the earliest letter is chosen during decryption.
+    ///
+    /// # Panics
+    /// * The table does not contain 26 symbols.
+    /// * A symbol is empty, or contains alphabetic or whitespace characters.
+    ///
+    fn new(symbols: Vec<String>) -> Pigpen {
+        if symbols.len() != 26 {
+            panic!("The symbol table must contain 26 symbols.");
+        }
+        for s in &symbols {
+            if s.is_empty() || s.chars().any(|c| c.is_alphabetic() || c.is_whitespace()) {
+                panic!("A symbol must not be empty, or contain letters or whitespace.");
+            }
+        }
+
+        Pigpen { symbols }
+    }
+
+    /// Encrypt a message using a Pigpen cipher.
+    ///
+    /// Each letter is replaced by its symbol, while any other characters are left in place.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Pigpen};
+    ///
+    /// let p = Pigpen::standard();
+    /// assert_eq!("⌟>>⌟⌞⊔· ⌟> ⊐⌟∨·□·", p.encrypt("Attack at dawn").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.validate_message(message)?;
+
+        Ok(message
+            .chars()
+            .map(|c| match alphabet::STANDARD.find_position(c) {
+                Some(pos) => self.symbols[pos].clone(),
+                None => c.to_string(),
+            })
+            .collect())
+    }
+
+    /// Decrypt a message using a Pigpen cipher.
+    ///
+    /// As the symbols do not carry case, the plaintext is uppercase.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Pigpen};
+    ///
+    /// let p = Pigpen::standard();
+    /// assert_eq!("ATTACK AT DAWN", p.decrypt("⌟>>⌟⌞⊔· ⌟> ⊐⌟∨·□·").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let mut plaintext = String::new();
+        let mut rest = ciphertext;
+
+        while let Some(c) = rest.chars().next() {
+            // Match the longest symbol, so that dotted symbols take precedence
+            let longest = self
+                .symbols
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| rest.starts_with(s.as_str()))
+                .max_by_key(|&(i, s)| (s.len(), Reverse(i)));
+
+            match longest {
+                Some((i, s)) => {
+                    plaintext.push(alphabet::STANDARD.get_letter(i, true));
+                    rest = &rest[s.len()..];
+                }
+                None => {
+                    plaintext.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+
+        Ok(plaintext)
+    }
+
+    /// Check that a message can be encrypted with a Pigpen cipher.
+    ///
+    /// Returns `Err` if the message contains a character that is used within the symbol table,
+    /// as this would be indistinguishable from a symbol during decryption.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Pigpen};
+    ///
+    /// let p = Pigpen::standard();
+    ///
+    /// assert!(p.validate_message("1 + 1 = 2").is_ok());
+    /// assert!(p.validate_message("1 < 2").is_err());
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        CipherError::find(
+            message,
+            "Message contains a character that is used within the symbol table.",
+            |c| self.symbols.iter().any(|s| s.contains(c)),
+        )
+    }
+}
+
+impl Pigpen {
+    /// Initialise a Pigpen cipher using the `STANDARD` symbol table.
+    ///
+    pub fn standard() -> Pigpen {
+        Pigpen::new(STANDARD.iter().map(|s| s.to_string()).collect())
+    }
+
+    /// Initialise a Pigpen cipher using the `ROSICRUCIAN` symbol table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Pigpen};
+    ///
+    /// let p = Pigpen::rosicrucian();
+    /// assert_eq!("⌞··⌜", p.encrypt("jz").unwrap());
+    /// assert_eq!("IZ", p.decrypt("⌞··⌜").unwrap());
+    /// ```
+    ///
+    pub fn rosicrucian() -> Pigpen {
+        Pigpen::new(ROSICRUCIAN.iter().map(|s| s.to_string()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_test() {
+        let p = Pigpen::standard();
+        assert_eq!("⌟⊔⌞⌟·∨>∨·∧·", p.encrypt("abcjstwz").unwrap());
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let p = Pigpen::standard();
+        assert_eq!("ABCJSTWZ", p.decrypt("⌟⊔⌞⌟·∨>∨·∧·").unwrap());
+    }
+
+    #[test]
+    fn all_letters() {
+        let m = "THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG";
+        let p = Pigpen::standard();
+
+        assert_eq!(m, p.decrypt(&p.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn with_utf8() {
+        let m = "Attack 🗡️ at dawn!";
+        let p = Pigpen::standard();
+
+        assert_eq!(m.to_uppercase(), p.decrypt(&p.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn rosicrucian_table() {
+        let m = "THE QUICK BROWN FOX";
+        let p = Pigpen::rosicrucian();
+
+        assert_eq!(m, p.decrypt(&p.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn custom_table() {
+        let table = (0..26).map(|i| format!("{:02}", i)).collect();
+        let p = Pigpen::new(table);
+
+        assert_eq!("071111 14!", p.encrypt("Hll o!").unwrap());
+        assert_eq!("HLL O!", p.decrypt("071111 14!").unwrap());
+    }
+
+    #[test]
+    fn message_with_symbols() {
+        assert!(Pigpen::standard().encrypt("a > b").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn short_table() {
+        Pigpen::new(vec![String::from("#"); 25]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn table_with_letters() {
+        let mut table = vec![String::from("#"); 26];
+        table[3] = String::from("d");
+        Pigpen::new(table);
+    }
+}
@@ -0,0 +1,249 @@
+//! A pipeline chains several ciphers together, so that a message is encrypted by each of them in
+//! turn.
+//!
+//! Many historical ciphers were built this way. The ADFGVX cipher, for example, is a Polybius
+//! square followed by a Columnar Transposition, and a Nihilist or double transposition cipher
+//! applies its stages in the same manner. A `Pipeline` lets any such composition be built from
+//! the ciphers of this crate. The message is encrypted by the first stage, whose output is
+//! encrypted by the second, and so on; decryption runs through the stages in reverse.
+//!
+use crate::common::cipher::{Cipher, DynCipher};
+use crate::common::cipher_error::CipherError;
+use std::fmt;
+
+/// A chain of ciphers, applied one after the other.
+///
+/// This struct is created by the `new()` method, and its stages are added with `then()`. See
+/// their documentation for more.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::{
+///     ADFGVXKey, Cipher, ColumnarTransposition, ColumnarTranspositionKey, Pipeline, Polybius,
+///     PolybiusKey, ADFGVX,
+/// };
+///
+/// // The ADFGVX cipher, built from its two stages
+/// let ids = ['A', 'D', 'F', 'G', 'V', 'X'];
+/// let square = String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8");
+/// let p = Pipeline::new()
+///     .then(Polybius::new(PolybiusKey {
+///         phrase: square.clone(),
+///         column_ids: ids,
+///         row_ids: ids,
+///     }))
+///     .then(ColumnarTransposition::new(ColumnarTranspositionKey {
+///         keystream: String::from("GERMAN"),
+///         null_char: None,
+///     }));
+/// let a = ADFGVX::new(ADFGVXKey {
+///     polybius_key: square,
+///     columnar_key: String::from("GERMAN"),
+///     null_char: None,
+/// });
+///
+/// let c = p.encrypt("Attack at 1200").unwrap();
+/// assert_eq!(a.encrypt("Attack at 1200").unwrap(), c);
+/// assert_eq!("Attack at 1200", p.decrypt(&c).unwrap());
+/// ```
+#[derive(Default)]
+pub struct Pipeline {
+    stages: Vec<Box<dyn DynCipher>>,
+}
+
+impl Pipeline {
+    /// Initialise a pipeline with no stages, which leaves messages unchanged.
+    ///
+    pub fn new() -> Pipeline {
+        Pipeline::default()
+    }
+
+    /// Add a `cipher` as the last stage of the pipeline.
+    ///
+    pub fn then<C: Cipher + 'static>(mut self, cipher: C) -> Pipeline {
+        self.stages.push(Box::new(cipher));
+        self
+    }
+
+    /// The number of stages in the pipeline.
+    ///
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Whether the pipeline has no stages.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+}
+
+impl fmt::Debug for Pipeline {
+    /// Writes the number of stages of the pipeline, as the stages themselves are boxed ciphers of
+    /// any type.
+    ///
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("stages", &self.stages.len())
+            .finish()
+    }
+}
+
+impl Cipher for Pipeline {
+    type Key = Vec<Pipeline>;
+    type Algorithm = Pipeline;
+
+    /// Initialise a pipeline that runs through the stages of each of the given `pipelines` in
+    /// turn.
+    ///
+    /// Note that `Pipeline::new()` names the method of the pipeline itself, that takes no key; this
+    /// method must be called as `<Pipeline as Cipher>::new()`.
+    ///
+    fn new(pipelines: Vec<Pipeline>) -> Pipeline {
+        Pipeline {
+            stages: pipelines.into_iter().flat_map(|p| p.stages).collect(),
+        }
+    }
+
+    /// Encrypt a message with each stage of the pipeline, from the first to the last.
+    ///
+    /// Returns the first `Err` of a stage, if any.
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.stages
+            .iter()
+            .try_fold(message.to_string(), |text, stage| stage.dyn_encrypt(&text))
+    }
+
+    /// Decrypt a message with each stage of the pipeline, from the last to the first.
+    ///
+    /// Returns the first `Err` of a stage, if any.
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        self.stages
+            .iter()
+            .rev()
+            .try_fold(ciphertext.to_string(), |text, stage| {
+                stage.dyn_decrypt(&text)
+            })
+    }
+
+    /// Check that a message can be encrypted by each stage of the pipeline.
+    ///
+    /// As each stage after the first is given the output of the stage before, the message is
+    /// encrypted by every stage but the last to check it.
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        let mut text = message.to_string();
+        for (i, stage) in self.stages.iter().enumerate() {
+            // Only the first stage sees the message itself, so only it can point at a symbol
+            match stage.dyn_validate_message(&text) {
+                Err(e) if i > 0 => return Err(e.without_symbol()),
+                result => result?,
+            }
+            if i + 1 < self.stages.len() {
+                text = stage.dyn_encrypt(&text)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether a character is foreign to the first stage of the pipeline, which is given the
+    /// message.
+    ///
+    /// A pipeline with no stages takes the default of `Cipher`, that the characters outside of
+    /// `a-z` and `A-Z` are foreign.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        match self.stages.first() {
+            Some(stage) => stage.dyn_is_foreign(c),
+            None => !c.is_ascii_alphabetic(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Caesar, Hill, Railfence, Vigenere};
+
+    #[test]
+    fn empty_pipeline() {
+        let p = Pipeline::new();
+        assert!(p.is_empty());
+        assert_eq!("Attack at dawn!", p.encrypt("Attack at dawn!").unwrap());
+    }
+
+    #[test]
+    fn decrypts_in_reverse() {
+        let p = Pipeline::new()
+            .then(Vigenere::new(String::from("lemon")))
+            .then(Railfence::new(3));
+        let m = "Attack at dawn!";
+        let c = p.encrypt(m).unwrap();
+
+        assert_eq!(
+            Railfence::new(3)
+                .encrypt(&Vigenere::new(String::from("lemon")).encrypt(m).unwrap())
+                .unwrap(),
+            c
+        );
+        assert_eq!(m, p.decrypt(&c).unwrap());
+    }
+
+    #[test]
+    fn joins_pipelines() {
+        let first = Pipeline::new().then(Caesar::new(3));
+        let second = Pipeline::new().then(Caesar::new(4)).then(Caesar::new(6));
+        let p = <Pipeline as Cipher>::new(vec![first, second]);
+
+        assert_eq!(3, p.len());
+        assert_eq!("Nggnpx", p.encrypt("Attack").unwrap());
+    }
+
+    #[test]
+    fn boxed_stages() {
+        let ciphers: Vec<Box<dyn DynCipher>> =
+            vec![Box::new(Caesar::new(3)), Box::new(Railfence::new(3))];
+        let p = ciphers.into_iter().fold(Pipeline::new(), |p, c| p.then(c));
+
+        assert_eq!(
+            Railfence::new(3)
+                .encrypt(&Caesar::new(3).encrypt("Attack at dawn!").unwrap())
+                .unwrap(),
+            p.encrypt("Attack at dawn!").unwrap()
+        );
+    }
+
+    #[test]
+    fn validates_each_stage() {
+        // The Hill cipher cannot take the spaces that the Caesar cipher passes through
+        let p = Pipeline::new()
+            .then(Caesar::new(3))
+            .then(Hill::from_phrase("CEFJCBDRH", 3));
+
+        assert!(p.validate_message("Attack").is_ok());
+        assert_eq!(
+            Err(CipherError::Message(
+                "Message cannot contain non-alphabetic symbols."
+            )),
+            p.validate_message("Attack at dawn")
+        );
+        assert!(p.encrypt("Attack at dawn").is_err());
+
+        // A symbol rejected by the first stage is reported in place
+        let p = Pipeline::new()
+            .then(Hill::from_phrase("CEFJCBDRH", 3))
+            .then(Caesar::new(3));
+        assert_eq!(
+            Err(CipherError::Symbol {
+                symbol: ' ',
+                offset: 6,
+                reason: "Message cannot contain non-alphabetic symbols.",
+            }),
+            p.validate_message("Attack at dawn")
+        );
+    }
+}
@@ -12,13 +12,37 @@
 //! This implementation uses the *latter* design, replacing all
 //! encountered 'J' characters with 'I'.
 //!
+use crate::common::cipher_error::CipherError;
 use crate::common::{alphabet, alphabet::Alphabet, cipher::Cipher, keygen::playfair_table};
 
 type Bigram = (char, char);
 
+/// The rule of the Playfair cipher by which a bigram was substituted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rule {
+    /// The letters were on the same row of the table, so were shifted along it.
+    Row,
+    /// The letters were on the same column of the table, so were shifted down it.
+    Column,
+    /// The letters were swapped for the other corners of the rectangle that they make.
+    Rectangle,
+}
+
+/// A step of an encryption with the Playfair cipher, as returned by `encrypt_with_trace()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    /// The bigram of the message, after any null character was added.
+    pub bigram: (char, char),
+    /// The rule by which the bigram was substituted.
+    pub rule: Rule,
+    /// The bigram of the ciphertext.
+    pub result: (char, char),
+}
+
 /// A Playfair cipher.
 ///
 /// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Playfair {
     /// The Playfair key table (5x5)
     rows: [String; 5],
@@ -26,27 +50,33 @@ pub struct Playfair {
     null_char: char,
 }
 
+/// The key of a Playfair cipher.
+///
+/// This struct is given to the `new()` method of `Playfair`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlayfairKey {
+    /// The keystream used to generate the playfair table.
+    pub keystream: String,
+    /// The character used to pad uneven messages during encryption, which defaults to 'X'.
+    pub null_char: Option<char>,
+}
+
 impl Cipher for Playfair {
-    type Key = (String, Option<char>);
+    type Key = PlayfairKey;
     type Algorithm = Playfair;
 
     /// Initialize a Playfair cipher.
     ///
-    /// The `key` tuple maps to the following `(String, Option<char>) = (keystream, null_char)`.
-    /// Where ...
-    ///
-    /// * The `keystream` is used to generate a playfair table.
-    /// * The `null_char` is the character that is used to pad uneven messages
-    /// during the encryption process. This value will default to 'X'.
+    /// The parts of the `key` are described by the fields of `PlayfairKey`.
     ///
     /// # Panics
     /// * The `keystream` must not be empty.
     /// * The `keystream` must not exceed the length of the playfair alphabet (25 characters).
     /// * The `keystream` must not contain non-alphabetic symbols or the letter 'J'.
     ///
-    fn new(key: (String, Option<char>)) -> Playfair {
-        let null_char = key.1.unwrap_or('X').to_ascii_uppercase();
-        let (rows, cols) = playfair_table(&key.0);
+    fn new(key: PlayfairKey) -> Playfair {
+        let null_char = key.null_char.unwrap_or('X').to_ascii_uppercase();
+        let (rows, cols) = playfair_table(&key.keystream);
 
         Playfair {
             rows,
@@ -72,9 +102,12 @@ impl Cipher for Playfair {
     /// Basic Usage:
     ///
     /// ```
-    /// use cipher_crypt::{Cipher, Playfair};
+    /// use cipher_crypt::{Cipher, Playfair, PlayfairKey};
     ///
-    /// let c = Playfair::new(("playfairexample".to_string(), None));
+    /// let c = Playfair::new(PlayfairKey {
+    ///     keystream: "playfairexample".to_string(),
+    ///     null_char: None,
+    /// });
     /// assert_eq!(
     ///     c.encrypt("Hidethegoldinthetreestump").unwrap(),
     ///     "BMODZBXDNABEKUDMUIXMKZZRYI"
@@ -82,18 +115,14 @@ impl Cipher for Playfair {
     /// ```
     ///
     fn encrypt(&self, message: &str) -> Result<String, &'static str> {
-        if !alphabet::PLAYFAIR.is_valid(&message) {
-            return Err("Message must only consist of alphabetic characters.");
-        } else if message.to_uppercase().contains(self.null_char) {
-            return Err("Message cannot contain the null character.");
-        }
+        self.validate_message(message)?;
 
         // Handles Rule 1 (Bigrams)
         let bmsg = self.bigram(&message.to_uppercase());
 
-        self.apply_rules(bmsg, |v, first, second| {
+        Ok(join(&self.apply_rules(bmsg, |v, first, second| {
             (v[(first + 1) % 5], v[(second + 1) % 5])
-        })
+        })))
     }
 
     /// Decrypt a message with the Playfair cipher.
@@ -113,9 +142,12 @@ impl Cipher for Playfair {
     /// Basic Usage:
     ///
     /// ```
-    /// use cipher_crypt::{Cipher, Playfair};
+    /// use cipher_crypt::{Cipher, Playfair, PlayfairKey};
     ///
-    /// let c = Playfair::new(("playfairexample".to_string(), None));
+    /// let c = Playfair::new(PlayfairKey {
+    ///     keystream: "playfairexample".to_string(),
+    ///     null_char: None,
+    /// });
     /// assert_eq!(
     ///     c.decrypt("BMODZBXDNABEKUDMUIXMKZZRYI").unwrap(),
     ///     "HIDETHEGOLDINTHETREXSTUMPX"
@@ -131,43 +163,178 @@ impl Cipher for Playfair {
         let bmsg = self.bigram(&message.to_uppercase());
 
         //Must be wary of negative wrap-around in modulo
-        self.apply_rules(bmsg, |v, first, second| {
+        Ok(join(&self.apply_rules(bmsg, |v, first, second| {
             (
                 v[first.checked_sub(1).unwrap_or(v.len() - 1)],
                 v[second.checked_sub(1).unwrap_or(v.len() - 1)],
             )
+        })))
+    }
+
+    /// Check that a message can be encrypted with the Playfair cipher.
+    ///
+    /// # Errors
+    /// * Message contains a non-alphabetic character (or the letter 'J').
+    /// * Message contains the null character.
+    /// * Message contains whitespace.
+    ///
+    /// # Examples
+    ///
+    /// Basic Usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, CipherError, Playfair, PlayfairKey};
+    ///
+    /// let c = Playfair::new(PlayfairKey {
+    ///     keystream: "playfairexample".to_string(),
+    ///     null_char: None,
+    /// });
+    /// assert!(c.validate_message("Hidethegoldinthetreestump").is_ok());
+    ///
+    /// let e = c.validate_message("Hidethegold!").unwrap_err();
+    /// assert_eq!(
+    ///     CipherError::Symbol {
+    ///         symbol: '!',
+    ///         offset: 11,
+    ///         reason: "Message must only consist of alphabetic characters.",
+    ///     },
+    ///     e
+    /// );
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        CipherError::find(
+            message,
+            "Message must only consist of alphabetic characters.",
+            |c| alphabet::PLAYFAIR.find_position(c).is_none(),
+        )?;
+
+        CipherError::find(message, "Message cannot contain the null character.", |c| {
+            c.to_ascii_uppercase() == self.null_char
         })
     }
+
+    /// Whether a character is foreign to the cipher, being outside of the table (which has no
+    /// `J`) or the null character.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        alphabet::PLAYFAIR.find_position(c).is_none() || c.to_ascii_uppercase() == self.null_char
+    }
 }
 
 impl Playfair {
+    /// The rows of the 5x5 table of the cipher, in uppercase.
+    ///
+    pub fn table(&self) -> &[String; 5] {
+        &self.rows
+    }
+
+    /// The character used to pad uneven messages, in uppercase.
+    ///
+    pub fn null_char(&self) -> char {
+        self.null_char
+    }
+
+    /// Draw the 5x5 table of the cipher, one row to a line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Playfair, PlayfairKey};
+    ///
+    /// let c = Playfair::new(PlayfairKey {
+    ///     keystream: "playfairexample".to_string(),
+    ///     null_char: None,
+    /// });
+    /// assert_eq!(
+    ///     "P L A Y F\nI R E X M\nB C D G H\nK N O Q S\nT U V W Z",
+    ///     c.render()
+    /// );
+    /// ```
+    ///
+    pub fn render(&self) -> String {
+        self.rows
+            .iter()
+            .map(|row| {
+                row.chars()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Encrypt a message with the Playfair cipher, returning the ciphertext along with the steps
+    /// taken to produce it: each bigram of the message, the rule applied to it and the bigram it
+    /// became.
+    ///
+    /// # Errors
+    /// * As for `encrypt()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::playfair::{Rule, Step};
+    /// use cipher_crypt::{Cipher, Playfair, PlayfairKey};
+    ///
+    /// let c = Playfair::new(PlayfairKey {
+    ///     keystream: "playfairexample".to_string(),
+    ///     null_char: None,
+    /// });
+    /// let (ciphertext, steps) = c.encrypt_with_trace("Hidethegold").unwrap();
+    ///
+    /// assert_eq!("BMODZBXDNAGE", ciphertext);
+    /// assert_eq!(
+    ///     Step {
+    ///         bigram: ('H', 'I'),
+    ///         rule: Rule::Rectangle,
+    ///         result: ('B', 'M'),
+    ///     },
+    ///     steps[0]
+    /// );
+    /// ```
+    ///
+    pub fn encrypt_with_trace(&self, message: &str) -> Result<(String, Vec<Step>), &'static str> {
+        self.validate_message(message)?;
+
+        let steps = self.apply_rules(self.bigram(&message.to_uppercase()), |v, first, second| {
+            (v[(first + 1) % 5], v[(second + 1) % 5])
+        });
+
+        Ok((join(&steps), steps))
+    }
+
     /// Apply the PlayFair cipher algorithm.
     ///
     /// The operations for encrypt and decrypt are identical
     /// except for the direction of the substitution choice.
     ///
-    fn apply_rules<F>(&self, bigrams: Vec<Bigram>, shift: F) -> Result<String, &'static str>
+    fn apply_rules<F>(&self, bigrams: Vec<Bigram>, shift: F) -> Vec<Step>
     where
         F: Fn(Vec<char>, usize, usize) -> Bigram,
     {
-        let mut text = String::new();
-        for bigram in bigrams {
-            let chars: Bigram;
-            if let Some(b) = self.apply_slice(bigram, &self.rows, &shift) {
-                // Rule 2 (Row)
-                chars = b;
-            } else if let Some(b) = self.apply_slice(bigram, &self.cols, &shift) {
-                // Rule 3 (Column)
-                chars = b;
-            } else {
-                // Rule 4 (Rectangle)
-                chars = self.apply_rectangle(bigram);
-            }
+        bigrams
+            .into_iter()
+            .map(|bigram| {
+                let (rule, result) = if let Some(b) = self.apply_slice(bigram, &self.rows, &shift) {
+                    // Rule 2 (Row)
+                    (Rule::Row, b)
+                } else if let Some(b) = self.apply_slice(bigram, &self.cols, &shift) {
+                    // Rule 3 (Column)
+                    (Rule::Column, b)
+                } else {
+                    // Rule 4 (Rectangle)
+                    (Rule::Rectangle, self.apply_rectangle(bigram))
+                };
 
-            text.push(chars.0);
-            text.push(chars.1);
-        }
-        Ok(text)
+                Step {
+                    bigram,
+                    rule,
+                    result,
+                }
+            })
+            .collect()
     }
 
     /// Apply rule 1 (bigrams).
@@ -268,6 +435,14 @@ impl Playfair {
     }
 }
 
+/// Joins the resulting bigrams of the steps into a piece of text.
+fn join(steps: &[Step]) -> String {
+    steps
+        .iter()
+        .flat_map(|s| vec![s.result.0, s.result.1])
+        .collect()
+}
+
 /// Identifies 2 corners of the rectangle.
 fn find_corners(b: Bigram, slices: &[String; 5]) -> (usize, usize) {
     let mut indices = (0, 0);
@@ -284,10 +459,14 @@ fn find_corners(b: Bigram, slices: &[String; 5]) -> (usize, usize) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::policy::ForeignCharPolicy;
 
     #[test]
     fn bigram_handles_repeats() {
-        let pf = Playfair::new(("test".to_string(), Some('X')));
+        let pf = Playfair::new(PlayfairKey {
+            keystream: "test".to_string(),
+            null_char: Some('X'),
+        });
         let message = "FIZZBAR";
         assert_eq!(
             vec![('F', 'I'), ('Z', 'X'), ('B', 'A'), ('R', 'X'),],
@@ -297,7 +476,10 @@ mod tests {
 
     #[test]
     fn bigram_handles_odd_length() {
-        let pf = Playfair::new(("test".to_string(), Some('Z')));
+        let pf = Playfair::new(PlayfairKey {
+            keystream: "test".to_string(),
+            null_char: Some('Z'),
+        });
         let message = "WORLD";
         assert_eq!(
             vec![('W', 'O'), ('R', 'L'), ('D', 'Z'),],
@@ -307,25 +489,74 @@ mod tests {
 
     #[test]
     fn invalid_encrypt_message_whitespace() {
-        let pf = Playfair::new(("playfairexample".to_string(), None));
+        let pf = Playfair::new(PlayfairKey {
+            keystream: "playfairexample".to_string(),
+            null_char: None,
+        });
         assert!(pf.encrypt("This contains whitespace").is_err());
     }
 
     #[test]
     fn invalid_encrypt_message_null_char() {
-        let pf = Playfair::new(("playfairexample".to_string(), Some('Z')));
+        let pf = Playfair::new(PlayfairKey {
+            keystream: "playfairexample".to_string(),
+            null_char: Some('Z'),
+        });
         assert!(pf.encrypt("Thiscontainsthenullcharz").is_err());
     }
 
+    #[test]
+    fn validate_message_null_char() {
+        let pf = Playfair::new(PlayfairKey {
+            keystream: "playfairexample".to_string(),
+            null_char: Some('Z'),
+        });
+        assert!(pf.validate_message("Thiscontainsthenullcharz").is_err());
+        assert!(pf.validate_message("Thisdoesnot").is_ok());
+    }
+
+    #[test]
+    fn validate_message_position() {
+        let pf = Playfair::new(PlayfairKey {
+            keystream: "playfairexample".to_string(),
+            null_char: Some('Z'),
+        });
+        assert_eq!(
+            Err(CipherError::Symbol {
+                symbol: ' ',
+                offset: 13,
+                reason: "Message must only consist of alphabetic characters.",
+            }),
+            pf.validate_message("Thiszcontains whitespace")
+        );
+
+        let e = pf.validate_message("Thiscontainsthenullcharz").unwrap_err();
+        assert_eq!(
+            CipherError::Symbol {
+                symbol: 'z',
+                offset: 23,
+                reason: "Message cannot contain the null character.",
+            },
+            e
+        );
+        assert_eq!(pf.encrypt("Thiscontainsthenullcharz"), Err(e.reason()));
+    }
+
     #[test]
     fn invalid_decrypt_message_symbols() {
-        let pf = Playfair::new(("playfairexample".to_string(), None));
+        let pf = Playfair::new(PlayfairKey {
+            keystream: "playfairexample".to_string(),
+            null_char: None,
+        });
         assert!(pf.decrypt("This!contains!whitespace").is_err());
     }
 
     #[test]
     fn simple_encrypt() {
-        let pf = Playfair::new(("playfairexample".to_string(), None));
+        let pf = Playfair::new(PlayfairKey {
+            keystream: "playfairexample".to_string(),
+            null_char: None,
+        });
         assert_eq!(
             "BMODZBXDNABEKUDMUIXMKZZRYI",
             pf.encrypt("Hidethegoldinthetreestump").unwrap(),
@@ -334,17 +565,71 @@ mod tests {
 
     #[test]
     fn simple_decrypt() {
-        let pf = Playfair::new(("playfairexample".to_string(), None));
+        let pf = Playfair::new(PlayfairKey {
+            keystream: "playfairexample".to_string(),
+            null_char: None,
+        });
         assert_eq!(
             "HIDETHEGOLDINTHETREXSTUMPX",
             pf.decrypt("BMODZBXDNABEKUDMUIXMKZZRYI").unwrap(),
         );
     }
 
+    #[test]
+    fn table_getter() {
+        let pf = Playfair::new(PlayfairKey {
+            keystream: "playfairexample".to_string(),
+            null_char: Some('q'),
+        });
+
+        assert_eq!("PLAYF", pf.table()[0]);
+        assert_eq!("TUVWZ", pf.table()[4]);
+        assert_eq!('Q', pf.null_char());
+    }
+
+    #[test]
+    fn trace_rules() {
+        let pf = Playfair::new(PlayfairKey {
+            keystream: "playfairexample".to_string(),
+            null_char: None,
+        });
+        let (ciphertext, steps) = pf.encrypt_with_trace("Dotreestump").unwrap();
+
+        assert_eq!(pf.encrypt("Dotreestump").unwrap(), ciphertext);
+        assert_eq!(
+            vec![
+                Rule::Column,
+                Rule::Rectangle,
+                Rule::Row,
+                Rule::Rectangle,
+                Rule::Rectangle,
+                Rule::Rectangle
+            ],
+            steps.iter().map(|s| s.rule).collect::<Vec<Rule>>()
+        );
+        assert_eq!(('E', 'X'), steps[2].bigram);
+    }
+
     #[test]
     fn negative_wrap_around() {
-        let pf = Playfair::new(("apt".to_string(), None));
+        let pf = Playfair::new(PlayfairKey {
+            keystream: "apt".to_string(),
+            null_char: None,
+        });
         let msg = "HELLOWORLD";
         assert_eq!("HELXOWORLD", pf.decrypt(&pf.encrypt(msg).unwrap()).unwrap());
     }
+
+    #[test]
+    fn strip_j_and_null_char() {
+        let pf = Playfair::new(PlayfairKey {
+            keystream: "playfairexample".to_string(),
+            null_char: None,
+        });
+
+        assert_eq!(
+            pf.encrypt("ustonebo"),
+            pf.encrypt_with_policy("Just one box!", ForeignCharPolicy::Strip)
+        );
+    }
 }
@@ -4,34 +4,44 @@
 //!
 use crate::common::alphabet::Alphabet;
 use crate::common::cipher::Cipher;
-use crate::common::{alphabet, keygen};
+#[cfg(feature = "parallel")]
+use crate::common::parallel;
+use crate::common::{alphabet, keygen, render};
 use std::collections::HashMap;
 
 /// A Polybius square cipher.
 ///
 /// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Polybius {
     square: HashMap<String, char>,
+    coordinates: HashMap<char, String>,
+    column_ids: [char; 6],
+    row_ids: [char; 6],
+}
+
+/// The key of a Polybius square cipher.
+///
+/// This struct is given to the `new()` method of `Polybius`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolybiusKey {
+    /// The phrase used to generate an alphanumeric keyed alphabet. It can contain the
+    /// characters `a-z 0-9`.
+    pub phrase: String,
+    /// The unique, alphabetic identifiers of the columns of the square.
+    pub column_ids: [char; 6],
+    /// The unique, alphabetic identifiers of the rows of the square.
+    pub row_ids: [char; 6],
 }
 
 impl Cipher for Polybius {
-    type Key = (String, [char; 6], [char; 6]);
+    type Key = PolybiusKey;
     type Algorithm = Polybius;
 
     /// Initialise a Polybius square cipher.
     ///
     /// In this implementation each part of the `key` is used to initialise a 6x6 polybius square.
-    /// The `key` tuple maps to the following `(String, [char; 6], [char; 6]) = (phase,
-    /// column_ids, row_ids)`.
-    ///
-    /// Where ...
-    ///
-    /// * `phrase` is used to generate an alphanumeric keyed alphabet. It can contain characters
-    /// `a-z 0-9`.
-    /// * `column_ids` are unique identifiers used for each column of the polybius square. Valid
-    /// characters are alphabetic only (`a-z`).
-    /// * `row_ids` are unique identifiers used for each row of the polybius square. Valid
-    /// characters can be alphabetic only (`a-z`).
+    /// The parts of the `key` are described by the fields of `PolybiusKey`.
     ///
     /// # Panics
     /// * If a non-alphanumeric symbol is part of the `key`.
@@ -57,20 +67,36 @@ impl Cipher for Polybius {
     /// Basic usage:
     ///
     /// ```
-    /// use cipher_crypt::{Cipher, Polybius};
+    /// use cipher_crypt::{Cipher, Polybius, PolybiusKey};
     ///
-    /// let p = Polybius::new((String::from("or0an3ge"), ['A','Z','C','D','E','F'],
-    ///     ['A','B','G','D','E','F']));;
+    /// let p = Polybius::new(PolybiusKey {
+    ///     phrase: String::from("or0an3ge"),
+    ///     column_ids: ['A', 'Z', 'C', 'D', 'E', 'F'],
+    ///     row_ids: ['A', 'B', 'G', 'D', 'E', 'F'],
+    /// });
     ///
     /// assert_eq!("EEAC AAazadaebabzdc adaebe EF ADdadagebzdc!",
     ///    p.encrypt("10 Oranges and 2 Apples!").unwrap());
     /// ```
     ///
-    fn new(key: (String, [char; 6], [char; 6])) -> Polybius {
-        let alphabet_key = keygen::keyed_alphabet(&key.0, &alphabet::ALPHANUMERIC, false);
-        let square = keygen::polybius_square(&alphabet_key, &key.1, &key.2);
+    fn new(key: PolybiusKey) -> Polybius {
+        let alphabet_key = keygen::keyed_alphabet(&key.phrase, &alphabet::ALPHANUMERIC, false);
+        let square = keygen::polybius_square(
+            &alphabet_key,
+            &alphabet::ALPHANUMERIC,
+            &key.column_ids,
+            &key.row_ids,
+        );
+
+        // The reverse of the square, so that each character of a message can be looked up
+        let coordinates = square.iter().map(|(id, &c)| (c, id.clone())).collect();
 
-        Polybius { square }
+        Polybius {
+            square,
+            coordinates,
+            column_ids: key.column_ids,
+            row_ids: key.row_ids,
+        }
     }
 
     /// Encrypt a message using a Polybius square cipher.
@@ -79,10 +105,13 @@ impl Cipher for Polybius {
     /// Basic usage:
     ///
     /// ```
-    /// use cipher_crypt::{Cipher, Polybius};
+    /// use cipher_crypt::{Cipher, Polybius, PolybiusKey};
     ///
-    /// let p = Polybius::new((String::from("p0lyb1us"), ['A','Z','C','D','E','F'],
-    ///     ['A','B','G','D','E','F']));;
+    /// let p = Polybius::new(PolybiusKey {
+    ///     phrase: String::from("p0lyb1us"),
+    ///     column_ids: ['A', 'Z', 'C', 'D', 'E', 'F'],
+    ///     row_ids: ['A', 'B', 'G', 'D', 'E', 'F'],
+    /// });
     ///
     /// assert_eq!("BCdfdfbcbdgf 🗡️ dfgcbf bfbcbzdf ezbcacac",
     ///    p.encrypt("Attack 🗡️ the east wall").unwrap());
@@ -91,12 +120,9 @@ impl Cipher for Polybius {
     fn encrypt(&self, message: &str) -> Result<String, &'static str> {
         Ok(message
             .chars()
-            .map(|c| {
-                if let Some((key, _)) = self.square.iter().find(|e| e.1 == &c) {
-                    key.clone()
-                } else {
-                    c.to_string()
-                }
+            .map(|c| match self.coordinates.get(&c) {
+                Some(id) => id.clone(),
+                None => c.to_string(),
             })
             .collect())
     }
@@ -107,10 +133,13 @@ impl Cipher for Polybius {
     /// Basic usage:
     ///
     /// ```
-    /// use cipher_crypt::{Cipher, Polybius};
+    /// use cipher_crypt::{Cipher, Polybius, PolybiusKey};
     ///
-    /// let p = Polybius::new((String::from("p0lyb1us"), ['A','Z','C','D','E','F'],
-    ///     ['A','B','G','D','E','F']));;
+    /// let p = Polybius::new(PolybiusKey {
+    ///     phrase: String::from("p0lyb1us"),
+    ///     column_ids: ['A', 'Z', 'C', 'D', 'E', 'F'],
+    ///     row_ids: ['A', 'B', 'G', 'D', 'E', 'F'],
+    /// });
     ///
     /// assert_eq!("Attack 🗡️ the east wall",
     ///    p.decrypt("BCdfdfbcbdgf 🗡️ dfgcbf bfbcbzdf ezbcacac").unwrap());
@@ -142,11 +171,154 @@ impl Cipher for Polybius {
 
         Ok(message)
     }
+
+    /// Whether a character is foreign to the cipher, being outside of `a-z`, `A-Z` and `0-9`.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        !c.is_ascii_alphanumeric()
+    }
+}
+
+impl Polybius {
+    /// The identifiers of the columns of the square.
+    ///
+    pub fn column_ids(&self) -> [char; 6] {
+        self.column_ids
+    }
+
+    /// The identifiers of the rows of the square.
+    ///
+    pub fn row_ids(&self) -> [char; 6] {
+        self.row_ids
+    }
+
+    /// Start building a Polybius square cipher, with defaults for the parts of the key that are not
+    /// set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Polybius};
+    ///
+    /// let p = Polybius::builder()
+    ///     .phrase("or0an3ge")
+    ///     .column_ids(['A', 'Z', 'C', 'D', 'E', 'F'])
+    ///     .row_ids(['A', 'B', 'G', 'D', 'E', 'F'])
+    ///     .build();
+    ///
+    /// assert_eq!("EEAC AAazadaebabzdc adaebe EF ADdadagebzdc!",
+    ///    p.encrypt("10 Oranges and 2 Apples!").unwrap());
+    /// ```
+    ///
+    pub fn builder() -> PolybiusBuilder {
+        PolybiusBuilder::default()
+    }
+
+    /// Encrypt a long message using a Polybius square cipher, sharing the work across threads.
+    ///
+    /// The message is split into chunks, which are encrypted on the threads of the global `rayon`
+    /// thread pool. The ciphertext is the same as that of `encrypt()`.
+    ///
+    #[cfg(feature = "parallel")]
+    pub fn encrypt_parallel(&self, message: &str) -> Result<String, &'static str> {
+        parallel::map_chunks(message, |chunk| self.encrypt(chunk))
+    }
+
+    /// Draw the square of the cipher, with its rows and columns labelled by their identifiers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::Polybius;
+    ///
+    /// let p = Polybius::builder().phrase("or0an3ge").build();
+    /// assert!(p.render().starts_with("  | A B C D E F\n--+------------\nA | o r 0 a n 3\n"));
+    /// ```
+    ///
+    pub fn render(&self) -> String {
+        let rows: Vec<(char, Vec<char>)> = self
+            .row_ids
+            .iter()
+            .map(|&r| {
+                let cells = self
+                    .column_ids
+                    .iter()
+                    .map(|&c| {
+                        let id = format!("{}{}", r, c).to_uppercase();
+                        self.square[&id].to_ascii_lowercase()
+                    })
+                    .collect();
+
+                (r, cells)
+            })
+            .collect();
+
+        render::labelled_grid(&self.column_ids, &rows)
+    }
+}
+
+/// Builds a Polybius square cipher one part of its key at a time.
+///
+/// This struct is created by the `builder()` method of `Polybius`. See its documentation for
+/// more.
+#[derive(Clone, Debug)]
+pub struct PolybiusBuilder {
+    phrase: String,
+    column_ids: [char; 6],
+    row_ids: [char; 6],
+}
+
+impl Default for PolybiusBuilder {
+    fn default() -> PolybiusBuilder {
+        PolybiusBuilder {
+            phrase: String::new(),
+            column_ids: ['A', 'B', 'C', 'D', 'E', 'F'],
+            row_ids: ['A', 'B', 'C', 'D', 'E', 'F'],
+        }
+    }
+}
+
+impl PolybiusBuilder {
+    /// Set the phrase used to key the square, which is the plain alphanumeric alphabet by
+    /// default.
+    ///
+    pub fn phrase(mut self, phrase: &str) -> PolybiusBuilder {
+        self.phrase = phrase.to_string();
+        self
+    }
+
+    /// Set the identifiers of the columns of the square, which are `A-F` by default.
+    ///
+    pub fn column_ids(mut self, column_ids: [char; 6]) -> PolybiusBuilder {
+        self.column_ids = column_ids;
+        self
+    }
+
+    /// Set the identifiers of the rows of the square, which are `A-F` by default.
+    ///
+    pub fn row_ids(mut self, row_ids: [char; 6]) -> PolybiusBuilder {
+        self.row_ids = row_ids;
+        self
+    }
+
+    /// Initialise the Polybius square cipher, as by `Polybius::new()`.
+    ///
+    /// # Panics
+    /// * The parts of the key are invalid, as for `Polybius::new()`.
+    ///
+    pub fn build(self) -> Polybius {
+        Polybius::new(PolybiusKey {
+            phrase: self.phrase,
+            column_ids: self.column_ids,
+            row_ids: self.row_ids,
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::policy::ForeignCharPolicy;
 
     #[test]
     fn encrypt_message() {
@@ -157,11 +329,11 @@ mod tests {
         //  D| l m p 4 q s
         //  E| 5 t u 6 v w
         //  F| 7 x 8 y 9 z
-        let p = Polybius::new((
-            "or0ange1bcdf2hijk3lmp4qs5tu6vw7x8y9z".to_string(),
-            ['A', 'B', 'C', 'D', 'E', 'F'],
-            ['A', 'B', 'C', 'D', 'E', 'F'],
-        ));
+        let p = Polybius::new(PolybiusKey {
+            phrase: "or0ange1bcdf2hijk3lmp4qs5tu6vw7x8y9z".to_string(),
+            column_ids: ['A', 'B', 'C', 'D', 'E', 'F'],
+            row_ids: ['A', 'B', 'C', 'D', 'E', 'F'],
+        });
 
         assert_eq!(
             "BBAC AAabadaeafbadf adaebe CA ADdcdcdabadf!",
@@ -169,13 +341,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn render_square() {
+        let p = Polybius::new(PolybiusKey {
+            phrase: String::from("or0an3ge"),
+            column_ids: ['A', 'Z', 'C', 'D', 'E', 'F'],
+            row_ids: ['A', 'B', 'G', 'D', 'E', 'F'],
+        });
+
+        assert_eq!(
+            "  | A Z C D E F\n\
+             --+------------\n\
+             A | o r 0 a n 3\n\
+             B | g e b c d f\n\
+             G | h i j k l m\n\
+             D | p q s t u v\n\
+             E | w x y z 1 2\n\
+             F | 4 5 6 7 8 9",
+            p.render()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn parallel_long_message() {
+        let p = Polybius::builder().phrase("or0an3ge").build();
+        let message = "10 Oranges and 2 Apples! ".repeat(5000);
+        let encrypted = p.encrypt_parallel(&message).unwrap();
+
+        assert_eq!(p.encrypt(&message).unwrap(), encrypted);
+        assert_eq!(message, p.decrypt(&encrypted).unwrap());
+    }
+
     #[test]
     fn decrypt_message() {
-        let p = Polybius::new((
-            "or0ange1bcdf2hijk3lmp4qs5tu6vw7x8y9z".to_string(),
-            ['A', 'B', 'C', 'D', 'E', 'F'],
-            ['A', 'B', 'C', 'D', 'E', 'F'],
-        ));
+        let p = Polybius::new(PolybiusKey {
+            phrase: "or0ange1bcdf2hijk3lmp4qs5tu6vw7x8y9z".to_string(),
+            column_ids: ['A', 'B', 'C', 'D', 'E', 'F'],
+            row_ids: ['A', 'B', 'C', 'D', 'E', 'F'],
+        });
 
         assert_eq!(
             "10 Oranges and 2 Apples!",
@@ -186,11 +390,11 @@ mod tests {
 
     #[test]
     fn invalid_decrypt_sequence() {
-        let p = Polybius::new((
-            "or0ange1bcdf2hijk3lmp4qs5tu6vw7x8y9z".to_string(),
-            ['A', 'B', 'C', 'D', 'E', 'F'],
-            ['A', 'B', 'C', 'D', 'E', 'F'],
-        ));
+        let p = Polybius::new(PolybiusKey {
+            phrase: "or0ange1bcdf2hijk3lmp4qs5tu6vw7x8y9z".to_string(),
+            column_ids: ['A', 'B', 'C', 'D', 'E', 'F'],
+            row_ids: ['A', 'B', 'C', 'D', 'E', 'F'],
+        });
 
         //The sequnce 'AZ' is unknown to the polybius square
         assert!(p
@@ -201,11 +405,11 @@ mod tests {
     #[test]
     fn with_utf8() {
         let m = "Attack 🗡️ the east wall";
-        let p = Polybius::new((
-            "or0ange1bcdf2hijk3lmp4qs5tu6vw7x8y9z".to_string(),
-            ['A', 'B', 'C', 'D', 'E', 'F'],
-            ['A', 'B', 'C', 'D', 'E', 'F'],
-        ));
+        let p = Polybius::new(PolybiusKey {
+            phrase: "or0ange1bcdf2hijk3lmp4qs5tu6vw7x8y9z".to_string(),
+            column_ids: ['A', 'B', 'C', 'D', 'E', 'F'],
+            row_ids: ['A', 'B', 'C', 'D', 'E', 'F'],
+        });
 
         assert_eq!(m, p.decrypt(&p.encrypt(m).unwrap()).unwrap());
     }
@@ -213,30 +417,56 @@ mod tests {
     #[test]
     #[should_panic]
     fn invalid_key_phrase() {
-        Polybius::new((
-            "F@IL".to_string(),
-            ['A', 'B', 'C', 'D', 'E', 'F'],
-            ['A', 'B', 'C', 'D', 'E', 'F'],
-        ));
+        Polybius::new(PolybiusKey {
+            phrase: "F@IL".to_string(),
+            column_ids: ['A', 'B', 'C', 'D', 'E', 'F'],
+            row_ids: ['A', 'B', 'C', 'D', 'E', 'F'],
+        });
     }
 
     #[test]
     #[should_panic]
     fn invalid_ids() {
-        Polybius::new((
-            "oranges".to_string(),
-            ['A', '!', 'C', 'D', 'E', 'F'],
-            ['A', 'B', '@', 'D', 'E', 'F'],
-        ));
+        Polybius::new(PolybiusKey {
+            phrase: "oranges".to_string(),
+            column_ids: ['A', '!', 'C', 'D', 'E', 'F'],
+            row_ids: ['A', 'B', '@', 'D', 'E', 'F'],
+        });
     }
 
     #[test]
     #[should_panic]
     fn repeated_ids() {
-        Polybius::new((
-            "oranges".to_string(),
-            ['A', 'A', 'C', 'D', 'E', 'F'],
-            ['A', 'C', 'C', 'D', 'E', 'F'],
-        ));
+        Polybius::new(PolybiusKey {
+            phrase: "oranges".to_string(),
+            column_ids: ['A', 'A', 'C', 'D', 'E', 'F'],
+            row_ids: ['A', 'C', 'C', 'D', 'E', 'F'],
+        });
+    }
+
+    #[test]
+    fn builder_defaults() {
+        let ids = ['A', 'B', 'C', 'D', 'E', 'F'];
+        let p = Polybius::builder().build();
+
+        assert_eq!(
+            Polybius::new(PolybiusKey {
+                phrase: String::new(),
+                column_ids: ids,
+                row_ids: ids
+            })
+            .encrypt("Attack at 1200"),
+            p.encrypt("Attack at 1200")
+        );
+    }
+
+    #[test]
+    fn strip_keeps_digits() {
+        let p = Polybius::builder().build();
+
+        assert_eq!(
+            p.encrypt("Attackat1200"),
+            p.encrypt_with_policy("Attack at 1200!", ForeignCharPolicy::Strip)
+        );
     }
 }
@@ -53,21 +53,20 @@ const SUBSTITUTION_TABLE: [[usize; 26]; 13] = [
 /// A Porta cipher.
 ///
 /// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Porta {
     key: String,
 }
 
-impl Cipher for Porta {
-    type Key = String;
-    type Algorithm = Porta;
-
+impl Porta {
     /// Initialize a Porta cipher given a specific key.
     ///
     /// # Panics
     /// * The `key` is empty.
     /// * The `key` contains a non-alphabetic symbol.
     ///
-    fn new(key: String) -> Porta {
+    pub fn new(key: impl Into<String>) -> Porta {
+        let key = key.into();
         if key.is_empty() {
             panic!("The key is empty.");
         }
@@ -78,6 +77,23 @@ impl Cipher for Porta {
         Porta { key }
     }
 
+    /// The key of the cipher.
+    ///
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl Cipher for Porta {
+    type Key = String;
+    type Algorithm = Porta;
+
+    /// Initialize a Porta cipher given a specific key, as by `Porta::new()`.
+    ///
+    fn new(key: String) -> Porta {
+        Porta::new(key)
+    }
+
     /// Encrypt a message using a Porta cipher.
     ///
     /// # Examples
@@ -86,14 +102,14 @@ impl Cipher for Porta {
     /// ```
     /// use cipher_crypt::{Cipher, Porta};
     ///
-    /// let v = Porta::new("melon".into());
+    /// let v = Porta::new("melon");
     /// assert_eq!(v.encrypt("We ride at dawn!").unwrap(), "Dt mpwx pb xtdl!");
     /// ```
     ///
     fn encrypt(&self, message: &str) -> Result<String, &'static str> {
         Ok(substitute::key_substitution(
             message,
-            &cyclic_keystream(&self.key, message),
+            cyclic_keystream(&self.key, message),
             |mi, ki| SUBSTITUTION_TABLE[ki / 2][mi],
         ))
     }
@@ -106,7 +122,7 @@ impl Cipher for Porta {
     /// ```
     /// use cipher_crypt::{Cipher, Porta};
     ///
-    /// let v = Porta::new(String::from("melon"));
+    /// let v = Porta::new("melon");
     /// assert_eq!(v.decrypt("Dt mpwx pb xtdl!").unwrap(), "We ride at dawn!");
     /// ```
     ///
@@ -122,21 +138,21 @@ mod tests {
     #[test]
     fn encrypt() {
         let message = "attackatdawn";
-        let porta = Porta::new("lemon".into());
+        let porta = Porta::new("lemon");
         assert_eq!(porta.encrypt(message).unwrap(), "seauvppaxtel");
     }
 
     #[test]
     fn decrypt() {
         let ciphertext = "seauvppaxtel";
-        let porta = Porta::new("lemon".into());
+        let porta = Porta::new("lemon");
         assert_eq!(porta.decrypt(ciphertext).unwrap(), "attackatdawn");
     }
 
     #[test]
     fn mixed_case() {
         let message = "Attack at Dawn!";
-        let porta = Porta::new("lemon".into());
+        let porta = Porta::new("lemon");
         let ciphertext = porta.encrypt(message).unwrap();
         let decrypted = porta.decrypt(&ciphertext).unwrap();
 
@@ -146,7 +162,7 @@ mod tests {
     #[test]
     fn with_utf8() {
         let message = "Peace 🗡️ Freedom and Liberty!";
-        let porta = Porta::new("utfeightisfun".into());
+        let porta = Porta::new("utfeightisfun");
         let ciphertext = porta.encrypt(message).unwrap();
         let decrypted = porta.decrypt(&ciphertext).unwrap();
 
@@ -155,18 +171,18 @@ mod tests {
 
     #[test]
     fn valid_key() {
-        Porta::new("LeMon".into());
+        Porta::new("LeMon");
     }
 
     #[test]
     #[should_panic]
     fn key_with_symbols() {
-        Porta::new("!em@n".into());
+        Porta::new("!em@n");
     }
 
     #[test]
     #[should_panic]
     fn key_with_whitespace() {
-        Porta::new("wow this key is a real lemon".into());
+        Porta::new("wow this key is a real lemon");
     }
 }
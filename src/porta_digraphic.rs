@@ -0,0 +1,330 @@
+//! The Porta digraphic cipher was described by Giambattista della Porta in his 1563 work _De
+//! Furtivis Literarum Notis_. It is the earliest known digraphic cipher, where pairs of letters
+//! are encrypted together rather than one at a time.
+//!
+//! Porta used an alphabet of 20 letters, without `J`, `K`, `U`, `W`, `X` or `Y`. His table had a
+//! row for the first letter of each pair and a column for the second, and each of the 400 cells
+//! held a unique symbol of his own invention. As there is a single symbol for each pair, the
+//! ciphertext is half the length of the message.
+//!
+//! In this implementation the cells of the table are numbered from `001` to `400`, and either
+//! the numbers or a set of 400 symbols (taken from the Unified Canadian Aboriginal Syllabics
+//! block of Unicode) can be used as the output. The rows and columns can each be keyed by a
+//! keyword. Before encryption the letters missing from the alphabet are replaced: `J` and `Y`
+//! with `I`, `K` with `C`, `U` and `W` with `V`, and `X` with `S`. A message with an odd number
+//! of letters is padded with a `Z`.
+//!
+use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
+
+/// The 20 letter alphabet of the table.
+pub const ALPHABET: &str = "abcdefghilmnopqrstvz";
+
+// The first of the 400 consecutive symbols used by the symbolic output
+const FIRST_SYMBOL: u32 = 0x1401;
+
+/// The forms of output for the Porta digraphic cipher.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// Each pair of letters is replaced by the three digit number of its cell, and the numbers
+    /// are separated by spaces.
+    Numeric,
+    /// Each pair of letters is replaced by the symbol in its cell.
+    Symbolic,
+}
+
+/// A Porta digraphic cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortaDigraphic {
+    rows: Vec<char>,
+    columns: Vec<char>,
+    output: Output,
+}
+
+/// The key of a Porta digraphic cipher.
+///
+/// This struct is given to the `new()` method of `PortaDigraphic`. See its documentation for
+/// more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PortaDigraphicKey {
+    /// Orders the letters of the rows, which hold the first letter of each pair. If it is
+    /// empty, the rows are in alphabetical order.
+    pub row_keyword: String,
+    /// Orders the letters of the columns, which hold the second letter of each pair. If it
+    /// is empty, the columns are in alphabetical order.
+    pub column_keyword: String,
+    /// The form of the ciphertext.
+    pub output: Output,
+}
+
+impl Cipher for PortaDigraphic {
+    type Key = PortaDigraphicKey;
+    type Algorithm = PortaDigraphic;
+
+    /// Initialise a Porta digraphic cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `PortaDigraphicKey`.
+    ///
+    /// # Panics
+    /// * The `row_keyword` or `column_keyword` contains a non-alphabetic symbol.
+    ///
+    fn new(key: PortaDigraphicKey) -> PortaDigraphic {
+        let PortaDigraphicKey {
+            row_keyword,
+            column_keyword,
+            output,
+        } = key;
+
+        PortaDigraphic {
+            rows: keyed_alphabet(&row_keyword),
+            columns: keyed_alphabet(&column_keyword),
+            output,
+        }
+    }
+
+    /// Encrypt a message using a Porta digraphic cipher.
+    ///
+    /// Whitespace within the message is not preserved.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::porta_digraphic::Output;
+    /// use cipher_crypt::{Cipher, PortaDigraphic, PortaDigraphicKey};
+    ///
+    /// let p = PortaDigraphic::new(PortaDigraphicKey {
+    ///     row_keyword: String::new(),
+    ///     column_keyword: String::new(),
+    ///     output: Output::Numeric,
+    /// });
+    /// assert_eq!("018 341 043 018 061 372", p.encrypt("Attack at dawn").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.validate_message(message)?;
+
+        let mut letters: Vec<char> = message
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| match c.to_ascii_lowercase() {
+                'j' | 'y' => 'i',
+                'k' => 'c',
+                'u' | 'w' => 'v',
+                'x' => 's',
+                l => l,
+            })
+            .collect();
+        if letters.len() % 2 == 1 {
+            letters.push('z');
+        }
+
+        let cells = letters.chunks(2).map(|pair| {
+            let row = self.rows.iter().position(|&r| r == pair[0]).unwrap();
+            let column = self.columns.iter().position(|&c| c == pair[1]).unwrap();
+
+            row * ALPHABET.len() + column
+        });
+
+        Ok(match self.output {
+            Output::Numeric => cells
+                .map(|cell| format!("{:03}", cell + 1))
+                .collect::<Vec<String>>()
+                .join(" "),
+            Output::Symbolic => cells
+                .map(|cell| std::char::from_u32(FIRST_SYMBOL + cell as u32).unwrap())
+                .collect(),
+        })
+    }
+
+    /// Decrypt a message using a Porta digraphic cipher.
+    ///
+    /// Whitespace within symbolic ciphertext is ignored. The plaintext is lowercase.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::porta_digraphic::Output;
+    /// use cipher_crypt::{Cipher, PortaDigraphic, PortaDigraphicKey};
+    ///
+    /// let p = PortaDigraphic::new(PortaDigraphicKey {
+    ///     row_keyword: String::new(),
+    ///     column_keyword: String::new(),
+    ///     output: Output::Symbolic,
+    /// });
+    /// assert_eq!("attaccatdavn", p.decrypt("ᐒᕕᐫᐒᐽᕴ").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let cells: Vec<usize> = match self.output {
+            Output::Numeric => ciphertext
+                .split_whitespace()
+                .map(|n| match n.parse::<usize>() {
+                    Ok(cell) if (1..=400).contains(&cell) => Ok(cell - 1),
+                    _ => Err("Ciphertext contains a number outside of the range 001 to 400."),
+                })
+                .collect::<Result<Vec<usize>, &'static str>>()?,
+            Output::Symbolic => ciphertext
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .map(|c| match (c as u32).checked_sub(FIRST_SYMBOL) {
+                    Some(cell) if cell < 400 => Ok(cell as usize),
+                    _ => Err("Ciphertext contains an unknown symbol."),
+                })
+                .collect::<Result<Vec<usize>, &'static str>>()?,
+        };
+
+        Ok(cells
+            .into_iter()
+            .flat_map(|cell| {
+                vec![
+                    self.rows[cell / ALPHABET.len()],
+                    self.columns[cell % ALPHABET.len()],
+                ]
+            })
+            .collect())
+    }
+
+    /// Check that a message can be encrypted with a Porta digraphic cipher.
+    ///
+    /// Returns `Err` if the message contains a symbol that is not alphabetic or whitespace.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::porta_digraphic::Output;
+    /// use cipher_crypt::{Cipher, PortaDigraphic, PortaDigraphicKey};
+    ///
+    /// let p = PortaDigraphic::new(PortaDigraphicKey {
+    ///     row_keyword: String::new(),
+    ///     column_keyword: String::new(),
+    ///     output: Output::Numeric,
+    /// });
+    ///
+    /// assert!(p.validate_message("Attack at dawn").is_ok());
+    /// assert!(p.validate_message("Attack at dawn!").is_err());
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        CipherError::find(
+            message,
+            "Message contains a symbol that is not alphabetic or whitespace.",
+            |c| !c.is_whitespace() && !c.is_ascii_alphabetic(),
+        )
+    }
+}
+
+/// The letters of the 20 letter alphabet, beginning with the distinct letters of the `keyword`.
+///
+fn keyed_alphabet(keyword: &str) -> Vec<char> {
+    if !keyword.chars().all(|c| c.is_ascii_alphabetic()) {
+        panic!("Keyword contains a non-alphabetic symbol.");
+    }
+
+    let mut keyed: Vec<char> = Vec::new();
+    for c in keyword.to_ascii_lowercase().chars().chain(ALPHABET.chars()) {
+        if ALPHABET.contains(c) && !keyed.contains(&c) {
+            keyed.push(c);
+        }
+    }
+
+    keyed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn porta(output: Output) -> PortaDigraphic {
+        PortaDigraphic::new(PortaDigraphicKey {
+            row_keyword: String::from("fortune"),
+            column_keyword: String::from("silent"),
+            output,
+        })
+    }
+
+    #[test]
+    fn encrypt_test() {
+        assert_eq!(
+            "284 106 284 142 073 115 270 027 180",
+            porta(Output::Numeric)
+                .encrypt("Meet me by the old oak")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_test() {
+        assert_eq!(
+            "meetmebitheoldoacz",
+            porta(Output::Numeric)
+                .decrypt("284 106 284 142 073 115 270 027 180")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn symbolic_matches_numeric() {
+        let m = "Meet me by the old oak";
+        let numbers: Vec<u32> = porta(Output::Numeric)
+            .encrypt(m)
+            .unwrap()
+            .split(' ')
+            .map(|n| n.parse().unwrap())
+            .collect();
+        let symbols: Vec<u32> = porta(Output::Symbolic)
+            .encrypt(m)
+            .unwrap()
+            .chars()
+            .map(|c| c as u32 - FIRST_SYMBOL + 1)
+            .collect();
+
+        assert_eq!(numbers, symbols);
+    }
+
+    #[test]
+    fn table_is_complete() {
+        let p = porta(Output::Symbolic);
+        let pairs: Vec<String> = ALPHABET
+            .chars()
+            .flat_map(|a| ALPHABET.chars().map(move |b| format!("{}{}", a, b)))
+            .collect();
+
+        let c = p.encrypt(&pairs.concat()).unwrap();
+        assert_eq!(400, c.chars().count());
+        assert_eq!(pairs.concat(), p.decrypt(&c).unwrap());
+    }
+
+    #[test]
+    fn missing_letters_replaced() {
+        let p = porta(Output::Numeric);
+        assert_eq!(p.encrypt("icvvsi").unwrap(), p.encrypt("jkuwxy").unwrap());
+    }
+
+    #[test]
+    fn invalid_ciphertext() {
+        assert!(porta(Output::Numeric).decrypt("000").is_err());
+        assert!(porta(Output::Numeric).decrypt("401").is_err());
+        assert!(porta(Output::Numeric).decrypt("1a").is_err());
+        assert!(porta(Output::Symbolic).decrypt("abc").is_err());
+    }
+
+    #[test]
+    fn with_utf8() {
+        assert!(porta(Output::Symbolic).encrypt("Peace 🗡️ Freedom").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn keyword_with_symbols() {
+        PortaDigraphic::new(PortaDigraphicKey {
+            row_keyword: String::from("fort une"),
+            column_keyword: String::new(),
+            output: Output::Numeric,
+        });
+    }
+}
@@ -0,0 +1,292 @@
+//! The Quagmire ciphers are a family of four periodic polyalphabetic ciphers, standardised by the
+//! American Cryptogram Association (ACA). Like the Vigenère cipher, each letter of the message is
+//! enciphered with a different row of a tableau, selected by a repeating indicator keyword.
+//! Unlike the Vigenère cipher, the alphabets of the tableau are mixed using a keyword.
+//!
+//! The four variants differ only in which of the alphabets are keyed:
+//!
+//! * Quagmire I - a keyed plaintext alphabet and a straight ciphertext alphabet.
+//! * Quagmire II - a straight plaintext alphabet and a keyed ciphertext alphabet.
+//! * Quagmire III - the plaintext and ciphertext alphabets are keyed with the same keyword.
+//! * Quagmire IV - the plaintext and ciphertext alphabets are keyed with different keywords.
+//!
+//! Each row of the tableau slides the ciphertext alphabet along so that a letter of the
+//! indicator sits beneath the plaintext letter `A`.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::keygen::{cyclic_keystream, keyed_alphabet};
+use crate::common::substitute;
+
+/// A Quagmire cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quagmire {
+    plain: Vec<usize>,
+    cipher: Vec<usize>,
+    indicator: String,
+}
+
+/// The key of a Quagmire cipher.
+///
+/// This struct is given to the `new()` method of `Quagmire`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuagmireKey {
+    /// The keyword of the plaintext alphabet - if it is `None` the straight alphabet is
+    /// used.
+    pub plain_keyword: Option<String>,
+    /// The keyword of the ciphertext alphabet - if it is `None` the straight alphabet is
+    /// used.
+    pub cipher_keyword: Option<String>,
+    /// The keyword that selects the row of the tableau for each letter.
+    pub indicator: String,
+}
+
+impl Cipher for Quagmire {
+    type Key = QuagmireKey;
+    type Algorithm = Quagmire;
+
+    /// Initialise a Quagmire cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `QuagmireKey`.
+    ///
+    /// A Quagmire I is therefore initialised with only a `plain_keyword`, a Quagmire II with only
+    /// a `cipher_keyword`, a Quagmire III with the same keyword for both and a Quagmire IV with
+    /// two different keywords.
+    ///
+    /// # Panics
+    /// * A keyword or the `indicator` is empty.
+    /// * A keyword or the `indicator` contains a non-alphabetic symbol.
+    ///
+    fn new(key: QuagmireKey) -> Quagmire {
+        let QuagmireKey {
+            plain_keyword,
+            cipher_keyword,
+            indicator,
+        } = key;
+        if indicator.is_empty() {
+            panic!("The indicator is empty.");
+        }
+        if !alphabet::STANDARD.is_valid(&indicator) {
+            panic!("The indicator contains a non-alphabetic symbol.");
+        }
+
+        Quagmire {
+            plain: tableau_alphabet(plain_keyword),
+            cipher: tableau_alphabet(cipher_keyword),
+            indicator,
+        }
+    }
+
+    /// Encrypt a message using a Quagmire cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Quagmire, QuagmireKey};
+    ///
+    /// // A Quagmire III cipher
+    /// let keyword = Some(String::from("springfever"));
+    /// let q = Quagmire::new(QuagmireKey {
+    ///     plain_keyword: keyword.clone(),
+    ///     cipher_keyword: keyword,
+    ///     indicator: String::from("flower"),
+    /// });
+    ///
+    /// assert_eq!("Fpiwav fp uwtx!", q.encrypt("Attack at dawn!").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        Ok(substitute::tableau_substitution(
+            message,
+            cyclic_keystream(&self.indicator, message),
+            &self.plain,
+            &self.cipher,
+            |ki| self.shift(ki),
+        ))
+    }
+
+    /// Decrypt a message using a Quagmire cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Quagmire, QuagmireKey};
+    ///
+    /// // A Quagmire III cipher
+    /// let keyword = Some(String::from("springfever"));
+    /// let q = Quagmire::new(QuagmireKey {
+    ///     plain_keyword: keyword.clone(),
+    ///     cipher_keyword: keyword,
+    ///     indicator: String::from("flower"),
+    /// });
+    ///
+    /// assert_eq!("Attack at dawn!", q.decrypt("Fpiwav fp uwtx!").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        Ok(substitute::tableau_substitution(
+            ciphertext,
+            cyclic_keystream(&self.indicator, ciphertext),
+            &self.cipher,
+            &self.plain,
+            |ki| -self.shift(ki),
+        ))
+    }
+}
+
+impl Quagmire {
+    /// The number of positions the ciphertext alphabet is slid along, so that the indicator
+    /// letter `ki` sits beneath the plaintext letter `A`.
+    ///
+    fn shift(&self, ki: usize) -> isize {
+        let under = self.cipher.iter().position(|&c| c == ki).unwrap() as isize;
+        let a = self.plain.iter().position(|&p| p == 0).unwrap() as isize;
+
+        under - a
+    }
+}
+
+/// The indices of the letters of an alphabet, keyed by an optional keyword.
+///
+fn tableau_alphabet(keyword: Option<String>) -> Vec<usize> {
+    let letters = match keyword {
+        Some(ref k) if k.is_empty() => panic!("The keyword is empty."),
+        Some(k) => keyed_alphabet(&k, &alphabet::STANDARD, false),
+        None => (0..26)
+            .map(|i| alphabet::STANDARD.get_letter(i, false))
+            .collect(),
+    };
+
+    letters
+        .chars()
+        .map(|c| alphabet::STANDARD.find_position(c).unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &str = "the quick brown fox jumps over the lazy dog";
+
+    fn quagmire(plain: Option<&str>, cipher: Option<&str>, indicator: &str) -> Quagmire {
+        Quagmire::new(QuagmireKey {
+            plain_keyword: plain.map(String::from),
+            cipher_keyword: cipher.map(String::from),
+            indicator: String::from(indicator),
+        })
+    }
+
+    #[test]
+    fn encrypt_quagmire_one() {
+        let q = quagmire(Some("springfever"), None, "flower");
+        assert_eq!(
+            "qpm gqlhr ppnea ixk jdndf fdpy wsu lrva rfa",
+            q.encrypt(MESSAGE).unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_quagmire_one() {
+        let q = quagmire(Some("springfever"), None, "flower");
+        assert_eq!(
+            MESSAGE,
+            q.decrypt("qpm gqlhr ppnea ixk jdndf fdpy wsu lrva rfa")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn encrypt_quagmire_two() {
+        let q = quagmire(None, Some("springfever"), "flower");
+        assert_eq!(
+            "zxw dpbvs qhuyq ufq lwogb brfx azs orgj ubh",
+            q.encrypt(MESSAGE).unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_quagmire_two() {
+        let q = quagmire(None, Some("springfever"), "flower");
+        assert_eq!(
+            MESSAGE,
+            q.decrypt("zxw dpbvs qhuyq ufq lwogb brfx azs orgj ubh")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn encrypt_quagmire_three() {
+        let q = quagmire(Some("springfever"), Some("springfever"), "flower");
+        assert_eq!(
+            "mtl fqwvw qklkp hpb djjva gfsz pwt jrwg ugi",
+            q.encrypt(MESSAGE).unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_quagmire_three() {
+        let q = quagmire(Some("springfever"), Some("springfever"), "flower");
+        assert_eq!(
+            MESSAGE,
+            q.decrypt("mtl fqwvw qklkp hpb djjva gfsz pwt jrwg ugi")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn encrypt_quagmire_four() {
+        let q = quagmire(Some("sensory"), Some("percussion"), "extra");
+        assert_eq!("Ednrdb xn samq!", q.encrypt("Attack at dawn!").unwrap());
+    }
+
+    #[test]
+    fn decrypt_quagmire_four() {
+        let q = quagmire(Some("sensory"), Some("percussion"), "extra");
+        assert_eq!("Attack at dawn!", q.decrypt("Ednrdb xn samq!").unwrap());
+    }
+
+    #[test]
+    fn straight_alphabets_are_vigenere() {
+        // Without any keyed alphabets, the tableau is the tabula recta
+        let q = quagmire(None, None, "lemon");
+        assert_eq!("lxfopvefrnhr", q.encrypt("attackatdawn").unwrap());
+    }
+
+    #[test]
+    fn with_utf8() {
+        let q = quagmire(Some("utf"), Some("eight"), "peace");
+        let m = "Peace 🗡️ Freedom and Liberty!";
+
+        assert_eq!(m, q.decrypt(&q.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_indicator() {
+        quagmire(Some("springfever"), None, "");
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_keyword() {
+        quagmire(None, Some(""), "flower");
+    }
+
+    #[test]
+    #[should_panic]
+    fn keyword_with_symbols() {
+        quagmire(Some("spring fever"), None, "flower");
+    }
+
+    #[test]
+    #[should_panic]
+    fn indicator_with_symbols() {
+        quagmire(Some("springfever"), None, "fl0wer");
+    }
+}
@@ -2,13 +2,17 @@
 //! incredibly insecure.
 //!
 //! This implementation currently transposes all input characters including whitespace and
-//! punctuation.
+//! punctuation. With the `graphemes` feature enabled, each grapheme cluster (such as a letter
+//! with its combining marks) is transposed as a whole.
+
+use crate::common::cipher::Cipher;
+use crate::common::grapheme;
+use std::borrow::Cow;
 
 /// A Railfence cipher.
 ///
 /// This struct is created by the `new()` method. See its documentation for more.
-use crate::common::cipher::Cipher;
-
+#[derive(Clone, Debug, PartialEq)]
 pub struct Railfence {
     rails: usize,
 }
@@ -17,17 +21,10 @@ impl Cipher for Railfence {
     type Key = usize;
     type Algorithm = Railfence;
 
-    /// Initialise a Railfence cipher given a specific key (number of rails).
-    ///
-    /// # Panics
-    /// * The `key` is 0.
+    /// Initialise a Railfence cipher given a specific key (number of rails), as by `Railfence::new()`.
     ///
     fn new(key: usize) -> Railfence {
-        if key == 0 {
-            panic!("The key is 0.");
-        }
-
-        Railfence { rails: key }
+        Railfence::new(key)
     }
 
     /// Encrypt a message using a Railfence cipher.
@@ -58,18 +55,20 @@ impl Cipher for Railfence {
         //   The encrypted message is then read line by line:
         //      Hoo!el,Wrdl l
 
-        // We simply return the message as the 'encrypted' message when there is one rail.
-        // This is because the message is transposed along a single rail without being altered.
-        if self.rails == 1 {
+        // We simply return the message as the 'encrypted' message when there is one rail, or a
+        // rail for each character. This is because the message is transposed without being
+        // altered.
+        if self.leaves_unchanged(message) {
             return Ok(message.to_string());
         }
 
         // Initialise the fence (a simple table)
-        // The form of an entry is (bool, char) => (is_msg_element, msg_element)
-        let mut table = vec![vec![(false, '.'); message.len()]; self.rails];
+        // The form of an entry is (bool, &str) => (is_msg_element, msg_element)
+        let elements = grapheme::units(message);
+        let mut table = vec![vec![(false, ""); elements.len()]; self.rails];
 
         //Transpose the message along the fence
-        for (col, element) in message.chars().enumerate() {
+        for (col, &element) in elements.iter().enumerate() {
             //Given the column (ith element of the message), determine which row to place the
             //character on
             let rail = Railfence::calc_current_rail(col, self.rails);
@@ -80,7 +79,7 @@ impl Cipher for Railfence {
             .iter()
             .flatten()
             .filter(|(is_element, _)| *is_element)
-            .map(|(_, element)| element)
+            .map(|(_, element)| *element)
             .collect::<String>())
     }
 
@@ -116,28 +115,29 @@ impl Cipher for Railfence {
         //   The decrypted message is then read in a zigzag:
         //      Hello, World!
 
-        // As mentioned previously, a single rail means that the original message has not been
-        // altered
-        if self.rails == 1 {
+        // As mentioned previously, a single rail (or a rail for each character) means that the
+        // original message has not been altered
+        if self.leaves_unchanged(ciphertext) {
             return Ok(ciphertext.to_string());
         }
 
-        let mut table = vec![vec![(false, '.'); ciphertext.len()]; self.rails];
+        let elements = grapheme::units(ciphertext);
+        let mut table = vec![vec![(false, ""); elements.len()]; self.rails];
 
         // Traverse the table and mark the elements that will be filled by the cipher text
-        for col in 0..ciphertext.len() {
+        for col in 0..elements.len() {
             let rail = Railfence::calc_current_rail(col, self.rails);
             table[rail][col].0 = true;
         }
 
         // Fill the identified positions in the table with the ciphertext, line by line
-        let mut ct_chars = ciphertext.chars();
+        let mut ct_elements = elements.iter();
         'outer: for row in &mut table {
             // For each element in the row, determine if a char should be placed there
             for element in row.iter_mut() {
                 if element.0 {
-                    if let Some(c) = ct_chars.next() {
-                        *element = (element.0, c);
+                    if let Some(&e) = ct_elements.next() {
+                        *element = (element.0, e);
                     } else {
                         // We have transposed all chars of the cipher text
                         break 'outer;
@@ -148,22 +148,112 @@ impl Cipher for Railfence {
 
         // From the transposed cipher text construct the original message
         let mut message = String::new();
-        for col in 0..ciphertext.len() {
+        for col in 0..elements.len() {
             // For this column, determine which row we should read from to get the next char
             // of the message
             let rail = Railfence::calc_current_rail(col, self.rails);
-            message.push(table[rail][col].1);
+            message.push_str(table[rail][col].1);
         }
 
         Ok(message)
     }
+
+    /// Encrypt a message, borrowing it where the fence would leave it unchanged.
+    ///
+    fn encrypt_cow<'a>(&self, message: &'a str) -> Result<Cow<'a, str>, &'static str> {
+        if self.leaves_unchanged(message) {
+            return Ok(Cow::Borrowed(message));
+        }
+
+        self.encrypt(message).map(Cow::Owned)
+    }
+
+    /// Decrypt a ciphertext, borrowing it where the fence would leave it unchanged.
+    ///
+    fn decrypt_cow<'a>(&self, ciphertext: &'a str) -> Result<Cow<'a, str>, &'static str> {
+        if self.leaves_unchanged(ciphertext) {
+            return Ok(Cow::Borrowed(ciphertext));
+        }
+
+        self.decrypt(ciphertext).map(Cow::Owned)
+    }
 }
 
 impl Railfence {
+    /// Initialise a Railfence cipher given a specific key (number of rails).
+    ///
+    /// This can be called in a `const` context, where a key of 0 fails to compile.
+    ///
+    /// # Panics
+    /// * The `key` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Railfence};
+    ///
+    /// const FENCE: Railfence = Railfence::new(3);
+    /// assert_eq!("Src s!ue-ertmsaepseeg", FENCE.encrypt("Super-secret message!").unwrap());
+    /// ```
+    ///
+    pub const fn new(key: usize) -> Railfence {
+        if key == 0 {
+            panic!("The key is 0.");
+        }
+
+        Railfence { rails: key }
+    }
+
+    /// The number of rails of the fence.
+    ///
+    pub fn rails(&self) -> usize {
+        self.rails
+    }
+
+    /// Whether the fence leaves a `text` unchanged, as it does when each of its characters is on
+    /// a rail of its own (or there is only one rail).
+    ///
+    fn leaves_unchanged(&self, text: &str) -> bool {
+        self.rails == 1 || text.chars().count() <= self.rails
+    }
+
+    /// Draw a `message` as it is written along the zig-zag of the rails, with a `.` marking each
+    /// empty place on a rail.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Railfence};
+    ///
+    /// let r = Railfence::new(3);
+    /// assert_eq!(
+    ///     "H...o...o...!\n.e.l.,.W.r.d.\n..l... ...l..",
+    ///     r.render("Hello, World!")
+    /// );
+    /// ```
+    ///
+    pub fn render(&self, message: &str) -> String {
+        if self.rails == 1 {
+            return message.to_string();
+        }
+
+        let elements = grapheme::units(message);
+        let mut table = vec![vec!["."; elements.len()]; self.rails];
+        for (col, &element) in elements.iter().enumerate() {
+            table[Railfence::calc_current_rail(col, self.rails)][col] = element;
+        }
+
+        table
+            .iter()
+            .map(|rail| rail.concat())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
     /// For a given column and the total number of 'rails' (rows), determine the current rail
     /// that should be referenced.
     ///
-    fn calc_current_rail(col: usize, total_rails: usize) -> usize {
+    pub(crate) fn calc_current_rail(col: usize, total_rails: usize) -> usize {
         // In the Railfence cipher the letters are placed diagonally in a zigzag,
         // so, with a key of 4 say, the row numbers will go
         //      0, 1, 2, 3, 2, 1, 0, 1, 2, 3, 2, 1, 0, ...
@@ -187,6 +277,12 @@ impl Railfence {
 mod tests {
     use super::*;
 
+    #[test]
+    fn render_single_rail() {
+        assert_eq!("Hello", Railfence::new(1).render("Hello"));
+        assert_eq!("H.l.o\n.e.l.", Railfence::new(2).render("Hello"));
+    }
+
     #[test]
     fn encrypt_test() {
         let message = "attackatdawn";
@@ -255,4 +351,36 @@ mod tests {
         let message = "ÂƮƮäƈķ ɑƬ Ðawŋ ✓";
         assert_eq!("ÂƈƬwƮäķɑ aŋ✓Ʈ Ð ", r.encrypt(message).unwrap());
     }
+
+    #[test]
+    fn unicode_decrypt() {
+        let r = Railfence::new(3);
+        assert_eq!("ÂƮƮäƈķ ɑƬ Ðawŋ ✓", r.decrypt("ÂƈƬwƮäķɑ aŋ✓Ʈ Ð ").unwrap());
+    }
+
+    #[cfg(feature = "graphemes")]
+    #[test]
+    fn keeps_graphemes_whole() {
+        let r = Railfence::new(2);
+        let message = "Cafe\u{301} 🗡️";
+
+        assert_eq!("Cf ae\u{301}🗡️", r.encrypt(message).unwrap());
+        assert_eq!(message, r.decrypt(&r.encrypt(message).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn cow_borrows_unchanged() {
+        assert!(matches!(
+            Railfence::new(1).encrypt_cow("Attack"),
+            Ok(Cow::Borrowed(_))
+        ));
+        assert!(matches!(
+            Railfence::new(6).decrypt_cow("Attack"),
+            Ok(Cow::Borrowed(_))
+        ));
+        assert_eq!(
+            Railfence::new(3).encrypt("Attack at dawn").unwrap(),
+            Railfence::new(3).encrypt_cow("Attack at dawn").unwrap()
+        );
+    }
 }
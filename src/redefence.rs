@@ -0,0 +1,266 @@
+//! The Redefence cipher is a variant of the Railfence cipher. The message is written in a zigzag
+//! across the rails as before, but the rails are then read off in an order given by a numeric
+//! key, rather than from top to bottom.
+//!
+//! The zigzag may also begin part of the way through its cycle, given by an offset. For example,
+//! with three rails and an offset of 1, the first character of the message is written on the
+//! second rail, heading downwards.
+//!
+//! Like the Railfence cipher, this implementation transposes all input characters including
+//! whitespace and punctuation, and with the `graphemes` feature keeps grapheme clusters whole.
+//!
+use crate::common::cipher::Cipher;
+use crate::common::grapheme;
+use crate::railfence::Railfence;
+
+/// A Redefence cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Redefence {
+    order: Vec<usize>,
+    offset: usize,
+}
+
+/// The key of a Redefence cipher.
+///
+/// This struct is given to the `new()` method of `Redefence`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RedefenceKey {
+    /// The number of each rail (from top to bottom) in the order that it is read. It must
+    /// contain each of the numbers `1` to the number of rails exactly once.
+    pub order: Vec<usize>,
+    /// The position within the zigzag that the message begins at.
+    pub offset: usize,
+}
+
+impl Cipher for Redefence {
+    type Key = RedefenceKey;
+    type Algorithm = Redefence;
+
+    /// Initialise a Redefence cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `RedefenceKey`.
+    ///
+    /// # Panics
+    /// * The `order` is empty.
+    /// * The `order` is not a permutation of the numbers `1` to the number of rails.
+    /// * The `offset` is not less than the length of a cycle of the zigzag (`2 * rails - 2`).
+    ///
+    fn new(key: RedefenceKey) -> Redefence {
+        let RedefenceKey { order, offset } = key;
+        if order.is_empty() {
+            panic!("The rail order is empty.");
+        }
+        if (1..=order.len()).any(|n| !order.contains(&n)) {
+            panic!("The rail order must contain each rail number exactly once.");
+        }
+        if offset > 0 && offset >= 2 * order.len() - 2 {
+            panic!("The offset must be less than the length of the zigzag cycle.");
+        }
+
+        Redefence { order, offset }
+    }
+
+    /// Encrypt a message using a Redefence cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Redefence, RedefenceKey};
+    ///
+    /// let r = Redefence::new(RedefenceKey { order: vec![2, 1, 3], offset: 0 });
+    /// assert_eq!("taka anActwt d!", r.encrypt("Attack at dawn!").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        let elements = grapheme::units(message);
+        let rails = self.rails(elements.len());
+
+        Ok(self
+            .reading_order()
+            .iter()
+            .flat_map(|&rail| {
+                elements
+                    .iter()
+                    .zip(rails.iter())
+                    .filter(move |&(_, &r)| r == rail)
+                    .map(|(&e, _)| e)
+            })
+            .collect())
+    }
+
+    /// Decrypt a message using a Redefence cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Redefence, RedefenceKey};
+    ///
+    /// let r = Redefence::new(RedefenceKey { order: vec![2, 1, 3], offset: 0 });
+    /// assert_eq!("Attack at dawn!", r.decrypt("taka anActwt d!").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let elements = grapheme::units(ciphertext);
+        let rails = self.rails(elements.len());
+
+        // Write the ciphertext onto each rail in turn, at the positions the rail occupies
+        let mut message = vec![""; rails.len()];
+        let mut ct_elements = elements.into_iter();
+        for rail in self.reading_order() {
+            for (i, _) in rails.iter().enumerate().filter(|&(_, &r)| r == rail) {
+                message[i] = ct_elements.next().unwrap();
+            }
+        }
+
+        Ok(message.concat())
+    }
+}
+
+impl Redefence {
+    /// The rail that each of the `len` characters of the message is written on.
+    ///
+    fn rails(&self, len: usize) -> Vec<usize> {
+        if self.order.len() == 1 {
+            return vec![0; len];
+        }
+
+        (0..len)
+            .map(|col| Railfence::calc_current_rail(col + self.offset, self.order.len()))
+            .collect()
+    }
+
+    /// The rails (counting from 0) in the order that they are read.
+    ///
+    fn reading_order(&self) -> Vec<usize> {
+        (1..=self.order.len())
+            .map(|n| self.order.iter().position(|&o| o == n).unwrap())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_test() {
+        let r = Redefence::new(RedefenceKey {
+            order: vec![3, 1, 2],
+            offset: 0,
+        });
+        assert_eq!(
+            "ERDSOEEFEAOCAIVDENWECRLTE",
+            r.encrypt("WEAREDISCOVEREDFLEEATONCE").unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let r = Redefence::new(RedefenceKey {
+            order: vec![3, 1, 2],
+            offset: 0,
+        });
+        assert_eq!(
+            "WEAREDISCOVEREDFLEEATONCE",
+            r.decrypt("ERDSOEEFEAOCAIVDENWECRLTE").unwrap()
+        );
+    }
+
+    #[test]
+    fn encrypt_with_offset() {
+        let r = Redefence::new(RedefenceKey {
+            order: vec![2, 3, 1, 4],
+            offset: 2,
+        });
+        assert_eq!(
+            "WAICRDETEEVLNRDOEFEOCESEA",
+            r.encrypt("WEAREDISCOVEREDFLEEATONCE").unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_with_offset() {
+        let r = Redefence::new(RedefenceKey {
+            order: vec![2, 3, 1, 4],
+            offset: 2,
+        });
+        assert_eq!(
+            "WEAREDISCOVEREDFLEEATONCE",
+            r.decrypt("WAICRDETEEVLNRDOEFEOCESEA").unwrap()
+        );
+    }
+
+    #[test]
+    fn ordered_rails_match_railfence() {
+        let m = "attackatdawn";
+        assert_eq!(
+            Railfence::new(6).encrypt(m).unwrap(),
+            Redefence::new(RedefenceKey {
+                order: (1..=6).collect(),
+                offset: 0
+            })
+            .encrypt(m)
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn single_rail() {
+        let r = Redefence::new(RedefenceKey {
+            order: vec![1],
+            offset: 0,
+        });
+        assert_eq!("attackatdawn", r.encrypt("attackatdawn").unwrap());
+    }
+
+    #[test]
+    fn with_utf8() {
+        let r = Redefence::new(RedefenceKey {
+            order: vec![4, 2, 1, 3],
+            offset: 5,
+        });
+        let m = "Peace 🗡️ Freedom and Liberty!";
+
+        assert_eq!(m, r.decrypt(&r.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_order() {
+        Redefence::new(RedefenceKey {
+            order: Vec::new(),
+            offset: 0,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn repeated_rail() {
+        Redefence::new(RedefenceKey {
+            order: vec![1, 2, 2],
+            offset: 0,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn unknown_rail() {
+        Redefence::new(RedefenceKey {
+            order: vec![1, 2, 4],
+            offset: 0,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn large_offset() {
+        Redefence::new(RedefenceKey {
+            order: vec![1, 2, 3],
+            offset: 4,
+        });
+    }
+}
@@ -0,0 +1,1205 @@
+//! Looks up the ciphers of this crate by name, for programs that choose a cipher and its key from
+//! text (such as the arguments of a command line tool).
+//!
+//! Each cipher in the registry has a `Factory`, which parses a key written as a string and gives
+//! back the cipher as a `Box<dyn DynCipher>`. A key that could not be given to the cipher is
+//! returned as an `Err`, rather than causing a panic.
+//!
+//! Every cipher of this crate is in the registry. Where part of a key is too large to write on a
+//! single line (such as the table of the Great Cipher, or the stencil of RS44), the factory
+//! uses the default or example provided by the cipher's module; its `key_format()` says which.
+//!
+//! # Example
+//!
+//! ```
+//! use cipher_crypt::registry;
+//! use cipher_crypt::Cipher;
+//!
+//! let factory = registry::lookup("Playfair").unwrap();
+//! let c = factory.parse("playfairexample").unwrap();
+//! assert_eq!("BMODZBXDNABEKUDMUIXMKZZRYI", c.encrypt("Hidethegoldinthetreestump").unwrap());
+//!
+//! assert!(registry::lookup("enigma").is_none());
+//! assert!(registry::ciphers().any(|f| f.name() == "vigenere"));
+//! ```
+//!
+use crate::common::alphabet::{self, Alphabet};
+use crate::common::cipher::{Cipher, DynCipher};
+use crate::keyboard_shift::{Direction, Layout};
+use crate::porta_digraphic::Output;
+use crate::rs44::{EXAMPLE_STENCIL, ROW_LABELS};
+use crate::uesugi::Script;
+use crate::{
+    ADFGVXKey, Affine, AffineHill, Autokey, AveMaria, Baconian, Beaufort, Bifid, BifidKey, Caesar,
+    CaesarBox, CardanGrille, CardanGrilleKey, ColumnarTransposition, Dryad, DryadKey,
+    FractionatedMorse, GreatCipher, Gronsfeld, Hill, Homophonic, JeffersonWheel, KamaSutra,
+    KeyboardShift, KeyboardShiftKey, Keyword, KeywordCaesar, KeywordCaesarKey, Nomenclator,
+    NomenclatorKey, NullCipher, NullCipherKey, OneTimePad, Pigpen, Playfair, PlayfairKey, Polybius,
+    Porta, PortaDigraphic, PortaDigraphicKey, Quagmire, QuagmireKey, RS44Key, Railfence, Redefence,
+    RedefenceKey, Scytale, SimpleSubstitution, Syllabary, SyllabaryKey, TableauCipher, TableauKey,
+    TapCode, TapCodeKey, Trifid, TrifidKey, Uesugi, UesugiKey, VICKey, Vigenere, Wheatstone,
+    WheatstoneKey, Wolseley, ADFGVX, M209, RS44, VIC,
+};
+use std::any::TypeId;
+
+type Parse = fn(&str) -> Result<Box<dyn DynCipher>, &'static str>;
+
+/// Makes a cipher of the registry from a key written as a string.
+///
+/// This struct is returned by the `lookup()` and `ciphers()` functions. See their documentation
+/// for more.
+pub struct Factory {
+    name: &'static str,
+    cipher: fn() -> TypeId,
+    key_format: &'static str,
+    parse: Parse,
+}
+
+impl Factory {
+    /// The name of the cipher, as the name of its module (such as `"keyword_caesar"`).
+    ///
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// A description of the key that `parse()` takes.
+    ///
+    pub fn key_format(&self) -> &'static str {
+        self.key_format
+    }
+
+    /// The `TypeId` of the cipher.
+    ///
+    pub(crate) fn type_id(&self) -> TypeId {
+        (self.cipher)()
+    }
+
+    /// Initialise the cipher from a `key` written as described by `key_format()`.
+    ///
+    /// Returns `Err` if the key could not be parsed, or could not be given to the cipher.
+    ///
+    pub fn parse(&self, key: &str) -> Result<Box<dyn DynCipher>, &'static str> {
+        (self.parse)(key)
+    }
+}
+
+const FACTORIES: [Factory; 47] = [
+    Factory {
+        name: "adfgvx",
+        cipher: TypeId::of::<ADFGVX>,
+        key_format:
+            "A phrase of the symbols `a-z` and `0-9` that keys the polybius square, and the \
+            key of the columnar transposition as parsed by `columnar_transposition`, separated by \
+            a comma (such as `ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8,german`).",
+        parse: |key| {
+            let (phrase, columnar) = key
+                .split_once(',')
+                .ok_or("The key must be a phrase and a columnar key, separated by a comma.")?;
+            let (phrase, columnar) = (phrase.trim(), columnar.trim());
+            if !alphabet::ALPHANUMERIC.is_valid(phrase) {
+                return Err("The phrase must only contain the symbols `a-z` and `0-9`.");
+            }
+            columnar.parse::<ColumnarTransposition>()?;
+
+            let (columnar_key, null_char) = match columnar.split_once(',') {
+                Some((keystream, null_char)) => (keystream, null_char.chars().next()),
+                None => (columnar, None),
+            };
+            Ok(Box::new(ADFGVX::new(ADFGVXKey {
+                polybius_key: phrase.to_string(),
+                columnar_key: columnar_key.to_string(),
+                null_char,
+            })))
+        },
+    },
+    Factory {
+        name: "affine",
+        cipher: TypeId::of::<Affine>,
+        key_format: "The numbers `a` and `b`, separated by a comma (such as `3,7`).",
+        parse: |key| Ok(Box::new(key.parse::<Affine>()?)),
+    },
+    Factory {
+        name: "affine_hill",
+        cipher: TypeId::of::<AffineHill>,
+        key_format:
+            "A phrase of the letters `a-z` as parsed by `hill`, and a shift of the letters \
+            `a-z` whose length is the chunk size, separated by a comma (such as `CEFJCBDRH,BCD`).",
+        parse: |key| {
+            let (phrase, shift) = key
+                .split_once(',')
+                .ok_or("The key must be a phrase and a shift, separated by a comma.")?;
+            let (phrase, shift) = (phrase.trim(), letters(shift)?);
+            phrase.parse::<Hill>()?;
+            if shift.len() * shift.len() != phrase.len() {
+                return Err(
+                    "The square of the length of the shift must equal the length of the \
+                    phrase.",
+                );
+            }
+
+            Ok(Box::new(AffineHill::from_phrase(phrase, &shift)))
+        },
+    },
+    Factory {
+        name: "autokey",
+        cipher: TypeId::of::<Autokey>,
+        key_format: "A word of the letters `a-z`.",
+        parse: |key| {
+            if !Autokey::key_problems(key).is_empty() {
+                return Err("The key must be a word of the letters `a-z`.");
+            }
+            Ok(Box::new(Autokey::new(key.to_string())))
+        },
+    },
+    Factory {
+        name: "ave_maria",
+        cipher: TypeId::of::<AveMaria>,
+        key_format: "No key, as the default tables are used.",
+        parse: |key| {
+            no_key(key)?;
+            Ok(Box::new(AveMaria::new(None)))
+        },
+    },
+    Factory {
+        name: "baconian",
+        cipher: TypeId::of::<Baconian>,
+        key_format: "No key for the classical alphabet, in which `i` and `j`, and `u` and `v`, \
+            share a code, or `distinct` for an alphabet in which every letter has its own code.",
+        parse: |key| {
+            let distinct = match key.trim().to_lowercase().as_str() {
+                "" => false,
+                "distinct" => true,
+                _ => return Err("The key must be empty or `distinct`."),
+            };
+            Ok(Box::new(
+                Baconian::builder().distinct_alphabet(distinct).build(),
+            ))
+        },
+    },
+    Factory {
+        name: "beaufort",
+        cipher: TypeId::of::<Beaufort>,
+        key_format: "A word of the letters `a-z`.",
+        parse: |key| Ok(Box::new(Beaufort::new(letters(key)?))),
+    },
+    Factory {
+        name: "bifid",
+        cipher: TypeId::of::<Bifid>,
+        key_format: "A phrase of the letters `a-z` and the period, which is at least 1, separated \
+            by a comma (such as `playfairexample,5`).",
+        parse: |key| {
+            let parts = parts(key, 2)?;
+            Ok(Box::new(Bifid::new(BifidKey {
+                phrase: letters(parts[0])?,
+                period: positive(parts[1])?,
+            })))
+        },
+    },
+    Factory {
+        name: "caesar",
+        cipher: TypeId::of::<Caesar>,
+        key_format: "The shift, from 1 to 26.",
+        parse: |key| Ok(Box::new(key.parse::<Caesar>()?)),
+    },
+    Factory {
+        name: "caesar_box",
+        cipher: TypeId::of::<CaesarBox>,
+        key_format: "The size of the square, which is at least 1, or no key for the smallest \
+            square that fits the message.",
+        parse: |key| {
+            let size = match key.trim() {
+                "" => None,
+                size => Some(positive(size)?),
+            };
+            Ok(Box::new(CaesarBox::new(size)))
+        },
+    },
+    Factory {
+        name: "cardan_grille",
+        cipher: TypeId::of::<CardanGrille>,
+        key_format:
+            "A grille of holes `X` and solid cells `.` or `-`, with at least one hole (such \
+            as `X..X.X`).",
+        parse: |key| {
+            let cells: Vec<char> = key.chars().filter(|c| !c.is_whitespace()).collect();
+            if !cells.iter().all(|c| matches!(c, 'X' | 'x' | '.' | '-')) {
+                return Err("The grille may only contain holes `X` or solid cells `.` and `-`.");
+            }
+            if !cells.iter().any(|c| matches!(c, 'X' | 'x')) {
+                return Err("The grille must contain at least one hole.");
+            }
+
+            Ok(Box::new(CardanGrille::new(CardanGrilleKey {
+                grille: key.to_string(),
+                decoy_text: None,
+            })))
+        },
+    },
+    Factory {
+        name: "columnar_transposition",
+        cipher: TypeId::of::<ColumnarTransposition>,
+        key_format: "A keystream, optionally followed by a comma and the null character (such as \
+            `zebras,x`).",
+        parse: |key| Ok(Box::new(key.parse::<ColumnarTransposition>()?)),
+    },
+    Factory {
+        name: "dryad",
+        cipher: TypeId::of::<Dryad>,
+        key_format: "The seed of a pad from `Dryad::generate_pad()` and the row letter `A-Y`, \
+            separated by a comma (such as `1944,c`).",
+        parse: |key| {
+            let parts = parts(key, 2)?;
+            let seed = parts[0].parse().map_err(|_| "The seed must be a number.")?;
+            let row = single(parts[1])?;
+            if !('a'..='y').contains(&row.to_ascii_lowercase()) {
+                return Err("The row must be one of the letters A-Y.");
+            }
+
+            Ok(Box::new(Dryad::new(DryadKey {
+                pad: Dryad::generate_pad(seed),
+                row,
+            })))
+        },
+    },
+    Factory {
+        name: "fractionated_morse",
+        cipher: TypeId::of::<FractionatedMorse>,
+        key_format: "A word of the letters `a-z`.",
+        parse: |key| Ok(Box::new(FractionatedMorse::new(letters(key)?))),
+    },
+    Factory {
+        name: "great_cipher",
+        cipher: TypeId::of::<GreatCipher>,
+        key_format: "No key, as the default table is used.",
+        parse: |key| {
+            no_key(key)?;
+            Ok(Box::new(GreatCipher::new(None)))
+        },
+    },
+    Factory {
+        name: "gronsfeld",
+        cipher: TypeId::of::<Gronsfeld>,
+        key_format: "A number, whose digits are the shifts of the key.",
+        parse: |key| {
+            let key = key.trim();
+            if key.is_empty() || !key.chars().all(|c| c.is_ascii_digit()) {
+                return Err("The key must be a number.");
+            }
+            Ok(Box::new(Gronsfeld::new(key.to_string())))
+        },
+    },
+    Factory {
+        name: "hill",
+        cipher: TypeId::of::<Hill>,
+        key_format: "A phrase of the letters `a-z`, whose length is a square (such as \
+            `CEFJCBDRH`).",
+        parse: |key| Ok(Box::new(key.parse::<Hill>()?)),
+    },
+    Factory {
+        name: "homophonic",
+        cipher: TypeId::of::<Homophonic>,
+        key_format: "A word of the letters `a-z`, as given to `Homophonic::from_keyword()`.",
+        parse: |key| Ok(Box::new(Homophonic::from_keyword(&letters(key)?))),
+    },
+    Factory {
+        name: "jefferson_wheel",
+        cipher: TypeId::of::<JeffersonWheel>,
+        key_format: "The order of the M-94 wheels, as distinct numbers from 1 to 25 separated by \
+            spaces, and the offset from 1 to 25, separated by a comma (such as `7 3 12 1,5`).",
+        parse: |key| {
+            let parts = parts(key, 2)?;
+            let order = numbers(parts[0])?;
+            if order.is_empty() || !order.iter().all(|&n| (1..=25).contains(&n)) {
+                return Err("The wheel order must contain numbers from 1 to 25.");
+            }
+            if (1..order.len()).any(|i| order[..i].contains(&order[i])) {
+                return Err("The wheel order must contain distinct wheel numbers.");
+            }
+            let offset = positive(parts[1])?;
+            if offset > 25 {
+                return Err("The offset must be between 1 and 25.");
+            }
+
+            Ok(Box::new(JeffersonWheel::m94(order, offset)))
+        },
+    },
+    Factory {
+        name: "kama_sutra",
+        cipher: TypeId::of::<KamaSutra>,
+        key_format: "Each of the letters `a-z` once, where the first letter is paired with the \
+            second, the third with the fourth, and so on.",
+        parse: |key| {
+            let key: Vec<char> = letters(key)?.to_lowercase().chars().collect();
+            if key.len() != 26 || ('a'..='z').any(|c| !key.contains(&c)) {
+                return Err("The key must contain each letter of the alphabet exactly once.");
+            }
+            Ok(Box::new(KamaSutra::new(
+                key.chunks(2).map(|p| (p[0], p[1])).collect(),
+            )))
+        },
+    },
+    Factory {
+        name: "keyboard_shift",
+        cipher: TypeId::of::<KeyboardShift>,
+        key_format: "The layout (`qwerty`, `azerty` or `dvorak`), the direction (`left`, `right`, \
+            `up` or `down`) and the shift, which is at least 1, separated by commas (such as \
+            `qwerty,right,1`).",
+        parse: |key| {
+            let parts = parts(key, 3)?;
+            let layout = match parts[0].to_lowercase().as_str() {
+                "qwerty" => Layout::Qwerty,
+                "azerty" => Layout::Azerty,
+                "dvorak" => Layout::Dvorak,
+                _ => return Err("The layout must be `qwerty`, `azerty` or `dvorak`."),
+            };
+            let direction = match parts[1].to_lowercase().as_str() {
+                "left" => Direction::Left,
+                "right" => Direction::Right,
+                "up" => Direction::Up,
+                "down" => Direction::Down,
+                _ => return Err("The direction must be `left`, `right`, `up` or `down`."),
+            };
+
+            Ok(Box::new(KeyboardShift::new(KeyboardShiftKey {
+                layout,
+                direction,
+                shift: positive(parts[2])?,
+            })))
+        },
+    },
+    Factory {
+        name: "keyword",
+        cipher: TypeId::of::<Keyword>,
+        key_format: "A word of the letters `a-z`.",
+        parse: |key| Ok(Box::new(Keyword::new(letters(key)?))),
+    },
+    Factory {
+        name: "keyword_caesar",
+        cipher: TypeId::of::<KeywordCaesar>,
+        key_format: "A word of the letters `a-z` and a shift from 0 to 25, separated by a comma \
+            (such as `kryptos,3`).",
+        parse: |key| {
+            let (keyword, shift) = key
+                .split_once(',')
+                .ok_or("The key must be a word and a shift, separated by a comma.")?;
+            let shift: usize = shift
+                .trim()
+                .parse()
+                .map_err(|_| "The shift must be a number.")?;
+            if shift > 25 {
+                return Err("The shift factor must be within the range 0 <= n <= 25.");
+            }
+
+            Ok(Box::new(KeywordCaesar::new(KeywordCaesarKey {
+                keyword: letters(keyword)?,
+                shift,
+            })))
+        },
+    },
+    Factory {
+        name: "m209",
+        cipher: TypeId::of::<M209>,
+        key_format: "The six letters that the wheels are set to, followed by the lines of a key \
+            list as read by `M209::from_key_list()`, all separated by semicolons (such as \
+            `AAAAAA;lugs = 1-0 2-0*4 0-3*3 0-4*2 0-5*8 0-6*9;wheel1 = ABDHIKMNSTVW;...`).",
+        parse: |key| {
+            let (positions, list) = key
+                .split_once(';')
+                .ok_or("The key must be the positions and a key list, separated by a semicolon.")?;
+            Ok(Box::new(M209::from_key_list(
+                &list.replace(';', "\n"),
+                positions.trim(),
+            )?))
+        },
+    },
+    Factory {
+        name: "nomenclator",
+        cipher: TypeId::of::<Nomenclator>,
+        key_format: "A word of the letters `a-z`, which keys the homophones of the letters as \
+            given to `Homophonic::from_keyword()`. The code book is left empty.",
+        parse: |key| {
+            Ok(Box::new(Nomenclator::new(NomenclatorKey {
+                code_book: Vec::new(),
+                letters: Homophonic::from_keyword(&letters(key)?),
+            })))
+        },
+    },
+    Factory {
+        name: "null_cipher",
+        cipher: TypeId::of::<NullCipher>,
+        key_format: "The positions of the letters that carry the message in successive words, \
+            counting from 1 and separated by spaces (such as `1 2`).",
+        parse: |key| {
+            let pattern = numbers(key)?;
+            if pattern.is_empty() || pattern.contains(&0) {
+                return Err("The pattern must contain positions of at least 1.");
+            }
+            Ok(Box::new(NullCipher::new(NullCipherKey {
+                pattern,
+                decoy_text: None,
+            })))
+        },
+    },
+    Factory {
+        name: "one_time_pad",
+        cipher: TypeId::of::<OneTimePad>,
+        key_format: "A pad of the letters `a-z`.",
+        parse: |key| Ok(Box::new(OneTimePad::new(letters(key)?))),
+    },
+    Factory {
+        name: "pigpen",
+        cipher: TypeId::of::<Pigpen>,
+        key_format: "The symbol table, which is `standard` or `rosicrucian`.",
+        parse: |key| match key.trim().to_lowercase().as_str() {
+            "standard" => Ok(Box::new(Pigpen::standard())),
+            "rosicrucian" => Ok(Box::new(Pigpen::rosicrucian())),
+            _ => Err("The symbol table must be `standard` or `rosicrucian`."),
+        },
+    },
+    Factory {
+        name: "playfair",
+        cipher: TypeId::of::<Playfair>,
+        key_format: "A keystream of up to 25 of the letters `a-z`, without `j`.",
+        parse: |key| {
+            let keystream = letters(key)?;
+            if keystream.len() > 25 || !alphabet::PLAYFAIR.is_valid(&keystream) {
+                return Err("The keystream must have up to 25 of the letters `a-z`, without `j`.");
+            }
+
+            Ok(Box::new(Playfair::new(PlayfairKey {
+                keystream,
+                null_char: None,
+            })))
+        },
+    },
+    Factory {
+        name: "polybius",
+        cipher: TypeId::of::<Polybius>,
+        key_format: "A phrase of the symbols `a-z` and `0-9`, which keys a square whose rows and \
+            columns are labelled `A-F`.",
+        parse: |key| {
+            let phrase = key.trim();
+            if !alphabet::ALPHANUMERIC.is_valid(phrase) {
+                return Err("The phrase must only contain the symbols `a-z` and `0-9`.");
+            }
+            Ok(Box::new(Polybius::builder().phrase(phrase).build()))
+        },
+    },
+    Factory {
+        name: "porta",
+        cipher: TypeId::of::<Porta>,
+        key_format: "A word of the letters `a-z`.",
+        parse: |key| Ok(Box::new(Porta::new(letters(key)?))),
+    },
+    Factory {
+        name: "porta_digraphic",
+        cipher: TypeId::of::<PortaDigraphic>,
+        key_format:
+            "The words of the letters `a-z` that key the rows and the columns, separated by \
+            a comma (such as `fortune,silent`). The output is numeric.",
+        parse: |key| {
+            let parts = parts(key, 2)?;
+            Ok(Box::new(PortaDigraphic::new(PortaDigraphicKey {
+                row_keyword: letters(parts[0])?,
+                column_keyword: letters(parts[1])?,
+                output: Output::Numeric,
+            })))
+        },
+    },
+    Factory {
+        name: "quagmire",
+        cipher: TypeId::of::<Quagmire>,
+        key_format: "The plaintext keyword, the ciphertext keyword and the indicator, each of the \
+            letters `a-z` and separated by commas, where either keyword may be left out for the \
+            plain alphabet (such as `,kryptos,police`).",
+        parse: |key| {
+            let parts = parts(key, 3)?;
+            let keyword = |k: &str| match k {
+                "" => Ok(None),
+                k => letters(k).map(Some),
+            };
+
+            Ok(Box::new(Quagmire::new(QuagmireKey {
+                plain_keyword: keyword(parts[0])?,
+                cipher_keyword: keyword(parts[1])?,
+                indicator: letters(parts[2])?,
+            })))
+        },
+    },
+    Factory {
+        name: "railfence",
+        cipher: TypeId::of::<Railfence>,
+        key_format: "The number of rails, which is at least 1.",
+        parse: |key| Ok(Box::new(Railfence::new(positive(key)?))),
+    },
+    Factory {
+        name: "redefence",
+        cipher: TypeId::of::<Redefence>,
+        key_format: "The order of the rails, as each of the numbers from 1 to the number of rails \
+            separated by spaces, and the offset, separated by a comma (such as `3 1 2,0`).",
+        parse: |key| {
+            let parts = parts(key, 2)?;
+            let order = numbers(parts[0])?;
+            if order.is_empty() || !is_permutation(&order, order.len()) {
+                return Err("The rail order must contain each rail number exactly once.");
+            }
+            let offset: usize = parts[1]
+                .parse()
+                .map_err(|_| "The offset must be a number.")?;
+            if offset > 0 && offset >= 2 * order.len() - 2 {
+                return Err("The offset must be less than the length of the zigzag cycle.");
+            }
+
+            Ok(Box::new(Redefence::new(RedefenceKey { order, offset })))
+        },
+    },
+    Factory {
+        name: "rs44",
+        cipher: TypeId::of::<RS44>,
+        key_format: "The 24 column labels of the `EXAMPLE_STENCIL`, as each of the numbers from 1 \
+            to 24 separated by spaces, the starting cell, as a row letter and a column label, and \
+            the minute, separated by commas (such as `20 23 19 ... 8 12,A20,0`).",
+        parse: |key| {
+            let parts = parts(key, 3)?;
+            let column_labels = numbers(parts[0])?;
+            if !is_permutation(&column_labels, 24) {
+                return Err(
+                    "The column labels must contain each of the numbers 1 to 24 exactly \
+                    once.",
+                );
+            }
+            let mut cell = parts[1].chars();
+            let row_label = cell.next().ok_or("The starting cell is empty.")?;
+            let column_label: usize = cell
+                .as_str()
+                .parse()
+                .map_err(|_| "The starting cell must be a row letter and a column label.")?;
+            let open = ROW_LABELS
+                .find(row_label.to_ascii_uppercase())
+                .zip(column_labels.iter().position(|&c| c == column_label))
+                .is_some_and(|(r, c)| EXAMPLE_STENCIL[r].as_bytes()[c] == b'.');
+            if !open {
+                return Err("The starting cell does not exist, or is blacked out.");
+            }
+
+            Ok(Box::new(RS44::new(RS44Key {
+                stencil: EXAMPLE_STENCIL.iter().map(|r| r.to_string()).collect(),
+                column_labels,
+                start: (row_label, column_label),
+                minute: parts[2]
+                    .parse()
+                    .map_err(|_| "The minute must be a number.")?,
+            })))
+        },
+    },
+    Factory {
+        name: "scytale",
+        cipher: TypeId::of::<Scytale>,
+        key_format: "The number of rows around the rod, which is at least 1.",
+        parse: |key| Ok(Box::new(Scytale::new(positive(key)?))),
+    },
+    Factory {
+        name: "simple_substitution",
+        cipher: TypeId::of::<SimpleSubstitution>,
+        key_format: "Each of the letters `a-z` once, in the order that they replace `a-z`.",
+        parse: |key| {
+            let key = letters(key)?.to_lowercase();
+            if key.len() != 26 || ('a'..='z').any(|c| !key.contains(c)) {
+                return Err("The key must contain each letter of the alphabet exactly once.");
+            }
+            Ok(Box::new(SimpleSubstitution::new(key)))
+        },
+    },
+    Factory {
+        name: "syllabary",
+        cipher: TypeId::of::<Syllabary>,
+        key_format:
+            "The row and column keys, each of 10 of the symbols `a-z` and `0-9`, separated \
+            by a comma (such as `cryptology,dictionary`). The default table is used.",
+        parse: |key| {
+            let parts = parts(key, 2)?;
+            if parts
+                .iter()
+                .any(|k| k.chars().count() != 10 || !alphabet::ALPHANUMERIC.is_valid(k))
+            {
+                return Err("The row and column keys must be 10 of the symbols `a-z` and `0-9`.");
+            }
+
+            Ok(Box::new(Syllabary::new(SyllabaryKey {
+                row_key: parts[0].to_string(),
+                column_key: parts[1].to_string(),
+                table: None,
+            })))
+        },
+    },
+    Factory {
+        name: "tableau",
+        cipher: TypeId::of::<TableauCipher>,
+        key_format: "The rows of the table separated by spaces, each a label and its alphabet of \
+            the letters `a-z` joined by `=`, followed by a comma and a keyword of the labels (such \
+            as `a=zyxwvutsrqponmlkjihgfedcba b=...,ab`).",
+        parse: |key| {
+            let parts = parts(key, 2)?;
+            let mut table: Vec<(char, String)> = Vec::new();
+            for row in parts[0].split_whitespace() {
+                let (label, alphabet) = row
+                    .split_once('=')
+                    .ok_or("Each row must be a label and an alphabet, joined by `=`.")?;
+                let label = single(label)?.to_ascii_lowercase();
+                let alphabet = letters(alphabet)?.to_lowercase();
+                if !label.is_ascii_alphabetic() || table.iter().any(|(l, _)| *l == label) {
+                    return Err("Each row must be labelled with a distinct letter.");
+                }
+                if alphabet.len() != 26 || ('a'..='z').any(|c| !alphabet.contains(c)) {
+                    return Err("Each row must contain each of the letters a-z exactly once.");
+                }
+                table.push((label, alphabet));
+            }
+            let keyword = letters(parts[1])?.to_lowercase();
+            if table.is_empty() || !keyword.chars().all(|c| table.iter().any(|(l, _)| *l == c)) {
+                return Err("Each letter of the keyword must label a row of the table.");
+            }
+
+            Ok(Box::new(TableauCipher::new(TableauKey { table, keyword })))
+        },
+    },
+    Factory {
+        name: "tap_code",
+        cipher: TypeId::of::<TapCode>,
+        key_format: "The tap and the separator, as two different characters written one after the \
+            other, where the tap is not whitespace (such as `.|`).",
+        parse: |key| {
+            let mut chars = key.chars();
+            match (chars.next(), chars.next(), chars.next()) {
+                (Some(tap), Some(separator), None) if !tap.is_whitespace() && tap != separator => {
+                    Ok(Box::new(TapCode::new(TapCodeKey { tap, separator })))
+                }
+                _ => Err("The key must be a tap and a different separator character."),
+            }
+        },
+    },
+    Factory {
+        name: "trifid",
+        cipher: TypeId::of::<Trifid>,
+        key_format: "A phrase of the letters `a-z` and `+`, and the period, which is at least 1, \
+            separated by a comma (such as `felixmariedelastelle,5`).",
+        parse: |key| {
+            let parts = parts(key, 2)?;
+            if !parts[0]
+                .chars()
+                .all(|c| c.is_ascii_alphabetic() || c == '+')
+            {
+                return Err("The phrase must only contain the letters `a-z` and `+`.");
+            }
+
+            Ok(Box::new(Trifid::new(TrifidKey {
+                phrase: parts[0].to_string(),
+                period: positive(parts[1])?,
+            })))
+        },
+    },
+    Factory {
+        name: "uesugi",
+        cipher: TypeId::of::<Uesugi>,
+        key_format:
+            "The row and column labels, each the digits `1-7` in any order, separated by a \
+            comma (such as `7123456,3456712`). Messages are written in Hepburn romanization.",
+        parse: |key| {
+            let parts = parts(key, 2)?;
+            let labels = |l: &str| -> Result<Vec<usize>, &'static str> {
+                let labels: Vec<usize> = l
+                    .chars()
+                    .map(|c| c.to_digit(10).map_or(0, |d| d as usize))
+                    .collect();
+                if !is_permutation(&labels, 7) {
+                    return Err("The labels must contain each of the numbers 1 to 7 exactly once.");
+                }
+                Ok(labels)
+            };
+
+            Ok(Box::new(Uesugi::new(UesugiKey {
+                row_labels: labels(parts[0])?,
+                column_labels: labels(parts[1])?,
+                script: Script::Romanized,
+            })))
+        },
+    },
+    Factory {
+        name: "vic",
+        cipher: TypeId::of::<VIC>,
+        key_format: "A phrase of at least 20 letters, the date as six digits, the personal number \
+            from 1 to 16 and the keygroup of five digits, separated by commas (such as `I dream \
+            of Jeannie with the light brown hair,030945,6,77651`).",
+        parse: |key| {
+            let parts = parts(key, 4)?;
+            let digits =
+                |d: &str, count: usize| d.len() == count && d.chars().all(|c| c.is_ascii_digit());
+            if parts[0].chars().filter(char::is_ascii_alphabetic).count() < 20 {
+                return Err("The phrase must contain at least 20 letters.");
+            }
+            if !digits(parts[1], 6) {
+                return Err("The date must be six digits.");
+            }
+            let personal_number = positive(parts[2])?;
+            if personal_number > 16 {
+                return Err("The personal number must be between 1 and 16.");
+            }
+            if !digits(parts[3], 5) {
+                return Err("The keygroup must be five digits.");
+            }
+
+            Ok(Box::new(VIC::new(VICKey {
+                phrase: parts[0].to_string(),
+                date: parts[1].to_string(),
+                personal_number,
+                keygroup: parts[3].to_string(),
+            })))
+        },
+    },
+    Factory {
+        name: "vigenere",
+        cipher: TypeId::of::<Vigenere>,
+        key_format: "A word of the letters `a-z`.",
+        parse: |key| {
+            if !Vigenere::key_problems(key).is_empty() {
+                return Err("The key must be a word of the letters `a-z`.");
+            }
+            Ok(Box::new(Vigenere::new(key.to_string())))
+        },
+    },
+    Factory {
+        name: "wheatstone",
+        cipher: TypeId::of::<Wheatstone>,
+        key_format:
+            "The keywords of the letters `a-z` that key the outer and inner alphabets, and \
+            the starting letter, separated by commas, where the outer keyword may be left out for \
+            the plain alphabet (such as `,cipher,a`).",
+        parse: |key| {
+            let parts = parts(key, 3)?;
+            if !alphabet::STANDARD.is_valid(parts[0]) {
+                return Err("The outer keyword must be a word of the letters `a-z`.");
+            }
+            let start = single(parts[2])?;
+            if !start.is_ascii_alphabetic() {
+                return Err("The starting letter must be alphabetic.");
+            }
+
+            Ok(Box::new(Wheatstone::new(WheatstoneKey {
+                outer_keyword: parts[0].to_string(),
+                inner_keyword: letters(parts[1])?,
+                start,
+            })))
+        },
+    },
+    Factory {
+        name: "wolseley",
+        cipher: TypeId::of::<Wolseley>,
+        key_format: "A word of the letters `a-z`.",
+        parse: |key| Ok(Box::new(Wolseley::new(letters(key)?))),
+    },
+];
+
+/// Look up a cipher by its `name`.
+///
+/// The case of the name is ignored, as are the underscores, hyphens and spaces between its words,
+/// so `"keyword_caesar"`, `"Keyword Caesar"` and `"KeywordCaesar"` all name the same cipher.
+/// Returns `None` if the registry has no cipher of that name.
+///
+pub fn lookup(name: &str) -> Option<&'static Factory> {
+    let normalise = |n: &str| -> String {
+        n.chars()
+            .filter(|c| !matches!(c, '_' | '-' | ' '))
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+
+    let name = normalise(name);
+    FACTORIES.iter().find(|f| normalise(f.name) == name)
+}
+
+/// Look up the cipher of a type `C`.
+///
+/// Returns `None` if the registry has no cipher of that type.
+///
+/// # Example
+///
+/// ```
+/// use cipher_crypt::{registry, CipherKind, Vigenere};
+///
+/// assert_eq!("vigenere", registry::lookup_cipher::<Vigenere>().unwrap().name());
+/// assert!(registry::lookup_cipher::<CipherKind>().is_none());
+/// ```
+///
+pub fn lookup_cipher<C: 'static>() -> Option<&'static Factory> {
+    FACTORIES.iter().find(|f| f.type_id() == TypeId::of::<C>())
+}
+
+/// The ciphers of the registry, in alphabetical order of their names.
+///
+pub fn ciphers() -> impl Iterator<Item = &'static Factory> {
+    FACTORIES.iter()
+}
+
+/// Check that a `key` is a word of the letters `a-z`, ignoring the whitespace around it.
+///
+fn letters(key: &str) -> Result<String, &'static str> {
+    let key = key.trim();
+    if key.is_empty() || !alphabet::STANDARD.is_valid(key) {
+        return Err("The key must be a word of the letters `a-z`.");
+    }
+
+    Ok(key.to_string())
+}
+
+/// Check that a `key` is a number of at least 1.
+///
+fn positive(key: &str) -> Result<usize, &'static str> {
+    match key.trim().parse() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err("The key must be a number of at least 1."),
+    }
+}
+
+/// Check that no key is given to a cipher that does not take one, ignoring whitespace.
+///
+fn no_key(key: &str) -> Result<(), &'static str> {
+    if !key.trim().is_empty() {
+        return Err("The cipher does not take a key.");
+    }
+
+    Ok(())
+}
+
+/// Check that a `key` is a single character, ignoring the whitespace around it.
+///
+fn single(key: &str) -> Result<char, &'static str> {
+    let mut chars = key.trim().chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err("The key must be a single character."),
+    }
+}
+
+/// Split a `key` into a `count` of parts separated by commas, trimming the whitespace around
+/// each part.
+///
+fn parts(key: &str, count: usize) -> Result<Vec<&str>, &'static str> {
+    let parts: Vec<&str> = key.split(',').map(str::trim).collect();
+    if parts.len() != count {
+        return Err("The key does not have the right number of parts, separated by commas.");
+    }
+
+    Ok(parts)
+}
+
+/// Parse a `list` of numbers separated by whitespace.
+///
+fn numbers(list: &str) -> Result<Vec<usize>, &'static str> {
+    list.split_whitespace()
+        .map(|n| {
+            n.parse()
+                .map_err(|_| "The key must list numbers separated by spaces.")
+        })
+        .collect()
+}
+
+/// Whether `numbers` contains each of the numbers from 1 to `count` exactly once.
+///
+fn is_permutation(numbers: &[usize], count: usize) -> bool {
+    numbers.len() == count && (1..=count).all(|n| numbers.contains(&n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CipherKind;
+
+    const M209: &str = "AAAAAA;lugs = 1-0 2-0*4 0-3*3 0-4*2 0-5*8 0-6*9;wheel1 = ABDHIKMNSTVW;\
+        wheel2 = ADEGJKLORSUX;wheel3 = ABGHJLMNRSTUX;wheel4 = CEFHIMNPSTU;wheel5 = BDEFHIMNPS;\
+        wheel6 = ABDHKNOQ";
+    const RS44: &str = "20 23 19 11 24 7 6 17 3 15 5 18 13 16 1 4 21 9 2 10 22 14 8 12,A20,0";
+    const TABLEAU: &str = "a=zyxwvutsrqponmlkjihgfedcba b=phqgiumeaylnofdxjkrcvstzwb,abba";
+
+    #[test]
+    fn every_cipher_parses() {
+        let keys = [
+            "ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8,german",
+            "3,7",
+            "CEFJCBDRH,BCD",
+            "fort",
+            "",
+            "distinct",
+            "fort",
+            "playfairexample,5",
+            "3",
+            "",
+            "X..X.X",
+            "zebras,x",
+            "1944,c",
+            "roundtable",
+            "",
+            "31415",
+            "CEFJCBDRH",
+            "cipher",
+            "7 3 12 1,5",
+            "qwertyuiopasdfghjklzxcvbnm",
+            "qwerty,right,1",
+            "kryptos",
+            "kryptos,3",
+            M209,
+            "cipher",
+            "1 2",
+            "xmckljweqzxmckljweqz",
+            "rosicrucian",
+            "playfairexample",
+            "or0an3ge",
+            "fort",
+            "fortune,silent",
+            ",kryptos,police",
+            "3",
+            "3 1 2,1",
+            RS44,
+            "4",
+            "phqgiumeaylnofdxjkrcvstzwb",
+            "cryptology,dictionary",
+            TABLEAU,
+            ".|",
+            "felixmariedelastelle,5",
+            "7123456,3456712",
+            "I dream of Jeannie with the light brown hair,030945,6,77651",
+            "lemon",
+            ",cipher,a",
+            "kryptos",
+        ];
+
+        assert_eq!(keys.len(), ciphers().count());
+        for (factory, key) in ciphers().zip(keys.iter()) {
+            let c = factory.parse(key).unwrap();
+            let m = match factory.name() {
+                "dryad" => "1944",
+                "uesugi" => "iroha",
+                _ => "Retreatnorth",
+            };
+            assert_eq!(
+                m.to_uppercase(),
+                c.decrypt(&c.encrypt(m).unwrap()).unwrap().to_uppercase()
+            );
+        }
+    }
+
+    #[test]
+    fn matches_cipher_kind() {
+        let kinds = [
+            (
+                CipherKind::ADFGVX {
+                    polybius_key: String::from("ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8"),
+                    columnar_key: String::from("german"),
+                    null_char: None,
+                },
+                "ph0qg64mea1yl2nofdxkr3cvs5zw7bj9uti8,german",
+            ),
+            (CipherKind::Affine { a: 3, b: 7 }, "3,7"),
+            (
+                CipherKind::Autokey {
+                    key: String::from("fort"),
+                },
+                "fort",
+            ),
+            (
+                CipherKind::Baconian {
+                    distinct: true,
+                    decoy_text: None,
+                },
+                "distinct",
+            ),
+            (
+                CipherKind::Beaufort {
+                    key: String::from("fort"),
+                },
+                "fort",
+            ),
+            (
+                CipherKind::Bifid {
+                    phrase: String::from("playfairexample"),
+                    period: 5,
+                },
+                "playfairexample,5",
+            ),
+            (CipherKind::Caesar { shift: 3 }, "3"),
+            (
+                CipherKind::ColumnarTransposition {
+                    keystream: String::from("zebras"),
+                    null_char: Some('x'),
+                },
+                "zebras,x",
+            ),
+            (
+                CipherKind::FractionatedMorse {
+                    key: String::from("roundtable"),
+                },
+                "roundtable",
+            ),
+            (
+                CipherKind::Gronsfeld {
+                    key: String::from("31415"),
+                },
+                "31415",
+            ),
+            (
+                CipherKind::Hill {
+                    phrase: String::from("CEFJCBDRH"),
+                    chunk_size: 3,
+                },
+                "CEFJCBDRH",
+            ),
+            (
+                CipherKind::Keyword {
+                    keyword: String::from("kryptos"),
+                },
+                "kryptos",
+            ),
+            (
+                CipherKind::KeywordCaesar {
+                    keyword: String::from("kryptos"),
+                    shift: 3,
+                },
+                "kryptos,3",
+            ),
+            (
+                CipherKind::OneTimePad {
+                    pad: String::from("xmckljweqzxmckljweqz"),
+                },
+                "xmckljweqzxmckljweqz",
+            ),
+            (
+                CipherKind::Playfair {
+                    keystream: String::from("playfairexample"),
+                    null_char: None,
+                },
+                "playfairexample",
+            ),
+            (
+                CipherKind::Polybius {
+                    phrase: String::from("or0an3ge"),
+                    column_ids: ['A', 'B', 'C', 'D', 'E', 'F'],
+                    row_ids: ['A', 'B', 'C', 'D', 'E', 'F'],
+                },
+                "or0an3ge",
+            ),
+            (
+                CipherKind::Porta {
+                    key: String::from("fort"),
+                },
+                "fort",
+            ),
+            (CipherKind::Railfence { rails: 3 }, "3"),
+            (CipherKind::Scytale { height: 4 }, "4"),
+            (
+                CipherKind::SimpleSubstitution {
+                    key: String::from("phqgiumeaylnofdxjkrcvstzwb"),
+                },
+                "phqgiumeaylnofdxjkrcvstzwb",
+            ),
+            (
+                CipherKind::Trifid {
+                    phrase: String::from("felixmariedelastelle"),
+                    period: 5,
+                },
+                "felixmariedelastelle,5",
+            ),
+            (
+                CipherKind::Vigenere {
+                    key: String::from("lemon"),
+                },
+                "lemon",
+            ),
+            (
+                CipherKind::Wolseley {
+                    keyword: String::from("kryptos"),
+                },
+                "kryptos",
+            ),
+        ];
+
+        for (kind, key) in kinds {
+            let factory = lookup(kind.name()).unwrap();
+            assert_eq!(
+                kind.encrypt("Retreat north"),
+                factory.parse(key).unwrap().encrypt("Retreat north")
+            );
+        }
+    }
+
+    #[test]
+    fn sorted_names() {
+        let names: Vec<&str> = ciphers().map(Factory::name).collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+
+        assert_eq!(sorted, names);
+    }
+
+    #[test]
+    fn lookup_ignores_case_and_separators() {
+        assert_eq!("keyword_caesar", lookup("Keyword-Caesar").unwrap().name());
+        assert_eq!(
+            "columnar_transposition",
+            lookup("ColumnarTransposition").unwrap().name()
+        );
+    }
+
+    #[test]
+    fn lookup_by_type() {
+        assert_eq!("caesar", lookup_cipher::<Caesar>().unwrap().name());
+        assert_eq!("keyword", lookup_cipher::<Keyword>().unwrap().name());
+        assert_eq!(
+            "keyword_caesar",
+            lookup_cipher::<KeywordCaesar>().unwrap().name()
+        );
+        assert!(lookup_cipher::<String>().is_none());
+    }
+
+    #[test]
+    fn invalid_keys() {
+        assert!(lookup("caesar").unwrap().parse("27").is_err());
+        assert!(lookup("vigenere").unwrap().parse("lem0n").is_err());
+        assert!(lookup("playfair").unwrap().parse("jump").is_err());
+        assert!(lookup("railfence").unwrap().parse("0").is_err());
+        assert!(lookup("simple_substitution").unwrap().parse("abc").is_err());
+        assert!(lookup("keyword_caesar")
+            .unwrap()
+            .parse("kryptos,26")
+            .is_err());
+        assert!(lookup("adfgvx").unwrap().parse("ph0!,german").is_err());
+        assert!(lookup("affine_hill")
+            .unwrap()
+            .parse("AAAAAAAAA,BCD")
+            .is_err());
+        assert!(lookup("ave_maria").unwrap().parse("key").is_err());
+        assert!(lookup("bifid").unwrap().parse("playfair,0").is_err());
+        assert!(lookup("dryad").unwrap().parse("1944,z").is_err());
+        assert!(lookup("jefferson_wheel").unwrap().parse("1 1,5").is_err());
+        assert!(lookup("kama_sutra").unwrap().parse("abcd").is_err());
+        assert!(lookup("keyboard_shift")
+            .unwrap()
+            .parse("qwerty,right,0")
+            .is_err());
+        assert!(lookup("m209")
+            .unwrap()
+            .parse("AAAAAA;lugs = 1-0*27")
+            .is_err());
+        assert!(lookup("redefence").unwrap().parse("3 1 2,4").is_err());
+        assert!(lookup("rs44").unwrap().parse("1 2 3,A1,0").is_err());
+        assert!(lookup("syllabary")
+            .unwrap()
+            .parse("short,dictionary")
+            .is_err());
+        assert!(lookup("tableau").unwrap().parse("a=abc,a").is_err());
+        assert!(lookup("tap_code").unwrap().parse("..").is_err());
+        assert!(lookup("uesugi").unwrap().parse("1234567,1234566").is_err());
+        assert!(lookup("vic")
+            .unwrap()
+            .parse("Too short,030945,6,77651")
+            .is_err());
+    }
+}
@@ -5,6 +5,8 @@
 //! this module does not implement the `Cipher` trait.
 //!
 use crate::common::alphabet::Alphabet;
+#[cfg(feature = "parallel")]
+use crate::common::parallel;
 use crate::common::{alphabet, substitute};
 
 /// Encrypt a message using the Rot13 substitute cipher.
@@ -39,10 +41,39 @@ pub fn decrypt(message: &str) -> String {
     substitute::shift_substitution(message, |i| alphabet::STANDARD.modulo((i + 13) as isize))
 }
 
+/// Encrypt a long message using the Rot13 substitute cipher, sharing the work across threads.
+///
+/// The message is split into chunks, which are encrypted on the threads of the global `rayon`
+/// thread pool. The ciphertext is the same as that of `encrypt()`.
+///
+#[cfg(feature = "parallel")]
+pub fn encrypt_parallel(message: &str) -> String {
+    parallel::map_chunks(message, |chunk| Ok(encrypt(chunk))).unwrap()
+}
+
+/// Decrypt a long message using the Rot13 substitute cipher, sharing the work across threads.
+///
+/// The plaintext is the same as that of `decrypt()`.
+///
+#[cfg(feature = "parallel")]
+pub fn decrypt_parallel(message: &str) -> String {
+    parallel::map_chunks(message, |chunk| Ok(decrypt(chunk))).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn parallel_long_message() {
+        let message = "Peace, Freedom and Liberty! 🗡️ ".repeat(5000);
+        let encrypted = encrypt_parallel(&message);
+
+        assert_eq!(encrypt(&message), encrypted);
+        assert_eq!(message, decrypt_parallel(&encrypted));
+    }
+
     #[test]
     fn with_utf8() {
         let message = "Peace, Freedom and Liberty! 🗡️";
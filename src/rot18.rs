@@ -0,0 +1,61 @@
+//! ROT18 combines ROT13 and ROT5, rotating each letter by 13 places and each digit by 5 places.
+//! It is sometimes used in place of ROT13 so that any numbers within the message are also
+//! obscured.
+//!
+//! As both of its parts are their own inverse, so is ROT18. That is,
+//! `ROT18(ROT18(message)) = message`. Due to its simplicity, this module does not implement the
+//! `Cipher` trait.
+//!
+use crate::{rot13, rot5};
+
+/// Encrypt a message using the Rot18 substitute cipher.
+///
+/// # Examples
+/// Basic usage:
+///
+/// ```
+/// use cipher_crypt::Rot18;
+///
+/// assert_eq!("Nggnpx ng 5nz!", &Rot18::encrypt("Attack at 0am!"));
+/// ```
+///
+pub fn encrypt(message: &str) -> String {
+    rot5::encrypt(&rot13::encrypt(message))
+}
+
+/// Decrypt a message using the Rot18 substitute cipher.
+///
+/// # Examples
+/// Basic usage:
+///
+/// ```
+/// use cipher_crypt::Rot18;
+///
+/// assert_eq!("Attack at 0am!", &Rot18::decrypt("Nggnpx ng 5nz!"));
+/// ```
+///
+pub fn decrypt(message: &str) -> String {
+    rot5::decrypt(&rot13::decrypt(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alphanumeric_encrypt() {
+        assert_eq!(
+            "nopqrstuvwxyzabcdefghijklm5678901234",
+            encrypt("abcdefghijklmnopqrstuvwxyz0123456789")
+        );
+    }
+
+    #[test]
+    fn with_utf8() {
+        let message = "Peace, Freedom and Liberty in 1776! 🗡️";
+        let encrypted = encrypt(message);
+        let decrypted = decrypt(&encrypted);
+
+        assert_eq!(decrypted, message);
+    }
+}
@@ -0,0 +1,65 @@
+//! ROT5 ("rotate by 5 places") is the equivalent of ROT13 for the digits `0-9`. It substitutes a
+//! digit with the one 5 places after it, leaving all other characters unchanged.
+//!
+//! Like ROT13, ROT5 is its own inverse. That is, `ROT5(ROT5(message)) = message`. Due to its
+//! simplicity, this module does not implement the `Cipher` trait.
+//!
+use crate::common::alphabet::Alphabet;
+use crate::common::{alphabet, substitute};
+
+/// Encrypt a message using the Rot5 substitute cipher.
+///
+/// # Examples
+/// Basic usage:
+///
+/// ```
+/// use cipher_crypt::Rot5;
+///
+/// assert_eq!("Call 860-7238", &Rot5::encrypt("Call 315-2783"));
+/// ```
+///
+pub fn encrypt(message: &str) -> String {
+    substitute::alphabet_shift_substitution(message, &alphabet::NUMERIC, |i| {
+        alphabet::NUMERIC.modulo((i + 5) as isize)
+    })
+}
+
+/// Decrypt a message using the Rot5 substitute cipher.
+///
+/// # Examples
+/// Basic usage:
+///
+/// ```
+/// use cipher_crypt::Rot5;
+///
+/// assert_eq!("Call 315-2783", &Rot5::decrypt("Call 860-7238"));
+/// ```
+///
+pub fn decrypt(message: &str) -> String {
+    encrypt(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits_encrypt() {
+        assert_eq!("5678901234", encrypt("0123456789"));
+    }
+
+    #[test]
+    fn letters_unchanged() {
+        let message = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        assert_eq!(message, encrypt(message));
+    }
+
+    #[test]
+    fn with_utf8() {
+        let message = "Peace, Freedom and Liberty in 1776! 🗡️";
+        let encrypted = encrypt(message);
+        let decrypted = decrypt(&encrypted);
+
+        assert_eq!(decrypted, message);
+    }
+}
@@ -0,0 +1,359 @@
+//! Rasterschlüssel 44 (RS44) was a hand cipher used by the German Army from 1944 until the end
+//! of the Second World War. It is a transposition cipher based on a stencil of 25 rows and 24
+//! columns, in which roughly half of the cells of each row are blacked out.
+//!
+//! The columns of the stencil are labelled with a scrambled sequence of the numbers 1 to 24, and
+//! the rows are labelled with the letters `A-Z` (omitting `J`). To encrypt a message the sender
+//! chooses a starting cell, and writes the message along the rows into the open cells of the
+//! stencil, skipping any that are blacked out. The message is then read down the columns, in the
+//! order of their labels. Rather than starting with the column labelled 1, the reading begins
+//! with a column that depends upon the time of transmission.
+//!
+//! In this implementation the reading begins with the column labelled `minute mod 24 + 1`, and
+//! continues in the order of the labels, wrapping around from 24 back to 1. The stencils were
+//! issued regularly and kept secret, so an `EXAMPLE_STENCIL` is provided for demonstration only.
+//!
+use crate::common::cipher::Cipher;
+
+/// The labels of the rows of the stencil, from top to bottom.
+pub const ROW_LABELS: &str = "ABCDEFGHIKLMNOPQRSTUVWXYZ";
+
+/// An example stencil, where each `.` is an open cell and each `#` is blacked out.
+pub const EXAMPLE_STENCIL: [&str; 25] = [
+    ".##.#.#.#.##..##..##.###",
+    ".##.#.###.##.##..#.###..",
+    "##..#.##....#########...",
+    "#...##.#....######.##.##",
+    "..#..#...######.####.#.#",
+    "#.#..#####..#...#####.#.",
+    "..#.#.##.##.##..###.#.##",
+    ".#####.##.#.###.#.....##",
+    "#.#.###.##..#.#..#.####.",
+    ".#.#..##..#######.#...##",
+    "#.###..#.####.#....###.#",
+    "##.###.##..####...#..#.#",
+    "##.#.###...#.#.#...#####",
+    "##.#.###.##..#..#####...",
+    "#..####..##.###...#.###.",
+    "###...#..##.#..######.#.",
+    ".#...#####.#.#..#..#####",
+    ".#.#....#..#########..##",
+    "....##.###.#.###..#.####",
+    ".#.#..##...#.####.#####.",
+    "....#..######.####.##..#",
+    "####..#...#...##.####.##",
+    "#####.#.#.##.#..###....#",
+    "###.#####.##.#..##....#.",
+    ".####....##.###.##.###..",
+];
+
+const ROWS: usize = 25;
+const COLUMNS: usize = 24;
+
+/// A Rasterschlüssel 44 cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RS44 {
+    open: Vec<Vec<bool>>,
+    columns: Vec<usize>,
+    start: (usize, usize),
+    minute: usize,
+}
+
+/// The key of a Rasterschlüssel 44 cipher.
+///
+/// This struct is given to the `new()` method of `RS44`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RS44Key {
+    /// The 25 rows of 24 cells, where each `.` is an open cell and each `#` is blacked out.
+    pub stencil: Vec<String>,
+    /// The numbers 1 to 24 in the order they label the columns.
+    pub column_labels: Vec<usize>,
+    /// The cell where the message begins, given by the label of its row and the label of
+    /// its column.
+    pub start: (char, usize),
+    /// The minute of the time of transmission, which determines the first column that is
+    /// read.
+    pub minute: usize,
+}
+
+impl Cipher for RS44 {
+    type Key = RS44Key;
+    type Algorithm = RS44;
+
+    /// Initialise a Rasterschlüssel 44 cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `RS44Key`.
+    ///
+    /// # Panics
+    /// * The `stencil` does not have 25 rows of 24 cells.
+    /// * The `stencil` contains a symbol other than `.` or `#`.
+    /// * The `column_labels` are not a permutation of the numbers 1 to 24.
+    /// * The `start` refers to a cell that does not exist, or is blacked out.
+    ///
+    fn new(key: RS44Key) -> RS44 {
+        let RS44Key {
+            stencil,
+            column_labels: columns,
+            start,
+            minute,
+        } = key;
+
+        if stencil.len() != ROWS || stencil.iter().any(|r| r.chars().count() != COLUMNS) {
+            panic!("The stencil must have 25 rows of 24 cells.");
+        }
+        let open: Vec<Vec<bool>> = stencil
+            .iter()
+            .map(|row| {
+                row.chars()
+                    .map(|c| match c {
+                        '.' => true,
+                        '#' => false,
+                        _ => panic!(
+                            "The stencil may only contain open `.` or blacked out `#` cells."
+                        ),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        if columns.len() != COLUMNS || (1..=COLUMNS).any(|n| !columns.contains(&n)) {
+            panic!("The column labels must contain each of the numbers 1 to 24 exactly once.");
+        }
+
+        let row = ROW_LABELS
+            .find(start.0.to_ascii_uppercase())
+            .expect("The starting row does not exist.");
+        let column = columns
+            .iter()
+            .position(|&c| c == start.1)
+            .expect("The starting column does not exist.");
+        if !open[row][column] {
+            panic!("The starting cell is blacked out.");
+        }
+
+        RS44 {
+            open,
+            columns,
+            start: (row, column),
+            minute,
+        }
+    }
+
+    /// Encrypt a message using a Rasterschlüssel 44 cipher.
+    ///
+    /// All characters of the message, including whitespace and punctuation, are transposed. An
+    /// `Err` is returned if the message does not fit in the open cells after the starting cell.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::rs44::EXAMPLE_STENCIL;
+    /// use cipher_crypt::{Cipher, RS44, RS44Key};
+    ///
+    /// let r = RS44::new(RS44Key {
+    ///     stencil: EXAMPLE_STENCIL.iter().map(|r| r.to_string()).collect(),
+    ///     column_labels: vec![
+    ///         20, 23, 19, 11, 24, 7, 6, 17, 3, 15, 5, 18, 13, 16, 1, 4, 21, 9, 2, 10, 22, 14, 8, 12,
+    ///     ],
+    ///     start: ('B', 20),
+    ///     minute: 35,
+    /// });
+    /// assert_eq!("AWCRAMRWAATOKOTTDOTN", r.encrypt("ATTACKATDAWNTOMORROW").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        let chars: Vec<char> = message.chars().collect();
+        let cells = self.cells(chars.len())?;
+
+        Ok(self
+            .reading_order(&cells)
+            .into_iter()
+            .map(|i| chars[i])
+            .collect())
+    }
+
+    /// Decrypt a message using a Rasterschlüssel 44 cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::rs44::EXAMPLE_STENCIL;
+    /// use cipher_crypt::{Cipher, RS44, RS44Key};
+    ///
+    /// let r = RS44::new(RS44Key {
+    ///     stencil: EXAMPLE_STENCIL.iter().map(|r| r.to_string()).collect(),
+    ///     column_labels: vec![
+    ///         20, 23, 19, 11, 24, 7, 6, 17, 3, 15, 5, 18, 13, 16, 1, 4, 21, 9, 2, 10, 22, 14, 8, 12,
+    ///     ],
+    ///     start: ('B', 20),
+    ///     minute: 35,
+    /// });
+    /// assert_eq!("ATTACKATDAWNTOMORROW", r.decrypt("AWCRAMRWAATOKOTTDOTN").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let chars: Vec<char> = ciphertext.chars().collect();
+        let cells = self.cells(chars.len())?;
+
+        let mut message = vec!['\0'; chars.len()];
+        for (i, c) in self.reading_order(&cells).into_iter().zip(chars) {
+            message[i] = c;
+        }
+
+        Ok(message.into_iter().collect())
+    }
+}
+
+impl RS44 {
+    /// The `(row, column)` of the open cells that hold each of the `len` characters of the
+    /// message, written along the rows from the starting cell.
+    ///
+    fn cells(&self, len: usize) -> Result<Vec<(usize, usize)>, &'static str> {
+        let cells: Vec<(usize, usize)> = (0..ROWS)
+            .flat_map(|r| (0..COLUMNS).map(move |c| (r, c)))
+            .skip_while(|&cell| cell != self.start)
+            .filter(|&(r, c)| self.open[r][c])
+            .take(len)
+            .collect();
+
+        if cells.len() < len {
+            return Err("The message does not fit in the stencil.");
+        }
+
+        Ok(cells)
+    }
+
+    /// The indices of the characters of the message, in the order they are read down the
+    /// columns.
+    ///
+    fn reading_order(&self, cells: &[(usize, usize)]) -> Vec<usize> {
+        let first = self.minute % COLUMNS;
+
+        (0..COLUMNS)
+            .map(|n| (first + n) % COLUMNS + 1)
+            .flat_map(|label| {
+                let column = self.columns.iter().position(|&c| c == label).unwrap();
+                let mut indices: Vec<usize> =
+                    (0..cells.len()).filter(|&i| cells[i].1 == column).collect();
+                indices.sort_by_key(|&i| cells[i].0);
+
+                indices
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLUMN_LABELS: [usize; 24] = [
+        20, 23, 19, 11, 24, 7, 6, 17, 3, 15, 5, 18, 13, 16, 1, 4, 21, 9, 2, 10, 22, 14, 8, 12,
+    ];
+
+    fn rs44(start: (char, usize), minute: usize) -> RS44 {
+        RS44::new(RS44Key {
+            stencil: EXAMPLE_STENCIL.iter().map(|r| r.to_string()).collect(),
+            column_labels: COLUMN_LABELS.to_vec(),
+            start,
+            minute,
+        })
+    }
+
+    #[test]
+    fn encrypt_test() {
+        assert_eq!(
+            "OLLFTTFEESSCDANWETEADAHLE",
+            rs44(('A', 20), 0)
+                .encrypt("DEFENDTHEEASTWALLOFCASTLE")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_test() {
+        assert_eq!(
+            "DEFENDTHEEASTWALLOFCASTLE",
+            rs44(('A', 20), 0)
+                .decrypt("OLLFTTFEESSCDANWETEADAHLE")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn minute_changes_first_column() {
+        let m = "DEFENDTHEEASTWALLOFCASTLE";
+        let c1 = rs44(('A', 20), 0).encrypt(m).unwrap();
+        let c2 = rs44(('A', 20), 24).encrypt(m).unwrap();
+        let c3 = rs44(('A', 20), 10).encrypt(m).unwrap();
+
+        assert_eq!(c1, c2);
+        assert_ne!(c1, c3);
+    }
+
+    #[test]
+    fn long_message() {
+        let r = rs44(('c', 11), 42);
+        let m = concat!(
+            "The quick brown fox jumps over the lazy dog. ",
+            "Pack my box with five dozen liquor jugs! ",
+            "How vexingly quick daft zebras jump."
+        );
+
+        assert_eq!(m, r.decrypt(&r.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn message_too_long() {
+        let r = rs44(('Z', 12), 0);
+        assert!(r.encrypt("ATTACKATDAWN").is_err());
+    }
+
+    #[test]
+    fn with_utf8() {
+        let r = rs44(('F', 14), 17);
+        let m = "Peace 🗡️ Freedom and Liberty!";
+
+        assert_eq!(m, r.decrypt(&r.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn small_stencil() {
+        RS44::new(RS44Key {
+            stencil: EXAMPLE_STENCIL[1..].iter().map(|r| r.to_string()).collect(),
+            column_labels: COLUMN_LABELS.to_vec(),
+            start: ('A', 20),
+            minute: 0,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_column_labels() {
+        let mut labels = COLUMN_LABELS.to_vec();
+        labels[0] = 23;
+        RS44::new(RS44Key {
+            stencil: EXAMPLE_STENCIL.iter().map(|r| r.to_string()).collect(),
+            column_labels: labels,
+            start: ('A', 20),
+            minute: 0,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn blacked_out_start() {
+        rs44(('A', 23), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unknown_row() {
+        rs44(('J', 20), 0);
+    }
+}
@@ -9,27 +9,38 @@
 //! circumference.
 //!
 //! Scytale encryption is only keyed by the number of letters that fit on each roll
-//! around the scytale. Therefore, it can be trivially cracked.
+//! around the scytale. Therefore, it can be trivially cracked by trying each height in turn, as
+//! `analysis::crack_scytale` does.
 //!
 use crate::common::cipher::Cipher;
+use std::borrow::Cow;
 
 /// A Scytale cipher.
 ///
 /// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Scytale {
     height: usize,
 }
 
-impl Cipher for Scytale {
-    type Key = usize;
-    type Algorithm = Scytale;
-
+impl Scytale {
     /// Initialize a Scytale cipher with a specific cylinder height.
     ///
+    /// This can be called in a `const` context, where a key of 0 fails to compile.
+    ///
     /// # Panics
     /// * The `key` is 0.
     ///
-    fn new(key: usize) -> Scytale {
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Scytale};
+    ///
+    /// const ROD: Scytale = Scytale::new(6);
+    /// assert_eq!("Pegr lefoporaryr !", ROD.encrypt("Prepare for glory!").unwrap());
+    /// ```
+    ///
+    pub const fn new(key: usize) -> Scytale {
         if key == 0 {
             panic!("Invalid key, height cannot be zero.");
         }
@@ -37,6 +48,30 @@ impl Cipher for Scytale {
         Scytale { height: key }
     }
 
+    /// The number of rows that the message is wrapped around the rod in.
+    ///
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the rod leaves a `text` unchanged, as it does when the rod is at least as tall as
+    /// the text is long (or has a height of 1).
+    ///
+    fn leaves_unchanged(&self, text: &str) -> bool {
+        self.height >= text.chars().count() || self.height == 1
+    }
+}
+
+impl Cipher for Scytale {
+    type Key = usize;
+    type Algorithm = Scytale;
+
+    /// Initialize a Scytale cipher with a specific cylinder height, as by `Scytale::new()`.
+    ///
+    fn new(key: usize) -> Scytale {
+        Scytale::new(key)
+    }
+
     /// Encrypt a message using a Scytale cipher.
     ///
     /// Whilst all characters (including utf8) can be encrypted during the transposition process,
@@ -56,7 +91,7 @@ impl Cipher for Scytale {
     ///
     fn encrypt(&self, message: &str) -> Result<String, &'static str> {
         // In both these cases the message is not altered
-        if self.height >= message.chars().count() || self.height == 1 {
+        if self.leaves_unchanged(message) {
             return Ok(message.to_string());
         }
 
@@ -96,7 +131,7 @@ impl Cipher for Scytale {
     ///
     fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
         // In both these cases the ciphertext has not been altered
-        if self.height >= ciphertext.chars().count() || self.height == 1 {
+        if self.leaves_unchanged(ciphertext) {
             return Ok(ciphertext.to_string());
         }
 
@@ -124,6 +159,26 @@ impl Cipher for Scytale {
         //Make sure to strip any padding characters
         Ok(plaintext.trim_end().to_string())
     }
+
+    /// Encrypt a message, borrowing it where the rod would leave it unchanged.
+    ///
+    fn encrypt_cow<'a>(&self, message: &'a str) -> Result<Cow<'a, str>, &'static str> {
+        if self.leaves_unchanged(message) {
+            return Ok(Cow::Borrowed(message));
+        }
+
+        self.encrypt(message).map(Cow::Owned)
+    }
+
+    /// Decrypt a ciphertext, borrowing it where the rod would leave it unchanged.
+    ///
+    fn decrypt_cow<'a>(&self, ciphertext: &'a str) -> Result<Cow<'a, str>, &'static str> {
+        if self.leaves_unchanged(ciphertext) {
+            return Ok(Cow::Borrowed(ciphertext));
+        }
+
+        self.decrypt(ciphertext).map(Cow::Owned)
+    }
 }
 
 #[cfg(test)]
@@ -192,4 +247,16 @@ mod tests {
         );
         assert_eq!(m, s.decrypt(&s.encrypt(m).unwrap()).unwrap());
     }
+
+    #[test]
+    fn cow_borrows_unchanged() {
+        assert!(matches!(
+            Scytale::new(1).encrypt_cow("Attack"),
+            Ok(Cow::Borrowed(_))
+        ));
+        assert!(matches!(
+            Scytale::new(3).encrypt_cow("Attack"),
+            Ok(Cow::Owned(_))
+        ));
+    }
 }
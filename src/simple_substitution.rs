@@ -0,0 +1,275 @@
+//! The Simple Substitution cipher is the most general form of monoalphabetic substitution, where
+//! each letter of the alphabet is replaced by another according to an arbitrary permutation.
+//!
+//! Although there are `26!` (roughly 4 x 10^26) possible keys, far too many to try each in turn,
+//! the cipher is easily broken with frequency analysis as each letter is always replaced by the
+//! same ciphertext letter.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::{ByteCipher, CharCipher, Cipher};
+use crate::common::substitute;
+use rand::seq::SliceRandom;
+use std::collections::HashMap;
+
+/// A Simple Substitution cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimpleSubstitution {
+    substitution: Vec<usize>,
+}
+
+impl Cipher for SimpleSubstitution {
+    type Key = String;
+    type Algorithm = SimpleSubstitution;
+
+    /// Initialise a Simple Substitution cipher given a permutation of the alphabet,
+    /// as by `SimpleSubstitution::new()`.
+    ///
+    fn new(key: String) -> SimpleSubstitution {
+        SimpleSubstitution::new(key)
+    }
+
+    /// Encrypt a message using a Simple Substitution cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, SimpleSubstitution};
+    ///
+    /// let s = SimpleSubstitution::new("phqgiumeaylnofdxjkrcvstzwb");
+    /// assert_eq!("Pccpql pc gptf!", s.encrypt("Attack at dawn!").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        Ok(self.encrypt_chars(message.chars()).collect())
+    }
+
+    /// Encrypt a message onto the end of `out`, one character at a time.
+    ///
+    fn encrypt_into(&self, message: &str, out: &mut String) -> Result<(), &'static str> {
+        out.extend(self.encrypt_chars(message.chars()));
+        Ok(())
+    }
+
+    /// Decrypt a message using a Simple Substitution cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, SimpleSubstitution};
+    ///
+    /// let s = SimpleSubstitution::new("phqgiumeaylnofdxjkrcvstzwb");
+    /// assert_eq!("Attack at dawn!", s.decrypt("Pccpql pc gptf!").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        Ok(self.decrypt_chars(ciphertext.chars()).collect())
+    }
+}
+
+impl CharCipher for SimpleSubstitution {
+    fn encrypt_char(&self, c: char) -> char {
+        substitute::shift_char(c, |idx| self.substitution[idx])
+    }
+
+    fn decrypt_char(&self, c: char) -> char {
+        substitute::shift_char(c, |idx| {
+            self.substitution.iter().position(|&s| s == idx).unwrap()
+        })
+    }
+}
+
+impl ByteCipher for SimpleSubstitution {}
+
+impl SimpleSubstitution {
+    /// Initialise a Simple Substitution cipher given a permutation of the alphabet.
+    ///
+    /// The `key` gives the substitution for each of the letters `a-z` in order. For example, a
+    /// key beginning with `q` will replace each `a` of the message with a `q`.
+    ///
+    /// # Panics
+    /// * The `key` is not 26 letters long.
+    /// * The `key` contains a non-alphabetic symbol.
+    /// * The `key` contains a letter more than once (it is not a bijection).
+    ///
+    pub fn new(key: impl Into<String>) -> SimpleSubstitution {
+        let key = key.into();
+        if key.chars().count() != 26 || !alphabet::STANDARD.is_valid(&key) {
+            panic!("The key must contain 26 letters.");
+        }
+
+        let substitution: Vec<usize> = key
+            .chars()
+            .map(|c| alphabet::STANDARD.find_position(c).unwrap())
+            .collect();
+        for i in 0..26 {
+            if !substitution.contains(&i) {
+                panic!("The key must contain each letter of the alphabet exactly once.");
+            }
+        }
+
+        SimpleSubstitution { substitution }
+    }
+
+    /// Initialise a Simple Substitution cipher from a mapping of plaintext to ciphertext letters.
+    ///
+    /// The case of the letters in the mapping is ignored.
+    ///
+    /// # Panics
+    /// * The `mapping` does not contain a substitution for every letter `a-z`.
+    /// * The `mapping` contains a non-alphabetic symbol.
+    /// * Two letters are mapped to the same letter (it is not a bijection).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, SimpleSubstitution};
+    /// use std::collections::HashMap;
+    ///
+    /// // A mapping that swaps each pair of letters 'a' <-> 'b', 'c' <-> 'd', ...
+    /// let mapping: HashMap<char, char> = (b'a'..=b'z')
+    ///     .map(|c| (c as char, (((c - b'a') ^ 1) + b'a') as char))
+    ///     .collect();
+    ///
+    /// let s = SimpleSubstitution::from_map(&mapping);
+    /// assert_eq!("Bssbdl bs cbxm!", s.encrypt("Attack at dawn!").unwrap());
+    /// ```
+    ///
+    pub fn from_map(mapping: &HashMap<char, char>) -> SimpleSubstitution {
+        let mut key = String::new();
+        for i in 0..26 {
+            let c = alphabet::STANDARD.get_letter(i, false);
+            let sub = mapping
+                .get(&c)
+                .or_else(|| mapping.get(&c.to_ascii_uppercase()))
+                .expect("The mapping must contain a substitution for every letter.");
+            key.push(sub.to_ascii_lowercase());
+        }
+
+        SimpleSubstitution::new(key)
+    }
+
+    /// Initialise a Simple Substitution cipher with a random permutation of the alphabet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, SimpleSubstitution};
+    ///
+    /// let s = SimpleSubstitution::random();
+    /// assert_eq!("Attack at dawn!", s.decrypt(&s.encrypt("Attack at dawn!").unwrap()).unwrap());
+    /// ```
+    ///
+    pub fn random() -> SimpleSubstitution {
+        let mut letters: Vec<char> = (0..26)
+            .map(|i| alphabet::STANDARD.get_letter(i, false))
+            .collect();
+        letters.shuffle(&mut rand::thread_rng());
+
+        SimpleSubstitution::new(letters.into_iter().collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decrypt_chars_test() {
+        let s = SimpleSubstitution::new(String::from("phqgiumeaylnofdxjkrcvstzwb"));
+        let decrypted: String = s.decrypt_chars("Cei Jvaql".chars()).collect();
+
+        assert_eq!("The Quick", decrypted);
+    }
+
+    #[test]
+    fn encrypt_test() {
+        let s = SimpleSubstitution::new(String::from("phqgiumeaylnofdxjkrcvstzwb"));
+        assert_eq!(
+            "cei jvaql hkdtf udz yvoxr dsik cei npbw gdm",
+            s.encrypt("the quick brown fox jumps over the lazy dog")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let s = SimpleSubstitution::new(String::from("phqgiumeaylnofdxjkrcvstzwb"));
+        assert_eq!(
+            "the quick brown fox jumps over the lazy dog",
+            s.decrypt("cei jvaql hkdtf udz yvoxr dsik cei npbw gdm")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn with_utf8() {
+        let s = SimpleSubstitution::new(String::from("PHQGIUMEAYLNOFDXJKRCVSTZWB"));
+        let m = "Peace 🗡️ Freedom and Liberty!";
+
+        assert_eq!(m, s.decrypt(&s.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn from_map_matches_new() {
+        let key = "phqgiumeaylnofdxjkrcvstzwb";
+        let mapping: HashMap<char, char> = (0..26)
+            .map(|i| alphabet::STANDARD.get_letter(i, true))
+            .zip(key.chars())
+            .collect();
+
+        let m = "Attack at dawn!";
+        assert_eq!(
+            SimpleSubstitution::new(String::from(key))
+                .encrypt(m)
+                .unwrap(),
+            SimpleSubstitution::from_map(&mapping).encrypt(m).unwrap()
+        );
+    }
+
+    #[test]
+    fn random_is_a_permutation() {
+        let s = SimpleSubstitution::random();
+
+        let mut sorted = s.substitution.clone();
+        sorted.sort_unstable();
+        assert_eq!((0..26).collect::<Vec<usize>>(), sorted);
+    }
+
+    #[test]
+    #[should_panic]
+    fn short_key() {
+        SimpleSubstitution::new(String::from("phqgiumeaylnofdxjkrcvstzw"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn duplicate_letters() {
+        SimpleSubstitution::new(String::from("phqgiumeaylnofdxjkrcvstzwp"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn key_with_symbols() {
+        SimpleSubstitution::new(String::from("phqgiumeaylnofdxjkrcvstzw!"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn incomplete_map() {
+        let mut mapping: HashMap<char, char> = HashMap::new();
+        mapping.insert('a', 'b');
+        SimpleSubstitution::from_map(&mapping);
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_bijective_map() {
+        let mapping: HashMap<char, char> = (b'a'..=b'z').map(|c| (c as char, 'a')).collect();
+        SimpleSubstitution::from_map(&mapping);
+    }
+}
@@ -0,0 +1,327 @@
+//! A syllabary (or syllabic) cipher replaces the letters and common syllables of a message with
+//! pairs of digits, read from the row and column of a 10x10 grid. Syllabaries were used from the
+//! 17th century onwards, and as late as the Second World War, as the grouping of common syllables
+//! hides the frequencies of the single letters.
+//!
+//! The rows and columns of the grid are labelled with the digits `0-9`, in an order given by two
+//! keywords. The letters of each keyword are numbered in alphabetical order, with repeated
+//! letters numbered from left to right, and the numbers are taken modulo 10 to give the labels.
+//!
+//! The message is broken into the longest entries of the grid that match it, ignoring case and
+//! whitespace. The default grid contains the letters `a-z`, the digits `0-9` and 64 of the most
+//! common syllables of English.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::keygen;
+
+/// The default entries of the syllabary grid, read row by row.
+pub const DEFAULT_TABLE: [&str; 100] = [
+    "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", //
+    "k", "l", "m", "n", "o", "p", "q", "r", "s", "t", //
+    "u", "v", "w", "x", "y", "z", "0", "1", "2", "3", //
+    "4", "5", "6", "7", "8", "9", "th", "he", "in", "er", //
+    "an", "re", "on", "at", "en", "nd", "ti", "es", "or", "te", //
+    "of", "ed", "is", "it", "al", "ar", "st", "to", "nt", "ng", //
+    "se", "ha", "as", "ou", "io", "le", "ve", "co", "me", "de", //
+    "hi", "ri", "ro", "ic", "ne", "ea", "ra", "ce", "li", "ch", //
+    "ll", "be", "ma", "si", "om", "ur", "the", "and", "ing", "ion", //
+    "ent", "her", "for", "tha", "nth", "int", "ere", "tio", "ter", "est", //
+];
+
+/// A Syllabary cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Syllabary {
+    table: Vec<String>,
+    rows: Vec<usize>,
+    columns: Vec<usize>,
+}
+
+/// The key of a Syllabary cipher.
+///
+/// This struct is given to the `new()` method of `Syllabary`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyllabaryKey {
+    /// The 10 alphanumeric characters that give the order of the digit labels of the rows.
+    pub row_key: String,
+    /// The 10 alphanumeric characters that give the order of the digit labels of the
+    /// columns.
+    pub column_key: String,
+    /// An optional table of the 100 entries of the grid, read row by row - default is the
+    /// `DEFAULT_TABLE`.
+    pub table: Option<Vec<String>>,
+}
+
+impl Cipher for Syllabary {
+    type Key = SyllabaryKey;
+    type Algorithm = Syllabary;
+
+    /// Initialise a Syllabary cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `SyllabaryKey`.
+    ///
+    /// # Panics
+    /// * The `row_key` or `column_key` is not 10 characters long.
+    /// * The `row_key` or `column_key` contains a non-alphanumeric symbol.
+    /// * The `table` does not contain 100 entries.
+    /// * An entry of the `table` is empty, contains whitespace, or is repeated.
+    ///
+    fn new(key: SyllabaryKey) -> Syllabary {
+        let SyllabaryKey {
+            row_key,
+            column_key,
+            table,
+        } = key;
+
+        let table: Vec<String> = match table {
+            Some(t) => t.iter().map(|e| e.to_lowercase()).collect(),
+            None => DEFAULT_TABLE.iter().map(|e| e.to_string()).collect(),
+        };
+        if table.len() != 100 {
+            panic!("The table must contain 100 entries.");
+        }
+        for (i, entry) in table.iter().enumerate() {
+            if entry.is_empty() || entry.chars().any(char::is_whitespace) {
+                panic!("An entry of the table is empty or contains whitespace.");
+            }
+            if table[..i].contains(entry) {
+                panic!("The table cannot contain repeated entries.");
+            }
+        }
+
+        Syllabary {
+            table,
+            rows: labels(&row_key),
+            columns: labels(&column_key),
+        }
+    }
+
+    /// Encrypt a message using a Syllabary cipher.
+    ///
+    /// Each entry of the grid is enciphered as its row label followed by its column label, and
+    /// the pairs are separated by spaces.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Syllabary, SyllabaryKey};
+    ///
+    /// let s = Syllabary::new(SyllabaryKey {
+    ///     row_key: String::from("cryptology"),
+    ///     column_key: String::from("dictionary"),
+    ///     table: None,
+    /// });
+    /// assert_eq!("26 76 93 59 73 17 75 99", s.encrypt("The quick fox").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        let text: Vec<char> = message
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .flat_map(char::to_lowercase)
+            .collect();
+
+        let mut pairs = Vec::new();
+        let mut i = 0;
+        while i < text.len() {
+            let (index, len) = self
+                .table
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| (index, entry.chars().collect::<Vec<char>>()))
+                .filter(|(_, entry)| text[i..].starts_with(entry))
+                .map(|(index, entry)| (index, entry.len()))
+                .max_by_key(|&(_, len)| len)
+                .ok_or("Message contains a symbol that is not in the table.")?;
+
+            pairs.push(format!(
+                "{}{}",
+                self.rows[index / 10],
+                self.columns[index % 10]
+            ));
+            i += len;
+        }
+
+        Ok(pairs.join(" "))
+    }
+
+    /// Decrypt a message using a Syllabary cipher.
+    ///
+    /// Whitespace within the ciphertext is ignored. The plaintext is lowercase, without any
+    /// whitespace.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Syllabary, SyllabaryKey};
+    ///
+    /// let s = Syllabary::new(SyllabaryKey {
+    ///     row_key: String::from("cryptology"),
+    ///     column_key: String::from("dictionary"),
+    ///     table: None,
+    /// });
+    /// assert_eq!("thequickfox", s.decrypt("26 76 93 59 73 17 75 99").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let digits: Vec<usize> = ciphertext
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_digit(10).map(|d| d as usize))
+            .collect::<Option<Vec<usize>>>()
+            .ok_or("Ciphertext contains a symbol that is not a digit.")?;
+
+        if !digits.len().is_multiple_of(2) {
+            return Err("Ciphertext contains an incomplete pair of digits.");
+        }
+
+        Ok(digits
+            .chunks(2)
+            .map(|rc| {
+                let row = self.rows.iter().position(|&r| r == rc[0]).unwrap();
+                let column = self.columns.iter().position(|&c| c == rc[1]).unwrap();
+
+                self.table[row * 10 + column].as_str()
+            })
+            .collect())
+    }
+
+    /// Whether a character is foreign to the cipher, being outside of `a-z`, `A-Z` and `0-9`.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        !c.is_ascii_alphanumeric()
+    }
+}
+
+/// The digit labels of the rows or columns of the grid, given by a 10 character keyword.
+///
+/// # Panics
+/// * The `key` is not 10 characters long.
+/// * The `key` contains a non-alphanumeric symbol.
+///
+fn labels(key: &str) -> Vec<usize> {
+    if key.chars().count() != 10 {
+        panic!("The row and column keys must be 10 characters long.");
+    }
+    if !alphabet::ALPHANUMERIC.is_valid(key) {
+        panic!("The row and column keys cannot contain non-alphanumeric symbols.");
+    }
+
+    let key: Vec<char> = key.to_lowercase().chars().collect();
+    keygen::sequentialise(&key)
+        .iter()
+        .map(|rank| rank % 10)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn syllabary(table: Option<Vec<String>>) -> Syllabary {
+        Syllabary::new(SyllabaryKey {
+            row_key: String::from("cryptology"),
+            column_key: String::from("dictionary"),
+            table,
+        })
+    }
+
+    #[test]
+    fn encrypt_test() {
+        assert_eq!(
+            "38 15 70 38 89 26 46 89 20",
+            syllabary(None).encrypt("Meet me at the station").unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_test() {
+        assert_eq!(
+            "meetmeatthestation",
+            syllabary(None)
+                .decrypt("38 15 70 38 89 26 46 89 20")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn longest_entry_is_used() {
+        let s = syllabary(None);
+
+        // "the" is a single entry, rather than "th" followed by "e"
+        assert_eq!(1, s.encrypt("the").unwrap().split(' ').count());
+        assert_eq!(2, s.encrypt("thx").unwrap().split(' ').count());
+    }
+
+    #[test]
+    fn with_digits() {
+        let s = syllabary(None);
+        let m = "Rendezvous at 0400";
+
+        assert_eq!(
+            "rendezvousat0400",
+            s.decrypt(&s.encrypt(m).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn user_table() {
+        let mut table: Vec<String> = (0..100).map(|i| format!("{:02}", i)).collect();
+        table[42] = String::from("hello");
+        let s = syllabary(Some(table));
+
+        assert_eq!("hello", s.decrypt(&s.encrypt("HELLO").unwrap()).unwrap());
+    }
+
+    #[test]
+    fn with_utf8() {
+        assert!(syllabary(None).encrypt("Peace 🗡️ Freedom").is_err());
+    }
+
+    #[test]
+    fn invalid_ciphertext() {
+        let s = syllabary(None);
+
+        assert!(s.decrypt("12 3").is_err());
+        assert!(s.decrypt("12 3a").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn short_key() {
+        Syllabary::new(SyllabaryKey {
+            row_key: String::from("cryptolog"),
+            column_key: String::from("dictionary"),
+            table: None,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn key_with_symbols() {
+        Syllabary::new(SyllabaryKey {
+            row_key: String::from("crypto-ogy"),
+            column_key: String::from("dictionary"),
+            table: None,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn small_table() {
+        syllabary(Some(vec![String::from("a"); 99]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn repeated_table_entries() {
+        let mut table: Vec<String> = DEFAULT_TABLE.iter().map(|e| e.to_string()).collect();
+        table[99] = String::from("A");
+        syllabary(Some(table));
+    }
+}
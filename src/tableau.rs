@@ -0,0 +1,309 @@
+//! A tableau cipher is a polyalphabetic substitution cipher defined by a table of substitution
+//! alphabets. Each row of the table is labelled with a letter, and replaces the letters `a-z` with
+//! the letters of its alphabet. The letters of a keyword, repeated as needed, select the row that
+//! is used for each letter of the message.
+//!
+//! Many classical ciphers are tableau ciphers with a particular table. For example, the Vigenère
+//! cipher uses a table of 26 rows, where the row labelled with each letter is the alphabet shifted
+//! along to begin with that letter:
+//!
+//! ```text
+//!    abcdefghijklmnopqrstuvwxyz
+//! a  abcdefghijklmnopqrstuvwxyz
+//! b  bcdefghijklmnopqrstuvwxyza
+//! c  cdefghijklmnopqrstuvwxyzab
+//! ...
+//! ```
+//!
+//! The `TableauCipher` accepts any table of between 1 and 26 rows, so that variants of these
+//! ciphers can be expressed without a dedicated implementation.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::keygen::cyclic_keystream;
+use crate::common::substitute;
+
+/// A Tableau cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableauCipher {
+    rows: Vec<Option<Vec<usize>>>,
+    keyword: String,
+}
+
+/// The key of a Tableau cipher.
+///
+/// This struct is given to the `new()` method of `TableauCipher`. See its documentation for
+/// more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableauKey {
+    /// Pairs the label of each row with its alphabet. The alphabet holds the letters that
+    /// replace `a-z`, in order.
+    pub table: Vec<(char, String)>,
+    /// Selects the row used for each letter of the message.
+    pub keyword: String,
+}
+
+impl Cipher for TableauCipher {
+    type Key = TableauKey;
+    type Algorithm = TableauCipher;
+
+    /// Initialise a Tableau cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `TableauKey`.
+    ///
+    /// The case of the labels, alphabets and keyword is ignored.
+    ///
+    /// # Panics
+    /// * The `table` does not have between 1 and 26 rows.
+    /// * A label is not alphabetic, or labels more than one row.
+    /// * An alphabet does not contain each of the letters `a-z` exactly once.
+    /// * The `keyword` is empty, or contains a letter that does not label a row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, TableauCipher, TableauKey};
+    ///
+    /// // A table of two rows: a Caesar shift of 3, and an Atbash
+    /// let table = vec![
+    ///     ('c', String::from("defghijklmnopqrstuvwxyzabc")),
+    ///     ('z', String::from("zyxwvutsrqponmlkjihgfedcba")),
+    /// ];
+    /// let t = TableauCipher::new(TableauKey { table, keyword: String::from("zcc") });
+    /// ```
+    ///
+    fn new(key: TableauKey) -> TableauCipher {
+        let TableauKey { table, keyword } = key;
+        if table.is_empty() || table.len() > 26 {
+            panic!("The table must have between 1 and 26 rows.");
+        }
+
+        let mut rows = vec![None; 26];
+        for (label, row) in table {
+            let label = alphabet::STANDARD
+                .find_position(label)
+                .expect("The label of a row must be alphabetic.");
+            if rows[label].is_some() {
+                panic!("A label cannot be used for more than one row.");
+            }
+
+            let substitution: Vec<usize> = row
+                .chars()
+                .map(|c| {
+                    alphabet::STANDARD
+                        .find_position(c)
+                        .expect("A row contains a non-alphabetic symbol.")
+                })
+                .collect();
+            if substitution.len() != 26 || (0..26).any(|i| !substitution.contains(&i)) {
+                panic!("Each row must contain each of the letters a-z exactly once.");
+            }
+            rows[label] = Some(substitution);
+        }
+
+        if keyword.is_empty() {
+            panic!("The keyword is empty.");
+        }
+        if !keyword.chars().all(|c| {
+            alphabet::STANDARD
+                .find_position(c)
+                .is_some_and(|k| rows[k].is_some())
+        }) {
+            panic!("Each letter of the keyword must label a row of the table.");
+        }
+
+        TableauCipher { rows, keyword }
+    }
+
+    /// Encrypt a message using a Tableau cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, TableauCipher, TableauKey};
+    ///
+    /// let table = vec![
+    ///     ('c', String::from("defghijklmnopqrstuvwxyzabc")),
+    ///     ('z', String::from("zyxwvutsrqponmlkjihgfedcba")),
+    /// ];
+    /// let t = TableauCipher::new(TableauKey { table, keyword: String::from("zcc") });
+    ///
+    /// assert_eq!("Zwwzfn zw gzzq!", t.encrypt("Attack at dawn!").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        Ok(substitute::key_substitution(
+            message,
+            cyclic_keystream(&self.keyword, message),
+            |ti, ki| self.row(ki)[ti],
+        ))
+    }
+
+    /// Decrypt a message using a Tableau cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, TableauCipher, TableauKey};
+    ///
+    /// let table = vec![
+    ///     ('c', String::from("defghijklmnopqrstuvwxyzabc")),
+    ///     ('z', String::from("zyxwvutsrqponmlkjihgfedcba")),
+    /// ];
+    /// let t = TableauCipher::new(TableauKey { table, keyword: String::from("zcc") });
+    ///
+    /// assert_eq!("Attack at dawn!", t.decrypt("Zwwzfn zw gzzq!").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        Ok(substitute::key_substitution(
+            ciphertext,
+            cyclic_keystream(&self.keyword, ciphertext),
+            |ti, ki| self.row(ki).iter().position(|&s| s == ti).unwrap(),
+        ))
+    }
+}
+
+impl TableauCipher {
+    /// The alphabet of the row labelled with the letter at index `ki`.
+    ///
+    fn row(&self, ki: usize) -> &[usize] {
+        self.rows[ki].as_ref().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Beaufort, Vigenere};
+
+    // Build a table of 26 rows, where `cell(row, column)` gives the index of each letter
+    fn table<F: Fn(usize, usize) -> usize>(cell: F) -> Vec<(char, String)> {
+        (0..26)
+            .map(|r| {
+                (
+                    alphabet::STANDARD.get_letter(r, false),
+                    (0..26)
+                        .map(|c| alphabet::STANDARD.get_letter(cell(r, c), false))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn encrypt_test() {
+        let t = TableauCipher::new(TableauKey {
+            table: vec![
+                ('a', String::from("qwertyuiopasdfghjklzxcvbnm")),
+                ('b', String::from("mnbvcxzlkjhgfdsapoiuytrewq")),
+            ],
+            keyword: String::from("abba"),
+        });
+        assert_eq!("quuqeh mz rmrf", t.encrypt("attack at dawn").unwrap());
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let t = TableauCipher::new(TableauKey {
+            table: vec![
+                ('a', String::from("qwertyuiopasdfghjklzxcvbnm")),
+                ('b', String::from("mnbvcxzlkjhgfdsapoiuytrewq")),
+            ],
+            keyword: String::from("abba"),
+        });
+        assert_eq!("attack at dawn", t.decrypt("quuqeh mz rmrf").unwrap());
+    }
+
+    #[test]
+    fn vigenere_table() {
+        let t = TableauCipher::new(TableauKey {
+            table: table(|r, c| (r + c) % 26),
+            keyword: String::from("lemon"),
+        });
+        let m = "Attack at dawn!";
+
+        assert_eq!(
+            Vigenere::new(String::from("lemon")).encrypt(m).unwrap(),
+            t.encrypt(m).unwrap()
+        );
+    }
+
+    #[test]
+    fn beaufort_table() {
+        let t = TableauCipher::new(TableauKey {
+            table: table(|r, c| (26 + r - c) % 26),
+            keyword: String::from("fortify"),
+        });
+        let m = "Defend the east wall of the castle";
+
+        assert_eq!(
+            Beaufort::new(String::from("fortify")).encrypt(m).unwrap(),
+            t.encrypt(m).unwrap()
+        );
+    }
+
+    #[test]
+    fn with_utf8() {
+        let t = TableauCipher::new(TableauKey {
+            table: table(|r, c| (r * 3 + c) % 26),
+            keyword: String::from("utf"),
+        });
+        let m = "Peace 🗡️ Freedom and Liberty!";
+
+        assert_eq!(m, t.decrypt(&t.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_table() {
+        TableauCipher::new(TableauKey {
+            table: Vec::new(),
+            keyword: String::from("a"),
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn short_row() {
+        TableauCipher::new(TableauKey {
+            table: vec![('a', String::from("bcdefghijklmnopqrstuvwxyz"))],
+            keyword: String::from("a"),
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_invertible_row() {
+        TableauCipher::new(TableauKey {
+            table: vec![('a', String::from("abcdefghijklmnopqrstuvwxya"))],
+            keyword: String::from("a"),
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn repeated_label() {
+        TableauCipher::new(TableauKey {
+            table: vec![
+                ('a', String::from("abcdefghijklmnopqrstuvwxyz")),
+                ('A', String::from("zyxwvutsrqponmlkjihgfedcba")),
+            ],
+            keyword: String::from("a"),
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn keyword_without_row() {
+        TableauCipher::new(TableauKey {
+            table: vec![('a', String::from("abcdefghijklmnopqrstuvwxyz"))],
+            keyword: String::from("ab"),
+        });
+    }
+}
@@ -0,0 +1,282 @@
+//! The tap code (or knock code) is a method of communicating letters with a series of taps. It
+//! was famously used by prisoners of war during the Vietnam War to communicate between cells.
+//!
+//! The letters are laid out in a 5x5 grid, with 'C' and 'K' sharing a cell:
+//!
+//! ```text
+//!      1  2  3  4  5
+//!   1  A  B  C  D  E
+//!   2  F  G  H  I  J
+//!   3  L  M  N  O  P
+//!   4  Q  R  S  T  U
+//!   5  V  W  X  Y  Z
+//! ```
+//!
+//! Each letter is transmitted as a group of taps for its row, followed by a group of taps for
+//! its column. For example, 'B' is tapped as `. ..` and 'K' as `. ...`. There is no way to tap a
+//! space, so words are run together.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
+
+// The letters of the grid, read row by row
+const GRID: &str = "ABCDEFGHIJLMNOPQRSTUVWXYZ";
+
+/// A Tap Code cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TapCode {
+    tap: char,
+    separator: char,
+}
+
+/// The key of a Tap Code cipher.
+///
+/// This struct is given to the `new()` method of `TapCode`. See its documentation for more.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TapCodeKey {
+    /// The character that represents a single tap, such as `.`.
+    pub tap: char,
+    /// The character placed between each group of taps, such as a space.
+    pub separator: char,
+}
+
+impl Cipher for TapCode {
+    type Key = TapCodeKey;
+    type Algorithm = TapCode;
+
+    /// Initialise a Tap Code cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `TapCodeKey`.
+    ///
+    /// # Panics
+    /// * The `tap` is whitespace.
+    /// * The `tap` and `separator` are the same character.
+    ///
+    fn new(key: TapCodeKey) -> TapCode {
+        if key.tap.is_whitespace() {
+            panic!("The tap must not be whitespace.");
+        }
+        if key.tap == key.separator {
+            panic!("The tap and separator must be different characters.");
+        }
+
+        TapCode {
+            tap: key.tap,
+            separator: key.separator,
+        }
+    }
+
+    /// Encrypt a message using a Tap Code cipher.
+    ///
+    /// Whitespace within the message is ignored, as is the case of each letter. As 'C' and 'K'
+    /// share a cell, a 'K' will be decrypted as a 'C'.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, TapCode, TapCodeKey};
+    ///
+    /// let t = TapCode::new(TapCodeKey { tap: '.', separator: ' ' });
+    /// assert_eq!("... . . ..... ..... ...", t.encrypt("Lex").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.validate_message(message)?;
+
+        let groups: Vec<String> = message
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .flat_map(|c| {
+                let pos = match c.to_ascii_uppercase() {
+                    'K' => GRID.find('C').unwrap(),
+                    upper => GRID.find(upper).unwrap(),
+                };
+
+                vec![self.taps(pos / 5 + 1), self.taps(pos % 5 + 1)]
+            })
+            .collect();
+
+        Ok(groups.join(&self.separator.to_string()))
+    }
+
+    /// Decrypt a message using a Tap Code cipher.
+    ///
+    /// Groups of taps may be separated by any number of `separator` or whitespace characters.
+    /// The plaintext is uppercase.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, TapCode, TapCodeKey};
+    ///
+    /// let t = TapCode::new(TapCodeKey { tap: '.', separator: ' ' });
+    /// assert_eq!("LEX", t.decrypt("...  .\n. .....  ..... ...").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let mut counts = Vec::new();
+        for group in ciphertext
+            .split(|c: char| c == self.separator || c.is_whitespace())
+            .filter(|g| !g.is_empty())
+        {
+            if !group.chars().all(|c| c == self.tap) {
+                return Err("Ciphertext contains an unknown symbol.");
+            }
+
+            match group.chars().count() {
+                n @ 1..=5 => counts.push(n),
+                _ => return Err("A group contains more than five taps."),
+            }
+        }
+
+        if counts.len() % 2 != 0 {
+            return Err("Ciphertext contains an incomplete letter.");
+        }
+
+        Ok(counts
+            .chunks(2)
+            .map(|rc| GRID.chars().nth((rc[0] - 1) * 5 + (rc[1] - 1)).unwrap())
+            .collect())
+    }
+
+    /// Check that a message can be encrypted with a Tap Code cipher.
+    ///
+    /// Returns `Err` if the message contains a symbol that is not alphabetic or whitespace.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, TapCode, TapCodeKey};
+    ///
+    /// let t = TapCode::new(TapCodeKey { tap: '.', separator: ' ' });
+    ///
+    /// assert!(t.validate_message("Hello there").is_ok());
+    /// assert!(t.validate_message("Hello there!").is_err());
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        CipherError::find(
+            message,
+            "Message contains a symbol that is not alphabetic or whitespace.",
+            |c| !c.is_whitespace() && alphabet::STANDARD.find_position(c).is_none(),
+        )
+    }
+}
+
+impl TapCode {
+    /// A group of `n` taps.
+    ///
+    fn taps(&self, n: usize) -> String {
+        self.tap.to_string().repeat(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_test() {
+        let t = TapCode::new(TapCodeKey {
+            tap: '.',
+            separator: ' ',
+        });
+        assert_eq!(
+            "..... .. . . .... .... . ..... .... ..",
+            t.encrypt("water").unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let t = TapCode::new(TapCodeKey {
+            tap: '.',
+            separator: ' ',
+        });
+        assert_eq!(
+            "WATER",
+            t.decrypt("..... .. . . .... .... . ..... .... ..").unwrap()
+        );
+    }
+
+    #[test]
+    fn c_and_k_share_a_cell() {
+        let t = TapCode::new(TapCodeKey {
+            tap: '.',
+            separator: ' ',
+        });
+
+        assert_eq!(t.encrypt("c").unwrap(), t.encrypt("k").unwrap());
+        assert_eq!("CNOCC", t.decrypt(&t.encrypt("Knock").unwrap()).unwrap());
+    }
+
+    #[test]
+    fn whitespace_is_ignored() {
+        let t = TapCode::new(TapCodeKey {
+            tap: '.',
+            separator: ' ',
+        });
+        assert_eq!(
+            "HITHERE",
+            t.decrypt(&t.encrypt("Hi there").unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn custom_symbols() {
+        let t = TapCode::new(TapCodeKey {
+            tap: 'x',
+            separator: '/',
+        });
+
+        assert_eq!("x/xx/xxx/x/x/xxxx", t.encrypt("bld").unwrap());
+        assert_eq!("BLD", t.decrypt("x/xx / xxx/x\n/x/xxxx/").unwrap());
+    }
+
+    #[test]
+    fn invalid_ciphertext() {
+        let t = TapCode::new(TapCodeKey {
+            tap: '.',
+            separator: ' ',
+        });
+
+        assert!(t.decrypt(". .. .").is_err());
+        assert!(t.decrypt("...... .").is_err());
+        assert!(t.decrypt(". -").is_err());
+    }
+
+    #[test]
+    fn invalid_message() {
+        assert!(TapCode::new(TapCodeKey {
+            tap: '.',
+            separator: ' '
+        })
+        .encrypt("Hello 2")
+        .is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn same_tap_and_separator() {
+        TapCode::new(TapCodeKey {
+            tap: '.',
+            separator: '.',
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn whitespace_tap() {
+        TapCode::new(TapCodeKey {
+            tap: ' ',
+            separator: '/',
+        });
+    }
+}
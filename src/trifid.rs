@@ -0,0 +1,235 @@
+//! The Trifid cipher was invented by Félix Delastelle in 1902. It extends the ideas of the Bifid
+//! cipher into a third dimension, using a 3x3x3 cube of 27 characters instead of a square.
+//!
+//! Each letter of the message is converted into its layer, row and column coordinates within the
+//! cube. For every group of `period` letters, the layer coordinates are written out, followed by
+//! the rows and then the columns. This sequence is then read in triples, with each triple being
+//! converted back into a character of the cube.
+//!
+//! As the cube contains a 27th symbol (`+` in this implementation) which has no case, the
+//! ciphertext and decrypted plaintext are always uppercase.
+//!
+use crate::common::cipher::Cipher;
+use crate::common::keygen;
+
+// The 27th symbol of the cube
+const FILL_CHAR: char = '+';
+
+type Coordinate = (usize, usize, usize);
+
+/// A Trifid cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trifid {
+    cube: [[[char; 3]; 3]; 3],
+    period: usize,
+}
+
+/// The key of a Trifid cipher.
+///
+/// This struct is given to the `new()` method of `Trifid`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrifidKey {
+    /// The phrase used to fill the cube. It may contain alphabetic characters and the `+`
+    /// symbol.
+    pub phrase: String,
+    /// The number of characters that are fractionated together.
+    pub period: usize,
+}
+
+impl Cipher for Trifid {
+    type Key = TrifidKey;
+    type Algorithm = Trifid;
+
+    /// Initialise a Trifid cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `TrifidKey`.
+    ///
+    /// # Panics
+    /// * The `phrase` contains a symbol that is not alphabetic or `+`.
+    /// * The `period` is 0.
+    ///
+    fn new(key: TrifidKey) -> Trifid {
+        if key.period == 0 {
+            panic!("The period must be greater than 0.");
+        }
+
+        Trifid {
+            cube: keygen::trifid_cube(&key.phrase, FILL_CHAR),
+            period: key.period,
+        }
+    }
+
+    /// Encrypt a message using a Trifid cipher.
+    ///
+    /// Characters that are not part of the cube are left in place.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Trifid, TrifidKey};
+    ///
+    /// let t = Trifid::new(TrifidKey { phrase: String::from("FELIXMARIEDELASTELLE"), period: 5 });
+    /// assert_eq!("FMJFVOISSUFTFPUFEQQC", t.encrypt("aidetoilecieltaidera").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        Ok(self.transform(message, |coords| {
+            // Write out the layers, rows and then columns, reading them off in triples
+            let seq: Vec<usize> = coords
+                .iter()
+                .map(|c| c.0)
+                .chain(coords.iter().map(|c| c.1))
+                .chain(coords.iter().map(|c| c.2))
+                .collect();
+
+            seq.chunks(3).map(|t| (t[0], t[1], t[2])).collect()
+        }))
+    }
+
+    /// Decrypt a message using a Trifid cipher.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Trifid, TrifidKey};
+    ///
+    /// let t = Trifid::new(TrifidKey { phrase: String::from("FELIXMARIEDELASTELLE"), period: 5 });
+    /// assert_eq!("AIDETOILECIELTAIDERA", t.decrypt("FMJFVOISSUFTFPUFEQQC").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        Ok(self.transform(ciphertext, |coords| {
+            // The coordinate sequence is split into thirds: layers, rows and columns
+            let seq: Vec<usize> = coords.iter().flat_map(|c| vec![c.0, c.1, c.2]).collect();
+            let n = coords.len();
+
+            (0..n)
+                .map(|i| (seq[i], seq[n + i], seq[2 * n + i]))
+                .collect()
+        }))
+    }
+
+    /// Whether a character is foreign to the cipher, being neither a letter nor the `+` symbol.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        !(c.is_ascii_alphabetic() || c == '+')
+    }
+}
+
+impl Trifid {
+    /// The number of characters that are fractionated together.
+    ///
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Find the (layer, row, column) coordinates of a character within the cube.
+    ///
+    fn coordinates(&self, c: char) -> Option<Coordinate> {
+        let c = c.to_ascii_uppercase();
+        for (l, layer) in self.cube.iter().enumerate() {
+            for (r, row) in layer.iter().enumerate() {
+                if let Some(col) = row.iter().position(|&x| x == c) {
+                    return Some((l, r, col));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Fractionate every character of the text that is part of the cube. For each block of
+    /// `period` coordinates, the closure `fractionate` returns the transformed coordinates.
+    /// The transformed characters are then placed back in the same positions.
+    ///
+    fn transform<F>(&self, text: &str, fractionate: F) -> String
+    where
+        F: Fn(&[Coordinate]) -> Vec<Coordinate>,
+    {
+        let coords: Vec<Coordinate> = text.chars().filter_map(|c| self.coordinates(c)).collect();
+        let mut transformed = coords
+            .chunks(self.period)
+            .flat_map(fractionate)
+            .map(|(l, r, c)| self.cube[l][r][c]);
+
+        text.chars()
+            .map(|c| match self.coordinates(c) {
+                Some(_) => transformed.next().expect("Transformed text is too short."),
+                None => c,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_message() {
+        let t = Trifid::new(TrifidKey {
+            phrase: String::from("FELIXMARIEDELASTELLE"),
+            period: 5,
+        });
+        assert_eq!(
+            "FMJFVOISSUFTFPUFEQQC",
+            t.encrypt("AIDETOILECIELTAIDERA").unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_message() {
+        let t = Trifid::new(TrifidKey {
+            phrase: String::from("FELIXMARIEDELASTELLE"),
+            period: 5,
+        });
+        assert_eq!(
+            "AIDETOILECIELTAIDERA",
+            t.decrypt("FMJFVOISSUFTFPUFEQQC").unwrap()
+        );
+    }
+
+    #[test]
+    fn with_symbols() {
+        let m = "Attack 🗡️ the east wall, 1+1!";
+        let t = Trifid::new(TrifidKey {
+            phrase: String::from("utfeight"),
+            period: 7,
+        });
+
+        assert_eq!(m.to_uppercase(), t.decrypt(&t.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn uneven_period() {
+        let m = "DEFENDTHEEASTWALLOFTHECASTLE";
+        let t = Trifid::new(TrifidKey {
+            phrase: String::from("fortification"),
+            period: 9,
+        });
+
+        assert_eq!(m, t.decrypt(&t.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_period() {
+        Trifid::new(TrifidKey {
+            phrase: String::from("key"),
+            period: 0,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_phrase() {
+        Trifid::new(TrifidKey {
+            phrase: String::from("F@IL"),
+            period: 5,
+        });
+    }
+}
@@ -0,0 +1,422 @@
+//! The Uesugi cipher is a Japanese checkerboard cipher attributed to the Uesugi clan of the 16th
+//! century, and described in the military treatise _Usami Ōkami Gunpō_.
+//!
+//! The 48 kana of the syllabary are written in the order of the _iroha_ poem into a square of 7
+//! rows and 7 columns, leaving the final cell empty:
+//!
+//! ```text
+//!    1  2  3  4  5  6  7
+//! 1  い ろ は に ほ へ と
+//! 2  ち り ぬ る を わ か
+//! 3  よ た れ そ つ ね な
+//! 4  ら む う ゐ の お く
+//! 5  や ま け ふ こ え て
+//! 6  あ さ き ゆ め み し
+//! 7  ゑ ひ も せ す ん
+//! ```
+//!
+//! Each kana of the message is replaced by the labels of its row and column. The labels of the
+//! rows and columns can be scrambled to form a key.
+//!
+//! For those without kana input, the cipher can be used in a romanized mode. The message is
+//! written in Hepburn romanization (e.g. `sakura`), and is broken into the longest syllables
+//! of the square that match it. As the square has no voiced kana, these must be written in their
+//! unvoiced form (e.g. `ka` rather than `ga`).
+//!
+use crate::common::cipher::Cipher;
+
+/// The 48 kana of the square in the order of the _iroha_ poem, paired with their romanization.
+pub const IROHA: [(char, &str); 48] = [
+    ('い', "i"),
+    ('ろ', "ro"),
+    ('は', "ha"),
+    ('に', "ni"),
+    ('ほ', "ho"),
+    ('へ', "he"),
+    ('と', "to"),
+    ('ち', "chi"),
+    ('り', "ri"),
+    ('ぬ', "nu"),
+    ('る', "ru"),
+    ('を', "wo"),
+    ('わ', "wa"),
+    ('か', "ka"),
+    ('よ', "yo"),
+    ('た', "ta"),
+    ('れ', "re"),
+    ('そ', "so"),
+    ('つ', "tsu"),
+    ('ね', "ne"),
+    ('な', "na"),
+    ('ら', "ra"),
+    ('む', "mu"),
+    ('う', "u"),
+    ('ゐ', "wi"),
+    ('の', "no"),
+    ('お', "o"),
+    ('く', "ku"),
+    ('や', "ya"),
+    ('ま', "ma"),
+    ('け', "ke"),
+    ('ふ', "fu"),
+    ('こ', "ko"),
+    ('え', "e"),
+    ('て', "te"),
+    ('あ', "a"),
+    ('さ', "sa"),
+    ('き', "ki"),
+    ('ゆ', "yu"),
+    ('め', "me"),
+    ('み', "mi"),
+    ('し', "shi"),
+    ('ゑ', "we"),
+    ('ひ', "hi"),
+    ('も', "mo"),
+    ('せ', "se"),
+    ('す', "su"),
+    ('ん', "n"),
+];
+
+const SIZE: usize = 7;
+
+/// The scripts that the messages of the Uesugi cipher can be written in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Script {
+    /// The message is written in hiragana.
+    Kana,
+    /// The message is written in Hepburn romanization.
+    Romanized,
+}
+
+/// An Uesugi cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Uesugi {
+    rows: Vec<usize>,
+    columns: Vec<usize>,
+    script: Script,
+}
+
+/// The key of an Uesugi cipher.
+///
+/// This struct is given to the `new()` method of `Uesugi`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UesugiKey {
+    /// The numbers 1 to 7 in the order they label the rows.
+    pub row_labels: Vec<usize>,
+    /// The numbers 1 to 7 in the order they label the columns.
+    pub column_labels: Vec<usize>,
+    /// The script of the message.
+    pub script: Script,
+}
+
+impl Cipher for Uesugi {
+    type Key = UesugiKey;
+    type Algorithm = Uesugi;
+
+    /// Initialise an Uesugi cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `UesugiKey`.
+    ///
+    /// # Panics
+    /// * The `row_labels` or `column_labels` are not a permutation of the numbers 1 to 7.
+    ///
+    fn new(key: UesugiKey) -> Uesugi {
+        let UesugiKey {
+            row_labels: rows,
+            column_labels: columns,
+            script,
+        } = key;
+        for labels in [&rows, &columns].iter() {
+            if labels.len() != SIZE || (1..=SIZE).any(|n| !labels.contains(&n)) {
+                panic!("The labels must contain each of the numbers 1 to 7 exactly once.");
+            }
+        }
+
+        Uesugi {
+            rows,
+            columns,
+            script,
+        }
+    }
+
+    /// Encrypt a message using an Uesugi cipher.
+    ///
+    /// Each kana is replaced by the labels of its row and column, and the pairs of labels are
+    /// separated by spaces. Whitespace within the message is not preserved.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::uesugi::Script;
+    /// use cipher_crypt::{Cipher, Uesugi, UesugiKey};
+    ///
+    /// let labels = vec![1, 2, 3, 4, 5, 6, 7];
+    /// let k = Uesugi::new(UesugiKey {
+    ///     row_labels: labels.clone(),
+    ///     column_labels: labels.clone(),
+    ///     script: Script::Kana,
+    /// });
+    /// let r = Uesugi::new(UesugiKey {
+    ///     row_labels: labels.clone(),
+    ///     column_labels: labels,
+    ///     script: Script::Romanized,
+    /// });
+    ///
+    /// assert_eq!("62 47 41", k.encrypt("さくら").unwrap());
+    /// assert_eq!("62 47 41", r.encrypt("sakura").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        let kana = match self.script {
+            Script::Kana => message
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .map(|c| {
+                    IROHA
+                        .iter()
+                        .position(|&(k, _)| k == c)
+                        .ok_or("Message contains a symbol that is not in the square.")
+                })
+                .collect::<Result<Vec<usize>, &'static str>>()?,
+            Script::Romanized => romanized_kana(message)?,
+        };
+
+        Ok(kana
+            .into_iter()
+            .map(|i| format!("{}{}", self.rows[i / SIZE], self.columns[i % SIZE]))
+            .collect::<Vec<String>>()
+            .join(" "))
+    }
+
+    /// Decrypt a message using an Uesugi cipher.
+    ///
+    /// Whitespace within the ciphertext is ignored. In the romanized script, `ん` is written as
+    /// `n'` where it is followed by a vowel or `y`, so that the plaintext can be read unambiguously.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::uesugi::Script;
+    /// use cipher_crypt::{Cipher, Uesugi, UesugiKey};
+    ///
+    /// let labels = vec![1, 2, 3, 4, 5, 6, 7];
+    /// let k = Uesugi::new(UesugiKey {
+    ///     row_labels: labels.clone(),
+    ///     column_labels: labels.clone(),
+    ///     script: Script::Kana,
+    /// });
+    /// let r = Uesugi::new(UesugiKey {
+    ///     row_labels: labels.clone(),
+    ///     column_labels: labels,
+    ///     script: Script::Romanized,
+    /// });
+    ///
+    /// assert_eq!("さくら", k.decrypt("62 47 41").unwrap());
+    /// assert_eq!("sakura", r.decrypt("62 47 41").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let labels: Vec<usize> = ciphertext
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| {
+                c.to_digit(10)
+                    .map(|d| d as usize)
+                    .ok_or("Ciphertext contains a symbol that is not a digit.")
+            })
+            .collect::<Result<Vec<usize>, &'static str>>()?;
+        if !labels.len().is_multiple_of(2) {
+            return Err("Ciphertext contains an incomplete pair of labels.");
+        }
+
+        let kana = labels
+            .chunks(2)
+            .map(|pair| {
+                let row = self.rows.iter().position(|&r| r == pair[0]);
+                let column = self.columns.iter().position(|&c| c == pair[1]);
+                match (row, column) {
+                    (Some(r), Some(c)) if r * SIZE + c < IROHA.len() => Ok(r * SIZE + c),
+                    _ => Err("Ciphertext refers to a cell that is not in the square."),
+                }
+            })
+            .collect::<Result<Vec<usize>, &'static str>>()?;
+
+        Ok(match self.script {
+            Script::Kana => kana.into_iter().map(|i| IROHA[i].0).collect(),
+            Script::Romanized => kana
+                .iter()
+                .enumerate()
+                .map(|(n, &i)| {
+                    let next = kana.get(n + 1).map(|&k| IROHA[k].1);
+                    match (IROHA[i].1, next) {
+                        ("n", Some(s)) if s.starts_with(|c| "aeiouy".contains(c)) => "n'",
+                        (syllable, _) => syllable,
+                    }
+                })
+                .collect(),
+        })
+    }
+
+    /// Whether a character is foreign to the cipher. In the kana script these are the characters
+    /// that are not kana of the square, and in the romanized script those that are neither
+    /// letters, whitespace nor apostrophes, as these separate the syllables.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        match self.script {
+            Script::Kana => !IROHA.iter().any(|&(k, _)| k == c),
+            Script::Romanized => !(c.is_ascii_alphabetic() || c.is_whitespace() || c == '\''),
+        }
+    }
+}
+
+/// Break a romanized `message` into the kana of the square, returning the index of each within
+/// the `IROHA`.
+///
+/// Each word of the message is broken into the longest syllables that match it, and apostrophes
+/// are treated as the end of a word.
+///
+fn romanized_kana(message: &str) -> Result<Vec<usize>, &'static str> {
+    let mut kana = Vec::new();
+    for word in message
+        .to_ascii_lowercase()
+        .split(|c: char| c.is_whitespace() || c == '\'')
+    {
+        let mut rest = word;
+        while !rest.is_empty() {
+            let (i, syllable) = IROHA
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, s))| rest.starts_with(s))
+                .max_by_key(|(_, (_, s))| s.len())
+                .map(|(i, (_, s))| (i, s))
+                .ok_or("Message contains a syllable that is not in the square.")?;
+
+            kana.push(i);
+            rest = &rest[syllable.len()..];
+        }
+    }
+
+    Ok(kana)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::policy::ForeignCharPolicy;
+
+    fn uesugi(script: Script) -> Uesugi {
+        Uesugi::new(UesugiKey {
+            row_labels: vec![3, 1, 4, 7, 5, 2, 6],
+            column_labels: vec![2, 7, 1, 5, 6, 4, 3],
+            script,
+        })
+    }
+
+    #[test]
+    fn encrypt_test() {
+        assert_eq!(
+            "56 64 52 76 74 46 21 24",
+            uesugi(Script::Kana).encrypt("こんやのおつきみ").unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_test() {
+        assert_eq!(
+            "こんやのおつきみ",
+            uesugi(Script::Kana)
+                .decrypt("56 64 52 76 74 46 21 24")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn romanized_round_trip() {
+        let r = uesugi(Script::Romanized);
+        let m = "kon'ya no otsukimi";
+
+        assert_eq!(
+            uesugi(Script::Kana).encrypt("こんやのおつきみ").unwrap(),
+            r.encrypt(m).unwrap()
+        );
+        assert_eq!(
+            "kon'yanootsukimi",
+            r.decrypt(&r.encrypt(m).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn romanized_longest_syllable() {
+        let r = uesugi(Script::Romanized);
+        let k = uesugi(Script::Kana);
+
+        assert_eq!(
+            k.encrypt("ちつし").unwrap(),
+            r.encrypt("chitsushi").unwrap()
+        );
+        assert_eq!(k.encrypt("んな").unwrap(), r.encrypt("n'na").unwrap());
+    }
+
+    #[test]
+    fn every_kana() {
+        let k = uesugi(Script::Kana);
+        let m: String = IROHA.iter().map(|&(c, _)| c).collect();
+
+        assert_eq!(m, k.decrypt(&k.encrypt(&m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn empty_cell() {
+        // The final cell of the square is labelled with row 6 and column 3
+        assert!(uesugi(Script::Kana).decrypt("63").is_err());
+    }
+
+    #[test]
+    fn invalid_ciphertext() {
+        let k = uesugi(Script::Kana);
+
+        assert!(k.decrypt("12 3").is_err());
+        assert!(k.decrypt("18").is_err());
+        assert!(k.decrypt("1a").is_err());
+    }
+
+    #[test]
+    fn voiced_syllable() {
+        assert!(uesugi(Script::Romanized).encrypt("ga").is_err());
+        assert!(uesugi(Script::Kana).encrypt("が").is_err());
+    }
+
+    #[test]
+    fn with_utf8() {
+        assert!(uesugi(Script::Kana).encrypt("こんにちは 🗡️").is_err());
+    }
+
+    #[test]
+    fn strip_keeps_kana() {
+        let k = uesugi(Script::Kana);
+        assert_eq!(
+            k.encrypt("こんやのおつきみ"),
+            k.encrypt_with_policy("「こんや、のおつきみ」", ForeignCharPolicy::Strip)
+        );
+
+        let r = uesugi(Script::Romanized);
+        assert_eq!(
+            r.encrypt("Kon'ya no otsukimi"),
+            r.encrypt_with_policy("Kon'ya no otsukimi!", ForeignCharPolicy::Strip)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_labels() {
+        Uesugi::new(UesugiKey {
+            row_labels: vec![1, 2, 3, 4, 5, 6, 6],
+            column_labels: vec![1, 2, 3, 4, 5, 6, 7],
+            script: Script::Kana,
+        });
+    }
+}
@@ -0,0 +1,235 @@
+//! Known-answer vectors for the ciphers of the `registry`, which check that a build of this crate
+//! (with whichever features it was compiled with) encrypts and decrypts as it should.
+//!
+//! Where a cipher has a well known worked example (such as the Vigenère cipher's `attackatdawn`
+//! under the key `lemon`), that example is used as its vector. Not every cipher has vectors; a
+//! cipher that makes random choices when it encrypts cannot be given a fixed ciphertext, and a
+//! cipher without vectors passes its `Cipher::self_test()` with `Ok(0)`.
+//!
+//! # Example
+//!
+//! ```
+//! use cipher_crypt::{vectors, Cipher, Playfair};
+//!
+//! assert!(vectors::self_test().is_ok());
+//! assert_eq!(Ok(1), Playfair::self_test());
+//! ```
+//!
+use crate::registry;
+
+/// A message, and the ciphertext that a cipher of the `registry` must encrypt it to under a key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Vector {
+    /// The name of the cipher in the `registry`.
+    pub cipher: &'static str,
+    /// The key of the cipher, in the format of its `Factory`.
+    pub key: &'static str,
+    /// The message, as it is also given back by decryption.
+    pub message: &'static str,
+    /// The ciphertext of the message.
+    pub ciphertext: &'static str,
+}
+
+impl Vector {
+    /// Check that the cipher encrypts the message to the ciphertext, and decrypts the ciphertext
+    /// back to the message.
+    ///
+    /// Returns `Err` if the cipher or its key could not be made, or either direction differs.
+    ///
+    pub fn check(&self) -> Result<(), &'static str> {
+        let cipher = registry::lookup(self.cipher)
+            .ok_or("The cipher of the vector is not in the registry.")?
+            .parse(self.key)?;
+
+        if cipher.dyn_encrypt(self.message)? != self.ciphertext {
+            return Err("The message did not encrypt to the ciphertext of the vector.");
+        }
+        if cipher.dyn_decrypt(self.ciphertext)? != self.message {
+            return Err("The ciphertext did not decrypt to the message of the vector.");
+        }
+
+        Ok(())
+    }
+}
+
+/// The known-answer vectors, in alphabetical order of the names of their ciphers.
+pub const VECTORS: [Vector; 19] = [
+    Vector {
+        cipher: "affine",
+        key: "3,7",
+        message: "Attack at dawn!",
+        ciphertext: "Hmmhnl hm qhvu!",
+    },
+    Vector {
+        cipher: "autokey",
+        key: "fort",
+        message: "Attack the east wall",
+        ciphertext: "Fhktcd mhg otzx aade",
+    },
+    Vector {
+        cipher: "beaufort",
+        key: "fortification",
+        message: "Defend the east wall",
+        ciphertext: "Ckmpvc pvw piwu jogi",
+    },
+    Vector {
+        cipher: "caesar",
+        key: "23",
+        message: "THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG",
+        ciphertext: "QEB NRFZH YOLTK CLU GRJMP LSBO QEB IXWV ALD",
+    },
+    Vector {
+        cipher: "columnar_transposition",
+        key: "zebras",
+        message: "Super-secret message!",
+        ciphertext: "respce!uemeers-taSs g",
+    },
+    Vector {
+        cipher: "fractionated_morse",
+        key: "roundtable",
+        message: "DEFENDTHEEASTWALL",
+        ciphertext: "ESOAVVBPUAQPBQJJEQUP",
+    },
+    Vector {
+        cipher: "gronsfeld",
+        key: "31415",
+        message: "Attack at dawn!",
+        ciphertext: "Duxbhn bx efzo!",
+    },
+    Vector {
+        cipher: "hill",
+        key: "GYBNQKURP",
+        message: "ACT",
+        ciphertext: "POH",
+    },
+    Vector {
+        cipher: "keyword",
+        key: "zebras",
+        message: "Flee at once. We are discovered!",
+        ciphertext: "Siaa zq lkba. Va zoa rfpbluaoar!",
+    },
+    Vector {
+        cipher: "keyword_caesar",
+        key: "zebras,4",
+        message: "Flee at once. We are discovered!",
+        ciphertext: "Edzz vm hgxz. Pz vkz yalxhozkzy!",
+    },
+    Vector {
+        cipher: "one_time_pad",
+        key: "xmckl",
+        message: "HELLO",
+        ciphertext: "EQNVZ",
+    },
+    Vector {
+        cipher: "playfair",
+        key: "playfairexample",
+        message: "HIDETHEGOLDINTHESTUMPS",
+        ciphertext: "BMODZBXDNABEKUDMKZZRFK",
+    },
+    Vector {
+        cipher: "polybius",
+        key: "or0an3ge",
+        message: "Attack at dawn!",
+        ciphertext: "ADddddadbdcd addd beadeaae!",
+    },
+    Vector {
+        cipher: "porta",
+        key: "melon",
+        message: "We ride at dawn!",
+        ciphertext: "Dt mpwx pb xtdl!",
+    },
+    Vector {
+        cipher: "railfence",
+        key: "3",
+        message: "WEAREDISCOVEREDFLEEATONCE",
+        ciphertext: "WECRLTEERDSOEEFEAOCAIVDEN",
+    },
+    Vector {
+        cipher: "scytale",
+        key: "6",
+        message: "Prepare for glory!",
+        ciphertext: "Pegr lefoporaryr !",
+    },
+    Vector {
+        cipher: "simple_substitution",
+        key: "phqgiumeaylnofdxjkrcvstzwb",
+        message: "Attack at dawn!",
+        ciphertext: "Pccpql pc gptf!",
+    },
+    Vector {
+        cipher: "vigenere",
+        key: "lemon",
+        message: "attackatdawn",
+        ciphertext: "lxfopvefrnhr",
+    },
+    Vector {
+        cipher: "wolseley",
+        key: "wolseley",
+        message: "Attack at dawn!",
+        ciphertext: "Qeeqnf qe mqzc!",
+    },
+];
+
+/// The vectors of the cipher of a `name`, which is looked up as by `registry::lookup()`.
+///
+pub fn of(name: &str) -> impl Iterator<Item = &'static Vector> {
+    let cipher = registry::lookup(name).map(|f| f.name());
+
+    VECTORS.iter().filter(move |v| Some(v.cipher) == cipher)
+}
+
+/// Check every known-answer vector, returning the number that passed.
+///
+/// Returns `Err` with the first vector that failed.
+///
+pub fn self_test() -> Result<usize, &'static Vector> {
+    check_all(VECTORS.iter())
+}
+
+/// Check each of the `vectors`, returning the number that passed or the first that failed.
+///
+pub(crate) fn check_all<I>(vectors: I) -> Result<usize, &'static Vector>
+where
+    I: Iterator<Item = &'static Vector>,
+{
+    let mut passed = 0;
+    for vector in vectors {
+        vector.check().map_err(|_| vector)?;
+        passed += 1;
+    }
+
+    Ok(passed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bifid, Cipher, CipherKind, ColumnarTransposition, Vigenere};
+
+    #[test]
+    fn every_vector_passes() {
+        assert_eq!(Ok(VECTORS.len()), self_test());
+    }
+
+    #[test]
+    fn wrong_ciphertext() {
+        let vector = Vector {
+            ciphertext: "QEB NRFZH YOLTK CLU GRJMP LSBO QEB IXWV ALE",
+            ..VECTORS[3]
+        };
+        assert!(vector.check().is_err());
+    }
+
+    #[test]
+    fn cipher_self_test() {
+        assert_eq!(Ok(1), Vigenere::self_test());
+        assert_eq!(Ok(1), ColumnarTransposition::self_test());
+    }
+
+    #[test]
+    fn cipher_without_vectors() {
+        assert_eq!(0, of("enigma").count());
+        assert_eq!(Ok(0), Bifid::self_test());
+        assert_eq!(Ok(0), CipherKind::self_test());
+    }
+}
@@ -0,0 +1,669 @@
+//! The VIC cipher is a pencil and paper cipher used by the Soviet spy Reino Häyhänen in the
+//! 1950s. It is regarded as one of the most complex hand ciphers ever used in the field, and
+//! was only broken after Häyhänen defected in 1957.
+//!
+//! The cipher is made up of three components:
+//!
+//! * A key schedule that uses chain addition and sequentialisation to expand the key material
+//!   (a phrase, a date, a personal number and a random message keygroup) into the keys for the
+//!   other two components.
+//! * A straddling checkerboard that converts the message into a stream of digits.
+//! * A double transposition of those digits. The first is a regular columnar transposition,
+//!   while the second is disrupted by triangular areas that are filled last.
+//!
+//! Finally, the keygroup is inserted into the ciphertext at a position determined by the last
+//! digit of the date, so that the recipient is able to recover it.
+//!
+//! The checkerboard used in this implementation places the letters `ET AON RIS` in its top row,
+//! with the remaining letters, a full stop `.` and a number shift `/` in its lower rows. Digits
+//! in the message are encoded as the number shift followed by the digit itself.
+//!
+use crate::common::checkerboard::StraddlingCheckerboard;
+use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
+use crate::common::keygen::{chain_addition, sequentialise};
+
+// The layout of the straddling checkerboard, blank cells are marked with spaces
+const CHECKERBOARD_TOP: &str = "ET AON RIS";
+const CHECKERBOARD_ROWS: &str = "BCDFGHJKLMPQUVWXYZ./";
+
+// The number of digits in a ciphertext group (and the keygroup)
+const GROUP_SIZE: usize = 5;
+
+/// The keys derived from the key schedule for a single message.
+#[derive(Clone, Debug, PartialEq)]
+struct Schedule {
+    first_transposition: Vec<u8>,
+    second_transposition: Vec<u8>,
+    checkerboard: StraddlingCheckerboard,
+}
+
+/// A VIC cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VIC {
+    phrase: Vec<char>,
+    date: Vec<u8>,
+    personal_number: u8,
+    keygroup: Vec<u8>,
+}
+
+/// The key of a VIC cipher.
+///
+/// This struct is given to the `new()` method of `VIC`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VICKey {
+    /// The phrase whose first 20 letters are used. Any other symbols are ignored.
+    pub phrase: String,
+    /// Six digits, such as `030945` for the 3rd of September 1945.
+    pub date: String,
+    /// A number between 1 and 16 that was assigned to the agent.
+    pub personal_number: usize,
+    /// Five random digits that should be chosen anew for each message.
+    pub keygroup: String,
+}
+
+impl Cipher for VIC {
+    type Key = VICKey;
+    type Algorithm = VIC;
+
+    /// Initialise a VIC cipher.
+    ///
+    /// The parts of the `key` are described by the fields of `VICKey`.
+    ///
+    /// # Panics
+    /// * The `phrase` contains less than 20 letters.
+    /// * The `date` is not six digits.
+    /// * The `personal_number` is not within the range 1 to 16.
+    /// * The `keygroup` is not five digits.
+    ///
+    fn new(key: VICKey) -> VIC {
+        let phrase: Vec<char> = key
+            .phrase
+            .chars()
+            .filter(|c| c.is_ascii_alphabetic())
+            .map(|c| c.to_ascii_uppercase())
+            .take(20)
+            .collect();
+        if phrase.len() < 20 {
+            panic!("The phrase must contain at least 20 letters.");
+        }
+
+        let date = parse_digits(&key.date, 6).expect("The date must be six digits.");
+        if key.personal_number < 1 || key.personal_number > 16 {
+            panic!("The personal number must be between 1 and 16.");
+        }
+        let keygroup =
+            parse_digits(&key.keygroup, GROUP_SIZE).expect("The keygroup must be five digits.");
+
+        VIC {
+            phrase,
+            date,
+            personal_number: key.personal_number as u8,
+            keygroup,
+        }
+    }
+
+    /// Encrypt a message using a VIC cipher.
+    ///
+    /// The message may contain letters, digits and full stops. Whitespace is removed before
+    /// encryption. The ciphertext is written as groups of five digits.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, VIC, VICKey};
+    ///
+    /// let v = VIC::new(VICKey {
+    ///     phrase: String::from("I dream of Jeannie with the light brown hair"),
+    ///     date: String::from("030945"),
+    ///     personal_number: 6,
+    ///     keygroup: String::from("77651"),
+    /// });
+    ///
+    /// let c = v.encrypt("Meet at the bridge at 10.").unwrap();
+    /// assert_eq!("MEETATTHEBRIDGEAT10.", v.decrypt(&c).unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.validate_message(message)?;
+
+        let schedule = self.schedule(&self.keygroup);
+        let message: String = message.chars().filter(|c| !c.is_whitespace()).collect();
+
+        let digits = schedule.checkerboard.encode(&message)?;
+        let digits = transpose(
+            &digits,
+            &columnar(&schedule.first_transposition, digits.len()),
+        );
+        let mut digits = transpose(
+            &digits,
+            &disrupted(&schedule.second_transposition, digits.len()),
+        );
+
+        // Insert the keygroup so that the recipient can recover the key schedule
+        let position = self.keygroup_position(digits.len());
+        digits.splice(position..position, self.keygroup.iter().cloned());
+
+        Ok(digits
+            .chunks(GROUP_SIZE)
+            .map(|g| g.iter().map(|&d| (b'0' + d) as char).collect::<String>())
+            .collect::<Vec<String>>()
+            .join(" "))
+    }
+
+    /// Decrypt a message using a VIC cipher.
+    ///
+    /// The keygroup is recovered from the ciphertext, so it does not need to match the keygroup
+    /// that this cipher was initialised with. Whitespace in the ciphertext is ignored.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, VIC, VICKey};
+    ///
+    /// let phrase = String::from("I dream of Jeannie with the light brown hair");
+    /// let sender = VIC::new(VICKey {
+    ///     phrase: phrase.clone(),
+    ///     date: String::from("030945"),
+    ///     personal_number: 6,
+    ///     keygroup: String::from("77651"),
+    /// });
+    /// let recipient = VIC::new(VICKey {
+    ///     phrase,
+    ///     date: String::from("030945"),
+    ///     personal_number: 6,
+    ///     keygroup: String::from("00000"),
+    /// });
+    ///
+    /// let c = sender.encrypt("Meet at the bridge.").unwrap();
+    /// assert_eq!("MEETATTHEBRIDGE.", recipient.decrypt(&c).unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let mut digits = Vec::new();
+        for c in ciphertext.chars().filter(|c| !c.is_whitespace()) {
+            match c.to_digit(10) {
+                Some(d) => digits.push(d as u8),
+                None => return Err("Ciphertext contains a non-numeric symbol."),
+            }
+        }
+        if digits.len() < GROUP_SIZE {
+            return Err("Ciphertext is too short to contain a keygroup.");
+        }
+
+        // Recover the keygroup from the position it was inserted
+        let position = self.keygroup_position(digits.len() - GROUP_SIZE);
+        let keygroup: Vec<u8> = digits.drain(position..position + GROUP_SIZE).collect();
+        let schedule = self.schedule(&keygroup);
+
+        let digits = untranspose(
+            &digits,
+            &disrupted(&schedule.second_transposition, digits.len()),
+        );
+        let digits = untranspose(
+            &digits,
+            &columnar(&schedule.first_transposition, digits.len()),
+        );
+
+        schedule.checkerboard.decode(&digits)
+    }
+
+    /// Check that a message can be encrypted with a VIC cipher.
+    ///
+    /// Returns `Err` if the message contains a symbol that is not a letter, digit, full stop or
+    /// whitespace.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, VIC, VICKey};
+    ///
+    /// let v = VIC::new(VICKey {
+    ///     phrase: String::from("I dream of Jeannie with the light brown hair"),
+    ///     date: String::from("030945"),
+    ///     personal_number: 6,
+    ///     keygroup: String::from("77651"),
+    /// });
+    ///
+    /// assert!(v.validate_message("Meet at the bridge at 10.").is_ok());
+    /// assert!(v.validate_message("Meet at the bridge!").is_err());
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        CipherError::find(
+            message,
+            "Message contains a symbol that is not within the checkerboard.",
+            |c| !(c.is_whitespace() || c.is_ascii_alphanumeric() || c == '.'),
+        )
+    }
+
+    /// Whether a character is foreign to the cipher, being outside of the checkerboard.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        !(c.is_ascii_alphanumeric() || c == '.')
+    }
+}
+
+impl VIC {
+    /// Expand the key material and a message keygroup into the keys for the checkerboard and
+    /// both transpositions.
+    ///
+    fn schedule(&self, keygroup: &[u8]) -> Schedule {
+        // Subtract the first five digits of the date from the keygroup (without borrowing) and
+        // extend the result to ten digits
+        let difference: Vec<u8> = keygroup
+            .iter()
+            .zip(self.date.iter())
+            .map(|(&k, &d)| (10 + k - d) % 10)
+            .collect();
+        let extended = chain_addition(&difference, 10);
+
+        // Sequentialise each half of the phrase
+        let first_half = sequential_digits(&self.phrase[..10]);
+        let second_half = sequential_digits(&self.phrase[10..]);
+
+        // Add the first half to the extended digits, then encode each digit by its position in
+        // `1234567890` using the second half
+        let seed: Vec<u8> = first_half
+            .iter()
+            .zip(extended.iter())
+            .map(|(&a, &b)| (a + b) % 10)
+            .map(|d| second_half[(d as usize + 9) % 10])
+            .collect();
+        let column_order = sequential_digits(&as_ranked(&seed));
+
+        // Chain add a further 50 digits, forming a block of five rows
+        let block = chain_addition(&seed, 60).split_off(10);
+        let last_row = &block[40..];
+
+        // The transposition lengths are the personal number added to the last two unequal
+        // digits of the block
+        let last = last_row[9];
+        let unequal = last_row.iter().rev().find(|&&d| d != last).cloned();
+        let first_length = (self.personal_number + unequal.unwrap_or(last)) as usize;
+        let second_length = (self.personal_number + last) as usize;
+
+        // Read the block out by its columns, in the order given by the sequentialised seed
+        let mut keys: Vec<u8> = Vec::new();
+        for rank in (1..=10).map(|r| r % 10) {
+            let col = column_order.iter().position(|&d| d == rank).unwrap();
+            keys.extend((0..5).map(|row| block[row * 10 + col]));
+        }
+        let second_transposition = keys.split_off(first_length);
+        keys.truncate(first_length);
+
+        Schedule {
+            first_transposition: keys,
+            second_transposition: second_transposition[..second_length].to_vec(),
+            checkerboard: StraddlingCheckerboard::new(
+                &sequential_digits(&as_ranked(last_row)),
+                CHECKERBOARD_TOP,
+                CHECKERBOARD_ROWS,
+            ),
+        }
+    }
+
+    /// The position the keygroup is inserted within a message of `length` digits. It is
+    /// inserted after the group given by the last digit of the date.
+    ///
+    fn keygroup_position(&self, length: usize) -> usize {
+        (GROUP_SIZE * self.date[5] as usize).min(length)
+    }
+}
+
+/// Parse a string of exactly `length` digits.
+///
+fn parse_digits(s: &str, length: usize) -> Option<Vec<u8>> {
+    let digits: Option<Vec<u8>> = s.chars().map(|c| c.to_digit(10).map(|d| d as u8)).collect();
+
+    digits.filter(|d| d.len() == length)
+}
+
+/// Rank digits so that `0` is treated as the highest digit, rather than the lowest.
+///
+fn as_ranked(digits: &[u8]) -> Vec<u8> {
+    digits
+        .iter()
+        .map(|&d| if d == 0 { 10 } else { d })
+        .collect()
+}
+
+/// Sequentialise a key, numbering each element with the digits `1234567890`.
+///
+fn sequential_digits<T: Ord>(key: &[T]) -> Vec<u8> {
+    sequentialise(key).iter().map(|&r| (r % 10) as u8).collect()
+}
+
+/// The order in which the cells of a grid are read, column by column, for a transposition key.
+///
+fn read_order(key: &[u8], length: usize) -> Vec<usize> {
+    let width = key.len();
+    let ranks = sequentialise(&as_ranked(key));
+
+    let mut order = Vec::with_capacity(length);
+    for rank in 1..=width {
+        let col = ranks.iter().position(|&r| r == rank).unwrap();
+        order.extend((col..length).step_by(width));
+    }
+
+    order
+}
+
+/// The (write, read) orders of a regular columnar transposition. The grid is written row by row.
+///
+fn columnar(key: &[u8], length: usize) -> (Vec<usize>, Vec<usize>) {
+    ((0..length).collect(), read_order(key, length))
+}
+
+/// The (write, read) orders of a disrupted columnar transposition.
+///
+/// The grid contains triangular areas. The first starts at the top of the column that is read
+/// first, extending to the right edge of the grid. Each following row of the area starts one
+/// column further to the right, until it has shrunk to nothing. The next area starts on the row
+/// below, at the column that is read second, and so on. The grid is written row by row, skipping
+/// the triangular areas, which are then filled row by row with the remaining digits.
+///
+fn disrupted(key: &[u8], length: usize) -> (Vec<usize>, Vec<usize>) {
+    let width = key.len();
+    let ranks = sequentialise(&as_ranked(key));
+    let rows = length.div_ceil(width);
+
+    let mut is_disrupted = vec![false; length];
+    let mut row = 0;
+    for rank in (1..=width).cycle() {
+        let start = ranks.iter().position(|&r| r == rank).unwrap();
+        for offset in 0..=(width - start) {
+            if row >= rows {
+                break;
+            }
+            for col in (start + offset)..width {
+                if let Some(cell) = is_disrupted.get_mut(row * width + col) {
+                    *cell = true;
+                }
+            }
+            row += 1;
+        }
+
+        if row >= rows {
+            break;
+        }
+    }
+
+    let write = (0..length)
+        .filter(|&i| !is_disrupted[i])
+        .chain((0..length).filter(|&i| is_disrupted[i]))
+        .collect();
+
+    (write, read_order(key, length))
+}
+
+/// Transpose digits by writing them into grid cells in the `write` order, and reading them from
+/// the cells in the `read` order.
+///
+fn transpose(digits: &[u8], orders: &(Vec<usize>, Vec<usize>)) -> Vec<u8> {
+    let mut grid = vec![0; digits.len()];
+    for (&cell, &d) in orders.0.iter().zip(digits.iter()) {
+        grid[cell] = d;
+    }
+
+    orders.1.iter().map(|&cell| grid[cell]).collect()
+}
+
+/// Reverse a transposition, by writing digits in the `read` order and reading in the `write`
+/// order.
+///
+fn untranspose(digits: &[u8], orders: &(Vec<usize>, Vec<usize>)) -> Vec<u8> {
+    transpose(digits, &(orders.1.clone(), orders.0.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vic(keygroup: &str) -> VIC {
+        VIC::new(VICKey {
+            phrase: String::from("I dream of Jeannie with the light brown hair"),
+            date: String::from("030945"),
+            personal_number: 6,
+            keygroup: String::from(keygroup),
+        })
+    }
+
+    // The key material of Häyhänen's example, with the date 3 September 1945 written as `391945`
+    fn hayhanen() -> VIC {
+        VIC::new(VICKey {
+            phrase: String::from("I dream of Jeannie with the light brown hair"),
+            date: String::from("391945"),
+            personal_number: 6,
+            keygroup: String::from("77651"),
+        })
+    }
+
+    #[test]
+    fn hayhanen_key_schedule() {
+        let v = hayhanen();
+
+        // The keygroup less the date, then chain added to ten digits
+        let difference = vec![4, 8, 5, 6, 7];
+        assert_eq!(
+            difference,
+            v.keygroup
+                .iter()
+                .zip(v.date.iter())
+                .map(|(&k, &d)| (10 + k - d) % 10)
+                .collect::<Vec<u8>>()
+        );
+        let extended = chain_addition(&difference, 10);
+        assert_eq!(vec![4, 8, 5, 6, 7, 2, 3, 1, 3, 9], extended);
+
+        // Each half of the phrase, sequentialised
+        assert_eq!(
+            vec![6, 2, 0, 3, 1, 8, 9, 5, 7, 4],
+            sequential_digits(&v.phrase[..10])
+        );
+        assert_eq!(
+            vec![1, 6, 7, 4, 2, 0, 5, 8, 3, 9],
+            sequential_digits(&v.phrase[10..])
+        );
+
+        // The sum of the first half and the extended digits, encoded by the second half
+        let seed = vec![9, 9, 2, 3, 8, 9, 6, 0, 9, 7];
+        assert_eq!(
+            vec![6, 7, 1, 2, 5, 8, 3, 0, 9, 4],
+            sequential_digits(&as_ranked(&seed))
+        );
+
+        // The block of five rows chain added from the seed
+        assert_eq!(
+            vec![
+                8, 1, 5, 1, 7, 5, 6, 9, 6, 5, //
+                9, 6, 6, 8, 2, 1, 5, 5, 1, 4, //
+                5, 2, 4, 0, 3, 6, 0, 6, 5, 9, //
+                7, 6, 4, 3, 9, 6, 6, 1, 4, 6, //
+                3, 0, 7, 2, 5, 2, 7, 5, 0, 9,
+            ],
+            chain_addition(&seed, 60).split_off(10)
+        );
+
+        // The personal number plus the last two unequal digits of the block give lengths of 6
+        // and 15, and the last row sequentialised labels the checkerboard
+        let schedule = v.schedule(&v.keygroup);
+        assert_eq!(vec![5, 6, 4, 4, 7, 1], schedule.first_transposition);
+        assert_eq!(
+            vec![8, 0, 3, 2, 6, 5, 0, 6, 7, 5, 4, 9, 6, 9, 7],
+            schedule.second_transposition
+        );
+        assert_eq!(
+            StraddlingCheckerboard::new(
+                &[3, 9, 6, 1, 4, 2, 7, 5, 0, 8],
+                CHECKERBOARD_TOP,
+                CHECKERBOARD_ROWS
+            ),
+            schedule.checkerboard
+        );
+    }
+
+    #[test]
+    fn hayhanen_ciphertext() {
+        let v = hayhanen();
+        let m = "WEAREPLEASEDTOHEAROFYOURSAFEARRIVAL.";
+        let c = "67641 65535 06114 91631 35726 77651 71385 73407 03471 01738 661";
+
+        assert_eq!(
+            "743153736031836694623154617747658161315507116070",
+            v.schedule(&v.keygroup)
+                .checkerboard
+                .encode(m)
+                .unwrap()
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<String>()
+        );
+        assert_eq!(c, v.encrypt(m).unwrap());
+        assert_eq!(m, v.decrypt(c).unwrap());
+    }
+
+    #[test]
+    fn encrypt_test() {
+        let c = vic("77651").encrypt("We are pleased to hear").unwrap();
+
+        assert!(c.chars().all(|c| c.is_ascii_digit() || c == ' '));
+        assert!(c.split(' ').all(|g| g.len() <= GROUP_SIZE));
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let v = vic("77651");
+        let m = "WEAREPLEASEDTOHEAROFYOURSAFEARRIVAL.";
+
+        assert_eq!(m, v.decrypt(&v.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn with_digits() {
+        let v = vic("19283");
+        let m = "MEETATPIER7AT2300.";
+
+        assert_eq!(m, v.decrypt(&v.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn keygroup_is_inserted() {
+        // The last digit of the date is 5, so the keygroup follows the fifth group
+        let c = vic("77651")
+            .encrypt("We are pleased to hear of your safe arrival")
+            .unwrap();
+        assert_eq!("77651", c.split(' ').nth(5).unwrap());
+    }
+
+    #[test]
+    fn keygroup_changes_ciphertext() {
+        let m = "Attack at dawn";
+        assert_ne!(
+            vic("11111").encrypt(m).unwrap(),
+            vic("22222").encrypt(m).unwrap()
+        );
+    }
+
+    #[test]
+    fn recipient_recovers_keygroup() {
+        let c = vic("48213").encrypt("Attack at dawn").unwrap();
+        assert_eq!("ATTACKATDAWN", vic("00000").decrypt(&c).unwrap());
+    }
+
+    #[test]
+    fn short_messages() {
+        let v = vic("55555");
+        for m in &["A", "AT", "SEND", "MONEY"] {
+            assert_eq!(*m, v.decrypt(&v.encrypt(m).unwrap()).unwrap());
+        }
+    }
+
+    #[test]
+    fn invalid_message() {
+        assert!(vic("77651").encrypt("Attack at dawn!").is_err());
+        assert_eq!(
+            Err(CipherError::Symbol {
+                symbol: '!',
+                offset: 14,
+                reason: "Message contains a symbol that is not within the checkerboard.",
+            }),
+            vic("77651").validate_message("Attack at dawn!")
+        );
+    }
+
+    #[test]
+    fn invalid_ciphertext() {
+        assert!(vic("77651").decrypt("1234").is_err());
+        assert!(vic("77651").decrypt("12345 6789a").is_err());
+    }
+
+    #[test]
+    fn disrupted_areas() {
+        // Key 3142: the areas start at columns 1 (read first) and then 3 (read second)
+        let (write, _) = disrupted(&[3, 1, 4, 2], 24);
+        assert_eq!(
+            vec![
+                0, 4, 5, 8, 9, 10, 12, 13, 14, 15, 16, 17, 18, 20, 21, 22, 23, 1, 2, 3, 6, 7, 11,
+                19
+            ],
+            write
+        );
+    }
+
+    #[test]
+    fn transpositions_are_reversible() {
+        let digits: Vec<u8> = (0..37).map(|i| (i * 7 % 10) as u8).collect();
+        let key = [9, 0, 2, 2, 5, 1, 3, 4, 0, 7, 1];
+
+        let orders = disrupted(&key, digits.len());
+        assert_eq!(digits, untranspose(&transpose(&digits, &orders), &orders));
+
+        let orders = columnar(&key, digits.len());
+        assert_eq!(digits, untranspose(&transpose(&digits, &orders), &orders));
+    }
+
+    #[test]
+    #[should_panic]
+    fn short_phrase() {
+        VIC::new(VICKey {
+            phrase: String::from("Too short"),
+            date: String::from("030945"),
+            personal_number: 6,
+            keygroup: String::from("77651"),
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_date() {
+        VIC::new(VICKey {
+            phrase: String::from("I dream of Jeannie with the light brown hair"),
+            date: String::from("3/9/45"),
+            personal_number: 6,
+            keygroup: String::from("77651"),
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_personal_number() {
+        VIC::new(VICKey {
+            phrase: String::from("I dream of Jeannie with the light brown hair"),
+            date: String::from("030945"),
+            personal_number: 17,
+            keygroup: String::from("77651"),
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_keygroup() {
+        vic("7765");
+    }
+}
@@ -6,37 +6,222 @@
 //!
 //!
 use crate::common::alphabet;
-use crate::common::alphabet::Alphabet;
+use crate::common::alphabet::{Alphabet, Custom};
 use crate::common::cipher::Cipher;
-use crate::common::keygen::cyclic_keystream;
+use crate::common::key_problem;
+use crate::common::key_problem::KeyProblem;
+use crate::common::keygen::alphabet_cyclic_keystream;
+use crate::common::render;
 use crate::common::substitute;
 
+/// A step of an encryption with the Vigenère cipher, as returned by `encrypt_with_trace()`.
+///
+/// The indices are the positions of the characters within the alphabet of the cipher.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    /// The character of the message.
+    pub message_char: char,
+    /// The character of the key that the message character was shifted by.
+    pub key_char: char,
+    /// The index of the message character.
+    pub message_index: usize,
+    /// The index of the key character.
+    pub key_index: usize,
+    /// The character of the ciphertext, at the sum of the indices.
+    pub result: char,
+}
+
 /// A Vigenère cipher.
 ///
-/// This struct is created by the `new()` method. See its documentation for more.
+/// This struct is created by the `new()` or `with_alphabet()` methods. See their documentation
+/// for more.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Vigenere {
     key: String,
+    alphabet: Custom,
 }
 
-impl Cipher for Vigenere {
-    type Key = String;
-    type Algorithm = Vigenere;
-
+impl Vigenere {
     /// Initialise a Vigenère cipher given a specific key.
     ///
     /// # Panics
     /// * The `key` is empty.
     /// * The `key` contains a non-alphabetic symbol.
     ///
-    fn new(key: String) -> Vigenere {
+    pub fn new(key: impl Into<String>) -> Vigenere {
+        Vigenere::with_alphabet(key, &alphabet::STANDARD)
+    }
+
+    /// The key of the cipher.
+    ///
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Initialise a Vigenère cipher given a specific key, that substitutes the symbols of an
+    /// `alphabet` other than `a-z`.
+    ///
+    /// The symbols of the message outside of the alphabet are left as they are, and do not use up
+    /// a symbol of the key.
+    ///
+    /// # Panics
+    /// * The `key` is empty.
+    /// * The `key` contains a symbol outside of the `alphabet`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::alphabet::Custom;
+    /// use cipher_crypt::{Cipher, Vigenere};
+    ///
+    /// let hex = Custom::new("0123456789abcdef");
+    /// let v = Vigenere::with_alphabet("c0ffee", &hex);
+    /// assert_eq!("cxdd9b9aee", v.encrypt("0xdeadbeef").unwrap());
+    /// ```
+    ///
+    pub fn with_alphabet<A: Alphabet>(key: impl Into<String>, alphabet: &A) -> Vigenere {
+        let key = key.into();
         if key.is_empty() {
             panic!("The key is empty.");
         }
-        if !alphabet::STANDARD.is_valid(&key) {
-            panic!("The key contains a non-alphabetic symbol.");
+        if !alphabet.is_valid(&key) {
+            panic!("The key contains a symbol outside of the alphabet.");
+        }
+
+        Vigenere {
+            key,
+            alphabet: Custom::from_alphabet(alphabet),
+        }
+    }
+
+    /// Encrypt a message using a Vigenère cipher, returning the ciphertext along with a step for
+    /// each character that was substituted.
+    ///
+    /// The symbols of the message outside of the alphabet are left as they are, so have no step.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::vigenere::Step;
+    /// use cipher_crypt::{Cipher, Vigenere};
+    ///
+    /// let v = Vigenere::new("lemon");
+    /// let (ciphertext, steps) = v.encrypt_with_trace("At dawn").unwrap();
+    ///
+    /// assert_eq!("Lx pojy", ciphertext);
+    /// assert_eq!(
+    ///     Step {
+    ///         message_char: 't',
+    ///         key_char: 'e',
+    ///         message_index: 19,
+    ///         key_index: 4,
+    ///         result: 'x',
+    ///     },
+    ///     steps[1]
+    /// );
+    /// ```
+    ///
+    pub fn encrypt_with_trace(&self, message: &str) -> Result<(String, Vec<Step>), &'static str> {
+        let mut key = self.key.chars().cycle();
+        let mut steps = Vec::new();
+
+        let ciphertext = message
+            .chars()
+            .map(
+                |message_char| match self.alphabet.find_position(message_char) {
+                    Some(message_index) => {
+                        // The key was checked to be within the alphabet when the cipher was made
+                        let key_char = key.next().unwrap();
+                        let key_index = self.alphabet.find_position(key_char).unwrap();
+                        let result = self.alphabet.get_letter(
+                            self.alphabet.modulo((message_index + key_index) as isize),
+                            message_char.is_uppercase(),
+                        );
+
+                        steps.push(Step {
+                            message_char,
+                            key_char,
+                            message_index,
+                            key_index,
+                            result,
+                        });
+                        result
+                    }
+                    None => message_char,
+                },
+            )
+            .collect();
+
+        Ok((ciphertext, steps))
+    }
+
+    /// Draw the tabula recta of the cipher, whose rows are its alphabet shifted by each of the
+    /// symbols that the key may contain.
+    ///
+    /// A message symbol is encrypted by finding its column, and taking the symbol of that column
+    /// on the row of the key symbol.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::alphabet::Custom;
+    /// use cipher_crypt::Vigenere;
+    ///
+    /// let v = Vigenere::with_alphabet("ba", &Custom::new("abc"));
+    /// assert_eq!(
+    ///     "  | a b c\n--+------\na | a b c\nb | b c a\nc | c a b",
+    ///     v.render()
+    /// );
+    /// ```
+    ///
+    pub fn render(&self) -> String {
+        let length = self.alphabet.length();
+        let letters: Vec<char> = (0..length)
+            .map(|i| self.alphabet.get_letter(i, false))
+            .collect();
+        let rows: Vec<(char, Vec<char>)> = letters
+            .iter()
+            .enumerate()
+            .map(|(shift, &label)| {
+                let cells = (0..length).map(|i| letters[(i + shift) % length]).collect();
+
+                (label, cells)
+            })
+            .collect();
+
+        render::labelled_grid(&letters, &rows)
+    }
+
+    /// List the problems with a `key` that would stop it from being given to `new()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cipher_crypt::{KeyProblem, Vigenere};
+    ///
+    /// assert_eq!(
+    ///     vec![KeyProblem::InvalidSymbol { position: 5, symbol: '!' }],
+    ///     Vigenere::key_problems("lemon!")
+    /// );
+    /// ```
+    ///
+    pub fn key_problems(key: &str) -> Vec<KeyProblem> {
+        if key.is_empty() {
+            return vec![KeyProblem::Empty];
         }
 
-        Vigenere { key }
+        key_problem::invalid_symbols(key, &alphabet::STANDARD)
+    }
+}
+
+impl Cipher for Vigenere {
+    type Key = String;
+    type Algorithm = Vigenere;
+
+    /// Initialise a Vigenère cipher given a specific key, as by `Vigenere::new()`.
+    ///
+    fn new(key: String) -> Vigenere {
+        Vigenere::new(key)
     }
 
     /// Encrypt a message using a Vigenère cipher.
@@ -47,7 +232,7 @@ impl Cipher for Vigenere {
     /// ```
     /// use cipher_crypt::{Cipher, Vigenere};
     ///
-    /// let v = Vigenere::new(String::from("giovan"));
+    /// let v = Vigenere::new("giovan");
     /// assert_eq!("O vsqee mmh vnl izsyig!", v.encrypt("I never get any credit!").unwrap());
     /// ```
     ///
@@ -56,10 +241,11 @@ impl Cipher for Vigenere {
         //         Ci = Ek(Mi) = (Mi + Ki) mod 26
         // Where;  Mi = position within the alphabet of ith char in message
         //         Ki = position within the alphabet of ith char in key
-        Ok(substitute::key_substitution(
+        Ok(substitute::alphabet_key_substitution(
             message,
-            &cyclic_keystream(&self.key, message),
-            |mi, ki| alphabet::STANDARD.modulo((mi + ki) as isize),
+            alphabet_cyclic_keystream(&self.key, message, &self.alphabet),
+            &self.alphabet,
+            |mi, ki| self.alphabet.modulo((mi + ki) as isize),
         ))
     }
 
@@ -71,7 +257,7 @@ impl Cipher for Vigenere {
     /// ```
     /// use cipher_crypt::{Cipher, Vigenere};
     ///
-    /// let v = Vigenere::new(String::from("giovan"));
+    /// let v = Vigenere::new("giovan");
     /// assert_eq!("I never get any credit!", v.decrypt("O vsqee mmh vnl izsyig!").unwrap());
     /// ```
     ///
@@ -80,12 +266,19 @@ impl Cipher for Vigenere {
         //         Mi = Dk(Ci) = (Ci - Ki) mod 26
         // Where;  Ci = position within the alphabet of ith char in cipher text
         //         Ki = position within the alphabet of ith char in key
-        Ok(substitute::key_substitution(
+        Ok(substitute::alphabet_key_substitution(
             ciphertext,
-            &cyclic_keystream(&self.key, ciphertext),
-            |ci, ki| alphabet::STANDARD.modulo(ci as isize - ki as isize),
+            alphabet_cyclic_keystream(&self.key, ciphertext, &self.alphabet),
+            &self.alphabet,
+            |ci, ki| self.alphabet.modulo(ci as isize - ki as isize),
         ))
     }
+
+    /// Whether a character is foreign to the cipher, being outside of its alphabet.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        self.alphabet.find_position(c).is_none()
+    }
 }
 
 #[cfg(test)]
@@ -127,6 +320,54 @@ mod tests {
         assert_eq!(decrypted, message);
     }
 
+    #[test]
+    fn custom_alphabet() {
+        // The Playfair alphabet has no 'j', so it is left as it is
+        let v = Vigenere::with_alphabet(String::from("lemon"), &alphabet::PLAYFAIR);
+        let message = "Jump at dawn!";
+        let encrypted = v.encrypt(message).unwrap();
+
+        assert_eq!('J', encrypted.chars().next().unwrap());
+        assert_eq!(message, v.decrypt(&encrypted).unwrap());
+    }
+
+    #[test]
+    fn trace_matches_encrypt() {
+        let v = Vigenere::new(String::from("Giovan"));
+        let message = "Peace 🗡️ Freedom and Liberty!";
+        let (ciphertext, steps) = v.encrypt_with_trace(message).unwrap();
+
+        assert_eq!(v.encrypt(message).unwrap(), ciphertext);
+        assert_eq!(22, steps.len());
+        assert_eq!(
+            ('P', 'G', 'V'),
+            (steps[0].message_char, steps[0].key_char, steps[0].result)
+        );
+    }
+
+    #[test]
+    fn render_tabula_recta() {
+        let table = Vigenere::new(String::from("lemon")).render();
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(28, lines.len());
+        assert_eq!(
+            "l | l m n o p q r s t u v w x y z a b c d e f g h i j k",
+            lines[13]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn key_outside_alphabet() {
+        Vigenere::with_alphabet(String::from("jam"), &alphabet::PLAYFAIR);
+    }
+
+    #[test]
+    fn str_key() {
+        assert_eq!(Vigenere::new(String::from("lemon")), Vigenere::new("lemon"));
+    }
+
     #[test]
     fn valid_key() {
         Vigenere::new(String::from("LeMon"));
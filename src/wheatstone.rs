@@ -0,0 +1,330 @@
+//! The Wheatstone cryptograph was invented by Charles Wheatstone, and exhibited at the Paris
+//! Exposition of 1867. Unlike most of the ciphers of its time, it is a mechanical device.
+//!
+//! The cryptograph is a dial with two concentric rings. The outer ring holds the 26 letters of the
+//! plaintext alphabet along with a blank (`+`) used between words, while the inner ring holds a
+//! mixed ciphertext alphabet of 26 letters. Two hands, like those of a clock, are geared together
+//! so that each time the long hand completes a revolution of the outer ring, the short hand
+//! advances one letter further around the inner ring.
+//!
+//! To encrypt, the long hand is always turned clockwise to the next letter of the message, and
+//! the letter of the inner ring under the short hand is the ciphertext. As the hands advance by
+//! the distance between each pair of letters, the ciphertext of each letter depends upon the
+//! letters that preceded it.
+//!
+//! As a repeated letter would require a full revolution of the long hand, which cannot be
+//! distinguished from moving to the next letter, the second of a repeated letter is replaced
+//! with `q` (or `x` when `q` itself is repeated).
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::cipher_error::CipherError;
+use crate::common::keygen::keyed_alphabet;
+
+// The blank of the outer ring, used between words
+const BLANK: char = '+';
+
+/// A Wheatstone cryptograph.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Wheatstone {
+    outer: Vec<char>,
+    inner: Vec<char>,
+    start: usize,
+}
+
+/// The key of a Wheatstone cryptograph.
+///
+/// This struct is given to the `new()` method of `Wheatstone`. See its documentation for more.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WheatstoneKey {
+    /// Mixes the letters of the outer ring, which follow the blank. If it is empty the
+    /// letters are in alphabetical order.
+    pub outer_keyword: String,
+    /// Mixes the letters of the inner ring.
+    pub inner_keyword: String,
+    /// The letter of the inner ring under the short hand, when the long hand points at the
+    /// blank.
+    pub start: char,
+}
+
+impl Cipher for Wheatstone {
+    type Key = WheatstoneKey;
+    type Algorithm = Wheatstone;
+
+    /// Initialise a Wheatstone cryptograph.
+    ///
+    /// The parts of the `key` are described by the fields of `WheatstoneKey`.
+    ///
+    /// # Panics
+    /// * The `outer_keyword` or `inner_keyword` contains a non-alphabetic symbol.
+    /// * The `inner_keyword` is empty.
+    /// * The `start` is not alphabetic.
+    ///
+    fn new(key: WheatstoneKey) -> Wheatstone {
+        let WheatstoneKey {
+            outer_keyword,
+            inner_keyword,
+            start,
+        } = key;
+        if inner_keyword.is_empty() {
+            panic!("The inner keyword is empty.");
+        }
+
+        let mut outer = vec![BLANK];
+        outer.extend(keyed_alphabet(&outer_keyword, &alphabet::STANDARD, false).chars());
+        let inner: Vec<char> = keyed_alphabet(&inner_keyword, &alphabet::STANDARD, false)
+            .chars()
+            .collect();
+
+        let start = inner
+            .iter()
+            .position(|&c| c == start.to_ascii_lowercase())
+            .expect("The starting letter must be alphabetic.");
+
+        Wheatstone {
+            outer,
+            inner,
+            start,
+        }
+    }
+
+    /// Encrypt a message using a Wheatstone cryptograph.
+    ///
+    /// Each run of whitespace within the message is enciphered as a single blank. The ciphertext
+    /// is lowercase.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Wheatstone, WheatstoneKey};
+    ///
+    /// let w = Wheatstone::new(WheatstoneKey {
+    ///     outer_keyword: String::new(),
+    ///     inner_keyword: String::from("cipher"),
+    ///     start: 'c',
+    /// });
+    /// assert_eq!("iushrlhexedapu", w.encrypt("Attack at dawn").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        self.validate_message(message)?;
+
+        let mut symbols: Vec<char> = Vec::new();
+        for word in message.split_whitespace() {
+            if !symbols.is_empty() {
+                symbols.push(BLANK);
+            }
+            for c in word.chars().map(|c| c.to_ascii_lowercase()) {
+                match symbols.last() {
+                    Some(&'q') if c == 'q' => symbols.push('x'),
+                    Some(&last) if last == c => symbols.push('q'),
+                    _ => symbols.push(c),
+                }
+            }
+        }
+
+        let mut long_hand = 0;
+        let mut short_hand = self.start;
+        Ok(symbols
+            .into_iter()
+            .map(|c| {
+                let target = self.outer.iter().position(|&o| o == c).unwrap();
+                short_hand = (short_hand + (target + 27 - long_hand) % 27) % 26;
+                long_hand = target;
+
+                self.inner[short_hand]
+            })
+            .collect())
+    }
+
+    /// Decrypt a message using a Wheatstone cryptograph.
+    ///
+    /// Each blank is deciphered as a space. The plaintext is lowercase.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Wheatstone, WheatstoneKey};
+    ///
+    /// let w = Wheatstone::new(WheatstoneKey {
+    ///     outer_keyword: String::new(),
+    ///     inner_keyword: String::from("cipher"),
+    ///     start: 'c',
+    /// });
+    /// assert_eq!("atqack at dawn", w.decrypt("iushrlhexedapu").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        let mut long_hand = 0;
+        let mut short_hand = self.start;
+
+        ciphertext
+            .chars()
+            .map(|c| {
+                let target = self
+                    .inner
+                    .iter()
+                    .position(|&i| i == c.to_ascii_lowercase())
+                    .ok_or("Ciphertext contains a non-alphabetic symbol.")?;
+
+                // The long hand moves between 1 and 26 places for each letter
+                let distance = match (target + 26 - short_hand) % 26 {
+                    0 => 26,
+                    d => d,
+                };
+                short_hand = target;
+                long_hand = (long_hand + distance) % 27;
+
+                Ok(match self.outer[long_hand] {
+                    BLANK => ' ',
+                    p => p,
+                })
+            })
+            .collect()
+    }
+
+    /// Check that a message can be encrypted with a Wheatstone cryptograph.
+    ///
+    /// Returns `Err` if the message contains a symbol that is not alphabetic or whitespace.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Wheatstone, WheatstoneKey};
+    ///
+    /// let w = Wheatstone::new(WheatstoneKey {
+    ///     outer_keyword: String::new(),
+    ///     inner_keyword: String::from("cipher"),
+    ///     start: 'c',
+    /// });
+    ///
+    /// assert!(w.validate_message("Attack at dawn").is_ok());
+    /// assert!(w.validate_message("Attack at dawn!").is_err());
+    /// ```
+    ///
+    fn validate_message(&self, message: &str) -> Result<(), CipherError> {
+        CipherError::find(
+            message,
+            "Message contains a symbol that is not alphabetic or whitespace.",
+            |c| !c.is_whitespace() && alphabet::STANDARD.find_position(c).is_none(),
+        )
+    }
+
+    /// Whether a character is foreign to the cipher, being neither a letter nor whitespace, as
+    /// the whitespace separates the words of the message.
+    ///
+    fn is_foreign(&self, c: char) -> bool {
+        !(c.is_ascii_alphabetic() || c.is_whitespace())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::policy::ForeignCharPolicy;
+
+    fn wheatstone() -> Wheatstone {
+        Wheatstone::new(WheatstoneKey {
+            outer_keyword: String::from("wheatstone"),
+            inner_keyword: String::from("cryptograph"),
+            start: 'p',
+        })
+    }
+
+    #[test]
+    fn encrypt_test() {
+        assert_eq!(
+            "hgaozrsmwhmplenewqd",
+            wheatstone().encrypt("The quick brown fox").unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_test() {
+        assert_eq!(
+            "the quick brown fox",
+            wheatstone().decrypt("hgaozrsmwhmplenewqd").unwrap()
+        );
+    }
+
+    #[test]
+    fn repeated_letters() {
+        let w = wheatstone();
+
+        assert_eq!("meqt", w.decrypt(&w.encrypt("meet").unwrap()).unwrap());
+        assert_eq!("qxq", w.decrypt(&w.encrypt("qqq").unwrap()).unwrap());
+    }
+
+    #[test]
+    fn whitespace_is_collapsed() {
+        let w = wheatstone();
+        assert_eq!(
+            w.encrypt("attack at dawn").unwrap(),
+            w.encrypt("  attack\n at\tdawn ").unwrap()
+        );
+    }
+
+    #[test]
+    fn ciphertext_depends_on_history() {
+        let w = wheatstone();
+
+        // The same letter is enciphered differently depending on the letters before it
+        let c: Vec<char> = w.encrypt("abab").unwrap().chars().collect();
+        assert_ne!(c[0], c[2]);
+    }
+
+    #[test]
+    fn invalid_ciphertext() {
+        assert!(wheatstone().decrypt("abc1").is_err());
+    }
+
+    #[test]
+    fn with_utf8() {
+        assert!(wheatstone().encrypt("Peace 🗡️ Freedom").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_inner_keyword() {
+        Wheatstone::new(WheatstoneKey {
+            outer_keyword: String::from("wheatstone"),
+            inner_keyword: String::new(),
+            start: 'p',
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn keyword_with_symbols() {
+        Wheatstone::new(WheatstoneKey {
+            outer_keyword: String::from("wheat stone"),
+            inner_keyword: String::from("cryptograph"),
+            start: 'p',
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_start() {
+        Wheatstone::new(WheatstoneKey {
+            outer_keyword: String::from("wheatstone"),
+            inner_keyword: String::from("cryptograph"),
+            start: '!',
+        });
+    }
+
+    #[test]
+    fn strip_keeps_whitespace() {
+        let w = wheatstone();
+        assert_eq!(
+            w.encrypt("Attack at dawn"),
+            w.encrypt_with_policy("Attack at dawn!", ForeignCharPolicy::Strip)
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! The Wolseley cipher is named after Field Marshal Garnet Wolseley, who is said to have used it
+//! during the British campaigns in Africa in the 1870s and 1880s.
+//!
+//! A 25 letter alphabet (with `J` merged into `I`) is keyed by a keyword, and its reverse is
+//! written beneath it. Each letter of the message is replaced with the letter that it is paired
+//! with. For example, with the keyword `wolseley`:
+//!
+//! ```text
+//! w o l s e y a b c d f g h i k m n p q r t u v x z
+//! z x v u t r q p n m k i h g f d c b a y e s l o w
+//! ```
+//!
+//! As each letter is paired with another, the cipher is reciprocal: encryption and decryption are
+//! the same operation. The letter in the middle of the alphabet is paired with itself.
+//!
+use crate::common::alphabet;
+use crate::common::alphabet::Alphabet;
+use crate::common::cipher::Cipher;
+use crate::common::{keygen, substitute};
+
+/// A Wolseley cipher.
+///
+/// This struct is created by the `new()` method. See its documentation for more.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Wolseley {
+    keyed: Vec<char>,
+}
+
+impl Wolseley {
+    /// Initialise a Wolseley cipher given a specific keyword.
+    ///
+    /// Any `j` within the keyword is treated as an `i`.
+    ///
+    /// # Panics
+    /// * The `keyword` is empty.
+    /// * The `keyword` contains a non-alphabetic symbol.
+    ///
+    pub fn new(keyword: impl Into<String>) -> Wolseley {
+        let keyword = keyword.into();
+        if keyword.is_empty() {
+            panic!("The keyword is empty.");
+        }
+
+        let keyword = keyword.replace(['j', 'J'], "i");
+        Wolseley {
+            keyed: keygen::keyed_alphabet(&keyword, &alphabet::PLAYFAIR, false)
+                .chars()
+                .collect(),
+        }
+    }
+}
+
+impl Cipher for Wolseley {
+    type Key = String;
+    type Algorithm = Wolseley;
+
+    /// Initialise a Wolseley cipher given a specific keyword, as by `Wolseley::new()`.
+    ///
+    fn new(keyword: String) -> Wolseley {
+        Wolseley::new(keyword)
+    }
+
+    /// Encrypt a message using a Wolseley cipher.
+    ///
+    /// Any `j` within the message is encrypted as an `i`.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Wolseley};
+    ///
+    /// let w = Wolseley::new("wolseley");
+    /// assert_eq!("Qeeqnf qe mqzc!", w.encrypt("Attack at dawn!").unwrap());
+    /// ```
+    ///
+    fn encrypt(&self, message: &str) -> Result<String, &'static str> {
+        Ok(substitute::shift_substitution(message, |idx| {
+            let letter = match alphabet::STANDARD.get_letter(idx, false) {
+                'j' => 'i',
+                l => l,
+            };
+            let pos = self.keyed.iter().position(|&k| k == letter).unwrap();
+
+            alphabet::STANDARD
+                .find_position(self.keyed[self.keyed.len() - 1 - pos])
+                .unwrap()
+        }))
+    }
+
+    /// Decrypt a message using a Wolseley cipher.
+    ///
+    /// As the cipher is reciprocal, this is identical to encryption.
+    ///
+    /// # Examples
+    /// Basic usage:
+    ///
+    /// ```
+    /// use cipher_crypt::{Cipher, Wolseley};
+    ///
+    /// let w = Wolseley::new("wolseley");
+    /// assert_eq!("Attack at dawn!", w.decrypt("Qeeqnf qe mqzc!").unwrap());
+    /// ```
+    ///
+    fn decrypt(&self, ciphertext: &str) -> Result<String, &'static str> {
+        self.encrypt(ciphertext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_test() {
+        let w = Wolseley::new(String::from("wolseley"));
+        assert_eq!(
+            "Dtte dt pr eht xvm xqf",
+            w.encrypt("Meet me by the old oak").unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypt_test() {
+        let w = Wolseley::new(String::from("wolseley"));
+        assert_eq!(
+            "Meet me by the old oak",
+            w.decrypt("Dtte dt pr eht xvm xqf").unwrap()
+        );
+    }
+
+    #[test]
+    fn is_reciprocal() {
+        let w = Wolseley::new(String::from("garnet"));
+        let m = "The quick brown fox leaps over the lazy dog";
+
+        assert_eq!(m, w.encrypt(&w.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn middle_letter_unchanged() {
+        // The middle letter of the keyed alphabet is the h
+        let w = Wolseley::new(String::from("wolseley"));
+        assert_eq!("H", w.encrypt("H").unwrap());
+    }
+
+    #[test]
+    fn j_as_i() {
+        let w = Wolseley::new(String::from("jam"));
+        assert_eq!(w.encrypt("icicle").unwrap(), w.encrypt("jcjcle").unwrap());
+        assert_eq!(
+            w.encrypt("m").unwrap(),
+            Wolseley::new(String::from("iam")).encrypt("m").unwrap()
+        );
+    }
+
+    #[test]
+    fn with_utf8() {
+        let w = Wolseley::new(String::from("utf"));
+        let m = "Peace 🗡️ Freedom and Liberty!";
+
+        assert_eq!(m, w.decrypt(&w.encrypt(m).unwrap()).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_keyword() {
+        Wolseley::new(String::from(""));
+    }
+
+    #[test]
+    #[should_panic]
+    fn keyword_with_symbols() {
+        Wolseley::new(String::from("wolse!ey"));
+    }
+}